@@ -0,0 +1,150 @@
+use crate::{
+    error::{Error, Result},
+    progress::ProgressPercent,
+    sync::{archive::archive_with_progress, ArchiveFormat, CommitId},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+};
+
+///
+#[derive(Clone, Debug)]
+pub struct ArchiveRequest {
+    ///
+    pub commit: CommitId,
+    ///
+    pub format: ArchiveFormat,
+    /// relative paths are below the workdir
+    pub output_path: String,
+}
+
+/// writes an archive of a commit in the background, large trees take
+/// a while
+pub struct AsyncArchive {
+    state: Arc<Mutex<Option<ArchiveRequest>>>,
+    last_result: Arc<Mutex<Option<(usize, String)>>>,
+    progress: Arc<Mutex<ProgressPercent>>,
+    sender: Sender<AsyncNotification>,
+}
+
+impl AsyncArchive {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(None)),
+            last_result: Arc::new(Mutex::new(None)),
+            progress: Arc::new(Mutex::new(ProgressPercent::empty())),
+            sender: sender.clone(),
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> Result<bool> {
+        let state = self.state.lock()?;
+        Ok(state.is_some())
+    }
+
+    /// number of archived files and the error (empty on success)
+    pub fn last_result(&self) -> Result<Option<(usize, String)>> {
+        let res = self.last_result.lock()?;
+        Ok(res.clone())
+    }
+
+    /// percent of the files written
+    pub fn progress(&self) -> Result<u8> {
+        let res = self.progress.lock()?;
+        Ok(res.progress)
+    }
+
+    ///
+    pub fn request(&mut self, params: ArchiveRequest) -> Result<()> {
+        log::trace!("request");
+
+        if self.is_pending()? {
+            return Ok(());
+        }
+
+        self.set_request(&params)?;
+        *self.progress.lock()? = ProgressPercent::empty();
+
+        let arc_state = Arc::clone(&self.state);
+        let arc_res = Arc::clone(&self.last_result);
+        let arc_progress = Arc::clone(&self.progress);
+        let sender = self.sender.clone();
+
+        thread::spawn(move || {
+            let res = archive_with_progress(
+                CWD,
+                params.commit,
+                params.format,
+                &params.output_path,
+                &mut |current, total| {
+                    let percent =
+                        ProgressPercent::new(current, total);
+                    let mut progress =
+                        arc_progress.lock().expect("lock failed");
+
+                    // notify only if the gauge moves
+                    if percent.progress != progress.progress {
+                        *progress = percent;
+                        sender
+                            .send(AsyncNotification::Archive)
+                            .expect("AsyncNotification error");
+                    }
+                },
+            );
+
+            Self::set_result(&arc_res, res).expect("result error");
+
+            Self::clear_request(&arc_state).expect("clear error");
+
+            sender
+                .send(AsyncNotification::Archive)
+                .expect("AsyncNotification error");
+        });
+
+        Ok(())
+    }
+
+    fn set_request(&self, params: &ArchiveRequest) -> Result<()> {
+        let mut state = self.state.lock()?;
+
+        if state.is_some() {
+            return Err(Error::Generic("pending request".into()));
+        }
+
+        *state = Some(params.clone());
+
+        Ok(())
+    }
+
+    fn clear_request(
+        state: &Arc<Mutex<Option<ArchiveRequest>>>,
+    ) -> Result<()> {
+        let mut state = state.lock()?;
+
+        *state = None;
+
+        Ok(())
+    }
+
+    fn set_result(
+        arc_result: &Arc<Mutex<Option<(usize, String)>>>,
+        res: Result<usize>,
+    ) -> Result<()> {
+        let mut last_res = arc_result.lock()?;
+
+        *last_res = match res {
+            Ok(count) => Some((count, String::new())),
+            Err(e) => {
+                log::error!("archive error: {}", e);
+                Some((0, e.to_string()))
+            }
+        };
+
+        Ok(())
+    }
+}
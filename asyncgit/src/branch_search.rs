@@ -0,0 +1,106 @@
+use crate::{
+    error::Result,
+    sync::{search_branches, BranchSearchResult},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+///
+#[derive(Default, Clone, Debug)]
+pub struct BranchSearchRequest {
+    ///
+    pub query: String,
+    /// maximum number of matching commits
+    pub limit: usize,
+}
+
+type SearchResult = Option<Result<BranchSearchResult>>;
+
+/// searches commits across all branches in the background
+pub struct AsyncBranchSearch {
+    last_result: Arc<Mutex<SearchResult>>,
+    scanned: Arc<AtomicUsize>,
+    pending: Arc<AtomicBool>,
+    sender: Sender<AsyncNotification>,
+}
+
+impl AsyncBranchSearch {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            last_result: Arc::new(Mutex::new(None)),
+            scanned: Arc::new(AtomicUsize::new(0)),
+            pending: Arc::new(AtomicBool::new(false)),
+            sender: sender.clone(),
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed)
+    }
+
+    /// number of commits scanned by the running search
+    pub fn progress(&self) -> usize {
+        self.scanned.load(Ordering::Relaxed)
+    }
+
+    /// hands out the result of the last finished search (only once)
+    pub fn take_last_result(&self) -> Result<SearchResult> {
+        let mut res = self.last_result.lock()?;
+        Ok(res.take())
+    }
+
+    ///
+    pub fn request(
+        &mut self,
+        params: BranchSearchRequest,
+    ) -> Result<()> {
+        log::trace!("request");
+
+        if self.is_pending() {
+            return Ok(());
+        }
+
+        self.pending.store(true, Ordering::Relaxed);
+        self.scanned.store(0, Ordering::Relaxed);
+        *self.last_result.lock()? = None;
+
+        let arc_result = Arc::clone(&self.last_result);
+        let arc_scanned = Arc::clone(&self.scanned);
+        let arc_pending = Arc::clone(&self.pending);
+        let sender = self.sender.clone();
+
+        rayon_core::spawn(move || {
+            let progress_sender = sender.clone();
+            let res = search_branches(
+                CWD,
+                &params.query,
+                params.limit,
+                &|scanned| {
+                    arc_scanned.store(scanned, Ordering::Relaxed);
+                    progress_sender
+                        .send(AsyncNotification::BranchSearch)
+                        .expect("error sending");
+                },
+            );
+
+            if let Err(e) = &res {
+                log::error!("branch search error: {}", e);
+            }
+
+            *arc_result.lock().expect("lock error") = Some(res);
+            arc_pending.store(false, Ordering::Relaxed);
+
+            sender
+                .send(AsyncNotification::BranchSearch)
+                .expect("error sending");
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{
+    error::Result,
+    sync::{get_branches_info, BranchInfo},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+/// local (`true`) or remote branches and the list read
+type BranchesResult = (bool, Vec<BranchInfo>);
+
+/// reads the branches with their upstream comparison (see
+/// `get_branches_info`) in the background
+pub struct AsyncBranches {
+    last_result: Arc<Mutex<Option<BranchesResult>>>,
+    pending: Arc<AtomicBool>,
+    sender: Sender<AsyncNotification>,
+}
+
+impl AsyncBranches {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            last_result: Arc::new(Mutex::new(None)),
+            pending: Arc::new(AtomicBool::new(false)),
+            sender: sender.clone(),
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed)
+    }
+
+    /// hands out the result of the last finished request (only once)
+    pub fn take_last_result(&self) -> Result<Option<BranchesResult>> {
+        let mut res = self.last_result.lock()?;
+        Ok(res.take())
+    }
+
+    /// ignored while another request runs, its result tells which
+    /// branches it holds
+    pub fn request(&mut self, local: bool) -> Result<()> {
+        log::trace!("request");
+
+        if self.is_pending() {
+            return Ok(());
+        }
+
+        self.pending.store(true, Ordering::Relaxed);
+
+        let arc_result = Arc::clone(&self.last_result);
+        let arc_pending = Arc::clone(&self.pending);
+        let sender = self.sender.clone();
+
+        rayon_core::spawn(move || {
+            match get_branches_info(CWD, local) {
+                Ok(branches) => {
+                    *arc_result.lock().expect("lock error") =
+                        Some((local, branches));
+                }
+                Err(e) => log::error!("branches error: {}", e),
+            }
+
+            arc_pending.store(false, Ordering::Relaxed);
+
+            sender
+                .send(AsyncNotification::Branches)
+                .expect("error sending");
+        });
+
+        Ok(())
+    }
+}
@@ -40,9 +40,11 @@ impl BranchName {
         self.last_result.as_ref().map(|last| last.1.clone())
     }
 
+    /// a detached HEAD forgets the last name
     fn fetch(&mut self, head: Head) -> Result<String> {
-        let name = get_branch_name(self.repo_path.as_str())?;
-        self.last_result = Some((head, name.clone()));
-        Ok(name)
+        let name = get_branch_name(self.repo_path.as_str());
+        self.last_result =
+            name.as_ref().ok().map(|name| (head, name.clone()));
+        name
     }
 }
@@ -0,0 +1,75 @@
+use crate::{
+    error::Result,
+    sync::{self, RefsSnapshot, RefsState},
+};
+use std::sync::Arc;
+
+/// keeps the last `RefsSnapshot` until the refs on disk change
+pub struct Refs {
+    last_result: Option<(RefsState, Arc<RefsSnapshot>)>,
+    repo_path: String,
+}
+
+impl Refs {
+    ///
+    pub fn new(path: &str) -> Self {
+        Self {
+            repo_path: path.to_string(),
+            last_result: None,
+        }
+    }
+
+    ///
+    pub fn lookup(&mut self) -> Result<Arc<RefsSnapshot>> {
+        let current_state =
+            sync::get_refs_state(self.repo_path.as_str())?;
+
+        if let Some((last_state, refs)) = self.last_result.as_ref() {
+            if *last_state == current_state {
+                return Ok(Arc::clone(refs));
+            }
+        }
+
+        self.fetch(current_state)
+    }
+
+    ///
+    pub fn last(&self) -> Option<Arc<RefsSnapshot>> {
+        self.last_result.as_ref().map(|last| Arc::clone(&last.1))
+    }
+
+    fn fetch(
+        &mut self,
+        state: RefsState,
+    ) -> Result<Arc<RefsSnapshot>> {
+        let refs = Arc::new(sync::get_refs(self.repo_path.as_str())?);
+        self.last_result = Some((state, Arc::clone(&refs)));
+        Ok(refs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Refs;
+    use crate::sync::{create_branch, tests::repo_init};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_cached_until_refs_change() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let mut cache = Refs::new(repo_path);
+        assert!(cache.last().is_none());
+
+        let first = cache.lookup().unwrap();
+        assert!(Arc::ptr_eq(&first, &cache.lookup().unwrap()));
+
+        create_branch(repo_path, "other").unwrap();
+
+        let second = cache.lookup().unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(second.local_branches.len(), 2);
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{
+    error::Result,
+    sync::{self, CommitDetails, CommitId, SignatureInfo},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+/// details fetched at the same time at most
+const MAX_WORKERS: usize = 2;
+
+#[derive(Default)]
+struct Jobs {
+    queue: VecDeque<CommitId>,
+    in_flight: HashSet<CommitId>,
+    workers: usize,
+    /// signatures verified or being verified
+    verified: HashSet<CommitId>,
+    verifying: usize,
+}
+
+/// fetches `CommitDetails` in the background and caches them by
+/// `CommitId`, so commits next to the selection can be prefetched.
+/// signatures are verified on demand only
+pub struct AsyncCommitDetails {
+    cache: Arc<Mutex<HashMap<CommitId, CommitDetails>>>,
+    signatures: Arc<Mutex<HashMap<CommitId, SignatureInfo>>>,
+    jobs: Arc<Mutex<Jobs>>,
+    sender: Sender<AsyncNotification>,
+}
+
+impl AsyncCommitDetails {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            signatures: Arc::new(Mutex::new(HashMap::new())),
+            jobs: Arc::new(Mutex::new(Jobs::default())),
+            sender: sender.clone(),
+        }
+    }
+
+    /// cached details of `id`, with the signature once verified
+    pub fn get(&self, id: CommitId) -> Result<Option<CommitDetails>> {
+        let mut details = self.cache.lock()?.get(&id).cloned();
+
+        if let Some(details) = &mut details {
+            details.signature =
+                self.signatures.lock()?.get(&id).cloned();
+        }
+
+        Ok(details)
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.jobs.lock().map_or(false, |jobs| {
+            jobs.workers > 0 || jobs.verifying > 0
+        })
+    }
+
+    /// verifies the signature of `id` in the background (once per
+    /// commit kept by `retain`), notified with
+    /// `AsyncNotification::CommitDetails` as well
+    pub fn verify_signature(&mut self, id: CommitId) -> Result<()> {
+        {
+            let mut jobs = self.jobs.lock()?;
+            if !jobs.verified.insert(id) {
+                return Ok(());
+            }
+            jobs.verifying += 1;
+        }
+
+        let arc_signatures = Arc::clone(&self.signatures);
+        let arc_jobs = Arc::clone(&self.jobs);
+        let sender = self.sender.clone();
+
+        rayon_core::spawn(move || {
+            let signature = sync::get_commit_signature(CWD, id)
+                .unwrap_or_else(|e| {
+                    log::error!("get_commit_signature: {}", e);
+                    None
+                });
+
+            if let Some(signature) = signature {
+                arc_signatures
+                    .lock()
+                    .expect("failed to cache signature")
+                    .insert(id, signature);
+            }
+
+            arc_jobs.lock().expect("failed to verify").verifying -= 1;
+
+            sender
+                .send(AsyncNotification::CommitDetails)
+                .expect("error sending");
+        });
+
+        Ok(())
+    }
+
+    /// queues `ids` (most wanted first) replacing whatever was
+    /// queued before, cached ones are skipped. every commit that
+    /// got fetched is notified with `AsyncNotification::CommitDetails`
+    pub fn fetch(&mut self, ids: &[CommitId]) -> Result<()> {
+        let new_workers = {
+            let cache = self.cache.lock()?;
+            let mut jobs = self.jobs.lock()?;
+
+            let queue = ids
+                .iter()
+                .filter(|id| {
+                    !cache.contains_key(id)
+                        && !jobs.in_flight.contains(id)
+                })
+                .copied()
+                .collect();
+            jobs.queue = queue;
+
+            let new_workers = jobs
+                .queue
+                .len()
+                .min(MAX_WORKERS)
+                .saturating_sub(jobs.workers);
+            jobs.workers += new_workers;
+
+            new_workers
+        };
+
+        for _ in 0..new_workers {
+            let arc_cache = Arc::clone(&self.cache);
+            let arc_jobs = Arc::clone(&self.jobs);
+            let sender = self.sender.clone();
+
+            rayon_core::spawn(move || {
+                Self::worker(&arc_cache, &arc_jobs, &sender)
+                    .expect("failed to fetch commit details");
+            });
+        }
+
+        Ok(())
+    }
+
+    /// evicts cached details of all commits but `keep`
+    pub fn retain(&mut self, keep: &[CommitId]) -> Result<()> {
+        self.cache.lock()?.retain(|id, _| keep.contains(id));
+        self.signatures.lock()?.retain(|id, _| keep.contains(id));
+        self.jobs.lock()?.verified.retain(|id| keep.contains(id));
+
+        Ok(())
+    }
+
+    fn worker(
+        arc_cache: &Arc<Mutex<HashMap<CommitId, CommitDetails>>>,
+        arc_jobs: &Arc<Mutex<Jobs>>,
+        sender: &Sender<AsyncNotification>,
+    ) -> Result<()> {
+        loop {
+            let id = {
+                let mut jobs = arc_jobs.lock()?;
+                if let Some(id) = jobs.queue.pop_front() {
+                    jobs.in_flight.insert(id);
+                    id
+                } else {
+                    // decided under the lock so `fetch` never
+                    // counts on a worker that is about to quit
+                    jobs.workers -= 1;
+                    return Ok(());
+                }
+            };
+
+            let details = sync::get_commit_details(CWD, id).ok();
+            let fetched = details.is_some();
+
+            if let Some(details) = details {
+                arc_cache.lock()?.insert(id, details);
+            }
+
+            arc_jobs.lock()?.in_flight.remove(&id);
+
+            // no notification on error, a requery would fail again
+            if fetched {
+                sender
+                    .send(AsyncNotification::CommitDetails)
+                    .expect("error sending");
+            }
+        }
+    }
+}
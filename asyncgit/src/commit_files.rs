@@ -1,7 +1,7 @@
 use crate::{
     error::Result,
-    sync::{self, CommitId},
-    AsyncNotification, StatusItem, CWD,
+    sync::{self, CommitFiles, CommitId, MergeDiff},
+    AsyncNotification, CWD,
 };
 use crossbeam_channel::Sender;
 use std::sync::{
@@ -9,12 +9,13 @@ use std::sync::{
     Arc, Mutex,
 };
 
-type ResultType = Vec<StatusItem>;
+type ResultType = CommitFiles;
+type RequestType = (CommitId, MergeDiff);
 struct Request<R, A>(R, A);
 
 ///
 pub struct AsyncCommitFiles {
-    current: Arc<Mutex<Option<Request<CommitId, ResultType>>>>,
+    current: Arc<Mutex<Option<Request<RequestType, ResultType>>>>,
     sender: Sender<AsyncNotification>,
     pending: Arc<AtomicUsize>,
 }
@@ -32,7 +33,7 @@ impl AsyncCommitFiles {
     ///
     pub fn current(
         &mut self,
-    ) -> Result<Option<(CommitId, ResultType)>> {
+    ) -> Result<Option<(RequestType, ResultType)>> {
         let c = self.current.lock()?;
 
         if let Some(c) = c.as_ref() {
@@ -48,7 +49,11 @@ impl AsyncCommitFiles {
     }
 
     ///
-    pub fn fetch(&mut self, id: CommitId) -> Result<()> {
+    pub fn fetch(
+        &mut self,
+        id: CommitId,
+        merge: MergeDiff,
+    ) -> Result<()> {
         if self.is_pending() {
             return Ok(());
         }
@@ -58,7 +63,7 @@ impl AsyncCommitFiles {
         {
             let current = self.current.lock()?;
             if let Some(c) = &*current {
-                if c.0 == id {
+                if c.0 == (id, merge) {
                     return Ok(());
                 }
             }
@@ -71,7 +76,7 @@ impl AsyncCommitFiles {
         self.pending.fetch_add(1, Ordering::Relaxed);
 
         rayon_core::spawn(move || {
-            Self::fetch_helper(id, arc_current)
+            Self::fetch_helper(id, merge, arc_current)
                 .expect("failed to fetch");
 
             arc_pending.fetch_sub(1, Ordering::Relaxed);
@@ -86,21 +91,23 @@ impl AsyncCommitFiles {
 
     fn fetch_helper(
         id: CommitId,
+        merge: MergeDiff,
         arc_current: Arc<
-            Mutex<Option<Request<CommitId, ResultType>>>,
+            Mutex<Option<Request<RequestType, ResultType>>>,
         >,
     ) -> Result<()> {
-        let res = sync::get_commit_files(CWD, id)?;
+        let res = sync::get_commit_files_split(CWD, id, merge)?;
 
         log::trace!(
-            "get_commit_files: {} ({})",
+            "get_commit_files: {} ({}+{})",
             id.to_string(),
-            res.len()
+            res.files.len(),
+            res.untracked.len()
         );
 
         {
             let mut current = arc_current.lock()?;
-            *current = Some(Request(id, res));
+            *current = Some(Request((id, merge), res));
         }
 
         Ok(())
@@ -0,0 +1,169 @@
+use crate::{
+    error::Result,
+    sync::{
+        utils::repo, CommitId, LogScope, LogSearch, LogWalker,
+        LogWalkerFilter,
+    },
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// commits checked between two looks at whether the search is
+/// outdated
+const BATCH_SIZE: usize = 1000;
+
+///
+#[derive(Clone)]
+pub struct CommitFilterRequest {
+    ///
+    pub search: LogSearch,
+    /// filter the log is walked with, so the indices of the matches
+    /// line up with it
+    pub walk_filter: Option<LogWalkerFilter>,
+    /// refs the log is walked from, for the same reason
+    pub scope: LogScope,
+    /// see `LogWalker::first_parent`, for the same reason
+    pub first_parent: bool,
+}
+
+///
+#[derive(Debug, Clone)]
+pub struct CommitFilterResult {
+    ///
+    pub search: LogSearch,
+    /// positions of the matching commits in the log, ascending
+    pub matches: Vec<usize>,
+    ///
+    pub ids: HashSet<CommitId>,
+}
+
+/// finds the commits of the log matching a search in the background,
+/// a new request makes a running one stop
+pub struct AsyncCommitFilter {
+    last_result: Arc<Mutex<Option<CommitFilterResult>>>,
+    /// bumped with every request, outdated searches stop on a change
+    generation: Arc<AtomicUsize>,
+    pending: Arc<AtomicBool>,
+    sender: Sender<AsyncNotification>,
+}
+
+impl AsyncCommitFilter {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            last_result: Arc::new(Mutex::new(None)),
+            generation: Arc::new(AtomicUsize::new(0)),
+            pending: Arc::new(AtomicBool::new(false)),
+            sender: sender.clone(),
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed)
+    }
+
+    /// hands out the result of the last finished search (only once)
+    pub fn take_last_result(
+        &self,
+    ) -> Result<Option<CommitFilterResult>> {
+        let mut res = self.last_result.lock()?;
+        Ok(res.take())
+    }
+
+    /// stops a running search, its result is dropped
+    pub fn cancel(&mut self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        self.pending.store(false, Ordering::Relaxed);
+    }
+
+    ///
+    pub fn request(
+        &mut self,
+        params: CommitFilterRequest,
+    ) -> Result<()> {
+        log::trace!("request");
+
+        let generation =
+            self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+
+        self.pending.store(true, Ordering::Relaxed);
+        *self.last_result.lock()? = None;
+
+        let arc_result = Arc::clone(&self.last_result);
+        let arc_generation = Arc::clone(&self.generation);
+        let arc_pending = Arc::clone(&self.pending);
+        let sender = self.sender.clone();
+
+        rayon_core::spawn(move || {
+            let outdated = || {
+                arc_generation.load(Ordering::Relaxed) != generation
+            };
+
+            let res = Self::search(&params, &outdated);
+
+            if outdated() {
+                return;
+            }
+
+            match res {
+                Ok(res) => {
+                    *arc_result.lock().expect("lock error") =
+                        Some(res);
+                }
+                Err(e) => log::error!("commit filter error: {}", e),
+            }
+            arc_pending.store(false, Ordering::Relaxed);
+
+            sender
+                .send(AsyncNotification::CommitFilter)
+                .expect("error sending");
+        });
+
+        Ok(())
+    }
+
+    fn search(
+        params: &CommitFilterRequest,
+        outdated: &dyn Fn() -> bool,
+    ) -> Result<CommitFilterResult> {
+        let repo = repo(CWD)?;
+        let mut walker = LogWalker::new(&repo)
+            .filter(params.walk_filter.clone())
+            .scope(params.scope)
+            .first_parent(params.first_parent);
+
+        let mut res = CommitFilterResult {
+            search: params.search.clone(),
+            matches: Vec::new(),
+            ids: HashSet::new(),
+        };
+        let mut index = 0_usize;
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+
+        loop {
+            batch.clear();
+            if walker.read(&mut batch, BATCH_SIZE)? == 0 || outdated()
+            {
+                break;
+            }
+
+            for id in &batch {
+                if params.search.matches(&repo, id)? {
+                    res.matches.push(index);
+                    res.ids.insert(*id);
+                }
+                index += 1;
+            }
+        }
+
+        Ok(res)
+    }
+}
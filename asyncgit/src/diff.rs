@@ -1,7 +1,10 @@
 use crate::{
-    error::Result,
+    error::{Error, Result},
     hash,
-    sync::{self, CommitId},
+    sync::{
+        self, utils::map_missing_object, CommitId, DiffOptions,
+        MergeDiff,
+    },
     AsyncNotification, FileDiff, CWD,
 };
 use crossbeam_channel::Sender;
@@ -16,21 +19,34 @@ use std::{
 ///
 #[derive(Hash, Clone, PartialEq)]
 pub enum DiffType {
-    /// diff in a given commit
-    Commit(CommitId),
+    /// diff in a given commit, merges against the given parent(s)
+    Commit(CommitId, MergeDiff),
+    /// diff between two commits (from, to)
+    Commits(CommitId, CommitId),
     /// diff against staged file
     Stage,
     /// diff against file in workdir
     WorkDir,
+    /// combined diff of all staged files beneath a folder
+    StageFolder,
+    /// combined diff of all workdir files beneath a folder
+    WorkDirFolder,
+    /// combined diff of all files of a stash, the path is unused
+    Stash(CommitId),
+    /// combined diff of all files changed between two commits
+    /// (from, to), the path is unused
+    Range(CommitId, CommitId),
 }
 
 ///
 #[derive(Hash, Clone, PartialEq)]
 pub struct DiffParams {
-    /// path to the file to diff
+    /// path to the file (or folder) to diff
     pub path: String,
     /// what kind of diff
     pub diff_type: DiffType,
+    ///
+    pub options: DiffOptions,
 }
 
 struct Request<R, A>(R, Option<A>);
@@ -48,6 +64,11 @@ pub struct AsyncDiff {
     last: Arc<Mutex<Option<LastResult<DiffParams, FileDiff>>>>,
     sender: Sender<AsyncNotification>,
     pending: Arc<AtomicUsize>,
+    /// files done and total of a running folder diff
+    progress: Arc<Mutex<Option<(usize, usize)>>>,
+    /// objects missing from a partial clone, other errors are only
+    /// logged
+    last_error: Arc<Mutex<Option<String>>>,
 }
 
 impl AsyncDiff {
@@ -58,9 +79,21 @@ impl AsyncDiff {
             last: Arc::new(Mutex::new(None)),
             sender: sender.clone(),
             pending: Arc::new(AtomicUsize::new(0)),
+            progress: Arc::new(Mutex::new(None)),
+            last_error: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// files done and total while a folder diff is computed
+    pub fn progress(&self) -> Result<Option<(usize, usize)>> {
+        Ok(*self.progress.lock()?)
+    }
+
+    /// error of a failed diff that should be shown
+    pub fn take_last_error(&mut self) -> Result<Option<String>> {
+        Ok(self.last_error.lock()?.take())
+    }
+
     ///
     pub fn last(&mut self) -> Result<Option<(DiffParams, FileDiff)>> {
         let last = self.last.lock()?;
@@ -106,6 +139,8 @@ impl AsyncDiff {
         let arc_last = Arc::clone(&self.last);
         let sender = self.sender.clone();
         let arc_pending = Arc::clone(&self.pending);
+        let arc_progress = Arc::clone(&self.progress);
+        let arc_error = Arc::clone(&self.last_error);
 
         self.pending.fetch_add(1, Ordering::Relaxed);
 
@@ -114,12 +149,21 @@ impl AsyncDiff {
                 params,
                 arc_last,
                 arc_current,
+                &arc_progress,
+                &sender,
                 hash,
             );
 
-            let notify = match notify {
+            let notify = match notify
+                .map_err(|e| map_missing_object(CWD, e))
+            {
                 Err(err) => {
                     log::error!("get_diff_helper error: {}", err);
+                    if let Error::PartialClone(_) = err {
+                        if let Ok(mut last_error) = arc_error.lock() {
+                            *last_error = Some(err.to_string());
+                        }
+                    }
                     true
                 }
                 Ok(notify) => notify,
@@ -145,20 +189,64 @@ impl AsyncDiff {
             Mutex<Option<LastResult<DiffParams, FileDiff>>>,
         >,
         arc_current: Arc<Mutex<Request<u64, FileDiff>>>,
+        arc_progress: &Mutex<Option<(usize, usize)>>,
+        sender: &Sender<AsyncNotification>,
         hash: u64,
     ) -> Result<bool> {
+        let options = Some(params.options);
         let res = match params.diff_type {
-            DiffType::Stage => {
-                sync::diff::get_diff(CWD, params.path.clone(), true)?
-            }
-            DiffType::WorkDir => {
-                sync::diff::get_diff(CWD, params.path.clone(), false)?
+            DiffType::StageFolder | DiffType::WorkDirFolder => {
+                let res = Self::get_diff_folder(
+                    &params,
+                    &arc_current,
+                    arc_progress,
+                    sender,
+                    hash,
+                );
+                *arc_progress.lock()? = None;
+
+                match res? {
+                    Some(res) => res,
+                    // superseded by a newer request
+                    None => return Ok(false),
+                }
             }
-            DiffType::Commit(id) => sync::diff::get_diff_commit(
+            DiffType::Stage => sync::diff::get_diff(
                 CWD,
-                id,
                 params.path.clone(),
+                true,
+                options,
             )?,
+            DiffType::WorkDir => sync::diff::get_diff(
+                CWD,
+                params.path.clone(),
+                false,
+                options,
+            )?,
+            DiffType::Commit(id, merge) => {
+                sync::diff::get_diff_commit(
+                    CWD,
+                    id,
+                    params.path.clone(),
+                    merge,
+                    options,
+                )?
+            }
+            DiffType::Stash(id) => {
+                sync::stash_diff(CWD, id, options)?
+            }
+            DiffType::Range(from, to) => {
+                sync::diff::get_diff_range(CWD, from, to, options)?
+            }
+            DiffType::Commits(from, to) => {
+                sync::diff::get_diff_commits(
+                    CWD,
+                    from,
+                    to,
+                    params.path.clone(),
+                    options,
+                )?
+            }
         };
 
         let mut notify = false;
@@ -182,6 +270,44 @@ impl AsyncDiff {
         Ok(notify)
     }
 
+    /// notifies whenever another percent of the files is done,
+    /// gives up as soon as a different diff is requested
+    fn get_diff_folder(
+        params: &DiffParams,
+        arc_current: &Mutex<Request<u64, FileDiff>>,
+        arc_progress: &Mutex<Option<(usize, usize)>>,
+        sender: &Sender<AsyncNotification>,
+        hash: u64,
+    ) -> Result<Option<FileDiff>> {
+        let mut last_percent = 0;
+
+        sync::diff::get_diff_folder(
+            CWD,
+            &params.path,
+            params.diff_type == DiffType::StageFolder,
+            Some(params.options),
+            |done, total| {
+                let current = arc_current
+                    .lock()
+                    .map_or(false, |current| current.0 == hash);
+
+                if let Ok(mut progress) = arc_progress.lock() {
+                    *progress = Some((done, total));
+                }
+
+                let percent = done * 100 / total.max(1);
+                if percent > last_percent {
+                    last_percent = percent;
+                    sender
+                        .send(AsyncNotification::Diff)
+                        .expect("error sending diff");
+                }
+
+                current
+            },
+        )
+    }
+
     fn get_last_param(&self) -> Result<Option<DiffParams>> {
         Ok(self.last.lock()?.clone().map(|e| e.params))
     }
@@ -21,6 +21,21 @@ pub enum Error {
     #[error("git: uncommitted changes")]
     UncommittedChanges,
 
+    #[error("git: not possible to fast-forward, {0} local and {1} incoming commits")]
+    NotFastForward(usize, usize),
+
+    #[error("git: checkout would overwrite local changes: {}", .0.join(", "))]
+    CheckoutConflicts(Vec<String>),
+
+    #[error("git: file changed since the diff was shown")]
+    OutdatedDiff,
+
+    #[error("git: object missing from this partial clone ({0}).\n\ngitui cannot download objects the clone filtered out. Use `git fetch` or `git checkout` on the command line to fetch them.")]
+    PartialClone(String),
+
+    #[error("signing failed: {0}")]
+    Sign(String),
+
     #[error("io error:{0}")]
     Io(#[from] std::io::Error),
 
@@ -30,6 +45,9 @@ pub enum Error {
     #[error("utf8 error:{0}")]
     Utf8Error(#[from] FromUtf8Error),
 
+    #[error("zip error:{0}")]
+    Zip(#[from] zip::result::ZipError),
+
     #[error("TryFromInt error:{0}")]
     IntError(#[from] TryFromIntError),
 }
@@ -11,26 +11,43 @@
 //TODO: get this in someday since expect still leads us to crashes sometimes
 // #![deny(clippy::expect_used)]
 
+mod archive;
+mod branch_search;
+mod branches;
 pub mod cached;
+mod commit_details;
 mod commit_files;
+mod commit_filter;
 mod diff;
 mod error;
 mod fetch;
+mod fetch_all;
 mod progress;
 mod push;
 mod push_tags;
+mod rebase;
 pub mod remote_progress;
 mod revlog;
 mod status;
 pub mod sync;
+mod syntax;
 mod tags;
 
 pub use crate::{
+    archive::{ArchiveRequest, AsyncArchive},
+    branch_search::{AsyncBranchSearch, BranchSearchRequest},
+    branches::AsyncBranches,
+    commit_details::AsyncCommitDetails,
     commit_files::AsyncCommitFiles,
+    commit_filter::{
+        AsyncCommitFilter, CommitFilterRequest, CommitFilterResult,
+    },
     diff::{AsyncDiff, DiffParams, DiffType},
     fetch::{AsyncFetch, FetchRequest},
+    fetch_all::{AsyncFetchAll, FetchAllRequest},
     push::{AsyncPush, PushRequest},
     push_tags::{AsyncPushTags, PushTagsRequest},
+    rebase::AsyncRebase,
     remote_progress::{RemoteProgress, RemoteProgressState},
     revlog::{AsyncLog, FetchStatus},
     status::{AsyncStatus, StatusParams},
@@ -38,6 +55,7 @@ pub use crate::{
         diff::{DiffLine, DiffLineType, FileDiff},
         status::{StatusItem, StatusItemType},
     },
+    syntax::{AsyncSyntax, DiffSyntax, SyntaxSpan},
     tags::AsyncTags,
 };
 use std::{
@@ -59,6 +77,8 @@ pub enum AsyncNotification {
     ///
     CommitFiles,
     ///
+    CommitDetails,
+    ///
     Tags,
     ///
     Push,
@@ -66,6 +86,20 @@ pub enum AsyncNotification {
     PushTags,
     ///
     Fetch,
+    ///
+    FetchAll,
+    ///
+    BranchSearch,
+    ///
+    Branches,
+    ///
+    CommitFilter,
+    ///
+    Syntax,
+    ///
+    Archive,
+    ///
+    Rebase,
 }
 
 /// current working director `./`
@@ -1,8 +1,12 @@
 use crate::{
     error::{Error, Result},
     sync::{
-        cred::BasicAuthCredential, remotes::push::push,
-        remotes::push::ProgressNotification,
+        branch_set_upstream_to,
+        cred::BasicAuthCredential,
+        remotes::push::{
+            branch_refspec, delete_branch_refspec, push, push_to,
+            ProgressNotification,
+        },
     },
     AsyncNotification, RemoteProgress, CWD,
 };
@@ -19,8 +23,17 @@ pub struct PushRequest {
     pub remote: String,
     ///
     pub branch: String,
-    ///
+    /// best-effort `--force-with-lease`: the remote ref is compared
+    /// to its tracking branch right before the push, not atomically
+    /// with it (libgit2 has no hook in between)
     pub force: bool,
+    /// branch on the remote, `None` for the one of the same name
+    /// (which becomes the upstream unless there is one already)
+    pub target_branch: Option<String>,
+    /// with a `target_branch`: make it the upstream after the push
+    pub set_upstream: bool,
+    /// with a `target_branch`: delete it on the remote instead
+    pub delete: bool,
     ///
     pub basic_credential: Option<BasicAuthCredential>,
 }
@@ -93,14 +106,50 @@ impl AsyncPush {
                 arc_progress,
             );
 
-            let res = push(
-                CWD,
-                params.remote.as_str(),
-                params.branch.as_str(),
-                params.force,
-                params.basic_credential.clone(),
-                Some(progress_sender.clone()),
-            );
+            let res = if let (Some(target), true) =
+                (&params.target_branch, params.delete)
+            {
+                push_to(
+                    CWD,
+                    params.remote.as_str(),
+                    &delete_branch_refspec(target),
+                    params.basic_credential.clone(),
+                    Some(progress_sender.clone()),
+                )
+            } else if let Some(target) = &params.target_branch {
+                push_to(
+                    CWD,
+                    params.remote.as_str(),
+                    &branch_refspec(
+                        &params.branch,
+                        target,
+                        params.force,
+                    ),
+                    params.basic_credential.clone(),
+                    Some(progress_sender.clone()),
+                )
+                .and_then(|()| {
+                    if params.set_upstream {
+                        branch_set_upstream_to(
+                            CWD,
+                            &params.branch,
+                            &params.remote,
+                            target,
+                        )
+                    } else {
+                        Ok(())
+                    }
+                })
+            } else {
+                push(
+                    CWD,
+                    params.remote.as_str(),
+                    params.branch.as_str(),
+                    params.force,
+                    params.basic_credential.clone(),
+                    Some(progress_sender.clone()),
+                )
+            };
 
             progress_sender
                 .send(ProgressNotification::Done)
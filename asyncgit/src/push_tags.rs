@@ -2,7 +2,9 @@ use crate::{
     error::{Error, Result},
     sync::{
         cred::BasicAuthCredential,
-        remotes::tags::{push_tags, PushTagsProgress},
+        remotes::tags::{
+            push_tag, push_tags, PushTagsProgress, PushTagsTarget,
+        },
     },
     AsyncNotification, RemoteProgress, CWD,
 };
@@ -18,6 +20,8 @@ pub struct PushTagsRequest {
     ///
     pub remote: String,
     ///
+    pub target: PushTagsTarget,
+    ///
     pub basic_credential: Option<BasicAuthCredential>,
 }
 
@@ -89,12 +93,26 @@ impl AsyncPushTags {
                 arc_progress,
             );
 
-            let res = push_tags(
-                CWD,
-                params.remote.as_str(),
-                params.basic_credential.clone(),
-                Some(progress_sender),
-            );
+            let res = match &params.target {
+                PushTagsTarget::Missing => push_tags(
+                    CWD,
+                    params.remote.as_str(),
+                    params.basic_credential.clone(),
+                    Some(progress_sender),
+                ),
+                PushTagsTarget::Tag(tag)
+                | PushTagsTarget::DeleteTag(tag) => push_tag(
+                    CWD,
+                    params.remote.as_str(),
+                    tag,
+                    matches!(
+                        params.target,
+                        PushTagsTarget::DeleteTag(_)
+                    ),
+                    params.basic_credential.clone(),
+                    Some(progress_sender),
+                ),
+            };
 
             handle.join().expect("joining thread failed");
 
@@ -0,0 +1,76 @@
+use crate::{
+    error::Result,
+    sync::{rebase_onto, RebaseState},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+/// how the rebase ended, or its error
+type RebaseResult = std::result::Result<RebaseState, String>;
+
+/// rebases HEAD onto a branch (see `rebase_onto`) in the
+/// background, long branches take a while
+pub struct AsyncRebase {
+    last_result: Arc<Mutex<Option<RebaseResult>>>,
+    pending: Arc<AtomicBool>,
+    sender: Sender<AsyncNotification>,
+}
+
+impl AsyncRebase {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            last_result: Arc::new(Mutex::new(None)),
+            pending: Arc::new(AtomicBool::new(false)),
+            sender: sender.clone(),
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed)
+    }
+
+    /// hands out the result of the last finished request (only once)
+    pub fn take_last_result(&self) -> Result<Option<RebaseResult>> {
+        let mut res = self.last_result.lock()?;
+        Ok(res.take())
+    }
+
+    /// ignored while another rebase runs
+    pub fn request(&mut self, branch: &str) -> Result<()> {
+        log::trace!("request");
+
+        if self.is_pending() {
+            return Ok(());
+        }
+
+        self.pending.store(true, Ordering::Relaxed);
+
+        let branch = branch.to_string();
+        let arc_result = Arc::clone(&self.last_result);
+        let arc_pending = Arc::clone(&self.pending);
+        let sender = self.sender.clone();
+
+        rayon_core::spawn(move || {
+            let res = rebase_onto(CWD, &branch).map_err(|e| {
+                log::error!("rebase error: {}", e);
+                e.to_string()
+            });
+
+            *arc_result.lock().expect("lock error") = Some(res);
+
+            arc_pending.store(false, Ordering::Relaxed);
+
+            sender
+                .send(AsyncNotification::Rebase)
+                .expect("error sending");
+        });
+
+        Ok(())
+    }
+}
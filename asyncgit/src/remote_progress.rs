@@ -25,6 +25,8 @@ pub enum RemoteProgressState {
     Pushing,
     /// fetch progress
     Transfer,
+    /// phase reported by the remote
+    Remote(String),
     /// remote progress done
     Done,
 }
@@ -149,6 +151,15 @@ impl From<ProgressNotification> for RemoteProgress {
                 objects,
                 total_objects,
             ),
+            ProgressNotification::Remote {
+                phase,
+                current,
+                total,
+            } => RemoteProgress::new(
+                RemoteProgressState::Remote(phase),
+                current,
+                total,
+            ),
             _ => RemoteProgress::new(RemoteProgressState::Done, 1, 1),
         }
     }
@@ -1,6 +1,9 @@
 use crate::{
     error::Result,
-    sync::{utils::repo, CommitId, LogWalker},
+    sync::{
+        utils::repo, CommitId, GraphRow, LogScope, LogWalker,
+        LogWalkerFilter,
+    },
     AsyncNotification, CWD,
 };
 use crossbeam_channel::Sender;
@@ -8,7 +11,7 @@ use git2::Oid;
 use scopetime::scope_time;
 use std::{
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc, Mutex,
     },
     thread,
@@ -26,34 +29,120 @@ pub enum FetchStatus {
     Started,
 }
 
+/// state of one walk, shared with its thread
+#[derive(Default)]
+struct Walk {
+    /// set once the walk is replaced: the thread stops
+    cancel: AtomicBool,
+    /// reading commits (not waiting for them to be wanted)
+    pending: AtomicBool,
+    /// the whole log is read
+    done: AtomicBool,
+    filter: Option<LogWalkerFilter>,
+    scope: LogScope,
+    first_parent: bool,
+    /// HEAD the walk started from
+    head: Option<Oid>,
+}
+
 ///
 pub struct AsyncLog {
     current: Arc<Mutex<Vec<CommitId>>>,
+    /// one row per entry of `current`, empty with a filter
+    graph: Arc<Mutex<Vec<GraphRow>>>,
     sender: Sender<AsyncNotification>,
-    pending: Arc<AtomicBool>,
+    walk: Option<Arc<Walk>>,
+    /// commits the view wants loaded, the walk waits beyond that
+    wanted: Arc<AtomicUsize>,
     background: Arc<AtomicBool>,
+    restart: Arc<AtomicBool>,
+    filter: Option<LogWalkerFilter>,
+    scope: LogScope,
+    first_parent: bool,
 }
 
 static LIMIT_COUNT: usize = 3000;
 static SLEEP_FOREGROUND: Duration = Duration::from_millis(2);
 static SLEEP_BACKGROUND: Duration = Duration::from_millis(1000);
+static SLEEP_IDLE: Duration = Duration::from_millis(50);
 
 impl AsyncLog {
     ///
     pub fn new(sender: &Sender<AsyncNotification>) -> Self {
         Self {
             current: Arc::new(Mutex::new(Vec::new())),
+            graph: Arc::new(Mutex::new(Vec::new())),
             sender: sender.clone(),
-            pending: Arc::new(AtomicBool::new(false)),
+            walk: None,
+            wanted: Arc::new(AtomicUsize::new(LIMIT_COUNT)),
             background: Arc::new(AtomicBool::new(false)),
+            restart: Arc::new(AtomicBool::new(false)),
+            filter: None,
+            scope: LogScope::Head,
+            first_parent: false,
         }
     }
 
-    ///
+    /// sets the filter used for the walk and invalidates the current
+    /// result, the next `fetch` will start a new walk
+    pub fn set_filter(
+        &mut self,
+        filter: Option<LogWalkerFilter>,
+    ) -> Result<()> {
+        self.filter = filter;
+
+        self.invalidate()
+    }
+
+    /// sets the refs the log is walked from and invalidates the
+    /// current result like `set_filter`, also used to walk anew
+    /// once other refs than HEAD moved
+    pub fn set_scope(&mut self, scope: LogScope) -> Result<()> {
+        self.scope = scope;
+
+        self.invalidate()
+    }
+
+    /// like `set_filter`, see `LogWalker::first_parent`
+    pub fn set_first_parent(
+        &mut self,
+        first_parent: bool,
+    ) -> Result<()> {
+        self.first_parent = first_parent;
+
+        self.invalidate()
+    }
+
+    fn invalidate(&mut self) -> Result<()> {
+        self.cancel();
+
+        let mut current = self.current.lock()?;
+        self.restart.store(true, Ordering::Relaxed);
+        current.clear();
+        self.graph.lock()?.clear();
+
+        Ok(())
+    }
+
+    /// commits loaded so far, more are read as they are wanted
     pub fn count(&mut self) -> Result<usize> {
         Ok(self.current.lock()?.len())
     }
 
+    /// makes sure the commits up to `index` (and a batch beyond) get
+    /// loaded, the walk stops reading once it is that far
+    pub fn want(&self, index: usize) {
+        self.wanted.store(
+            index.saturating_add(LIMIT_COUNT),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// index of `id` in the commits loaded so far
+    pub fn position(&self, id: CommitId) -> Result<Option<usize>> {
+        Ok(self.current.lock()?.iter().position(|c| *c == id))
+    }
+
     ///
     pub fn get_slice(
         &self,
@@ -68,67 +157,105 @@ impl AsyncLog {
         Ok(list[min..max].to_vec())
     }
 
+    /// graph rows of the commits of `get_slice`, none with a filter
+    pub fn get_graph_slice(
+        &self,
+        start_index: usize,
+        amount: usize,
+    ) -> Result<Vec<GraphRow>> {
+        let graph = self.graph.lock()?;
+        let min = start_index.min(graph.len());
+        let max = (min + amount).min(graph.len());
+        Ok(graph[min..max].to_vec())
+    }
+
     ///
     pub fn is_pending(&self) -> bool {
-        self.pending.load(Ordering::Relaxed)
+        self.walk.as_ref().map_or(false, |walk| {
+            walk.pending.load(Ordering::Relaxed)
+        })
     }
 
-    ///
-    pub fn set_background(&mut self) {
-        self.background.store(true, Ordering::Relaxed)
+    /// no more commits to load, the last walk read the whole log
+    pub fn is_done(&self) -> bool {
+        self.walk
+            .as_ref()
+            .map_or(false, |walk| walk.done.load(Ordering::Relaxed))
     }
 
     ///
-    fn current_head(&self) -> Result<CommitId> {
-        Ok(self
-            .current
-            .lock()?
-            .first()
-            .map_or(Oid::zero().into(), |f| *f))
+    pub fn set_background(&mut self) {
+        self.background.store(true, Ordering::Relaxed)
     }
 
-    ///
+    /// compared to the HEAD the last walk started from, the first
+    /// commit loaded is a different one with a filter
     fn head_changed(&self) -> Result<bool> {
-        if let Ok(head) = repo(CWD)?.head() {
-            if let Some(head) = head.target() {
-                return Ok(head != self.current_head()?.into());
-            }
+        if let Some(head) = Self::head()? {
+            return Ok(self
+                .walk
+                .as_ref()
+                .map_or(true, |walk| walk.head != Some(head)));
         }
         Ok(false)
     }
 
-    ///
+    fn head() -> Result<Option<Oid>> {
+        Ok(repo(CWD)?.head().ok().and_then(|head| head.target()))
+    }
+
+    /// starts a new walk if the log changed, the running one (still
+    /// reading or waiting for more commits to be wanted) is cancelled
     pub fn fetch(&mut self) -> Result<FetchStatus> {
         self.background.store(false, Ordering::Relaxed);
 
-        if self.is_pending() {
-            return Ok(FetchStatus::Pending);
-        }
-
-        if !self.head_changed()? {
-            return Ok(FetchStatus::NoChange);
+        if !self.restart.load(Ordering::Relaxed) {
+            if self.is_pending() {
+                return Ok(FetchStatus::Pending);
+            }
+            if !self.head_changed()? {
+                return Ok(FetchStatus::NoChange);
+            }
         }
 
+        self.cancel();
         self.clear()?;
+        self.restart.store(false, Ordering::Relaxed);
+        self.wanted.store(LIMIT_COUNT, Ordering::Relaxed);
+
+        let walk = Arc::new(Walk {
+            filter: self.filter.clone(),
+            scope: self.scope,
+            first_parent: self.first_parent,
+            head: Self::head()?,
+            ..Walk::default()
+        });
+        walk.pending.store(true, Ordering::Relaxed);
+        self.walk = Some(Arc::clone(&walk));
 
         let arc_current = Arc::clone(&self.current);
+        let arc_graph = Arc::clone(&self.graph);
+        let arc_wanted = Arc::clone(&self.wanted);
         let sender = self.sender.clone();
-        let arc_pending = Arc::clone(&self.pending);
         let arc_background = Arc::clone(&self.background);
 
-        self.pending.store(true, Ordering::Relaxed);
-
-        rayon_core::spawn(move || {
+        // not on the rayon pool: the walk may wait a long time for
+        // its commits to be wanted
+        thread::spawn(move || {
             scope_time!("async::revlog");
 
             AsyncLog::fetch_helper(
+                &walk,
                 arc_current,
-                arc_background,
+                arc_graph,
+                &arc_wanted,
+                &arc_background,
                 &sender,
             )
             .expect("failed to fetch");
 
-            arc_pending.store(false, Ordering::Relaxed);
+            walk.pending.store(false, Ordering::Relaxed);
+            walk.done.store(true, Ordering::Relaxed);
 
             Self::notify(&sender);
         });
@@ -137,43 +264,75 @@ impl AsyncLog {
     }
 
     fn fetch_helper(
+        walk: &Walk,
         arc_current: Arc<Mutex<Vec<CommitId>>>,
-        arc_background: Arc<AtomicBool>,
+        arc_graph: Arc<Mutex<Vec<GraphRow>>>,
+        arc_wanted: &AtomicUsize,
+        arc_background: &AtomicBool,
         sender: &Sender<AsyncNotification>,
     ) -> Result<()> {
         let mut entries = Vec::with_capacity(LIMIT_COUNT);
         let r = repo(CWD)?;
-        let mut walker = LogWalker::new(&r);
+        let mut walker = LogWalker::new(&r)
+            .graph(true)
+            .filter(walk.filter.clone())
+            .scope(walk.scope)
+            .first_parent(walk.first_parent);
         loop {
+            while arc_current.lock()?.len()
+                >= arc_wanted.load(Ordering::Relaxed)
+            {
+                if walk.cancel.load(Ordering::Relaxed) {
+                    return Ok(());
+                }
+                walk.pending.store(false, Ordering::Relaxed);
+                thread::sleep(SLEEP_IDLE);
+            }
+            walk.pending.store(true, Ordering::Relaxed);
+
             entries.clear();
             let res_is_err =
                 walker.read(&mut entries, LIMIT_COUNT).is_err();
 
             if !res_is_err {
                 let mut current = arc_current.lock()?;
+
+                // replaced meanwhile, results are outdated
+                if walk.cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+
                 current.extend(entries.iter());
+                arc_graph.lock()?.extend(walker.take_graph());
             }
 
-            if res_is_err || entries.len() <= 1 {
+            if res_is_err || entries.len() < LIMIT_COUNT {
                 break;
-            } else {
-                Self::notify(&sender);
-
-                let sleep_duration =
-                    if arc_background.load(Ordering::Relaxed) {
-                        SLEEP_BACKGROUND
-                    } else {
-                        SLEEP_FOREGROUND
-                    };
-                thread::sleep(sleep_duration);
             }
+
+            Self::notify(sender);
+
+            let sleep_duration =
+                if arc_background.load(Ordering::Relaxed) {
+                    SLEEP_BACKGROUND
+                } else {
+                    SLEEP_FOREGROUND
+                };
+            thread::sleep(sleep_duration);
         }
 
         Ok(())
     }
 
+    fn cancel(&mut self) {
+        if let Some(walk) = self.walk.take() {
+            walk.cancel.store(true, Ordering::Relaxed);
+        }
+    }
+
     fn clear(&mut self) -> Result<()> {
         self.current.lock()?.clear();
+        self.graph.lock()?.clear();
         Ok(())
     }
 
@@ -181,3 +340,36 @@ impl AsyncLog {
         sender.send(AsyncNotification::Log).expect("error sending");
     }
 }
+
+impl Drop for AsyncLog {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_fetch_with_filter_walks_once() {
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+        let mut log = AsyncLog::new(&sender);
+
+        // nothing matches: the first commit loaded is never HEAD
+        log.set_filter(Some(Arc::new(Box::new(|_, _| Ok(false)))))
+            .unwrap();
+
+        assert!(log.fetch().unwrap() == FetchStatus::Started);
+
+        let start = Instant::now();
+        while !log.is_done() {
+            assert!(start.elapsed() < Duration::from_secs(30));
+            thread::sleep(SLEEP_IDLE);
+        }
+
+        assert_eq!(log.count().unwrap(), 0);
+        assert!(log.fetch().unwrap() == FetchStatus::NoChange);
+    }
+}
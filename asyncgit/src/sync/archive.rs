@@ -0,0 +1,320 @@
+//! sync git api for writing the tree of a commit into an archive
+//! (like `git archive`)
+
+use super::{
+    utils::{repo, work_dir},
+    CommitId,
+};
+use crate::error::{Error, Result};
+use flate2::{write::GzEncoder, Compression};
+use git2::{
+    Commit, ObjectType, Oid, Repository, TreeWalkMode, TreeWalkResult,
+};
+use scopetime::scope_time;
+use std::{
+    convert::TryFrom,
+    fs::{self, File},
+    io::{BufWriter, Seek, Write},
+};
+use tar::{EntryType, Header};
+use time::OffsetDateTime;
+use zip::{
+    write::FileOptions, CompressionMethod, DateTime, ZipWriter,
+};
+
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ArchiveFormat {
+    ///
+    Tar,
+    ///
+    TarGz,
+    ///
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// including the leading dot
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Tar => ".tar",
+            Self::TarGz => ".tar.gz",
+            Self::Zip => ".zip",
+        }
+    }
+}
+
+/// a file of the archived tree
+struct Entry {
+    path: String,
+    id: Oid,
+    mode: i32,
+}
+
+impl Entry {
+    const fn is_link(&self) -> bool {
+        self.mode == 0o120_000
+    }
+
+    /// permission bits as git tracks them
+    const fn permissions(&self) -> u32 {
+        if self.mode == 0o100_755 {
+            0o755
+        } else if self.is_link() {
+            0o777
+        } else {
+            0o644
+        }
+    }
+
+    /// `content` is the blob of this (symlink) entry
+    fn link_target<'b>(&self, content: &'b [u8]) -> Result<&'b str> {
+        std::str::from_utf8(content).map_err(|_| {
+            Error::Generic(format!(
+                "symlink target is not valid utf8: {}",
+                self.path
+            ))
+        })
+    }
+}
+
+/// writes all files of `commit` into `output_path` (relative paths
+/// are below the workdir), returns the number of files
+pub fn archive(
+    repo_path: &str,
+    commit: CommitId,
+    format: ArchiveFormat,
+    output_path: &str,
+) -> Result<usize> {
+    archive_with_progress(
+        repo_path,
+        commit,
+        format,
+        output_path,
+        &mut |_, _| (),
+    )
+}
+
+/// `progress` is called with the number of files written and the
+/// total after each file
+pub(crate) fn archive_with_progress(
+    repo_path: &str,
+    commit: CommitId,
+    format: ArchiveFormat,
+    output_path: &str,
+    progress: &mut dyn FnMut(usize, usize),
+) -> Result<usize> {
+    scope_time!("archive");
+
+    let repo = repo(repo_path)?;
+    let commit = repo.find_commit(commit.into())?;
+    let entries = tree_entries(&commit)?;
+
+    let path = work_dir(&repo)?.join(output_path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = BufWriter::new(File::create(&path)?);
+
+    let mut writer = ArchiveWriter {
+        repo: &repo,
+        entries: &entries,
+        time: commit.time(),
+        progress,
+    };
+
+    match format {
+        ArchiveFormat::Tar => writer.tar(file)?.flush()?,
+        ArchiveFormat::TarGz => writer
+            .tar(GzEncoder::new(file, Compression::default()))?
+            .finish()?
+            .flush()?,
+        ArchiveFormat::Zip => writer.zip(file)?.flush()?,
+    }
+
+    Ok(entries.len())
+}
+
+/// files and symlinks in tree order, submodules are left out
+fn tree_entries(commit: &Commit) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+
+    commit.tree()?.walk(
+        TreeWalkMode::PreOrder,
+        |parent, entry| {
+            if entry.kind() == Some(ObjectType::Blob) {
+                if let Some(name) = entry.name() {
+                    entries.push(Entry {
+                        path: format!("{}{}", parent, name),
+                        id: entry.id(),
+                        mode: entry.filemode(),
+                    });
+                }
+            }
+            TreeWalkResult::Ok
+        },
+    )?;
+
+    Ok(entries)
+}
+
+struct ArchiveWriter<'a> {
+    repo: &'a Repository,
+    entries: &'a [Entry],
+    time: git2::Time,
+    progress: &'a mut dyn FnMut(usize, usize),
+}
+
+impl<'a> ArchiveWriter<'a> {
+    fn tar<W: Write>(&mut self, out: W) -> Result<W> {
+        let mtime = u64::try_from(self.time.seconds()).unwrap_or(0);
+        let mut builder = tar::Builder::new(out);
+
+        for (idx, entry) in self.entries.iter().enumerate() {
+            let blob = self.repo.find_blob(entry.id)?;
+            let content = blob.content();
+
+            let mut header = Header::new_gnu();
+            header.set_mode(entry.permissions());
+            header.set_mtime(mtime);
+            header.set_uid(0);
+            header.set_gid(0);
+
+            if entry.is_link() {
+                header.set_entry_type(EntryType::Symlink);
+                header.set_size(0);
+                let target = entry.link_target(content)?;
+                builder.append_link(
+                    &mut header,
+                    &entry.path,
+                    target,
+                )?;
+            } else {
+                header.set_entry_type(EntryType::Regular);
+                header.set_size(u64::try_from(content.len())?);
+                builder.append_data(
+                    &mut header,
+                    &entry.path,
+                    content,
+                )?;
+            }
+
+            (self.progress)(idx + 1, self.entries.len());
+        }
+
+        Ok(builder.into_inner()?)
+    }
+
+    fn zip<W: Write + Seek>(&mut self, out: W) -> Result<W> {
+        let mut zip = ZipWriter::new(out);
+
+        // zip stores local time, dates before 1980 can not be stored
+        let modified = OffsetDateTime::from_unix_timestamp(
+            self.time.seconds()
+                + i64::from(self.time.offset_minutes()) * 60,
+        )
+        .ok()
+        .and_then(|time| DateTime::try_from(time).ok())
+        .unwrap_or_default();
+
+        for (idx, entry) in self.entries.iter().enumerate() {
+            let blob = self.repo.find_blob(entry.id)?;
+            let content = blob.content();
+
+            let options = FileOptions::default()
+                .compression_method(CompressionMethod::Deflated)
+                .last_modified_time(modified)
+                .unix_permissions(entry.permissions())
+                .large_file(u32::try_from(content.len()).is_err());
+
+            if entry.is_link() {
+                let target = entry.link_target(content)?;
+                zip.add_symlink(
+                    entry.path.as_str(),
+                    target,
+                    options,
+                )?;
+            } else {
+                zip.start_file(entry.path.as_str(), options)?;
+                zip.write_all(content)?;
+            }
+
+            (self.progress)(idx + 1, self.entries.len());
+        }
+
+        Ok(zip.finish()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::tests::{repo_init, write_commit_file};
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    #[test]
+    fn test_archive_tar() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let dir = "d".repeat(120);
+        let long = format!("{}/{}", dir, "f".repeat(110));
+        fs::create_dir(root.join(&dir)).unwrap();
+        write_commit_file(&repo, "a.txt", "a", "c1");
+        let id = write_commit_file(&repo, &long, "bb", "c2");
+
+        let count =
+            archive(repo_path, id, ArchiveFormat::Tar, "out/x.tar")
+                .unwrap();
+        assert_eq!(count, 2);
+
+        let file = File::open(root.join("out/x.tar")).unwrap();
+        let mut tar = tar::Archive::new(file);
+        let mut files = Vec::new();
+        for entry in tar.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let path =
+                entry.path().unwrap().to_string_lossy().to_string();
+            let mut content = String::new();
+            entry.read_to_string(&mut content).unwrap();
+            files.push((path, content));
+        }
+
+        assert_eq!(
+            files,
+            vec![
+                (String::from("a.txt"), String::from("a")),
+                (long, String::from("bb")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_archive_zip() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let id = write_commit_file(&repo, "a.txt", "aaaa", "c1");
+
+        archive(repo_path, id, ArchiveFormat::Zip, "x.zip").unwrap();
+        archive(repo_path, id, ArchiveFormat::TarGz, "x.tar.gz")
+            .unwrap();
+
+        let file = File::open(root.join("x.zip")).unwrap();
+        let mut zip = zip::ZipArchive::new(file).unwrap();
+        assert_eq!(zip.len(), 1);
+        let mut entry = zip.by_index(0).unwrap();
+        assert_eq!(entry.name(), "a.txt");
+        assert_eq!(entry.unix_mode(), Some(0o100_644));
+        let mut content = String::new();
+        entry.read_to_string(&mut content).unwrap();
+        assert_eq!(content, "aaaa");
+
+        let file = File::open(root.join("x.tar.gz")).unwrap();
+        let mut tar = tar::Archive::new(GzDecoder::new(file));
+        assert_eq!(tar.entries().unwrap().count(), 1);
+    }
+}
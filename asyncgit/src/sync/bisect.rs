@@ -0,0 +1,426 @@
+//! finding the commit that introduced a bug (`git bisect`) by
+//! classifying checked out commits as good or bad. the state is kept
+//! like git does (`refs/bisect/*`, `.git/BISECT_START`) so either
+//! one can continue or reset it
+
+use super::{
+    branch::checkout_commit,
+    utils::{bytes2string, has_uncommitted_changes, repo},
+    CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{
+    build::CheckoutBuilder, Oid, Repository, RepositoryState, Sort,
+};
+use scopetime::scope_time;
+use std::{fs, io::Write};
+
+const START_FILE: &str = "BISECT_START";
+const LOG_FILE: &str = "BISECT_LOG";
+const TERMS_FILE: &str = "BISECT_TERMS";
+const REF_BAD: &str = "refs/bisect/bad";
+const REF_PREFIX_GOOD: &str = "refs/bisect/good-";
+const REF_PREFIX_SKIP: &str = "refs/bisect/skip-";
+
+/// what is left to do after a commit was classified
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BisectStep {
+    /// `id` is checked out to be classified next
+    Test(CommitId),
+    /// the first bad commit
+    Found(CommitId),
+    /// the first bad commit is one of the skipped ones
+    OnlySkipped,
+}
+
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BisectState {
+    ///
+    pub bad: CommitId,
+    ///
+    pub good: Vec<CommitId>,
+    ///
+    pub skipped: Vec<CommitId>,
+    /// commits that may be the first bad one (including `bad`),
+    /// newest first
+    pub remaining: Vec<CommitId>,
+    ///
+    pub step: BisectStep,
+}
+
+/// starts bisecting the commits between `good` and `bad` and checks
+/// out the first one to test. needs a clean working dir
+pub fn bisect_start(
+    repo_path: &str,
+    bad: CommitId,
+    good: CommitId,
+) -> Result<BisectStep> {
+    scope_time!("bisect_start");
+
+    let repo = repo(repo_path)?;
+
+    if repo.state() != RepositoryState::Clean {
+        return Err(Error::Generic(String::from(
+            "repository is not in a clean state",
+        )));
+    }
+    if has_uncommitted_changes(&repo)? {
+        return Err(Error::UncommittedChanges);
+    }
+    if bad == good
+        || !repo.graph_descendant_of(bad.into(), good.into())?
+    {
+        return Err(Error::Generic(String::from(
+            "the good commit has to be an ancestor of the bad one",
+        )));
+    }
+
+    let head = repo.head()?;
+    let start = if head.is_branch() {
+        bytes2string(head.shorthand_bytes())?
+    } else {
+        head.peel_to_commit()?.id().to_string()
+    };
+
+    fs::write(repo.path().join(START_FILE), format!("{}\n", start))?;
+    fs::write(repo.path().join(TERMS_FILE), "bad\ngood\n")?;
+    fs::write(repo.path().join(LOG_FILE), "git bisect start\n")?;
+
+    mark(&repo, bad, REF_BAD, "bad")?;
+    mark(&repo, good, &good_ref(good), "good")?;
+
+    next_step(repo_path, &repo)
+}
+
+/// `id` does not have the bug
+pub fn bisect_good(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<BisectStep> {
+    scope_time!("bisect_good");
+
+    let repo = repo(repo_path)?;
+    ensure_bisecting(&repo)?;
+
+    mark(&repo, id, &good_ref(id), "good")?;
+
+    next_step(repo_path, &repo)
+}
+
+/// `id` has the bug
+pub fn bisect_bad(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<BisectStep> {
+    scope_time!("bisect_bad");
+
+    let repo = repo(repo_path)?;
+    ensure_bisecting(&repo)?;
+
+    mark(&repo, id, REF_BAD, "bad")?;
+
+    next_step(repo_path, &repo)
+}
+
+/// `id` cannot be tested, another commit nearby is checked out
+pub fn bisect_skip(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<BisectStep> {
+    scope_time!("bisect_skip");
+
+    let repo = repo(repo_path)?;
+    ensure_bisecting(&repo)?;
+
+    mark(
+        &repo,
+        id,
+        &format!("{}{}", REF_PREFIX_SKIP, id.to_string()),
+        "skip",
+    )?;
+
+    next_step(repo_path, &repo)
+}
+
+/// `None` unless bisecting
+pub fn bisect_state(repo_path: &str) -> Result<Option<BisectState>> {
+    scope_time!("bisect_state");
+
+    let repo = repo(repo_path)?;
+
+    if !is_bisecting(&repo) {
+        return Ok(None);
+    }
+
+    get_state(&repo).map(Some)
+}
+
+/// ends bisecting and checks out the branch (or commit) it was
+/// started on
+pub fn bisect_reset(repo_path: &str) -> Result<()> {
+    scope_time!("bisect_reset");
+
+    let repo = repo(repo_path)?;
+    ensure_bisecting(&repo)?;
+
+    let start = fs::read_to_string(repo.path().join(START_FILE))?;
+    let start = start.trim();
+
+    if let Ok(branch) =
+        repo.find_branch(start, git2::BranchType::Local)
+    {
+        let branch_ref = branch.into_reference();
+        let commit = branch_ref.peel_to_commit()?;
+        repo.checkout_tree(
+            commit.as_object(),
+            Some(CheckoutBuilder::new().safe()),
+        )?;
+        repo.set_head(&bytes2string(branch_ref.name_bytes())?)?;
+    } else {
+        checkout_commit(repo_path, Oid::from_str(start)?.into())?;
+    }
+
+    for reference in repo.references_glob("refs/bisect/*")? {
+        reference?.delete()?;
+    }
+    for file in &[START_FILE, LOG_FILE, TERMS_FILE] {
+        let path = repo.path().join(file);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// written first when starting and removed last when resetting
+fn is_bisecting(repo: &Repository) -> bool {
+    repo.path().join(START_FILE).exists()
+}
+
+fn ensure_bisecting(repo: &Repository) -> Result<()> {
+    if is_bisecting(repo) {
+        Ok(())
+    } else {
+        Err(Error::Generic(String::from("not bisecting")))
+    }
+}
+
+fn good_ref(id: CommitId) -> String {
+    format!("{}{}", REF_PREFIX_GOOD, id.to_string())
+}
+
+fn mark(
+    repo: &Repository,
+    id: CommitId,
+    ref_name: &str,
+    term: &str,
+) -> Result<()> {
+    repo.reference(ref_name, id.into(), true, "bisect")?;
+
+    let mut log = fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(repo.path().join(LOG_FILE))?;
+    writeln!(log, "git bisect {} {}", term, id.to_string())?;
+
+    Ok(())
+}
+
+fn ids_of_refs(
+    repo: &Repository,
+    prefix: &str,
+) -> Result<Vec<CommitId>> {
+    let mut res = Vec::new();
+    for reference in repo.references_glob(&format!("{}*", prefix))? {
+        if let Some(id) = reference?.target() {
+            res.push(id.into());
+        }
+    }
+
+    Ok(res)
+}
+
+fn get_state(repo: &Repository) -> Result<BisectState> {
+    let bad: CommitId = repo
+        .find_reference(REF_BAD)?
+        .target()
+        .ok_or_else(|| {
+            Error::Generic(String::from("invalid bad commit"))
+        })?
+        .into();
+    let good = ids_of_refs(repo, REF_PREFIX_GOOD)?;
+    let skipped = ids_of_refs(repo, REF_PREFIX_SKIP)?;
+
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+    walk.push(bad.into())?;
+    for id in &good {
+        walk.hide((*id).into())?;
+    }
+    let remaining = walk
+        .map(|id| id.map(CommitId::from))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let testable = remaining
+        .iter()
+        .filter(|id| **id != bad && !skipped.contains(id))
+        .collect::<Vec<_>>();
+
+    // halfway through is exact for a linear history
+    let step = if remaining.len() <= 1 {
+        BisectStep::Found(bad)
+    } else if testable.is_empty() {
+        BisectStep::OnlySkipped
+    } else {
+        BisectStep::Test(*testable[testable.len() / 2])
+    };
+
+    Ok(BisectState {
+        bad,
+        good,
+        skipped,
+        remaining,
+        step,
+    })
+}
+
+fn next_step(
+    repo_path: &str,
+    repo: &Repository,
+) -> Result<BisectStep> {
+    let state = get_state(repo)?;
+
+    match state.step {
+        BisectStep::Test(id) => checkout_commit(repo_path, id)?,
+        BisectStep::Found(id) => {
+            let mut log = fs::OpenOptions::new()
+                .append(true)
+                .open(repo.path().join(LOG_FILE))?;
+            writeln!(
+                log,
+                "# first bad commit: [{}]",
+                id.to_string()
+            )?;
+        }
+        BisectStep::OnlySkipped => (),
+    }
+
+    Ok(state.step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        branch::get_branch_name, commit, get_head, stage_add_file,
+        tests::repo_init, utils::repo_write_file,
+    };
+    use std::path::Path;
+
+    /// commits `0..count` of `f`, the bug is in all from `buggy` on
+    fn commits_with_bug(
+        repo: &Repository,
+        repo_path: &str,
+        count: usize,
+        buggy: usize,
+    ) -> Vec<CommitId> {
+        (0..count)
+            .map(|i| {
+                let content = if i >= buggy { "bug" } else { "ok" };
+                repo_write_file(
+                    repo,
+                    "f",
+                    &format!("{} {}", content, i),
+                )
+                .unwrap();
+                stage_add_file(repo_path, Path::new("f")).unwrap();
+                commit(repo_path, &format!("c{}", i)).unwrap()
+            })
+            .collect()
+    }
+
+    fn has_bug(root: &Path) -> bool {
+        fs::read_to_string(root.join("f"))
+            .unwrap()
+            .starts_with("bug")
+    }
+
+    #[test]
+    fn test_find_culprit() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let ids = commits_with_bug(&repo, repo_path, 10, 6);
+
+        let mut step =
+            bisect_start(repo_path, ids[9], ids[0]).unwrap();
+        assert_eq!(repo.state(), RepositoryState::Bisect);
+
+        let mut steps = 0;
+        while let BisectStep::Test(id) = step {
+            assert_eq!(get_head(repo_path).unwrap(), id);
+            step = if has_bug(root) {
+                bisect_bad(repo_path, id).unwrap()
+            } else {
+                bisect_good(repo_path, id).unwrap()
+            };
+            steps += 1;
+        }
+
+        assert_eq!(step, BisectStep::Found(ids[6]));
+        assert!(steps <= 4);
+
+        let state = bisect_state(repo_path).unwrap().unwrap();
+        assert_eq!(state.remaining, vec![ids[6]]);
+
+        bisect_reset(repo_path).unwrap();
+
+        assert_eq!(bisect_state(repo_path).unwrap(), None);
+        assert_eq!(get_branch_name(repo_path).unwrap(), "master");
+        assert_eq!(get_head(repo_path).unwrap(), ids[9]);
+        assert_eq!(repo.state(), RepositoryState::Clean);
+        assert!(repo
+            .references_glob("refs/bisect/*")
+            .unwrap()
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn test_skip() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let ids = commits_with_bug(&repo, repo_path, 3, 2);
+
+        let step = bisect_start(repo_path, ids[2], ids[0]).unwrap();
+        assert_eq!(step, BisectStep::Test(ids[1]));
+
+        let step = bisect_skip(repo_path, ids[1]).unwrap();
+        assert_eq!(step, BisectStep::OnlySkipped);
+
+        let state = bisect_state(repo_path).unwrap().unwrap();
+        assert_eq!(state.skipped, vec![ids[1]]);
+        assert_eq!(state.remaining, vec![ids[2], ids[1]]);
+    }
+
+    #[test]
+    fn test_start_invalid() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let ids = commits_with_bug(&repo, repo_path, 2, 1);
+
+        assert!(bisect_start(repo_path, ids[0], ids[1]).is_err());
+        assert!(bisect_good(repo_path, ids[0]).is_err());
+
+        repo_write_file(&repo, "f", "changed").unwrap();
+        assert!(bisect_start(repo_path, ids[1], ids[0]).is_err());
+        assert_eq!(bisect_state(repo_path).unwrap(), None);
+    }
+}
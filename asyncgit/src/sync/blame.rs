@@ -0,0 +1,208 @@
+//! line-wise origin of a file in a commit (`git blame`)
+
+use super::{
+    encoding::decode_guess,
+    utils::{map_missing_object, repo},
+    CommitId,
+};
+use crate::error::{Error, Result};
+use git2::BlameOptions;
+use scopetime::scope_time;
+use std::path::Path;
+
+/// commit that last changed a line
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlameLine {
+    ///
+    pub commit_id: CommitId,
+    ///
+    pub author: String,
+    /// author time
+    pub time: i64,
+    ///
+    pub content: String,
+}
+
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileBlame {
+    ///
+    pub path: String,
+    /// the blamed revision of the file
+    pub commit_id: CommitId,
+    ///
+    pub lines: Vec<BlameLine>,
+}
+
+/// blames `path` as of `commit_id` (`HEAD` if `None`),
+/// uncommitted changes are not part of it
+pub fn blame(
+    repo_path: &str,
+    path: &str,
+    commit_id: Option<CommitId>,
+) -> Result<FileBlame> {
+    scope_time!("blame");
+
+    let repo = repo(repo_path)?;
+
+    let commit = match commit_id {
+        Some(id) => repo.find_commit(id.into())?,
+        None => repo.head()?.peel_to_commit()?,
+    };
+
+    let blob = commit
+        .tree()?
+        .get_path(Path::new(path))?
+        .to_object(&repo)
+        .and_then(|object| object.peel_to_blob())
+        .map_err(|e| map_missing_object(repo_path, e.into()))?;
+
+    let mut opts = BlameOptions::new();
+    opts.newest_commit(commit.id());
+    let file_blame = repo
+        .blame_file(Path::new(path), Some(&mut opts))
+        .map_err(|e| map_missing_object(repo_path, e.into()))?;
+
+    // shown like in diffs
+    let lines = decode_guess(blob.content())
+        .lines()
+        .enumerate()
+        .map(|(idx, content)| {
+            let hunk =
+                file_blame.get_line(idx + 1).ok_or_else(|| {
+                    Error::Generic(format!(
+                        "line {} not blamed",
+                        idx + 1
+                    ))
+                })?;
+            let signature = hunk.final_signature();
+
+            Ok(BlameLine {
+                commit_id: hunk.final_commit_id().into(),
+                author: signature.name().unwrap_or_default().into(),
+                time: signature.when().seconds(),
+                content: content.into(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(FileBlame {
+        path: path.into(),
+        commit_id: commit.id().into(),
+        lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        commit, stage_add_file, tests::repo_init,
+        utils::repo_write_file,
+    };
+
+    #[test]
+    fn test_blame() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo_write_file(&repo, "f", "a\nb\n").unwrap();
+        stage_add_file(repo_path, Path::new("f")).unwrap();
+        let first = commit(repo_path, "first").unwrap();
+
+        repo_write_file(&repo, "f", "a\nc\nd\n").unwrap();
+        stage_add_file(repo_path, Path::new("f")).unwrap();
+        let second = commit(repo_path, "second").unwrap();
+
+        // uncommitted changes are ignored
+        repo_write_file(&repo, "f", "x\n").unwrap();
+
+        let res = blame(repo_path, "f", None).unwrap();
+
+        assert_eq!(res.commit_id, second);
+        assert_eq!(
+            res.lines
+                .iter()
+                .map(|l| (l.commit_id, l.content.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(first, "a"), (second, "c"), (second, "d")]
+        );
+        assert_eq!(res.lines[0].author, "name");
+
+        let res = blame(repo_path, "f", Some(first)).unwrap();
+
+        assert_eq!(
+            res.lines
+                .iter()
+                .map(|l| (l.commit_id, l.content.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(first, "a"), (first, "b")]
+        );
+    }
+
+    #[test]
+    fn test_blame_latin1() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        // "café" in latin-1
+        std::fs::write(root.join("f"), b"caf\xe9\n").unwrap();
+        stage_add_file(repo_path, Path::new("f")).unwrap();
+        commit(repo_path, "first").unwrap();
+
+        let res = blame(repo_path, "f", None).unwrap();
+
+        assert_eq!(res.lines[0].content, "café");
+    }
+
+    #[test]
+    fn test_blame_missing_file() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo_write_file(&repo, "f", "a\n").unwrap();
+
+        assert!(blame(repo_path, "f", None).is_err());
+    }
+
+    #[test]
+    fn test_blame_partial_clone() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo_write_file(&repo, "f", "a\n").unwrap();
+        stage_add_file(repo_path, Path::new("f")).unwrap();
+        commit(repo_path, "first").unwrap();
+
+        // what a clone with `--filter=blob:none` looks like
+        let blob = repo
+            .head()
+            .unwrap()
+            .peel_to_tree()
+            .unwrap()
+            .get_path(Path::new("f"))
+            .unwrap()
+            .id()
+            .to_string();
+        std::fs::remove_file(
+            repo.path()
+                .join("objects")
+                .join(&blob[..2])
+                .join(&blob[2..]),
+        )
+        .unwrap();
+        repo.config()
+            .unwrap()
+            .set_bool("remote.origin.promisor", true)
+            .unwrap();
+
+        assert!(matches!(
+            blame(repo_path, "f", None),
+            Err(Error::PartialClone(_))
+        ));
+    }
+}
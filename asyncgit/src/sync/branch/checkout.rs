@@ -0,0 +1,295 @@
+//! checking out branches with local changes in the way
+
+use super::checkout_branch;
+use crate::{
+    error::{Error, Result},
+    sync::{stash_apply, stash_drop, stash_save, utils, CommitId},
+};
+use git2::{build::CheckoutBuilder, CheckoutNotificationType};
+use scopetime::scope_time;
+
+/// paths with local changes that a checkout of `branch_ref` would
+/// overwrite (empty if the checkout can carry all local changes)
+pub fn checkout_conflicts(
+    repo_path: &str,
+    branch_ref: &str,
+) -> Result<Vec<String>> {
+    scope_time!("checkout_conflicts");
+
+    let repo = utils::repo(repo_path)?;
+    let tree = repo.find_reference(branch_ref)?.peel_to_tree()?;
+
+    let mut conflicts = Vec::new();
+
+    let res = {
+        let mut builder = CheckoutBuilder::new();
+        builder
+            .safe()
+            .dry_run()
+            .notify_on(CheckoutNotificationType::CONFLICT)
+            .notify(|_, path, _, _, _| {
+                if let Some(path) = path {
+                    conflicts
+                        .push(path.to_string_lossy().to_string());
+                }
+                true
+            });
+
+        repo.checkout_tree(tree.as_object(), Some(&mut builder))
+    };
+
+    // a dry run with conflicts fails, that is what we are after
+    match res {
+        Err(e) if conflicts.is_empty() => {
+            Err(utils::map_missing_object(repo_path, e.into()))
+        }
+        _ => Ok(conflicts),
+    }
+}
+
+/// like `git checkout -f`: local changes in tracked files are lost
+pub fn checkout_branch_force(
+    repo_path: &str,
+    branch_ref: &str,
+) -> Result<()> {
+    scope_time!("checkout_branch_force");
+
+    checkout_branch_with(
+        repo_path,
+        branch_ref,
+        CheckoutBuilder::new().force(),
+    )
+}
+
+/// stashes all local changes (including untracked files) and checks
+/// out `branch_ref`. with `reapply` the stash is applied and dropped
+/// afterwards. returns the stash if it was kept.
+pub fn stash_and_checkout_branch(
+    repo_path: &str,
+    branch_ref: &str,
+    reapply: bool,
+) -> Result<Option<CommitId>> {
+    scope_time!("stash_and_checkout_branch");
+
+    let stash = stash_save(
+        repo_path,
+        Some(&format!("autostash: checkout {}", branch_ref)),
+        true,
+        false,
+    )?;
+
+    if let Err(e) = checkout_branch(repo_path, branch_ref) {
+        // back to where we started
+        stash_apply(repo_path, stash, false)?;
+        stash_drop(repo_path, stash)?;
+        return Err(e);
+    }
+
+    if !reapply {
+        return Ok(Some(stash));
+    }
+
+    // applying would leave conflict markers in the workdir
+    let res = if stash_merges_cleanly(repo_path, stash)? {
+        stash_apply(repo_path, stash, false)
+    } else {
+        Err(Error::Generic(String::from("conflicts with branch")))
+    };
+
+    if let Err(e) = res {
+        return Err(Error::Generic(format!(
+            "switched branch, but re-applying local changes failed \
+            (they are kept in stash {}): {}",
+            stash.get_short_string(),
+            e
+        )));
+    }
+
+    stash_drop(repo_path, stash)?;
+
+    Ok(None)
+}
+
+fn stash_merges_cleanly(
+    repo_path: &str,
+    stash: CommitId,
+) -> Result<bool> {
+    let repo = utils::repo(repo_path)?;
+    let stash = repo.find_commit(stash.into())?;
+
+    let base = stash.parent(0)?.tree()?;
+    let head = repo.head()?.peel_to_tree()?;
+
+    let index =
+        repo.merge_trees(&base, &head, &stash.tree()?, None)?;
+
+    Ok(!index.has_conflicts())
+}
+
+pub(crate) fn checkout_branch_with(
+    repo_path: &str,
+    branch_ref: &str,
+    builder: &mut CheckoutBuilder,
+) -> Result<()> {
+    let repo = utils::repo(repo_path)?;
+    let tree = repo.find_reference(branch_ref)?.peel_to_tree()?;
+
+    repo.checkout_tree(tree.as_object(), Some(builder))
+        .map_err(|e| {
+            utils::map_missing_object(repo_path, e.into())
+        })?;
+    repo.set_head(branch_ref)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        create_branch, get_stashes,
+        tests::{repo_init, write_commit_file},
+        utils::repo_write_file,
+    };
+    use std::{fs, path::Path};
+
+    /// master and `other` differ in `a.txt`, both contain `b.txt`
+    fn setup(repo: &git2::Repository, repo_path: &str) {
+        write_commit_file(repo, "a.txt", "master", "a");
+        write_commit_file(repo, "b.txt", "b", "b");
+        create_branch(repo_path, "other").unwrap();
+        write_commit_file(repo, "a.txt", "other", "a other");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+    }
+
+    fn read(root: &Path, file: &str) -> String {
+        fs::read_to_string(root.join(file)).unwrap()
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup(&repo, repo_path);
+
+        repo_write_file(&repo, "b.txt", "local").unwrap();
+        assert!(checkout_conflicts(repo_path, "refs/heads/other")
+            .unwrap()
+            .is_empty());
+
+        repo_write_file(&repo, "a.txt", "local").unwrap();
+        assert_eq!(
+            checkout_conflicts(repo_path, "refs/heads/other")
+                .unwrap(),
+            vec![String::from("a.txt")]
+        );
+
+        assert!(matches!(
+            checkout_branch(repo_path, "refs/heads/other"),
+            Err(Error::CheckoutConflicts(_))
+        ));
+    }
+
+    #[test]
+    fn test_checkout_keeps_local_changes() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup(&repo, repo_path);
+
+        repo_write_file(&repo, "b.txt", "local").unwrap();
+        checkout_branch(repo_path, "refs/heads/other").unwrap();
+
+        assert_eq!(read(root, "a.txt"), "other");
+        assert_eq!(read(root, "b.txt"), "local");
+    }
+
+    #[test]
+    fn test_force() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup(&repo, repo_path);
+
+        repo_write_file(&repo, "a.txt", "local").unwrap();
+        checkout_branch_force(repo_path, "refs/heads/other").unwrap();
+
+        assert_eq!(read(root, "a.txt"), "other");
+        assert_eq!(
+            repo.head().unwrap().name().unwrap(),
+            "refs/heads/other"
+        );
+    }
+
+    #[test]
+    fn test_stash_and_checkout() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup(&repo, repo_path);
+
+        repo_write_file(&repo, "a.txt", "local").unwrap();
+        let stash = stash_and_checkout_branch(
+            repo_path,
+            "refs/heads/other",
+            false,
+        )
+        .unwrap();
+
+        assert!(stash.is_some());
+        assert_eq!(get_stashes(repo_path).unwrap().len(), 1);
+        assert_eq!(read(root, "a.txt"), "other");
+    }
+
+    #[test]
+    fn test_stash_and_checkout_reapply_conflict() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup(&repo, repo_path);
+
+        repo_write_file(&repo, "a.txt", "local").unwrap();
+        let res = stash_and_checkout_branch(
+            repo_path,
+            "refs/heads/other",
+            true,
+        );
+
+        // switched, but the local change is kept in the stash
+        assert!(res.is_err());
+        assert_eq!(get_stashes(repo_path).unwrap().len(), 1);
+        assert_eq!(read(root, "a.txt"), "other");
+    }
+
+    #[test]
+    fn test_stash_and_checkout_reapply() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup(&repo, repo_path);
+
+        repo_write_file(&repo, "b.txt", "local").unwrap();
+        repo_write_file(&repo, "new.txt", "untracked").unwrap();
+
+        assert_eq!(
+            stash_and_checkout_branch(
+                repo_path,
+                "refs/heads/other",
+                true
+            )
+            .unwrap(),
+            None
+        );
+
+        assert!(get_stashes(repo_path).unwrap().is_empty());
+        assert_eq!(read(root, "b.txt"), "local");
+        assert_eq!(read(root, "new.txt"), "untracked");
+    }
+}
@@ -0,0 +1,96 @@
+//! descriptions of local branches (`branch.<name>.description`,
+//! like `git branch --edit-description`)
+
+use crate::{error::Result, sync::utils};
+use git2::{BranchType, ConfigLevel};
+use scopetime::scope_time;
+
+fn config_key(branch: &str) -> String {
+    format!("branch.{}.description", branch)
+}
+
+/// description of the local `branch` (its name), `None` if it has
+/// none
+pub fn get_description(
+    repo_path: &str,
+    branch: &str,
+) -> Result<Option<String>> {
+    scope_time!("get_description");
+
+    let repo = utils::repo(repo_path)?;
+    let cfg = repo.config()?;
+
+    match cfg.get_string(&config_key(branch)) {
+        Ok(description) => Ok(Some(description)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// an empty (or blank) `description` removes it
+pub fn set_description(
+    repo_path: &str,
+    branch: &str,
+    description: &str,
+) -> Result<()> {
+    scope_time!("set_description");
+
+    let repo = utils::repo(repo_path)?;
+    // only existing branches get one
+    repo.find_branch(branch, BranchType::Local)?;
+
+    let mut cfg = repo.config()?.open_level(ConfigLevel::Local)?;
+    let key = config_key(branch);
+
+    if description.trim().is_empty() {
+        match cfg.remove(&key) {
+            Err(e) if e.code() != git2::ErrorCode::NotFound => {
+                return Err(e.into())
+            }
+            _ => (),
+        }
+    } else {
+        cfg.set_str(&key, description)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+    use super::{get_description, set_description};
+    use crate::sync::tests::repo_init;
+
+    #[test]
+    fn test_description() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        create_branch(repo_path, "branch1").unwrap();
+
+        assert_eq!(
+            get_description(repo_path, "branch1").unwrap(),
+            None
+        );
+
+        set_description(repo_path, "branch1", "review notes\nmore")
+            .unwrap();
+        assert_eq!(
+            get_description(repo_path, "branch1").unwrap().as_deref(),
+            Some("review notes\nmore")
+        );
+
+        set_description(repo_path, "branch1", " ").unwrap();
+        assert_eq!(
+            get_description(repo_path, "branch1").unwrap(),
+            None
+        );
+
+        // removing none is fine
+        set_description(repo_path, "branch1", "").unwrap();
+
+        assert!(set_description(repo_path, "nope", "x").is_err());
+    }
+}
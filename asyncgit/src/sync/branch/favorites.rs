@@ -0,0 +1,208 @@
+//! branches pinned to the top of the branch list, stored
+//! in the config of the repo (`gitui.favoriteBranch`)
+
+use crate::{error::Result, sync::utils};
+use git2::{Config, ConfigLevel, Repository};
+use scopetime::scope_time;
+
+const CONFIG_KEY: &str = "gitui.favoriteBranch";
+
+fn local_config(repo: &Repository) -> Result<Config> {
+    Ok(repo.config()?.open_level(ConfigLevel::Local)?)
+}
+
+fn read_favorites(cfg: &Config) -> Result<Vec<String>> {
+    let mut res = Vec::new();
+    let entries = cfg.multivar(CONFIG_KEY, None)?;
+    for entry in &entries {
+        if let Some(value) = entry?.value() {
+            res.push(value.to_string());
+        }
+    }
+
+    Ok(res)
+}
+
+fn write_favorites(
+    cfg: &mut Config,
+    favorites: &[String],
+) -> Result<()> {
+    match cfg.remove_multivar(CONFIG_KEY, ".*") {
+        Err(e) if e.code() != git2::ErrorCode::NotFound => {
+            return Err(e.into())
+        }
+        _ => (),
+    }
+
+    for reference in favorites {
+        // a regex matching no existing value appends
+        cfg.set_multivar(CONFIG_KEY, "^$", reference)?;
+    }
+
+    Ok(())
+}
+
+/// references (`refs/heads/..` or `refs/remotes/..`) of the
+/// favorite branches. ones whose branch is missing are kept (a
+/// remote branch may be back with the next fetch), deleting or
+/// renaming a branch through gitui updates them
+pub fn get_favorite_branches(repo_path: &str) -> Result<Vec<String>> {
+    scope_time!("get_favorite_branches");
+
+    let repo = utils::repo(repo_path)?;
+
+    read_favorites(&local_config(&repo)?)
+}
+
+/// replaces the favorite `branch_ref` by `new_ref` (renamed) or
+/// drops it (`None`, deleted), nothing happens unless it is one
+pub(crate) fn replace_favorite_branch(
+    repo: &Repository,
+    branch_ref: &str,
+    new_ref: Option<&str>,
+) -> Result<()> {
+    let mut cfg = local_config(repo)?;
+    let favorites = read_favorites(&cfg)?;
+
+    if !favorites.iter().any(|f| f == branch_ref) {
+        return Ok(());
+    }
+
+    let favorites = favorites
+        .into_iter()
+        .filter_map(|f| {
+            if f == branch_ref {
+                new_ref.map(String::from)
+            } else {
+                Some(f)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    write_favorites(&mut cfg, &favorites)
+}
+
+/// adds or removes `branch_ref` from the favorites,
+/// returns whether it is a favorite now
+pub fn toggle_favorite_branch(
+    repo_path: &str,
+    branch_ref: &str,
+) -> Result<bool> {
+    scope_time!("toggle_favorite_branch");
+
+    let repo = utils::repo(repo_path)?;
+    let mut cfg = local_config(&repo)?;
+
+    let mut favorites = read_favorites(&cfg)?;
+    let favorite = if favorites.iter().any(|f| f == branch_ref) {
+        favorites.retain(|f| f != branch_ref);
+        false
+    } else {
+        favorites.push(branch_ref.to_string());
+        true
+    };
+
+    write_favorites(&mut cfg, &favorites)?;
+
+    Ok(favorite)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sync::{
+        branch::{
+            checkout_branch, create_branch, delete_branch,
+            rename::rename_branch,
+        },
+        tests::repo_init,
+    };
+
+    #[test]
+    fn test_toggle_favorite() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        create_branch(repo_path, "b1").unwrap();
+        create_branch(repo_path, "b.2").unwrap();
+
+        assert!(get_favorite_branches(repo_path).unwrap().is_empty());
+
+        assert!(toggle_favorite_branch(repo_path, "refs/heads/b1")
+            .unwrap());
+        assert!(toggle_favorite_branch(repo_path, "refs/heads/b.2")
+            .unwrap());
+        assert_eq!(
+            get_favorite_branches(repo_path).unwrap(),
+            vec![
+                String::from("refs/heads/b1"),
+                String::from("refs/heads/b.2")
+            ]
+        );
+
+        assert!(!toggle_favorite_branch(repo_path, "refs/heads/b1")
+            .unwrap());
+        assert_eq!(
+            get_favorite_branches(repo_path).unwrap(),
+            vec![String::from("refs/heads/b.2")]
+        );
+    }
+
+    #[test]
+    fn test_deleted_favorite_dropped() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        create_branch(repo_path, "b1").unwrap();
+        toggle_favorite_branch(repo_path, "refs/heads/b1").unwrap();
+
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+        delete_branch(repo_path, "refs/heads/b1").unwrap();
+
+        assert!(get_favorite_branches(repo_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_missing_favorite_kept() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let head = repo.head().unwrap().target().unwrap();
+        repo.reference("refs/remotes/origin/b1", head, false, "")
+            .unwrap();
+        toggle_favorite_branch(repo_path, "refs/remotes/origin/b1")
+            .unwrap();
+
+        // like a fetch pruning it
+        repo.find_reference("refs/remotes/origin/b1")
+            .unwrap()
+            .delete()
+            .unwrap();
+
+        assert_eq!(
+            get_favorite_branches(repo_path).unwrap(),
+            vec![String::from("refs/remotes/origin/b1")]
+        );
+    }
+
+    #[test]
+    fn test_renamed_favorite() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        create_branch(repo_path, "b1").unwrap();
+        toggle_favorite_branch(repo_path, "refs/heads/b1").unwrap();
+
+        rename_branch(repo_path, "refs/heads/b1", "b2").unwrap();
+
+        assert_eq!(
+            get_favorite_branches(repo_path).unwrap(),
+            vec![String::from("refs/heads/b2")]
+        );
+    }
+}
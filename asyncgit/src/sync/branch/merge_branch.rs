@@ -0,0 +1,225 @@
+//! merging any branch into `HEAD`
+
+use crate::{
+    error::{Error, Result},
+    sync::{commit::signature_allow_undefined_name, utils, CommitId},
+};
+use git2::{build::CheckoutBuilder, Reference};
+use scopetime::scope_time;
+
+/// how `merge_branch` combines the branch with `HEAD`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MergeStrategy {
+    /// `--ff-only`: move `HEAD` forward or fail
+    FastForwardOnly,
+    /// `--no-ff`: always create a merge commit
+    NoFastForward,
+}
+
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MergeResult {
+    /// the branch is contained in `HEAD` already
+    UpToDate,
+    ///
+    FastForward(CommitId),
+    /// the new merge commit
+    Merged(CommitId),
+    /// the repo is left in the merge state with conflicts to resolve
+    Conflict,
+}
+
+/// merges the branch `branch_ref` (local or remote) into `HEAD`
+pub fn merge_branch(
+    repo_path: &str,
+    branch_ref: &str,
+    strategy: MergeStrategy,
+) -> Result<MergeResult> {
+    scope_time!("merge_branch");
+
+    let repo = utils::repo(repo_path)?;
+
+    let reference = repo.find_reference(branch_ref)?;
+    let commit = reference.peel_to_commit()?;
+    let annotated = repo.reference_to_annotated_commit(&reference)?;
+
+    let (analysis, _) = repo.merge_analysis(&[&annotated])?;
+
+    if analysis.is_up_to_date() {
+        return Ok(MergeResult::UpToDate);
+    }
+
+    //TODO: support merge on unborn
+    if analysis.is_unborn() {
+        return Err(Error::Generic("head is unborn".into()));
+    }
+
+    if utils::has_uncommitted_changes(&repo)? {
+        return Err(Error::UncommittedChanges);
+    }
+
+    let name = reference.shorthand().unwrap_or(branch_ref);
+
+    match strategy {
+        MergeStrategy::FastForwardOnly => {
+            if !analysis.is_fast_forward() {
+                return Err(Error::Generic(
+                    "fast forward merge not possible".into(),
+                ));
+            }
+
+            repo.checkout_tree(
+                commit.as_object(),
+                Some(CheckoutBuilder::new().safe()),
+            )?;
+            repo.head()?.set_target(
+                commit.id(),
+                &format!("merge {}: Fast-forward", name),
+            )?;
+
+            Ok(MergeResult::FastForward(commit.id().into()))
+        }
+        MergeStrategy::NoFastForward => {
+            repo.merge(&[&annotated], None, None)?;
+
+            if repo.index()?.has_conflicts() {
+                return Ok(MergeResult::Conflict);
+            }
+
+            let signature = signature_allow_undefined_name(&repo)?;
+            let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+            let head = repo.head()?.peel_to_commit()?;
+
+            let id = repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                &merge_message(&reference, name),
+                &tree,
+                &[&head, &commit],
+            )?;
+
+            repo.cleanup_state()?;
+
+            Ok(MergeResult::Merged(id.into()))
+        }
+    }
+}
+
+/// like git's default merge message
+fn merge_message(reference: &Reference, name: &str) -> String {
+    if reference.is_remote() {
+        format!("Merge remote-tracking branch '{}'", name)
+    } else {
+        format!("Merge branch '{}'", name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, create_branch, repo_state,
+        tests::{repo_init, write_commit_file},
+        RepoState,
+    };
+    use git2::Repository;
+
+    /// `feature` one commit ahead of `master`, `HEAD` on master
+    fn feature_ahead(repo: &Repository, repo_path: &str) -> CommitId {
+        write_commit_file(repo, "a", "a", "a");
+        create_branch(repo_path, "feature").unwrap();
+        let id = write_commit_file(repo, "b", "b", "b");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        id
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let feature = feature_ahead(&repo, repo_path);
+
+        let res = merge_branch(
+            repo_path,
+            "refs/heads/feature",
+            MergeStrategy::FastForwardOnly,
+        )
+        .unwrap();
+
+        assert_eq!(res, MergeResult::FastForward(feature));
+        assert_eq!(utils::get_head(repo_path).unwrap(), feature);
+        assert!(root.join("b").exists());
+
+        assert_eq!(
+            merge_branch(
+                repo_path,
+                "refs/heads/feature",
+                MergeStrategy::NoFastForward,
+            )
+            .unwrap(),
+            MergeResult::UpToDate
+        );
+    }
+
+    #[test]
+    fn test_no_fast_forward() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let feature = feature_ahead(&repo, repo_path);
+
+        let res = merge_branch(
+            repo_path,
+            "refs/heads/feature",
+            MergeStrategy::NoFastForward,
+        )
+        .unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(res, MergeResult::Merged(head.id().into()));
+        assert_eq!(head.parent_count(), 2);
+        assert_eq!(
+            CommitId::from(head.parent_id(1).unwrap()),
+            feature
+        );
+        assert_eq!(head.message(), Some("Merge branch 'feature'"));
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+    }
+
+    #[test]
+    fn test_diverged() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        feature_ahead(&repo, repo_path);
+        write_commit_file(&repo, "b", "other", "c");
+
+        assert!(merge_branch(
+            repo_path,
+            "refs/heads/feature",
+            MergeStrategy::FastForwardOnly,
+        )
+        .is_err());
+
+        assert_eq!(
+            merge_branch(
+                repo_path,
+                "refs/heads/feature",
+                MergeStrategy::NoFastForward,
+            )
+            .unwrap(),
+            MergeResult::Conflict
+        );
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Merge);
+        assert!(utils::repo(repo_path)
+            .unwrap()
+            .index()
+            .unwrap()
+            .has_conflicts());
+    }
+}
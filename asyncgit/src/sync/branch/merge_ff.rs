@@ -7,7 +7,8 @@ use crate::{
 };
 use scopetime::scope_time;
 
-///
+/// fails with `Error::NotFastForward` if the branch and its upstream
+/// diverged
 pub fn branch_merge_upstream_fastforward(
     repo_path: &str,
     branch: &str,
@@ -28,9 +29,11 @@ pub fn branch_merge_upstream_fastforward(
     let (analysis, _) = repo.merge_analysis(&[&annotated])?;
 
     if !analysis.is_fast_forward() {
-        return Err(Error::Generic(
-            "fast forward merge not possible".into(),
-        ));
+        let (ahead, behind) = repo.graph_ahead_behind(
+            repo.head()?.peel_to_commit()?.id(),
+            upstream_commit.id(),
+        )?;
+        return Err(Error::NotFastForward(ahead, behind));
     }
 
     //TODO: support merge on unborn
@@ -46,7 +49,7 @@ pub fn branch_merge_upstream_fastforward(
 }
 
 #[cfg(test)]
-pub mod test {
+mod test {
     use super::*;
     use crate::sync::{
         remotes::{fetch_origin, push::push},
@@ -135,4 +138,27 @@ pub mod test {
         assert_eq!(commits[1], commit1);
         assert_eq!(commits[0], commit2);
     }
+    #[test]
+    fn test_merge_fastforward_diverged() {
+        let (r1_dir, _repo) = repo_init_bare().unwrap();
+        let r1_path = r1_dir.path().to_str().unwrap();
+
+        let (clone1_dir, clone1) = repo_clone(r1_path).unwrap();
+        let (clone2_dir, clone2) = repo_clone(r1_path).unwrap();
+        let clone1_path = clone1_dir.path().to_str().unwrap();
+        let clone2_path = clone2_dir.path().to_str().unwrap();
+
+        write_commit_file(&clone1, "test.txt", "test", "commit1");
+        push(clone1_path, "origin", "master", false, None, None)
+            .unwrap();
+
+        write_commit_file(&clone2, "test2.txt", "test", "commit2");
+        fetch_origin(clone2_path, "master", None, None).unwrap();
+
+        let res =
+            branch_merge_upstream_fastforward(clone2_path, "master");
+
+        assert!(matches!(res, Err(Error::NotFastForward(1, 1))));
+        assert_eq!(get_commit_ids(&clone2, 10).len(), 1);
+    }
 }
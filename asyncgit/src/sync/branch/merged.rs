@@ -0,0 +1,84 @@
+//! branches already merged into another one
+
+use crate::{error::Result, sync::utils};
+use git2::BranchType;
+use scopetime::scope_time;
+
+/// references of the local branches whose top commit is in the
+/// history of `target` (a branch reference), like
+/// `git branch --merged <target>`. leaves out `target` itself and
+/// the checked out branch, which cannot be deleted
+pub fn branches_merged_into(
+    repo_path: &str,
+    target: &str,
+) -> Result<Vec<String>> {
+    scope_time!("branches_merged_into");
+
+    let repo = utils::repo(repo_path)?;
+    let target_id =
+        repo.find_reference(target)?.peel_to_commit()?.id();
+
+    let mut merged = Vec::new();
+    for b in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = b?;
+        let reference = branch.get();
+
+        let name = match reference.name() {
+            Some(name) if name != target && !branch.is_head() => {
+                name.to_string()
+            }
+            _ => continue,
+        };
+
+        let id = reference.peel_to_commit()?.id();
+        if id == target_id
+            || repo.graph_descendant_of(target_id, id)?
+        {
+            merged.push(name);
+        }
+    }
+
+    merged.sort();
+
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+    use super::branches_merged_into;
+    use crate::sync::tests::{repo_init, write_commit_file};
+
+    #[test]
+    fn test_merged_branches() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        create_branch(repo_path, "behind").unwrap();
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit_file(&repo, "a.txt", "a", "a");
+
+        // same commit as master
+        create_branch(repo_path, "same").unwrap();
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+        create_branch(repo_path, "ahead").unwrap();
+        write_commit_file(&repo, "b.txt", "b", "b");
+
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+        assert_eq!(
+            branches_merged_into(repo_path, "refs/heads/master")
+                .unwrap(),
+            vec!["refs/heads/behind", "refs/heads/same"]
+        );
+
+        // the checked out branch is left out
+        assert_eq!(
+            branches_merged_into(repo_path, "refs/heads/ahead")
+                .unwrap(),
+            vec!["refs/heads/behind", "refs/heads/same"]
+        );
+    }
+}
@@ -1,9 +1,16 @@
 //! branch functions
 
+pub mod checkout;
+pub mod description;
+pub mod favorites;
+pub mod merge_branch;
 pub mod merge_commit;
 pub mod merge_ff;
 pub mod merge_rebase;
+pub mod merged;
 pub mod rename;
+pub mod search;
+pub mod upstream_diff;
 
 use super::{
     remotes::get_default_remote_in_repo, utils::bytes2string,
@@ -12,7 +19,7 @@ use crate::{
     error::{Error, Result},
     sync::{utils, CommitId},
 };
-use git2::{BranchType, Repository};
+use git2::{build::CheckoutBuilder, Branch, BranchType, Repository};
 use scopetime::scope_time;
 use utils::get_head_repo;
 
@@ -45,7 +52,7 @@ pub(crate) fn get_branch_name_repo(
 }
 
 ///
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LocalBranch {
     ///
     pub is_head: bool,
@@ -53,10 +60,12 @@ pub struct LocalBranch {
     pub has_upstream: bool,
     ///
     pub remote: Option<String>,
+    /// only filled by `get_branches_info`
+    pub upstream_compare: Option<BranchCompare>,
 }
 
 ///
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum BranchDetails {
     ///
     Local(LocalBranch),
@@ -65,7 +74,7 @@ pub enum BranchDetails {
 }
 
 ///
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BranchInfo {
     ///
     pub name: String,
@@ -75,6 +84,8 @@ pub struct BranchInfo {
     pub top_commit_message: String,
     ///
     pub top_commit: CommitId,
+    /// commit time of `top_commit` (secs since epoch)
+    pub top_commit_time: i64,
     ///
     pub details: BranchDetails,
 }
@@ -91,7 +102,9 @@ impl BranchInfo {
 }
 
 /// returns a list of `BranchInfo` with a simple summary on each branch
-/// `local` filters for local branches otherwise remote branches will be returned
+/// `local` filters for local branches otherwise remote branches will be returned.
+/// local branches with an upstream are compared to it, which walks
+/// their history (see `AsyncBranches`)
 pub fn get_branches_info(
     repo_path: &str,
     local: bool,
@@ -107,40 +120,23 @@ pub fn get_branches_info(
     let repo = utils::repo(repo_path)?;
     let mut branches_for_display: Vec<BranchInfo> = repo
         .branches(Some(filter))?
-        .map(|b| {
-            let branch = b?.0;
-            let top_commit = branch.get().peel_to_commit()?;
-            let reference = bytes2string(branch.get().name_bytes())?;
-            let upstream = branch.upstream();
-
-            let remote = repo
-                .branch_upstream_remote(&reference)
-                .ok()
-                .as_ref()
-                .and_then(|buf| buf.as_str())
-                .map(String::from);
-
-            let details = if local {
-                BranchDetails::Local(LocalBranch {
-                    is_head: branch.is_head(),
-                    has_upstream: upstream.is_ok(),
-                    remote,
+        .filter_map(|b| {
+            b.map_err(Error::from)
+                .and_then(|(b, _)| {
+                    let mut info = branch_info(&repo, &b, local)?;
+                    if let (
+                        BranchDetails::Local(details),
+                        Ok(upstream),
+                    ) = (&mut info.details, b.upstream())
+                    {
+                        details.upstream_compare =
+                            compare_branches(&repo, &b, &upstream)
+                                .ok();
+                    }
+                    Ok(info)
                 })
-            } else {
-                BranchDetails::Remote
-            };
-
-            Ok(BranchInfo {
-                name: bytes2string(branch.name_bytes()?)?,
-                reference,
-                top_commit_message: bytes2string(
-                    top_commit.summary_bytes().unwrap_or_default(),
-                )?,
-                top_commit: top_commit.id().into(),
-                details,
-            })
+                .ok()
         })
-        .filter_map(Result::ok)
         .collect();
 
     branches_for_display.sort_by(|a, b| a.name.cmp(&b.name));
@@ -148,8 +144,47 @@ pub fn get_branches_info(
     Ok(branches_for_display)
 }
 
+pub(crate) fn branch_info(
+    repo: &Repository,
+    branch: &Branch,
+    local: bool,
+) -> Result<BranchInfo> {
+    let top_commit = branch.get().peel_to_commit()?;
+    let reference = bytes2string(branch.get().name_bytes())?;
+    let upstream = branch.upstream();
+
+    let remote = repo
+        .branch_upstream_remote(&reference)
+        .ok()
+        .as_ref()
+        .and_then(|buf| buf.as_str())
+        .map(String::from);
+
+    let details = if local {
+        BranchDetails::Local(LocalBranch {
+            is_head: branch.is_head(),
+            has_upstream: upstream.is_ok(),
+            remote,
+            upstream_compare: None,
+        })
+    } else {
+        BranchDetails::Remote
+    };
+
+    Ok(BranchInfo {
+        name: bytes2string(branch.name_bytes()?)?,
+        reference,
+        top_commit_message: bytes2string(
+            top_commit.summary_bytes().unwrap_or_default(),
+        )?,
+        top_commit: top_commit.id().into(),
+        top_commit_time: top_commit.time().seconds(),
+        details,
+    })
+}
+
 ///
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct BranchCompare {
     ///
     pub ahead: usize,
@@ -176,6 +211,38 @@ pub(crate) fn branch_set_upstream(
     Ok(())
 }
 
+/// makes `remote/remote_branch` the upstream of the local `branch`
+pub fn branch_set_upstream_to(
+    repo_path: &str,
+    branch: &str,
+    remote: &str,
+    remote_branch: &str,
+) -> Result<()> {
+    scope_time!("branch_set_upstream_to");
+
+    set_upstream(
+        repo_path,
+        branch,
+        Some(&format!("{}/{}", remote, remote_branch)),
+    )
+}
+
+/// makes the remote tracking branch `upstream` (like `origin/master`)
+/// the upstream of the local `branch`, `None` unsets it
+pub fn set_upstream(
+    repo_path: &str,
+    branch: &str,
+    upstream: Option<&str>,
+) -> Result<()> {
+    scope_time!("set_upstream");
+
+    let repo = utils::repo(repo_path)?;
+    let mut branch = repo.find_branch(branch, BranchType::Local)?;
+    branch.set_upstream(upstream)?;
+
+    Ok(())
+}
+
 /// returns remote of the upstream tracking branch for `branch`
 pub fn get_branch_remote(
     repo_path: &str,
@@ -192,6 +259,34 @@ pub fn get_branch_remote(
     }
 }
 
+/// how a pull combines the fetched upstream with the local branch
+/// once a fast-forward is not possible
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PullStrategy {
+    /// merge commit
+    Merge,
+    /// local commits are rebased onto the upstream
+    Rebase,
+    /// `--ff-only`: refuse
+    FastForwardOnly,
+}
+
+/// `pull.ff = only` wins over `pull.rebase`
+pub fn config_pull_strategy(repo_path: &str) -> Result<PullStrategy> {
+    let repo = utils::repo(repo_path)?;
+    let config = repo.config()?;
+
+    if config.get_string("pull.ff").ok().as_deref() == Some("only") {
+        return Ok(PullStrategy::FastForwardOnly);
+    }
+
+    Ok(if config_is_pull_rebase(repo_path)? {
+        PullStrategy::Rebase
+    } else {
+        PullStrategy::Merge
+    })
+}
+
 /// returns whether the pull merge strategy is set to rebase
 pub fn config_is_pull_rebase(repo_path: &str) -> Result<bool> {
     let repo = utils::repo(repo_path)?;
@@ -219,11 +314,16 @@ pub fn branch_compare_upstream(
 
     let upstream = branch.upstream()?;
 
-    let branch_commit =
-        branch.into_reference().peel_to_commit()?.id();
+    compare_branches(&repo, &branch, &upstream)
+}
 
-    let upstream_commit =
-        upstream.into_reference().peel_to_commit()?.id();
+fn compare_branches(
+    repo: &Repository,
+    branch: &Branch,
+    upstream: &Branch,
+) -> Result<BranchCompare> {
+    let branch_commit = branch.get().peel_to_commit()?.id();
+    let upstream_commit = upstream.get().peel_to_commit()?.id();
 
     let (ahead, behind) =
         repo.graph_ahead_behind(branch_commit, upstream_commit)?;
@@ -231,40 +331,50 @@ pub fn branch_compare_upstream(
     Ok(BranchCompare { ahead, behind })
 }
 
-/// Modify HEAD to point to a branch then checkout head, does not work if there are uncommitted changes
+/// Checkout the branch and point HEAD to it, local changes are kept
+/// unless they conflict with the branch (see `checkout_conflicts`)
 pub fn checkout_branch(
     repo_path: &str,
     branch_ref: &str,
 ) -> Result<()> {
     scope_time!("checkout_branch");
 
-    // This defaults to a safe checkout, so don't delete anything that
-    // hasn't been committed or stashed, in this case it will Err
+    let conflicts =
+        checkout::checkout_conflicts(repo_path, branch_ref)?;
+    if !conflicts.is_empty() {
+        return Err(Error::CheckoutConflicts(conflicts));
+    }
+
+    checkout::checkout_branch_with(
+        repo_path,
+        branch_ref,
+        CheckoutBuilder::new().safe(),
+    )
+}
+
+/// detaches `HEAD` at `id`, refuses to touch uncommitted changes
+pub fn checkout_commit(repo_path: &str, id: CommitId) -> Result<()> {
+    scope_time!("checkout_commit");
+
     let repo = utils::repo(repo_path)?;
-    let cur_ref = repo.head()?;
-    let statuses = repo.statuses(Some(
-        git2::StatusOptions::new().include_ignored(false),
-    ))?;
-
-    if statuses.is_empty() {
-        repo.set_head(branch_ref)?;
-
-        if let Err(e) = repo.checkout_head(Some(
-            git2::build::CheckoutBuilder::new().force(),
-        )) {
-            // This is safe beacuse cur_ref was just found
-            repo.set_head(
-                bytes2string(cur_ref.name_bytes())?.as_str(),
-            )?;
-            return Err(Error::Git(e));
-        }
-        Ok(())
-    } else {
-        Err(Error::UncommittedChanges)
+
+    if utils::has_uncommitted_changes(&repo)? {
+        return Err(Error::UncommittedChanges);
     }
+
+    let commit = repo.find_commit(id.into())?;
+    repo.checkout_tree(
+        commit.as_object(),
+        Some(CheckoutBuilder::new().safe()),
+    )
+    .map_err(|e| utils::map_missing_object(repo_path, e.into()))?;
+    repo.set_head_detached(id.into())?;
+
+    Ok(())
 }
 
-///
+/// creates a local branch of the name the branch has on its remote,
+/// tracking it, and checks that out
 pub fn checkout_remote_branch(
     repo_path: &str,
     branch: &BranchInfo,
@@ -283,11 +393,15 @@ pub fn checkout_remote_branch(
         return Err(Error::UncommittedChanges);
     }
 
-    let name = if let Some(pos) = branch.name.rfind('/') {
-        branch.name[pos..].to_string()
-    } else {
-        branch.name.clone()
-    };
+    let (_, name) =
+        get_remote_branch_parts(repo_path, &branch.reference)?;
+
+    if repo.find_branch(&name, BranchType::Local).is_ok() {
+        return Err(Error::Generic(format!(
+            "local branch '{}' exists already",
+            name
+        )));
+    }
 
     let commit = repo.find_commit(branch.top_commit.into())?;
     let mut new_branch = repo.branch(&name, &commit, false)?;
@@ -303,11 +417,34 @@ pub fn checkout_remote_branch(
     )) {
         // This is safe beacuse cur_ref was just found
         repo.set_head(bytes2string(cur_ref.name_bytes())?.as_str())?;
-        return Err(Error::Git(e));
+        return Err(utils::map_missing_object(repo_path, e.into()));
     }
     Ok(())
 }
 
+/// splits the remote tracking branch `branch_ref` into the remote
+/// and the name of the branch on it
+pub fn get_remote_branch_parts(
+    repo_path: &str,
+    branch_ref: &str,
+) -> Result<(String, String)> {
+    scope_time!("get_remote_branch_parts");
+
+    let repo = utils::repo(repo_path)?;
+    let remote =
+        bytes2string(repo.branch_remote_name(branch_ref)?.as_ref())?;
+    let branch = branch_ref
+        .strip_prefix(&format!("refs/remotes/{}/", remote))
+        .ok_or_else(|| {
+            Error::Generic(format!(
+                "'{}' is no remote tracking branch",
+                branch_ref
+            ))
+        })?;
+
+    Ok((remote, branch.to_string()))
+}
+
 /// The user must not be on the branch for the branch to be deleted
 pub fn delete_branch(
     repo_path: &str,
@@ -323,6 +460,9 @@ pub fn delete_branch(
     } else {
         return Err(Error::Generic("You cannot be on the branch you want to delete, switch branch, then delete this branch".to_string()));
     }
+
+    favorites::replace_favorite_branch(&repo, branch_ref, None)?;
+
     Ok(())
 }
 
@@ -343,6 +483,23 @@ pub fn create_branch(repo_path: &str, name: &str) -> Result<()> {
     Ok(())
 }
 
+/// creates the branch `name` at `id`, unlike `create_branch` it is
+/// not checked out
+pub fn create_branch_at(
+    repo_path: &str,
+    name: &str,
+    id: CommitId,
+) -> Result<()> {
+    scope_time!("create_branch_at");
+
+    let repo = utils::repo(repo_path)?;
+
+    let commit = repo.find_commit(id.into())?;
+    repo.branch(name, &commit, false)?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests_branch_name {
     use super::*;
@@ -376,7 +533,7 @@ mod tests_branch_name {
 #[cfg(test)]
 mod tests_create_branch {
     use super::*;
-    use crate::sync::tests::repo_init;
+    use crate::sync::tests::{repo_init, write_commit_file};
 
     #[test]
     fn test_smoke() {
@@ -391,6 +548,31 @@ mod tests_create_branch {
             "branch1"
         );
     }
+
+    #[test]
+    fn test_create_at() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = write_commit_file(&repo, "a.txt", "a", "c1");
+        write_commit_file(&repo, "b.txt", "b", "c2");
+
+        create_branch_at(repo_path, "old", first).unwrap();
+
+        assert_eq!(
+            get_branch_name(repo_path).unwrap().as_str(),
+            "master"
+        );
+        assert_eq!(
+            repo.find_branch("old", BranchType::Local)
+                .unwrap()
+                .get()
+                .target(),
+            Some(first.into())
+        );
+        assert!(create_branch_at(repo_path, "old", first).is_err());
+    }
 }
 
 #[cfg(test)]
@@ -412,6 +594,39 @@ mod tests_branch_compare {
     }
 }
 
+#[cfg(test)]
+mod tests_pull_strategy {
+    use super::*;
+    use crate::sync::tests::repo_init;
+
+    #[test]
+    fn test_smoke() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert_eq!(
+            config_pull_strategy(repo_path).unwrap(),
+            PullStrategy::Merge
+        );
+
+        let mut config = repo.config().unwrap();
+        config.set_str("pull.rebase", "true").unwrap();
+
+        assert_eq!(
+            config_pull_strategy(repo_path).unwrap(),
+            PullStrategy::Rebase
+        );
+
+        config.set_str("pull.ff", "only").unwrap();
+
+        assert_eq!(
+            config_pull_strategy(repo_path).unwrap(),
+            PullStrategy::FastForwardOnly
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests_branches {
     use super::*;
@@ -699,6 +914,20 @@ mod test_remote_branches {
         assert_eq!(&branches[0].name, "origin/HEAD");
         assert_eq!(&branches[1].name, "origin/foo");
         assert_eq!(&branches[2].name, "origin/master");
+
+        assert_eq!(
+            get_remote_branch_parts(
+                clone2_dir,
+                &branches[1].reference
+            )
+            .unwrap(),
+            (String::from("origin"), String::from("foo"))
+        );
+        assert!(get_remote_branch_parts(
+            clone2_dir,
+            "refs/heads/master"
+        )
+        .is_err());
     }
 
     #[test]
@@ -742,4 +971,109 @@ mod test_remote_branches {
 
         assert_eq!(&get_branch_name(clone2_dir).unwrap(), "foo");
     }
+
+    #[test]
+    fn test_checkout_remote_branch_nested_name() {
+        let (r1_dir, _repo) = repo_init_bare().unwrap();
+
+        let (clone1_dir, clone1) =
+            repo_clone(r1_dir.path().to_str().unwrap()).unwrap();
+        let clone1_dir = clone1_dir.path().to_str().unwrap();
+
+        write_commit_file(&clone1, "test.txt", "test", "commit1");
+        push(clone1_dir, "origin", "master", false, None, None)
+            .unwrap();
+        create_branch(clone1_dir, "feature/foo").unwrap();
+        push(clone1_dir, "origin", "feature/foo", false, None, None)
+            .unwrap();
+
+        let (clone2_dir, clone2) =
+            repo_clone(r1_dir.path().to_str().unwrap()).unwrap();
+        let clone2_dir = clone2_dir.path().to_str().unwrap();
+
+        let branches = get_branches_info(clone2_dir, false).unwrap();
+        assert_eq!(&branches[1].name, "origin/feature/foo");
+
+        checkout_remote_branch(clone2_dir, &branches[1]).unwrap();
+
+        assert_eq!(
+            &get_branch_name(clone2_dir).unwrap(),
+            "feature/foo"
+        );
+        assert_eq!(
+            clone2
+                .find_branch("feature/foo", BranchType::Local)
+                .unwrap()
+                .upstream()
+                .unwrap()
+                .name()
+                .unwrap(),
+            Some("origin/feature/foo")
+        );
+
+        // a second checkout does not clobber the local branch
+        assert!(
+            checkout_remote_branch(clone2_dir, &branches[1]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_set_upstream() {
+        let (r1_dir, _repo) = repo_init_bare().unwrap();
+
+        let (clone1_dir, clone1) =
+            repo_clone(r1_dir.path().to_str().unwrap()).unwrap();
+        let clone1_dir = clone1_dir.path().to_str().unwrap();
+
+        write_commit_file(&clone1, "test.txt", "test", "commit1");
+        push(clone1_dir, "origin", "master", false, None, None)
+            .unwrap();
+
+        // created outside gitui: no upstream
+        create_branch(clone1_dir, "foo").unwrap();
+        assert_eq!(
+            get_branch_remote(clone1_dir, "foo").unwrap(),
+            None
+        );
+
+        set_upstream(clone1_dir, "foo", Some("origin/master"))
+            .unwrap();
+
+        assert_eq!(
+            get_branch_remote(clone1_dir, "foo").unwrap(),
+            Some(String::from("origin"))
+        );
+        write_commit_file(&clone1, "test.txt", "test2", "commit2");
+        assert_eq!(
+            branch_compare_upstream(clone1_dir, "foo").unwrap().ahead,
+            1
+        );
+
+        let branches = get_branches_info(clone1_dir, true).unwrap();
+        let foo = branches.iter().find(|b| b.name == "foo").unwrap();
+        assert_eq!(
+            foo.local_details().unwrap().upstream_compare,
+            Some(BranchCompare {
+                ahead: 1,
+                behind: 0
+            })
+        );
+        assert_eq!(
+            foo.top_commit_time,
+            clone1
+                .find_commit(foo.top_commit.into())
+                .unwrap()
+                .time()
+                .seconds()
+        );
+
+        set_upstream(clone1_dir, "foo", None).unwrap();
+
+        assert_eq!(
+            get_branch_remote(clone1_dir, "foo").unwrap(),
+            None
+        );
+        assert!(set_upstream(clone1_dir, "foo", Some("origin/nope"))
+            .is_err());
+    }
 }
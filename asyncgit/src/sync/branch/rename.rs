@@ -1,5 +1,6 @@
 //! renaming of branches
 
+use super::favorites::replace_favorite_branch;
 use crate::{error::Result, sync::utils};
 use scopetime::scope_time;
 
@@ -14,7 +15,11 @@ pub fn rename_branch(
     let repo = utils::repo(repo_path)?;
     let branch_as_ref = repo.find_reference(branch_ref)?;
     let mut branch = git2::Branch::wrap(branch_as_ref);
-    branch.rename(new_name, true)?;
+    let renamed = branch.rename(new_name, true)?;
+
+    if let Some(new_ref) = renamed.get().name() {
+        replace_favorite_branch(&repo, branch_ref, Some(new_ref))?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,226 @@
+//! searching commits across all branches
+
+use super::BranchInfo;
+use crate::{
+    error::Result,
+    sync::{get_refs, utils, CommitId, CommitInfo, LogWalker},
+};
+use git2::Commit;
+use scopetime::scope_time;
+
+/// commits scanned between two progress reports
+const BATCH_SIZE: usize = 1000;
+
+///
+#[derive(Debug)]
+pub struct BranchSearchMatch {
+    ///
+    pub commit: CommitInfo,
+    /// indices into `BranchSearchResult::branches` containing the commit
+    pub branches: Vec<usize>,
+}
+
+///
+#[derive(Debug)]
+pub struct BranchSearchResult {
+    ///
+    pub query: String,
+    /// local and remote branches that were searched
+    pub branches: Vec<BranchInfo>,
+    /// every matching commit only once, newest first
+    pub matches: Vec<BranchSearchMatch>,
+    /// search stopped after `limit` matches
+    pub limit_reached: bool,
+}
+
+/// finds commits reachable from any local or remote branch whose
+/// message, author or hash matches `query` (case insensitive),
+/// `progress` is called with the number of commits scanned so far
+pub fn search_branches(
+    repo_path: &str,
+    query: &str,
+    limit: usize,
+    progress: &dyn Fn(usize),
+) -> Result<BranchSearchResult> {
+    scope_time!("search_branches");
+
+    let refs = get_refs(repo_path)?;
+    let mut branches = refs.local_branches;
+    branches.extend(refs.remote_branches);
+
+    let repo = utils::repo(repo_path)?;
+    let query_lower = query.to_lowercase();
+
+    let tips = branches.iter().map(|b| b.top_commit).collect();
+    let mut walker = LogWalker::new(&repo).tips(tips);
+
+    let mut matches = Vec::new();
+    let mut limit_reached = false;
+    let mut scanned = 0_usize;
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+
+    'walk: loop {
+        batch.clear();
+        if walker.read(&mut batch, BATCH_SIZE)? == 0 {
+            break;
+        }
+
+        for id in &batch {
+            let commit = repo.find_commit((*id).into())?;
+
+            if commit_matches(&commit, &query_lower) {
+                if matches.len() == limit {
+                    limit_reached = true;
+                    break 'walk;
+                }
+
+                matches.push(BranchSearchMatch {
+                    commit: CommitInfo {
+                        message: commit
+                            .summary()
+                            .unwrap_or_default()
+                            .to_string(),
+                        time: commit.time().seconds(),
+                        time_offset: commit.time().offset_minutes(),
+                        author: commit
+                            .author()
+                            .name()
+                            .unwrap_or_default()
+                            .to_string(),
+                        id: *id,
+                        refs: Vec::new(),
+                    },
+                    branches: Vec::new(),
+                });
+            }
+        }
+
+        scanned += batch.len();
+        progress(scanned);
+    }
+
+    for m in &mut matches {
+        m.branches =
+            containing_branches(&repo, &branches, m.commit.id);
+    }
+
+    Ok(BranchSearchResult {
+        query: query.to_string(),
+        branches,
+        matches,
+        limit_reached,
+    })
+}
+
+fn commit_matches(commit: &Commit, query_lower: &str) -> bool {
+    let message = String::from_utf8_lossy(commit.message_bytes());
+    let author = commit.author();
+
+    commit.id().to_string().starts_with(query_lower)
+        || message.to_lowercase().contains(query_lower)
+        || String::from_utf8_lossy(author.name_bytes())
+            .to_lowercase()
+            .contains(query_lower)
+        || String::from_utf8_lossy(author.email_bytes())
+            .to_lowercase()
+            .contains(query_lower)
+}
+
+fn containing_branches(
+    repo: &git2::Repository,
+    branches: &[BranchInfo],
+    id: CommitId,
+) -> Vec<usize> {
+    branches
+        .iter()
+        .enumerate()
+        .filter(|(_, b)| {
+            b.top_commit == id
+                || repo
+                    .graph_descendant_of(
+                        b.top_commit.into(),
+                        id.into(),
+                    )
+                    .unwrap_or_default()
+        })
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::search_branches;
+    use crate::sync::{
+        checkout_branch, create_branch,
+        tests::{repo_init, write_commit_file},
+    };
+
+    #[test]
+    fn test_search_branches() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let shared = write_commit_file(&repo, "a", "a", "shared fix");
+
+        create_branch(repo_path, "other").unwrap();
+        let other = write_commit_file(&repo, "b", "b", "other fix");
+
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit_file(&repo, "c", "c", "unrelated");
+
+        let res =
+            search_branches(repo_path, "FIX", 10, &|_| ()).unwrap();
+
+        assert_eq!(res.matches.len(), 2);
+        assert!(!res.limit_reached);
+
+        let names = |idx: &[usize]| {
+            idx.iter()
+                .map(|i| res.branches[*i].name.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        let m_other = res
+            .matches
+            .iter()
+            .find(|m| m.commit.id == other)
+            .unwrap();
+        assert_eq!(names(&m_other.branches), vec!["other"]);
+
+        let m_shared = res
+            .matches
+            .iter()
+            .find(|m| m.commit.id == shared)
+            .unwrap();
+        assert_eq!(
+            names(&m_shared.branches),
+            vec!["master", "other"]
+        );
+    }
+
+    #[test]
+    fn test_search_limit_and_hash() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a", "a", "change 1");
+        let id = write_commit_file(&repo, "b", "b", "change 2");
+
+        let res =
+            search_branches(repo_path, "change", 1, &|_| ()).unwrap();
+        assert_eq!(res.matches.len(), 1);
+        assert!(res.limit_reached);
+
+        let res = search_branches(
+            repo_path,
+            &id.get_short_string(),
+            10,
+            &|_| (),
+        )
+        .unwrap();
+        assert_eq!(res.matches.len(), 1);
+        assert_eq!(res.matches[0].commit.id, id);
+    }
+}
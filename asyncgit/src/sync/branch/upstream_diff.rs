@@ -0,0 +1,144 @@
+//! everything a push would publish (`git diff @{upstream}...HEAD`)
+
+use crate::{
+    error::{Error, Result},
+    sync::{
+        commit_files::{diff_status_items, get_commits_diff},
+        utils, CommitId,
+    },
+    StatusItem,
+};
+use git2::BranchType;
+use scopetime::scope_time;
+
+///
+#[derive(Debug)]
+pub struct UpstreamDiff {
+    /// like `origin/master`
+    pub upstream: String,
+    /// where branch and upstream diverged, the diff starts here
+    pub merge_base: CommitId,
+    ///
+    pub head: CommitId,
+    /// local commits not in upstream
+    pub commits: usize,
+    ///
+    pub files: Vec<StatusItem>,
+    ///
+    pub insertions: usize,
+    ///
+    pub deletions: usize,
+}
+
+impl UpstreamDiff {
+    /// nothing to review: upstream contains all local commits
+    pub fn is_merged(&self) -> bool {
+        self.commits == 0
+    }
+}
+
+/// combined diff of all commits on `branch` that are not in its
+/// upstream, changes that only happened upstream are left out
+pub fn get_upstream_diff(
+    repo_path: &str,
+    branch: &str,
+) -> Result<UpstreamDiff> {
+    scope_time!("get_upstream_diff");
+
+    let repo = utils::repo(repo_path)?;
+
+    let local = repo.find_branch(branch, BranchType::Local)?;
+    let upstream = local.upstream().map_err(|_| {
+        Error::Generic(format!(
+            "branch '{}' has no upstream configured",
+            branch
+        ))
+    })?;
+
+    let upstream_name =
+        upstream.name()?.map(String::from).unwrap_or_default();
+
+    let head = local.get().peel_to_commit()?.id();
+    let upstream_head = upstream.get().peel_to_commit()?.id();
+
+    let merge_base = repo.merge_base(head, upstream_head)?;
+    let (commits, _) =
+        repo.graph_ahead_behind(head, upstream_head)?;
+
+    let diff = get_commits_diff(
+        &repo,
+        merge_base.into(),
+        head.into(),
+        None,
+        None,
+    )?;
+    let stats = diff.stats()?;
+
+    Ok(UpstreamDiff {
+        upstream: upstream_name,
+        merge_base: merge_base.into(),
+        head: head.into(),
+        commits,
+        files: diff_status_items(&diff)?,
+        insertions: stats.insertions(),
+        deletions: stats.deletions(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, create_branch,
+        tests::{repo_init, write_commit_file},
+    };
+
+    #[test]
+    fn test_no_upstream() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert!(get_upstream_diff(repo_path, "master").is_err());
+    }
+
+    #[test]
+    fn test_upstream_diff() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a.txt", "a", "a");
+        create_branch(repo_path, "feature").unwrap();
+
+        repo.find_branch("feature", BranchType::Local)
+            .unwrap()
+            .set_upstream(Some("master"))
+            .unwrap();
+
+        let res = get_upstream_diff(repo_path, "feature").unwrap();
+        assert!(res.is_merged());
+        assert!(res.files.is_empty());
+
+        write_commit_file(&repo, "a.txt", "a\nb\n", "feature 1");
+        write_commit_file(&repo, "c.txt", "c\n", "feature 2");
+
+        // only on upstream, not part of what we would push
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit_file(&repo, "d.txt", "d\n", "master");
+
+        let res = get_upstream_diff(repo_path, "feature").unwrap();
+
+        assert_eq!(res.upstream, "master");
+        assert_eq!(res.commits, 2);
+        assert_eq!(
+            res.files
+                .iter()
+                .map(|f| f.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a.txt", "c.txt"]
+        );
+        assert_eq!(res.insertions, 3);
+        assert_eq!(res.deletions, 1);
+    }
+}
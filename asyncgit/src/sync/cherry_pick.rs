@@ -0,0 +1,237 @@
+//! applying the changes of a commit onto HEAD (`git cherry-pick`).
+//! a conflict leaves the repo in the cherry-pick state (like git)
+//! until it is continued or aborted
+
+use super::{
+    commit::signature_allow_undefined_name,
+    utils::{has_uncommitted_changes, repo},
+    CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{Commit, Repository, RepositoryState, ResetType};
+use scopetime::scope_time;
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CherryPickResult {
+    /// id of the new commit
+    Committed(CommitId),
+    /// resolve and stage the conflicts, then continue (or abort)
+    Conflict,
+}
+
+/// commits the changes of `id` onto HEAD keeping its author and
+/// message. needs a clean working dir, merge commits are rejected
+pub fn cherry_pick(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<CherryPickResult> {
+    scope_time!("cherry_pick");
+
+    let repo = repo(repo_path)?;
+
+    if repo.state() != RepositoryState::Clean {
+        return Err(Error::Generic(String::from(
+            "repository is not in a clean state",
+        )));
+    }
+    if has_uncommitted_changes(&repo)? {
+        return Err(Error::UncommittedChanges);
+    }
+
+    let commit = repo.find_commit(id.into())?;
+    if commit.parent_count() > 1 {
+        return Err(Error::Generic(String::from(
+            "merge commits cannot be cherry-picked",
+        )));
+    }
+
+    repo.cherrypick(&commit, None)?;
+
+    if repo.index()?.has_conflicts() {
+        return Ok(CherryPickResult::Conflict);
+    }
+
+    commit_cherry_pick(&repo, &commit)
+        .map(CherryPickResult::Committed)
+}
+
+/// commits the resolved (staged) cherry-pick
+pub fn cherry_pick_continue(repo_path: &str) -> Result<CommitId> {
+    scope_time!("cherry_pick_continue");
+
+    let repo = repo(repo_path)?;
+    ensure_cherry_picking(&repo)?;
+
+    if repo.index()?.has_conflicts() {
+        return Err(Error::Generic(String::from(
+            "resolve all conflicts first",
+        )));
+    }
+
+    let commit =
+        repo.find_reference("CHERRY_PICK_HEAD")?.peel_to_commit()?;
+
+    commit_cherry_pick(&repo, &commit)
+}
+
+/// drops the stopped cherry-pick, like `git cherry-pick --abort`
+pub fn cherry_pick_abort(repo_path: &str) -> Result<()> {
+    scope_time!("cherry_pick_abort");
+
+    let repo = repo(repo_path)?;
+    ensure_cherry_picking(&repo)?;
+
+    let head = repo.head()?.peel_to_commit()?;
+    repo.reset(head.as_object(), ResetType::Hard, None)?;
+    repo.cleanup_state()?;
+
+    Ok(())
+}
+
+fn ensure_cherry_picking(repo: &Repository) -> Result<()> {
+    if repo.state() != RepositoryState::CherryPick {
+        return Err(Error::Generic(String::from(
+            "no cherry-pick in progress",
+        )));
+    }
+
+    Ok(())
+}
+
+fn commit_cherry_pick(
+    repo: &Repository,
+    original: &Commit<'_>,
+) -> Result<CommitId> {
+    let head = repo.head()?.peel_to_commit()?;
+    let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+
+    if tree.id() == head.tree_id() {
+        repo.cleanup_state()?;
+        return Err(Error::Generic(String::from(
+            "nothing to commit, the changes are part of HEAD already",
+        )));
+    }
+
+    let committer = signature_allow_undefined_name(repo)?;
+    let msg = String::from_utf8_lossy(original.message_bytes());
+
+    let id = repo.commit(
+        Some("HEAD"),
+        &original.author(),
+        &committer,
+        &msg,
+        &tree,
+        &[&head],
+    )?;
+
+    repo.cleanup_state()?;
+
+    Ok(id.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, create_branch, repo_state, stage_add_file,
+        tests::{repo_init, write_commit_file},
+        utils::repo_write_file,
+        RepoState,
+    };
+    use std::{fs, path::Path};
+
+    /// returns the conflicting commit to pick and the HEAD it
+    /// conflicts with
+    fn conflicting_commits(
+        repo: &Repository,
+        repo_path: &str,
+    ) -> (CommitId, CommitId) {
+        write_commit_file(repo, "f", "0", "base");
+        create_branch(repo_path, "other").unwrap();
+        let picked = write_commit_file(repo, "f", "1", "one");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        let head = write_commit_file(repo, "f", "2", "two");
+
+        (picked, head)
+    }
+
+    #[test]
+    fn test_cherry_pick() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        create_branch(repo_path, "other").unwrap();
+        let picked =
+            write_commit_file(&repo, "a", "a", "add a\n\nbody");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit_file(&repo, "b", "b", "add b");
+
+        let res = cherry_pick(repo_path, picked).unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(
+            res,
+            CherryPickResult::Committed(head.id().into())
+        );
+        assert_eq!(head.message(), Some("add a\n\nbody"));
+        assert_eq!(fs::read_to_string(root.join("a")).unwrap(), "a");
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+
+        // the changes are on master now
+        assert!(cherry_pick(repo_path, picked).is_err());
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+    }
+
+    #[test]
+    fn test_conflict_continue() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let (picked, _) = conflicting_commits(&repo, repo_path);
+
+        assert_eq!(
+            cherry_pick(repo_path, picked).unwrap(),
+            CherryPickResult::Conflict
+        );
+        assert_eq!(
+            repo_state(repo_path).unwrap(),
+            RepoState::CherryPick
+        );
+        assert!(cherry_pick_continue(repo_path).is_err());
+
+        repo_write_file(&repo, "f", "12").unwrap();
+        stage_add_file(repo_path, Path::new("f")).unwrap();
+
+        cherry_pick_continue(repo_path).unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some("one"));
+        assert_eq!(head.parent(0).unwrap().message(), Some("two"));
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+    }
+
+    #[test]
+    fn test_conflict_abort() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let (picked, head) = conflicting_commits(&repo, repo_path);
+
+        cherry_pick(repo_path, picked).unwrap();
+        cherry_pick_abort(repo_path).unwrap();
+
+        assert_eq!(
+            CommitId::from(
+                repo.head().unwrap().peel_to_commit().unwrap().id()
+            ),
+            head
+        );
+        assert_eq!(fs::read_to_string(root.join("f")).unwrap(), "2");
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert!(cherry_pick_abort(repo_path).is_err());
+    }
+}
@@ -0,0 +1,332 @@
+//! removing untracked files (`git clean`)
+
+use super::utils::{repo, work_dir};
+use crate::error::{Error, Result};
+use git2::{Repository, Status, StatusOptions};
+use scopetime::scope_time;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// what to remove besides untracked files
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct CleanOptions {
+    /// untracked directories (`git clean -d`)
+    pub directories: bool,
+    /// ignored files (`git clean -x`)
+    pub ignored: bool,
+}
+
+/// lists what `run_clean` would remove, directories end with `/`.
+/// nested repositories are kept like `git clean -d` does (without
+/// `-ff`)
+pub fn preview_clean(
+    repo_path: &str,
+    options: CleanOptions,
+) -> Result<Vec<String>> {
+    scope_time!("preview_clean");
+
+    let repo = repo(repo_path)?;
+    let work_dir = work_dir(&repo)?;
+
+    let statuses = repo.statuses(Some(
+        StatusOptions::default()
+            .include_untracked(true)
+            .recurse_untracked_dirs(false)
+            .include_ignored(options.ignored)
+            .recurse_ignored_dirs(false)
+            .exclude_submodules(true),
+    ))?;
+
+    let mut res = Vec::new();
+
+    for e in statuses.iter() {
+        let status = e.status();
+        if !status.intersects(Status::WT_NEW | Status::IGNORED) {
+            continue;
+        }
+
+        if let Some(path) = e.path() {
+            if path.ends_with('/')
+                && (!options.directories
+                    || is_nested_repo(&work_dir.join(path)))
+            {
+                continue;
+            }
+
+            res.push(path.to_string());
+        }
+    }
+
+    res.sort();
+
+    Ok(res)
+}
+
+/// removes the `previewed` paths that `preview_clean` still
+/// reports, so nothing can be removed that was not shown before
+/// and anything that got tracked in between is kept. in untracked
+/// directories ignored files (unless `options.ignored`) and nested
+/// repositories are kept.
+/// returns the number of removed entries.
+pub fn run_clean(
+    repo_path: &str,
+    options: CleanOptions,
+    previewed: &[String],
+) -> Result<usize> {
+    scope_time!("run_clean");
+
+    let current = preview_clean(repo_path, options)?;
+    let repo = repo(repo_path)?;
+    let work_dir = fs::canonicalize(work_dir(&repo)?)?;
+
+    let mut removed = 0;
+
+    for path in previewed.iter().filter(|p| current.contains(p)) {
+        let full_path = path_in_work_dir(&work_dir, path)?;
+
+        if fs::symlink_metadata(&full_path)?.is_dir() {
+            clean_dir(&repo, &work_dir, &full_path, options.ignored)?;
+        } else {
+            fs::remove_file(&full_path)?;
+        }
+
+        if !full_path.exists() {
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
+
+/// `path` below the (canonical) `work_dir`, symlinks in its last
+/// component are not followed
+fn path_in_work_dir(work_dir: &Path, path: &str) -> Result<PathBuf> {
+    let outside = || {
+        Error::Generic(format!(
+            "refusing to clean path outside of repo: {}",
+            path
+        ))
+    };
+
+    let full_path = work_dir.join(path);
+    let name = full_path.file_name().ok_or_else(outside)?;
+    let parent =
+        fs::canonicalize(full_path.parent().ok_or_else(outside)?)?;
+
+    if !parent.starts_with(work_dir) {
+        return Err(outside());
+    }
+
+    Ok(parent.join(name))
+}
+
+/// removes the untracked directory `dir` like `git clean -d`: ignored
+/// files are kept unless `ignored` and nested repositories always,
+/// the directory itself only once it is empty
+fn clean_dir(
+    repo: &Repository,
+    work_dir: &Path,
+    dir: &Path,
+    ignored: bool,
+) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let relative = path.strip_prefix(work_dir).map_err(|_| {
+            Error::Generic(format!(
+                "refusing to clean path outside of repo: {}",
+                path.display()
+            ))
+        })?;
+
+        if !ignored && repo.is_path_ignored(relative)? {
+            continue;
+        }
+
+        if fs::symlink_metadata(&path)?.is_dir() {
+            if !is_nested_repo(&path) {
+                clean_dir(repo, work_dir, &path, ignored)?;
+            }
+        } else {
+            fs::remove_file(&path)?;
+        }
+    }
+
+    if fs::read_dir(dir)?.next().is_none() {
+        fs::remove_dir(dir)?;
+    }
+
+    Ok(())
+}
+
+fn is_nested_repo(dir: &Path) -> bool {
+    dir.join(".git").exists()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        stage_add_file,
+        tests::{repo_init, write_commit_file},
+        utils::repo_write_file,
+    };
+
+    fn setup(repo: &git2::Repository, root: &Path) {
+        write_commit_file(repo, ".gitignore", "*.log\n", "ignore");
+        write_commit_file(repo, "tracked.txt", "t", "tracked");
+
+        repo_write_file(repo, "tracked.txt", "modified").unwrap();
+        repo_write_file(repo, "new.txt", "n").unwrap();
+        repo_write_file(repo, "build.log", "l").unwrap();
+        fs::create_dir(root.join("out")).unwrap();
+        repo_write_file(repo, "out/a.o", "o").unwrap();
+    }
+
+    #[test]
+    fn test_preview() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup(&repo, root);
+
+        assert_eq!(
+            preview_clean(repo_path, CleanOptions::default())
+                .unwrap(),
+            vec![String::from("new.txt")]
+        );
+
+        assert_eq!(
+            preview_clean(
+                repo_path,
+                CleanOptions {
+                    directories: true,
+                    ignored: true,
+                }
+            )
+            .unwrap(),
+            vec![
+                String::from("build.log"),
+                String::from("new.txt"),
+                String::from("out/"),
+            ]
+        );
+
+        // nothing touched by a preview
+        assert!(root.join("out/a.o").exists());
+    }
+
+    #[test]
+    fn test_run() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup(&repo, root);
+
+        let options = CleanOptions {
+            directories: true,
+            ignored: false,
+        };
+        let preview = preview_clean(repo_path, options).unwrap();
+
+        assert_eq!(
+            run_clean(repo_path, options, &preview).unwrap(),
+            2
+        );
+
+        assert!(!root.join("new.txt").exists());
+        assert!(!root.join("out").exists());
+        assert!(root.join("build.log").exists());
+        assert!(root.join("tracked.txt").exists());
+    }
+
+    #[test]
+    fn test_run_only_previewed() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup(&repo, root);
+
+        let options = CleanOptions::default();
+        let preview = vec![
+            String::from("new.txt"),
+            String::from("tracked.txt"),
+            String::from("../outside"),
+        ];
+
+        // got tracked after the preview
+        repo_write_file(&repo, "new2.txt", "n").unwrap();
+        stage_add_file(repo_path, Path::new("new.txt")).unwrap();
+
+        assert_eq!(
+            run_clean(repo_path, options, &preview).unwrap(),
+            0
+        );
+
+        assert!(root.join("new.txt").exists());
+        assert!(root.join("new2.txt").exists());
+        assert!(root.join("tracked.txt").exists());
+    }
+
+    #[test]
+    fn test_run_keeps_ignored_in_dir() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup(&repo, root);
+        fs::create_dir(root.join("out/sub")).unwrap();
+        repo_write_file(&repo, "out/sub/secrets.log", "s").unwrap();
+
+        let options = CleanOptions {
+            directories: true,
+            ignored: false,
+        };
+        let preview = preview_clean(repo_path, options).unwrap();
+        run_clean(repo_path, options, &preview).unwrap();
+
+        assert!(!root.join("out/a.o").exists());
+        assert!(root.join("out/sub/secrets.log").exists());
+
+        let options = CleanOptions {
+            directories: true,
+            ignored: true,
+        };
+        let preview = preview_clean(repo_path, options).unwrap();
+        run_clean(repo_path, options, &preview).unwrap();
+
+        assert!(!root.join("out").exists());
+    }
+
+    #[test]
+    fn test_run_keeps_nested_repo() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        setup(&repo, root);
+        git2::Repository::init(root.join("nested")).unwrap();
+        fs::write(root.join("nested/f"), "f").unwrap();
+        fs::create_dir(root.join("out/inner")).unwrap();
+        git2::Repository::init(root.join("out/inner")).unwrap();
+
+        let options = CleanOptions {
+            directories: true,
+            ignored: true,
+        };
+        let preview = preview_clean(repo_path, options).unwrap();
+
+        assert!(!preview.contains(&String::from("nested/")));
+
+        run_clean(repo_path, options, &preview).unwrap();
+
+        assert!(root.join("nested/.git").exists());
+        assert!(root.join("nested/f").exists());
+        assert!(root.join("out/inner/.git").exists());
+        assert!(!root.join("out/a.o").exists());
+    }
+}
@@ -0,0 +1,640 @@
+pub mod trailers;
+
+use super::{
+    get_head,
+    signature::sign_commit_buffer,
+    utils::{repo, work_dir},
+    CommitId, CommitSignature,
+};
+use crate::error::{Error, Result};
+use git2::{
+    Commit, ErrorCode, ObjectType, Oid, Repository, Signature, Time,
+    Tree,
+};
+use scopetime::scope_time;
+
+/// with an `author` (to fix a commit made with the wrong identity)
+/// the committer becomes the current identity like in git,
+/// otherwise both are kept
+pub fn amend(
+    repo_path: &str,
+    id: CommitId,
+    msg: &str,
+    author: Option<&CommitSignature>,
+) -> Result<CommitId> {
+    scope_time!("amend");
+
+    let repo = repo(repo_path)?;
+    let commit = repo.find_commit(id.into())?;
+
+    let author = author
+        .map(|a| {
+            Signature::new(
+                &a.name,
+                &a.email,
+                &Time::new(a.time, a.time_offset),
+            )
+        })
+        .transpose()?;
+    let committer = match author {
+        Some(_) => Some(signature_allow_undefined_name(&repo)?),
+        None => None,
+    };
+
+    let mut index = repo.index()?;
+    let tree_id = index.write_tree()?;
+    let tree = repo.find_tree(tree_id)?;
+
+    let parents = commit.parents().collect::<Vec<_>>();
+    if let Some(new_id) = commit_signed(
+        &repo,
+        author.as_ref().unwrap_or(&commit.author()),
+        committer.as_ref().unwrap_or(&commit.committer()),
+        msg,
+        &tree,
+        &parents.iter().collect::<Vec<_>>(),
+        "commit (amend)",
+    )? {
+        return Ok(CommitId::new(new_id));
+    }
+
+    let new_id = commit.amend(
+        Some("HEAD"),
+        author.as_ref(),
+        committer.as_ref(),
+        None,
+        Some(msg),
+        Some(&tree),
+    )?;
+
+    Ok(CommitId::new(new_id))
+}
+
+/// Wrap Repository::signature to allow unknown user.name.
+///
+/// See <https://github.com/extrawurst/gitui/issues/79>.
+pub(crate) fn signature_allow_undefined_name(
+    repo: &Repository,
+) -> std::result::Result<Signature<'_>, git2::Error> {
+    let signature = repo.signature();
+
+    if let Err(ref e) = signature {
+        if e.code() == ErrorCode::NotFound {
+            let config = repo.config()?;
+
+            if let (Err(_), Ok(email_entry)) = (
+                config.get_entry("user.name"),
+                config.get_entry("user.email"),
+            ) {
+                if let Some(email) = email_entry.value() {
+                    return Signature::now("unknown", email);
+                }
+            };
+        }
+    }
+
+    signature
+}
+
+/// this does not run any git hooks
+pub fn commit(repo_path: &str, msg: &str) -> Result<CommitId> {
+    scope_time!("commit");
+
+    let repo = repo(repo_path)?;
+
+    let signature = signature_allow_undefined_name(&repo)?;
+    let mut index = repo.index()?;
+    let tree_id = index.write_tree()?;
+    let tree = repo.find_tree(tree_id)?;
+
+    let parents = if let Ok(id) = get_head(repo_path) {
+        vec![repo.find_commit(id.into())?]
+    } else {
+        Vec::new()
+    };
+
+    let parents = parents.iter().collect::<Vec<_>>();
+
+    if let Some(id) = commit_signed(
+        &repo,
+        &signature,
+        &signature,
+        msg,
+        &tree,
+        parents.as_slice(),
+        if parents.is_empty() {
+            "commit (initial)"
+        } else {
+            "commit"
+        },
+    )? {
+        return Ok(id.into());
+    }
+
+    Ok(repo
+        .commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            msg,
+            &tree,
+            parents.as_slice(),
+        )?
+        .into())
+}
+
+/// writes the commit signed and moves `HEAD` (or the branch it
+/// points to) like `Repository::commit` would, `None` if
+/// `commit.gpgsign` is not set
+fn commit_signed(
+    repo: &Repository,
+    author: &Signature<'_>,
+    committer: &Signature<'_>,
+    msg: &str,
+    tree: &Tree<'_>,
+    parents: &[&Commit<'_>],
+    reflog_action: &str,
+) -> Result<Option<Oid>> {
+    let buffer = repo.commit_create_buffer(
+        author, committer, msg, tree, parents,
+    )?;
+    let buffer = buffer.as_str().unwrap_or_default();
+
+    let signature =
+        match sign_commit_buffer(repo, committer, buffer.as_bytes())?
+        {
+            Some(signature) => signature,
+            None => return Ok(None),
+        };
+
+    let id = repo.commit_signed(buffer, &signature, None)?;
+
+    let reflog_msg = format!(
+        "{}: {}",
+        reflog_action,
+        msg.lines().next().unwrap_or_default()
+    );
+    let head = repo.find_reference("HEAD")?;
+    match head.symbolic_target() {
+        Some(target) => {
+            repo.reference(target, id, true, &reflog_msg)?;
+        }
+        None => repo.set_head_detached(id)?,
+    }
+
+    Ok(Some(id))
+}
+
+/// content of the file `commit.template` points to, `None` if
+/// unset. a relative path is relative to the workdir
+pub fn commit_msg_template(
+    repo_path: &str,
+) -> Result<Option<String>> {
+    scope_time!("commit_msg_template");
+
+    let repo = repo(repo_path)?;
+
+    let path = match repo.config()?.get_path("commit.template") {
+        Ok(path) => path,
+        Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let path = work_dir(&repo)?.join(path);
+
+    Ok(Some(std::fs::read_to_string(path)?))
+}
+
+/// commits the staged changes as `fixup! <subject of target>` (or
+/// `squash!`), an autosquash rebase folds it into `target` later
+pub fn commit_fixup(
+    repo_path: &str,
+    target: CommitId,
+    squash: bool,
+) -> Result<CommitId> {
+    scope_time!("commit_fixup");
+
+    let repo = repo(repo_path)?;
+
+    let head_tree = repo.head()?.peel_to_tree()?.id();
+    if repo.index()?.write_tree()? == head_tree {
+        return Err(Error::Generic(String::from(
+            "nothing staged to fix up the commit with",
+        )));
+    }
+
+    let target = repo.find_commit(target.into())?;
+    let msg = format!(
+        "{}! {}",
+        if squash { "squash" } else { "fixup" },
+        target.summary().unwrap_or_default()
+    );
+
+    commit(repo_path, &msg)
+}
+
+/// Tag a commit with a lightweight tag, see `tag_annotated` for
+/// tags with a message.
+///
+/// This function will return an `Err(…)` variant if the tag’s name is refused
+/// by git or if the tag already exists.
+pub fn tag(
+    repo_path: &str,
+    commit_id: &CommitId,
+    tag: &str,
+) -> Result<CommitId> {
+    scope_time!("tag");
+
+    let repo = repo(repo_path)?;
+
+    let target = repo
+        .find_object(commit_id.get_oid(), Some(ObjectType::Commit))?;
+
+    repo.tag_lightweight(tag, &target, false)?;
+
+    Ok(*commit_id)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::error::Result;
+    #[cfg(unix)]
+    use crate::sync::tests::fake_gpg;
+    use crate::sync::{
+        commit, get_commit_details, get_commit_files, stage_add_file,
+        tags::get_tags,
+        tests::{get_statuses, repo_init, repo_init_empty},
+        utils::get_head,
+        CommitSignature, LogWalker,
+    };
+    use commit::{amend, commit_fixup, commit_msg_template, tag};
+    use git2::{Repository, Signature};
+    use std::{fs::File, io::Write, path::Path};
+
+    fn count_commits(repo: &Repository, max: usize) -> usize {
+        let mut items = Vec::new();
+        let mut walk = LogWalker::new(&repo);
+        walk.read(&mut items, max).unwrap();
+        items.len()
+    }
+
+    #[test]
+    fn test_commit() {
+        let file_path = Path::new("foo");
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(&root.join(file_path))
+            .unwrap()
+            .write_all(b"test\nfoo")
+            .unwrap();
+
+        assert_eq!(get_statuses(repo_path), (1, 0));
+
+        stage_add_file(repo_path, file_path).unwrap();
+
+        assert_eq!(get_statuses(repo_path), (0, 1));
+
+        commit(repo_path, "commit msg").unwrap();
+
+        assert_eq!(get_statuses(repo_path), (0, 0));
+    }
+
+    #[test]
+    fn test_commit_fixup() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(root.join("foo"))
+            .unwrap()
+            .write_all(b"a")
+            .unwrap();
+        stage_add_file(repo_path, Path::new("foo")).unwrap();
+        let target = commit(repo_path, "add foo\n\nbody").unwrap();
+
+        assert!(commit_fixup(repo_path, target, false).is_err());
+
+        File::create(root.join("foo"))
+            .unwrap()
+            .write_all(b"b")
+            .unwrap();
+        stage_add_file(repo_path, Path::new("foo")).unwrap();
+        let fixup = commit_fixup(repo_path, target, true).unwrap();
+
+        assert_eq!(
+            get_commit_details(repo_path, fixup)
+                .unwrap()
+                .message
+                .unwrap()
+                .subject,
+            "squash! add foo"
+        );
+    }
+
+    #[test]
+    fn test_commit_in_empty_repo() {
+        let file_path = Path::new("foo");
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert_eq!(get_statuses(repo_path), (0, 0));
+
+        File::create(&root.join(file_path))
+            .unwrap()
+            .write_all(b"test\nfoo")
+            .unwrap();
+
+        assert_eq!(get_statuses(repo_path), (1, 0));
+
+        stage_add_file(repo_path, file_path).unwrap();
+
+        assert_eq!(get_statuses(repo_path), (0, 1));
+
+        commit(repo_path, "commit msg").unwrap();
+
+        assert_eq!(get_statuses(repo_path), (0, 0));
+    }
+
+    #[test]
+    fn test_amend() -> Result<()> {
+        let file_path1 = Path::new("foo");
+        let file_path2 = Path::new("foo2");
+        let (_td, repo) = repo_init_empty()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(&root.join(file_path1))?.write_all(b"test1")?;
+
+        stage_add_file(repo_path, file_path1)?;
+        let id = commit(repo_path, "commit msg")?;
+
+        assert_eq!(count_commits(&repo, 10), 1);
+
+        File::create(&root.join(file_path2))?.write_all(b"test2")?;
+
+        stage_add_file(repo_path, file_path2)?;
+
+        let new_id = amend(repo_path, id, "amended", None)?;
+
+        assert_eq!(count_commits(&repo, 10), 1);
+
+        let details = get_commit_details(repo_path, new_id)?;
+        assert_eq!(details.message.unwrap().subject, "amended");
+
+        let files = get_commit_files(repo_path, new_id)?;
+
+        assert_eq!(files.len(), 2);
+
+        let head = get_head(repo_path)?;
+
+        assert_eq!(head, new_id);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_amend_author() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let sig = Signature::now("wrong", "wrong@x")?;
+        let head = repo.head()?.peel_to_commit()?;
+        let id = repo.commit(
+            Some("HEAD"),
+            &sig,
+            &sig,
+            "msg",
+            &head.tree()?,
+            &[&head],
+        )?;
+
+        let author = CommitSignature {
+            name: String::from("right"),
+            email: String::from("right@x"),
+            time: 1_600_000_000,
+            time_offset: 120,
+        };
+        let new_id =
+            amend(repo_path, id.into(), "amended", Some(&author))?;
+
+        let details = get_commit_details(repo_path, new_id)?;
+        assert_eq!(details.author, author);
+        let committer = details.committer.unwrap();
+        assert_eq!(committer.name, "name");
+        assert_eq!(committer.email, "email");
+
+        // without an author both stay
+        let new_id = amend(repo_path, new_id, "again", None)?;
+        let details = get_commit_details(repo_path, new_id)?;
+        assert_eq!(details.author, author);
+        assert_eq!(details.committer.unwrap().name, "name");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tag() -> Result<()> {
+        let file_path = Path::new("foo");
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(&root.join(file_path))?
+            .write_all(b"test\nfoo")?;
+
+        stage_add_file(repo_path, file_path)?;
+
+        let new_id = commit(repo_path, "commit msg")?;
+
+        tag(repo_path, &new_id, "tag")?;
+
+        assert_eq!(
+            get_tags(repo_path).unwrap()[&new_id],
+            vec!["tag"]
+        );
+
+        assert!(matches!(tag(repo_path, &new_id, "tag"), Err(_)));
+
+        assert_eq!(
+            get_tags(repo_path).unwrap()[&new_id],
+            vec!["tag"]
+        );
+
+        tag(repo_path, &new_id, "second-tag")?;
+
+        assert_eq!(
+            get_tags(repo_path).unwrap()[&new_id],
+            vec!["second-tag", "tag"]
+        );
+
+        Ok(())
+    }
+
+    /// Beware: this test has to be run with a `$HOME/.gitconfig` that has
+    /// `user.email` not set. Otherwise, git falls back to the value of
+    /// `user.email` in `$HOME/.gitconfig` and this test fails.
+    ///
+    /// As of February 2021, `repo_init_empty` sets all git config locations
+    /// to an empty temporary directory, so this constraint is met.
+    #[test]
+    fn test_empty_email() -> Result<()> {
+        let file_path = Path::new("foo");
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(&root.join(file_path))?
+            .write_all(b"test\nfoo")?;
+
+        stage_add_file(repo_path, file_path)?;
+
+        repo.config()?.remove("user.email")?;
+
+        let error = commit(repo_path, "commit msg");
+
+        assert!(matches!(error, Err(_)));
+
+        repo.config()?.set_str("user.email", "email")?;
+
+        let success = commit(repo_path, "commit msg");
+
+        assert!(matches!(success, Ok(_)));
+        assert_eq!(count_commits(&repo, 10), 1);
+
+        let details =
+            get_commit_details(repo_path, success.unwrap()).unwrap();
+
+        assert_eq!(details.author.name, "name");
+        assert_eq!(details.author.email, "email");
+
+        Ok(())
+    }
+
+    /// See comment to `test_empty_email`.
+    #[test]
+    fn test_empty_name() -> Result<()> {
+        let file_path = Path::new("foo");
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(&root.join(file_path))?
+            .write_all(b"test\nfoo")?;
+
+        stage_add_file(repo_path, file_path)?;
+
+        repo.config()?.remove("user.name")?;
+
+        let mut success = commit(repo_path, "commit msg");
+
+        assert!(matches!(success, Ok(_)));
+        assert_eq!(count_commits(&repo, 10), 1);
+
+        let mut details =
+            get_commit_details(repo_path, success.unwrap()).unwrap();
+
+        assert_eq!(details.author.name, "unknown");
+        assert_eq!(details.author.email, "email");
+
+        repo.config()?.set_str("user.name", "name")?;
+
+        success = commit(repo_path, "commit msg");
+
+        assert!(matches!(success, Ok(_)));
+        assert_eq!(count_commits(&repo, 10), 2);
+
+        details =
+            get_commit_details(repo_path, success.unwrap()).unwrap();
+
+        assert_eq!(details.author.name, "name");
+        assert_eq!(details.author.email, "email");
+
+        Ok(())
+    }
+
+    /// signs commits with `fake_gpg`
+    #[cfg(unix)]
+    fn fake_commit_signing(repo: &Repository, fail: bool) {
+        fake_gpg(repo, fail);
+
+        let mut config = repo.config().unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_commit_signed() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        fake_commit_signing(&repo, false);
+
+        File::create(root.join("foo"))?.write_all(b"a")?;
+        stage_add_file(repo_path, Path::new("foo"))?;
+        let id = commit(repo_path, "signed\n\nbody")?;
+
+        assert_eq!(get_head(repo_path)?, id);
+        let (sig, _) = repo.extract_signature(&id.into(), None)?;
+        assert_eq!(sig.as_str().unwrap(), "fake sig\n");
+        assert_eq!(
+            repo.reflog("HEAD")?.get(0).unwrap().message(),
+            Some("commit: signed")
+        );
+
+        let id = amend(repo_path, id, "amended", None)?;
+
+        assert_eq!(get_head(repo_path)?, id);
+        assert_eq!(count_commits(&repo, 10), 2);
+        assert!(repo.extract_signature(&id.into(), None).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_commit_msg_template() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert_eq!(commit_msg_template(repo_path)?, None);
+
+        File::create(root.join("template"))?
+            .write_all(b"subject\n\n# why\n")?;
+        repo.config()?.set_str("commit.template", "template")?;
+
+        assert_eq!(
+            commit_msg_template(repo_path)?.as_deref(),
+            Some("subject\n\n# why\n")
+        );
+
+        repo.config()?.set_str("commit.template", "missing")?;
+        assert!(commit_msg_template(repo_path).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_commit_signing_fails() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        fake_commit_signing(&repo, true);
+        let head = get_head(repo_path)?;
+
+        File::create(root.join("foo"))?.write_all(b"a")?;
+        stage_add_file(repo_path, Path::new("foo"))?;
+        let res = commit(repo_path, "not signed");
+
+        assert!(matches!(res, Err(crate::error::Error::Sign(_))));
+        assert_eq!(get_head(repo_path)?, head);
+
+        Ok(())
+    }
+}
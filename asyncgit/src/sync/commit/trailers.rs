@@ -0,0 +1,202 @@
+//! trailers at the end of commit messages (like
+//! `git interpret-trailers`)
+
+use super::signature_allow_undefined_name;
+use crate::{error::Result, sync::utils::repo};
+use scopetime::scope_time;
+use std::collections::HashSet;
+
+///
+pub const TRAILER_SIGNED_OFF_BY: &str = "Signed-off-by";
+///
+pub const TRAILER_CO_AUTHORED_BY: &str = "Co-authored-by";
+
+/// `Token: value` with a token of letters, digits and dashes
+fn is_trailer(line: &str) -> bool {
+    line.find(": ").map_or(false, |idx| {
+        idx > 0
+            && line[..idx]
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    })
+}
+
+/// appends `key: value` to the trailer block (last paragraph) of
+/// `msg`, a new block is started if there is none. a trailer that is
+/// there already is not added again
+pub fn add_trailer(msg: &str, key: &str, value: &str) -> String {
+    let msg = msg.trim_end();
+    let trailer = format!("{}: {}", key, value);
+
+    let block = msg
+        .rfind("\n\n")
+        .map(|idx| &msg[idx + 2..])
+        .filter(|block| block.lines().all(is_trailer));
+
+    match block {
+        Some(block) if block.lines().any(|l| l == trailer) => {
+            msg.to_string()
+        }
+        Some(_) => format!("{}\n{}", msg, trailer),
+        // keeps the subject line free in an empty message
+        None => format!("{}\n\n{}", msg, trailer),
+    }
+}
+
+/// `format.signoff` of the repo config
+pub fn signoff_enabled(repo_path: &str) -> Result<bool> {
+    let repo = repo(repo_path)?;
+    let config = repo.config()?;
+
+    Ok(config.get_bool("format.signoff").unwrap_or_default())
+}
+
+/// `Name <email>` of the committer, for a `Signed-off-by` trailer
+pub fn get_signoff(repo_path: &str) -> Result<String> {
+    scope_time!("get_signoff");
+
+    let repo = repo(repo_path)?;
+    let signature = signature_allow_undefined_name(&repo)?;
+
+    Ok(format!(
+        "{} <{}>",
+        signature.name().unwrap_or_default(),
+        signature.email().unwrap_or_default()
+    ))
+}
+
+/// `Name <email>` of the authors of the last `max_commits` commits
+/// of HEAD, most recent first, leaving out the committer itself
+pub fn get_recent_authors(
+    repo_path: &str,
+    max_commits: usize,
+) -> Result<Vec<String>> {
+    scope_time!("get_recent_authors");
+
+    let repo = repo(repo_path)?;
+    let own_email = signature_allow_undefined_name(&repo)
+        .ok()
+        .and_then(|s| s.email().map(String::from));
+
+    let mut walk = repo.revwalk()?;
+    if walk.push_head().is_err() {
+        // no commits yet
+        return Ok(Vec::new());
+    }
+
+    let mut seen = HashSet::new();
+    let mut authors = Vec::new();
+    for id in walk.take(max_commits) {
+        let commit = repo.find_commit(id?)?;
+        let author = commit.author();
+        let (name, email) = match (author.name(), author.email()) {
+            (Some(name), Some(email)) => (name, email),
+            _ => continue,
+        };
+
+        if Some(email) != own_email.as_deref()
+            && seen.insert(email.to_string())
+        {
+            authors.push(format!("{} <{}>", name, email));
+        }
+    }
+
+    Ok(authors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        add_trailer, get_recent_authors, get_signoff,
+        signoff_enabled, TRAILER_CO_AUTHORED_BY,
+        TRAILER_SIGNED_OFF_BY,
+    };
+    use crate::sync::tests::repo_init;
+    use git2::{Repository, Signature};
+
+    fn commit_as(repo: &Repository, name: &str, email: &str) {
+        let sig = Signature::now(name, email).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let tree = head.tree().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "msg", &tree, &[&head])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_add_trailer() {
+        assert_eq!(
+            add_trailer(
+                "subject\n",
+                TRAILER_SIGNED_OFF_BY,
+                "a <a@b>"
+            ),
+            "subject\n\nSigned-off-by: a <a@b>"
+        );
+        assert_eq!(
+            add_trailer("", TRAILER_CO_AUTHORED_BY, "a <a@b>"),
+            "\n\nCo-authored-by: a <a@b>"
+        );
+
+        // appended to an existing block
+        let msg = "subject\n\nbody\n\nCo-authored-by: a <a@b>";
+        let msg = add_trailer(msg, TRAILER_SIGNED_OFF_BY, "c <c@d>");
+        assert_eq!(
+            msg,
+            "subject\n\nbody\n\nCo-authored-by: a <a@b>\n\
+             Signed-off-by: c <c@d>"
+        );
+
+        // not twice
+        assert_eq!(
+            add_trailer(&msg, TRAILER_CO_AUTHORED_BY, "a <a@b>"),
+            msg
+        );
+
+        // a body is no trailer block
+        assert_eq!(
+            add_trailer(
+                "subject\n\nsee the docs",
+                TRAILER_SIGNED_OFF_BY,
+                "c <c@d>"
+            ),
+            "subject\n\nsee the docs\n\nSigned-off-by: c <c@d>"
+        );
+    }
+
+    #[test]
+    fn test_signoff() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert_eq!(get_signoff(repo_path).unwrap(), "name <email>");
+
+        assert!(!signoff_enabled(repo_path).unwrap());
+        repo.config()
+            .unwrap()
+            .set_bool("format.signoff", true)
+            .unwrap();
+        assert!(signoff_enabled(repo_path).unwrap());
+    }
+
+    #[test]
+    fn test_recent_authors() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        commit_as(&repo, "a", "a@x");
+        commit_as(&repo, "b", "b@x");
+        commit_as(&repo, "a", "a@x");
+        commit_as(&repo, "name", "email");
+
+        assert_eq!(
+            get_recent_authors(repo_path, 10).unwrap(),
+            vec!["a <a@x>", "b <b@x>"]
+        );
+        assert_eq!(
+            get_recent_authors(repo_path, 2).unwrap(),
+            vec!["a <a@x>"]
+        );
+    }
+}
@@ -1,10 +1,13 @@
-use super::{commits_info::get_message, utils::repo, CommitId};
+use super::{
+    commits_info::get_message, encoding::decode_with_label,
+    signature::SignatureInfo, utils::repo, CommitId,
+};
 use crate::error::Result;
 use git2::Signature;
 use scopetime::scope_time;
 
 ///
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct CommitSignature {
     ///
     pub name: String,
@@ -12,6 +15,8 @@ pub struct CommitSignature {
     pub email: String,
     /// time in secs since Unix epoch
     pub time: i64,
+    /// time zone the signature was made in, minutes east of UTC
+    pub time_offset: i32,
 }
 
 impl CommitSignature {
@@ -22,11 +27,13 @@ impl CommitSignature {
             email: s.email().unwrap_or("").to_string(),
 
             time: s.when().seconds(),
+            time_offset: s.when().offset_minutes(),
         }
     }
 }
 
 ///
+#[derive(Clone)]
 pub struct CommitMessage {
     /// first line
     pub subject: String,
@@ -68,6 +75,7 @@ impl CommitMessage {
 }
 
 ///
+#[derive(Clone)]
 pub struct CommitDetails {
     ///
     pub author: CommitSignature,
@@ -77,6 +85,12 @@ pub struct CommitDetails {
     pub message: Option<CommitMessage>,
     ///
     pub hash: String,
+    /// `encoding` header of the commit if we cannot decode it,
+    /// the message is shown as lossy utf8 then
+    pub unsupported_encoding: Option<String>,
+    /// verification result if the commit is signed, left out by
+    /// `get_commit_details` (see `get_commit_signature`)
+    pub signature: Option<SignatureInfo>,
 }
 
 ///
@@ -101,11 +115,19 @@ pub fn get_commit_details(
     let msg =
         CommitMessage::from(get_message(&commit, None).as_str());
 
+    let unsupported_encoding = decode_with_label(
+        commit.message_bytes(),
+        commit.message_encoding(),
+    )
+    .1;
+
     let details = CommitDetails {
         author,
         committer,
         message: Some(msg),
         hash: id.to_string(),
+        unsupported_encoding,
+        signature: None,
     };
 
     Ok(details)
@@ -149,6 +171,49 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_msg_encoding_header() -> Result<()> {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let tree = repo.treebuilder(None)?.write()?;
+        let sig = "a <a@b.c> 0 +0000";
+
+        let raw_commit = |encoding: &str| -> Vec<u8> {
+            let mut raw = format!(
+                "tree {}\nauthor {}\ncommitter {}\nencoding {}\n\n",
+                tree, sig, sig, encoding
+            )
+            .into_bytes();
+            raw.extend_from_slice(b"caf\xe9\n");
+            raw
+        };
+
+        let odb = repo.odb()?;
+
+        let id = odb.write(
+            git2::ObjectType::Commit,
+            &raw_commit("ISO-8859-1"),
+        )?;
+        let res = get_commit_details(repo_path, id.into())?;
+        assert_eq!(res.message.unwrap().subject, "café");
+        assert_eq!(res.unsupported_encoding, None);
+
+        let id = odb.write(
+            git2::ObjectType::Commit,
+            &raw_commit("Shift_JIS"),
+        )?;
+        let res = get_commit_details(repo_path, id.into())?;
+        assert_eq!(res.message.unwrap().subject, "caf\u{FFFD}");
+        assert_eq!(
+            res.unsupported_encoding,
+            Some(String::from("Shift_JIS"))
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_msg_linefeeds() -> Result<()> {
         let msg = CommitMessage::from("foo\nbar\r\ntest");
@@ -1,9 +1,15 @@
-use super::{stash::is_stash_commit, utils::repo, CommitId};
+use super::{
+    diff::DiffOptions, stash::is_stash_commit, utils::repo, CommitId,
+};
 use crate::{
     error::Error, error::Result, StatusItem, StatusItemType,
 };
-use git2::{Diff, DiffDelta, DiffOptions, Repository};
+use git2::{
+    Commit, Delta, Diff, DiffDelta, DiffFindOptions,
+    DiffOptions as GitDiffOptions, Repository, Tree,
+};
 use scopetime::scope_time;
+use std::path::Path;
 
 /// get all files that are part of a commit
 pub fn get_commit_files(
@@ -14,18 +20,147 @@ pub fn get_commit_files(
 
     let repo = repo(repo_path)?;
 
-    let diff = get_commit_diff(&repo, id, None)?;
+    let diff = get_commit_diff(&repo, id, 0, None, None)?;
+
+    diff_status_items(&diff)
+}
+
+/// what the changes of a merge commit are shown against, commits
+/// with a single parent are always diffed against it
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MergeDiff {
+    /// the n-th parent, `0` being the one merged into
+    Parent(usize),
+    /// only files changed against every parent (like `git show -c`),
+    /// their diff lists the hunks against each parent in turn
+    Combined,
+}
+
+impl Default for MergeDiff {
+    fn default() -> Self {
+        Self::Parent(0)
+    }
+}
+
+///
+pub fn get_commit_parents(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<Vec<CommitId>> {
+    let repo = repo(repo_path)?;
+    let commit = repo.find_commit(id.into())?;
+
+    Ok(commit.parent_ids().map(CommitId::new).collect())
+}
 
+/// files of a commit, see `get_commit_files_split`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CommitFiles {
+    /// changes of the commit itself (tracked files for a stash)
+    pub files: Vec<StatusItem>,
+    /// untracked files stored by a stash, empty otherwise
+    pub untracked: Vec<StatusItem>,
+}
+
+/// like `get_commit_files` but the untracked files of a stash
+/// (`--include-untracked`) are listed apart from its tracked changes
+pub fn get_commit_files_split(
+    repo_path: &str,
+    id: CommitId,
+    merge: MergeDiff,
+) -> Result<CommitFiles> {
+    scope_time!("get_commit_files_split");
+
+    let repo = repo(repo_path)?;
+
+    let commit = repo.find_commit(id.into())?;
+    let files = match merge {
+        MergeDiff::Parent(parent) => diff_status_items(
+            &get_commit_own_diff(&repo, &commit, parent, None, None)?,
+        )?,
+        MergeDiff::Combined => get_combined_files(&repo, &commit)?,
+    };
+
+    let untracked = match get_stash_untracked_commit(repo_path, id)? {
+        Some(untracked_id) => {
+            let untracked_commit =
+                repo.find_commit(untracked_id.into())?;
+            diff_status_items(&get_commit_own_diff(
+                &repo,
+                &untracked_commit,
+                0,
+                None,
+                None,
+            )?)?
+        }
+        None => Vec::new(),
+    };
+
+    Ok(CommitFiles { files, untracked })
+}
+
+/// files changed against every parent, as changed against the first
+fn get_combined_files(
+    repo: &Repository,
+    commit: &Commit<'_>,
+) -> Result<Vec<StatusItem>> {
+    let mut files = diff_status_items(&get_commit_own_diff(
+        repo, commit, 0, None, None,
+    )?)?;
+
+    for parent in 1..commit.parent_count() {
+        let others = diff_status_items(&get_commit_own_diff(
+            repo, commit, parent, None, None,
+        )?)?;
+        files.retain(|file| {
+            others.iter().any(|other| other.path == file.path)
+        });
+    }
+
+    Ok(files)
+}
+
+/// the commit holding the untracked files of a stash: its third
+/// parent. `None` if `id` is no stash or stashed no untracked files
+pub fn get_stash_untracked_commit(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<Option<CommitId>> {
+    if !is_stash_commit(repo_path, &id)? {
+        return Ok(None);
+    }
+
+    let repo = repo(repo_path)?;
+    let commit = repo.find_commit(id.into())?;
+
+    Ok(commit.parent_id(2).ok().map(CommitId::new))
+}
+
+/// files touched by `diff`
+pub(crate) fn diff_status_items(
+    diff: &Diff<'_>,
+) -> Result<Vec<StatusItem>> {
     let mut res = Vec::new();
 
     diff.foreach(
         &mut |delta: DiffDelta<'_>, _progress| {
-            res.push(StatusItem {
-                path: delta
-                    .new_file()
-                    .path()
+            let path = |file: git2::DiffFile<'_>| {
+                file.path()
                     .map(|p| p.to_str().unwrap_or("").to_string())
-                    .unwrap_or_default(),
+                    .unwrap_or_default()
+            };
+            let old_path = if matches!(
+                delta.status(),
+                Delta::Renamed | Delta::Copied
+            ) {
+                Some(path(delta.old_file()))
+            } else {
+                None
+            };
+
+            res.push(StatusItem {
+                path: path(delta.new_file()),
+                old_path,
                 status: StatusItemType::from(delta.status()),
             });
             true
@@ -38,32 +173,37 @@ pub fn get_commit_files(
     Ok(res)
 }
 
-///
+/// diff between the trees of two commits (`git diff from to`)
+pub(crate) fn get_commits_diff(
+    repo: &Repository,
+    from: CommitId,
+    to: CommitId,
+    pathspec: Option<String>,
+    options: Option<DiffOptions>,
+) -> Result<Diff<'_>> {
+    let from_tree = repo.find_commit(from.into())?.tree()?;
+    let to_tree = repo.find_commit(to.into())?.tree()?;
+
+    diff_trees(repo, Some(&from_tree), &to_tree, pathspec, options)
+}
+
+/// diff of `id` against its `parent`-th parent
 pub(crate) fn get_commit_diff(
     repo: &Repository,
     id: CommitId,
+    parent: usize,
     pathspec: Option<String>,
+    options: Option<DiffOptions>,
 ) -> Result<Diff<'_>> {
     // scope_time!("get_commit_diff");
 
     let commit = repo.find_commit(id.into())?;
-    let commit_tree = commit.tree()?;
-    let parent = if commit.parent_count() > 0 {
-        Some(repo.find_commit(commit.parent_id(0)?)?.tree()?)
-    } else {
-        None
-    };
-
-    let mut opts = DiffOptions::new();
-    if let Some(p) = &pathspec {
-        opts.pathspec(p.clone());
-    }
-    opts.show_binary(true);
-
-    let mut diff = repo.diff_tree_to_tree(
-        parent.as_ref(),
-        Some(&commit_tree),
-        Some(&mut opts),
+    let mut diff = get_commit_own_diff(
+        repo,
+        &commit,
+        parent,
+        pathspec.clone(),
+        options,
     )?;
 
     if is_stash_commit(
@@ -77,7 +217,9 @@ pub(crate) fn get_commit_diff(
             let untracked_diff = get_commit_diff(
                 repo,
                 CommitId::new(untracked_commit),
+                0,
                 pathspec,
+                options,
             )?;
 
             diff.merge(&untracked_diff)?;
@@ -87,18 +229,105 @@ pub(crate) fn get_commit_diff(
     Ok(diff)
 }
 
+/// diff of `commit` against its `parent`-th parent (none for a root
+/// commit)
+fn get_commit_own_diff<'a>(
+    repo: &'a Repository,
+    commit: &Commit<'_>,
+    parent: usize,
+    pathspec: Option<String>,
+    options: Option<DiffOptions>,
+) -> Result<Diff<'a>> {
+    let commit_tree = commit.tree()?;
+    let parent = if commit.parent_count() > 0 {
+        Some(repo.find_commit(commit.parent_id(parent)?)?.tree()?)
+    } else {
+        None
+    };
+
+    diff_trees(repo, parent.as_ref(), &commit_tree, pathspec, options)
+}
+
+/// renames and copies are detected, a file limited to by `pathspec`
+/// that was renamed is diffed against the file it was renamed from
+fn diff_trees<'a>(
+    repo: &'a Repository,
+    old: Option<&Tree<'_>>,
+    new: &Tree<'_>,
+    pathspec: Option<String>,
+    options: Option<DiffOptions>,
+) -> Result<Diff<'a>> {
+    let diff = |paths: &[&str]| -> Result<Diff<'a>> {
+        let mut opts = GitDiffOptions::new();
+        for path in paths {
+            opts.pathspec(path);
+        }
+        if let Some(options) = options {
+            options.apply(&mut opts);
+        }
+        opts.show_binary(true);
+
+        let mut diff =
+            repo.diff_tree_to_tree(old, Some(new), Some(&mut opts))?;
+        diff.find_similar(Some(
+            DiffFindOptions::new().renames(true).copies(true),
+        ))?;
+
+        Ok(diff)
+    };
+
+    let path = match pathspec {
+        Some(path) => path,
+        None => return diff(&[]),
+    };
+
+    let file_diff = diff(&[&path])?;
+    let added = file_diff.deltas().len() == 1
+        && file_diff.deltas().all(|d| d.status() == Delta::Added);
+    if !added {
+        return Ok(file_diff);
+    }
+
+    let source = diff(&[])?.deltas().find_map(|d| {
+        if d.status() == Delta::Renamed
+            && d.new_file().path() == Some(Path::new(&path))
+        {
+            d.old_file()
+                .path()
+                .and_then(|p| p.to_str())
+                .map(String::from)
+        } else {
+            None
+        }
+    });
+
+    match source {
+        Some(source) => diff(&[&path, &source]),
+        None => Ok(file_diff),
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::get_commit_files;
+    use super::{
+        get_commit_files, get_commit_files_split, get_commit_parents,
+        get_stash_untracked_commit, MergeDiff,
+    };
     use crate::{
         error::Result,
         sync::{
-            commit, stage_add_file, stash_save,
-            tests::{get_statuses, repo_init},
+            commit, get_diff_commit, stage_add_file,
+            stage_addremoved, stash_save,
+            status::{get_status, StatusType},
+            tests::{get_statuses, repo_init, write_commit_file},
         },
-        StatusItemType,
+        DiffLineType, StatusItemType,
+    };
+    use std::{
+        fs::{self, File},
+        io::Write,
+        path::Path,
     };
-    use std::{fs::File, io::Write, path::Path};
 
     #[test]
     fn test_smoke() -> Result<()> {
@@ -170,4 +399,194 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_stash_files_split() -> Result<()> {
+        let file_path1 = Path::new("file1.txt");
+        let file_path2 = Path::new("file2.txt");
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(root.join(file_path1))?.write_all(b"test")?;
+        stage_add_file(repo_path, file_path1)?;
+        let id = commit(repo_path, "c1")?;
+
+        assert_eq!(get_stash_untracked_commit(repo_path, id)?, None);
+
+        File::create(root.join(file_path1))?
+            .write_all(b"modified")?;
+        File::create(root.join(file_path2))?.write_all(b"new")?;
+
+        let with_untracked =
+            stash_save(repo_path, None, true, false)?;
+
+        assert!(get_stash_untracked_commit(
+            repo_path,
+            with_untracked
+        )?
+        .is_some());
+
+        let files = get_commit_files_split(
+            repo_path,
+            with_untracked,
+            MergeDiff::default(),
+        )?;
+
+        assert_eq!(files.files.len(), 1);
+        assert_eq!(files.files[0].path, "file1.txt");
+        assert_eq!(files.files[0].status, StatusItemType::Modified);
+        assert_eq!(files.untracked.len(), 1);
+        assert_eq!(files.untracked[0].path, "file2.txt");
+        assert_eq!(files.untracked[0].status, StatusItemType::New);
+
+        File::create(root.join(file_path1))?.write_all(b"again")?;
+
+        let without_untracked =
+            stash_save(repo_path, None, false, false)?;
+
+        assert_eq!(
+            get_stash_untracked_commit(repo_path, without_untracked)?,
+            None
+        );
+
+        let files = get_commit_files_split(
+            repo_path,
+            without_untracked,
+            MergeDiff::default(),
+        )?;
+
+        assert_eq!(files.files.len(), 1);
+        assert!(files.untracked.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_renamed() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let content: String =
+            (1..=20).map(|i| format!("line {}\n", i)).collect();
+        File::create(root.join("old.txt"))?
+            .write_all(content.as_bytes())?;
+        stage_add_file(repo_path, Path::new("old.txt"))?;
+        commit(repo_path, "c1")?;
+
+        fs::remove_file(root.join("old.txt"))?;
+        File::create(root.join("new.txt"))?.write_all(
+            content.replace("line 10\n", "line ten\n").as_bytes(),
+        )?;
+        stage_addremoved(repo_path, Path::new("old.txt"))?;
+        stage_add_file(repo_path, Path::new("new.txt"))?;
+
+        let staged = get_status(repo_path, StatusType::Stage, false)?;
+        assert_eq!(staged.len(), 1);
+        assert_eq!(staged[0].status, StatusItemType::Renamed);
+        assert_eq!(staged[0].old_path.as_deref(), Some("old.txt"));
+
+        let id = commit(repo_path, "c2")?;
+
+        let files = get_commit_files(repo_path, id)?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "new.txt");
+        assert_eq!(files[0].old_path.as_deref(), Some("old.txt"));
+
+        // only the changed line, not the whole file as added
+        let diff = get_diff_commit(
+            repo_path,
+            id,
+            "new.txt".into(),
+            MergeDiff::default(),
+            None,
+        )?;
+        let changed: Vec<_> = diff.hunks[0]
+            .lines
+            .iter()
+            .filter(|l| {
+                l.line_type == DiffLineType::Add
+                    || l.line_type == DiffLineType::Delete
+            })
+            .map(|l| l.content.as_str())
+            .collect();
+        assert_eq!(changed, vec!["line 10\n", "line ten\n"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_diff() -> Result<()> {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "x.txt", "1\n", "c1");
+        let c2 = write_commit_file(&repo, "y.txt", "2\n", "c2");
+
+        File::create(root.join("x.txt"))?.write_all(b"3\n")?;
+        File::create(root.join("z.txt"))?.write_all(b"z\n")?;
+        stage_add_file(repo_path, Path::new("x.txt"))?;
+        stage_add_file(repo_path, Path::new("z.txt"))?;
+
+        let mut index = repo.index()?;
+        index.read(true)?;
+        let tree = repo.find_tree(index.write_tree()?)?;
+        let sig = repo.signature()?;
+        let merge: crate::sync::CommitId = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "merge",
+                &tree,
+                &[
+                    &repo.find_commit(c2.into())?,
+                    &repo.find_commit(c1.into())?,
+                ],
+            )?
+            .into();
+
+        assert_eq!(
+            get_commit_parents(repo_path, merge)?,
+            vec![c2, c1]
+        );
+
+        let paths = |merge_diff| -> Result<Vec<String>> {
+            Ok(get_commit_files_split(repo_path, merge, merge_diff)?
+                .files
+                .into_iter()
+                .map(|f| f.path)
+                .collect())
+        };
+
+        assert_eq!(
+            paths(MergeDiff::Parent(0))?,
+            vec!["x.txt", "z.txt"]
+        );
+        assert_eq!(
+            paths(MergeDiff::Parent(1))?,
+            vec!["x.txt", "y.txt", "z.txt"]
+        );
+        assert_eq!(
+            paths(MergeDiff::Combined)?,
+            vec!["x.txt", "z.txt"]
+        );
+
+        let diff = |merge_diff| {
+            get_diff_commit(
+                repo_path,
+                merge,
+                "x.txt".into(),
+                merge_diff,
+                None,
+            )
+        };
+
+        assert_eq!(diff(MergeDiff::Parent(0))?.hunks.len(), 1);
+        assert_eq!(diff(MergeDiff::Combined)?.hunks.len(), 2);
+
+        Ok(())
+    }
 }
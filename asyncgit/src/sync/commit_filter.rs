@@ -0,0 +1,231 @@
+//! matching commits of the log against a search query
+
+use super::{CommitId, LogWalkerFilter};
+use crate::error::Result;
+use git2::{Commit, Repository};
+use std::sync::Arc;
+
+/// what part of a commit a search looks at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchField {
+    /// message, author or sha prefix
+    All,
+    ///
+    Message,
+    /// name or email
+    Author,
+    /// hash prefix
+    Sha,
+    /// a file the commit touched (compared to its first parent)
+    Path,
+}
+
+/// case insensitive query, a leading `msg:`, `author:`, `sha:` or
+/// `path:` narrows it down to that field
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogSearch {
+    ///
+    pub field: SearchField,
+    /// lowercase
+    pub query: String,
+}
+
+impl LogSearch {
+    /// `None` for an empty query
+    pub fn parse(text: &str) -> Option<Self> {
+        const PREFIXES: [(&str, SearchField); 4] = [
+            ("msg:", SearchField::Message),
+            ("author:", SearchField::Author),
+            ("sha:", SearchField::Sha),
+            ("path:", SearchField::Path),
+        ];
+
+        let text = text.trim();
+        let (field, query) = PREFIXES
+            .iter()
+            .find_map(|(prefix, field)| {
+                text.strip_prefix(prefix).map(|q| (*field, q))
+            })
+            .unwrap_or((SearchField::All, text));
+
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            None
+        } else {
+            Some(Self { field, query })
+        }
+    }
+
+    ///
+    pub fn matches(
+        &self,
+        repo: &Repository,
+        id: &CommitId,
+    ) -> Result<bool> {
+        let commit = repo.find_commit((*id).into())?;
+
+        Ok(match self.field {
+            SearchField::All => {
+                self.sha_matches(&commit)
+                    || self.message_matches(&commit)
+                    || self.author_matches(&commit)
+            }
+            SearchField::Message => self.message_matches(&commit),
+            SearchField::Author => self.author_matches(&commit),
+            SearchField::Sha => self.sha_matches(&commit),
+            SearchField::Path => self.path_matches(repo, &commit)?,
+        })
+    }
+
+    fn sha_matches(&self, commit: &Commit) -> bool {
+        commit.id().to_string().starts_with(&self.query)
+    }
+
+    fn message_matches(&self, commit: &Commit) -> bool {
+        String::from_utf8_lossy(commit.message_bytes())
+            .to_lowercase()
+            .contains(&self.query)
+    }
+
+    fn author_matches(&self, commit: &Commit) -> bool {
+        let author = commit.author();
+
+        String::from_utf8_lossy(author.name_bytes())
+            .to_lowercase()
+            .contains(&self.query)
+            || String::from_utf8_lossy(author.email_bytes())
+                .to_lowercase()
+                .contains(&self.query)
+    }
+
+    fn path_matches(
+        &self,
+        repo: &Repository,
+        commit: &Commit,
+    ) -> Result<bool> {
+        let parent_tree = if commit.parent_count() > 0 {
+            Some(commit.parent(0)?.tree()?)
+        } else {
+            None
+        };
+        let diff = repo.diff_tree_to_tree(
+            parent_tree.as_ref(),
+            Some(&commit.tree()?),
+            None,
+        )?;
+
+        Ok(diff.deltas().any(|delta| {
+            [delta.old_file().path(), delta.new_file().path()]
+                .iter()
+                .flatten()
+                .any(|path| {
+                    path.to_string_lossy()
+                        .to_lowercase()
+                        .contains(&self.query)
+                })
+        }))
+    }
+}
+
+/// walk filter passing only commits matching `search`
+pub fn log_search_filter(search: LogSearch) -> LogWalkerFilter {
+    Arc::new(Box::new(move |repo: &Repository, id: &CommitId| {
+        search.matches(repo, id)
+    }))
+}
+
+/// passes commits passing all of `filters`, `None` without any
+pub fn combine_filters(
+    filters: Vec<LogWalkerFilter>,
+) -> Option<LogWalkerFilter> {
+    match filters.len() {
+        0 => None,
+        1 => filters.into_iter().next(),
+        _ => Some(Arc::new(Box::new(
+            move |repo: &Repository, id: &CommitId| {
+                for filter in &filters {
+                    if !filter(repo, id)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            },
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        no_merges_filter,
+        tests::{repo_init_empty, write_commit_file},
+        LogWalker,
+    };
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(LogSearch::parse("  "), None);
+        assert_eq!(LogSearch::parse("path: "), None);
+
+        let search = LogSearch::parse("Author: Foo").unwrap();
+        assert_eq!(search.field, SearchField::All);
+        assert_eq!(search.query, "author: foo");
+
+        let search = LogSearch::parse("path: src/Lib").unwrap();
+        assert_eq!(search.field, SearchField::Path);
+        assert_eq!(search.query, "src/lib");
+    }
+
+    #[test]
+    fn test_matches() {
+        let (_td, repo) = repo_init_empty().unwrap();
+
+        let first =
+            write_commit_file(&repo, "lib.rs", "a", "Add Lib");
+        let second = write_commit_file(&repo, "README", "b", "docs");
+
+        let matching = |text: &str| {
+            let search = LogSearch::parse(text).unwrap();
+            [first, second]
+                .iter()
+                .filter(|id| search.matches(&repo, id).unwrap())
+                .copied()
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matching("add lib"), vec![first]);
+        assert_eq!(matching("msg:DOCS"), vec![second]);
+        assert_eq!(matching("path:.rs"), vec![first]);
+        assert_eq!(matching("path:readme"), vec![second]);
+        assert_eq!(matching("author:name"), vec![first, second]);
+        assert_eq!(
+            matching(&format!("sha:{}", &second.to_string()[..7])),
+            vec![second]
+        );
+        assert!(matching("msg:src").is_empty());
+    }
+
+    #[test]
+    fn test_filter_walk() {
+        let (_td, repo) = repo_init_empty().unwrap();
+
+        let first = write_commit_file(&repo, "a", "a", "fix a");
+        write_commit_file(&repo, "b", "b", "feature");
+        let third = write_commit_file(&repo, "c", "c", "fix c");
+
+        let filter = combine_filters(vec![
+            no_merges_filter(),
+            log_search_filter(LogSearch::parse("fix").unwrap()),
+        ]);
+
+        let mut items = Vec::new();
+        LogWalker::new(&repo)
+            .filter(filter)
+            .read(&mut items, 10)
+            .unwrap();
+
+        assert_eq!(items, vec![third, first]);
+        assert!(combine_filters(Vec::new()).is_none());
+    }
+}
@@ -0,0 +1,263 @@
+//! lanes of the commit graph (like `git log --graph`)
+
+use super::CommitId;
+use std::collections::HashSet;
+
+/// how a lane passes through the row of a commit,
+/// `up`/`down` connect to the rows above/below and `left`/`right` to
+/// the neighbouring lanes
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct GraphCell {
+    ///
+    pub up: bool,
+    ///
+    pub down: bool,
+    ///
+    pub left: bool,
+    ///
+    pub right: bool,
+    /// the commit of the row sits on this lane
+    pub commit: bool,
+}
+
+impl GraphCell {
+    const fn is_empty(self) -> bool {
+        !(self.up
+            || self.down
+            || self.left
+            || self.right
+            || self.commit)
+    }
+}
+
+/// the graph in the row of one commit, a cell per lane
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GraphRow {
+    ///
+    pub cells: Vec<GraphCell>,
+}
+
+impl GraphRow {
+    /// lane the commit of the row sits on
+    pub fn commit_lane(&self) -> Option<usize> {
+        self.cells.iter().position(|cell| cell.commit)
+    }
+
+    fn connect(&mut self, a: usize, b: usize) {
+        let (from, to) = if a < b { (a, b) } else { (b, a) };
+
+        if to >= self.cells.len() {
+            self.cells.resize(to + 1, GraphCell::default());
+        }
+
+        self.cells[from].right = true;
+        for cell in &mut self.cells[from + 1..to] {
+            cell.left = true;
+            cell.right = true;
+        }
+        self.cells[to].left = true;
+    }
+}
+
+/// assigns commits to lanes, fed with the commits of a walk in order
+/// (children before their parents)
+#[derive(Default)]
+pub struct CommitGraph {
+    /// the commit each lane waits for
+    lanes: Vec<Option<CommitId>>,
+    seen: HashSet<CommitId>,
+}
+
+impl CommitGraph {
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// the row of the next commit of the walk
+    pub fn push(
+        &mut self,
+        id: CommitId,
+        parents: &[CommitId],
+    ) -> GraphRow {
+        let mut row = GraphRow {
+            cells: self
+                .lanes
+                .iter()
+                .map(|lane| GraphCell {
+                    up: lane.is_some(),
+                    ..GraphCell::default()
+                })
+                .collect(),
+        };
+
+        let children: Vec<usize> = self
+            .lanes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, lane)| {
+                if *lane == Some(id) {
+                    Some(i)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let lane = children
+            .first()
+            .copied()
+            .unwrap_or_else(|| self.free_lane());
+        if lane >= row.cells.len() {
+            row.cells.resize(lane + 1, GraphCell::default());
+        }
+        row.cells[lane].commit = true;
+
+        // branches forked off this commit end here
+        for &child in children.iter().skip(1) {
+            self.lanes[child] = None;
+            row.connect(child, lane);
+        }
+        self.lanes[lane] = None;
+        self.seen.insert(id);
+
+        for (i, parent) in parents.iter().enumerate() {
+            // out of order walk: the lane would never end
+            if self.seen.contains(parent) {
+                continue;
+            }
+
+            if let Some(other) =
+                self.lanes.iter().position(|l| *l == Some(*parent))
+            {
+                // the lane further right joins this one
+                if other > lane && self.lanes[lane].is_none() {
+                    self.lanes[other] = None;
+                    self.lanes[lane] = Some(*parent);
+                }
+                row.connect(lane, other);
+            } else if i == 0 || self.lanes[lane].is_none() {
+                self.lanes[lane] = Some(*parent);
+            } else {
+                let other = self.free_lane();
+                self.lanes[other] = Some(*parent);
+                row.connect(lane, other);
+            }
+        }
+
+        if row.cells.len() < self.lanes.len() {
+            row.cells.resize(self.lanes.len(), GraphCell::default());
+        }
+        for (cell, lane) in row.cells.iter_mut().zip(&self.lanes) {
+            cell.down = lane.is_some();
+        }
+
+        while self.lanes.last().map_or(false, Option::is_none) {
+            self.lanes.pop();
+        }
+        while row.cells.last().map_or(false, |c| c.is_empty()) {
+            row.cells.pop();
+        }
+
+        row
+    }
+
+    fn free_lane(&mut self) -> usize {
+        if let Some(free) =
+            self.lanes.iter().position(Option::is_none)
+        {
+            free
+        } else {
+            self.lanes.push(None);
+            self.lanes.len() - 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Oid;
+
+    fn id(n: u8) -> CommitId {
+        Oid::from_bytes(&[n; 20]).unwrap().into()
+    }
+
+    fn lanes(row: &GraphRow) -> String {
+        row.cells
+            .iter()
+            .map(|c| match (c.commit, c.up, c.down) {
+                (true, _, _) => '*',
+                (false, true, true) => '|',
+                (false, true, false) => '/',
+                (false, false, true) => '\\',
+                (false, false, false) => '-',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_linear() {
+        let mut graph = CommitGraph::new();
+
+        let row = graph.push(id(3), &[id(2)]);
+        assert_eq!(lanes(&row), "*");
+        assert!(!row.cells[0].up && row.cells[0].down);
+
+        let row = graph.push(id(2), &[id(1)]);
+        assert!(row.cells[0].up && row.cells[0].down);
+
+        let row = graph.push(id(1), &[]);
+        assert!(row.cells[0].up && !row.cells[0].down);
+        assert_eq!(row.commit_lane(), Some(0));
+    }
+
+    #[test]
+    fn test_merge() {
+        // 4 merges 3 (on a branch off 1) into 2
+        let mut graph = CommitGraph::new();
+
+        let row = graph.push(id(4), &[id(2), id(3)]);
+        assert_eq!(lanes(&row), "*\\");
+        assert!(row.cells[0].right && row.cells[1].left);
+
+        let row = graph.push(id(3), &[id(1)]);
+        assert_eq!(lanes(&row), "|*");
+        assert!(!row.cells[1].left);
+
+        // both lanes wait for 1, the right one joins here
+        let row = graph.push(id(2), &[id(1)]);
+        assert_eq!(lanes(&row), "*/");
+        assert!(row.cells[0].right && row.cells[1].left);
+
+        let row = graph.push(id(1), &[]);
+        assert_eq!(lanes(&row), "*");
+
+        assert!(graph.lanes.is_empty());
+    }
+
+    #[test]
+    fn test_lane_reused() {
+        let mut graph = CommitGraph::new();
+
+        graph.push(id(5), &[id(4), id(3)]);
+        graph.push(id(3), &[id(1)]);
+        graph.push(id(4), &[id(1)]);
+        graph.push(id(1), &[]);
+        let row = graph.push(id(9), &[id(8)]);
+
+        // an unrelated tip starts in the first free lane again
+        assert_eq!(lanes(&row), "*");
+    }
+
+    #[test]
+    fn test_parent_seen_first() {
+        let mut graph = CommitGraph::new();
+
+        graph.push(id(1), &[]);
+        let row = graph.push(id(2), &[id(1)]);
+
+        assert_eq!(lanes(&row), "*");
+        assert!(!row.cells[0].down);
+    }
+}
@@ -1,7 +1,8 @@
-use super::utils::repo;
+use super::{encoding::decode_with_label, utils::repo};
 use crate::error::Result;
-use git2::{Commit, Error, Oid};
+use git2::{Commit, Error, Oid, ReferenceType, Repository};
 use scopetime::scope_time;
+use std::collections::HashMap;
 use unicode_truncate::UnicodeTruncateStr;
 
 /// identifies a single commit
@@ -47,6 +48,30 @@ impl From<Oid> for CommitId {
     }
 }
 
+/// a ref pointing at a commit
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommitRef {
+    /// with the branch it is on, `None` if detached
+    Head(Option<String>),
+    ///
+    LocalBranch(String),
+    ///
+    RemoteBranch(String),
+    ///
+    Tag(String),
+}
+
+impl CommitRef {
+    const fn rank(&self) -> u8 {
+        match self {
+            Self::Head(_) => 0,
+            Self::LocalBranch(_) => 1,
+            Self::RemoteBranch(_) => 2,
+            Self::Tag(_) => 3,
+        }
+    }
+}
+
 ///
 #[derive(Debug)]
 pub struct CommitInfo {
@@ -54,10 +79,15 @@ pub struct CommitInfo {
     pub message: String,
     ///
     pub time: i64,
+    /// time zone of `time` as recorded in the commit,
+    /// minutes east of UTC
+    pub time_offset: i32,
     ///
     pub author: String,
     ///
     pub id: CommitId,
+    /// HEAD first, then local and remote branches and tags
+    pub refs: Vec<CommitRef>,
 }
 
 ///
@@ -69,6 +99,7 @@ pub fn get_commits_info(
     scope_time!("get_commits_info");
 
     let repo = repo(repo_path)?;
+    let mut refs = refs_by_commit(&repo)?;
 
     let commits = ids
         .iter()
@@ -88,7 +119,11 @@ pub fn get_commits_info(
                 message,
                 author,
                 time: c.time().seconds(),
+                time_offset: c.time().offset_minutes(),
                 id: CommitId(c.id()),
+                refs: refs
+                    .remove(&CommitId(c.id()))
+                    .unwrap_or_default(),
             }
         })
         .collect::<Vec<_>>();
@@ -96,12 +131,75 @@ pub fn get_commits_info(
     Ok(res)
 }
 
+/// all branches and tags (and HEAD) by the commit they point to,
+/// symbolic refs like `origin/HEAD` are left out
+fn refs_by_commit(
+    repo: &Repository,
+) -> Result<HashMap<CommitId, Vec<CommitRef>>> {
+    let mut res: HashMap<CommitId, Vec<CommitRef>> = HashMap::new();
+
+    let head_branch = if repo.head_detached().unwrap_or_default() {
+        None
+    } else {
+        repo.head().ok().and_then(|head| {
+            head.shorthand().map(ToString::to_string)
+        })
+    };
+
+    if let Ok(head) = repo.head() {
+        if let Ok(commit) = head.peel_to_commit() {
+            res.entry(commit.id().into())
+                .or_default()
+                .push(CommitRef::Head(head_branch.clone()));
+        }
+    }
+
+    for reference in repo.references()? {
+        let reference = reference?;
+
+        if reference.kind() != Some(ReferenceType::Direct) {
+            continue;
+        }
+
+        let name = match reference.shorthand() {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+
+        let commit_ref = if reference.is_branch() {
+            if head_branch.as_ref() == Some(&name) {
+                continue;
+            }
+            CommitRef::LocalBranch(name)
+        } else if reference.is_remote() {
+            CommitRef::RemoteBranch(name)
+        } else if reference.is_tag() {
+            CommitRef::Tag(name)
+        } else {
+            continue;
+        };
+
+        if let Ok(commit) = reference.peel_to_commit() {
+            res.entry(commit.id().into())
+                .or_default()
+                .push(commit_ref);
+        }
+    }
+
+    for refs in res.values_mut() {
+        refs.sort_by_key(CommitRef::rank);
+    }
+
+    Ok(res)
+}
+
 ///
 pub fn get_message(
     c: &Commit,
     message_length_limit: Option<usize>,
 ) -> String {
-    let msg = String::from_utf8_lossy(c.message_bytes());
+    let (msg, _) =
+        decode_with_label(c.message_bytes(), c.message_encoding());
     let msg = msg.trim();
 
     if let Some(limit) = message_length_limit {
@@ -113,12 +211,15 @@ pub fn get_message(
 
 #[cfg(test)]
 mod tests {
-    use super::get_commits_info;
+    use super::{get_commits_info, CommitRef};
     use crate::error::Result;
     use crate::sync::{
-        commit, stage_add_file, tests::repo_init_empty,
+        checkout_commit, commit, create_branch_at, stage_add_file,
+        tag,
+        tests::{repo_init, repo_init_empty, write_commit_file},
         utils::get_head_repo,
     };
+    use git2::{Signature, Time};
     use std::{fs::File, io::Write, path::Path};
 
     #[test]
@@ -172,4 +273,62 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_refs() -> Result<()> {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+        let c2 = write_commit_file(&repo, "a.txt", "b", "c2");
+        create_branch_at(repo_path, "other", c1)?;
+        tag(repo_path, &c1, "v1")?;
+
+        let res = get_commits_info(repo_path, &[c2, c1], 50)?;
+
+        assert_eq!(
+            res[0].refs,
+            vec![CommitRef::Head(Some(String::from("master")))]
+        );
+        assert_eq!(
+            res[1].refs,
+            vec![
+                CommitRef::LocalBranch(String::from("other")),
+                CommitRef::Tag(String::from("v1")),
+            ]
+        );
+
+        checkout_commit(repo_path, c1)?;
+
+        let res = get_commits_info(repo_path, &[c2, c1], 50)?;
+
+        assert_eq!(
+            res[0].refs,
+            vec![CommitRef::LocalBranch(String::from("master"))]
+        );
+        assert_eq!(res[1].refs[0], CommitRef::Head(None));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_time_offset() -> Result<()> {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let sig =
+            Signature::new("name", "email", &Time::new(0, -300))?;
+        let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+        let id =
+            repo.commit(Some("HEAD"), &sig, &sig, "msg", &tree, &[])?;
+
+        let res = get_commits_info(repo_path, &[id.into()], 50)?;
+
+        assert_eq!(res[0].time, 0);
+        assert_eq!(res[0].time_offset, -300);
+
+        Ok(())
+    }
 }
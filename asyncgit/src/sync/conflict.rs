@@ -0,0 +1,385 @@
+//! conflicts a merge, rebase, revert or stash apply left behind:
+//! the hunks between the conflict markers of a file and taking one
+//! side of them
+
+use super::utils::{repo, work_dir};
+use crate::error::{Error, Result};
+use git2::{IndexConflict, Repository};
+use scopetime::scope_time;
+use std::{fs, path::Path};
+
+const MARKER_OURS: &str = "<<<<<<<";
+const MARKER_BASE: &str = "|||||||";
+const MARKER_SEPARATOR: &str = "=======";
+const MARKER_THEIRS: &str = ">>>>>>>";
+
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ConflictSide {
+    ///
+    Ours,
+    ///
+    Theirs,
+}
+
+/// the lines of one region between conflict markers
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictHunk {
+    /// line of the `<<<<<<<` marker, 0 based
+    pub line: usize,
+    ///
+    pub ours: Vec<String>,
+    /// only with `merge.conflictStyle=diff3`
+    pub base: Option<Vec<String>>,
+    ///
+    pub theirs: Vec<String>,
+}
+
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictInfo {
+    ///
+    pub path: String,
+    /// `false` if our side deleted the file
+    pub in_ours: bool,
+    /// `false` if their side deleted the file
+    pub in_theirs: bool,
+    /// empty for binary files and delete/modify conflicts, these
+    /// can only be resolved by taking a side as a whole
+    pub hunks: Vec<ConflictHunk>,
+}
+
+/// line ranges of a hunk in the file
+struct MarkerRange {
+    start: usize,
+    base: Option<usize>,
+    separator: usize,
+    end: usize,
+}
+
+///
+pub fn get_conflict(
+    repo_path: &str,
+    path: &str,
+) -> Result<ConflictInfo> {
+    scope_time!("get_conflict");
+
+    let repo = repo(repo_path)?;
+    let conflict = find_conflict(&repo, path)?;
+
+    let content = read_workdir_file(&repo, path)?;
+    let lines = split_lines(&content);
+    let hunks = parse_markers(&lines)
+        .iter()
+        .map(|range| {
+            let text = |from: usize, to: usize| {
+                lines[from + 1..to]
+                    .iter()
+                    .map(|l| l.trim_end_matches('\r').to_string())
+                    .collect::<Vec<_>>()
+            };
+
+            ConflictHunk {
+                line: range.start,
+                ours: text(
+                    range.start,
+                    range.base.unwrap_or(range.separator),
+                ),
+                base: range
+                    .base
+                    .map(|base| text(base, range.separator)),
+                theirs: text(range.separator, range.end),
+            }
+        })
+        .collect();
+
+    Ok(ConflictInfo {
+        path: path.to_string(),
+        in_ours: conflict.our.is_some(),
+        in_theirs: conflict.their.is_some(),
+        hunks,
+    })
+}
+
+/// replaces the hunk at index `hunk` with its `side`, the file is
+/// staged (resolved) once no hunk is left. returns whether it was
+pub fn conflict_take_hunk(
+    repo_path: &str,
+    path: &str,
+    hunk: usize,
+    side: ConflictSide,
+) -> Result<bool> {
+    scope_time!("conflict_take_hunk");
+
+    let repo = repo(repo_path)?;
+    find_conflict(&repo, path)?;
+
+    let content = read_workdir_file(&repo, path)?;
+    let lines = split_lines(&content);
+    let ranges = parse_markers(&lines);
+    let range = ranges.get(hunk).ok_or_else(|| {
+        Error::Generic(format!(
+            "no conflict hunk {} in {}",
+            hunk, path
+        ))
+    })?;
+
+    let taken = match side {
+        ConflictSide::Ours => {
+            &lines[range.start + 1
+                ..range.base.unwrap_or(range.separator)]
+        }
+        ConflictSide::Theirs => {
+            &lines[range.separator + 1..range.end]
+        }
+    };
+
+    let mut res = lines[..range.start].to_vec();
+    res.extend_from_slice(taken);
+    res.extend_from_slice(&lines[range.end + 1..]);
+
+    fs::write(work_dir(&repo)?.join(path), res.join("\n"))?;
+
+    if ranges.len() == 1 {
+        conflict_mark_resolved(repo_path, path)?;
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
+/// resolves the conflict with the whole file of `side` (like
+/// `git checkout --ours/--theirs` and `git add`), deleting the file
+/// if that side did
+pub fn conflict_take(
+    repo_path: &str,
+    path: &str,
+    side: ConflictSide,
+) -> Result<()> {
+    scope_time!("conflict_take");
+
+    let repo = repo(repo_path)?;
+    let conflict = find_conflict(&repo, path)?;
+
+    let entry = match side {
+        ConflictSide::Ours => conflict.our,
+        ConflictSide::Theirs => conflict.their,
+    };
+
+    let file = work_dir(&repo)?.join(path);
+    let mut index = repo.index()?;
+
+    if let Some(entry) = entry {
+        let blob = repo.find_blob(entry.id)?;
+        fs::write(&file, blob.content())?;
+        index.add_path(Path::new(path))?;
+    } else {
+        if file.exists() {
+            fs::remove_file(&file)?;
+        }
+        index.remove_path(Path::new(path))?;
+    }
+
+    index.write()?;
+
+    Ok(())
+}
+
+/// stages the file as it is in the workdir (or its deletion),
+/// refuses while conflict markers are left in it
+pub fn conflict_mark_resolved(
+    repo_path: &str,
+    path: &str,
+) -> Result<()> {
+    scope_time!("conflict_mark_resolved");
+
+    let repo = repo(repo_path)?;
+    let file = work_dir(&repo)?.join(path);
+    let mut index = repo.index()?;
+
+    if file.exists() {
+        let content = read_workdir_file(&repo, path)?;
+        if !parse_markers(&split_lines(&content)).is_empty() {
+            return Err(Error::Generic(format!(
+                "conflict markers left in {}",
+                path
+            )));
+        }
+        index.add_path(Path::new(path))?;
+    } else {
+        index.remove_path(Path::new(path))?;
+    }
+
+    index.write()?;
+
+    Ok(())
+}
+
+fn find_conflict(
+    repo: &Repository,
+    path: &str,
+) -> Result<IndexConflict> {
+    let index = repo.index()?;
+
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let entry_path =
+            [&conflict.our, &conflict.their, &conflict.ancestor]
+                .iter()
+                .find_map(|e| e.as_ref())
+                .map(|e| {
+                    String::from_utf8_lossy(&e.path).to_string()
+                });
+
+        if entry_path.as_deref() == Some(path) {
+            return Ok(conflict);
+        }
+    }
+
+    Err(Error::Generic(format!("{} is not conflicted", path)))
+}
+
+/// lossy for binary files, they have no markers anyway
+fn read_workdir_file(
+    repo: &Repository,
+    path: &str,
+) -> Result<String> {
+    let file = work_dir(repo)?.join(path);
+
+    Ok(if file.exists() {
+        String::from_utf8_lossy(&fs::read(file)?).to_string()
+    } else {
+        String::new()
+    })
+}
+
+/// joining with `\n` gives back the content, `\r` is kept
+fn split_lines(content: &str) -> Vec<&str> {
+    content.split('\n').collect()
+}
+
+fn is_marker(line: &str, marker: &str) -> bool {
+    let line = line.trim_end_matches('\r');
+    line.starts_with(marker)
+        && (line.len() == marker.len()
+            || line[marker.len()..].starts_with(' '))
+}
+
+/// complete `<<<<<<<` (`|||||||`) `=======` `>>>>>>>` sequences
+fn parse_markers(lines: &[&str]) -> Vec<MarkerRange> {
+    let mut res = Vec::new();
+    let mut current: Option<MarkerRange> = None;
+
+    for (idx, line) in lines.iter().enumerate() {
+        if is_marker(line, MARKER_OURS) {
+            current = Some(MarkerRange {
+                start: idx,
+                base: None,
+                separator: 0,
+                end: 0,
+            });
+        } else if let Some(range) = current.as_mut() {
+            if is_marker(line, MARKER_BASE) && range.separator == 0 {
+                range.base = Some(idx);
+            } else if line.trim_end_matches('\r') == MARKER_SEPARATOR
+                && range.separator == 0
+            {
+                range.separator = idx;
+            } else if is_marker(line, MARKER_THEIRS)
+                && range.separator != 0
+            {
+                range.end = idx;
+                res.extend(current.take());
+            }
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        revert_commit,
+        tests::{repo_init, write_commit_file},
+        RevertResult,
+    };
+
+    /// reverting `b` on top of `c` conflicts in the middle line
+    fn conflicted_repo(
+        repo: &Repository,
+        repo_path: &str,
+    ) -> Result<()> {
+        write_commit_file(repo, "f", "a\nx\nz\n", "a");
+        let reverted = write_commit_file(repo, "f", "a\ny\nz\n", "b");
+        write_commit_file(repo, "f", "a\nw\nz\n", "c");
+
+        assert_eq!(
+            revert_commit(repo_path, reverted)?,
+            RevertResult::Conflict
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_markers() {
+        let lines = split_lines(
+            "a\n<<<<<<< ours\nb\n||||||| base\nc\n=======\nd\n>>>>>>> theirs\ne\n<<<<<<< x\n=======\n",
+        );
+        let ranges = parse_markers(&lines);
+
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].start, 1);
+        assert_eq!(ranges[0].base, Some(3));
+        assert_eq!(ranges[0].separator, 5);
+        assert_eq!(ranges[0].end, 7);
+    }
+
+    #[test]
+    fn test_take_hunk() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        conflicted_repo(&repo, repo_path)?;
+
+        let info = get_conflict(repo_path, "f")?;
+        assert_eq!(info.hunks.len(), 1);
+        assert_eq!(info.hunks[0].ours, vec![String::from("w")]);
+        assert_eq!(info.hunks[0].theirs, vec![String::from("x")]);
+        assert!(info.in_ours && info.in_theirs);
+
+        assert!(conflict_take_hunk(
+            repo_path,
+            "f",
+            0,
+            ConflictSide::Theirs
+        )?);
+
+        assert_eq!(fs::read_to_string(root.join("f"))?, "a\nx\nz\n");
+        assert!(!repo.index()?.has_conflicts());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_take_side_and_resolve() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        conflicted_repo(&repo, repo_path)?;
+
+        assert!(conflict_mark_resolved(repo_path, "f").is_err());
+
+        conflict_take(repo_path, "f", ConflictSide::Ours)?;
+
+        assert_eq!(fs::read_to_string(root.join("f"))?, "a\nw\nz\n");
+        assert!(!repo.index()?.has_conflicts());
+        assert!(get_conflict(repo_path, "f").is_err());
+
+        Ok(())
+    }
+}
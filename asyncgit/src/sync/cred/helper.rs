@@ -0,0 +1,236 @@
+//! `git credential fill/approve/reject`, so https credentials come
+//! from and go to the configured helpers (manager, libsecret,
+//! osxkeychain, store..) just like with git itself
+
+use super::BasicAuthCredential;
+use crate::error::{Error, Result};
+use scopetime::scope_time;
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+/// returned by the credentials callback when the remote refused the
+/// credentials it handed out before
+pub(crate) const BAD_CREDENTIALS: &str = "Bad credentials.";
+
+/// what the helpers know for `url`, git is kept from prompting on
+/// the terminal, so this is `None` if they know nothing
+pub fn fill(url: &str) -> Result<Option<BasicAuthCredential>> {
+    scope_time!("credential_fill");
+
+    let output = match run("fill", &describe(url, None)?) {
+        Ok(output) => output,
+        Err(e) => {
+            log::debug!("credential fill: {}", e);
+            return Ok(None);
+        }
+    };
+
+    let mut cred = BasicAuthCredential::new(None, None);
+    for line in output.lines() {
+        let mut parts = line.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some("username"), Some(value)) => {
+                cred.username = Some(value.to_string());
+            }
+            (Some("password"), Some(value)) => {
+                cred.password = Some(value.to_string());
+            }
+            _ => (),
+        }
+    }
+
+    Ok(if cred.is_complete() { Some(cred) } else { None })
+}
+
+/// lets the helpers store credentials that worked
+pub fn approve(url: &str, cred: &BasicAuthCredential) -> Result<()> {
+    scope_time!("credential_approve");
+
+    run("approve", &describe(url, Some(cred))?).map(|_| ())
+}
+
+/// lets the helpers forget credentials the remote refused
+pub fn reject(url: &str, cred: &BasicAuthCredential) -> Result<()> {
+    scope_time!("credential_reject");
+
+    run("reject", &describe(url, Some(cred))?).map(|_| ())
+}
+
+/// approves or rejects `cred` depending on the outcome `res` of
+/// talking to the remote at `url`
+pub(crate) fn report<T>(
+    url: Option<&str>,
+    cred: Option<&BasicAuthCredential>,
+    res: &Result<T>,
+) {
+    let (url, cred) = match (url, cred) {
+        (Some(url), Some(cred))
+            if url.starts_with("http") && cred.is_complete() =>
+        {
+            (url, cred)
+        }
+        _ => return,
+    };
+
+    let outcome = match res {
+        Ok(_) => approve(url, cred),
+        Err(Error::Git(e))
+            if e.code() == git2::ErrorCode::Auth
+                || e.message() == BAD_CREDENTIALS =>
+        {
+            reject(url, cred)
+        }
+        Err(_) => Ok(()),
+    };
+
+    if let Err(e) = outcome {
+        log::error!("credential helper: {}", e);
+    }
+}
+
+/// the input git reads: `url` and optionally the credentials,
+/// terminated by an empty line. values with line breaks or NUL are
+/// refused, they could add attributes of their own
+fn describe(
+    url: &str,
+    cred: Option<&BasicAuthCredential>,
+) -> Result<String> {
+    let mut res = String::new();
+
+    let mut attributes = vec![("url", Some(url))];
+    if let Some(cred) = cred {
+        attributes.push(("username", cred.username.as_deref()));
+        attributes.push(("password", cred.password.as_deref()));
+    }
+
+    for (key, value) in attributes {
+        if let Some(value) = value {
+            if value.contains(&['\n', '\r', '\0'][..]) {
+                return Err(Error::Generic(format!(
+                    "credential {} contains a line break or NUL",
+                    key
+                )));
+            }
+            res.push_str(&format!("{}={}\n", key, value));
+        }
+    }
+
+    res.push('\n');
+    Ok(res)
+}
+
+fn run(action: &str, input: &str) -> Result<String> {
+    let mut child = Command::new("git")
+        .args(&["credential", action])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GCM_INTERACTIVE", "never")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| Error::Generic(String::from("no stdin")))?
+        .write_all(input.as_bytes())?;
+
+    let output = child.wait_with_output()?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(Error::Generic(format!(
+            "git credential {}: {}",
+            action,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::env;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            describe(
+                "https://host/repo",
+                Some(&BasicAuthCredential::new(
+                    Some("user".to_owned()),
+                    Some("pwd".to_owned())
+                ))
+            )
+            .unwrap(),
+            "url=https://host/repo\nusername=user\npassword=pwd\n\n"
+        );
+        assert_eq!(
+            describe("https://host", None).unwrap(),
+            "url=https://host\n\n"
+        );
+    }
+
+    #[test]
+    fn test_describe_injection() {
+        assert!(describe("https://host\nhost=evil", None).is_err());
+        assert!(describe(
+            "https://host",
+            Some(&BasicAuthCredential::new(
+                Some("user\npassword=x".to_owned()),
+                Some("pwd".to_owned())
+            ))
+        )
+        .is_err());
+        assert!(describe(
+            "https://host",
+            Some(&BasicAuthCredential::new(
+                Some("user".to_owned()),
+                Some("pwd\0".to_owned())
+            ))
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_store_helper() {
+        let td = TempDir::new().unwrap();
+        let store = td.path().join("store");
+        let config = td.path().join("config");
+        std::fs::write(
+            &config,
+            format!(
+                "[credential]\n\thelper = store --file={}\n",
+                store.to_str().unwrap()
+            ),
+        )
+        .unwrap();
+        let old = env::var_os("GIT_CONFIG_GLOBAL");
+        env::set_var("GIT_CONFIG_GLOBAL", &config);
+
+        let url = "https://example.com/repo.git";
+        let cred = BasicAuthCredential::new(
+            Some("user".to_owned()),
+            Some("secret".to_owned()),
+        );
+
+        assert_eq!(fill(url).unwrap(), None);
+
+        approve(url, &cred).unwrap();
+        assert_eq!(fill(url).unwrap(), Some(cred.clone()));
+
+        reject(url, &cred).unwrap();
+        assert_eq!(fill(url).unwrap(), None);
+
+        match old {
+            Some(old) => env::set_var("GIT_CONFIG_GLOBAL", old),
+            None => env::remove_var("GIT_CONFIG_GLOBAL"),
+        }
+    }
+}
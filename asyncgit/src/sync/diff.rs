@@ -1,17 +1,22 @@
 //! sync git api for fetching a diff
 
 use super::{
-    commit_files::get_commit_diff,
+    commit_files::{
+        diff_status_items, get_commit_diff, get_commits_diff,
+        MergeDiff,
+    },
+    encoding::decode_guess,
+    status::{get_status, StatusType},
     utils::{self, get_head_repo, work_dir},
-    CommitId,
+    word_diff, CommitId,
 };
 use crate::{error::Error, error::Result, hash};
 use git2::{
-    Delta, Diff, DiffDelta, DiffFormat, DiffHunk, DiffOptions, Patch,
-    Repository,
+    Delta, Diff, DiffDelta, DiffFormat, DiffHunk,
+    DiffOptions as GitDiffOptions, Patch, Repository,
 };
 use scopetime::scope_time;
-use std::{cell::RefCell, fs, path::Path, rc::Rc};
+use std::{cell::RefCell, fs, ops::Range, path::Path, rc::Rc};
 
 /// type of diff of a single line
 #[derive(Copy, Clone, PartialEq, Hash, Debug)]
@@ -24,6 +29,12 @@ pub enum DiffLineType {
     Add,
     /// line deleted
     Delete,
+    /// `\ No newline at end of file` after the last line of
+    /// a side, informational and not part of the content
+    NoNewline,
+    /// path of the file the following hunks belong to
+    /// in a diff of several files
+    File,
 }
 
 impl Default for DiffLineType {
@@ -41,6 +52,9 @@ pub struct DiffLine {
     pub line_type: DiffLineType,
     ///
     pub position: DiffLinePosition,
+    /// byte ranges of `content` differing from the line paired with
+    /// it on the other side of the change
+    pub changes: Vec<Range<usize>>,
 }
 
 ///
@@ -111,18 +125,52 @@ pub struct FileDiff {
     pub size_delta: i64,
 }
 
+/// how whitespace and unchanged lines are treated in a diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DiffOptions {
+    /// changes in the amount of whitespace (`git diff -b`)
+    pub ignore_whitespace_change: bool,
+    /// all whitespace (`git diff -w`)
+    pub ignore_whitespace: bool,
+    /// unchanged lines shown around each change
+    pub context: u32,
+    /// unchanged lines between two changes still joining
+    /// them into one hunk
+    pub interhunk_lines: u32,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            ignore_whitespace_change: false,
+            ignore_whitespace: false,
+            context: 3,
+            interhunk_lines: 0,
+        }
+    }
+}
+
+impl DiffOptions {
+    pub(crate) fn apply(self, opt: &mut GitDiffOptions) {
+        opt.ignore_whitespace_change(self.ignore_whitespace_change);
+        opt.ignore_whitespace(self.ignore_whitespace);
+        opt.context_lines(self.context);
+        opt.interhunk_lines(self.interhunk_lines);
+    }
+}
+
 pub(crate) fn get_diff_raw<'a>(
     repo: &'a Repository,
     p: &str,
     stage: bool,
     reverse: bool,
-    context: Option<u32>,
+    options: Option<DiffOptions>,
 ) -> Result<Diff<'a>> {
     // scope_time!("get_diff_raw");
 
-    let mut opt = DiffOptions::new();
-    if let Some(context) = context {
-        opt.context_lines(context);
+    let mut opt = GitDiffOptions::new();
+    if let Some(options) = options {
+        options.apply(&mut opt);
     }
     opt.pathspec(p);
     opt.reverse(reverse);
@@ -160,12 +208,13 @@ pub fn get_diff(
     //TODO: make &str
     p: String,
     stage: bool,
+    options: Option<DiffOptions>,
 ) -> Result<FileDiff> {
     scope_time!("get_diff");
 
     let repo = utils::repo(repo_path)?;
     let work_dir = work_dir(&repo)?;
-    let diff = get_diff_raw(&repo, &p, stage, false, None)?;
+    let diff = get_diff_raw(&repo, &p, stage, false, options)?;
 
     raw_diff_to_file_diff(&diff, work_dir)
 }
@@ -176,18 +225,242 @@ pub fn get_diff_commit(
     repo_path: &str,
     id: CommitId,
     p: String,
+    merge: MergeDiff,
+    options: Option<DiffOptions>,
 ) -> Result<FileDiff> {
     scope_time!("get_diff_commit");
 
     let repo = utils::repo(repo_path)?;
     let work_dir = work_dir(&repo)?;
-    let diff = get_commit_diff(&repo, id, Some(p))?;
+
+    let parents = match merge {
+        MergeDiff::Parent(parent) => parent..parent + 1,
+        MergeDiff::Combined => {
+            0..repo.find_commit(id.into())?.parent_count().max(1)
+        }
+    };
+
+    let first = parents.start;
+    let mut res = FileDiff::default();
+    for parent in parents {
+        let diff = get_commit_diff(
+            &repo,
+            id,
+            parent,
+            Some(p.clone()),
+            options,
+        )?;
+        let file_diff = raw_diff_to_file_diff(&diff, work_dir)?;
+
+        if parent == first {
+            res.sizes = file_diff.sizes;
+            res.size_delta = file_diff.size_delta;
+        }
+        res.lines += file_diff.lines;
+        res.hunks.extend(file_diff.hunks);
+    }
+
+    Ok(res)
+}
+
+/// returns diff of a specific file between two commits
+pub fn get_diff_commits(
+    repo_path: &str,
+    from: CommitId,
+    to: CommitId,
+    p: String,
+    options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+    scope_time!("get_diff_commits");
+
+    let repo = utils::repo(repo_path)?;
+    let work_dir = work_dir(&repo)?;
+    let diff = get_commits_diff(&repo, from, to, Some(p), options)?;
 
     raw_diff_to_file_diff(&diff, work_dir)
 }
 
+/// combined diff of all files changed between two commits (or
+/// any revisions), each file starts with a `DiffLineType::File` line
+pub fn get_diff_range(
+    repo_path: &str,
+    from: CommitId,
+    to: CommitId,
+    options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+    scope_time!("get_diff_range");
+
+    let repo = utils::repo(repo_path)?;
+    let work_dir = work_dir(&repo)?;
+
+    let mut res = FileDiff::default();
+    let files = diff_status_items(&get_commits_diff(
+        &repo, from, to, None, None,
+    )?)?;
+
+    for file in files {
+        let diff = get_commits_diff(
+            &repo,
+            from,
+            to,
+            Some(file.path.clone()),
+            options,
+        )?;
+        res.append_file(
+            &file.path,
+            raw_diff_to_file_diff(&diff, work_dir)?,
+        );
+    }
+
+    Ok(res)
+}
+
+/// combined diff of all changed files beneath `folder` either
+/// in `stage` or workdir, each file starts with a `DiffLineType::File`
+/// line. `progress` is called with the files done and total before
+/// each file, returning `false` cancels (`None` is returned then)
+pub fn get_diff_folder<F>(
+    repo_path: &str,
+    folder: &str,
+    stage: bool,
+    options: Option<DiffOptions>,
+    mut progress: F,
+) -> Result<Option<FileDiff>>
+where
+    F: FnMut(usize, usize) -> bool,
+{
+    scope_time!("get_diff_folder");
+
+    let repo = utils::repo(repo_path)?;
+    let work_dir = work_dir(&repo)?;
+
+    let prefix = format!("{}/", folder.trim_end_matches('/'));
+    let status_type = if stage {
+        StatusType::Stage
+    } else {
+        StatusType::WorkingDir
+    };
+    let files: Vec<String> =
+        get_status(repo_path, status_type, true)?
+            .into_iter()
+            .map(|item| item.path)
+            .filter(|path| path.starts_with(&prefix))
+            .collect();
+
+    let mut res = FileDiff::default();
+
+    for (i, path) in files.iter().enumerate() {
+        if !progress(i, files.len()) {
+            return Ok(None);
+        }
+
+        let diff = get_diff_raw(&repo, path, stage, false, options)?;
+        res.append_file(
+            path,
+            raw_diff_to_file_diff(&diff, work_dir)?,
+        );
+    }
+
+    Ok(Some(res))
+}
+
+impl FileDiff {
+    /// appends the diff of one file to a combined diff,
+    /// behind a `DiffLineType::File` line with its `path`
+    pub(crate) fn append_file(&mut self, path: &str, file: Self) {
+        self.hunks.push(Hunk {
+            header_hash: hash(&path),
+            lines: vec![DiffLine {
+                content: path.to_string(),
+                line_type: DiffLineType::File,
+                position: DiffLinePosition::default(),
+                changes: Vec::new(),
+            }],
+        });
+        self.lines += 1 + file.lines;
+        self.hunks.extend(file.hunks);
+        self.sizes = (
+            self.sizes.0.saturating_add(file.sizes.0),
+            self.sizes.1.saturating_add(file.sizes.1),
+        );
+        self.size_delta =
+            self.size_delta.saturating_add(file.size_delta);
+    }
+}
+
+/// lines added and deleted in a file
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FileLineStats {
+    ///
+    pub path: String,
+    ///
+    pub additions: usize,
+    ///
+    pub deletions: usize,
+}
+
+/// line stats of every staged file (like `git diff --cached --numstat`),
+/// binary files count zero lines
+pub fn get_stage_stats(
+    repo_path: &str,
+) -> Result<Vec<FileLineStats>> {
+    scope_time!("get_stage_stats");
+
+    let repo = utils::repo(repo_path)?;
+    let diff = get_diff_raw(&repo, "", true, false, None)?;
+
+    let mut res = Vec::with_capacity(diff.deltas().len());
+    for (idx, delta) in diff.deltas().enumerate() {
+        let path = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .and_then(Path::to_str)
+            .map(String::from)
+            .unwrap_or_default();
+
+        let (additions, deletions) =
+            match Patch::from_diff(&diff, idx)? {
+                Some(patch) => {
+                    let (_, additions, deletions) =
+                        patch.line_stats()?;
+                    (additions, deletions)
+                }
+                None => (0, 0),
+            };
+
+        res.push(FileLineStats {
+            path,
+            additions,
+            deletions,
+        });
+    }
+
+    Ok(res)
+}
+
+/// the staged changes as one unified patch (like
+/// `git diff --cached`), e.g. to review them while committing
+pub fn get_stage_patch(repo_path: &str) -> Result<String> {
+    scope_time!("get_stage_patch");
+
+    let repo = utils::repo(repo_path)?;
+    let diff = get_diff_raw(&repo, "", true, false, None)?;
+
+    let mut res = String::new();
+    diff.print(DiffFormat::Patch, |_, _, line| {
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            res.push(line.origin());
+        }
+        res.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })?;
+
+    Ok(res)
+}
+
 ///
-fn raw_diff_to_file_diff<'a>(
+pub(crate) fn raw_diff_to_file_diff<'a>(
     diff: &'a Diff,
     work_dir: &Path,
 ) -> Result<FileDiff> {
@@ -200,11 +473,13 @@ fn raw_diff_to_file_diff<'a>(
         let adder = move |header: &HunkHeader,
                           lines: &Vec<DiffLine>| {
             let mut res = res_cell.borrow_mut();
+            let mut lines = lines.clone();
+            word_diff::mark_changes(&mut lines);
+            res.lines += lines.len();
             res.hunks.push(Hunk {
                 header_hash: hash(header),
-                lines: lines.clone(),
+                lines,
             });
-            res.lines += lines.len();
         };
 
         let res_cell = Rc::clone(&res);
@@ -235,16 +510,17 @@ fn raw_diff_to_file_diff<'a>(
 
                 let line_type = match line.origin() {
                     'H' => DiffLineType::Header,
-                    '<' | '-' => DiffLineType::Delete,
-                    '>' | '+' => DiffLineType::Add,
+                    '-' => DiffLineType::Delete,
+                    '+' => DiffLineType::Add,
+                    '=' | '<' | '>' => DiffLineType::NoNewline,
                     _ => DiffLineType::None,
                 };
 
                 let diff_line = DiffLine {
                     position: DiffLinePosition::from(&line),
-                    content: String::from_utf8_lossy(line.content())
-                        .to_string(),
+                    content: decode_guess(line.content()).to_string(),
                     line_type,
+                    changes: Vec::new(),
                 };
 
                 current_lines.push(diff_line);
@@ -344,12 +620,19 @@ fn new_file_content(path: &Path) -> Option<Vec<u8>> {
 
 #[cfg(test)]
 mod tests {
-    use super::{get_diff, get_diff_commit};
+    use super::{
+        get_diff, get_diff_commit, get_diff_folder, get_diff_range,
+        get_stage_patch, get_stage_stats, DiffLineType, DiffOptions,
+        FileLineStats, MergeDiff,
+    };
     use crate::error::Result;
     use crate::sync::{
         commit, stage_add_file,
         status::{get_status, StatusType},
-        tests::{get_statuses, repo_init, repo_init_empty},
+        tests::{
+            get_statuses, repo_init, repo_init_empty,
+            write_commit_file,
+        },
     };
     use std::{
         fs::{self, File},
@@ -373,14 +656,63 @@ mod tests {
 
         assert_eq!(get_statuses(repo_path), (1, 0));
 
-        let diff =
-            get_diff(repo_path, "foo/bar.txt".to_string(), false)
-                .unwrap();
+        let diff = get_diff(
+            repo_path,
+            "foo/bar.txt".to_string(),
+            false,
+            None,
+        )
+        .unwrap();
 
         assert_eq!(diff.hunks.len(), 1);
         assert_eq!(diff.hunks[0].lines[1].content, "test\n");
     }
 
+    #[test]
+    fn test_no_newline_at_eof() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(root.join("foo.txt"))
+            .unwrap()
+            .write_all(b"a\nb\n")
+            .unwrap();
+        stage_add_file(repo_path, Path::new("foo.txt")).unwrap();
+        commit(repo_path, "commit").unwrap();
+
+        File::create(root.join("foo.txt"))
+            .unwrap()
+            .write_all(b"a\nc")
+            .unwrap();
+
+        let diff =
+            get_diff(repo_path, "foo.txt".to_string(), false, None)
+                .unwrap();
+
+        let types = diff.hunks[0]
+            .lines
+            .iter()
+            .map(|l| l.line_type)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                DiffLineType::Header,
+                DiffLineType::None,
+                DiffLineType::Delete,
+                DiffLineType::Add,
+                DiffLineType::NoNewline,
+            ]
+        );
+
+        let marker = &diff.hunks[0].lines[4];
+        assert_eq!(
+            marker.content.trim(),
+            "\\ No newline at end of file"
+        );
+    }
+
     #[test]
     fn test_empty_repo() {
         let file_path = Path::new("foo.txt");
@@ -405,6 +737,7 @@ mod tests {
             repo_path,
             String::from(file_path.to_str().unwrap()),
             true,
+            None,
         )
         .unwrap();
 
@@ -472,8 +805,9 @@ mod tests {
 
         assert_eq!(get_statuses(repo_path), (1, 1));
 
-        let res = get_diff(repo_path, "bar.txt".to_string(), false)
-            .unwrap();
+        let res =
+            get_diff(repo_path, "bar.txt".to_string(), false, None)
+                .unwrap();
 
         assert_eq!(res.hunks.len(), 2)
     }
@@ -496,6 +830,7 @@ mod tests {
             sub_path.to_str().unwrap(),
             String::from(file_path.to_str().unwrap()),
             false,
+            None,
         )
         .unwrap();
 
@@ -522,6 +857,7 @@ mod tests {
             repo_path,
             String::from(file_path.to_str().unwrap()),
             false,
+            None,
         )
         .unwrap();
 
@@ -546,6 +882,7 @@ mod tests {
             repo_path,
             String::from(file_path.to_str().unwrap()),
             false,
+            None,
         )
         .unwrap();
 
@@ -576,8 +913,14 @@ mod tests {
 
         let id = commit(repo_path, "").unwrap();
 
-        let diff =
-            get_diff_commit(repo_path, id, String::new()).unwrap();
+        let diff = get_diff_commit(
+            repo_path,
+            id,
+            String::new(),
+            MergeDiff::default(),
+            None,
+        )
+        .unwrap();
 
         dbg!(&diff);
         assert_eq!(diff.sizes, (1, 2));
@@ -585,4 +928,232 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_diff_folder() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        File::create(root.join("a/foo.txt"))
+            .unwrap()
+            .write_all(b"foo\n")
+            .unwrap();
+        File::create(root.join("a/b/bar.txt"))
+            .unwrap()
+            .write_all(b"bar\n")
+            .unwrap();
+        File::create(root.join("other.txt"))
+            .unwrap()
+            .write_all(b"other\n")
+            .unwrap();
+
+        let mut calls = Vec::new();
+        let diff = get_diff_folder(
+            repo_path,
+            "a",
+            false,
+            None,
+            |done, total| {
+                calls.push((done, total));
+                true
+            },
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(calls, vec![(0, 2), (1, 2)]);
+
+        let files: Vec<&str> = diff
+            .hunks
+            .iter()
+            .flat_map(|hunk| hunk.lines.iter())
+            .filter(|line| line.line_type == DiffLineType::File)
+            .map(|line| line.content.as_str())
+            .collect();
+        assert_eq!(files, vec!["a/b/bar.txt", "a/foo.txt"]);
+
+        // separator + header + added line for each file
+        assert_eq!(diff.lines, 6);
+        assert_eq!(
+            diff.hunks.iter().map(|h| h.lines.len()).sum::<usize>(),
+            diff.lines
+        );
+
+        assert!(get_diff_folder(
+            repo_path,
+            "a",
+            false,
+            None,
+            |_, _| false
+        )
+        .unwrap()
+        .is_none());
+    }
+
+    #[test]
+    fn test_diff_range() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let from = write_commit_file(&repo, "a.txt", "a\n", "c1");
+        write_commit_file(&repo, "b.txt", "b\n", "c2");
+        let to = write_commit_file(&repo, "a.txt", "aa\n", "c3");
+
+        let diff = get_diff_range(repo_path, from, to, None).unwrap();
+
+        let files: Vec<&str> = diff
+            .hunks
+            .iter()
+            .flat_map(|hunk| hunk.lines.iter())
+            .filter(|line| line.line_type == DiffLineType::File)
+            .map(|line| line.content.as_str())
+            .collect();
+        assert_eq!(files, vec!["a.txt", "b.txt"]);
+
+        let changed = |line_type| {
+            diff.hunks
+                .iter()
+                .flat_map(|hunk| hunk.lines.iter())
+                .filter(|line| line.line_type == line_type)
+                .count()
+        };
+        assert_eq!(changed(DiffLineType::Add), 2);
+        assert_eq!(changed(DiffLineType::Delete), 1);
+
+        // the other way round
+        let diff = get_diff_range(repo_path, to, from, None).unwrap();
+        assert_eq!(
+            diff.hunks
+                .iter()
+                .flat_map(|hunk| hunk.lines.iter())
+                .filter(|line| line.line_type == DiffLineType::Delete)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_stage_stats() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(root.join("foo.txt"))
+            .unwrap()
+            .write_all(b"a\nb\n")
+            .unwrap();
+        stage_add_file(repo_path, Path::new("foo.txt")).unwrap();
+        commit(repo_path, "init").unwrap();
+
+        File::create(root.join("foo.txt"))
+            .unwrap()
+            .write_all(b"a\nc\nd\n")
+            .unwrap();
+        File::create(root.join("bar.txt"))
+            .unwrap()
+            .write_all(b"bar\n")
+            .unwrap();
+
+        assert!(get_stage_stats(repo_path).unwrap().is_empty());
+
+        stage_add_file(repo_path, Path::new("foo.txt")).unwrap();
+        stage_add_file(repo_path, Path::new("bar.txt")).unwrap();
+
+        assert_eq!(
+            get_stage_stats(repo_path).unwrap(),
+            vec![
+                FileLineStats {
+                    path: String::from("bar.txt"),
+                    additions: 1,
+                    deletions: 0,
+                },
+                FileLineStats {
+                    path: String::from("foo.txt"),
+                    additions: 2,
+                    deletions: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stage_patch() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(root.join("foo.txt"))
+            .unwrap()
+            .write_all(b"a\nb\n")
+            .unwrap();
+        stage_add_file(repo_path, Path::new("foo.txt")).unwrap();
+        commit(repo_path, "init").unwrap();
+
+        File::create(root.join("foo.txt"))
+            .unwrap()
+            .write_all(b"a\nc\n")
+            .unwrap();
+
+        assert!(get_stage_patch(repo_path).unwrap().is_empty());
+
+        stage_add_file(repo_path, Path::new("foo.txt")).unwrap();
+
+        let patch = get_stage_patch(repo_path).unwrap();
+        assert!(patch.starts_with("diff --git a/foo.txt b/foo.txt\n"));
+        assert!(patch.ends_with("@@ -1,2 +1,2 @@\n a\n-b\n+c\n"));
+    }
+
+    #[test]
+    fn test_diff_options() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(
+            &repo,
+            "foo.txt",
+            "a b\n1\n2\n3\n4\n5\n6\n7\nc\n",
+            "c1",
+        );
+        fs::write(
+            root.join("foo.txt"),
+            "a  b\n1\n2\n3\n4\n5\n6\n7\nd\n",
+        )
+        .unwrap();
+
+        let diff = |options: DiffOptions| {
+            get_diff(
+                repo_path,
+                "foo.txt".to_string(),
+                false,
+                Some(options),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(diff(DiffOptions::default()).hunks.len(), 2);
+
+        let ignored = diff(DiffOptions {
+            ignore_whitespace_change: true,
+            ..DiffOptions::default()
+        });
+        assert_eq!(ignored.hunks.len(), 1);
+        assert_eq!(ignored.hunks[0].lines[1].content, "5\n");
+
+        let joined = diff(DiffOptions {
+            interhunk_lines: 1,
+            ..DiffOptions::default()
+        });
+        assert_eq!(joined.hunks.len(), 1);
+
+        let no_context = diff(DiffOptions {
+            ignore_whitespace: true,
+            context: 0,
+            ..DiffOptions::default()
+        });
+        assert_eq!(no_context.lines, 3);
+    }
 }
@@ -0,0 +1,528 @@
+//! snapshots of workdir changes taken before discarding them, so the
+//! last discard can be undone
+
+use super::{
+    commit::signature_allow_undefined_name,
+    utils::{repo, work_dir},
+    CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{
+    build::TreeUpdateBuilder, Commit, FileMode, Oid, Repository,
+    Signature, StatusOptions,
+};
+use scopetime::scope_time;
+use std::{fs, path::Path};
+
+/// every backup is a commit on top of the previous one, its message
+/// lists the backed up paths (the ones missing in its tree were
+/// deleted in the workdir)
+const BACKUP_REF: &str = "refs/gitui/discarded";
+
+/// first line of the message of a backup
+const BACKUP_SUBJECT: &str = "discarded changes";
+
+/// older backups are dropped once there are more
+const MAX_BACKUPS: usize = 20;
+
+/// saves the workdir content of the changed files at or below
+/// `paths` (files or folders) before they get discarded, all of them
+/// in one backup. returns it, `None` if there was nothing to back up
+pub fn backup_discard(
+    repo_path: &str,
+    paths: &[&str],
+) -> Result<Option<CommitId>> {
+    scope_time!("backup_discard");
+
+    let repo = repo(repo_path)?;
+    let work_dir = work_dir(&repo)?;
+
+    let mut options = StatusOptions::default();
+    for path in paths {
+        options.pathspec(path);
+    }
+
+    let statuses = repo.statuses(Some(
+        options
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .exclude_submodules(true),
+    ))?;
+
+    let mut backed_up = Vec::new();
+    let mut update = TreeUpdateBuilder::new();
+
+    for entry in statuses.iter() {
+        if !entry.status().is_wt_new()
+            && !entry.status().is_wt_modified()
+            && !entry.status().is_wt_deleted()
+            && !entry.status().is_wt_typechange()
+        {
+            continue;
+        }
+
+        if let Some(entry_path) = entry.path() {
+            if let Some((blob, mode)) =
+                workdir_blob(&repo, &work_dir.join(entry_path))?
+            {
+                update.upsert(entry_path, blob, mode);
+            }
+            backed_up.push(entry_path.to_string());
+        }
+    }
+
+    if backed_up.is_empty() {
+        return Ok(None);
+    }
+
+    let empty = repo.find_tree(repo.treebuilder(None)?.write()?)?;
+    let tree =
+        repo.find_tree(update.create_updated(&repo, &empty)?)?;
+
+    let parent = backup_parent(&repo)?;
+    let parents: Vec<&Commit> = parent.iter().collect();
+
+    let signature = signature_allow_undefined_name(&repo)
+        .or_else(|_| Signature::now("gitui", "gitui"))?;
+
+    let id = repo.commit(
+        None,
+        &signature,
+        &signature,
+        &format!("{}\n\n{}\n", BACKUP_SUBJECT, backed_up.join("\n")),
+        &tree,
+        &parents,
+    )?;
+    repo.reference(BACKUP_REF, id, true, "discard backup")?;
+
+    Ok(Some(id.into()))
+}
+
+/// drops the backup `id` again if it is the last one, for a discard
+/// that failed after all
+pub fn drop_discard_backup(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<()> {
+    scope_time!("drop_discard_backup");
+
+    let repo = repo(repo_path)?;
+
+    if let Some(backup) = last_backup(&repo)? {
+        if backup.id() == id.into() {
+            pop_backup(&repo, &backup)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// paths of the last discard that was backed up
+pub fn get_discard_backup(
+    repo_path: &str,
+) -> Result<Option<Vec<String>>> {
+    scope_time!("get_discard_backup");
+
+    let repo = repo(repo_path)?;
+    let backup = last_backup(&repo)?;
+
+    Ok(backup.as_ref().map(backup_paths))
+}
+
+/// writes the files of the last backup back to the workdir (deleting
+/// the ones that were deleted) and drops that backup,
+/// returns the restored paths
+pub fn undo_discard(repo_path: &str) -> Result<Vec<String>> {
+    scope_time!("undo_discard");
+
+    let repo = repo(repo_path)?;
+    let work_dir = work_dir(&repo)?;
+
+    let backup = last_backup(&repo)?.ok_or_else(|| {
+        Error::Generic(String::from(
+            "no discarded changes to restore",
+        ))
+    })?;
+    let tree = backup.tree()?;
+    let paths = backup_paths(&backup);
+
+    for path in &paths {
+        let full_path = work_dir.join(path);
+
+        // never write through a symlink
+        if fs::symlink_metadata(&full_path)
+            .map_or(false, |meta| !meta.is_dir())
+        {
+            fs::remove_file(&full_path)?;
+        }
+
+        if let Ok(entry) = tree.get_path(Path::new(path)) {
+            let blob = repo.find_blob(entry.id())?;
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            restore_file(
+                &full_path,
+                blob.content(),
+                entry.filemode(),
+            )?;
+        }
+    }
+
+    pop_backup(&repo, &backup)?;
+
+    Ok(paths)
+}
+
+fn last_backup(repo: &Repository) -> Result<Option<Commit<'_>>> {
+    match repo.find_reference(BACKUP_REF) {
+        Ok(reference) => Ok(Some(reference.peel_to_commit()?)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// moves the ref back to the backup before `backup`
+fn pop_backup(repo: &Repository, backup: &Commit) -> Result<()> {
+    let mut reference = repo.find_reference(BACKUP_REF)?;
+    match backup.parents().next() {
+        Some(parent) => {
+            reference.set_target(parent.id(), "undo discard")?;
+        }
+        None => reference.delete()?,
+    }
+
+    Ok(())
+}
+
+/// the backup a new one goes on top of, the oldest ones are copied
+/// onto a shorter chain if there are `MAX_BACKUPS` already
+fn backup_parent(repo: &Repository) -> Result<Option<Commit<'_>>> {
+    let mut chain = Vec::new();
+    let mut next = last_backup(repo)?;
+    while let Some(backup) = next {
+        next = backup.parents().next();
+        chain.push(backup);
+    }
+
+    if chain.len() < MAX_BACKUPS {
+        return Ok(chain.into_iter().next());
+    }
+
+    chain.truncate(MAX_BACKUPS - 1);
+
+    let mut parent: Option<Commit> = None;
+    for backup in chain.iter().rev() {
+        let parents: Vec<&Commit> = parent.iter().collect();
+        let id = repo.commit(
+            None,
+            &backup.author(),
+            &backup.committer(),
+            &String::from_utf8_lossy(backup.message_bytes()),
+            &backup.tree()?,
+            &parents,
+        )?;
+        parent = Some(repo.find_commit(id)?);
+    }
+
+    Ok(parent)
+}
+
+/// blob and mode of a workdir file like git would store it, `None`
+/// if it is missing
+fn workdir_blob(
+    repo: &Repository,
+    full_path: &Path,
+) -> Result<Option<(Oid, FileMode)>> {
+    let meta = match fs::symlink_metadata(full_path) {
+        Ok(meta) => meta,
+        Err(_) => return Ok(None),
+    };
+
+    if meta.file_type().is_symlink() {
+        let target = fs::read_link(full_path)?;
+        let blob = repo.blob(target.to_string_lossy().as_bytes())?;
+        return Ok(Some((blob, FileMode::Link)));
+    }
+
+    if !meta.is_file() {
+        return Ok(None);
+    }
+
+    let mode = if is_executable(&meta) {
+        FileMode::BlobExecutable
+    } else {
+        FileMode::Blob
+    };
+
+    Ok(Some((repo.blob_path(full_path)?, mode)))
+}
+
+#[cfg(unix)]
+fn is_executable(meta: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    meta.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+const fn is_executable(_meta: &fs::Metadata) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn restore_file(
+    path: &Path,
+    content: &[u8],
+    mode: i32,
+) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if mode == i32::from(FileMode::Link) {
+        std::os::unix::fs::symlink(
+            String::from_utf8_lossy(content).as_ref(),
+            path,
+        )?;
+        return Ok(());
+    }
+
+    fs::write(path, content)?;
+
+    if mode == i32::from(FileMode::BlobExecutable) {
+        let mut permissions = fs::metadata(path)?.permissions();
+        permissions.set_mode(permissions.mode() | 0o111);
+        fs::set_permissions(path, permissions)?;
+    }
+
+    Ok(())
+}
+
+/// symlinks are written as files holding their target, like git does
+/// without symlink support
+#[cfg(not(unix))]
+fn restore_file(
+    path: &Path,
+    content: &[u8],
+    _mode: i32,
+) -> Result<()> {
+    fs::write(path, content)?;
+    Ok(())
+}
+
+fn backup_paths(backup: &Commit) -> Vec<String> {
+    backup
+        .message()
+        .unwrap_or_default()
+        .lines()
+        .skip(2)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hash,
+        sync::{
+            diff::get_diff,
+            discard_lines, reset_workdir,
+            tests::{repo_init, write_commit_file},
+            utils::repo_write_file,
+        },
+    };
+
+    #[test]
+    fn test_undo_reset_folder() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        fs::create_dir(root.join("foo")).unwrap();
+        write_commit_file(&repo, "foo/a.txt", "a", "c1");
+        write_commit_file(&repo, "foo/b.txt", "b", "c2");
+        repo_write_file(&repo, "foo/a.txt", "changed").unwrap();
+        fs::remove_file(root.join("foo/b.txt")).unwrap();
+        repo_write_file(&repo, "foo/new.txt", "new").unwrap();
+
+        assert_eq!(get_discard_backup(repo_path).unwrap(), None);
+
+        backup_discard(repo_path, &["foo"]).unwrap();
+        reset_workdir(repo_path, "foo").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.join("foo/a.txt")).unwrap(),
+            "a"
+        );
+        assert!(root.join("foo/b.txt").is_file());
+        assert!(!root.join("foo/new.txt").exists());
+        assert_eq!(
+            get_discard_backup(repo_path).unwrap().map(|p| p.len()),
+            Some(3)
+        );
+
+        undo_discard(repo_path).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.join("foo/a.txt")).unwrap(),
+            "changed"
+        );
+        assert!(!root.join("foo/b.txt").exists());
+        assert_eq!(
+            fs::read_to_string(root.join("foo/new.txt")).unwrap(),
+            "new"
+        );
+        assert_eq!(get_discard_backup(repo_path).unwrap(), None);
+        assert!(undo_discard(repo_path).is_err());
+    }
+
+    #[test]
+    fn test_undo_in_order() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a.txt", "1\n2\n", "c1");
+
+        repo_write_file(&repo, "a.txt", "1\n2\n3\n").unwrap();
+        backup_discard(repo_path, &["a.txt"]).unwrap();
+        reset_workdir(repo_path, "a.txt").unwrap();
+
+        repo_write_file(&repo, "a.txt", "0\n1\n2\n").unwrap();
+        backup_discard(repo_path, &["a.txt"]).unwrap();
+        let diff =
+            get_diff(repo_path, "a.txt".into(), false, None).unwrap();
+        discard_lines(
+            repo_path,
+            "a.txt",
+            &[diff.hunks[0].lines[1].position],
+            hash(&diff),
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_discard_backup(repo_path).unwrap(),
+            Some(vec![String::from("a.txt")])
+        );
+
+        undo_discard(repo_path).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).unwrap(),
+            "0\n1\n2\n"
+        );
+
+        undo_discard(repo_path).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).unwrap(),
+            "1\n2\n3\n"
+        );
+    }
+
+    #[test]
+    fn test_backup_several_paths() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a.txt", "a", "c1");
+        write_commit_file(&repo, "b.txt", "b", "c2");
+        repo_write_file(&repo, "a.txt", "a2").unwrap();
+        repo_write_file(&repo, "b.txt", "b2").unwrap();
+        repo_write_file(&repo, "c.txt", "c2").unwrap();
+
+        backup_discard(repo_path, &["a.txt", "b.txt"]).unwrap();
+
+        assert_eq!(
+            get_discard_backup(repo_path).unwrap(),
+            Some(vec![String::from("a.txt"), String::from("b.txt")])
+        );
+    }
+
+    #[test]
+    fn test_drop_failed_backup() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a.txt", "a", "c1");
+        repo_write_file(&repo, "a.txt", "a2").unwrap();
+        let first = backup_discard(repo_path, &["a.txt"]).unwrap();
+        repo_write_file(&repo, "a.txt", "a3").unwrap();
+        let second = backup_discard(repo_path, &["a.txt"]).unwrap();
+
+        // only the last one is dropped
+        drop_discard_backup(repo_path, first.unwrap()).unwrap();
+        drop_discard_backup(repo_path, second.unwrap()).unwrap();
+
+        undo_discard(repo_path).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).unwrap(),
+            "a2"
+        );
+        assert_eq!(get_discard_backup(repo_path).unwrap(), None);
+        assert_eq!(
+            backup_discard(repo_path, &["b.txt"]).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_backup_limit() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        for i in 0..MAX_BACKUPS + 3 {
+            repo_write_file(&repo, "a.txt", &i.to_string()).unwrap();
+            backup_discard(repo_path, &["a.txt"]).unwrap();
+        }
+
+        let mut restored = Vec::new();
+        while undo_discard(repo_path).is_ok() {
+            restored.push(
+                fs::read_to_string(root.join("a.txt")).unwrap(),
+            );
+        }
+
+        assert_eq!(restored.len(), MAX_BACKUPS);
+        assert_eq!(restored[0], (MAX_BACKUPS + 2).to_string());
+        assert_eq!(restored[MAX_BACKUPS - 1], "3");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_undo_keeps_modes() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "run.sh", "echo", "c1");
+        repo_write_file(&repo, "run.sh", "echo 2").unwrap();
+        fs::set_permissions(
+            root.join("run.sh"),
+            fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink("run.sh", root.join("link"))
+            .unwrap();
+
+        backup_discard(repo_path, &["run.sh", "link"]).unwrap();
+        reset_workdir(repo_path, "run.sh").unwrap();
+        fs::remove_file(root.join("link")).unwrap();
+
+        undo_discard(repo_path).unwrap();
+
+        let mode = fs::metadata(root.join("run.sh"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o111, 0o111);
+        assert_eq!(
+            fs::read_link(root.join("link")).unwrap(),
+            Path::new("run.sh")
+        );
+    }
+}
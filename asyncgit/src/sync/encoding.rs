@@ -0,0 +1,166 @@
+//! decoding of text that is not necessarily utf8
+
+use std::borrow::Cow;
+
+/// windows-1252 differs from latin1 only in `0x80..=0x9F`,
+/// unassigned bytes map to the C1 control like in the WHATWG spec
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}',
+    '\u{2026}', '\u{2020}', '\u{2021}', '\u{2C6}', '\u{2030}',
+    '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}',
+    '\u{2022}', '\u{2013}', '\u{2014}', '\u{2DC}', '\u{2122}',
+    '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+];
+
+/// encodings that are decoded without lookup tables of an external
+/// crate, which covers what is common in older western repos
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Encoding {
+    ///
+    Utf8,
+    /// iso-8859-1
+    Latin1,
+    /// iso-8859-15
+    Latin9,
+    ///
+    Windows1252,
+}
+
+impl Encoding {
+    /// `None` for labels we cannot decode (like `Shift_JIS`)
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.trim().to_lowercase().as_str() {
+            "utf-8" | "utf8" | "us-ascii" | "ascii" => {
+                Some(Self::Utf8)
+            }
+            "iso-8859-1" | "iso8859-1" | "iso_8859-1" | "latin1"
+            | "latin-1" | "l1" => Some(Self::Latin1),
+            "iso-8859-15" | "iso8859-15" | "iso_8859-15"
+            | "latin9" | "latin-9" => Some(Self::Latin9),
+            "windows-1252" | "cp1252" => Some(Self::Windows1252),
+            _ => None,
+        }
+    }
+
+    ///
+    pub fn decode(self, bytes: &[u8]) -> Cow<'_, str> {
+        match self {
+            Self::Utf8 => String::from_utf8_lossy(bytes),
+            Self::Latin1 => Cow::from(
+                bytes
+                    .iter()
+                    .map(|b| char::from(*b))
+                    .collect::<String>(),
+            ),
+            Self::Latin9 => Cow::from(
+                bytes.iter().map(|b| latin9(*b)).collect::<String>(),
+            ),
+            Self::Windows1252 => Cow::from(
+                bytes
+                    .iter()
+                    .map(|b| windows_1252(*b))
+                    .collect::<String>(),
+            ),
+        }
+    }
+}
+
+fn windows_1252(b: u8) -> char {
+    match b {
+        0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(b - 0x80)],
+        _ => char::from(b),
+    }
+}
+
+fn latin9(b: u8) -> char {
+    match b {
+        0xA4 => '\u{20AC}',
+        0xA6 => '\u{160}',
+        0xA8 => '\u{161}',
+        0xB4 => '\u{17D}',
+        0xB8 => '\u{17E}',
+        0xBC => '\u{152}',
+        0xBD => '\u{153}',
+        0xBE => '\u{178}',
+        _ => char::from(b),
+    }
+}
+
+/// decodes `bytes` according to the `encoding` header of a commit.
+/// the second value is the label if it is not supported, the text
+/// is lossy utf8 then.
+pub fn decode_with_label<'a>(
+    bytes: &'a [u8],
+    label: Option<&str>,
+) -> (Cow<'a, str>, Option<String>) {
+    match label {
+        None => (String::from_utf8_lossy(bytes), None),
+        Some(label) => Encoding::from_label(label).map_or_else(
+            || {
+                (
+                    String::from_utf8_lossy(bytes),
+                    Some(label.to_string()),
+                )
+            },
+            |encoding| (encoding.decode(bytes), None),
+        ),
+    }
+}
+
+/// content of unknown encoding (like file content in a diff): utf8
+/// if valid, otherwise most likely a legacy 8bit encoding
+pub fn decode_guess(bytes: &[u8]) -> Cow<'_, str> {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => Cow::from(s),
+        Err(_) => Encoding::Windows1252.decode(bytes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels() {
+        assert_eq!(
+            Encoding::from_label("ISO-8859-1"),
+            Some(Encoding::Latin1)
+        );
+        assert_eq!(
+            Encoding::from_label(" cp1252"),
+            Some(Encoding::Windows1252)
+        );
+        assert_eq!(Encoding::from_label("Shift_JIS"), None);
+    }
+
+    #[test]
+    fn test_decode() {
+        let bytes = b"caf\xe9 \x80 \xa4";
+
+        assert_eq!(Encoding::Latin1.decode(bytes), "café \u{80} ¤");
+        assert_eq!(Encoding::Latin9.decode(bytes), "café \u{80} €");
+        assert_eq!(Encoding::Windows1252.decode(bytes), "café € ¤");
+    }
+
+    #[test]
+    fn test_decode_with_label() {
+        assert_eq!(
+            decode_with_label(b"caf\xe9", Some("latin1")),
+            (Cow::from("café"), None)
+        );
+        assert_eq!(
+            decode_with_label(b"caf\xe9", Some("Shift_JIS")),
+            (
+                Cow::from("caf\u{FFFD}"),
+                Some(String::from("Shift_JIS"))
+            )
+        );
+    }
+
+    #[test]
+    fn test_decode_guess() {
+        assert_eq!(decode_guess("café".as_bytes()), "café");
+        assert_eq!(decode_guess(b"\x93caf\xe9\x94"), "“café”");
+    }
+}
@@ -0,0 +1,188 @@
+//! commits changing a file (like `git log --follow`)
+
+use super::{utils, CommitId, LogWalker};
+use crate::error::Result;
+use git2::{Commit, Delta, DiffFindOptions, Oid, Repository};
+use scopetime::scope_time;
+use std::path::Path;
+
+/// commits read from the walk at once
+const BATCH_SIZE: usize = 1000;
+
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileHistoryEntry {
+    ///
+    pub commit: CommitId,
+    /// the file is named like this in `commit`
+    pub path: String,
+}
+
+/// commits reachable from `HEAD` that changed `path`, newest first.
+/// a rename is followed to the older name of the file, merges are
+/// left out unless the file differs from all their parents
+pub fn file_history(
+    repo_path: &str,
+    path: &str,
+) -> Result<Vec<FileHistoryEntry>> {
+    scope_time!("file_history");
+
+    let repo = utils::repo(repo_path)?;
+    let mut walker = LogWalker::new(&repo);
+
+    let mut path = path.to_string();
+    let mut res = Vec::new();
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+
+    'walk: loop {
+        batch.clear();
+        if walker.read(&mut batch, BATCH_SIZE)? == 0 {
+            break;
+        }
+
+        for id in &batch {
+            let commit = repo.find_commit((*id).into())?;
+            let blob = blob_id(&commit, &path)?;
+
+            let mut parent_blobs = Vec::new();
+            for parent in commit.parents() {
+                parent_blobs.push(blob_id(&parent, &path)?);
+            }
+
+            // a root commit without the file has no parent to match
+            if parent_blobs.contains(&blob)
+                || (blob.is_none() && parent_blobs.is_empty())
+            {
+                continue;
+            }
+
+            res.push(FileHistoryEntry {
+                commit: *id,
+                path: path.clone(),
+            });
+
+            // appeared here: renamed or added
+            if blob.is_some()
+                && parent_blobs.first().map_or(true, Option::is_none)
+            {
+                match renamed_from(&repo, &commit, &path)? {
+                    Some(old_path) => path = old_path,
+                    None => break 'walk,
+                }
+            }
+        }
+    }
+
+    Ok(res)
+}
+
+fn blob_id(commit: &Commit, path: &str) -> Result<Option<Oid>> {
+    match commit.tree()?.get_path(Path::new(path)) {
+        Ok(entry) => Ok(Some(entry.id())),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// old name of `path` if `commit` renamed it (compared to its first
+/// parent)
+fn renamed_from(
+    repo: &Repository,
+    commit: &Commit,
+    path: &str,
+) -> Result<Option<String>> {
+    if commit.parent_count() == 0 {
+        return Ok(None);
+    }
+
+    let mut diff = repo.diff_tree_to_tree(
+        Some(&commit.parent(0)?.tree()?),
+        Some(&commit.tree()?),
+        None,
+    )?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+    Ok(diff
+        .deltas()
+        .find(|delta| {
+            delta.status() == Delta::Renamed
+                && delta.new_file().path() == Some(Path::new(path))
+        })
+        .and_then(|delta| delta.old_file().path())
+        .map(|old| old.to_string_lossy().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        commit, stage_add_file, stage_addremoved,
+        tests::{repo_init_empty, write_commit_file},
+    };
+    use std::fs;
+
+    fn commits(history: &[FileHistoryEntry]) -> Vec<CommitId> {
+        history.iter().map(|e| e.commit).collect()
+    }
+
+    #[test]
+    fn test_history() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "b.txt", "b", "c0");
+        let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+        write_commit_file(&repo, "b.txt", "bb", "c2");
+        let c3 = write_commit_file(&repo, "a.txt", "aa", "c3");
+
+        let history = file_history(repo_path, "a.txt").unwrap();
+
+        assert_eq!(commits(&history), vec![c3, c1]);
+        assert!(history.iter().all(|e| e.path == "a.txt"));
+    }
+
+    #[test]
+    fn test_follow_rename() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let content =
+            "some lines\nto be found\nagain after\nthe move\n";
+        let c1 = write_commit_file(&repo, "old.txt", content, "c1");
+
+        fs::rename(root.join("old.txt"), root.join("new.txt"))
+            .unwrap();
+        stage_add_file(repo_path, Path::new("new.txt")).unwrap();
+        stage_addremoved(repo_path, Path::new("old.txt")).unwrap();
+        let c2 = commit(repo_path, "rename").unwrap();
+
+        fs::write(root.join("new.txt"), format!("{}more\n", content))
+            .unwrap();
+        stage_add_file(repo_path, Path::new("new.txt")).unwrap();
+        let c3 = commit(repo_path, "c3").unwrap();
+
+        let history = file_history(repo_path, "new.txt").unwrap();
+
+        assert_eq!(commits(&history), vec![c3, c2, c1]);
+        assert_eq!(history[1].path, "new.txt");
+        assert_eq!(history[2].path, "old.txt");
+    }
+
+    #[test]
+    fn test_deleted_file() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+        fs::remove_file(root.join("a.txt")).unwrap();
+        stage_addremoved(repo_path, Path::new("a.txt")).unwrap();
+        let c2 = commit(repo_path, "delete").unwrap();
+
+        let history = file_history(repo_path, "a.txt").unwrap();
+
+        assert_eq!(commits(&history), vec![c2, c1]);
+    }
+}
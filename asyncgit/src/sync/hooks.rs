@@ -11,6 +11,7 @@ use std::{
 const HOOK_POST_COMMIT: &str = ".git/hooks/post-commit";
 const HOOK_PRE_COMMIT: &str = ".git/hooks/pre-commit";
 const HOOK_COMMIT_MSG: &str = ".git/hooks/commit-msg";
+const HOOK_PREPARE_COMMIT_MSG: &str = ".git/hooks/prepare-commit-msg";
 const HOOK_COMMIT_MSG_TEMP_FILE: &str = ".git/COMMIT_EDITMSG";
 
 /// this hook is documented here https://git-scm.com/docs/githooks#_commit_msg
@@ -46,6 +47,64 @@ pub fn hooks_commit_msg(
     }
 }
 
+/// what the message handed to `prepare-commit-msg` was prefilled
+/// with, the hook gets it as its source argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrepareCommitMsgSource {
+    /// `commit.template`
+    Template,
+    /// the message of HEAD, which is amended
+    Amend,
+}
+
+impl PrepareCommitMsgSource {
+    const fn args(self) -> &'static [&'static str] {
+        match self {
+            Self::Template => &["template"],
+            // what git passes on `--amend`
+            Self::Amend => &["commit", "HEAD"],
+        }
+    }
+}
+
+/// this hook is documented here https://git-scm.com/docs/githooks#_prepare_commit_msg
+/// it gets the message in the same temp file as `commit-msg` and the
+/// `source` of it, `None` for a message started from scratch
+pub fn hooks_prepare_commit_msg(
+    repo_path: &str,
+    source: Option<PrepareCommitMsgSource>,
+    msg: &mut String,
+) -> Result<HookResult> {
+    scope_time!("hooks_prepare_commit_msg");
+
+    let work_dir = work_dir_as_string(repo_path)?;
+
+    if hook_runable(work_dir.as_str(), HOOK_PREPARE_COMMIT_MSG) {
+        let temp_file = Path::new(work_dir.as_str())
+            .join(HOOK_COMMIT_MSG_TEMP_FILE);
+        File::create(&temp_file)?.write_all(msg.as_bytes())?;
+
+        let mut args = vec![HOOK_COMMIT_MSG_TEMP_FILE];
+        args.extend(
+            source.map_or(&[][..], PrepareCommitMsgSource::args),
+        );
+
+        let res = run_hook(
+            work_dir.as_str(),
+            HOOK_PREPARE_COMMIT_MSG,
+            &args,
+        )?;
+
+        // load possibly altered msg
+        msg.clear();
+        File::open(temp_file)?.read_to_string(msg)?;
+
+        Ok(res)
+    } else {
+        Ok(HookResult::Ok)
+    }
+}
+
 /// this hook is documented here https://git-scm.com/docs/githooks#_pre_commit
 ///
 pub fn hooks_pre_commit(repo_path: &str) -> Result<HookResult> {
@@ -210,6 +269,44 @@ exit 0
         assert_eq!(msg, String::from("test"));
     }
 
+    #[test]
+    fn test_hooks_prepare_commit_msg() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let hook = b"#!/bin/sh
+echo \"source:$2 $3\" >> $1
+exit 0
+        ";
+
+        create_hook(root, HOOK_PREPARE_COMMIT_MSG, hook);
+
+        let mut msg = String::from("test\n");
+        let res = hooks_prepare_commit_msg(repo_path, None, &mut msg)
+            .unwrap();
+        assert_eq!(res, HookResult::Ok);
+        assert_eq!(msg, String::from("test\nsource: \n"));
+
+        let mut msg = String::new();
+        hooks_prepare_commit_msg(
+            repo_path,
+            Some(PrepareCommitMsgSource::Template),
+            &mut msg,
+        )
+        .unwrap();
+        assert_eq!(msg, String::from("source:template \n"));
+
+        let mut msg = String::new();
+        hooks_prepare_commit_msg(
+            repo_path,
+            Some(PrepareCommitMsgSource::Amend),
+            &mut msg,
+        )
+        .unwrap();
+        assert_eq!(msg, String::from("source:commit HEAD\n"));
+    }
+
     #[test]
     fn test_pre_commit_sh() {
         let (_td, repo) = repo_init().unwrap();
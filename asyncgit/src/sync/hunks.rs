@@ -1,5 +1,10 @@
 use super::{
-    diff::{get_diff_raw, HunkHeader},
+    diff::{
+        get_diff, get_diff_raw, DiffLine, DiffLineType, Hunk,
+        HunkHeader,
+    },
+    patches::hunk_side,
+    status::{get_status, StatusType},
     utils::repo,
 };
 use crate::{
@@ -8,6 +13,7 @@ use crate::{
 };
 use git2::{ApplyLocation, ApplyOptions, Diff};
 use scopetime::scope_time;
+use std::ops::Range;
 
 ///
 pub fn stage_hunk(
@@ -147,12 +153,192 @@ pub fn unstage_hunk(
     Ok(count == 1)
 }
 
+/// unstaged hunk yielded by `UnstagedHunks`
+#[derive(Debug, Clone)]
+pub struct UnstagedHunk {
+    ///
+    pub path: String,
+    /// an untracked file is a single hunk, staged as a whole
+    pub untracked: bool,
+    ///
+    pub hunk: Hunk,
+    /// index of the file and count of all unstaged files
+    pub file: (usize, usize),
+    /// index of the hunk and count of all hunks of the file
+    pub index: (usize, usize),
+}
+
+/// walks the hunks of all unstaged files (like `git add -p`).
+/// a file is diffed again for each of its hunks, continuing after
+/// the lines of the workdir the hunk yielded last spans (staging does
+/// not change them), so it does not matter if that hunk was staged,
+/// partly staged or skipped
+pub struct UnstagedHunks {
+    repo_path: String,
+    files: Vec<String>,
+    file_idx: usize,
+    /// first line of the workdir not yet walked in the current file
+    line: u32,
+}
+
+impl UnstagedHunks {
+    ///
+    pub fn new(repo_path: &str) -> Result<Self> {
+        let files =
+            get_status(repo_path, StatusType::WorkingDir, true)?
+                .into_iter()
+                .map(|item| item.path)
+                .collect();
+
+        Ok(Self {
+            repo_path: repo_path.to_string(),
+            files,
+            file_idx: 0,
+            line: 0,
+        })
+    }
+
+    fn next_hunk(&mut self) -> Result<Option<UnstagedHunk>> {
+        while let Some(path) = self.files.get(self.file_idx) {
+            let diff =
+                get_diff(&self.repo_path, path.clone(), false, None)?;
+
+            let next = diff.hunks.iter().enumerate().find_map(
+                |(index, hunk)| {
+                    workdir_lines(hunk)
+                        .filter(|(start, _)| *start >= self.line)
+                        .map(|lines| (index, lines))
+                },
+            );
+
+            if let Some((index, (start, lines))) = next {
+                self.line = start + lines.max(1);
+
+                return Ok(Some(UnstagedHunk {
+                    path: path.clone(),
+                    untracked: diff.untracked,
+                    hunk: diff.hunks[index].clone(),
+                    file: (self.file_idx, self.files.len()),
+                    index: (index, diff.hunks.len()),
+                }));
+            }
+
+            self.file_idx += 1;
+            self.line = 0;
+        }
+
+        Ok(None)
+    }
+}
+
+impl Iterator for UnstagedHunks {
+    type Item = Result<UnstagedHunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_hunk().transpose()
+    }
+}
+
+/// start and count of the workdir lines of a hunk of an unstaged diff
+fn workdir_lines(hunk: &Hunk) -> Option<(u32, u32)> {
+    hunk_side(&hunk.lines.first()?.content, '+')
+}
+
+/// cuts a hunk into smaller ones at the unchanged lines between its
+/// changes (like `s` of `git add -p`), the unchanged lines in between
+/// are part of both neighbours. empty if there is nothing to split
+pub fn split_hunk(hunk: &Hunk) -> Vec<Hunk> {
+    let body = hunk.lines.get(1..).unwrap_or_default();
+
+    let mut changes: Vec<Range<usize>> = Vec::new();
+    for (i, line) in body.iter().enumerate() {
+        let extends = changes.last().map_or(false, |c| c.end == i);
+        match line.line_type {
+            DiffLineType::Add | DiffLineType::Delete if extends => {
+                if let Some(last) = changes.last_mut() {
+                    last.end = i + 1;
+                }
+            }
+            DiffLineType::Add | DiffLineType::Delete => {
+                changes.push(i..i + 1);
+            }
+            DiffLineType::NoNewline if extends => {
+                if let Some(last) = changes.last_mut() {
+                    last.end = i + 1;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    if changes.len() < 2 {
+        return Vec::new();
+    }
+
+    (0..changes.len())
+        .map(|i| {
+            let start = if i == 0 { 0 } else { changes[i - 1].end };
+            let end =
+                changes.get(i + 1).map_or(body.len(), |c| c.start);
+            hunk_part(&body[start..end])
+        })
+        .collect()
+}
+
+fn hunk_part(lines: &[DiffLine]) -> Hunk {
+    let count = |typ: DiffLineType| {
+        lines
+            .iter()
+            .filter(|l| {
+                l.line_type == DiffLineType::None
+                    || l.line_type == typ
+            })
+            .count() as u32
+    };
+
+    let header = HunkHeader {
+        old_start: lines
+            .iter()
+            .find_map(|l| l.position.old_lineno)
+            .unwrap_or_default(),
+        old_lines: count(DiffLineType::Delete),
+        new_start: lines
+            .iter()
+            .find_map(|l| l.position.new_lineno)
+            .unwrap_or_default(),
+        new_lines: count(DiffLineType::Add),
+    };
+
+    let mut part = vec![DiffLine {
+        content: format!(
+            "@@ -{},{} +{},{} @@\n",
+            header.old_start,
+            header.old_lines,
+            header.new_start,
+            header.new_lines
+        ),
+        line_type: DiffLineType::Header,
+        ..DiffLine::default()
+    }];
+    part.extend_from_slice(lines);
+
+    Hunk {
+        header_hash: hash(&header),
+        lines: part,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         error::Result,
-        sync::{diff::get_diff, tests::repo_init_empty},
+        sync::{
+            diff::get_diff,
+            stage_lines,
+            tests::{repo_init, repo_init_empty, write_commit_file},
+            utils::repo_write_file,
+        },
     };
     use std::{
         fs::{self, File},
@@ -176,6 +362,7 @@ mod tests {
             sub_path.to_str().unwrap(),
             String::from(file_path.to_str().unwrap()),
             false,
+            None,
         )?;
 
         assert!(reset_hunk(
@@ -187,4 +374,94 @@ mod tests {
 
         Ok(())
     }
+
+    fn numbered_lines(changed: &[usize]) -> String {
+        (1..=20)
+            .map(|i| {
+                if changed.contains(&i) {
+                    format!("{} changed\n", i)
+                } else {
+                    format!("{}\n", i)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_unstaged_hunks() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a.txt", &numbered_lines(&[]), "c1");
+        write_commit_file(&repo, "b.txt", &numbered_lines(&[]), "c2");
+        repo_write_file(&repo, "a.txt", &numbered_lines(&[2, 18]))?;
+        repo_write_file(&repo, "b.txt", &numbered_lines(&[10]))?;
+        repo_write_file(&repo, "c.txt", "new\n")?;
+
+        let mut hunks = UnstagedHunks::new(repo_path)?;
+
+        let first = hunks.next().unwrap()?;
+        assert_eq!(first.path, "a.txt");
+        assert_eq!(first.file, (0, 3));
+        assert_eq!(first.index, (0, 2));
+        stage_hunk(repo_path, first.path, first.hunk.header_hash)?;
+
+        // the staged hunk is gone from the unstaged diff
+        let second = hunks.next().unwrap()?;
+        assert_eq!(second.path, "a.txt");
+        assert_eq!(second.index, (0, 1));
+
+        // skipped, but not yielded again
+        let third = hunks.next().unwrap()?;
+        assert_eq!(third.path, "b.txt");
+        assert_eq!(third.file, (1, 3));
+
+        let fourth = hunks.next().unwrap()?;
+        assert_eq!(fourth.path, "c.txt");
+        assert!(fourth.untracked);
+
+        assert!(hunks.next().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_hunk() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a.txt", &numbered_lines(&[]), "c1");
+        repo_write_file(&repo, "a.txt", &numbered_lines(&[2, 6]))?;
+
+        let diff = get_diff(repo_path, "a.txt".into(), false, None)?;
+        assert_eq!(diff.hunks.len(), 1);
+
+        let parts = split_hunk(&diff.hunks[0]);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].lines[0].content, "@@ -1,5 +1,5 @@\n");
+        assert_eq!(parts[1].lines[0].content, "@@ -3,7 +3,7 @@\n");
+        assert!(split_hunk(&parts[1]).is_empty());
+
+        let positions: Vec<_> = parts[1]
+            .lines
+            .iter()
+            .filter(|l| {
+                l.line_type == DiffLineType::Add
+                    || l.line_type == DiffLineType::Delete
+            })
+            .map(|l| l.position)
+            .collect();
+        stage_lines(repo_path, "a.txt", false, &positions)?;
+
+        let staged = get_diff(repo_path, "a.txt".into(), true, None)?;
+        assert_eq!(staged.hunks.len(), 1);
+        assert_eq!(
+            staged.hunks[0].lines[0].content,
+            "@@ -3,7 +3,7 @@\n"
+        );
+
+        Ok(())
+    }
 }
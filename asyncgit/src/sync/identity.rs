@@ -0,0 +1,105 @@
+//! `user.name`/`user.email` used for commits
+
+use super::utils::repo;
+use crate::error::Result;
+use git2::{Config, ConfigLevel};
+use scopetime::scope_time;
+
+///
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Identity {
+    ///
+    pub name: Option<String>,
+    ///
+    pub email: Option<String>,
+    /// both are set in the repo's own config, otherwise they
+    /// come from the global (or system) config, if at all
+    pub local: bool,
+}
+
+impl Identity {
+    ///
+    pub fn is_complete(&self) -> bool {
+        self.name.is_some() && self.email.is_some()
+    }
+}
+
+fn get_string(cfg: &Config, key: &str) -> Option<String> {
+    cfg.get_string(key).ok().filter(|s| !s.is_empty())
+}
+
+/// identity commits in `repo_path` are made with
+pub fn get_identity(repo_path: &str) -> Result<Identity> {
+    scope_time!("get_identity");
+
+    let repo = repo(repo_path)?;
+    let cfg = repo.config()?;
+
+    let local =
+        cfg.open_level(ConfigLevel::Local).map_or(false, |local| {
+            get_string(&local, "user.name").is_some()
+                && get_string(&local, "user.email").is_some()
+        });
+
+    Ok(Identity {
+        name: get_string(&cfg, "user.name"),
+        email: get_string(&cfg, "user.email"),
+        local,
+    })
+}
+
+/// writes `name` and `email` to the repo's own config
+/// (like `git config --local`)
+pub fn set_local_identity(
+    repo_path: &str,
+    name: &str,
+    email: &str,
+) -> Result<()> {
+    scope_time!("set_local_identity");
+
+    let repo = repo(repo_path)?;
+    let mut cfg = repo.config()?.open_level(ConfigLevel::Local)?;
+
+    cfg.set_str("user.name", name)?;
+    cfg.set_str("user.email", email)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::tests::repo_init;
+
+    #[test]
+    fn test_identity() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let identity = get_identity(repo_path).unwrap();
+        assert_eq!(identity.name, Some(String::from("name")));
+        assert_eq!(identity.email, Some(String::from("email")));
+        assert!(identity.local);
+
+        repo.config().unwrap().remove("user.email").unwrap();
+
+        let identity = get_identity(repo_path).unwrap();
+        assert!(!identity.local);
+        assert!(!identity.is_complete());
+    }
+
+    #[test]
+    fn test_set_local_identity() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        set_local_identity(repo_path, "work", "me@work.com").unwrap();
+
+        let identity = get_identity(repo_path).unwrap();
+        assert_eq!(identity.name, Some(String::from("work")));
+        assert_eq!(identity.email, Some(String::from("me@work.com")));
+        assert!(identity.local);
+    }
+}
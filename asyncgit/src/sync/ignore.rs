@@ -1,14 +1,39 @@
 use super::utils::{repo, work_dir};
-use crate::error::Result;
+use crate::error::{Error, Result};
 use scopetime::scope_time;
 use std::{
-    fs::{File, OpenOptions},
+    env,
+    fs::{self, File, OpenOptions},
     io::{Read, Seek, SeekFrom, Write},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 static GITIGNORE: &str = ".gitignore";
 
+/// patterns to ignore `path` by: the path itself, its extension and
+/// the folder it is in (a folder ends with `/`)
+pub fn ignore_patterns(path: &str, is_folder: bool) -> Vec<String> {
+    let path = path.trim_end_matches('/');
+
+    if is_folder {
+        return vec![format!("{}/", path)];
+    }
+
+    let mut res = vec![path.to_string()];
+
+    let file = Path::new(path);
+    if let Some(ext) = file.extension() {
+        res.push(format!("*.{}", ext.to_string_lossy()));
+    }
+    if let Some(folder) = file.parent() {
+        if !folder.as_os_str().is_empty() {
+            res.push(format!("{}/", folder.to_string_lossy()));
+        }
+    }
+
+    res
+}
+
 /// add file or path to root ignore file
 pub fn add_to_ignore(
     repo_path: &str,
@@ -18,10 +43,77 @@ pub fn add_to_ignore(
 
     let repo = repo(repo_path)?;
 
+    append_pattern(&work_dir(&repo)?.join(GITIGNORE), path_to_ignore)
+}
+
+/// add a pattern to the ignore file of the user (`core.excludesfile`,
+/// by default `~/.config/git/ignore`)
+pub fn add_to_global_ignore(
+    repo_path: &str,
+    pattern: &str,
+) -> Result<()> {
+    scope_time!("add_to_global_ignore");
+
+    let ignore_file = global_ignore_file(repo_path)?;
+
+    if let Some(folder) = ignore_file.parent() {
+        fs::create_dir_all(folder)?;
+    }
+
+    append_pattern(&ignore_file, pattern)
+}
+
+/// content of the root ignore file, empty if there is none
+pub fn read_gitignore(repo_path: &str) -> Result<String> {
+    scope_time!("read_gitignore");
+
+    let repo = repo(repo_path)?;
     let ignore_file = work_dir(&repo)?.join(GITIGNORE);
 
-    let optional_newline = ignore_file.exists()
-        && !file_ends_with_newline(&ignore_file)?;
+    if ignore_file.exists() {
+        Ok(fs::read_to_string(ignore_file)?)
+    } else {
+        Ok(String::new())
+    }
+}
+
+/// replaces the content of the root ignore file
+pub fn write_gitignore(repo_path: &str, content: &str) -> Result<()> {
+    scope_time!("write_gitignore");
+
+    let repo = repo(repo_path)?;
+
+    fs::write(work_dir(&repo)?.join(GITIGNORE), content)?;
+
+    Ok(())
+}
+
+///
+pub fn global_ignore_file(repo_path: &str) -> Result<PathBuf> {
+    let repo = repo(repo_path)?;
+
+    if let Ok(path) = repo.config()?.get_path("core.excludesfile") {
+        return Ok(path);
+    }
+
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var_os("HOME")
+                .map(|home| PathBuf::from(home).join(".config"))
+        })
+        .map(|config| config.join("git").join("ignore"))
+        .ok_or_else(|| {
+            Error::Generic(String::from(
+                "no home directory for the global ignore file",
+            ))
+        })
+}
+
+fn append_pattern(ignore_file: &Path, pattern: &str) -> Result<()> {
+    let optional_newline =
+        ignore_file.exists() && !file_ends_with_newline(ignore_file)?;
 
     let mut file = OpenOptions::new()
         .append(true)
@@ -32,7 +124,7 @@ pub fn add_to_ignore(
         file,
         "{}{}",
         if optional_newline { "\n" } else { "" },
-        path_to_ignore
+        pattern
     )?;
 
     Ok(())
@@ -46,8 +138,6 @@ fn file_ends_with_newline(file: &Path) -> Result<bool> {
     let mut last_char = String::with_capacity(1);
     file.read_to_string(&mut last_char)?;
 
-    dbg!(&last_char);
-
     Ok(last_char == "\n")
 }
 
@@ -126,4 +216,53 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_ignore_patterns() {
+        assert_eq!(
+            ignore_patterns("src/foo.rs", false),
+            vec!["src/foo.rs", "*.rs", "src/"]
+        );
+        assert_eq!(
+            ignore_patterns("Makefile", false),
+            vec!["Makefile"]
+        );
+        assert_eq!(ignore_patterns("target", true), vec!["target/"]);
+    }
+
+    #[test]
+    fn test_global_ignore() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+        let excludes = root.join("excludes/ignore");
+
+        repo.config()?.set_str(
+            "core.excludesfile",
+            excludes.to_str().unwrap(),
+        )?;
+
+        add_to_global_ignore(repo_path, "*.log")?;
+
+        assert_eq!(fs::read_to_string(&excludes)?, "*.log\n");
+        assert!(!root.join(".gitignore").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_gitignore() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert_eq!(read_gitignore(repo_path)?, "");
+
+        write_gitignore(repo_path, "target/\n")?;
+        add_to_ignore(repo_path, "*.log")?;
+
+        assert_eq!(read_gitignore(repo_path)?, "target/\n*.log\n");
+
+        Ok(())
+    }
 }
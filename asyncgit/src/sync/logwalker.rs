@@ -1,11 +1,53 @@
-use super::CommitId;
+use super::{
+    commit_graph::{CommitGraph, GraphRow},
+    CommitId,
+};
 use crate::error::Result;
 use git2::{Repository, Revwalk};
+use std::sync::Arc;
+
+/// decides whether a commit is part of the walk result (`true`) or skipped
+pub type LogWalkerFilter = Arc<
+    Box<dyn Fn(&Repository, &CommitId) -> Result<bool> + Send + Sync>,
+>;
+
+/// filter skipping merge commits (like `git log --no-merges`)
+pub fn no_merges_filter() -> LogWalkerFilter {
+    Arc::new(Box::new(|repo: &Repository, id: &CommitId| {
+        let commit = repo.find_commit((*id).into())?;
+        Ok(commit.parent_count() <= 1)
+    }))
+}
+
+/// refs the log is walked from
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LogScope {
+    /// the current branch (like `git log`)
+    Head,
+    /// HEAD, all branches and tags (like `git log --all` without
+    /// stashes and other refs), remote branches only if `remotes`
+    All {
+        ///
+        remotes: bool,
+    },
+}
+
+impl Default for LogScope {
+    fn default() -> Self {
+        Self::Head
+    }
+}
 
 ///
 pub struct LogWalker<'a> {
     repo: &'a Repository,
     revwalk: Option<Revwalk<'a>>,
+    tips: Vec<CommitId>,
+    scope: LogScope,
+    first_parent: bool,
+    filter: Option<LogWalkerFilter>,
+    graph: Option<CommitGraph>,
+    graph_rows: Vec<GraphRow>,
 }
 
 impl<'a> LogWalker<'a> {
@@ -14,10 +56,59 @@ impl<'a> LogWalker<'a> {
         Self {
             repo,
             revwalk: None,
+            tips: Vec::new(),
+            scope: LogScope::Head,
+            first_parent: false,
+            filter: None,
+            graph: None,
+            graph_rows: Vec::new(),
         }
     }
 
-    ///
+    /// walk history of all `tips` instead of HEAD
+    pub fn tips(self, tips: Vec<CommitId>) -> Self {
+        Self { tips, ..self }
+    }
+
+    /// refs to walk from without `tips`
+    pub fn scope(self, scope: LogScope) -> Self {
+        Self { scope, ..self }
+    }
+
+    /// follows only the first parent of merge commits (like
+    /// `git log --first-parent`)
+    pub fn first_parent(self, first_parent: bool) -> Self {
+        Self {
+            first_parent,
+            ..self
+        }
+    }
+
+    /// only commits passing `filter` will be returned by `read`
+    pub fn filter(self, filter: Option<LogWalkerFilter>) -> Self {
+        Self { filter, ..self }
+    }
+
+    /// lays out the commits read in a graph (see `take_graph`),
+    /// not done with a `filter` as it would cut the lanes
+    pub fn graph(self, enabled: bool) -> Self {
+        Self {
+            graph: if enabled {
+                Some(CommitGraph::new())
+            } else {
+                None
+            },
+            ..self
+        }
+    }
+
+    /// graph rows of the commits read since the last call, one per
+    /// commit in the same order
+    pub fn take_graph(&mut self) -> Vec<GraphRow> {
+        std::mem::take(&mut self.graph_rows)
+    }
+
+    /// reads up to `limit` commits (after filtering) into `out`
     pub fn read(
         &mut self,
         out: &mut Vec<CommitId>,
@@ -27,13 +118,56 @@ impl<'a> LogWalker<'a> {
 
         if self.revwalk.is_none() {
             let mut walk = self.repo.revwalk()?;
-            walk.push_head()?;
+            if let (true, LogScope::All { remotes }) =
+                (self.tips.is_empty(), self.scope)
+            {
+                // an unborn HEAD has no history, other refs might
+                walk.push_head().ok();
+                walk.push_glob("refs/heads")?;
+                walk.push_glob("refs/tags")?;
+                if remotes {
+                    walk.push_glob("refs/remotes")?;
+                }
+            } else if self.tips.is_empty() {
+                walk.push_head()?;
+            } else {
+                for tip in &self.tips {
+                    walk.push((*tip).into())?;
+                }
+            }
+            if self.first_parent {
+                walk.simplify_first_parent()?;
+            }
             self.revwalk = Some(walk);
         }
 
         if let Some(ref mut walk) = self.revwalk {
             for id in walk.into_iter().flatten() {
-                out.push(id.into());
+                let id: CommitId = id.into();
+
+                if let Some(filter) = &self.filter {
+                    if !filter(self.repo, &id)? {
+                        continue;
+                    }
+                }
+
+                if let (Some(graph), None) =
+                    (&mut self.graph, &self.filter)
+                {
+                    let mut parents: Vec<CommitId> = self
+                        .repo
+                        .find_commit(id.into())?
+                        .parent_ids()
+                        .map(CommitId::from)
+                        .collect();
+                    // the others are never walked to
+                    if self.first_parent {
+                        parents.truncate(1);
+                    }
+                    self.graph_rows.push(graph.push(id, &parents));
+                }
+
+                out.push(id);
                 count += 1;
 
                 if count == limit {
@@ -50,8 +184,9 @@ impl<'a> LogWalker<'a> {
 mod tests {
     use super::*;
     use crate::sync::{
-        commit, get_commits_info, stage_add_file,
-        tests::repo_init_empty,
+        checkout_branch, commit, create_branch, get_commits_info,
+        stage_add_file,
+        tests::{repo_init_empty, write_commit_file},
     };
     use std::{fs::File, io::Write, path::Path};
 
@@ -110,4 +245,187 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_no_merges() -> Result<()> {
+        let (_td, repo) = repo_init_empty().unwrap();
+
+        let first = write_commit_file(&repo, "a.txt", "a", "first");
+        let second = write_commit_file(&repo, "b.txt", "b", "second");
+
+        let tree = repo.find_commit(second.into())?.tree()?;
+        let sig = repo.signature()?;
+        let merge = repo.commit(
+            Some("HEAD"),
+            &sig,
+            &sig,
+            "merge",
+            &tree,
+            &[
+                &repo.find_commit(second.into())?,
+                &repo.find_commit(first.into())?,
+            ],
+        )?;
+
+        let mut items = Vec::new();
+        LogWalker::new(&repo).read(&mut items, 100).unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0], merge.into());
+
+        let mut items = Vec::new();
+        let mut walk =
+            LogWalker::new(&repo).filter(Some(no_merges_filter()));
+        let count = walk.read(&mut items, 1).unwrap();
+
+        assert_eq!(count, 1);
+        assert_eq!(items, vec![second]);
+
+        walk.read(&mut items, 100).unwrap();
+        assert_eq!(items, vec![second, first]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scope() -> Result<()> {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = write_commit_file(&repo, "a.txt", "a", "first");
+        create_branch(repo_path, "other")?;
+        let other = write_commit_file(&repo, "b.txt", "b", "other");
+        checkout_branch(repo_path, "refs/heads/master")?;
+        repo.reference(
+            "refs/remotes/origin/master",
+            other.into(),
+            true,
+            "",
+        )?;
+        let second = write_commit_file(&repo, "c.txt", "c", "second");
+
+        let mut items = Vec::new();
+        LogWalker::new(&repo).read(&mut items, 100)?;
+        assert_eq!(items, vec![second, first]);
+
+        let mut items = Vec::new();
+        LogWalker::new(&repo)
+            .scope(LogScope::All { remotes: false })
+            .read(&mut items, 100)?;
+        assert_eq!(items.len(), 3);
+        assert!(items.contains(&other));
+
+        repo.find_reference("refs/heads/other")?.delete()?;
+
+        let mut items = Vec::new();
+        LogWalker::new(&repo)
+            .scope(LogScope::All { remotes: false })
+            .read(&mut items, 100)?;
+        assert_eq!(items, vec![second, first]);
+
+        let mut items = Vec::new();
+        LogWalker::new(&repo)
+            .scope(LogScope::All { remotes: true })
+            .read(&mut items, 100)?;
+        assert_eq!(items.len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_graph() -> Result<()> {
+        let (_td, repo) = repo_init_empty().unwrap();
+
+        let first = write_commit_file(&repo, "a.txt", "a", "first");
+        let second = write_commit_file(&repo, "b.txt", "b", "second");
+
+        let tree = repo.find_commit(second.into())?.tree()?;
+        let sig = repo.signature()?;
+        // merges `first` (again) as second parent: a lane forks off
+        // and joins back
+        repo.commit(
+            Some("HEAD"),
+            &sig,
+            &sig,
+            "merge",
+            &tree,
+            &[
+                &repo.find_commit(second.into())?,
+                &repo.find_commit(first.into())?,
+            ],
+        )?;
+
+        let mut items = Vec::new();
+        let mut walk = LogWalker::new(&repo).graph(true);
+        walk.read(&mut items, 2).unwrap();
+        let rows = walk.take_graph();
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].cells[0].commit && rows[0].cells[1].down);
+        assert!(rows[1].cells[1].up && !rows[1].cells[1].down);
+
+        walk.read(&mut items, 100).unwrap();
+        let rows = walk.take_graph();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].cells.len(), 1);
+        assert_eq!(rows[0].commit_lane(), Some(0));
+
+        let mut walk = LogWalker::new(&repo)
+            .graph(true)
+            .filter(Some(no_merges_filter()));
+        walk.read(&mut items, 100).unwrap();
+        assert!(walk.take_graph().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_first_parent() -> Result<()> {
+        let (_td, repo) = repo_init_empty().unwrap();
+
+        let first = write_commit_file(&repo, "a.txt", "a", "first");
+        let second = write_commit_file(&repo, "b.txt", "b", "second");
+
+        let sig = repo.signature()?;
+        let first_commit = repo.find_commit(first.into())?;
+        let side: CommitId = repo
+            .commit(
+                None,
+                &sig,
+                &sig,
+                "side",
+                &first_commit.tree()?,
+                &[&first_commit],
+            )?
+            .into();
+        let merge: CommitId = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "merge",
+                &repo.find_commit(second.into())?.tree()?,
+                &[
+                    &repo.find_commit(second.into())?,
+                    &repo.find_commit(side.into())?,
+                ],
+            )?
+            .into();
+
+        let mut items = Vec::new();
+        LogWalker::new(&repo).read(&mut items, 100)?;
+        assert_eq!(items.len(), 4);
+        assert!(items.contains(&side));
+
+        let mut items = Vec::new();
+        let mut walk =
+            LogWalker::new(&repo).graph(true).first_parent(true);
+        walk.read(&mut items, 100)?;
+        assert_eq!(items, vec![merge, second, first]);
+        assert!(walk
+            .take_graph()
+            .iter()
+            .all(|row| row.cells.len() == 1));
+
+        Ok(())
+    }
 }
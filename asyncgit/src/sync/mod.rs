@@ -3,64 +3,198 @@
 //TODO: remove once we have this activated on the toplevel
 #![deny(clippy::expect_used)]
 
+pub mod archive;
+mod bisect;
+mod blame;
 pub mod branch;
+mod cherry_pick;
+pub mod clean;
 mod commit;
 mod commit_details;
 mod commit_files;
+mod commit_filter;
+mod commit_graph;
 mod commits_info;
+mod conflict;
 pub mod cred;
 pub mod diff;
+mod discard_backup;
+mod encoding;
+mod file_history;
 mod hooks;
 mod hunks;
+mod identity;
 mod ignore;
 mod logwalker;
-mod patches;
+pub mod objects;
+pub mod patches;
+mod rebase;
+mod reflog;
+mod refs;
 pub mod remotes;
 mod reset;
+mod revert;
+mod signature;
 mod staging;
 mod stash;
 mod state;
 pub mod status;
+mod submodules;
 mod tags;
+mod tree;
 pub mod utils;
+mod word_diff;
+mod worktrees;
 
+pub use archive::{archive, ArchiveFormat};
+pub use bisect::{
+    bisect_bad, bisect_good, bisect_reset, bisect_skip, bisect_start,
+    bisect_state, BisectState, BisectStep,
+};
+pub use blame::{blame, BlameLine, FileBlame};
 pub use branch::{
-    branch_compare_upstream, checkout_branch, config_is_pull_rebase,
-    create_branch, delete_branch, get_branch_remote,
-    get_branches_info, merge_commit::merge_upstream_commit,
+    branch_compare_upstream, branch_set_upstream_to, checkout_branch,
+    checkout_commit, config_is_pull_rebase, config_pull_strategy,
+    create_branch, create_branch_at, delete_branch,
+    description::{get_description, set_description},
+    favorites::{get_favorite_branches, toggle_favorite_branch},
+    get_branch_remote, get_branches_info, get_remote_branch_parts,
+    merge_branch::{merge_branch, MergeResult, MergeStrategy},
+    merge_commit::merge_upstream_commit,
     merge_ff::branch_merge_upstream_fastforward,
-    merge_rebase::merge_upstream_rebase, rename::rename_branch,
-    BranchCompare, BranchInfo,
+    merge_rebase::merge_upstream_rebase,
+    merged::branches_merged_into,
+    rename::rename_branch,
+    search::{
+        search_branches, BranchSearchMatch, BranchSearchResult,
+    },
+    set_upstream,
+    upstream_diff::{get_upstream_diff, UpstreamDiff},
+    BranchCompare, BranchDetails, BranchInfo, PullStrategy,
+};
+pub use cherry_pick::{
+    cherry_pick, cherry_pick_abort, cherry_pick_continue,
+    CherryPickResult,
+};
+pub use clean::{preview_clean, run_clean, CleanOptions};
+pub use commit::{
+    amend, commit, commit_fixup, commit_msg_template, tag,
+    trailers::{
+        add_trailer, get_recent_authors, get_signoff,
+        signoff_enabled, TRAILER_CO_AUTHORED_BY,
+        TRAILER_SIGNED_OFF_BY,
+    },
 };
-pub use commit::{amend, commit, tag};
 pub use commit_details::{
-    get_commit_details, CommitDetails, CommitMessage,
+    get_commit_details, CommitDetails, CommitMessage, CommitSignature,
+};
+pub use commit_files::{
+    get_commit_files, get_commit_files_split, get_commit_parents,
+    get_stash_untracked_commit, CommitFiles, MergeDiff,
+};
+pub use commit_filter::{
+    combine_filters, log_search_filter, LogSearch, SearchField,
+};
+pub use commit_graph::{GraphCell, GraphRow};
+pub use commits_info::{
+    get_commits_info, CommitId, CommitInfo, CommitRef,
+};
+pub use conflict::{
+    conflict_mark_resolved, conflict_take, conflict_take_hunk,
+    get_conflict, ConflictHunk, ConflictInfo, ConflictSide,
+};
+pub use diff::{
+    get_diff_commit, get_stage_patch, get_stage_stats, DiffOptions,
+    FileLineStats,
 };
-pub use commit_files::get_commit_files;
-pub use commits_info::{get_commits_info, CommitId, CommitInfo};
-pub use diff::get_diff_commit;
+pub use discard_backup::{
+    backup_discard, drop_discard_backup, get_discard_backup,
+    undo_discard,
+};
+pub use file_history::{file_history, FileHistoryEntry};
 pub use hooks::{
-    hooks_commit_msg, hooks_post_commit, hooks_pre_commit, HookResult,
+    hooks_commit_msg, hooks_post_commit, hooks_pre_commit,
+    hooks_prepare_commit_msg, HookResult, PrepareCommitMsgSource,
+};
+pub use hunks::{
+    reset_hunk, split_hunk, stage_hunk, unstage_hunk, UnstagedHunk,
+    UnstagedHunks,
+};
+pub use identity::{get_identity, set_local_identity, Identity};
+pub use ignore::{
+    add_to_global_ignore, add_to_ignore, global_ignore_file,
+    ignore_patterns, read_gitignore, write_gitignore,
+};
+pub use logwalker::{
+    no_merges_filter, LogScope, LogWalker, LogWalkerFilter,
 };
-pub use hunks::{reset_hunk, stage_hunk, unstage_hunk};
-pub use ignore::add_to_ignore;
-pub use logwalker::LogWalker;
+pub use objects::{
+    resolve_commit, show_object, ObjectDump, ObjectKind, ObjectLine,
+};
+pub use patches::{
+    apply_patch, apply_to_index, export_patch, get_file_patch,
+    get_hunk_patch,
+};
+pub use rebase::{
+    check_drop_commit, drop_commit, get_autosquash_todo,
+    get_rebase_progress, get_rebase_todo, get_squash_todo,
+    rebase_abort, rebase_continue, rebase_interactive, rebase_onto,
+    rebase_skip, reword, RebaseAction, RebaseProgress, RebaseState,
+    RebaseTodo,
+};
+pub use reflog::{get_reflog, ReflogEntry};
+pub use refs::{get_refs, get_refs_state, RefsSnapshot, RefsState};
 pub use remotes::{
-    get_default_remote, get_remotes, push::AsyncProgress,
-    tags::PushTagsProgress,
+    add_remote, get_default_remote, get_remotes, get_remotes_info,
+    push::AsyncProgress,
+    remove_remote, rename_remote, set_url,
+    tags::{PushTagsProgress, PushTagsTarget},
+    RemoteInfo,
+};
+pub use reset::{
+    clean_workdir, reset_hard, reset_stage, reset_to_commit,
+    reset_workdir, ResetKind,
+};
+pub use revert::{
+    revert_abort, revert_commit, revert_continue, RevertResult,
+};
+pub use signature::{
+    commit_signing_enabled, get_commit_signature,
+    tag_signing_enabled, SignatureFormat, SignatureInfo,
+    SignatureStatus,
 };
-pub use reset::{reset_stage, reset_workdir};
 pub use staging::{discard_lines, stage_lines};
-pub use stash::{get_stashes, stash_apply, stash_drop, stash_save};
-pub use state::{repo_state, RepoState};
-pub use tags::{get_tags, CommitTags, Tags};
+pub use stash::{
+    get_stashes, stash_apply, stash_apply_index, stash_branch,
+    stash_diff, stash_drop, stash_pop, stash_rename, stash_save,
+};
+pub use state::{
+    is_stopped_state, repo_state, state_abort, state_continue,
+    RepoState,
+};
+pub use submodules::{
+    get_submodules, init_submodule, submodule_status,
+    submodule_workdir, update_submodule, SubmoduleInfo,
+    SubmoduleStatus,
+};
+pub use tags::{
+    delete_tag, get_tags, get_tags_with_metadata, tag_annotated,
+    CommitTags, TagWithMetadata, Tags,
+};
+pub use tree::{checkout_file, tree_files, TreeFile};
 pub use utils::{
-    get_head, get_head_tuple, is_bare_repo, is_repo, stage_add_all,
-    stage_add_file, stage_addremoved, Head,
+    get_detached_head, get_head, get_head_tuple, get_index_problem,
+    is_bare_repo, is_head_detached, is_partial_clone, is_repo,
+    rebuild_index_from_head, remove_stale_index_lock, stage_add_all,
+    stage_add_file, stage_addremoved, Head, IndexProblem,
+};
+pub use worktrees::{
+    create_worktree, get_worktrees, prune_worktrees, remove_worktree,
+    WorktreeInfo,
 };
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::{
         commit, stage_add_file,
         status::{get_status, StatusType},
@@ -179,6 +313,31 @@ mod tests {
         Ok((tmp_repo_dir, bare_repo))
     }
 
+    /// `gpg.program` pretending to be gpg, `fail` makes it refuse
+    #[cfg(unix)]
+    pub fn fake_gpg(repo: &Repository, fail: bool) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = repo.path().join("fake-gpg");
+        let body = if fail {
+            "echo 'no secret key' >&2; exit 2"
+        } else {
+            "cat > /dev/null; echo '[GNUPG:] SIG_CREATED D 1 8 00 0 X' >&2; echo 'fake sig'"
+        };
+        std::fs::write(&script, format!("#!/bin/sh\n{}\n", body))
+            .unwrap();
+        std::fs::set_permissions(
+            &script,
+            std::fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+
+        repo.config()
+            .unwrap()
+            .set_str("gpg.program", script.to_str().unwrap())
+            .unwrap();
+    }
+
     /// helper returning amount of files with changes in the (wd,stage)
     pub fn get_statuses(repo_path: &str) -> (usize, usize) {
         (
@@ -0,0 +1,323 @@
+//! sync git api for inspecting raw objects (like `git cat-file -p`)
+
+use super::{utils::repo, CommitId};
+use crate::error::{Error, Result};
+use git2::{ObjectType, Oid, Repository};
+use scopetime::scope_time;
+use std::fmt;
+
+/// blob content beyond this size is not part of a dump
+pub const BLOB_DUMP_MAX_SIZE: usize = 64 * 1024;
+
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ObjectKind {
+    ///
+    Commit,
+    ///
+    Tree,
+    ///
+    Blob,
+    ///
+    Tag,
+}
+
+impl fmt::Display for ObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Commit => "commit",
+            Self::Tree => "tree",
+            Self::Blob => "blob",
+            Self::Tag => "tag",
+        };
+
+        f.write_str(name)
+    }
+}
+
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectLine {
+    ///
+    pub text: String,
+    /// id of the object this line refers to (if any)
+    pub link: Option<String>,
+}
+
+impl ObjectLine {
+    fn new(text: String, link: Option<String>) -> Self {
+        Self { text, link }
+    }
+}
+
+///
+#[derive(Debug)]
+pub struct ObjectDump {
+    /// full hex id of the object
+    pub id: String,
+    ///
+    pub kind: ObjectKind,
+    /// size of the object in bytes
+    pub size: usize,
+    /// readable content: headers, tree entries or blob lines
+    pub lines: Vec<ObjectLine>,
+    /// blob content is binary and therefore not dumped
+    pub binary: bool,
+    /// blob content got cut at `BLOB_DUMP_MAX_SIZE`
+    pub truncated: bool,
+}
+
+/// the commit `spec` (anything `git rev-parse` understands) points
+/// to, tags are peeled
+pub fn resolve_commit(
+    repo_path: &str,
+    spec: &str,
+) -> Result<CommitId> {
+    scope_time!("resolve_commit");
+
+    let repo = repo(repo_path)?;
+
+    let object = repo.revparse_single(spec)?;
+    let commit = object.peel_to_commit().map_err(|_| {
+        Error::Generic(format!("'{}' is not a commit", spec))
+    })?;
+
+    Ok(commit.id().into())
+}
+
+/// returns type and readable content of the object `id` points to,
+/// `id` can be anything that `git rev-parse` understands
+pub fn show_object(repo_path: &str, id: &str) -> Result<ObjectDump> {
+    scope_time!("show_object");
+
+    let repo = repo(repo_path)?;
+
+    let oid = repo.revparse_single(id)?.id();
+    let (size, kind) = repo.odb()?.read_header(oid)?;
+
+    let mut dump = ObjectDump {
+        id: oid.to_string(),
+        kind: ObjectKind::Blob,
+        size,
+        lines: Vec::new(),
+        binary: false,
+        truncated: false,
+    };
+
+    match kind {
+        ObjectType::Commit | ObjectType::Tag => {
+            dump.kind = if kind == ObjectType::Tag {
+                ObjectKind::Tag
+            } else {
+                ObjectKind::Commit
+            };
+            dump.lines = dump_headers(&repo, oid)?;
+        }
+        ObjectType::Tree => {
+            dump.kind = ObjectKind::Tree;
+            dump.lines = dump_tree(&repo, oid)?;
+        }
+        ObjectType::Blob => {
+            let blob = repo.find_blob(oid)?;
+            let content = blob.content();
+
+            if blob.is_binary() {
+                dump.binary = true;
+            } else {
+                dump.truncated = content.len() > BLOB_DUMP_MAX_SIZE;
+                let end = content.len().min(BLOB_DUMP_MAX_SIZE);
+
+                dump.lines = String::from_utf8_lossy(&content[..end])
+                    .lines()
+                    .map(|line| {
+                        ObjectLine::new(line.to_string(), None)
+                    })
+                    .collect();
+            }
+        }
+        _ => {
+            return Err(Error::Generic(format!(
+                "unsupported object type: {}",
+                kind
+            )))
+        }
+    }
+
+    Ok(dump)
+}
+
+/// commits and tags: header lines (linking to referenced objects)
+/// followed by the raw message
+fn dump_headers(
+    repo: &Repository,
+    oid: Oid,
+) -> Result<Vec<ObjectLine>> {
+    let odb = repo.odb()?;
+    let obj = odb.read(oid)?;
+    let text = String::from_utf8_lossy(obj.data());
+
+    let mut in_headers = true;
+
+    Ok(text
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                in_headers = false;
+            }
+
+            let link =
+                if in_headers { header_link(line) } else { None };
+
+            ObjectLine::new(line.to_string(), link)
+        })
+        .collect())
+}
+
+fn header_link(line: &str) -> Option<String> {
+    let mut parts = line.splitn(2, ' ');
+    let key = parts.next()?;
+    let value = parts.next()?;
+
+    match key {
+        "tree" | "parent" | "object" => {
+            Oid::from_str(value).ok().map(|oid| oid.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn dump_tree(repo: &Repository, oid: Oid) -> Result<Vec<ObjectLine>> {
+    let tree = repo.find_tree(oid)?;
+
+    Ok(tree
+        .iter()
+        .map(|entry| {
+            let kind = entry.kind().map_or("?", |k| k.str());
+            let text = format!(
+                "{:06o} {} {}\t{}",
+                entry.filemode(),
+                kind,
+                entry.id(),
+                String::from_utf8_lossy(entry.name_bytes())
+            );
+
+            // submodule entries point into another repository
+            let link = if entry.kind() == Some(ObjectType::Commit) {
+                None
+            } else {
+                Some(entry.id().to_string())
+            };
+
+            ObjectLine::new(text, link)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::tests::{repo_init, write_commit_file};
+    use std::{fs::File, io::Write};
+
+    #[test]
+    fn test_commit_tree_blob() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let id = write_commit_file(&repo, "foo.txt", "a\nb\n", "msg");
+
+        let commit = show_object(repo_path, &id.to_string()).unwrap();
+        assert_eq!(commit.kind, ObjectKind::Commit);
+        assert!(commit.lines[0].text.starts_with("tree "));
+        assert!(commit.lines.iter().any(|l| l.text == "msg"));
+
+        let tree_id = commit.lines[0].link.clone().unwrap();
+        let parent = &commit.lines[1];
+        assert!(parent.text.starts_with("parent "));
+        assert!(parent.link.is_some());
+
+        let tree = show_object(repo_path, &tree_id).unwrap();
+        assert_eq!(tree.kind, ObjectKind::Tree);
+        assert_eq!(tree.lines.len(), 1);
+        assert!(tree.lines[0].text.ends_with("\tfoo.txt"));
+
+        let blob_id = tree.lines[0].link.clone().unwrap();
+        let blob = show_object(repo_path, &blob_id).unwrap();
+        assert_eq!(blob.kind, ObjectKind::Blob);
+        assert_eq!(blob.size, 4);
+        assert_eq!(blob.lines.len(), 2);
+        assert!(!blob.binary);
+        assert!(!blob.truncated);
+    }
+
+    #[test]
+    fn test_revspec() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "foo.txt", "a", "msg");
+
+        let blob = show_object(repo_path, "HEAD:foo.txt").unwrap();
+        assert_eq!(blob.kind, ObjectKind::Blob);
+        assert_eq!(blob.lines[0].text, "a");
+
+        assert!(show_object(repo_path, "HEAD:bar.txt").is_err());
+    }
+
+    #[test]
+    fn test_resolve_commit() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = write_commit_file(&repo, "foo.txt", "a", "c1");
+        let second = write_commit_file(&repo, "foo.txt", "b", "c2");
+
+        assert_eq!(
+            resolve_commit(repo_path, "HEAD").unwrap(),
+            second
+        );
+        assert_eq!(
+            resolve_commit(repo_path, "HEAD~1").unwrap(),
+            first
+        );
+        assert_eq!(
+            resolve_commit(repo_path, &first.get_short_string())
+                .unwrap(),
+            first
+        );
+
+        assert!(resolve_commit(repo_path, "HEAD:foo.txt").is_err());
+        assert!(resolve_commit(repo_path, "nope").is_err());
+    }
+
+    #[test]
+    fn test_binary_and_big_blobs() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(root.join("bin"))
+            .unwrap()
+            .write_all(&[0, 1, 2, 0, 3])
+            .unwrap();
+        let big = "a\n".repeat(BLOB_DUMP_MAX_SIZE);
+        File::create(root.join("big"))
+            .unwrap()
+            .write_all(big.as_bytes())
+            .unwrap();
+
+        let blob = repo.blob_path(&root.join("bin")).unwrap();
+        let dump = show_object(repo_path, &blob.to_string()).unwrap();
+        assert!(dump.binary);
+        assert!(dump.lines.is_empty());
+
+        let blob = repo.blob_path(&root.join("big")).unwrap();
+        let dump = show_object(repo_path, &blob.to_string()).unwrap();
+        assert!(dump.truncated);
+        assert_eq!(dump.size, BLOB_DUMP_MAX_SIZE * 2);
+        assert_eq!(dump.lines.len(), BLOB_DUMP_MAX_SIZE / 2);
+    }
+}
@@ -1,6 +1,20 @@
-use super::diff::{get_diff_raw, HunkHeader};
-use crate::error::{Error, Result};
-use git2::{Diff, DiffLine, Patch, Repository};
+//! sync git api for creating, exporting and applying patches
+
+use super::{
+    diff::{get_diff_raw, DiffOptions, HunkHeader},
+    utils::{repo, work_dir},
+    CommitId,
+};
+use crate::{
+    error::{Error, Result},
+    hash,
+};
+use git2::{
+    ApplyLocation, Diff, DiffLine, DiffOptions as GitDiffOptions,
+    Patch, Repository,
+};
+use scopetime::scope_time;
+use std::fs;
 
 //
 pub(crate) struct HunkLines<'a> {
@@ -15,8 +29,16 @@ pub(crate) fn get_file_diff_patch_and_hunklines<'a>(
     is_staged: bool,
     reverse: bool,
 ) -> Result<(Patch<'a>, Vec<HunkLines<'a>>)> {
-    let diff =
-        get_diff_raw(&repo, file, is_staged, reverse, Some(1))?;
+    let diff = get_diff_raw(
+        &repo,
+        file,
+        is_staged,
+        reverse,
+        Some(DiffOptions {
+            context: 1,
+            ..DiffOptions::default()
+        }),
+    )?;
     let patches = get_patches(&diff)?;
     if patches.len() > 1 {
         return Err(Error::Generic(String::from("patch error")));
@@ -31,6 +53,322 @@ pub(crate) fn get_file_diff_patch_and_hunklines<'a>(
     Ok((patch, lines))
 }
 
+/// unified patch of `file`, staged or in the workdir, ready to be
+/// applied with `git apply` (untracked files become new files).
+/// bytes as they are, the file need not be utf8
+pub fn get_file_patch(
+    repo_path: &str,
+    file: &str,
+    is_staged: bool,
+) -> Result<Vec<u8>> {
+    scope_time!("get_file_patch");
+
+    let repo = repo(repo_path)?;
+    let diff = if is_staged {
+        get_diff_raw(&repo, file, true, false, None)?
+    } else {
+        // unlike for the hunk operations the content of untracked
+        // files has to be part of the diff
+        let mut opt = GitDiffOptions::new();
+        opt.pathspec(file)
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true);
+        repo.diff_index_to_workdir(None, Some(&mut opt))?
+    };
+
+    let mut res = Vec::new();
+    for mut patch in get_patches(&diff)? {
+        res.extend_from_slice(&patch.to_buf()?);
+    }
+
+    Ok(res)
+}
+
+/// the unstaged hunk of `file` with `hunk_hash` as a patch of its
+/// own, to be edited and staged with `apply_to_index`. hunks that
+/// are not utf8 (e.g. latin-1 files) are refused, their bytes would
+/// not survive the round trip through the editor
+pub fn get_hunk_patch(
+    repo_path: &str,
+    file: &str,
+    hunk_hash: u64,
+) -> Result<String> {
+    scope_time!("get_hunk_patch");
+
+    let repo = repo(repo_path)?;
+    let diff = get_diff_raw(&repo, file, false, false, None)?;
+
+    for patch in get_patches(&diff)? {
+        for hunk_idx in 0..patch.num_hunks() {
+            let (hunk, count_lines) = patch.hunk(hunk_idx)?;
+            let header =
+                String::from_utf8_lossy(hunk.header()).to_string();
+            if hash(&HunkHeader::from(hunk)) != hunk_hash {
+                continue;
+            }
+
+            let mut res = format!(
+                "diff --git a/{0} b/{0}\n--- a/{0}\n+++ b/{0}\n{1}",
+                file, header
+            );
+            for line_idx in 0..count_lines {
+                let line = patch.line_in_hunk(hunk_idx, line_idx)?;
+                let content = std::str::from_utf8(line.content())
+                    .map_err(|_| {
+                        Error::Generic(format!(
+                            "hunk of {} is not valid utf8, it cannot be edited",
+                            file
+                        ))
+                    })?;
+                // missing newline markers bring their own prefix
+                if matches!(line.origin(), ' ' | '+' | '-') {
+                    res.push(line.origin());
+                }
+                res.push_str(content);
+            }
+
+            return Ok(res);
+        }
+    }
+
+    Err(Error::Generic(String::from("hunk not found")))
+}
+
+/// stages a patch that might have been edited by hand: `#` lines are
+/// dropped and the line counts of the hunk headers recounted, hunks
+/// left without changes are skipped
+pub fn apply_to_index(repo_path: &str, patch: &str) -> Result<()> {
+    scope_time!("apply_to_index");
+
+    let patch = match recount_hunks(patch)? {
+        Some(patch) => patch,
+        None => return Ok(()),
+    };
+
+    let repo = repo(repo_path)?;
+    let diff = Diff::from_buffer(patch.as_bytes())?;
+    repo.apply(&diff, ApplyLocation::Index, None)?;
+
+    Ok(())
+}
+
+/// writes each of `commits` (numbered in the given order) as a
+/// patch file into `dir` like `git format-patch` does, relative
+/// paths are below the workdir, returns the written files
+pub fn export_patch(
+    repo_path: &str,
+    commits: &[CommitId],
+    dir: &str,
+) -> Result<Vec<String>> {
+    scope_time!("export_patch");
+
+    let repo = repo(repo_path)?;
+
+    let commits = commits
+        .iter()
+        .map(|id| {
+            let commit = repo.find_commit((*id).into())?;
+            if commit.parent_count() > 1 {
+                return Err(Error::Generic(format!(
+                    "merge commit {} cannot be exported",
+                    id.get_short_string()
+                )));
+            }
+            if commit.summary_bytes().is_none() {
+                return Err(Error::Generic(format!(
+                    "commit {} has no message",
+                    id.get_short_string()
+                )));
+            }
+            Ok(commit)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let dir = work_dir(&repo)?.join(dir);
+    fs::create_dir_all(&dir)?;
+
+    let mut files = Vec::with_capacity(commits.len());
+    for (idx, commit) in commits.iter().enumerate() {
+        let parent_tree = match commit.parents().next() {
+            Some(parent) => Some(parent.tree()?),
+            None => None,
+        };
+        let mut diff = repo.diff_tree_to_tree(
+            parent_tree.as_ref(),
+            Some(&commit.tree()?),
+            None,
+        )?;
+        let email =
+            diff.format_email(idx + 1, commits.len(), commit, None)?;
+
+        let path = dir.join(patch_file_name(
+            idx + 1,
+            commit.summary().unwrap_or_default(),
+        ));
+        fs::write(&path, &*email)?;
+        files.push(path.to_string_lossy().to_string());
+    }
+
+    Ok(files)
+}
+
+/// applies the patch file at `path` (relative to the workdir) like
+/// `git apply`, with `to_index` its changes get staged as well
+/// (`git apply --index`)
+pub fn apply_patch(
+    repo_path: &str,
+    path: &str,
+    to_index: bool,
+) -> Result<()> {
+    scope_time!("apply_patch");
+
+    let repo = repo(repo_path)?;
+    let content = fs::read(work_dir(&repo)?.join(path))?;
+
+    let diff = Diff::from_buffer(&content)?;
+    let location = if to_index {
+        ApplyLocation::Both
+    } else {
+        ApplyLocation::WorkDir
+    };
+    repo.apply(&diff, location, None)?;
+
+    Ok(())
+}
+
+/// `0001-fix-the-thing.patch` like `git format-patch` names them
+fn patch_file_name(number: usize, summary: &str) -> String {
+    let mut name = String::with_capacity(summary.len());
+    for c in summary.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+            name.push(c);
+        } else if !name.is_empty() && !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+
+    let name: String = name.chars().take(52).collect();
+
+    format!(
+        "{:04}-{}.patch",
+        number,
+        name.trim_end_matches(|c| c == '-' || c == '.')
+    )
+}
+
+/// `None` if no hunk with changes is left
+fn recount_hunks(patch: &str) -> Result<Option<String>> {
+    let mut res = String::with_capacity(patch.len());
+    let mut hunk: Option<(&str, Vec<&str>)> = None;
+    let mut changes = false;
+
+    for line in patch.lines().filter(|l| !l.starts_with('#')) {
+        if line.starts_with("@@") {
+            if let Some((header, body)) = hunk.take() {
+                changes |= push_hunk(&mut res, header, &body)?;
+            }
+            hunk = Some((line, Vec::new()));
+        } else if let Some((_, body)) = &mut hunk {
+            body.push(line);
+        } else {
+            res.push_str(line);
+            res.push('\n');
+        }
+    }
+
+    if let Some((header, body)) = hunk {
+        changes |= push_hunk(&mut res, header, &body)?;
+    }
+
+    Ok(if changes { Some(res) } else { None })
+}
+
+/// `false` if the hunk has no changes and was left out
+fn push_hunk(
+    res: &mut String,
+    header: &str,
+    mut body: &[&str],
+) -> Result<bool> {
+    // editors tend to leave empty lines at the end
+    while body.last().map_or(false, |l| l.is_empty()) {
+        body = &body[..body.len() - 1];
+    }
+
+    let invalid =
+        || Error::Generic(format!("invalid hunk header: {}", header));
+    let (old_start, _) =
+        hunk_side(header, '-').ok_or_else(invalid)?;
+    let (new_start, _) =
+        hunk_side(header, '+').ok_or_else(invalid)?;
+    let section =
+        header[2..].find("@@").map_or("", |end| &header[end + 4..]);
+
+    let (mut old_lines, mut new_lines) = (0, 0);
+    for line in body {
+        match line.chars().next() {
+            Some(' ') | None => {
+                old_lines += 1;
+                new_lines += 1;
+            }
+            Some('-') => old_lines += 1,
+            Some('+') => new_lines += 1,
+            Some('\\') => (),
+            Some(_) => {
+                return Err(Error::Generic(format!(
+                    "invalid line in hunk: {}",
+                    line
+                )))
+            }
+        }
+    }
+
+    let changes = body
+        .iter()
+        .any(|l| l.starts_with('-') || l.starts_with('+'));
+    if changes {
+        res.push_str(&format!(
+            "@@ -{},{} +{},{} @@{}\n",
+            old_start, old_lines, new_start, new_lines, section
+        ));
+        for line in body {
+            if line.is_empty() {
+                res.push(' ');
+            }
+            res.push_str(line);
+            res.push('\n');
+        }
+    }
+
+    Ok(changes)
+}
+
+/// start line and line count of the old (`-`) or new (`+`) side of
+/// a hunk header like `@@ -1,3 +2 @@`
+pub(crate) fn hunk_side(
+    header: &str,
+    side: char,
+) -> Option<(u32, u32)> {
+    let mut parts = header.trim_start_matches('@').split_whitespace();
+    let part = if side == '-' {
+        parts.next()?
+    } else {
+        parts.nth(1)?
+    };
+    if !part.starts_with(side) {
+        return None;
+    }
+
+    let mut numbers = part[1..].split(',');
+    let start = numbers.next()?.parse().ok()?;
+    let lines = match numbers.next() {
+        Some(lines) => lines.parse().ok()?,
+        None => 1,
+    };
+
+    Some((start, lines))
+}
+
 //
 fn patch_get_hunklines<'a>(
     patch: &Patch<'a>,
@@ -72,3 +410,226 @@ fn get_patches<'a>(diff: &Diff<'a>) -> Result<Vec<Patch<'a>>> {
 
     Ok(res)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        commit,
+        diff::get_diff,
+        reset_hard, stage_add_file,
+        status::{get_status, StatusType},
+        tests::{repo_init, write_commit_file},
+        utils::{get_head, repo_write_file},
+    };
+    use std::{fs, path::Path};
+
+    #[test]
+    fn test_file_patch_applies() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo_write_file(&repo, "foo.txt", "a\nb\n").unwrap();
+        stage_add_file(repo_path, Path::new("foo.txt")).unwrap();
+        commit(repo_path, "init").unwrap();
+
+        repo_write_file(&repo, "foo.txt", "a\nc\n").unwrap();
+
+        let patch =
+            get_file_patch(repo_path, "foo.txt", false).unwrap();
+        let text = String::from_utf8(patch.clone()).unwrap();
+        assert!(text.starts_with("diff --git a/foo.txt b/foo.txt"));
+        assert!(text.contains("-b\n+c\n"));
+
+        assert!(get_file_patch(repo_path, "foo.txt", true)
+            .unwrap()
+            .is_empty());
+
+        repo_write_file(&repo, "foo.txt", "a\nb\n").unwrap();
+        let diff = Diff::from_buffer(&patch).unwrap();
+        repo.apply(&diff, ApplyLocation::WorkDir, None).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.join("foo.txt")).unwrap(),
+            "a\nc\n"
+        );
+    }
+
+    #[test]
+    fn test_file_patch_untracked() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo_write_file(&repo, "new.txt", "x\n").unwrap();
+
+        let patch = String::from_utf8(
+            get_file_patch(repo_path, "new.txt", false).unwrap(),
+        )
+        .unwrap();
+        assert!(patch.contains("new file mode"));
+        assert!(patch.contains("+x\n"));
+    }
+
+    #[test]
+    fn test_file_patch_latin1() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        fs::write(root.join("foo.txt"), b"caf\xe9\n").unwrap();
+        stage_add_file(repo_path, Path::new("foo.txt")).unwrap();
+        commit(repo_path, "init").unwrap();
+
+        fs::write(root.join("foo.txt"), b"th\xe9\n").unwrap();
+
+        let patch =
+            get_file_patch(repo_path, "foo.txt", false).unwrap();
+
+        fs::write(root.join("foo.txt"), b"caf\xe9\n").unwrap();
+        let diff = Diff::from_buffer(&patch).unwrap();
+        repo.apply(&diff, ApplyLocation::WorkDir, None).unwrap();
+
+        assert_eq!(
+            fs::read(root.join("foo.txt")).unwrap(),
+            b"th\xe9\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_edited_hunk() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo_write_file(&repo, "foo.txt", "a\nb\nc\n").unwrap();
+        stage_add_file(repo_path, Path::new("foo.txt")).unwrap();
+        commit(repo_path, "init").unwrap();
+
+        repo_write_file(&repo, "foo.txt", "a\nB\nc\nd\n").unwrap();
+
+        let diff = get_diff(repo_path, "foo.txt".into(), false, None)
+            .unwrap();
+        let patch = get_hunk_patch(
+            repo_path,
+            "foo.txt",
+            diff.hunks[0].header_hash,
+        )
+        .unwrap();
+        assert!(patch.contains("@@ -1,3 +1,4 @@\n a\n-b\n+B\n"));
+
+        // leave out the added line, comment and trailing line
+        let edited =
+            format!("{}# comment\n\n", patch.replace("+d\n", ""));
+        apply_to_index(repo_path, &edited).unwrap();
+
+        let staged =
+            get_diff(repo_path, "foo.txt".into(), true, None)
+                .unwrap();
+        let lines: Vec<&str> = staged.hunks[0]
+            .lines
+            .iter()
+            .map(|l| l.content.as_str())
+            .collect();
+        assert_eq!(lines[1..], ["a\n", "b\n", "B\n", "c\n"]);
+    }
+
+    #[test]
+    fn test_hunk_patch_latin1() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo_write_file(&repo, "foo.txt", "a\n").unwrap();
+        stage_add_file(repo_path, Path::new("foo.txt")).unwrap();
+        commit(repo_path, "init").unwrap();
+
+        // "é" in latin-1
+        fs::write(root.join("foo.txt"), b"a\ncaf\xe9\n").unwrap();
+
+        let diff = get_diff(repo_path, "foo.txt".into(), false, None)
+            .unwrap();
+        assert!(get_hunk_patch(
+            repo_path,
+            "foo.txt",
+            diff.hunks[0].header_hash,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_export_apply_patches() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let base = get_head(repo_path).unwrap();
+        let c1 = write_commit_file(&repo, "a.txt", "a\n", "add a");
+        let c2 =
+            write_commit_file(&repo, "a.txt", "a\nb\n", "extend: a!");
+
+        let files =
+            export_patch(repo_path, &[c1, c2], "out").unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files[0].ends_with("0001-add-a.patch"));
+        assert!(files[1].ends_with("0002-extend-a.patch"));
+
+        let content = fs::read_to_string(&files[1]).unwrap();
+        assert!(content.contains("Subject: [PATCH 2/2] extend: a!"));
+        assert!(content.contains("+b\n"));
+
+        reset_hard(repo_path, base).unwrap();
+
+        apply_patch(repo_path, "out/0001-add-a.patch", false)
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).unwrap(),
+            "a\n"
+        );
+        assert!(get_status(repo_path, StatusType::Stage, false)
+            .unwrap()
+            .is_empty());
+
+        repo_write_file(&repo, "a.txt", "a\n").unwrap();
+        stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+        apply_patch(repo_path, "out/0002-extend-a.patch", true)
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).unwrap(),
+            "a\nb\n"
+        );
+        assert_eq!(
+            get_status(repo_path, StatusType::WorkingDir, false)
+                .unwrap()
+                .len(),
+            0
+        );
+
+        assert!(apply_patch(repo_path, "out/missing.patch", true)
+            .is_err());
+    }
+
+    #[test]
+    fn test_patch_file_name() {
+        assert_eq!(
+            patch_file_name(1, "Fix: the [thing]."),
+            "0001-Fix-the-thing.patch"
+        );
+        assert_eq!(patch_file_name(12, "..."), "0012-.patch");
+    }
+
+    #[test]
+    fn test_recount_hunks() {
+        let patch =
+            "--- a/x\n+++ b/x\n@@ -2,9 +2,9 @@ fn x\n a\n+b\n\n";
+
+        assert_eq!(
+            recount_hunks(patch).unwrap().unwrap(),
+            "--- a/x\n+++ b/x\n@@ -2,1 +2,2 @@ fn x\n a\n+b\n"
+        );
+        assert_eq!(recount_hunks("@@ -1 +1 @@\n a\n").unwrap(), None);
+        assert!(recount_hunks("@@ -1 +1 @@\n*a\n").is_err());
+        assert!(recount_hunks("@@ 1 1 @@\n+a\n").is_err());
+    }
+}
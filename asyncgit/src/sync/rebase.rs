@@ -0,0 +1,1141 @@
+//! interactive rebase (like `git rebase -i`) driven step by step
+//! through libgit2. the state is kept in `.git/rebase-merge` so a
+//! rebase stopped by a conflict can be continued, skipped or aborted
+//! (by git as well)
+
+use super::{
+    commit::signature_allow_undefined_name,
+    utils::{has_uncommitted_changes, repo},
+    CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{
+    BranchType, Commit, Oid, Rebase, Repository, RepositoryState,
+    ResetType, Sort,
+};
+use scopetime::scope_time;
+use std::{fs, path::PathBuf};
+
+/// actions of the steps, the `cmt.<n>` files only know the commits
+const TODO_FILE: &str = "gitui-todo";
+
+/// what happens to a commit of the todo list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebaseAction {
+    ///
+    Pick,
+    /// pick with a new message
+    Reword(String),
+    /// meld into the previous commit, keeping both messages
+    Squash,
+    /// meld into the previous commit, keeping its message only
+    Fixup,
+    ///
+    Drop,
+}
+
+impl RebaseAction {
+    /// keyword in the todo list (like git)
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Reword(_) => "reword",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+            Self::Drop => "drop",
+        }
+    }
+
+    const fn melds(&self) -> bool {
+        matches!(self, Self::Squash | Self::Fixup)
+    }
+}
+
+/// entry of the todo list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebaseTodo {
+    ///
+    pub id: CommitId,
+    ///
+    pub action: RebaseAction,
+    /// first line of the original message
+    pub summary: String,
+}
+
+/// outcome of running the todo list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebaseState {
+    /// the branch points to the rewritten history
+    Finished,
+    /// the step conflicted: resolve, stage and continue (or skip)
+    Conflict(RebaseTodo),
+}
+
+/// state of a rebase that stopped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebaseProgress {
+    /// step that stopped the rebase
+    pub current: Option<RebaseTodo>,
+    /// steps still to do after `current`
+    pub remaining: Vec<RebaseTodo>,
+    /// steps done already
+    pub done: usize,
+}
+
+/// commits after `base` up to HEAD (oldest first) all picked,
+/// merge commits cannot be part of it
+pub fn get_rebase_todo(
+    repo_path: &str,
+    base: CommitId,
+) -> Result<Vec<RebaseTodo>> {
+    scope_time!("get_rebase_todo");
+
+    let repo = repo(repo_path)?;
+    let head = repo.head()?.peel_to_commit()?.id();
+
+    if head != base.into()
+        && !repo.graph_descendant_of(head, base.into())?
+    {
+        return Err(Error::Generic(String::from(
+            "base is not an ancestor of HEAD",
+        )));
+    }
+
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    walk.push(head)?;
+    walk.hide(base.into())?;
+
+    let mut res = Vec::new();
+    for id in walk {
+        let commit = repo.find_commit(id?)?;
+        if commit.parent_count() > 1 {
+            return Err(Error::Generic(String::from(
+                "merge commits cannot be rebased",
+            )));
+        }
+
+        res.push(RebaseTodo {
+            id: commit.id().into(),
+            action: RebaseAction::Pick,
+            summary: summary(&commit),
+        });
+    }
+
+    Ok(res)
+}
+
+/// todo list melding `ids` into the oldest of them: the others are
+/// moved right after it and squashed. returns the base of that
+/// rebase (the parent of the oldest commit) and the todo list
+pub fn get_squash_todo(
+    repo_path: &str,
+    ids: &[CommitId],
+) -> Result<(CommitId, Vec<RebaseTodo>)> {
+    scope_time!("get_squash_todo");
+
+    if ids.len() < 2 {
+        return Err(Error::Generic(String::from(
+            "at least two commits are needed to squash",
+        )));
+    }
+
+    let repo = repo(repo_path)?;
+
+    let oldest = ids
+        .iter()
+        .copied()
+        .find(|id| {
+            ids.iter().all(|other| {
+                other == id
+                    || repo
+                        .graph_descendant_of(
+                            (*other).into(),
+                            (*id).into(),
+                        )
+                        .unwrap_or_default()
+            })
+        })
+        .ok_or_else(|| {
+            Error::Generic(String::from(
+                "the commits are not in one line of history",
+            ))
+        })?;
+
+    let base = parent_of(&repo, oldest)?;
+
+    let (mut squashed, mut todo): (Vec<_>, Vec<_>) =
+        get_rebase_todo(repo_path, base)?.into_iter().partition(
+            |item| item.id != oldest && ids.contains(&item.id),
+        );
+
+    if squashed.len() + 1 != ids.len() {
+        return Err(Error::Generic(String::from(
+            "only commits of the current branch can be squashed",
+        )));
+    }
+
+    for item in &mut squashed {
+        item.action = RebaseAction::Squash;
+    }
+
+    // the oldest commit is the first one after the base
+    todo.splice(1..1, squashed);
+
+    Ok((base, todo))
+}
+
+/// todo list folding the `fixup!` and `squash!` commits of HEAD into
+/// their targets, like `git rebase -i --autosquash`. the rebase
+/// starts at the parent of the oldest target, returned as well
+pub fn get_autosquash_todo(
+    repo_path: &str,
+) -> Result<(CommitId, Vec<RebaseTodo>)> {
+    scope_time!("get_autosquash_todo");
+
+    let repo = repo(repo_path)?;
+    let mut commit = repo.head()?.peel_to_commit()?;
+
+    // subjects (or hashes) of targets not found yet
+    let mut pending: Vec<String> = Vec::new();
+    let mut oldest_target = None;
+
+    loop {
+        let subject = summary(&commit);
+        let id = commit.id().to_string();
+
+        pending.retain(|target| {
+            let found = is_fixup_target(target, &subject, &id);
+            if found {
+                oldest_target = Some(commit.id());
+            }
+            !found
+        });
+
+        if let Some((_, target)) = fixup_target(&subject) {
+            pending.push(target.to_string());
+        } else if pending.is_empty() && oldest_target.is_some() {
+            break;
+        }
+
+        if commit.parent_count() != 1 {
+            break;
+        }
+        commit = commit.parent(0)?;
+    }
+
+    let base = oldest_target
+        .and_then(|id| repo.find_commit(id).ok())
+        .and_then(|target| target.parent_id(0).ok())
+        .ok_or_else(|| {
+            Error::Generic(String::from(
+                "no fixup! or squash! commits with a target to fold them into",
+            ))
+        })?;
+
+    let todo = get_rebase_todo(repo_path, base.into())?;
+
+    Ok((base.into(), autosquash(todo)))
+}
+
+/// action and target (subject or hash) of a `fixup!`/`squash!`
+/// subject, nested prefixes (`fixup! fixup! a`) are stripped
+fn fixup_target(subject: &str) -> Option<(RebaseAction, &str)> {
+    let action = if subject.starts_with("fixup! ") {
+        RebaseAction::Fixup
+    } else if subject.starts_with("squash! ") {
+        RebaseAction::Squash
+    } else {
+        return None;
+    };
+
+    let mut target = subject;
+    while let Some(rest) = target
+        .strip_prefix("fixup! ")
+        .or_else(|| target.strip_prefix("squash! "))
+    {
+        target = rest;
+    }
+
+    Some((action, target))
+}
+
+/// a target is the subject or (like in git at least 4 digits of)
+/// the hash of the commit
+fn is_fixup_target(target: &str, subject: &str, id: &str) -> bool {
+    target == subject
+        || (target.len() >= 4
+            && target.chars().all(|c| c.is_ascii_hexdigit())
+            && id.starts_with(target))
+}
+
+/// moves the `fixup!`/`squash!` commits right after their targets
+/// (behind the ones moved there already)
+fn autosquash(todo: Vec<RebaseTodo>) -> Vec<RebaseTodo> {
+    let mut res: Vec<RebaseTodo> = Vec::with_capacity(todo.len());
+
+    for item in todo {
+        let target = fixup_target(&item.summary).and_then(
+            |(action, target)| {
+                res.iter()
+                    .position(|t| {
+                        is_fixup_target(
+                            target,
+                            &t.summary,
+                            &t.id.to_string(),
+                        )
+                    })
+                    .map(|pos| (action, pos))
+            },
+        );
+
+        if let Some((action, pos)) = target {
+            let mut at = pos + 1;
+            while at < res.len() && res[at].action.melds() {
+                at += 1;
+            }
+            res.insert(at, RebaseTodo { action, ..item });
+        } else {
+            res.push(item);
+        }
+    }
+
+    res
+}
+
+/// replaces the message of `commit` by `msg`, rebasing the commits
+/// after it onto the reworded one
+pub fn reword(
+    repo_path: &str,
+    commit: CommitId,
+    msg: &str,
+) -> Result<RebaseState> {
+    scope_time!("reword");
+
+    rewrite_commit(
+        repo_path,
+        commit,
+        RebaseAction::Reword(msg.to_string()),
+    )
+}
+
+/// errors unless `commit` is a non-merge commit of the current
+/// branch, returns whether the upstream of the branch has it already
+/// (dropping it then needs a force push)
+pub fn check_drop_commit(
+    repo_path: &str,
+    commit: CommitId,
+) -> Result<bool> {
+    scope_time!("check_drop_commit");
+
+    let repo = repo(repo_path)?;
+
+    if repo.find_commit(commit.into())?.parent_count() > 1 {
+        return Err(Error::Generic(String::from(
+            "merge commits cannot be dropped",
+        )));
+    }
+
+    let head = repo.head()?;
+    let head_id = head.peel_to_commit()?.id();
+    if head_id != commit.into()
+        && !repo.graph_descendant_of(head_id, commit.into())?
+    {
+        return Err(Error::Generic(String::from(
+            "only commits of the current branch can be dropped",
+        )));
+    }
+
+    let upstream = head
+        .shorthand()
+        .and_then(|name| {
+            repo.find_branch(name, BranchType::Local).ok()
+        })
+        .and_then(|branch| branch.upstream().ok())
+        .and_then(|upstream| upstream.get().target());
+
+    Ok(match upstream {
+        Some(upstream) => {
+            upstream == commit.into()
+                || repo
+                    .graph_descendant_of(upstream, commit.into())?
+        }
+        None => false,
+    })
+}
+
+/// removes `commit` from the history of the current branch, the
+/// commits after it are rebased onto its parent
+pub fn drop_commit(
+    repo_path: &str,
+    commit: CommitId,
+) -> Result<RebaseState> {
+    scope_time!("drop_commit");
+
+    check_drop_commit(repo_path, commit)?;
+
+    rewrite_commit(repo_path, commit, RebaseAction::Drop)
+}
+
+/// rebase onto the parent of `commit` applying `action` to it and
+/// picking the commits after it
+fn rewrite_commit(
+    repo_path: &str,
+    commit: CommitId,
+    action: RebaseAction,
+) -> Result<RebaseState> {
+    let base = parent_of(&repo(repo_path)?, commit)?;
+
+    let mut todo = get_rebase_todo(repo_path, base)?;
+    let item = todo
+        .iter_mut()
+        .find(|item| item.id == commit)
+        .ok_or_else(|| {
+            Error::Generic(String::from(
+                "only commits of the current branch can be rewritten",
+            ))
+        })?;
+    item.action = action;
+
+    rebase_interactive(repo_path, base, &todo)
+}
+
+/// rebases HEAD onto `base` following `todo` (in order), like
+/// `git rebase -i` the state is kept in `.git/rebase-merge`: the
+/// picked commits in the `cmt.<n>` files libgit2 walks and the
+/// actions for them in the todo file next to it. HEAD is detached
+/// while the rebase runs, its branch is only moved once all steps
+/// are done
+pub fn rebase_interactive(
+    repo_path: &str,
+    base: CommitId,
+    todo: &[RebaseTodo],
+) -> Result<RebaseState> {
+    scope_time!("rebase_interactive");
+
+    let repo = repo(repo_path)?;
+
+    if repo.state() != RepositoryState::Clean {
+        return Err(Error::Generic(String::from(
+            "repository is not in a clean state",
+        )));
+    }
+    if has_uncommitted_changes(&repo)? {
+        return Err(Error::UncommittedChanges);
+    }
+    if todo
+        .iter()
+        .find(|item| item.action != RebaseAction::Drop)
+        .map_or(false, |item| item.action.melds())
+    {
+        return Err(Error::Generic(String::from(
+            "the first commit cannot be squashed or fixed up",
+        )));
+    }
+
+    let todo = todo
+        .iter()
+        .filter(|item| item.action != RebaseAction::Drop)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    {
+        // the commits of HEAD not on `base`, the same ones as `todo`
+        // but in the order of the walk, they are replaced below
+        let base = repo.find_annotated_commit(base.into())?;
+        repo.rebase(None, Some(&base), None, None)?;
+    }
+
+    write_todo(&repo, &todo)?;
+
+    let mut rebase = repo.open_rebase(None)?;
+    run_todo(&repo, &mut rebase, &todo)
+}
+
+/// rebases the commits of HEAD that are not on `branch` (a branch
+/// reference) onto its top commit, like `git rebase <branch>`.
+/// a conflict stops it like any other rebase
+pub fn rebase_onto(
+    repo_path: &str,
+    branch: &str,
+) -> Result<RebaseState> {
+    scope_time!("rebase_onto");
+
+    let (onto, base) = {
+        let repo = repo(repo_path)?;
+        let onto =
+            repo.find_reference(branch)?.peel_to_commit()?.id();
+        let head = repo.head()?.peel_to_commit()?.id();
+        (onto, repo.merge_base(head, onto)?)
+    };
+
+    if base == onto {
+        return Err(Error::Generic(String::from(
+            "HEAD is up to date with the branch already",
+        )));
+    }
+
+    let todo = get_rebase_todo(repo_path, base.into())?;
+
+    rebase_interactive(repo_path, onto.into(), &todo)
+}
+
+/// commits the resolved (staged) step that stopped the rebase
+/// and carries on with the remaining ones
+pub fn rebase_continue(repo_path: &str) -> Result<RebaseState> {
+    scope_time!("rebase_continue");
+
+    let repo = repo(repo_path)?;
+    let (mut rebase, todo) = open(&repo)?;
+
+    if repo.index()?.has_conflicts() {
+        return Err(Error::Generic(String::from(
+            "resolve all conflicts first",
+        )));
+    }
+
+    if let Some(current) =
+        rebase.operation_current().and_then(|idx| todo.get(idx))
+    {
+        commit_step(&repo, &mut rebase, current)?;
+    }
+
+    run_todo(&repo, &mut rebase, &todo)
+}
+
+/// drops the step that stopped the rebase (and its changes)
+/// and carries on with the remaining ones
+pub fn rebase_skip(repo_path: &str) -> Result<RebaseState> {
+    scope_time!("rebase_skip");
+
+    let repo = repo(repo_path)?;
+    let (mut rebase, todo) = open(&repo)?;
+
+    let head = repo.head()?.peel_to_commit()?;
+    repo.reset(head.as_object(), ResetType::Hard, None)?;
+
+    run_todo(&repo, &mut rebase, &todo)
+}
+
+/// restores HEAD (and its branch) to where it was before the rebase
+pub fn rebase_abort(repo_path: &str) -> Result<()> {
+    scope_time!("rebase_abort");
+
+    let repo = repo(repo_path)?;
+    let (mut rebase, _) = open(&repo)?;
+
+    rebase.abort()?;
+
+    Ok(())
+}
+
+/// `None` unless a rebase is in progress
+pub fn get_rebase_progress(
+    repo_path: &str,
+) -> Result<Option<RebaseProgress>> {
+    scope_time!("get_rebase_progress");
+
+    let repo = repo(repo_path)?;
+
+    if !is_rebase_in_progress(&repo) {
+        return Ok(None);
+    }
+
+    let (mut rebase, mut todo) = open(&repo)?;
+
+    Ok(Some(match rebase.operation_current() {
+        Some(idx) if idx < todo.len() => {
+            let remaining = todo.split_off(idx + 1);
+            RebaseProgress {
+                current: todo.pop(),
+                remaining,
+                done: idx,
+            }
+        }
+        _ => RebaseProgress {
+            current: None,
+            remaining: todo,
+            done: 0,
+        },
+    }))
+}
+
+fn is_rebase_in_progress(repo: &Repository) -> bool {
+    matches!(
+        repo.state(),
+        RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge
+    )
+}
+
+fn state_dir(repo: &Repository) -> PathBuf {
+    repo.path().join("rebase-merge")
+}
+
+/// the rebase in progress and its todo list
+fn open(repo: &Repository) -> Result<(Rebase<'_>, Vec<RebaseTodo>)> {
+    if !is_rebase_in_progress(repo) {
+        return Err(Error::Generic(String::from(
+            "no rebase in progress",
+        )));
+    }
+
+    let mut rebase = repo.open_rebase(None)?;
+    let todo = read_todo(repo, &mut rebase)?;
+
+    Ok((rebase, todo))
+}
+
+fn parent_of(repo: &Repository, id: CommitId) -> Result<CommitId> {
+    Ok(repo
+        .find_commit(id.into())?
+        .parent_id(0)
+        .map_err(|_| {
+            Error::Generic(String::from(
+                "the root commit cannot be rebased",
+            ))
+        })?
+        .into())
+}
+
+fn summary(commit: &Commit<'_>) -> String {
+    commit.summary().unwrap_or_default().to_string()
+}
+
+fn message(commit: &Commit<'_>) -> String {
+    String::from_utf8_lossy(commit.message_bytes()).to_string()
+}
+
+/// applies the steps after the current one until they are all done
+/// or one conflicts
+fn run_todo(
+    repo: &Repository,
+    rebase: &mut Rebase<'_>,
+    todo: &[RebaseTodo],
+) -> Result<RebaseState> {
+    while let Some(op) = rebase.next() {
+        op?;
+
+        let item = rebase
+            .operation_current()
+            .and_then(|idx| todo.get(idx))
+            .ok_or_else(|| {
+                Error::Generic(String::from("invalid rebase state"))
+            })?;
+
+        if repo.index()?.has_conflicts() {
+            return Ok(RebaseState::Conflict(item.clone()));
+        }
+
+        commit_step(repo, rebase, item)?;
+    }
+
+    rebase.finish(Some(&signature_allow_undefined_name(repo)?))?;
+
+    Ok(RebaseState::Finished)
+}
+
+/// commits the index as the result of `item`, a step without
+/// any changes left (applied upstream already) is dropped
+fn commit_step(
+    repo: &Repository,
+    rebase: &mut Rebase<'_>,
+    item: &RebaseTodo,
+) -> Result<()> {
+    let original = repo.find_commit(item.id.into())?;
+    let head = repo.head()?.peel_to_commit()?;
+    let committer = signature_allow_undefined_name(repo)?;
+
+    let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+
+    if tree.id() == head.tree_id() {
+        return Ok(());
+    }
+
+    match &item.action {
+        RebaseAction::Squash | RebaseAction::Fixup => {
+            let msg = if item.action == RebaseAction::Squash {
+                format!(
+                    "{}\n\n{}",
+                    message(&head).trim_end(),
+                    message(&original)
+                )
+            } else {
+                message(&head)
+            };
+
+            head.amend(
+                Some("HEAD"),
+                None,
+                Some(&committer),
+                None,
+                Some(&msg),
+                Some(&tree),
+            )?;
+        }
+        RebaseAction::Reword(msg) => {
+            rebase.commit(None, &committer, Some(msg))?;
+        }
+        _ => {
+            rebase.commit(None, &committer, None)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// one line per `cmt.<n>` file, a reworded commit has its message
+/// in a file of its own
+fn write_todo(repo: &Repository, todo: &[RebaseTodo]) -> Result<()> {
+    let dir = state_dir(repo);
+
+    let mut lines = String::new();
+    for (idx, item) in todo.iter().enumerate() {
+        let id = item.id.to_string();
+        lines.push_str(&format!("{} {}\n", item.action.name(), id));
+
+        fs::write(dir.join(format!("cmt.{}", idx + 1)), &id)?;
+
+        if let RebaseAction::Reword(msg) = &item.action {
+            fs::write(dir.join(format!("message-{}", id)), msg)?;
+        }
+    }
+
+    fs::write(dir.join("end"), todo.len().to_string())?;
+    fs::write(dir.join(TODO_FILE), lines)?;
+
+    Ok(())
+}
+
+/// the steps of `rebase`, all picked if it was not started by gitui
+fn read_todo(
+    repo: &Repository,
+    rebase: &mut Rebase<'_>,
+) -> Result<Vec<RebaseTodo>> {
+    let path = state_dir(repo).join(TODO_FILE);
+
+    if path.exists() {
+        return fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| parse_todo_line(repo, line))
+            .collect();
+    }
+
+    (0..rebase.len())
+        .filter_map(|idx| rebase.nth(idx).map(|op| op.id()))
+        .map(|id| {
+            Ok(RebaseTodo {
+                id: id.into(),
+                action: RebaseAction::Pick,
+                summary: summary(&repo.find_commit(id)?),
+            })
+        })
+        .collect()
+}
+
+fn parse_todo_line(
+    repo: &Repository,
+    line: &str,
+) -> Result<RebaseTodo> {
+    let invalid =
+        || Error::Generic(format!("invalid rebase todo: {}", line));
+
+    let mut words = line.split(' ');
+    let action = words.next().ok_or_else(invalid)?;
+    let id = Oid::from_str(words.next().ok_or_else(invalid)?)?;
+
+    let action = match action {
+        "pick" => RebaseAction::Pick,
+        "reword" => RebaseAction::Reword(fs::read_to_string(
+            state_dir(repo).join(format!("message-{}", id)),
+        )?),
+        "squash" => RebaseAction::Squash,
+        "fixup" => RebaseAction::Fixup,
+        _ => return Err(invalid()),
+    };
+
+    Ok(RebaseTodo {
+        id: id.into(),
+        action,
+        summary: summary(&repo.find_commit(id)?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        stage_add_file,
+        tests::{repo_init, write_commit_file},
+        utils::repo_write_file,
+        RepoState,
+    };
+    use std::path::Path;
+
+    fn head_messages(repo: &Repository, count: usize) -> Vec<String> {
+        let mut res = Vec::new();
+        let mut commit =
+            repo.head().unwrap().peel_to_commit().unwrap();
+        for _ in 0..count {
+            res.push(message(&commit));
+            commit = match commit.parent(0) {
+                Ok(parent) => parent,
+                Err(_) => break,
+            };
+        }
+        res
+    }
+
+    #[test]
+    fn test_autosquash() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let base = write_commit_file(&repo, "base", "0", "base");
+        write_commit_file(&repo, "a", "a", "a");
+        write_commit_file(&repo, "b", "b", "b");
+        write_commit_file(&repo, "a", "aa", "fixup! a");
+        write_commit_file(&repo, "c", "c", "c");
+        write_commit_file(&repo, "b", "b2", "squash! b");
+        write_commit_file(&repo, "a", "aaa", "fixup! fixup! a");
+
+        let (todo_base, todo) =
+            get_autosquash_todo(repo_path).unwrap();
+        assert_eq!(todo_base, base);
+        assert_eq!(
+            todo.iter()
+                .map(|t| (t.summary.as_str(), t.action.name()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("a", "pick"),
+                ("fixup! a", "fixup"),
+                ("fixup! fixup! a", "fixup"),
+                ("b", "pick"),
+                ("squash! b", "squash"),
+                ("c", "pick"),
+            ]
+        );
+
+        assert_eq!(
+            rebase_interactive(repo_path, todo_base, &todo).unwrap(),
+            RebaseState::Finished
+        );
+        assert_eq!(
+            head_messages(&repo, 4),
+            vec!["c", "b\n\nsquash! b", "a", "base"]
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("a")).unwrap(),
+            "aaa"
+        );
+        assert!(get_autosquash_todo(repo_path).is_err());
+    }
+
+    #[test]
+    fn test_fixup_target_hash() {
+        let id = "a1b2c3d4e5";
+
+        assert!(is_fixup_target("a", "a", id));
+        assert!(!is_fixup_target("a", "b", id));
+        assert!(!is_fixup_target("a1b", "b", id));
+        assert!(is_fixup_target("a1b2", "b", id));
+        assert!(!is_fixup_target("a1b3", "b", id));
+    }
+
+    #[test]
+    fn test_squash_todo() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let base = write_commit_file(&repo, "base", "0", "base");
+        let a = write_commit_file(&repo, "a", "a", "a");
+        write_commit_file(&repo, "b", "b", "b");
+        let c = write_commit_file(&repo, "c", "c", "c");
+
+        assert!(get_squash_todo(repo_path, &[a]).is_err());
+
+        let (todo_base, todo) =
+            get_squash_todo(repo_path, &[c, a]).unwrap();
+        assert_eq!(todo_base, base);
+        assert_eq!(
+            todo.iter()
+                .map(|t| (t.summary.as_str(), t.action.name()))
+                .collect::<Vec<_>>(),
+            vec![("a", "pick"), ("c", "squash"), ("b", "pick")]
+        );
+
+        assert_eq!(
+            rebase_interactive(repo_path, todo_base, &todo).unwrap(),
+            RebaseState::Finished
+        );
+        assert_eq!(
+            head_messages(&repo, 3),
+            vec!["b", "a\n\nc", "base"]
+        );
+    }
+
+    #[test]
+    fn test_reword() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let initial = repo.head().unwrap().target().unwrap();
+        write_commit_file(&repo, "base", "0", "base");
+        let a = write_commit_file(&repo, "a", "a", "a");
+        write_commit_file(&repo, "b", "b", "b");
+
+        assert!(reword(repo_path, initial.into(), "root").is_err());
+
+        assert_eq!(
+            reword(repo_path, a, "a2\n\nbody").unwrap(),
+            RebaseState::Finished
+        );
+        assert_eq!(
+            head_messages(&repo, 3),
+            vec!["b", "a2\n\nbody", "base"]
+        );
+        assert!(root.join("b").is_file());
+    }
+
+    #[test]
+    fn test_drop_commit() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "base", "0", "base");
+        let a = write_commit_file(&repo, "a", "a", "a");
+        write_commit_file(&repo, "b", "b", "b");
+
+        assert!(!check_drop_commit(repo_path, a).unwrap());
+
+        let head = repo.head().unwrap();
+        let branch_name = head.shorthand().unwrap();
+        repo.branch(
+            "up",
+            &repo.find_commit(a.into()).unwrap(),
+            false,
+        )
+        .unwrap();
+        let mut branch =
+            repo.find_branch(branch_name, BranchType::Local).unwrap();
+        branch.set_upstream(Some("up")).unwrap();
+        assert!(check_drop_commit(repo_path, a).unwrap());
+        branch.set_upstream(None).unwrap();
+
+        assert_eq!(
+            drop_commit(repo_path, a).unwrap(),
+            RebaseState::Finished
+        );
+        assert_eq!(head_messages(&repo, 2), vec!["b", "base"]);
+        assert!(!root.join("a").exists());
+        assert!(check_drop_commit(repo_path, a).is_err());
+    }
+
+    #[test]
+    fn test_reorder_squash_drop() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let base = write_commit_file(&repo, "base", "0", "base");
+        write_commit_file(&repo, "a", "a", "a");
+        write_commit_file(&repo, "b", "b", "b");
+        write_commit_file(&repo, "c", "c", "c");
+        write_commit_file(&repo, "d", "d", "d");
+
+        let todo = get_rebase_todo(repo_path, base).unwrap();
+        assert_eq!(
+            todo.iter()
+                .map(|t| t.summary.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c", "d"]
+        );
+
+        let mut todo = todo;
+        todo.swap(0, 2);
+        todo[1].action = RebaseAction::Squash;
+        todo[2].action = RebaseAction::Fixup;
+        todo[3].action = RebaseAction::Reword(String::from("d2"));
+
+        assert_eq!(
+            rebase_interactive(repo_path, base, &todo).unwrap(),
+            RebaseState::Finished
+        );
+
+        assert_eq!(
+            head_messages(&repo, 3),
+            vec!["d2", "c\n\nb", "base"]
+        );
+        assert!(repo.head().unwrap().is_branch());
+        assert!(root.join("a").exists());
+        assert_eq!(get_rebase_progress(repo_path).unwrap(), None);
+    }
+
+    #[test]
+    fn test_drop() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let base = write_commit_file(&repo, "base", "0", "base");
+        write_commit_file(&repo, "a", "a", "a");
+        write_commit_file(&repo, "b", "b", "b");
+
+        let mut todo = get_rebase_todo(repo_path, base).unwrap();
+        todo[0].action = RebaseAction::Drop;
+
+        rebase_interactive(repo_path, base, &todo).unwrap();
+
+        assert_eq!(head_messages(&repo, 2), vec!["b", "base"]);
+        assert!(!root.join("a").exists());
+    }
+
+    #[test]
+    fn test_first_cannot_meld() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let base = write_commit_file(&repo, "base", "0", "base");
+        write_commit_file(&repo, "a", "a", "a");
+
+        let mut todo = get_rebase_todo(repo_path, base).unwrap();
+        todo[0].action = RebaseAction::Fixup;
+
+        assert!(rebase_interactive(repo_path, base, &todo).is_err());
+        assert_eq!(get_rebase_progress(repo_path).unwrap(), None);
+    }
+
+    #[test]
+    fn test_conflict_abort() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let base = write_commit_file(&repo, "f", "0", "base");
+        write_commit_file(&repo, "f", "1", "a");
+        let orig = write_commit_file(&repo, "f", "2", "b");
+
+        let mut todo = get_rebase_todo(repo_path, base).unwrap();
+        todo.swap(0, 1);
+
+        let state =
+            rebase_interactive(repo_path, base, &todo).unwrap();
+        assert!(
+            matches!(state, RebaseState::Conflict(ref t) if t.summary == "b")
+        );
+
+        let progress =
+            get_rebase_progress(repo_path).unwrap().unwrap();
+        assert_eq!(progress.current.unwrap().summary, "b");
+        assert_eq!(progress.remaining.len(), 1);
+        assert_eq!(progress.done, 0);
+        assert_eq!(
+            crate::sync::repo_state(repo_path).unwrap(),
+            RepoState::Rebase
+        );
+        assert_eq!(repo.state(), RepositoryState::RebaseMerge);
+        assert!(repo.path().join("rebase-merge/cmt.2").is_file());
+
+        rebase_abort(repo_path).unwrap();
+
+        assert_eq!(
+            CommitId::from(
+                repo.head().unwrap().peel_to_commit().unwrap().id()
+            ),
+            orig
+        );
+        assert!(repo.head().unwrap().is_branch());
+        assert_eq!(fs::read_to_string(root.join("f")).unwrap(), "2");
+        assert_eq!(
+            crate::sync::repo_state(repo_path).unwrap(),
+            RepoState::Clean
+        );
+    }
+
+    #[test]
+    fn test_rebase_onto() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "f", "0", "base");
+        crate::sync::create_branch(repo_path, "other").unwrap();
+        write_commit_file(&repo, "g", "0", "other");
+        crate::sync::checkout_branch(repo_path, "refs/heads/master")
+            .unwrap();
+        write_commit_file(&repo, "h", "0", "a");
+
+        assert_eq!(
+            rebase_onto(repo_path, "refs/heads/other").unwrap(),
+            RebaseState::Finished
+        );
+        assert_eq!(
+            head_messages(&repo, 3),
+            vec!["a", "other", "base"]
+        );
+        assert!(repo.head().unwrap().is_branch());
+
+        // nothing left to rebase
+        assert!(rebase_onto(repo_path, "refs/heads/other").is_err());
+
+        crate::sync::checkout_branch(repo_path, "refs/heads/other")
+            .unwrap();
+        write_commit_file(&repo, "h", "1", "conflicting");
+
+        let state = rebase_onto(repo_path, "refs/heads/master");
+        assert!(matches!(
+            state,
+            Ok(RebaseState::Conflict(ref t)) if t.summary == "conflicting"
+        ));
+        assert_eq!(
+            crate::sync::repo_state(repo_path).unwrap(),
+            RepoState::Rebase
+        );
+    }
+
+    #[test]
+    fn test_conflict_continue_skip() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let base = write_commit_file(&repo, "f", "0", "base");
+        write_commit_file(&repo, "f", "1", "a");
+        write_commit_file(&repo, "f", "2", "b");
+
+        let mut todo = get_rebase_todo(repo_path, base).unwrap();
+        todo.swap(0, 1);
+
+        rebase_interactive(repo_path, base, &todo).unwrap();
+
+        assert!(rebase_continue(repo_path).is_err());
+
+        repo_write_file(&repo, "f", "2").unwrap();
+        stage_add_file(repo_path, Path::new("f")).unwrap();
+
+        let state = rebase_continue(repo_path).unwrap();
+        assert!(
+            matches!(state, RebaseState::Conflict(ref t) if t.summary == "a")
+        );
+        assert_eq!(
+            get_rebase_progress(repo_path).unwrap().unwrap().done,
+            1
+        );
+
+        assert_eq!(
+            rebase_skip(repo_path).unwrap(),
+            RebaseState::Finished
+        );
+
+        assert_eq!(head_messages(&repo, 2), vec!["b", "base"]);
+        assert_eq!(fs::read_to_string(root.join("f")).unwrap(), "2");
+        assert!(repo.head().unwrap().is_branch());
+    }
+}
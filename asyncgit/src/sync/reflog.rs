@@ -0,0 +1,122 @@
+//! entries of the reflog of `HEAD` or a branch, every position the
+//! ref had is kept there so bad resets and rebases can be undone
+
+use super::{utils::repo, CommitId};
+use crate::error::Result;
+use scopetime::scope_time;
+
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReflogEntry {
+    /// where the ref pointed to after this entry
+    pub id: CommitId,
+    /// where it pointed to before, zero for the first entry
+    pub old_id: CommitId,
+    /// like `commit: <subject>` or `reset: moving to HEAD~1`
+    pub message: String,
+    ///
+    pub committer: String,
+    /// seconds since epoch
+    pub time: i64,
+}
+
+/// entries of `ref_name` (`HEAD` or a full ref), newest first
+pub fn get_reflog(
+    repo_path: &str,
+    ref_name: &str,
+) -> Result<Vec<ReflogEntry>> {
+    scope_time!("get_reflog");
+
+    let repo = repo(repo_path)?;
+    let reflog = repo.reflog(ref_name)?;
+
+    let res = reflog
+        .iter()
+        .map(|entry| {
+            let committer = entry.committer();
+
+            ReflogEntry {
+                id: entry.id_new().into(),
+                old_id: entry.id_old().into(),
+                message: entry
+                    .message()
+                    .unwrap_or_default()
+                    .trim_end()
+                    .to_string(),
+                committer: committer
+                    .name()
+                    .unwrap_or_default()
+                    .to_string(),
+                time: committer.when().seconds(),
+            }
+        })
+        .collect();
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_commit, commit, reset_hard, stage_add_file,
+        tests::repo_init, utils::repo_write_file,
+    };
+    use std::{fs, path::Path};
+
+    #[test]
+    fn test_undo_reset() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo_write_file(&repo, "f", "1").unwrap();
+        stage_add_file(repo_path, Path::new("f")).unwrap();
+        let id = commit(repo_path, "add f").unwrap();
+
+        let first = get_reflog(repo_path, "HEAD").unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].id, id);
+        assert_eq!(first[0].old_id, first[1].id);
+        assert_eq!(first[0].message, "commit: add f");
+        assert_eq!(first[0].committer, "name");
+
+        reset_hard(repo_path, first[1].id).unwrap();
+        assert!(!root.join("f").exists());
+
+        let entries = get_reflog(repo_path, "HEAD").unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].id, first[1].id);
+        assert_eq!(
+            get_reflog(repo_path, "refs/heads/master").unwrap().len(),
+            3
+        );
+
+        // undo the reset
+        reset_hard(repo_path, entries[1].id).unwrap();
+        assert_eq!(fs::read_to_string(root.join("f")).unwrap(), "1");
+    }
+
+    #[test]
+    fn test_checkout() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo_write_file(&repo, "f", "1").unwrap();
+        stage_add_file(repo_path, Path::new("f")).unwrap();
+        commit(repo_path, "add f").unwrap();
+
+        let entries = get_reflog(repo_path, "HEAD").unwrap();
+
+        repo_write_file(&repo, "f", "2").unwrap();
+        assert!(checkout_commit(repo_path, entries[1].id).is_err());
+
+        repo_write_file(&repo, "f", "1").unwrap();
+        checkout_commit(repo_path, entries[1].id).unwrap();
+
+        assert!(repo.head_detached().unwrap());
+        assert!(!root.join("f").exists());
+        assert_eq!(get_reflog(repo_path, "HEAD").unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,271 @@
+//! reading all refs of a repo in one go
+
+use super::{
+    branch::{branch_info, BranchInfo},
+    utils, CommitId, Tags,
+};
+use crate::error::Result;
+use git2::{Branch, Reference, Repository};
+use scopetime::scope_time;
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+/// fingerprint of the ref storage on disk,
+/// changes whenever a ref (or HEAD) is created, updated or removed
+/// and whenever the config (upstreams of the branches) is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefsState(u64);
+
+/// everything the UI needs to know about refs (tags and branches)
+#[derive(Debug, Default)]
+pub struct RefsSnapshot {
+    ///
+    pub tags: Tags,
+    /// sorted by name
+    pub local_branches: Vec<BranchInfo>,
+    /// sorted by name
+    pub remote_branches: Vec<BranchInfo>,
+}
+
+impl RefsSnapshot {
+    ///
+    pub fn branches(&self, local: bool) -> &[BranchInfo] {
+        if local {
+            &self.local_branches
+        } else {
+            &self.remote_branches
+        }
+    }
+}
+
+/// cheap to compute compared to `get_refs`: only stats `HEAD`,
+/// `packed-refs`, `config` and the directories below `refs/` (a ref
+/// update is a rename inside its directory and so bumps the dir
+/// mtime)
+pub fn get_refs_state(repo_path: &str) -> Result<RefsState> {
+    scope_time!("get_refs_state");
+
+    let repo = utils::repo(repo_path)?;
+    let common_dir = common_dir(&repo);
+
+    let mut hasher = DefaultHasher::new();
+
+    hash_file_state(&repo.path().join("HEAD"), &mut hasher);
+    hash_file_state(&common_dir.join("packed-refs"), &mut hasher);
+    hash_file_state(&common_dir.join("config"), &mut hasher);
+    hash_dir_state(&common_dir.join("refs"), &mut hasher);
+
+    Ok(RefsState(hasher.finish()))
+}
+
+/// iterates all refs exactly once to build tags and branch lists
+pub fn get_refs(repo_path: &str) -> Result<RefsSnapshot> {
+    scope_time!("get_refs");
+
+    let repo = utils::repo(repo_path)?;
+
+    let mut res = RefsSnapshot::default();
+
+    for reference in repo.references()? {
+        let reference = reference?;
+
+        if reference.is_tag() {
+            add_tag(&repo, &reference, &mut res.tags);
+        } else if reference.is_branch() || reference.is_remote() {
+            let local = reference.is_branch();
+            if let Ok(info) =
+                branch_info(&repo, &Branch::wrap(reference), local)
+            {
+                if local {
+                    res.local_branches.push(info);
+                } else {
+                    res.remote_branches.push(info);
+                }
+            }
+        }
+    }
+
+    res.local_branches.sort_by(|a, b| a.name.cmp(&b.name));
+    res.remote_branches.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(res)
+}
+
+fn add_tag(
+    repo: &Repository,
+    reference: &Reference,
+    tags: &mut Tags,
+) {
+    let name = match reference.shorthand() {
+        Some(name) => name.to_string(),
+        None => return,
+    };
+
+    let id = match reference.target() {
+        Some(id) => id,
+        None => return,
+    };
+
+    //NOTE: see `get_tags` on annotated vs. lightweight tags
+    let target = if let Ok(tag) = repo.find_tag(id) {
+        CommitId::new(tag.target_id())
+    } else if repo.find_commit(id).is_ok() {
+        CommitId::new(id)
+    } else {
+        return;
+    };
+
+    tags.entry(target).or_default().push(name);
+}
+
+/// linked worktrees keep their refs in the main repos git dir
+fn common_dir(repo: &Repository) -> PathBuf {
+    fs::read_to_string(repo.path().join("commondir")).map_or_else(
+        |_| repo.path().to_path_buf(),
+        |dir| repo.path().join(dir.trim()),
+    )
+}
+
+fn hash_file_state(path: &Path, hasher: &mut DefaultHasher) {
+    if let Ok(meta) = fs::metadata(path) {
+        meta.modified().ok().hash(hasher);
+        meta.len().hash(hasher);
+    }
+}
+
+fn hash_dir_state(path: &Path, hasher: &mut DefaultHasher) {
+    hash_file_state(path, hasher);
+
+    if let Ok(entries) = fs::read_dir(path) {
+        let mut dirs = Vec::new();
+        let mut count = 0_usize;
+
+        for entry in entries.filter_map(std::result::Result::ok) {
+            count += 1;
+            if entry
+                .file_type()
+                .map(|t| t.is_dir())
+                .unwrap_or_default()
+            {
+                dirs.push(entry.path());
+            }
+        }
+
+        count.hash(hasher);
+
+        // read_dir order is not guaranteed
+        dirs.sort();
+        for dir in dirs {
+            hash_dir_state(&dir, hasher);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_refs, get_refs_state};
+    use crate::sync::{
+        create_branch, get_branches_info, get_tags,
+        tests::{repo_init, write_commit_file},
+    };
+    use std::time::Instant;
+
+    #[test]
+    fn test_refs_match_single_lookups() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        create_branch(repo_path, "other").unwrap();
+        let id = write_commit_file(&repo, "a", "a", "commit");
+        let target = repo.find_object(id.into(), None).unwrap();
+        let sig = repo.signature().unwrap();
+        repo.tag("annotated", &target, &sig, "msg", false).unwrap();
+        repo.tag_lightweight("light", &target, false).unwrap();
+
+        let refs = get_refs(repo_path).unwrap();
+
+        assert_eq!(refs.tags, get_tags(repo_path).unwrap());
+
+        let names = |branches: &[super::BranchInfo]| {
+            branches
+                .iter()
+                .map(|b| (b.name.clone(), b.top_commit))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(refs.branches(true)),
+            names(&get_branches_info(repo_path, true).unwrap())
+        );
+        assert_eq!(refs.branches(true).len(), 2);
+        assert!(refs.branches(false).is_empty());
+    }
+
+    #[test]
+    fn test_refs_state_changes() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let state = get_refs_state(repo_path).unwrap();
+        assert_eq!(state, get_refs_state(repo_path).unwrap());
+
+        create_branch(repo_path, "foo/bar").unwrap();
+        let state2 = get_refs_state(repo_path).unwrap();
+        assert_ne!(state, state2);
+
+        write_commit_file(&repo, "a", "a", "commit");
+        let state3 = get_refs_state(repo_path).unwrap();
+        assert_ne!(state2, state3);
+
+        // the upstream is part of the branch info
+        repo.config()
+            .unwrap()
+            .set_str("branch.master.remote", "origin")
+            .unwrap();
+        assert_ne!(state3, get_refs_state(repo_path).unwrap());
+    }
+
+    #[test]
+    fn test_many_refs() {
+        const COUNT: usize = 2000;
+
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        for i in 0..COUNT {
+            repo.branch(&format!("b/{}", i), &head, false).unwrap();
+            repo.tag_lightweight(
+                &format!("t{}", i),
+                head.as_object(),
+                false,
+            )
+            .unwrap();
+        }
+
+        let start = Instant::now();
+        let refs = get_refs(repo_path).unwrap();
+        let refs_time = start.elapsed();
+
+        let start = Instant::now();
+        get_refs_state(repo_path).unwrap();
+        let state_time = start.elapsed();
+
+        // initial `master` branch
+        assert_eq!(refs.local_branches.len(), COUNT + 1);
+        assert_eq!(refs.tags[&head.id().into()].len(), COUNT);
+
+        log::info!(
+            "{} refs: get_refs {:?}, get_refs_state {:?}",
+            COUNT * 2,
+            refs_time,
+            state_time
+        );
+    }
+}
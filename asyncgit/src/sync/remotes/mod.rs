@@ -6,14 +6,16 @@ pub(crate) mod tags;
 use crate::{
     error::{Error, Result},
     sync::{
-        cred::BasicAuthCredential,
-        remotes::push::ProgressNotification, utils,
+        cred::{helper, BasicAuthCredential},
+        remotes::push::ProgressNotification,
+        utils,
     },
 };
-use crossbeam_channel::Sender;
-use git2::{FetchOptions, Repository};
+use crossbeam_channel::{unbounded, Receiver, Select, Sender};
+use git2::{FetchOptions, FetchPrune, Repository};
 use push::remote_callbacks;
 use scopetime::scope_time;
+use std::thread;
 
 /// origin
 pub const DEFAULT_REMOTE_NAME: &str = "origin";
@@ -30,6 +32,92 @@ pub fn get_remotes(repo_path: &str) -> Result<Vec<String>> {
     Ok(remotes)
 }
 
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteInfo {
+    ///
+    pub name: String,
+    ///
+    pub url: Option<String>,
+    /// `pushurl` if it differs from `url`
+    pub push_url: Option<String>,
+}
+
+/// all remotes with their urls, sorted by name
+pub fn get_remotes_info(repo_path: &str) -> Result<Vec<RemoteInfo>> {
+    scope_time!("get_remotes_info");
+
+    let repo = utils::repo(repo_path)?;
+    let remotes = repo.remotes()?;
+
+    remotes
+        .iter()
+        .flatten()
+        .map(|name| {
+            let remote = repo.find_remote(name)?;
+            Ok(RemoteInfo {
+                name: name.to_string(),
+                url: remote.url().map(String::from),
+                push_url: remote.pushurl().map(String::from),
+            })
+        })
+        .collect()
+}
+
+/// `git remote add <name> <url>`
+pub fn add_remote(
+    repo_path: &str,
+    name: &str,
+    url: &str,
+) -> Result<()> {
+    scope_time!("add_remote");
+
+    let repo = utils::repo(repo_path)?;
+    repo.remote(name, url)?;
+
+    Ok(())
+}
+
+/// `git remote remove <name>`, its remote branches are deleted too
+pub fn remove_remote(repo_path: &str, name: &str) -> Result<()> {
+    scope_time!("remove_remote");
+
+    let repo = utils::repo(repo_path)?;
+    repo.remote_delete(name)?;
+
+    Ok(())
+}
+
+/// `git remote rename`, moves the remote branches and the upstreams
+/// of local branches along
+pub fn rename_remote(
+    repo_path: &str,
+    name: &str,
+    new_name: &str,
+) -> Result<()> {
+    scope_time!("rename_remote");
+
+    let repo = utils::repo(repo_path)?;
+    let problems = repo.remote_rename(name, new_name)?;
+
+    // fetch refspecs that are not the default one stay as they are
+    for problem in problems.iter().flatten() {
+        log::warn!("rename remote: refspec not renamed: {}", problem);
+    }
+
+    Ok(())
+}
+
+/// `git remote set-url <name> <url>`
+pub fn set_url(repo_path: &str, name: &str, url: &str) -> Result<()> {
+    scope_time!("set_url");
+
+    let repo = utils::repo(repo_path)?;
+    repo.remote_set_url(name, url)?;
+
+    Ok(())
+}
+
 /// tries to find origin or the only remote that is defined if any
 /// in case of multiple remotes and none named *origin* we fail
 pub fn get_default_remote(repo_path: &str) -> Result<String> {
@@ -87,20 +175,261 @@ pub(crate) fn fetch_origin(
     let mut options = FetchOptions::new();
     options.remote_callbacks(remote_callbacks(
         progress_sender,
-        basic_credential,
+        basic_credential.clone(),
+    ));
+
+    let res = remote
+        .fetch(&[branch], Some(&mut options), None)
+        .map_err(Into::into);
+    helper::report(remote.url(), basic_credential.as_ref(), &res);
+    res?;
+
+    Ok(remote.stats().received_bytes())
+}
+
+/// `git fetch --all [--prune]`: every remote is fetched in its own
+/// thread, the reported transfer progress sums up all of them.
+/// returns the received bytes
+pub(crate) fn fetch_all(
+    repo_path: &str,
+    prune: bool,
+    basic_credential: Option<BasicAuthCredential>,
+    progress_sender: Option<Sender<ProgressNotification>>,
+) -> Result<usize> {
+    scope_time!("fetch_all");
+
+    let remotes = get_remotes(repo_path)?;
+
+    let (receivers, handles): (Vec<_>, Vec<_>) = remotes
+        .iter()
+        .map(|remote| {
+            let (sender, receiver) = unbounded();
+            let repo_path = repo_path.to_string();
+            let remote = remote.clone();
+            let basic_credential = basic_credential.clone();
+
+            let handle = thread::spawn(move || {
+                fetch_remote(
+                    &repo_path,
+                    &remote,
+                    prune,
+                    basic_credential,
+                    sender,
+                )
+            });
+
+            (receiver, handle)
+        })
+        .unzip();
+
+    sum_transfer_progress(&receivers, progress_sender.as_ref());
+
+    let mut bytes = 0;
+    let mut errors = Vec::new();
+    for (remote, handle) in remotes.iter().zip(handles) {
+        match handle.join() {
+            Ok(Ok(received)) => bytes += received,
+            Ok(Err(e)) => errors.push(format!("{}: {}", remote, e)),
+            Err(_) => {
+                errors.push(format!("{}: fetch panicked", remote))
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(bytes)
+    } else {
+        Err(Error::Generic(errors.join("\n")))
+    }
+}
+
+fn fetch_remote(
+    repo_path: &str,
+    remote: &str,
+    prune: bool,
+    basic_credential: Option<BasicAuthCredential>,
+    progress_sender: Sender<ProgressNotification>,
+) -> Result<usize> {
+    let repo = utils::repo(repo_path)?;
+    let mut remote = repo.find_remote(remote)?;
+
+    let mut options = FetchOptions::new();
+    options.remote_callbacks(remote_callbacks(
+        Some(progress_sender),
+        basic_credential.clone(),
     ));
+    options.prune(if prune {
+        FetchPrune::On
+    } else {
+        FetchPrune::Unspecified
+    });
+    // the remotes are fetched at the same time and would all
+    // write it
+    options.update_fetchhead(false);
 
-    remote.fetch(&[branch], Some(&mut options), None)?;
+    let res = remote
+        .fetch::<&str>(&[], Some(&mut options), None)
+        .map_err(Into::into);
+    helper::report(remote.url(), basic_credential.as_ref(), &res);
+    res?;
 
     Ok(remote.stats().received_bytes())
 }
 
+/// forwards the transfer progress of all `receivers` added up until
+/// each of them is closed
+fn sum_transfer_progress(
+    receivers: &[Receiver<ProgressNotification>],
+    sender: Option<&Sender<ProgressNotification>>,
+) {
+    let mut transfers = vec![(0, 0); receivers.len()];
+    let mut select = Select::new();
+    for receiver in receivers {
+        select.recv(receiver);
+    }
+
+    let mut open = receivers.len();
+    while open > 0 {
+        let operation = select.select();
+        let index = operation.index();
+
+        match operation.recv(&receivers[index]) {
+            Ok(ProgressNotification::Transfer {
+                objects,
+                total_objects,
+            }) => {
+                transfers[index] = (objects, total_objects);
+
+                if let Some(sender) = sender {
+                    sender
+                        .send(ProgressNotification::Transfer {
+                            objects: transfers
+                                .iter()
+                                .map(|t| t.0)
+                                .sum(),
+                            total_objects: transfers
+                                .iter()
+                                .map(|t| t.1)
+                                .sum(),
+                        })
+                        .ok();
+                }
+            }
+            Ok(_) => (),
+            Err(_) => {
+                select.remove(index);
+                open -= 1;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sync::tests::debug_cmd_print;
+    use crate::sync::{
+        remotes::push::{branch_refspec, push_to},
+        tests::{
+            debug_cmd_print, repo_init, repo_init_bare,
+            write_commit_file,
+        },
+    };
     use tempfile::TempDir;
 
+    #[test]
+    fn test_manage_remotes() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        add_remote(repo_path, "origin", "https://a.example/repo")
+            .unwrap();
+        add_remote(repo_path, "fork", "https://b.example/repo")
+            .unwrap();
+        assert!(add_remote(repo_path, "fork", "https://c.example")
+            .is_err());
+
+        set_url(repo_path, "fork", "https://c.example/repo").unwrap();
+        rename_remote(repo_path, "origin", "upstream").unwrap();
+
+        assert_eq!(
+            get_remotes_info(repo_path).unwrap(),
+            vec![
+                RemoteInfo {
+                    name: "fork".into(),
+                    url: Some("https://c.example/repo".into()),
+                    push_url: None,
+                },
+                RemoteInfo {
+                    name: "upstream".into(),
+                    url: Some("https://a.example/repo".into()),
+                    push_url: None,
+                },
+            ]
+        );
+
+        remove_remote(repo_path, "fork").unwrap();
+
+        assert_eq!(
+            get_remotes(repo_path).unwrap(),
+            vec![String::from("upstream")]
+        );
+    }
+
+    #[test]
+    fn test_fetch_all_prune() {
+        let (tmp_a_dir, upstream_a) = repo_init_bare().unwrap();
+        let (tmp_b_dir, _) = repo_init_bare().unwrap();
+        let (tmp_repo_dir, repo) = repo_init().unwrap();
+        let (tmp_other_dir, other) = repo_init().unwrap();
+        let a_path = tmp_a_dir.path().to_str().unwrap();
+        let b_path = tmp_b_dir.path().to_str().unwrap();
+        let repo_path = tmp_repo_dir.path().to_str().unwrap();
+        let other_path = tmp_other_dir.path().to_str().unwrap();
+
+        for r in &[&repo, &other] {
+            r.remote("a", a_path).unwrap();
+            r.remote("b", b_path).unwrap();
+        }
+
+        write_commit_file(&repo, "a", "a", "a");
+        for (remote, target) in
+            &[("a", "master"), ("b", "master"), ("a", "gone")]
+        {
+            push_to(
+                repo_path,
+                remote,
+                &branch_refspec("master", target, false),
+                None,
+                None,
+            )
+            .unwrap();
+        }
+
+        assert!(fetch_all(other_path, true, None, None).unwrap() > 0);
+
+        for name in &["a/master", "a/gone", "b/master"] {
+            assert!(other
+                .find_reference(&format!("refs/remotes/{}", name))
+                .is_ok());
+        }
+
+        upstream_a
+            .find_reference("refs/heads/gone")
+            .unwrap()
+            .delete()
+            .unwrap();
+
+        fetch_all(other_path, false, None, None).unwrap();
+        assert!(other.find_reference("refs/remotes/a/gone").is_ok());
+
+        fetch_all(other_path, true, None, None).unwrap();
+        assert!(other.find_reference("refs/remotes/a/gone").is_err());
+        assert!(other
+            .find_reference("refs/remotes/a/master")
+            .is_ok());
+    }
+
     #[test]
     fn test_smoke() {
         let td = TempDir::new().unwrap();
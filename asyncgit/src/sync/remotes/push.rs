@@ -1,16 +1,20 @@
 use super::utils;
 use crate::{
-    error::Result,
+    error::{Error, Result},
     progress::ProgressPercent,
     sync::{
-        branch::branch_set_upstream, cred::BasicAuthCredential,
+        branch::branch_set_upstream,
+        cred::{
+            helper::{self, BAD_CREDENTIALS},
+            BasicAuthCredential, SshCredentials,
+        },
         CommitId,
     },
 };
 use crossbeam_channel::Sender;
 use git2::{
-    Cred, Error as GitError, PackBuilderStage, PushOptions,
-    RemoteCallbacks,
+    Cred, Direction, Error as GitError, PackBuilderStage,
+    PushOptions, Remote, RemoteCallbacks, Repository,
 };
 use scopetime::scope_time;
 
@@ -59,10 +63,54 @@ pub(crate) enum ProgressNotification {
         ///
         current: usize,
     },
+    /// progress the remote reports (sideband), like
+    /// resolving deltas after a push
+    Remote {
+        /// e.g. `Resolving deltas`
+        phase: String,
+        ///
+        current: usize,
+        ///
+        total: usize,
+    },
     ///
     Done,
 }
 
+impl ProgressNotification {
+    /// parses the last message of sideband `data` like
+    /// `Resolving deltas:  50% (1/2)\r`
+    fn from_sideband(data: &[u8]) -> Option<Self> {
+        let text = String::from_utf8_lossy(data);
+        let line = text
+            .split(|c| c == '\r' || c == '\n')
+            .map(str::trim)
+            .rev()
+            .find(|line| !line.is_empty())?;
+
+        let phase = line.split(':').next().unwrap_or(line).trim();
+
+        let (current, total) = line
+            .rfind('(')
+            .and_then(|start| {
+                let counts = &line[start + 1..];
+                let counts = &counts[..counts.find(')')?];
+                let mut counts = counts.splitn(2, '/');
+                Some((
+                    counts.next()?.trim().parse().ok()?,
+                    counts.next()?.trim().parse().ok()?,
+                ))
+            })
+            .unwrap_or((0, 0));
+
+        Some(Self::Remote {
+            phase: phase.to_string(),
+            current,
+            total,
+        })
+    }
+}
+
 impl AsyncProgress for ProgressNotification {
     fn is_done(&self) -> bool {
         *self == ProgressNotification::Done
@@ -91,12 +139,19 @@ impl AsyncProgress for ProgressNotification {
                 total_objects,
                 ..
             } => ProgressPercent::new(objects, total_objects),
+            ProgressNotification::Remote {
+                current, total, ..
+            } => ProgressPercent::new(current, total),
             _ => ProgressPercent::full(),
         }
     }
 }
 
-///
+/// pushes `branch` to the branch of the same name on `remote` and
+/// makes that the upstream unless the branch has one already.
+/// `force` has the semantics of `--force-with-lease`: the push is
+/// refused if the remote branch is not where the remote tracking
+/// branch says (it moved since the last fetch)
 pub(crate) fn push(
     repo_path: &str,
     remote: &str,
@@ -107,6 +162,50 @@ pub(crate) fn push(
 ) -> Result<()> {
     scope_time!("push");
 
+    push_to(
+        repo_path,
+        remote,
+        &branch_refspec(branch, branch, force),
+        basic_credential,
+        progress_sender,
+    )?;
+
+    let repo = utils::repo(repo_path)?;
+    branch_set_upstream(&repo, branch)?;
+
+    Ok(())
+}
+
+/// `[+]refs/heads/<branch>:refs/heads/<target>`
+pub(crate) fn branch_refspec(
+    branch: &str,
+    target: &str,
+    force: bool,
+) -> String {
+    format!(
+        "{}refs/heads/{}:refs/heads/{}",
+        if force { "+" } else { "" },
+        branch,
+        target
+    )
+}
+
+/// `:refs/heads/<target>`, pushing it deletes `target` on the remote
+pub(crate) fn delete_branch_refspec(target: &str) -> String {
+    format!(":refs/heads/{}", target)
+}
+
+/// like `git push <remote> <refspec>`, a forced refspec (`+`) is
+/// checked like `--force-with-lease` (see `push`)
+pub(crate) fn push_to(
+    repo_path: &str,
+    remote: &str,
+    refspec: &str,
+    basic_credential: Option<BasicAuthCredential>,
+    progress_sender: Option<Sender<ProgressNotification>>,
+) -> Result<()> {
+    scope_time!("push_to");
+
     let repo = utils::repo(repo_path)?;
     let mut remote = repo.find_remote(remote)?;
 
@@ -114,20 +213,69 @@ pub(crate) fn push(
 
     options.remote_callbacks(remote_callbacks(
         progress_sender,
-        basic_credential,
+        basic_credential.clone(),
     ));
     options.packbuilder_parallelism(0);
 
-    let branch_name = format!("refs/heads/{}", branch);
-    if force {
-        remote.push(
-            &[String::from("+") + &branch_name],
-            Some(&mut options),
-        )?;
+    let force = refspec.starts_with('+');
+    let res = if force {
+        let src = refspec.trim_start_matches('+');
+        let dst = src.rsplit(':').next().unwrap_or(src);
+        check_lease(&repo, &mut remote, dst, &basic_credential)
     } else {
-        remote.push(&[branch_name.as_str()], Some(&mut options))?;
+        Ok(())
+    }
+    .and_then(|_| {
+        remote
+            .push(&[refspec], Some(&mut options))
+            .map_err(Into::into)
+    });
+    helper::report(remote.url(), basic_credential.as_ref(), &res);
+    res?;
+
+    Ok(())
+}
+
+/// errors unless the ref `dst` on the remote is at the commit of its
+/// remote tracking branch (or both do not exist). this is a separate
+/// connection before the push, so it is a best-effort lease only:
+/// git2 has no `push_negotiation` callback to re-check in between
+fn check_lease(
+    repo: &Repository,
+    remote: &mut Remote,
+    dst: &str,
+    basic_credential: &Option<BasicAuthCredential>,
+) -> Result<()> {
+    scope_time!("check_lease");
+
+    let branch = dst.trim_start_matches("refs/heads/");
+    let tracking = format!(
+        "refs/remotes/{}/{}",
+        remote.name().unwrap_or_default(),
+        branch
+    );
+    let expected = repo
+        .find_reference(&tracking)
+        .ok()
+        .and_then(|reference| reference.target());
+
+    let conn = remote.connect_auth(
+        Direction::Push,
+        Some(remote_callbacks(None, basic_credential.clone())),
+        None,
+    )?;
+    let actual = conn
+        .list()?
+        .iter()
+        .find(|head| head.name() == dst)
+        .map(|head| head.oid());
+
+    if expected != actual {
+        return Err(Error::Generic(format!(
+            "force push refused: '{}' moved on the remote since the last fetch",
+            branch
+        )));
     }
-    branch_set_upstream(&repo, branch)?;
 
     Ok(())
 }
@@ -181,6 +329,19 @@ pub(crate) fn remote_callbacks<'a>(
         true
     });
 
+    let sender_clone = sender.clone();
+    callbacks.sideband_progress(move |data| {
+        log::debug!("remote: {}", String::from_utf8_lossy(data));
+
+        if let (Some(sender), Some(notification)) = (
+            sender_clone.as_ref(),
+            ProgressNotification::from_sideband(data),
+        ) {
+            sender.send(notification).ok();
+        }
+        true
+    });
+
     callbacks.pack_progress(move |stage, current, total| {
         log::debug!("packing: {:?} - {}/{}", stage, current, total);
 
@@ -193,6 +354,10 @@ pub(crate) fn remote_callbacks<'a>(
         });
     });
 
+    // ssh keys are tried one after the other instead
+    let mut ssh_credentials = SshCredentials::new(
+        basic_credential.as_ref().and_then(|c| c.password.clone()),
+    );
     let mut first_call_to_credentials = true;
     // This boolean is used to avoid multiple calls to credentials callback.
     // If credentials are bad, we don't ask the user to re-fill their creds. We push an error and they will be able to restart their action (for example a push) and retype their creds.
@@ -207,23 +372,33 @@ pub(crate) fn remote_callbacks<'a>(
                 username_from_url,
                 allowed_types
             );
+            if allowed_types.is_ssh_key() {
+                let username = username_from_url.or_else(|| {
+                    basic_credential
+                        .as_ref()
+                        .and_then(|c| c.username.as_deref())
+                });
+                return match username {
+                    Some(username) => ssh_credentials
+                        .next(username)
+                        .unwrap_or_else(|| {
+                            Err(GitError::from_str(
+                                "No ssh key was accepted (agent and ~/.ssh).",
+                            ))
+                        }),
+                    None => Err(GitError::from_str(
+                        " Couldn't extract username from url.",
+                    )),
+                };
+            }
+
             if first_call_to_credentials {
                 first_call_to_credentials = false;
             } else {
-                return Err(GitError::from_str("Bad credentials."));
+                return Err(GitError::from_str(BAD_CREDENTIALS));
             }
 
             match &basic_credential {
-                _ if allowed_types.is_ssh_key() => {
-                    match username_from_url {
-                        Some(username) => {
-                            Cred::ssh_key_from_agent(username)
-                        }
-                        None => Err(GitError::from_str(
-                            " Couldn't extract username from url.",
-                        )),
-                    }
-                }
                 Some(BasicAuthCredential {
                     username: Some(user),
                     password: Some(pwd),
@@ -252,11 +427,43 @@ mod tests {
     use super::*;
     use crate::sync::{
         self,
-        tests::{get_commit_ids, repo_init, repo_init_bare},
+        remotes::fetch_origin,
+        tests::{
+            get_commit_ids, repo_init, repo_init_bare,
+            write_commit_file,
+        },
     };
     use git2::Repository;
     use std::{fs::File, io::Write, path::Path};
 
+    #[test]
+    fn test_sideband_progress() {
+        assert_eq!(
+            ProgressNotification::from_sideband(
+                b"Resolving deltas:   0% (0/2)\rResolving deltas:  50% (1/2)\r"
+            ),
+            Some(ProgressNotification::Remote {
+                phase: String::from("Resolving deltas"),
+                current: 1,
+                total: 2,
+            })
+        );
+
+        assert_eq!(
+            ProgressNotification::from_sideband(b"Processing...\n"),
+            Some(ProgressNotification::Remote {
+                phase: String::from("Processing..."),
+                current: 0,
+                total: 0,
+            })
+        );
+
+        assert_eq!(
+            ProgressNotification::from_sideband(b"\r\n"),
+            None
+        );
+    }
+
     #[test]
     fn test_force_push() {
         // This test mimics the scenario of 2 people having 2
@@ -329,6 +536,26 @@ mod tests {
             true
         );
 
+        // Attempt force push without knowing the remote branch,
+        // the lease refuses to overwrite what was never fetched
+        assert!(push(
+            tmp_other_repo_dir.path().to_str().unwrap(),
+            "origin",
+            "master",
+            true,
+            None,
+            None,
+        )
+        .is_err());
+
+        fetch_origin(
+            tmp_other_repo_dir.path().to_str().unwrap(),
+            "master",
+            None,
+            None,
+        )
+        .unwrap();
+
         // Attempt force push,
         // should work as it forces the push through
         assert_eq!(
@@ -459,6 +686,14 @@ mod tests {
         let commits = get_commit_ids(&upstream, 1);
         assert!(!commits.contains(&repo_2_commit));
 
+        fetch_origin(
+            tmp_other_repo_dir.path().to_str().unwrap(),
+            "master",
+            None,
+            None,
+        )
+        .unwrap();
+
         // Attempt force push,
         // should work as it forces the push through
 
@@ -486,4 +721,127 @@ mod tests {
                 .id();
         assert_eq!(new_upstream_parent, repo_2_parent,);
     }
+
+    #[test]
+    fn test_force_push_lease() {
+        let (tmp_repo_dir, repo) = repo_init().unwrap();
+        let (tmp_other_repo_dir, other_repo) = repo_init().unwrap();
+        let (tmp_upstream_dir, _) = repo_init_bare().unwrap();
+        let repo_path = tmp_repo_dir.path().to_str().unwrap();
+        let other_path = tmp_other_repo_dir.path().to_str().unwrap();
+        let upstream_path = tmp_upstream_dir.path().to_str().unwrap();
+
+        repo.remote("origin", upstream_path).unwrap();
+        other_repo.remote("origin", upstream_path).unwrap();
+
+        write_commit_file(&repo, "a", "a", "a");
+        push(repo_path, "origin", "master", false, None, None)
+            .unwrap();
+
+        write_commit_file(&other_repo, "b", "b", "b");
+        fetch_origin(other_path, "master", None, None).unwrap();
+
+        // the remote moves after the fetch
+        write_commit_file(&repo, "c", "c", "c");
+        push(repo_path, "origin", "master", false, None, None)
+            .unwrap();
+
+        assert!(push(
+            other_path, "origin", "master", true, None, None
+        )
+        .is_err());
+
+        fetch_origin(other_path, "master", None, None).unwrap();
+
+        push(other_path, "origin", "master", true, None, None)
+            .unwrap();
+    }
+    #[test]
+    fn test_push_to_other_branch() {
+        let (tmp_repo_dir, repo) = repo_init().unwrap();
+        let (tmp_upstream_dir, upstream) = repo_init_bare().unwrap();
+        let repo_path = tmp_repo_dir.path().to_str().unwrap();
+        let upstream_path = tmp_upstream_dir.path().to_str().unwrap();
+
+        repo.remote("origin", upstream_path).unwrap();
+
+        let id = write_commit_file(&repo, "a", "a", "a");
+
+        push_to(
+            repo_path,
+            "origin",
+            &branch_refspec("master", "review", false),
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            upstream
+                .find_reference("refs/heads/review")
+                .unwrap()
+                .target()
+                .map(CommitId::from),
+            Some(id)
+        );
+        assert!(upstream
+            .find_reference("refs/heads/master")
+            .is_err());
+
+        sync::branch_set_upstream_to(
+            repo_path, "master", "origin", "review",
+        )
+        .unwrap();
+
+        assert_eq!(
+            repo.find_branch("master", git2::BranchType::Local)
+                .unwrap()
+                .upstream()
+                .unwrap()
+                .name()
+                .unwrap(),
+            Some("origin/review")
+        );
+    }
+
+    #[test]
+    fn test_push_delete_remote_branch() {
+        let (tmp_repo_dir, repo) = repo_init().unwrap();
+        let (tmp_upstream_dir, upstream) = repo_init_bare().unwrap();
+        let repo_path = tmp_repo_dir.path().to_str().unwrap();
+        let upstream_path = tmp_upstream_dir.path().to_str().unwrap();
+
+        repo.remote("origin", upstream_path).unwrap();
+
+        write_commit_file(&repo, "a", "a", "a");
+
+        push_to(
+            repo_path,
+            "origin",
+            &branch_refspec("master", "feature", false),
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert!(repo
+            .find_reference("refs/remotes/origin/feature")
+            .is_ok());
+
+        push_to(
+            repo_path,
+            "origin",
+            &delete_branch_refspec("feature"),
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert!(upstream
+            .find_reference("refs/heads/feature")
+            .is_err());
+        assert!(repo
+            .find_reference("refs/remotes/origin/feature")
+            .is_err());
+    }
 }
@@ -5,11 +5,12 @@ use super::{
     utils,
 };
 use crate::{
-    error::Result, progress::ProgressPercent,
-    sync::cred::BasicAuthCredential,
+    error::Result,
+    progress::ProgressPercent,
+    sync::cred::{helper, BasicAuthCredential},
 };
 use crossbeam_channel::Sender;
-use git2::{Direction, PushOptions};
+use git2::{Direction, PushOptions, Remote};
 use scopetime::scope_time;
 use std::collections::HashSet;
 
@@ -44,6 +45,23 @@ impl AsyncProgress for PushTagsProgress {
     }
 }
 
+/// what `AsyncPushTags` sends to the remote
+#[derive(Debug, Clone, PartialEq)]
+pub enum PushTagsTarget {
+    /// all local tags the remote does not know yet
+    Missing,
+    /// a single tag by name
+    Tag(String),
+    /// deletes the tag of that name on the remote
+    DeleteTag(String),
+}
+
+impl Default for PushTagsTarget {
+    fn default() -> Self {
+        Self::Missing
+    }
+}
+
 /// lists the remotes tags
 fn remote_tag_refs(
     repo_path: &str,
@@ -110,18 +128,93 @@ pub(crate) fn push_tags(
         .as_ref()
         .map(|sender| sender.send(PushTagsProgress::CheckRemote));
 
-    let tags_missing = tags_missing_remote(
+    let repo = utils::repo(repo_path)?;
+    let mut remote_handle = repo.find_remote(remote)?;
+
+    let res = push_missing_tags(
         repo_path,
         remote,
+        &mut remote_handle,
         basic_credential.clone(),
-    )?;
+        progress_sender.as_ref(),
+    );
+    helper::report(
+        remote_handle.url(),
+        basic_credential.as_ref(),
+        &res,
+    );
+    res?;
+
+    progress_sender
+        .as_ref()
+        .map(|sender| sender.send(PushTagsProgress::Done));
+
+    Ok(())
+}
+
+/// pushes (or with `delete` removes on the remote) a single tag
+pub(crate) fn push_tag(
+    repo_path: &str,
+    remote: &str,
+    tag: &str,
+    delete: bool,
+    basic_credential: Option<BasicAuthCredential>,
+    progress_sender: Option<Sender<PushTagsProgress>>,
+) -> Result<()> {
+    scope_time!("push_tag");
+
+    progress_sender.as_ref().map(|sender| {
+        sender.send(PushTagsProgress::Push {
+            pushed: 0,
+            total: 1,
+        })
+    });
 
     let repo = utils::repo(repo_path)?;
     let mut remote = repo.find_remote(remote)?;
 
+    let mut options = PushOptions::new();
+    options.remote_callbacks(remote_callbacks(
+        None,
+        basic_credential.clone(),
+    ));
+    options.packbuilder_parallelism(0);
+
+    let refspec = if delete {
+        format!(":refs/tags/{}", tag)
+    } else {
+        format!("refs/tags/{}", tag)
+    };
+    let res = remote
+        .push(&[refspec.as_str()], Some(&mut options))
+        .map_err(Into::into);
+    helper::report(remote.url(), basic_credential.as_ref(), &res);
+    res?;
+
+    progress_sender
+        .as_ref()
+        .map(|sender| sender.send(PushTagsProgress::Done));
+
+    Ok(())
+}
+
+/// pushes the tags the remote does not know yet one by one
+fn push_missing_tags(
+    repo_path: &str,
+    remote_name: &str,
+    remote: &mut Remote,
+    basic_credential: Option<BasicAuthCredential>,
+    progress_sender: Option<&Sender<PushTagsProgress>>,
+) -> Result<()> {
+    let tags_missing = tags_missing_remote(
+        repo_path,
+        remote_name,
+        basic_credential.clone(),
+    )?;
+
     let total = tags_missing.len();
 
-    progress_sender.as_ref().map(|sender| {
+    progress_sender.map(|sender| {
         sender.send(PushTagsProgress::Push { pushed: 0, total })
     });
 
@@ -134,7 +227,7 @@ pub(crate) fn push_tags(
         options.packbuilder_parallelism(0);
         remote.push(&[tag.as_str()], Some(&mut options))?;
 
-        progress_sender.as_ref().map(|sender| {
+        progress_sender.map(|sender| {
             sender.send(PushTagsProgress::Push {
                 pushed: idx + 1,
                 total,
@@ -142,10 +235,6 @@ pub(crate) fn push_tags(
         });
     }
 
-    progress_sender
-        .as_ref()
-        .map(|sender| sender.send(PushTagsProgress::Done));
-
     Ok(())
 }
 
@@ -270,4 +359,38 @@ mod tests {
             tags_missing_remote(clone1_dir, "origin", None).unwrap();
         assert!(tags_missing.is_empty());
     }
+
+    #[test]
+    fn test_push_and_delete_single_tag() {
+        let (r1_dir, _repo) = repo_init_bare().unwrap();
+        let r1_dir = r1_dir.path().to_str().unwrap();
+
+        let (clone1_dir, clone1) = repo_clone(r1_dir).unwrap();
+
+        let clone1_dir = clone1_dir.path().to_str().unwrap();
+
+        let commit1 =
+            write_commit_file(&clone1, "test.txt", "test", "commit1");
+
+        sync::tag(clone1_dir, &commit1, "tag1").unwrap();
+        sync::tag(clone1_dir, &commit1, "tag2").unwrap();
+
+        push(clone1_dir, "origin", "master", false, None, None)
+            .unwrap();
+        push_tag(clone1_dir, "origin", "tag2", false, None, None)
+            .unwrap();
+
+        assert_eq!(
+            remote_tag_refs(clone1_dir, "origin", None).unwrap(),
+            vec![String::from("refs/tags/tag2")]
+        );
+
+        push_tag(clone1_dir, "origin", "tag2", true, None, None)
+            .unwrap();
+
+        assert!(remote_tag_refs(clone1_dir, "origin", None)
+            .unwrap()
+            .is_empty());
+        assert_eq!(sync::get_tags(clone1_dir).unwrap().len(), 1);
+    }
 }
@@ -1,6 +1,10 @@
-use super::utils::{get_head_repo, repo};
+use super::{
+    clean::{preview_clean, run_clean, CleanOptions},
+    utils::{get_head_repo, repo},
+    CommitId,
+};
 use crate::error::Result;
-use git2::{build::CheckoutBuilder, ObjectType};
+use git2::{build::CheckoutBuilder, ObjectType, ResetType};
 use scopetime::scope_time;
 
 ///
@@ -38,16 +42,90 @@ pub fn reset_workdir(repo_path: &str, path: &str) -> Result<()> {
     Ok(())
 }
 
+/// what `reset_to_commit` keeps of the current state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetKind {
+    /// index and workdir stay as they are
+    Soft,
+    /// the index is reset, the workdir stays as it is
+    Mixed,
+    /// index and workdir are reset, all changes are lost
+    Hard,
+}
+
+impl From<ResetKind> for ResetType {
+    fn from(kind: ResetKind) -> Self {
+        match kind {
+            ResetKind::Soft => Self::Soft,
+            ResetKind::Mixed => Self::Mixed,
+            ResetKind::Hard => Self::Hard,
+        }
+    }
+}
+
+/// moves the current branch (or the detached `HEAD`) to `id`
+pub fn reset_to_commit(
+    repo_path: &str,
+    id: CommitId,
+    kind: ResetKind,
+) -> Result<()> {
+    scope_time!("reset_to_commit");
+
+    let repo = repo(repo_path)?;
+
+    let obj =
+        repo.find_object(id.into(), Some(ObjectType::Commit))?;
+    repo.reset(&obj, kind.into(), None)?;
+
+    Ok(())
+}
+
+/// moves the current branch (or the detached `HEAD`) to `id`,
+/// dropping all changes of the index and the workdir
+pub fn reset_hard(repo_path: &str, id: CommitId) -> Result<()> {
+    reset_to_commit(repo_path, id, ResetKind::Hard)
+}
+
+/// removes untracked files like `git clean -f`, `include_dirs` and
+/// `include_ignored` add `-d` and `-x`. with `dry_run` (`-n`)
+/// nothing is removed. returns what is (or would be) removed,
+/// directories end with `/`
+pub fn clean_workdir(
+    repo_path: &str,
+    include_dirs: bool,
+    include_ignored: bool,
+    dry_run: bool,
+) -> Result<Vec<String>> {
+    scope_time!("clean_workdir");
+
+    let options = CleanOptions {
+        directories: include_dirs,
+        ignored: include_ignored,
+    };
+    let paths = preview_clean(repo_path, options)?;
+
+    if !dry_run {
+        run_clean(repo_path, options, &paths)?;
+    }
+
+    Ok(paths)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{reset_stage, reset_workdir};
+    use super::{
+        clean_workdir, reset_stage, reset_to_commit, reset_workdir,
+        ResetKind,
+    };
     use crate::error::Result;
     use crate::sync::{
         commit,
         status::{get_status, StatusType},
         tests::{
-            debug_cmd_print, get_statuses, repo_init, repo_init_empty,
+            debug_cmd_print, get_statuses, repo_init,
+            repo_init_empty, write_commit_file,
         },
+        utils::get_head,
         utils::{stage_add_all, stage_add_file},
     };
     use std::{
@@ -151,6 +229,30 @@ mod tests {
         assert_eq!(get_statuses(repo_path), (0, 0));
     }
 
+    #[test]
+    fn test_reset_to_commit() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = write_commit_file(&repo, "a.txt", "a", "c1");
+        let second = write_commit_file(&repo, "b.txt", "b", "c2");
+
+        reset_to_commit(repo_path, first, ResetKind::Soft).unwrap();
+        assert_eq!(get_head(repo_path).unwrap(), first);
+        assert_eq!(get_statuses(repo_path), (0, 1));
+
+        reset_to_commit(repo_path, second, ResetKind::Hard).unwrap();
+        reset_to_commit(repo_path, first, ResetKind::Mixed).unwrap();
+        assert_eq!(get_head(repo_path).unwrap(), first);
+        assert_eq!(get_statuses(repo_path), (1, 0));
+
+        reset_to_commit(repo_path, second, ResetKind::Hard).unwrap();
+        reset_to_commit(repo_path, first, ResetKind::Hard).unwrap();
+        assert_eq!(get_statuses(repo_path), (0, 0));
+        assert!(!root.join("b.txt").exists());
+    }
+
     #[test]
     fn test_reset_folder() -> Result<()> {
         let (_td, repo) = repo_init().unwrap();
@@ -311,4 +413,28 @@ mod tests {
 
         assert_eq!(get_statuses(repo_path), (0, 0));
     }
+
+    #[test]
+    fn test_clean_workdir() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        fs::create_dir_all(root.join("dir")).unwrap();
+        fs::write(root.join("dir/b.txt"), "b").unwrap();
+        fs::write(root.join("a.txt"), "a").unwrap();
+
+        assert_eq!(
+            clean_workdir(repo_path, true, false, true).unwrap(),
+            vec!["a.txt", "dir/"]
+        );
+        assert_eq!(get_statuses(repo_path), (2, 0));
+
+        assert_eq!(
+            clean_workdir(repo_path, false, false, false).unwrap(),
+            vec!["a.txt"]
+        );
+        assert!(!root.join("a.txt").exists());
+        assert!(root.join("dir/b.txt").exists());
+    }
 }
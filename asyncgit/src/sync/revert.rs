@@ -0,0 +1,228 @@
+//! undoing the changes of a commit with a new one (`git revert`).
+//! a conflict leaves the repo in the revert state until it is
+//! continued or aborted
+
+use super::{
+    commit::signature_allow_undefined_name,
+    utils::{has_uncommitted_changes, repo},
+    CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{Commit, Repository, RepositoryState, ResetType};
+use scopetime::scope_time;
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevertResult {
+    /// id of the revert commit
+    Committed(CommitId),
+    /// resolve and stage the conflicts, then continue (or abort)
+    Conflict,
+}
+
+/// commits the inverse of `id` onto HEAD with the message git uses.
+/// needs a clean working dir, merge commits are rejected
+pub fn revert_commit(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<RevertResult> {
+    scope_time!("revert_commit");
+
+    let repo = repo(repo_path)?;
+
+    if repo.state() != RepositoryState::Clean {
+        return Err(Error::Generic(String::from(
+            "repository is not in a clean state",
+        )));
+    }
+    if has_uncommitted_changes(&repo)? {
+        return Err(Error::UncommittedChanges);
+    }
+
+    let commit = repo.find_commit(id.into())?;
+    if commit.parent_count() > 1 {
+        return Err(Error::Generic(String::from(
+            "merge commits cannot be reverted",
+        )));
+    }
+
+    repo.revert(&commit, None)?;
+
+    if repo.index()?.has_conflicts() {
+        return Ok(RevertResult::Conflict);
+    }
+
+    commit_revert(&repo, &commit).map(RevertResult::Committed)
+}
+
+/// commits the resolved (staged) revert
+pub fn revert_continue(repo_path: &str) -> Result<CommitId> {
+    scope_time!("revert_continue");
+
+    let repo = repo(repo_path)?;
+    ensure_reverting(&repo)?;
+
+    if repo.index()?.has_conflicts() {
+        return Err(Error::Generic(String::from(
+            "resolve all conflicts first",
+        )));
+    }
+
+    let commit =
+        repo.find_reference("REVERT_HEAD")?.peel_to_commit()?;
+
+    commit_revert(&repo, &commit)
+}
+
+/// drops the stopped revert, like `git revert --abort`
+pub fn revert_abort(repo_path: &str) -> Result<()> {
+    scope_time!("revert_abort");
+
+    let repo = repo(repo_path)?;
+    ensure_reverting(&repo)?;
+
+    let head = repo.head()?.peel_to_commit()?;
+    repo.reset(head.as_object(), ResetType::Hard, None)?;
+    repo.cleanup_state()?;
+
+    Ok(())
+}
+
+fn ensure_reverting(repo: &Repository) -> Result<()> {
+    if repo.state() != RepositoryState::Revert {
+        return Err(Error::Generic(String::from(
+            "no revert in progress",
+        )));
+    }
+
+    Ok(())
+}
+
+fn revert_message(reverted: &Commit<'_>) -> String {
+    format!(
+        "Revert \"{}\"\n\nThis reverts commit {}.\n",
+        reverted.summary().unwrap_or_default(),
+        reverted.id()
+    )
+}
+
+fn commit_revert(
+    repo: &Repository,
+    reverted: &Commit<'_>,
+) -> Result<CommitId> {
+    let head = repo.head()?.peel_to_commit()?;
+    let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+
+    if tree.id() == head.tree_id() {
+        repo.cleanup_state()?;
+        return Err(Error::Generic(String::from(
+            "nothing to commit, the changes are reverted already",
+        )));
+    }
+
+    let signature = signature_allow_undefined_name(repo)?;
+
+    let id = repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        &revert_message(reverted),
+        &tree,
+        &[&head],
+    )?;
+
+    repo.cleanup_state()?;
+
+    Ok(id.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        repo_state, stage_add_file,
+        tests::{repo_init, write_commit_file},
+        utils::repo_write_file,
+        RepoState,
+    };
+    use std::{fs, path::Path};
+
+    #[test]
+    fn test_revert() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "f", "0", "base");
+        let reverted = write_commit_file(&repo, "f", "1", "one");
+
+        let res = revert_commit(repo_path, reverted).unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(res, RevertResult::Committed(head.id().into()));
+        assert_eq!(
+            head.message(),
+            Some(
+                format!(
+                    "Revert \"one\"\n\nThis reverts commit {}.\n",
+                    reverted.to_string()
+                )
+                .as_str()
+            )
+        );
+        assert_eq!(fs::read_to_string(root.join("f")).unwrap(), "0");
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+    }
+
+    #[test]
+    fn test_conflict_continue() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "f", "0", "base");
+        let reverted = write_commit_file(&repo, "f", "1", "one");
+        write_commit_file(&repo, "f", "2", "two");
+
+        assert_eq!(
+            revert_commit(repo_path, reverted).unwrap(),
+            RevertResult::Conflict
+        );
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Revert);
+        assert!(revert_continue(repo_path).is_err());
+
+        repo_write_file(&repo, "f", "02").unwrap();
+        stage_add_file(repo_path, Path::new("f")).unwrap();
+
+        revert_continue(repo_path).unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.summary(), Some("Revert \"one\""));
+        assert_eq!(head.parent(0).unwrap().message(), Some("two"));
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+    }
+
+    #[test]
+    fn test_conflict_abort() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "f", "0", "base");
+        let reverted = write_commit_file(&repo, "f", "1", "one");
+        let head = write_commit_file(&repo, "f", "2", "two");
+
+        revert_commit(repo_path, reverted).unwrap();
+        revert_abort(repo_path).unwrap();
+
+        assert_eq!(
+            CommitId::from(
+                repo.head().unwrap().peel_to_commit().unwrap().id()
+            ),
+            head
+        );
+        assert_eq!(fs::read_to_string(root.join("f")).unwrap(), "2");
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert!(revert_abort(repo_path).is_err());
+    }
+}
@@ -0,0 +1,649 @@
+//! verification of signed commits like `git log --show-signature`
+//! and signing new ones per `commit.gpgsign` (or tags per
+//! `tag.gpgSign`)
+
+use super::CommitId;
+use crate::error::{Error, Result};
+use git2::{Config, ErrorCode, Repository, Signature};
+use scopetime::scope_time;
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Output, Stdio},
+};
+use tempfile::NamedTempFile;
+
+const SSH_ARMOR: &str = "-----BEGIN SSH SIGNATURE-----";
+const X509_ARMOR: &str = "-----BEGIN SIGNED MESSAGE-----";
+const SSH_NAMESPACE: &str = "git";
+
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SignatureFormat {
+    /// openpgp, verified with `gpg`
+    Gpg,
+    /// verified with `ssh-keygen` against `gpg.ssh.allowedSignersFile`
+    Ssh,
+    /// cms/s-mime of an x509 certificate, verified with `gpgsm`
+    X509,
+}
+
+impl SignatureFormat {
+    fn from_signature(signature: &str) -> Self {
+        let signature = signature.trim_start();
+        if signature.starts_with(SSH_ARMOR) {
+            Self::Ssh
+        } else if signature.starts_with(X509_ARMOR) {
+            Self::X509
+        } else {
+            Self::Gpg
+        }
+    }
+}
+
+/// outcome of a verification, ordered from best to worst
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SignatureStatus {
+    /// good signature from a trusted key
+    Trusted,
+    /// good signature from a key of unknown or no trust
+    Untrusted,
+    /// good signature, but the key (or signature) expired
+    ExpiredKey,
+    /// good signature from a revoked key
+    RevokedKey,
+    /// the key needed to verify is not available
+    NoPublicKey,
+    /// signature does not match the commit
+    Bad,
+    /// verifier not available or its output not understood
+    Unknown,
+}
+
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureInfo {
+    ///
+    pub format: SignatureFormat,
+    ///
+    pub status: SignatureStatus,
+    /// gpg user id or ssh principal
+    pub signer: Option<String>,
+    /// gpg key id/fingerprint or ssh key type and fingerprint
+    pub key: Option<String>,
+}
+
+/// `None` if the commit is not signed. problems of the verifier
+/// itself end up as `SignatureStatus::Unknown`. this runs the
+/// verifier (`gpg`, `ssh-keygen`..), so it is meant for the commit
+/// shown only
+pub fn get_commit_signature(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<Option<SignatureInfo>> {
+    scope_time!("get_commit_signature");
+
+    let repo = super::utils::repo(repo_path)?;
+
+    let (signature, data) = match repo
+        .extract_signature(&id.into(), None)
+    {
+        Ok(res) => res,
+        Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let signature = String::from_utf8_lossy(&signature);
+    let format = SignatureFormat::from_signature(&signature);
+
+    // removed once dropped
+    let mut sig_file = NamedTempFile::new()?;
+    sig_file.write_all(signature.as_bytes())?;
+    let sig_path = sig_file.path();
+
+    let config = repo.config()?;
+    let res = match format {
+        SignatureFormat::Gpg => verify_gpg(&config, sig_path, &data),
+        SignatureFormat::Ssh => verify_ssh(&config, sig_path, &data),
+        SignatureFormat::X509 => {
+            verify_x509(&config, sig_path, &data)
+        }
+    };
+
+    let (status, signer, key) = res.unwrap_or_else(|e| {
+        log::error!("signature verification failed: {}", e);
+        (SignatureStatus::Unknown, None, None)
+    });
+
+    Ok(Some(SignatureInfo {
+        format,
+        status,
+        signer,
+        key,
+    }))
+}
+
+type Verification = (SignatureStatus, Option<String>, Option<String>);
+
+fn verify_gpg(
+    config: &Config,
+    sig_file: &Path,
+    data: &[u8],
+) -> Result<Verification> {
+    let program = config
+        .get_string("gpg.openpgp.program")
+        .or_else(|_| config.get_string("gpg.program"))
+        .unwrap_or_else(|_| String::from("gpg"));
+
+    let mut cmd = Command::new(program);
+    cmd.args(&["--keyid-format=long", "--status-fd=1", "--verify"])
+        .arg(sig_file)
+        .arg("-");
+
+    let (_, output) = run_with_input(cmd, data)?;
+
+    Ok(parse_gpg_status(&output))
+}
+
+/// `gpgsm` reports in the status format of `gpg`
+fn verify_x509(
+    config: &Config,
+    sig_file: &Path,
+    data: &[u8],
+) -> Result<Verification> {
+    let program = config
+        .get_string("gpg.x509.program")
+        .unwrap_or_else(|_| String::from("gpgsm"));
+
+    let mut cmd = Command::new(program);
+    cmd.args(&["--status-fd=1", "--verify"])
+        .arg(sig_file)
+        .arg("-");
+
+    let (_, output) = run_with_input(cmd, data)?;
+
+    Ok(parse_gpg_status(&output))
+}
+
+/// like git: the principal is looked up in the allowed signers,
+/// signatures of keys not listed there are only checked for
+/// integrity and reported as untrusted
+fn verify_ssh(
+    config: &Config,
+    sig_file: &Path,
+    data: &[u8],
+) -> Result<Verification> {
+    let program = config
+        .get_string("gpg.ssh.program")
+        .unwrap_or_else(|_| String::from("ssh-keygen"));
+
+    let allowed_signers =
+        config.get_path("gpg.ssh.allowedSignersFile").ok();
+
+    let principal = allowed_signers.as_ref().and_then(|allowed| {
+        let output = Command::new(&program)
+            .args(&["-Y", "find-principals", "-f"])
+            .arg(allowed)
+            .arg("-s")
+            .arg(sig_file)
+            .output()
+            .ok()?;
+
+        if output.status.success() {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .next()
+                .map(String::from)
+        } else {
+            None
+        }
+    });
+
+    let mut cmd = Command::new(&program);
+    match (&allowed_signers, &principal) {
+        (Some(allowed), Some(principal)) => {
+            cmd.args(&["-Y", "verify", "-f"])
+                .arg(allowed)
+                .args(&["-I", principal.as_str()]);
+        }
+        _ => {
+            cmd.args(&["-Y", "check-novalidate"]);
+        }
+    }
+    cmd.args(&["-n", SSH_NAMESPACE, "-s"]).arg(sig_file);
+
+    let (success, output) = run_with_input(cmd, data)?;
+
+    let status = match (success, &principal) {
+        (false, _) => SignatureStatus::Bad,
+        (true, Some(_)) => SignatureStatus::Trusted,
+        (true, None) => SignatureStatus::Untrusted,
+    };
+
+    Ok((status, principal, parse_ssh_key(&output)))
+}
+
+/// `commit.gpgsign` of the repo config
+pub fn commit_signing_enabled(repo_path: &str) -> Result<bool> {
+    let repo = super::utils::repo(repo_path)?;
+    let config = repo.config()?;
+
+    Ok(config.get_bool("commit.gpgsign").unwrap_or_default())
+}
+
+/// `tag.gpgSign` of the repo config
+pub fn tag_signing_enabled(repo_path: &str) -> Result<bool> {
+    let repo = super::utils::repo(repo_path)?;
+    let config = repo.config()?;
+
+    Ok(config.get_bool("tag.gpgsign").unwrap_or_default())
+}
+
+/// signature of the commit `buffer` in the `gpg.format` of the
+/// config, `None` unless `commit.gpgsign` is set
+pub(crate) fn sign_commit_buffer(
+    repo: &Repository,
+    committer: &Signature<'_>,
+    buffer: &[u8],
+) -> Result<Option<String>> {
+    scope_time!("sign_commit_buffer");
+
+    let config = repo.config()?;
+
+    if !config.get_bool("commit.gpgsign").unwrap_or_default() {
+        return Ok(None);
+    }
+
+    sign_buffer(repo, committer, buffer).map(Some)
+}
+
+/// signature of `buffer` in the `gpg.format` of the config, the key
+/// defaults to the one of `signer`
+pub(crate) fn sign_buffer(
+    repo: &Repository,
+    signer: &Signature<'_>,
+    buffer: &[u8],
+) -> Result<String> {
+    let config = repo.config()?;
+
+    let key = config.get_string("user.signingkey").ok();
+    let format = config
+        .get_string("gpg.format")
+        .unwrap_or_else(|_| String::from("openpgp"));
+
+    match format.as_str() {
+        "openpgp" | "x509" => {
+            // like git: the committer identifies the key by default
+            let key = key.unwrap_or_else(|| {
+                format!(
+                    "{} <{}>",
+                    signer.name().unwrap_or_default(),
+                    signer.email().unwrap_or_default()
+                )
+            });
+            sign_gpg(&config, &format, &key, buffer)
+        }
+        "ssh" => {
+            let key = key.ok_or_else(|| {
+                Error::Sign(String::from(
+                    "user.signingkey is needed for ssh signing",
+                ))
+            })?;
+            sign_ssh(&config, &key, buffer)
+        }
+        _ => Err(Error::Sign(format!(
+            "unsupported gpg.format: {}",
+            format
+        ))),
+    }
+}
+
+fn sign_gpg(
+    config: &Config,
+    format: &str,
+    key: &str,
+    buffer: &[u8],
+) -> Result<String> {
+    let program = config
+        .get_string(&format!("gpg.{}.program", format))
+        .or_else(|_| {
+            if format == "openpgp" {
+                config.get_string("gpg.program")
+            } else {
+                Ok(String::from("gpgsm"))
+            }
+        })
+        .unwrap_or_else(|_| String::from("gpg"));
+
+    let mut cmd = Command::new(&program);
+    cmd.args(&["--status-fd=2", "-bsau", key]);
+
+    let output = run_piped(cmd, buffer)
+        .map_err(|e| Error::Sign(format!("{}: {}", program, e)))?;
+    let status = String::from_utf8_lossy(&output.stderr);
+
+    if !output.status.success()
+        || !status.contains("[GNUPG:] SIG_CREATED ")
+    {
+        return Err(Error::Sign(format!(
+            "{} did not sign:\n{}",
+            program,
+            status.trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// the key is a path or, like in git, the public key itself
+/// (`key::ssh-ed25519 ..`) whose private key is in the ssh agent
+fn sign_ssh(
+    config: &Config,
+    key: &str,
+    buffer: &[u8],
+) -> Result<String> {
+    let program = config
+        .get_string("gpg.ssh.program")
+        .unwrap_or_else(|_| String::from("ssh-keygen"));
+
+    let literal = key
+        .strip_prefix("key::")
+        .or_else(|| Some(key).filter(|key| key.starts_with("ssh-")));
+    // removed once dropped
+    let literal_file = match literal {
+        Some(literal) => {
+            let mut file = NamedTempFile::new()?;
+            file.write_all(literal.as_bytes())?;
+            Some(file)
+        }
+        None => None,
+    };
+    let key_file = literal_file
+        .as_ref()
+        .map_or_else(|| Path::new(key), NamedTempFile::path);
+
+    let mut cmd = Command::new(&program);
+    cmd.args(&["-Y", "sign", "-n", SSH_NAMESPACE, "-f"])
+        .arg(key_file);
+
+    let output = run_piped(cmd, buffer)
+        .map_err(|e| Error::Sign(format!("{}: {}", program, e)))?;
+
+    if !output.status.success() {
+        return Err(Error::Sign(format!(
+            "{} did not sign:\n{}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn run_piped(mut cmd: Command, input: &[u8]) -> Result<Output> {
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| Error::Generic(String::from("no stdin")))?
+        .write_all(input)?;
+
+    Ok(child.wait_with_output()?)
+}
+
+/// runs `cmd` with `input` on stdin, returns whether it succeeded
+/// and stdout followed by stderr
+fn run_with_input(
+    cmd: Command,
+    input: &[u8],
+) -> Result<(bool, String)> {
+    let output = run_piped(cmd, input)?;
+
+    Ok((
+        output.status.success(),
+        format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ),
+    ))
+}
+
+/// interprets the `--status-fd` lines of `gpg --verify`
+fn parse_gpg_status(output: &str) -> Verification {
+    let mut status = SignatureStatus::Unknown;
+    let mut good = false;
+    let mut trusted = false;
+    let mut signer = None;
+    let mut key = None;
+
+    for line in output.lines() {
+        let line = match line.strip_prefix("[GNUPG:] ") {
+            Some(line) => line,
+            None => continue,
+        };
+
+        let mut words = line.splitn(3, ' ');
+        let keyword = words.next().unwrap_or_default();
+        let key_id = words.next().map(String::from);
+        let user_id = words.next().map(String::from);
+
+        match keyword {
+            "GOODSIG" => {
+                good = true;
+                key = key_id;
+                signer = user_id;
+            }
+            "EXPSIG" | "EXPKEYSIG" => {
+                status = SignatureStatus::ExpiredKey;
+                key = key_id;
+                signer = user_id;
+            }
+            "REVKEYSIG" => {
+                status = SignatureStatus::RevokedKey;
+                key = key_id;
+                signer = user_id;
+            }
+            "BADSIG" => {
+                status = SignatureStatus::Bad;
+                key = key_id;
+                signer = user_id;
+            }
+            "NO_PUBKEY" => {
+                status = SignatureStatus::NoPublicKey;
+                key = key_id;
+            }
+            "TRUST_MARGINAL" | "TRUST_FULLY" | "TRUST_ULTIMATE" => {
+                trusted = true;
+            }
+            _ => (),
+        }
+    }
+
+    if good && status == SignatureStatus::Unknown {
+        status = if trusted {
+            SignatureStatus::Trusted
+        } else {
+            SignatureStatus::Untrusted
+        };
+    }
+
+    (status, signer, key)
+}
+
+/// `Good "git" signature for x with ED25519 key SHA256:..`
+fn parse_ssh_key(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        line.find(" with ").map(|pos| {
+            line[pos + " with ".len()..].trim().to_string()
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        commit, stage_add_file, tests::repo_init,
+        utils::repo_write_file,
+    };
+
+    #[test]
+    fn test_format() {
+        assert_eq!(
+            SignatureFormat::from_signature(
+                "-----BEGIN PGP SIGNATURE-----\n\n-----END"
+            ),
+            SignatureFormat::Gpg
+        );
+        assert_eq!(
+            SignatureFormat::from_signature(
+                "-----BEGIN SSH SIGNATURE-----\nU1NIU0lH\n-----END"
+            ),
+            SignatureFormat::Ssh
+        );
+        assert_eq!(
+            SignatureFormat::from_signature(
+                "-----BEGIN SIGNED MESSAGE-----\nMIAG\n-----END"
+            ),
+            SignatureFormat::X509
+        );
+    }
+
+    #[test]
+    fn test_gpg_trust_levels() {
+        let trusted = "[GNUPG:] NEWSIG\n\
+            [GNUPG:] GOODSIG 0123456789ABCDEF Jane <jane@x.org>\n\
+            [GNUPG:] VALIDSIG 00FF 2021-03-01\n\
+            [GNUPG:] TRUST_ULTIMATE 0 pgp\n";
+        assert_eq!(
+            parse_gpg_status(trusted),
+            (
+                SignatureStatus::Trusted,
+                Some(String::from("Jane <jane@x.org>")),
+                Some(String::from("0123456789ABCDEF")),
+            )
+        );
+
+        let untrusted = "[GNUPG:] GOODSIG 0123456789ABCDEF Jane\n\
+            [GNUPG:] TRUST_UNDEFINED 0 pgp\n";
+        assert_eq!(
+            parse_gpg_status(untrusted).0,
+            SignatureStatus::Untrusted
+        );
+
+        let expired = "[GNUPG:] KEYEXPIRED 1600000000\n\
+            [GNUPG:] EXPKEYSIG 0123456789ABCDEF Jane\n";
+        assert_eq!(
+            parse_gpg_status(expired).0,
+            SignatureStatus::ExpiredKey
+        );
+
+        let revoked = "[GNUPG:] REVKEYSIG 0123456789ABCDEF Jane\n";
+        assert_eq!(
+            parse_gpg_status(revoked).0,
+            SignatureStatus::RevokedKey
+        );
+
+        let no_key =
+            "[GNUPG:] ERRSIG 0123456789ABCDEF 1 10 00 16 9\n\
+            [GNUPG:] NO_PUBKEY 0123456789ABCDEF\n";
+        assert_eq!(
+            parse_gpg_status(no_key),
+            (
+                SignatureStatus::NoPublicKey,
+                None,
+                Some(String::from("0123456789ABCDEF")),
+            )
+        );
+
+        let bad = "[GNUPG:] BADSIG 0123456789ABCDEF Jane\n";
+        assert_eq!(parse_gpg_status(bad).0, SignatureStatus::Bad);
+
+        assert_eq!(
+            parse_gpg_status("gpg: not found").0,
+            SignatureStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn test_ssh_key() {
+        assert_eq!(
+            parse_ssh_key(
+                "Good \"git\" signature for jane@x.org with ED25519 key SHA256:abc\n"
+            ),
+            Some(String::from("ED25519 key SHA256:abc"))
+        );
+        assert_eq!(
+            parse_ssh_key("Could not verify signature.\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unsigned_commit() {
+        let (_td, repo) = repo_init().unwrap();
+
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo_write_file(&repo, "foo", "a").unwrap();
+        stage_add_file(repo_path, Path::new("foo")).unwrap();
+        let id = commit(repo_path, "unsigned").unwrap();
+
+        assert_eq!(
+            get_commit_signature(repo_path, id).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_verify_outside_repo() {
+        let (_td, repo) = repo_init().unwrap();
+
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let sig = repo.signature().unwrap();
+        let buffer = repo
+            .commit_create_buffer(
+                &sig,
+                &sig,
+                "signed",
+                &head.tree().unwrap(),
+                &[&head],
+            )
+            .unwrap();
+        let id = repo
+            .commit_signed(
+                buffer.as_str().unwrap(),
+                "-----BEGIN PGP SIGNATURE-----\n\nabc\n-----END PGP SIGNATURE-----\n",
+                None,
+            )
+            .unwrap();
+
+        let git_dir = || {
+            let mut entries = std::fs::read_dir(repo.path())
+                .unwrap()
+                .map(|e| e.unwrap().file_name())
+                .collect::<Vec<_>>();
+            entries.sort();
+            entries
+        };
+        let before = git_dir();
+
+        let res = get_commit_signature(repo_path, id.into())
+            .unwrap()
+            .unwrap();
+        assert_eq!(res.format, SignatureFormat::Gpg);
+        assert_ne!(res.status, SignatureStatus::Trusted);
+
+        assert_eq!(git_dir(), before);
+    }
+}
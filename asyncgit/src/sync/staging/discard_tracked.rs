@@ -1,17 +1,25 @@
 use super::{apply_selection, load_file};
-use crate::error::Result;
 use crate::sync::{
-    diff::DiffLinePosition,
+    diff::{get_diff, DiffLinePosition},
     patches::get_file_diff_patch_and_hunklines,
     utils::{repo, repo_write_file},
 };
+use crate::{
+    error::{Error, Result},
+    hash,
+};
 use scopetime::scope_time;
 
-/// discards specific lines in an unstaged hunk of a diff
+/// discards specific lines in an unstaged hunk of a diff.
+/// `diff_hash` is the hash (see `crate::hash`) of the diff the lines
+/// were picked from, if the file changed since then the line numbers
+/// would point at the wrong content and `Error::OutdatedDiff` is
+/// returned instead of touching the file.
 pub fn discard_lines(
     repo_path: &str,
     file_path: &str,
     lines: &[DiffLinePosition],
+    diff_hash: u64,
 ) -> Result<()> {
     scope_time!("discard_lines");
 
@@ -19,6 +27,12 @@ pub fn discard_lines(
         return Ok(());
     }
 
+    let current_diff =
+        get_diff(repo_path, file_path.to_string(), false, None)?;
+    if hash(&current_diff) != diff_hash {
+        return Err(Error::OutdatedDiff);
+    }
+
     let repo = repo(repo_path)?;
     repo.index()?.read(true)?;
 
@@ -43,7 +57,17 @@ pub fn discard_lines(
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::sync::tests::{repo_init, write_commit_file};
+    use crate::sync::{
+        diff::get_diff,
+        tests::{repo_init, write_commit_file},
+    };
+
+    fn diff_hash(path: &str) -> u64 {
+        crate::hash(
+            &get_diff(path, String::from("test.txt"), false, None)
+                .unwrap(),
+        )
+    }
 
     #[test]
     fn test_discard() {
@@ -88,6 +112,7 @@ mod test {
                     new_lineno: Some(2),
                 },
             ],
+            diff_hash(path),
         )
         .unwrap();
 
@@ -127,6 +152,7 @@ end
                 old_lineno: None,
                 new_lineno: Some(3),
             }],
+            diff_hash(path),
         )
         .unwrap();
 
@@ -172,6 +198,7 @@ end
                     new_lineno: Some(2),
                 },
             ],
+            diff_hash(path),
         )
         .unwrap();
 
@@ -219,6 +246,7 @@ end
                     new_lineno: Some(4),
                 },
             ],
+            diff_hash(path),
         )
         .unwrap();
 
@@ -262,6 +290,7 @@ end
                     new_lineno: Some(2),
                 },
             ],
+            diff_hash(path),
         )
         .unwrap();
 
@@ -301,6 +330,7 @@ end
                 old_lineno: Some(2),
                 new_lineno: None,
             }],
+            diff_hash(path),
         )
         .unwrap();
 
@@ -334,6 +364,7 @@ end
                 old_lineno: None,
                 new_lineno: Some(2),
             }],
+            diff_hash(path),
         )
         .unwrap();
 
@@ -341,4 +372,40 @@ end
 
         assert_eq!(result_file.as_str(), FILE_3);
     }
+
+    #[test]
+    fn test_discard_outdated_diff() {
+        static FILE_1: &str = "start\nend\n";
+        static FILE_2: &str = "start\n1\n2\nend\n";
+        static FILE_3: &str = "start\n0\n1\n2\nend\n";
+
+        let (path, repo) = repo_init().unwrap();
+        let path = path.path().to_str().unwrap();
+
+        write_commit_file(&repo, "test.txt", FILE_1, "c1");
+
+        repo_write_file(&repo, "test.txt", FILE_2).unwrap();
+
+        // lines picked in this diff
+        let hash = diff_hash(path);
+
+        // file changed on disk after the diff was shown
+        repo_write_file(&repo, "test.txt", FILE_3).unwrap();
+
+        let res = discard_lines(
+            path,
+            "test.txt",
+            &[DiffLinePosition {
+                old_lineno: None,
+                new_lineno: Some(3),
+            }],
+            hash,
+        );
+
+        assert!(matches!(res, Err(Error::OutdatedDiff)));
+
+        let result_file = load_file(&repo, "test.txt").unwrap();
+
+        assert_eq!(result_file.as_str(), FILE_3);
+    }
 }
@@ -98,7 +98,8 @@ mod test {
         .unwrap();
 
         let diff =
-            get_diff(path, String::from("test.txt"), true).unwrap();
+            get_diff(path, String::from("test.txt"), true, None)
+                .unwrap();
 
         assert_eq!(diff.lines, 3);
         assert_eq!(
@@ -141,7 +142,8 @@ c = 4";
         .unwrap();
 
         let diff =
-            get_diff(path, String::from("test.txt"), true).unwrap();
+            get_diff(path, String::from("test.txt"), true, None)
+                .unwrap();
 
         assert_eq!(diff.lines, 5);
         assert_eq!(
@@ -175,7 +177,8 @@ c = 4";
         assert_eq!(get_statuses(path), (0, 1));
 
         let diff_before =
-            get_diff(path, String::from("test.txt"), true).unwrap();
+            get_diff(path, String::from("test.txt"), true, None)
+                .unwrap();
 
         assert_eq!(diff_before.lines, 5);
 
@@ -193,7 +196,8 @@ c = 4";
         assert_eq!(get_statuses(path), (1, 1));
 
         let diff =
-            get_diff(path, String::from("test.txt"), true).unwrap();
+            get_diff(path, String::from("test.txt"), true, None)
+                .unwrap();
 
         assert_eq!(diff.lines, 4);
     }
@@ -1,4 +1,9 @@
-use super::{utils::repo, CommitId};
+use super::{
+    commit_files::{get_commit_diff, get_commit_files},
+    diff::{raw_diff_to_file_diff, DiffOptions, FileDiff},
+    utils::{has_uncommitted_changes, repo, work_dir},
+    CommitId,
+};
 use crate::error::{Error, Result};
 use git2::{
     build::CheckoutBuilder, Oid, Repository, StashApplyOptions,
@@ -6,6 +11,8 @@ use git2::{
 };
 use scopetime::scope_time;
 
+const STASH_REF: &str = "refs/stash";
+
 ///
 pub fn get_stashes(repo_path: &str) -> Result<Vec<CommitId>> {
     scope_time!("get_stashes");
@@ -31,6 +38,44 @@ pub fn is_stash_commit(
     Ok(stashes.contains(&id))
 }
 
+/// combined diff of all files of a stash, its untracked files
+/// included, each file starts with a `DiffLineType::File` line
+pub fn stash_diff(
+    repo_path: &str,
+    stash_id: CommitId,
+    options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+    scope_time!("stash_diff");
+
+    if !is_stash_commit(repo_path, &stash_id)? {
+        return Err(Error::Generic(format!(
+            "{} is no stash",
+            stash_id.get_short_string()
+        )));
+    }
+
+    let repo = repo(repo_path)?;
+    let work_dir = work_dir(&repo)?;
+
+    let mut res = FileDiff::default();
+
+    for file in get_commit_files(repo_path, stash_id)? {
+        let diff = get_commit_diff(
+            &repo,
+            stash_id,
+            0,
+            Some(file.path.clone()),
+            options,
+        )?;
+        res.append_file(
+            &file.path,
+            raw_diff_to_file_diff(&diff, work_dir)?,
+        );
+    }
+
+    Ok(res)
+}
+
 ///
 pub fn stash_drop(repo_path: &str, stash_id: CommitId) -> Result<()> {
     scope_time!("stash_drop");
@@ -66,6 +111,175 @@ pub fn stash_apply(
     Ok(())
 }
 
+/// like `stash_apply` but the staged changes of the stash are
+/// staged again (`git stash apply --index`)
+pub fn stash_apply_index(
+    repo_path: &str,
+    stash_id: CommitId,
+) -> Result<()> {
+    scope_time!("stash_apply_index");
+
+    let mut repo = repo(repo_path)?;
+
+    let index = get_stash_index(&mut repo, stash_id.get_oid())?;
+
+    let mut opt = StashApplyOptions::default();
+    opt.reinstantiate_index();
+    repo.stash_apply(index, Some(&mut opt))?;
+
+    Ok(())
+}
+
+/// applies and drops the stash, it is kept if it does not apply
+/// cleanly (`git stash pop`, `--index` with `reinstate_index`)
+pub fn stash_pop(
+    repo_path: &str,
+    stash_id: CommitId,
+    reinstate_index: bool,
+) -> Result<()> {
+    scope_time!("stash_pop");
+
+    let mut repo = repo(repo_path)?;
+
+    let index = get_stash_index(&mut repo, stash_id.get_oid())?;
+
+    pop_at(&mut repo, index, reinstate_index)
+}
+
+/// creates `branch` at the commit the stash was made on, switches
+/// to it and pops the stash there with its index, so the stash
+/// applies without conflicts (`git stash branch`)
+pub fn stash_branch(
+    repo_path: &str,
+    stash_id: CommitId,
+    branch: &str,
+) -> Result<()> {
+    scope_time!("stash_branch");
+
+    let mut repo = repo(repo_path)?;
+
+    let index = get_stash_index(&mut repo, stash_id.get_oid())?;
+
+    if has_uncommitted_changes(&repo)? {
+        return Err(Error::UncommittedChanges);
+    }
+
+    {
+        let base = repo.find_commit(stash_id.into())?.parent(0)?;
+        let branch_ref = repo.branch(branch, &base, false)?;
+        let branch_name =
+            branch_ref.get().name().ok_or_else(|| {
+                Error::Generic(String::from("branch name not utf8"))
+            })?;
+
+        repo.checkout_tree(
+            base.as_object(),
+            Some(CheckoutBuilder::new().safe()),
+        )?;
+        repo.set_head(branch_name)?;
+    }
+
+    pop_at(&mut repo, index, true)
+}
+
+fn pop_at(
+    repo: &mut Repository,
+    index: usize,
+    reinstate_index: bool,
+) -> Result<()> {
+    let mut opt = StashApplyOptions::default();
+    if reinstate_index {
+        opt.reinstantiate_index();
+    }
+    repo.stash_pop(index, Some(&mut opt))?;
+
+    Ok(())
+}
+
+/// replaces the message of a stash keeping its position in the
+/// stash list, the stash commit is recreated with the same tree and
+/// parents (base, index and untracked files) so only its id changes.
+/// returns the new id.
+pub fn stash_rename(
+    repo_path: &str,
+    stash_id: CommitId,
+    message: &str,
+) -> Result<CommitId> {
+    scope_time!("stash_rename");
+
+    let mut repo = repo(repo_path)?;
+
+    // fails early if this is no stash
+    get_stash_index(&mut repo, stash_id.into())?;
+
+    let stash = repo.find_commit(stash_id.into())?;
+    let parents = stash.parents().collect::<Vec<_>>();
+    let new_message = stash_message_with_branch(
+        &String::from_utf8_lossy(stash.message_bytes()),
+        message,
+    );
+
+    let new_id = repo.commit(
+        None,
+        &stash.author(),
+        &stash.committer(),
+        &new_message,
+        &stash.tree()?,
+        &parents.iter().collect::<Vec<_>>(),
+    )?;
+
+    // the stash list is the reflog of `refs/stash`, newest first
+    let mut reflog = repo.reflog(STASH_REF)?;
+    let entries = reflog
+        .iter()
+        .map(|entry| {
+            if entry.id_new() == stash_id.get_oid() {
+                (
+                    new_id,
+                    entry.committer().to_owned(),
+                    new_message.clone(),
+                )
+            } else {
+                (
+                    entry.id_new(),
+                    entry.committer().to_owned(),
+                    entry.message().unwrap_or_default().to_string(),
+                )
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if let Some((newest, _, _)) = entries.first() {
+        // updating the ref logs an entry, replaced just below
+        repo.reference(STASH_REF, *newest, true, "")?;
+    }
+
+    for idx in (0..reflog.len()).rev() {
+        reflog.remove(idx, false)?;
+    }
+    for (id, committer, msg) in entries.iter().rev() {
+        reflog.append(*id, committer, Some(msg))?;
+    }
+    reflog.write()?;
+
+    Ok(new_id.into())
+}
+
+/// keeps the `On <branch>: ` prefix git puts in front of stash
+/// messages (`WIP on` if created without a message)
+fn stash_message_with_branch(old: &str, message: &str) -> String {
+    let branch = old.split(": ").next().and_then(|prefix| {
+        prefix
+            .strip_prefix("On ")
+            .or_else(|| prefix.strip_prefix("WIP on "))
+    });
+
+    branch.map_or_else(
+        || message.to_string(),
+        |branch| format!("On {}: {}", branch, message),
+    )
+}
+
 fn get_stash_index(
     repo: &mut Repository,
     stash_id: Oid,
@@ -117,12 +331,15 @@ pub fn stash_save(
 mod tests {
     use super::*;
     use crate::sync::{
-        commit, get_commit_files, get_commits_info, stage_add_file,
+        commit,
+        diff::DiffLineType,
+        get_commit_files, get_commits_info, reset_hard,
+        stage_add_file,
         tests::{
             debug_cmd_print, get_statuses, repo_init,
             write_commit_file,
         },
-        utils::repo_write_file,
+        utils::{get_head_repo, repo_write_file},
     };
     use std::{fs::File, io::Write, path::Path};
 
@@ -286,4 +503,150 @@ mod tests {
 
         assert!(res.is_ok());
     }
+
+    #[test]
+    fn test_stash_rename() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "test.txt", "test", "c1");
+
+        repo_write_file(&repo, "test.txt", "first")?;
+        let first =
+            stash_save(repo_path, Some("first"), true, false)?;
+
+        repo_write_file(&repo, "test.txt", "second")?;
+        repo_write_file(&repo, "new.txt", "untracked")?;
+        let second = stash_save(repo_path, None, true, false)?;
+
+        repo_write_file(&repo, "test.txt", "third")?;
+        let third =
+            stash_save(repo_path, Some("third"), true, false)?;
+
+        let renamed = stash_rename(repo_path, second, "renamed")?;
+
+        // same position in the list
+        assert_eq!(
+            get_stashes(repo_path)?,
+            vec![third, renamed, first]
+        );
+
+        let old = repo.find_commit(second.into())?;
+        let new = repo.find_commit(renamed.into())?;
+        assert_eq!(new.message(), Some("On master: renamed"));
+        assert_eq!(new.tree_id(), old.tree_id());
+        assert_eq!(
+            new.parent_ids().collect::<Vec<_>>(),
+            old.parent_ids().collect::<Vec<_>>()
+        );
+
+        // the newest one is what `refs/stash` points to
+        let renamed_third = stash_rename(repo_path, third, "3rd")?;
+        assert_eq!(
+            get_stashes(repo_path)?,
+            vec![renamed_third, renamed, first]
+        );
+
+        stash_apply(repo_path, renamed, false)?;
+        assert_eq!(
+            std::fs::read_to_string(root.join("new.txt"))?,
+            "untracked"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stash_diff() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a.txt", "a\n", "a");
+        repo_write_file(&repo, "a.txt", "b\n")?;
+        repo_write_file(&repo, "new.txt", "untracked\n")?;
+
+        let id = stash_save(repo_path, None, true, false)?;
+
+        let diff = stash_diff(repo_path, id, None)?;
+        let lines: Vec<_> = diff
+            .hunks
+            .iter()
+            .flat_map(|h| h.lines.iter())
+            .filter(|l| l.line_type != DiffLineType::Header)
+            .map(|l| (l.line_type, l.content.trim_end().to_string()))
+            .collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                (DiffLineType::File, String::from("a.txt")),
+                (DiffLineType::Delete, String::from("a")),
+                (DiffLineType::Add, String::from("b")),
+                (DiffLineType::File, String::from("new.txt")),
+                (DiffLineType::Add, String::from("untracked")),
+            ]
+        );
+
+        assert!(stash_diff(repo_path, get_head_repo(&repo)?, None)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_and_apply_index() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "a.txt", "a", "a");
+        repo_write_file(&repo, "a.txt", "staged")?;
+        stage_add_file(repo_path, Path::new("a.txt"))?;
+
+        let id = stash_save(repo_path, None, true, false)?;
+
+        stash_apply_index(repo_path, id)?;
+        assert_eq!(get_statuses(repo_path), (0, 1));
+        assert_eq!(get_stashes(repo_path)?, vec![id]);
+
+        reset_hard(repo_path, get_head_repo(&repo)?)?;
+
+        stash_pop(repo_path, id, false)?;
+        assert_eq!(get_statuses(repo_path), (1, 0));
+        assert!(get_stashes(repo_path)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stash_branch() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let base = write_commit_file(&repo, "a.txt", "a", "a");
+        repo_write_file(&repo, "a.txt", "stashed")?;
+        let id = stash_save(repo_path, None, true, false)?;
+
+        // would conflict with the stash on master
+        write_commit_file(&repo, "a.txt", "b", "b");
+
+        repo_write_file(&repo, "a.txt", "dirty")?;
+        assert!(stash_branch(repo_path, id, "fix").is_err());
+        reset_hard(repo_path, get_head_repo(&repo)?)?;
+
+        stash_branch(repo_path, id, "fix")?;
+
+        assert_eq!(repo.head()?.name(), Some("refs/heads/fix"));
+        assert_eq!(get_head_repo(&repo)?, base);
+        assert_eq!(
+            std::fs::read_to_string(root.join("a.txt"))?,
+            "stashed"
+        );
+        assert!(get_stashes(repo_path)?.is_empty());
+
+        Ok(())
+    }
 }
@@ -1,14 +1,30 @@
-use crate::{error::Result, sync::utils};
-use git2::RepositoryState;
+use crate::{
+    error::{Error, Result},
+    sync::{
+        cherry_pick_abort, cherry_pick_continue,
+        commit::signature_allow_undefined_name, rebase_abort,
+        rebase_continue, revert_abort, revert_continue, utils,
+    },
+};
+use git2::{Oid, Repository, RepositoryState, ResetType};
 use scopetime::scope_time;
+use std::fs;
 
 ///
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum RepoState {
     ///
     Clean,
     ///
     Merge,
+    /// stopped by a conflict
+    CherryPick,
+    /// stopped by a conflict
+    Revert,
+    /// stopped by a conflict, any rebase in `.git/rebase-merge`
+    Rebase,
+    ///
+    Bisect,
     ///
     Other,
 }
@@ -18,6 +34,12 @@ impl From<RepositoryState> for RepoState {
         match state {
             RepositoryState::Clean => RepoState::Clean,
             RepositoryState::Merge => RepoState::Merge,
+            RepositoryState::CherryPick => RepoState::CherryPick,
+            RepositoryState::Revert => RepoState::Revert,
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge => RepoState::Rebase,
+            RepositoryState::Bisect => RepoState::Bisect,
             _ => RepoState::Other,
         }
     }
@@ -27,7 +49,238 @@ impl From<RepositoryState> for RepoState {
 pub fn repo_state(repo_path: &str) -> Result<RepoState> {
     scope_time!("repo_state");
 
-    let repo = utils::repo(repo_path)?;
+    Ok(utils::repo(repo_path)?.state().into())
+}
+
+/// whether the state stopped on conflicts and is resumed with
+/// `state_continue` (or dropped with `state_abort`)
+pub const fn is_stopped_state(state: RepoState) -> bool {
+    matches!(
+        state,
+        RepoState::Merge
+            | RepoState::Rebase
+            | RepoState::CherryPick
+            | RepoState::Revert
+    )
+}
+
+/// commits the staged resolution of the stopped merge, rebase,
+/// cherry-pick or revert. returns the state afterwards, a rebase
+/// can stop on the conflicts of its next step
+pub fn state_continue(repo_path: &str) -> Result<RepoState> {
+    scope_time!("state_continue");
+
+    match repo_state(repo_path)? {
+        RepoState::Merge => merge_continue(&utils::repo(repo_path)?)?,
+        RepoState::Rebase => {
+            rebase_continue(repo_path)?;
+        }
+        RepoState::CherryPick => {
+            cherry_pick_continue(repo_path)?;
+        }
+        RepoState::Revert => {
+            revert_continue(repo_path)?;
+        }
+        state => return Err(not_stopped(state)),
+    }
+
+    repo_state(repo_path)
+}
+
+/// drops the stopped merge, rebase, cherry-pick or revert and
+/// restores `HEAD` as it was before
+pub fn state_abort(repo_path: &str) -> Result<()> {
+    scope_time!("state_abort");
+
+    match repo_state(repo_path)? {
+        RepoState::Merge => merge_abort(&utils::repo(repo_path)?),
+        RepoState::Rebase => rebase_abort(repo_path),
+        RepoState::CherryPick => cherry_pick_abort(repo_path),
+        RepoState::Revert => revert_abort(repo_path),
+        state => Err(not_stopped(state)),
+    }
+}
+
+fn not_stopped(state: RepoState) -> Error {
+    Error::Generic(format!(
+        "nothing to continue or abort ({:?})",
+        state
+    ))
+}
+
+/// the merge commit with `HEAD` and all `MERGE_HEAD`s as parents
+fn merge_continue(repo: &Repository) -> Result<()> {
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+        return Err(Error::Generic(String::from(
+            "resolve all conflicts first",
+        )));
+    }
+
+    let head = repo.head()?.peel_to_commit()?;
+    let mut parents = vec![head];
+    // one line per merged commit, more for an octopus merge
+    for line in fs::read_to_string(repo.path().join("MERGE_HEAD"))?
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+    {
+        parents.push(repo.find_commit(Oid::from_str(line.trim())?)?);
+    }
+
+    let msg = repo
+        .message()
+        .ok()
+        .and_then(|msg| git2::message_prettify(msg, Some(b'#')).ok())
+        .filter(|msg| !msg.is_empty())
+        .unwrap_or_else(|| String::from("Merge"));
+
+    let signature = signature_allow_undefined_name(repo)?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        &msg,
+        &tree,
+        &parents.iter().collect::<Vec<_>>(),
+    )?;
+
+    repo.cleanup_state()?;
+
+    Ok(())
+}
+
+/// like `git merge --abort`
+fn merge_abort(repo: &Repository) -> Result<()> {
+    let head = repo.head()?.peel_to_commit()?;
+    repo.reset(head.as_object(), ResetType::Hard, None)?;
+    repo.cleanup_state()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, create_branch, merge_branch, stage_add_file,
+        tests::{repo_init, write_commit_file},
+        utils::repo_write_file,
+        MergeResult, MergeStrategy,
+    };
+    use std::path::Path;
+
+    /// `HEAD` and `feature` both change `f`
+    fn merge_conflict(repo: &Repository, repo_path: &str) {
+        write_commit_file(repo, "f", "a", "a");
+        create_branch(repo_path, "feature").unwrap();
+        write_commit_file(repo, "f", "b", "b");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit_file(repo, "f", "c", "c");
+
+        assert_eq!(
+            merge_branch(
+                repo_path,
+                "refs/heads/feature",
+                MergeStrategy::NoFastForward,
+            )
+            .unwrap(),
+            MergeResult::Conflict
+        );
+    }
+
+    #[test]
+    fn test_merge_continue() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        merge_conflict(&repo, repo_path);
+        assert!(is_stopped_state(repo_state(repo_path).unwrap()));
+
+        assert!(state_continue(repo_path).is_err());
+
+        repo_write_file(&repo, "f", "resolved").unwrap();
+        stage_add_file(repo_path, Path::new("f")).unwrap();
+
+        assert_eq!(
+            state_continue(repo_path).unwrap(),
+            RepoState::Clean
+        );
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_count(), 2);
+        assert!(head
+            .message()
+            .unwrap()
+            .starts_with("Merge branch 'feature'\n"));
+    }
+
+    #[test]
+    fn test_merge_abort() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        merge_conflict(&repo, repo_path);
+
+        state_abort(repo_path).unwrap();
+
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert_eq!(fs::read_to_string(root.join("f")).unwrap(), "c");
+        assert!(state_abort(repo_path).is_err());
+    }
+
+    #[test]
+    fn test_rebase_continue_abort() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        write_commit_file(&repo, "f", "a", "a");
+        create_branch(repo_path, "feature").unwrap();
+        write_commit_file(&repo, "f", "b", "b");
+        write_commit_file(&repo, "g", "b", "b2");
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+        write_commit_file(&repo, "f", "c", "c");
+        checkout_branch(repo_path, "refs/heads/feature").unwrap();
 
-    Ok(repo.state().into())
+        // started like any other tool would, without gitui's todo
+        let rebase_conflict = || {
+            let repo = Repository::open(root).unwrap();
+            let master = repo
+                .find_annotated_commit(
+                    repo.refname_to_id("refs/heads/master").unwrap(),
+                )
+                .unwrap();
+            let mut rebase =
+                repo.rebase(None, Some(&master), None, None).unwrap();
+            rebase.next().unwrap().unwrap();
+            assert!(repo.index().unwrap().has_conflicts());
+        };
+
+        rebase_conflict();
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+
+        state_abort(repo_path).unwrap();
+        assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+        assert_eq!(fs::read_to_string(root.join("f")).unwrap(), "b");
+
+        rebase_conflict();
+
+        repo_write_file(&repo, "f", "resolved").unwrap();
+        stage_add_file(repo_path, Path::new("f")).unwrap();
+
+        assert_eq!(
+            state_continue(repo_path).unwrap(),
+            RepoState::Clean
+        );
+
+        let head = repo.head().unwrap();
+        assert_eq!(head.shorthand(), Some("feature"));
+        let head = head.peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some("b2"));
+        assert_eq!(head.parent(0).unwrap().message(), Some("b"));
+    }
 }
@@ -24,7 +24,10 @@ pub enum StatusItemType {
 
 impl From<Status> for StatusItemType {
     fn from(s: Status) -> Self {
-        if s.is_index_new() || s.is_wt_new() {
+        // a conflicted entry can carry other flags too
+        if s.is_conflicted() {
+            Self::Conflicted
+        } else if s.is_index_new() || s.is_wt_new() {
             Self::New
         } else if s.is_index_deleted() || s.is_wt_deleted() {
             Self::Deleted
@@ -32,8 +35,6 @@ impl From<Status> for StatusItemType {
             Self::Renamed
         } else if s.is_index_typechange() || s.is_wt_typechange() {
             Self::Typechange
-        } else if s.is_conflicted() {
-            Self::Conflicted
         } else {
             Self::Modified
         }
@@ -46,6 +47,8 @@ impl From<Delta> for StatusItemType {
             Delta::Added => StatusItemType::New,
             Delta::Deleted => StatusItemType::Deleted,
             Delta::Renamed => StatusItemType::Renamed,
+            // the copied file is new, the copy source stays
+            Delta::Copied => StatusItemType::New,
             Delta::Typechange => StatusItemType::Typechange,
             _ => StatusItemType::Modified,
         }
@@ -57,6 +60,8 @@ impl From<Delta> for StatusItemType {
 pub struct StatusItem {
     ///
     pub path: String,
+    /// path a renamed (or copied) file had before
+    pub old_path: Option<String>,
     ///
     pub status: StatusItemType,
 }
@@ -132,8 +137,22 @@ pub fn get_status(
             })?,
         };
 
+        let old_path = e
+            .head_to_index()
+            .or_else(|| e.index_to_workdir())
+            .filter(|diff| {
+                matches!(
+                    diff.status(),
+                    Delta::Renamed | Delta::Copied
+                )
+            })
+            .and_then(|diff| diff.old_file().path())
+            .and_then(|path| path.to_str())
+            .map(String::from);
+
         res.push(StatusItem {
             path,
+            old_path,
             status: StatusItemType::from(status),
         });
     }
@@ -0,0 +1,246 @@
+//! listing and updating the submodules of a repo
+
+use super::{
+    utils::{repo, work_dir},
+    CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{Repository, Submodule, SubmoduleIgnore};
+use scopetime::scope_time;
+use std::path::Path;
+
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SubmoduleStatus {
+    /// cloned into the workdir
+    pub initialized: bool,
+    /// the checked out commit is not the one recorded in `HEAD`
+    pub out_of_date: bool,
+    /// changes or untracked files inside of it
+    pub dirty: bool,
+}
+
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmoduleInfo {
+    /// path relative to the workdir
+    pub path: String,
+    ///
+    pub url: Option<String>,
+    /// commit recorded in `HEAD`
+    pub head_id: Option<CommitId>,
+    /// commit checked out in the workdir
+    pub workdir_id: Option<CommitId>,
+    ///
+    pub status: SubmoduleStatus,
+}
+
+/// all submodules, sorted by path
+pub fn get_submodules(repo_path: &str) -> Result<Vec<SubmoduleInfo>> {
+    scope_time!("get_submodules");
+
+    let repo = repo(repo_path)?;
+
+    let mut res = repo
+        .submodules()?
+        .iter()
+        .map(|sm| submodule_info(&repo, sm))
+        .collect::<Result<Vec<_>>>()?;
+
+    res.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(res)
+}
+
+///
+pub fn submodule_status(
+    repo_path: &str,
+    path: &str,
+) -> Result<SubmoduleStatus> {
+    scope_time!("submodule_status");
+
+    let repo = repo(repo_path)?;
+    let sm = repo.find_submodule(path)?;
+
+    get_status(&repo, &sm)
+}
+
+/// registers the url in the config, like `git submodule init`
+pub fn init_submodule(repo_path: &str, path: &str) -> Result<()> {
+    scope_time!("init_submodule");
+
+    let repo = repo(repo_path)?;
+    let mut sm = repo.find_submodule(path)?;
+
+    sm.init(false)?;
+
+    Ok(())
+}
+
+/// clones (if needed) and checks out the commit recorded in `HEAD`,
+/// like `git submodule update --init`
+pub fn update_submodule(repo_path: &str, path: &str) -> Result<()> {
+    scope_time!("update_submodule");
+
+    let repo = repo(repo_path)?;
+    let mut sm = repo.find_submodule(path)?;
+
+    sm.update(true, None)?;
+
+    Ok(())
+}
+
+/// workdir of an initialized submodule to open it as a repo
+pub fn submodule_workdir(
+    repo_path: &str,
+    path: &str,
+) -> Result<String> {
+    scope_time!("submodule_workdir");
+
+    let repo = repo(repo_path)?;
+    let sm = repo.find_submodule(path)?;
+
+    if !get_status(&repo, &sm)?.initialized {
+        return Err(Error::Generic(format!(
+            "submodule not initialized: {}",
+            path
+        )));
+    }
+
+    let sub_repo = sm.open()?;
+    let workdir = work_dir(&sub_repo)?;
+
+    Ok(workdir.to_string_lossy().to_string())
+}
+
+fn submodule_info(
+    repo: &Repository,
+    sm: &Submodule<'_>,
+) -> Result<SubmoduleInfo> {
+    Ok(SubmoduleInfo {
+        path: path_string(sm.path()),
+        url: sm.url().map(String::from),
+        head_id: sm.head_id().map(CommitId::new),
+        workdir_id: sm.workdir_id().map(CommitId::new),
+        status: get_status(repo, sm)?,
+    })
+}
+
+fn get_status(
+    repo: &Repository,
+    sm: &Submodule<'_>,
+) -> Result<SubmoduleStatus> {
+    let name = sm.name().ok_or_else(|| {
+        Error::Generic(String::from("submodule name not utf8"))
+    })?;
+    let status =
+        repo.submodule_status(name, SubmoduleIgnore::None)?;
+
+    Ok(SubmoduleStatus {
+        initialized: status.is_in_wd()
+            && !status.is_wd_uninitialized(),
+        out_of_date: status.is_wd_modified(),
+        dirty: status.intersects(
+            git2::SubmoduleStatus::WD_INDEX_MODIFIED
+                | git2::SubmoduleStatus::WD_WD_MODIFIED
+                | git2::SubmoduleStatus::WD_UNTRACKED,
+        ),
+    })
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        commit, stage_add_file, tests::repo_init,
+        utils::repo_write_file,
+    };
+    use tempfile::TempDir;
+
+    /// repo with the submodule `sub` cloned from a second repo,
+    /// which is returned as well
+    fn repo_with_submodule(
+    ) -> (TempDir, Repository, TempDir, Repository) {
+        let (td_sub, repo_sub) = repo_init().unwrap();
+        repo_write_file(&repo_sub, "f", "1").unwrap();
+        let sub_path = repo_sub.workdir().unwrap().to_str().unwrap();
+        stage_add_file(sub_path, Path::new("f")).unwrap();
+        commit(sub_path, "sub commit").unwrap();
+
+        let (td, repo) = repo_init().unwrap();
+        {
+            let mut sm = repo
+                .submodule(sub_path, Path::new("sub"), true)
+                .unwrap();
+            sm.clone(None).unwrap();
+            sm.add_finalize().unwrap();
+        }
+        let repo_path = repo.workdir().unwrap().to_str().unwrap();
+        commit(repo_path, "add submodule").unwrap();
+
+        (td, repo, td_sub, repo_sub)
+    }
+
+    #[test]
+    fn test_list() {
+        let (_td, repo, _td_sub, repo_sub) = repo_with_submodule();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let res = get_submodules(repo_path).unwrap();
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].path, "sub");
+        assert_eq!(
+            res[0].head_id,
+            Some(CommitId::new(
+                repo_sub.head().unwrap().target().unwrap()
+            ))
+        );
+        assert_eq!(res[0].head_id, res[0].workdir_id);
+        assert_eq!(
+            res[0].status,
+            SubmoduleStatus {
+                initialized: true,
+                out_of_date: false,
+                dirty: false,
+            }
+        );
+
+        repo_write_file(&repo, "sub/f", "2").unwrap();
+
+        assert!(submodule_status(repo_path, "sub").unwrap().dirty);
+    }
+
+    #[test]
+    fn test_update() {
+        let (_td, repo, _td_sub, _repo_sub) = repo_with_submodule();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let workdir = submodule_workdir(repo_path, "sub").unwrap();
+        {
+            let sub = Repository::open(&workdir).unwrap();
+            let mut config = sub.config().unwrap();
+            config.set_str("user.name", "name").unwrap();
+            config.set_str("user.email", "email").unwrap();
+        }
+        repo_write_file(&repo, "sub/g", "g").unwrap();
+        stage_add_file(&workdir, Path::new("g")).unwrap();
+        commit(&workdir, "moved on").unwrap();
+
+        let status = submodule_status(repo_path, "sub").unwrap();
+        assert!(status.out_of_date);
+
+        update_submodule(repo_path, "sub").unwrap();
+
+        let status = submodule_status(repo_path, "sub").unwrap();
+        assert!(!status.out_of_date);
+        assert!(root.join("sub/f").exists());
+        assert!(!root.join("sub/g").exists());
+    }
+}
@@ -1,5 +1,9 @@
-use super::{utils::repo, CommitId};
-use crate::error::Result;
+use super::{
+    commit::signature_allow_undefined_name, signature::sign_buffer,
+    utils::repo, CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{ObjectType, Reference};
 use scopetime::scope_time;
 use std::collections::BTreeMap;
 
@@ -8,6 +12,20 @@ pub type CommitTags = Vec<String>;
 /// hashmap of tag target commit hash to tag names
 pub type Tags = BTreeMap<CommitId, CommitTags>;
 
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagWithMetadata {
+    ///
+    pub name: String,
+    /// the commit the tag points to
+    pub target: CommitId,
+    /// `false` for lightweight tags
+    pub annotated: bool,
+    /// first line of the tag message, the commit summary for
+    /// lightweight tags
+    pub message: String,
+}
+
 /// returns `Tags` type filled with all tags found in repo
 pub fn get_tags(repo_path: &str) -> Result<Tags> {
     scope_time!("get_tags");
@@ -45,11 +63,124 @@ pub fn get_tags(repo_path: &str) -> Result<Tags> {
     Ok(res)
 }
 
+/// all tags pointing to a commit, sorted by name
+pub fn get_tags_with_metadata(
+    repo_path: &str,
+) -> Result<Vec<TagWithMetadata>> {
+    scope_time!("get_tags_with_metadata");
+
+    let repo = repo(repo_path)?;
+    let names = repo.tag_names(None)?;
+
+    let mut res = Vec::with_capacity(names.len());
+
+    for name in names.iter().flatten() {
+        let reference =
+            repo.find_reference(&format!("refs/tags/{}", name))?;
+
+        let commit = if let Ok(commit) = reference.peel_to_commit() {
+            commit
+        } else {
+            continue;
+        };
+
+        let (annotated, message) =
+            if let Ok(tag) = reference.peel_to_tag() {
+                (
+                    true,
+                    tag.message()
+                        .and_then(|msg| msg.lines().next())
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+            } else {
+                (false, commit.summary().unwrap_or_default().into())
+            };
+
+        res.push(TagWithMetadata {
+            name: name.to_string(),
+            target: commit.id().into(),
+            annotated,
+            message,
+        });
+    }
+
+    res.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(res)
+}
+
+/// like `git tag -a` (`-s` with `sign`), returns the id of the tag
+/// object
+pub fn tag_annotated(
+    repo_path: &str,
+    commit_id: &CommitId,
+    tag: &str,
+    message: &str,
+    sign: bool,
+) -> Result<CommitId> {
+    scope_time!("tag_annotated");
+
+    let repo = repo(repo_path)?;
+
+    let tagger = signature_allow_undefined_name(&repo)?;
+    let target = repo
+        .find_object(commit_id.get_oid(), Some(ObjectType::Commit))?;
+    let message = git2::message_prettify(message, None)?;
+
+    if !sign {
+        return Ok(repo
+            .tag(tag, &target, &tagger, &message, false)?
+            .into());
+    }
+
+    let ref_name = format!("refs/tags/{}", tag);
+    if !Reference::is_valid_name(&ref_name) {
+        return Err(Error::Generic(format!(
+            "invalid tag name: {}",
+            tag
+        )));
+    }
+
+    let when = tagger.when();
+    let offset = when.offset_minutes().abs();
+    let mut buffer = format!(
+        "object {}\ntype commit\ntag {}\ntagger {} <{}> {} {}{:02}{:02}\n\n{}",
+        commit_id.get_oid(),
+        tag,
+        tagger.name().unwrap_or_default(),
+        tagger.email().unwrap_or_default(),
+        when.seconds(),
+        when.sign(),
+        offset / 60,
+        offset % 60,
+        message,
+    );
+    // git appends the signature of tags to the message
+    buffer.push_str(&sign_buffer(&repo, &tagger, buffer.as_bytes())?);
+
+    let id = repo.odb()?.write(ObjectType::Tag, buffer.as_bytes())?;
+    repo.reference(&ref_name, id, false, "")?;
+
+    Ok(id.into())
+}
+
+/// deletes the local tag only
+pub fn delete_tag(repo_path: &str, tag: &str) -> Result<()> {
+    scope_time!("delete_tag");
+
+    let repo = repo(repo_path)?;
+    repo.tag_delete(tag)?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sync::tests::repo_init;
-    use git2::ObjectType;
+    #[cfg(unix)]
+    use crate::sync::tests::fake_gpg;
+    use crate::sync::tests::{repo_init, write_commit_file};
 
     #[test]
     fn test_smoke() {
@@ -83,4 +214,99 @@ mod tests {
             vec!["a", "b"]
         );
     }
+
+    #[test]
+    fn test_tags_with_metadata() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let id = write_commit_file(&repo, "a", "a", "commit a");
+        let target = repo
+            .find_object(id.get_oid(), Some(ObjectType::Commit))
+            .unwrap();
+        repo.tag_lightweight("light", &target, false).unwrap();
+        tag_annotated(
+            repo_path,
+            &id,
+            "annotated",
+            "release\n\nbody",
+            false,
+        )
+        .unwrap();
+
+        let tags = get_tags_with_metadata(repo_path).unwrap();
+
+        assert_eq!(
+            tags,
+            vec![
+                TagWithMetadata {
+                    name: String::from("annotated"),
+                    target: id,
+                    annotated: true,
+                    message: String::from("release"),
+                },
+                TagWithMetadata {
+                    name: String::from("light"),
+                    target: id,
+                    annotated: false,
+                    message: String::from("commit a"),
+                },
+            ]
+        );
+
+        delete_tag(repo_path, "annotated").unwrap();
+
+        let tags = get_tags_with_metadata(repo_path).unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name, "light");
+        assert!(delete_tag(repo_path, "annotated").is_err());
+    }
+
+    #[test]
+    fn test_annotated_message() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let id = write_commit_file(&repo, "a", "a", "commit a");
+        let tag_id =
+            tag_annotated(repo_path, &id, "v1", "v1\n\nnotes", false)
+                .unwrap();
+
+        let tag = repo.find_tag(tag_id.into()).unwrap();
+        assert_eq!(tag.message(), Some("v1\n\nnotes\n"));
+        assert_eq!(CommitId::from(tag.target_id()), id);
+        assert!(tag_annotated(repo_path, &id, "v1", "again", false)
+            .is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_annotated_signed() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        fake_gpg(&repo, false);
+
+        let id = write_commit_file(&repo, "a", "a", "commit a");
+        let tag_id =
+            tag_annotated(repo_path, &id, "v1", "v1", true).unwrap();
+
+        let tag = repo.find_tag(tag_id.into()).unwrap();
+        assert_eq!(tag.name(), Some("v1"));
+        assert_eq!(tag.message(), Some("v1\nfake sig\n"));
+        assert_eq!(
+            get_tags_with_metadata(repo_path).unwrap()[0].target,
+            id
+        );
+
+        fake_gpg(&repo, true);
+
+        assert!(
+            tag_annotated(repo_path, &id, "v2", "v2", true).is_err()
+        );
+        assert!(repo.find_reference("refs/tags/v2").is_err());
+    }
 }
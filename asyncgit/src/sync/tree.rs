@@ -0,0 +1,139 @@
+//! sync git api for the files of a commit's tree
+
+use super::{utils::repo, CommitId};
+use crate::error::{Error, Result};
+use git2::{
+    build::CheckoutBuilder, ObjectType, TreeWalkMode, TreeWalkResult,
+};
+use scopetime::scope_time;
+use std::path::Path;
+
+/// a file in the tree of a commit
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeFile {
+    /// relative to the repo root
+    pub path: String,
+    /// full hex id of the blob
+    pub id: String,
+}
+
+/// all files (no folders or submodules) in the tree of `commit`,
+/// in tree order
+pub fn tree_files(
+    repo_path: &str,
+    commit: CommitId,
+) -> Result<Vec<TreeFile>> {
+    scope_time!("tree_files");
+
+    let repo = repo(repo_path)?;
+    let tree = repo.find_commit(commit.into())?.tree()?;
+
+    let mut files = Vec::new();
+    tree.walk(TreeWalkMode::PreOrder, |parent, entry| {
+        if entry.kind() == Some(ObjectType::Blob) {
+            if let Some(name) = entry.name() {
+                files.push(TreeFile {
+                    path: format!("{}{}", parent, name),
+                    id: entry.id().to_string(),
+                });
+            }
+        }
+        TreeWalkResult::Ok
+    })?;
+
+    Ok(files)
+}
+
+/// restores `path` in the index and the workdir to its version in
+/// `commit` (like `git checkout <commit> -- <path>`), changes of it
+/// are lost
+pub fn checkout_file(
+    repo_path: &str,
+    commit: CommitId,
+    path: &str,
+) -> Result<()> {
+    scope_time!("checkout_file");
+
+    let repo = repo(repo_path)?;
+    let commit = repo.find_commit(commit.into())?;
+
+    if commit.tree()?.get_path(Path::new(path)).is_err() {
+        return Err(Error::Generic(format!(
+            "'{}' is not part of the commit",
+            path
+        )));
+    }
+
+    let mut checkout_opts = CheckoutBuilder::new();
+    checkout_opts.update_index(true).force().path(path);
+
+    repo.checkout_tree(commit.as_object(), Some(&mut checkout_opts))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        show_object,
+        status::{get_status, StatusType},
+        tests::{repo_init, write_commit_file},
+        utils::repo_write_file,
+    };
+    use std::fs;
+
+    #[test]
+    fn test_tree_files() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        fs::create_dir(root.join("foo")).unwrap();
+        write_commit_file(&repo, "b.txt", "b", "c1");
+        let first =
+            write_commit_file(&repo, "foo/a.txt", "old", "c2");
+        write_commit_file(&repo, "foo/a.txt", "newer", "c3");
+
+        let files = tree_files(repo_path, first).unwrap();
+        let paths: Vec<&str> =
+            files.iter().map(|f| f.path.as_str()).collect();
+
+        assert_eq!(paths, vec!["b.txt", "foo/a.txt"]);
+
+        let blob = show_object(repo_path, &files[1].id).unwrap();
+        assert_eq!(blob.lines[0].text, "old");
+    }
+
+    #[test]
+    fn test_checkout_file() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = write_commit_file(&repo, "a.txt", "old", "c1");
+        write_commit_file(&repo, "a.txt", "newer", "c2");
+        repo_write_file(&repo, "a.txt", "changed").unwrap();
+
+        checkout_file(repo_path, first, "a.txt").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).unwrap(),
+            "old"
+        );
+        assert_eq!(
+            get_status(repo_path, StatusType::Stage, false)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            get_status(repo_path, StatusType::WorkingDir, false)
+                .unwrap()
+                .len(),
+            0
+        );
+
+        assert!(checkout_file(repo_path, first, "b.txt").is_err());
+    }
+}
@@ -2,9 +2,22 @@
 
 use super::CommitId;
 use crate::error::{Error, Result};
-use git2::{IndexAddOption, Repository, RepositoryOpenFlags};
+use git2::{
+    ErrorClass, ErrorCode, Index, IndexAddOption, Repository,
+    RepositoryOpenFlags, StatusOptions,
+};
 use scopetime::scope_time;
-use std::{fs::File, io::Write, path::Path};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime},
+};
+
+/// a lock not touched for this long is not held by an operation
+/// of gitui itself (those only take milliseconds)
+const STALE_INDEX_LOCK_AGE: Duration = Duration::from_secs(10);
 
 ///
 #[derive(PartialEq, Debug, Clone)]
@@ -81,6 +94,65 @@ pub fn get_head_tuple(repo_path: &str) -> Result<Head> {
     Ok(Head { name, id })
 }
 
+/// `true` if HEAD points directly to a commit instead of a branch
+pub fn is_head_detached(repo_path: &str) -> Result<bool> {
+    let repo = repo(repo_path)?;
+
+    Ok(repo.head_detached()?)
+}
+
+/// the commit HEAD points to if it is detached
+pub fn get_detached_head(
+    repo_path: &str,
+) -> Result<Option<CommitId>> {
+    let repo = repo(repo_path)?;
+
+    if repo.head_detached()? {
+        Ok(Some(get_head_repo(&repo)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// `true` if the repo was cloned with a filter (`git clone --filter`),
+/// objects missing locally are promised by the remote then
+pub fn is_partial_clone(repo_path: &str) -> Result<bool> {
+    let repo = repo(repo_path)?;
+    let config = repo.config()?;
+
+    if config.get_string("extensions.partialclone").is_ok() {
+        return Ok(true);
+    }
+
+    let entries = config.entries(Some(r"remote\..*\.promisor"))?;
+    for entry in &entries {
+        if entry?.value() == Some("true") {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// libgit2 cannot fetch the objects a partial clone left out, a
+/// missing object is explained as such instead of "not found"
+pub(crate) fn map_missing_object(
+    repo_path: &str,
+    err: Error,
+) -> Error {
+    match err {
+        Error::Git(e)
+            if e.code() == ErrorCode::NotFound
+                && e.class() == ErrorClass::Odb
+                && is_partial_clone(repo_path)
+                    .unwrap_or_default() =>
+        {
+            Error::PartialClone(e.message().to_string())
+        }
+        err => err,
+    }
+}
+
 ///
 pub fn get_head_refname(repo: &Repository) -> Result<String> {
     let head = repo.head()?;
@@ -168,6 +240,157 @@ pub fn get_config_string(
     }
 }
 
+/// a broken index that blocks (almost) every operation
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum IndexProblem {
+    /// `index.lock` exists and is older than a few seconds,
+    /// probably left behind by a crashed git process
+    Locked,
+    /// the index can not be read
+    Corrupt,
+}
+
+fn index_lock_path(repo: &Repository) -> PathBuf {
+    repo.path().join("index.lock")
+}
+
+fn lock_age(lock: &Path) -> Option<Duration> {
+    let modified = fs::metadata(lock).ok()?.modified().ok()?;
+    // a timestamp in the future counts as fresh
+    Some(
+        SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default(),
+    )
+}
+
+/// best-effort check for a running `git` process,
+/// `None` if it could not be determined
+fn git_process_running() -> Option<bool> {
+    #[cfg(windows)]
+    let output = Command::new("tasklist")
+        .args(&["/FI", "IMAGENAME eq git.exe", "/NH"])
+        .output()
+        .ok()?;
+    #[cfg(not(windows))]
+    let output = Command::new("ps")
+        .args(&["-A", "-o", "comm="])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let running =
+        String::from_utf8_lossy(&output.stdout).lines().any(|line| {
+            let name = line.trim();
+            let name =
+                name.rsplit(&['/', '\\'][..]).next().unwrap_or(name);
+            name == "git" || name.starts_with("git.exe")
+        });
+
+    Some(running)
+}
+
+/// checks for a stale `index.lock` or an unreadable index.
+/// a recent lock is assumed to be held by a running operation
+pub fn get_index_problem(
+    repo_path: &str,
+) -> Result<Option<IndexProblem>> {
+    scope_time!("get_index_problem");
+
+    let repo = repo(repo_path)?;
+
+    if let Some(age) = lock_age(&index_lock_path(&repo)) {
+        return Ok((age >= STALE_INDEX_LOCK_AGE)
+            .then(|| IndexProblem::Locked));
+    }
+
+    if repo.index().and_then(|mut index| index.read(true)).is_err() {
+        return Ok(Some(IndexProblem::Corrupt));
+    }
+
+    Ok(None)
+}
+
+/// removes `index.lock` if it is stale: untouched for a while and no
+/// `git` process is running. returns `false` if the lock was kept,
+/// which it also is if the processes cannot be listed
+pub fn remove_stale_index_lock(repo_path: &str) -> Result<bool> {
+    scope_time!("remove_stale_index_lock");
+
+    remove_index_lock_older_than(
+        repo_path,
+        STALE_INDEX_LOCK_AGE,
+        git_process_running,
+    )
+}
+
+/// `git_running` is `git_process_running` unless testing
+fn remove_index_lock_older_than(
+    repo_path: &str,
+    min_age: Duration,
+    git_running: impl FnOnce() -> Option<bool>,
+) -> Result<bool> {
+    let repo = repo(repo_path)?;
+    let lock = index_lock_path(&repo);
+
+    match lock_age(&lock) {
+        Some(age) if age >= min_age => (),
+        _ => return Ok(false),
+    }
+
+    match git_running() {
+        Some(false) => (),
+        Some(true) => {
+            log::info!("index.lock kept: git process running");
+            return Ok(false);
+        }
+        None => {
+            log::info!("index.lock kept: git processes unknown");
+            return Ok(false);
+        }
+    }
+
+    fs::remove_file(&lock)?;
+
+    Ok(true)
+}
+
+/// replaces an unreadable index with the tree of HEAD (like removing
+/// it and `git reset`), the working dir is not touched but anything
+/// staged is lost
+pub fn rebuild_index_from_head(repo_path: &str) -> Result<()> {
+    scope_time!("rebuild_index_from_head");
+
+    let repo = repo(repo_path)?;
+
+    let index_path = repo.path().join("index");
+    if index_path.exists() {
+        fs::remove_file(&index_path)?;
+    }
+
+    let mut index = Index::open(&index_path)?;
+    if let Ok(id) = get_head_repo(&repo) {
+        let tree = repo.find_commit(id.into())?.tree()?;
+        index.read_tree(&tree)?;
+    }
+    index.write()?;
+
+    Ok(())
+}
+
+/// staged or unstaged changes to tracked files
+pub(crate) fn has_uncommitted_changes(
+    repo: &Repository,
+) -> Result<bool> {
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(false).include_ignored(false);
+
+    Ok(!repo.statuses(Some(&mut opts))?.is_empty())
+}
+
 pub(crate) fn bytes2string(bytes: &[u8]) -> Result<String> {
     Ok(String::from_utf8(bytes.to_vec())?)
 }
@@ -369,4 +592,142 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_head_detached() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert!(!is_head_detached(repo_path).unwrap());
+        assert_eq!(get_detached_head(repo_path).unwrap(), None);
+
+        let head = get_head_repo(&repo).unwrap();
+        repo.set_head_detached(head.into()).unwrap();
+
+        assert!(is_head_detached(repo_path).unwrap());
+        assert_eq!(get_detached_head(repo_path).unwrap(), Some(head));
+    }
+
+    #[test]
+    fn test_partial_clone() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert!(!is_partial_clone(repo_path).unwrap());
+
+        let mut config = repo.config().unwrap();
+        config.set_bool("remote.origin.promisor", true).unwrap();
+        config
+            .set_str("remote.origin.partialclonefilter", "blob:none")
+            .unwrap();
+
+        assert!(is_partial_clone(repo_path).unwrap());
+    }
+
+    #[test]
+    fn test_map_missing_object() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let missing = || {
+            Error::Git(git2::Error::new(
+                ErrorCode::NotFound,
+                ErrorClass::Odb,
+                "object not found",
+            ))
+        };
+
+        assert!(matches!(
+            map_missing_object(repo_path, missing()),
+            Error::Git(_)
+        ));
+
+        repo.config()
+            .unwrap()
+            .set_bool("remote.origin.promisor", true)
+            .unwrap();
+
+        assert!(matches!(
+            map_missing_object(repo_path, missing()),
+            Error::PartialClone(_)
+        ));
+        assert!(matches!(
+            map_missing_object(repo_path, Error::NoHead),
+            Error::NoHead
+        ));
+    }
+
+    #[test]
+    fn test_fresh_index_lock_kept() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert_eq!(get_index_problem(repo_path).unwrap(), None);
+        assert!(!remove_stale_index_lock(repo_path).unwrap());
+
+        File::create(repo.path().join("index.lock")).unwrap();
+
+        // too recent to be stale
+        assert_eq!(get_index_problem(repo_path).unwrap(), None);
+        assert!(!remove_stale_index_lock(repo_path).unwrap());
+        assert!(repo.path().join("index.lock").exists());
+    }
+
+    #[test]
+    fn test_remove_index_lock() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo_write_file(&repo, "foo", "a").unwrap();
+        File::create(repo.path().join("index.lock")).unwrap();
+        assert!(stage_add_file(repo_path, Path::new("foo")).is_err());
+
+        let remove = |git_running: Option<bool>| {
+            remove_index_lock_older_than(
+                repo_path,
+                Duration::from_secs(0),
+                || git_running,
+            )
+            .unwrap()
+        };
+
+        assert!(!remove(Some(true)));
+        assert!(!remove(None));
+        assert!(repo.path().join("index.lock").exists());
+
+        assert!(remove(Some(false)));
+        assert!(!repo.path().join("index.lock").exists());
+        stage_add_file(repo_path, Path::new("foo")).unwrap();
+    }
+
+    #[test]
+    fn test_rebuild_corrupt_index() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo_write_file(&repo, "foo", "a").unwrap();
+        stage_add_file(repo_path, Path::new("foo")).unwrap();
+        commit(repo_path, "add foo").unwrap();
+
+        File::create(repo.path().join("index"))
+            .unwrap()
+            .write_all(b"DIRC garbage")
+            .unwrap();
+
+        assert_eq!(
+            get_index_problem(repo_path).unwrap(),
+            Some(IndexProblem::Corrupt)
+        );
+
+        rebuild_index_from_head(repo_path).unwrap();
+
+        assert_eq!(get_index_problem(repo_path).unwrap(), None);
+        assert_eq!(get_statuses(repo_path), (0, 0));
+    }
 }
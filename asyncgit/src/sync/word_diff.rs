@@ -0,0 +1,253 @@
+//! changed words within the modified lines of a hunk
+
+use super::diff::{DiffLine, DiffLineType};
+use std::ops::Range;
+
+/// longer lines are left alone, comparing them is quadratic
+const MAX_TOKENS: usize = 512;
+
+/// changed byte ranges of the old and the new line
+type ChangedRanges = (Vec<Range<usize>>, Vec<Range<usize>>);
+
+/// pairs the deleted lines of a hunk with the lines added right
+/// after them (in order) and marks the words differing between
+/// each pair
+pub(crate) fn mark_changes(lines: &mut [DiffLine]) {
+    let mut start = 0;
+    while start < lines.len() {
+        let (deleted, end) =
+            block(lines, start, DiffLineType::Delete);
+        let (added, end) = block(lines, end, DiffLineType::Add);
+
+        for (&old, &new) in deleted.iter().zip(&added) {
+            if let Some((old_changes, new_changes)) = changed_ranges(
+                &lines[old].content,
+                &lines[new].content,
+            ) {
+                lines[old].changes = old_changes;
+                lines[new].changes = new_changes;
+            }
+        }
+
+        start = if end > start { end } else { start + 1 };
+    }
+}
+
+/// indices of the lines of type `typ` starting at `start` (a missing
+/// newline marker in between does not end them) and the index after
+fn block(
+    lines: &[DiffLine],
+    start: usize,
+    typ: DiffLineType,
+) -> (Vec<usize>, usize) {
+    let mut indices = Vec::new();
+    let mut end = start;
+
+    while let Some(line) = lines.get(end) {
+        if line.line_type == typ {
+            indices.push(end);
+        } else if indices.is_empty()
+            || line.line_type != DiffLineType::NoNewline
+        {
+            break;
+        }
+        end += 1;
+    }
+
+    (indices, end)
+}
+
+/// byte ranges of the words of `old` and `new` not found in the
+/// other one, `None` if the lines have nothing but whitespace in
+/// common (or are too long to compare)
+fn changed_ranges(old: &str, new: &str) -> Option<ChangedRanges> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+
+    if old_tokens.len() > MAX_TOKENS || new_tokens.len() > MAX_TOKENS
+    {
+        return None;
+    }
+
+    let old_words: Vec<&str> =
+        old_tokens.iter().map(|r| &old[r.clone()]).collect();
+    let new_words: Vec<&str> =
+        new_tokens.iter().map(|r| &new[r.clone()]).collect();
+
+    let (old_common, new_common) =
+        common_tokens(&old_words, &new_words);
+
+    let shares_words = old_common
+        .iter()
+        .zip(&old_words)
+        .any(|(common, word)| *common && !word.trim().is_empty());
+    if !shares_words {
+        return None;
+    }
+
+    Some((
+        merge_changed(&old_tokens, &old_common),
+        merge_changed(&new_tokens, &new_common),
+    ))
+}
+
+/// runs of word characters, runs of whitespace and single other
+/// characters, without the line ending
+fn tokenize(line: &str) -> Vec<Range<usize>> {
+    let line = line.trim_end_matches(|c| c == '\n' || c == '\r');
+
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            1
+        } else if c.is_whitespace() {
+            2
+        } else {
+            0
+        }
+    };
+
+    let mut tokens: Vec<Range<usize>> = Vec::new();
+    let mut last_class = 0;
+    for (i, c) in line.char_indices() {
+        let c_class = class(c);
+        match tokens.last_mut() {
+            Some(last) if c_class != 0 && c_class == last_class => {
+                last.end = i + c.len_utf8();
+            }
+            _ => tokens.push(i..i + c.len_utf8()),
+        }
+        last_class = c_class;
+    }
+
+    tokens
+}
+
+/// which tokens of each side are part of their longest common
+/// subsequence
+fn common_tokens(
+    old: &[&str],
+    new: &[&str],
+) -> (Vec<bool>, Vec<bool>) {
+    let width = new.len() + 1;
+    let mut lengths = vec![0_u16; (old.len() + 1) * width];
+
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j]
+                    .max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut old_common = vec![false; old.len()];
+    let mut new_common = vec![false; new.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            old_common[i] = true;
+            new_common[j] = true;
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j]
+            >= lengths[i * width + j + 1]
+        {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    (old_common, new_common)
+}
+
+/// byte ranges of the tokens not in common, neighbours joined
+fn merge_changed(
+    tokens: &[Range<usize>],
+    common: &[bool],
+) -> Vec<Range<usize>> {
+    let mut res: Vec<Range<usize>> = Vec::new();
+
+    for (token, _) in
+        tokens.iter().zip(common).filter(|(_, common)| !**common)
+    {
+        match res.last_mut() {
+            Some(last) if last.end == token.start => {
+                last.end = token.end;
+            }
+            _ => res.push(token.clone()),
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(content: &str, line_type: DiffLineType) -> DiffLine {
+        DiffLine {
+            content: content.to_string(),
+            line_type,
+            ..DiffLine::default()
+        }
+    }
+
+    fn changed(line: &DiffLine) -> Vec<&str> {
+        line.changes
+            .iter()
+            .map(|r| &line.content[r.clone()])
+            .collect()
+    }
+
+    #[test]
+    fn test_changed_words() {
+        let mut lines = vec![
+            line("@@ -1,3 +1,3 @@\n", DiffLineType::Header),
+            line("let foo = bar(1);\n", DiffLineType::Delete),
+            line("unrelated\n", DiffLineType::Delete),
+            line("let foo = baz(1, 2);\n", DiffLineType::Add),
+            line("something else\n", DiffLineType::Add),
+            line("context\n", DiffLineType::None),
+        ];
+
+        mark_changes(&mut lines);
+
+        assert_eq!(changed(&lines[1]), vec!["bar"]);
+        assert_eq!(changed(&lines[3]), vec!["baz", ", 2"]);
+        assert!(lines[2].changes.is_empty());
+        assert!(lines[4].changes.is_empty());
+        assert!(lines[0].changes.is_empty());
+        assert!(lines[5].changes.is_empty());
+    }
+
+    #[test]
+    fn test_across_missing_newline() {
+        let mut lines = vec![
+            line("a b", DiffLineType::Delete),
+            line(
+                "\n\\ No newline at end of file\n",
+                DiffLineType::NoNewline,
+            ),
+            line("a c\n", DiffLineType::Add),
+        ];
+
+        mark_changes(&mut lines);
+
+        assert_eq!(changed(&lines[0]), vec!["b"]);
+        assert_eq!(changed(&lines[2]), vec!["c"]);
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let line = "fn  foo_1(x)\r\n";
+
+        let tokens: Vec<&str> =
+            tokenize(line).into_iter().map(|r| &line[r]).collect();
+
+        assert_eq!(tokens, vec!["fn", "  ", "foo_1", "(", "x", ")"]);
+    }
+}
@@ -0,0 +1,321 @@
+//! linked worktrees of a repo (`git worktree`)
+
+use super::utils::{repo, work_dir};
+use crate::error::{Error, Result};
+use git2::{
+    Repository, StatusOptions, Worktree, WorktreeAddOptions,
+    WorktreeLockStatus, WorktreePruneOptions,
+};
+use scopetime::scope_time;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeInfo {
+    /// `None` for the main worktree
+    pub name: Option<String>,
+    ///
+    pub path: String,
+    /// branch checked out, `None` if detached or missing
+    pub branch: Option<String>,
+    /// the one `repo_path` belongs to
+    pub is_current: bool,
+    ///
+    pub is_locked: bool,
+    /// its directory is gone, `prune_worktrees` drops it
+    pub is_prunable: bool,
+}
+
+/// the main worktree followed by the linked ones sorted by name
+pub fn get_worktrees(repo_path: &str) -> Result<Vec<WorktreeInfo>> {
+    scope_time!("get_worktrees");
+
+    let repo = repo(repo_path)?;
+    let current = canonical(work_dir(&repo)?);
+
+    let main = main_repo(&repo)?;
+    let main_path = work_dir(&main)?;
+
+    let mut res = vec![WorktreeInfo {
+        name: None,
+        path: path_string(main_path),
+        branch: head_branch(&main),
+        is_current: canonical(main_path) == current,
+        is_locked: false,
+        is_prunable: false,
+    }];
+
+    let mut names = main
+        .worktrees()?
+        .iter()
+        .flatten()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    names.sort();
+
+    for name in names {
+        let wt = main.find_worktree(&name)?;
+        let wt_repo = Repository::open_from_worktree(&wt).ok();
+
+        res.push(WorktreeInfo {
+            path: path_string(wt.path()),
+            branch: wt_repo.as_ref().and_then(head_branch),
+            is_current: canonical(wt.path()) == current,
+            is_locked: matches!(
+                wt.is_locked()?,
+                WorktreeLockStatus::Locked(_)
+            ),
+            is_prunable: wt.is_prunable(None)?,
+            name: Some(name),
+        });
+    }
+
+    Ok(res)
+}
+
+/// checks out the local `branch` in a new worktree next to the main
+/// one (`<main dir>-<branch>`), the branch is created from `HEAD` if
+/// it does not exist. returns the path of the new worktree
+pub fn create_worktree(
+    repo_path: &str,
+    branch: &str,
+) -> Result<String> {
+    scope_time!("create_worktree");
+
+    let repo = repo(repo_path)?;
+    let main = main_repo(&repo)?;
+
+    let name = branch.replace('/', "-");
+    let path = sibling_path(work_dir(&main)?, &name)?;
+    if path.exists() {
+        return Err(Error::Generic(format!(
+            "path exists already: {}",
+            path.to_string_lossy()
+        )));
+    }
+
+    let reference =
+        match main.find_branch(branch, git2::BranchType::Local) {
+            Ok(b) => b.into_reference(),
+            Err(_) => {
+                let head = main.head()?.peel_to_commit()?;
+                main.branch(branch, &head, false)?.into_reference()
+            }
+        };
+
+    let mut opts = WorktreeAddOptions::new();
+    opts.reference(Some(&reference));
+
+    let wt = main.worktree(&name, &path, Some(&opts))?;
+
+    Ok(path_string(wt.path()))
+}
+
+/// changes to tracked files and untracked files, which
+/// `git worktree remove` refuses to delete as well (ignored files
+/// are fine)
+fn has_changes(repo: &Repository) -> Result<bool> {
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true).include_ignored(false);
+
+    Ok(!repo.statuses(Some(&mut opts))?.is_empty())
+}
+
+/// deletes the worktree `name` and its directory, refuses to drop
+/// uncommitted changes, untracked files or the worktree `repo_path`
+/// belongs to. a directory that cannot be checked for changes is
+/// kept, a missing one is just pruned
+pub fn remove_worktree(repo_path: &str, name: &str) -> Result<()> {
+    scope_time!("remove_worktree");
+
+    let repo = repo(repo_path)?;
+    let main = main_repo(&repo)?;
+    let wt = main.find_worktree(name)?;
+
+    if canonical(wt.path()) == canonical(work_dir(&repo)?) {
+        return Err(Error::Generic(String::from(
+            "cannot remove the current worktree",
+        )));
+    }
+    match Repository::open_from_worktree(&wt) {
+        Ok(wt_repo) => {
+            if has_changes(&wt_repo)? {
+                return Err(Error::UncommittedChanges);
+            }
+        }
+        Err(e) if wt.path().exists() => {
+            return Err(Error::Generic(format!(
+                "cannot check the worktree for changes, it is kept: {}",
+                e
+            )));
+        }
+        Err(_) => (),
+    }
+
+    wt.prune(Some(
+        WorktreePruneOptions::new().valid(true).working_tree(true),
+    ))?;
+
+    Ok(())
+}
+
+/// drops the worktrees whose directory is gone, returns their count
+pub fn prune_worktrees(repo_path: &str) -> Result<usize> {
+    scope_time!("prune_worktrees");
+
+    let repo = repo(repo_path)?;
+    let main = main_repo(&repo)?;
+
+    let prunable = main
+        .worktrees()?
+        .iter()
+        .flatten()
+        .map(|name| main.find_worktree(name))
+        .collect::<std::result::Result<Vec<Worktree>, _>>()?
+        .into_iter()
+        .filter(|wt| wt.is_prunable(None).unwrap_or_default())
+        .collect::<Vec<_>>();
+
+    for wt in &prunable {
+        wt.prune(None)?;
+    }
+
+    Ok(prunable.len())
+}
+
+/// the worktrees are listed and added through the main repo, the
+/// git dir of a linked worktree points to it in its `commondir` file
+fn main_repo(repo: &Repository) -> Result<Repository> {
+    if repo.is_worktree() {
+        let common_dir =
+            fs::read_to_string(repo.path().join("commondir"))?;
+        Ok(Repository::open(repo.path().join(common_dir.trim()))?)
+    } else {
+        Ok(Repository::open(repo.path())?)
+    }
+}
+
+fn head_branch(repo: &Repository) -> Option<String> {
+    let head = repo.head().ok()?;
+    if head.is_branch() {
+        head.shorthand().map(String::from)
+    } else {
+        None
+    }
+}
+
+fn sibling_path(main_path: &Path, name: &str) -> Result<PathBuf> {
+    let main_path = canonical(main_path);
+    let dir_name = main_path
+        .file_name()
+        .and_then(std::ffi::OsStr::to_str)
+        .ok_or_else(|| {
+            Error::Generic(String::from("invalid workdir"))
+        })?;
+
+    Ok(main_path.with_file_name(format!("{}-{}", dir_name, name)))
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().trim_end_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{branch::get_branch_name, tests::repo_init};
+
+    #[test]
+    fn test_create_list_remove() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let res = get_worktrees(repo_path).unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].name, None);
+        assert_eq!(res[0].branch, Some(String::from("master")));
+        assert!(res[0].is_current);
+
+        let path = create_worktree(repo_path, "feature/x").unwrap();
+
+        assert!(path.ends_with("-feature-x"));
+        assert_eq!(
+            get_branch_name(&path).unwrap(),
+            String::from("feature/x")
+        );
+
+        let res = get_worktrees(&path).unwrap();
+        assert_eq!(res.len(), 2);
+        assert!(!res[0].is_current);
+        assert_eq!(res[1].name, Some(String::from("feature-x")));
+        assert_eq!(res[1].branch, Some(String::from("feature/x")));
+        assert!(res[1].is_current);
+        assert!(!res[1].is_prunable);
+
+        assert!(remove_worktree(&path, "feature-x").is_err());
+
+        remove_worktree(repo_path, "feature-x").unwrap();
+
+        assert!(!Path::new(&path).exists());
+        assert_eq!(get_worktrees(repo_path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_remove_keeps_untracked() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let path = create_worktree(repo_path, "other").unwrap();
+        let file = Path::new(&path).join("new.txt");
+        fs::write(&file, "keep me").unwrap();
+
+        assert!(remove_worktree(repo_path, "other").is_err());
+        assert!(file.exists());
+        assert_eq!(get_worktrees(repo_path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_remove_unreadable() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let path = create_worktree(repo_path, "other").unwrap();
+        let file = Path::new(&path).join("new.txt");
+        fs::write(&file, "keep me").unwrap();
+        fs::write(Path::new(&path).join(".git"), "broken").unwrap();
+
+        assert!(remove_worktree(repo_path, "other").is_err());
+        assert!(file.exists());
+
+        fs::remove_dir_all(&path).unwrap();
+
+        remove_worktree(repo_path, "other").unwrap();
+        assert_eq!(get_worktrees(repo_path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_prune() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let path = create_worktree(repo_path, "other").unwrap();
+        fs::remove_dir_all(&path).unwrap();
+
+        let res = get_worktrees(repo_path).unwrap();
+        assert!(res[1].is_prunable);
+
+        assert_eq!(prune_worktrees(repo_path).unwrap(), 1);
+        assert_eq!(get_worktrees(repo_path).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,303 @@
+use crate::{
+    error::Result, AsyncNotification, DiffLineType, FileDiff,
+};
+use crossbeam_channel::Sender;
+use once_cell::sync::Lazy;
+use std::{
+    ops::Range,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Theme, ThemeSet},
+    parsing::{SyntaxReference, SyntaxSet},
+};
+
+/// works on dark and light terminals alike
+const THEME: &str = "base16-ocean.dark";
+
+/// part of a line colored by its syntax
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxSpan {
+    /// bytes of the line content
+    pub range: Range<usize>,
+    /// rgb
+    pub color: (u8, u8, u8),
+}
+
+/// spans of every line of a diff (in order of all its hunks), empty
+/// for lines not highlighted
+pub type DiffSyntax = Vec<Vec<SyntaxSpan>>;
+
+/// loaded with the first diff highlighted, shared by all of them
+static HIGHLIGHTER: Lazy<Highlighter> = Lazy::new(Highlighter::new);
+
+/// hash of the diff and its syntax
+type LastSyntax = Option<(u64, Arc<DiffSyntax>)>;
+
+struct Highlighter {
+    syntaxes: SyntaxSet,
+    theme: Theme,
+}
+
+impl Highlighter {
+    fn new() -> Self {
+        let mut themes = ThemeSet::load_defaults();
+
+        Self {
+            syntaxes: SyntaxSet::load_defaults_newlines(),
+            theme: themes.themes.remove(THEME).unwrap_or_default(),
+        }
+    }
+
+    fn syntax(&self, path: &str) -> Option<&SyntaxReference> {
+        let path = Path::new(path);
+
+        path.extension().or_else(|| path.file_name()).and_then(
+            |ext| {
+                self.syntaxes
+                    .find_syntax_by_extension(&ext.to_string_lossy())
+            },
+        )
+    }
+
+    /// old and new lines of each hunk are highlighted on their own,
+    /// a combined diff switches the syntax with every file
+    fn highlight_diff(
+        &self,
+        path: &str,
+        diff: &FileDiff,
+    ) -> DiffSyntax {
+        let mut syntax = self.syntax(path);
+        let mut res = Vec::with_capacity(diff.lines);
+
+        for hunk in &diff.hunks {
+            let mut old =
+                syntax.map(|s| HighlightLines::new(s, &self.theme));
+            let mut new =
+                syntax.map(|s| HighlightLines::new(s, &self.theme));
+
+            for line in &hunk.lines {
+                let spans = match line.line_type {
+                    DiffLineType::None => {
+                        if let Some(old) = &mut old {
+                            old.highlight(
+                                &line.content,
+                                &self.syntaxes,
+                            );
+                        }
+                        self.spans(&mut new, &line.content)
+                    }
+                    DiffLineType::Add => {
+                        self.spans(&mut new, &line.content)
+                    }
+                    DiffLineType::Delete => {
+                        self.spans(&mut old, &line.content)
+                    }
+                    DiffLineType::File => {
+                        syntax = self.syntax(&line.content);
+                        Vec::new()
+                    }
+                    DiffLineType::Header
+                    | DiffLineType::NoNewline => Vec::new(),
+                };
+
+                res.push(spans);
+            }
+        }
+
+        res
+    }
+
+    fn spans(
+        &self,
+        highlighter: &mut Option<HighlightLines>,
+        content: &str,
+    ) -> Vec<SyntaxSpan> {
+        let highlighter = match highlighter {
+            Some(highlighter) => highlighter,
+            None => return Vec::new(),
+        };
+
+        let mut start = 0;
+        highlighter
+            .highlight(content, &self.syntaxes)
+            .into_iter()
+            .map(|(style, text)| {
+                let range = start..start + text.len();
+                start = range.end;
+                SyntaxSpan {
+                    range,
+                    color: (
+                        style.foreground.r,
+                        style.foreground.g,
+                        style.foreground.b,
+                    ),
+                }
+            })
+            .collect()
+    }
+}
+
+/// highlights diffs in the background
+pub struct AsyncSyntax {
+    /// hash of the diff requested last
+    current: Arc<Mutex<u64>>,
+    last: Arc<Mutex<LastSyntax>>,
+    pending: Arc<AtomicUsize>,
+    sender: Sender<AsyncNotification>,
+}
+
+impl AsyncSyntax {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            current: Arc::new(Mutex::new(0)),
+            last: Arc::new(Mutex::new(None)),
+            pending: Arc::new(AtomicUsize::new(0)),
+            sender: sender.clone(),
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    /// syntax of the diff with `hash` once highlighted
+    pub fn last(&self, hash: u64) -> Result<Option<Arc<DiffSyntax>>> {
+        Ok(self
+            .last
+            .lock()?
+            .as_ref()
+            .filter(|(last_hash, _)| *last_hash == hash)
+            .map(|(_, syntax)| Arc::clone(syntax)))
+    }
+
+    /// `hash` tells the diff apart, `path` picks the language
+    pub fn request(
+        &mut self,
+        hash: u64,
+        path: String,
+        diff: FileDiff,
+    ) -> Result<()> {
+        log::trace!("request");
+
+        {
+            let mut current = self.current.lock()?;
+            if *current == hash {
+                return Ok(());
+            }
+            *current = hash;
+        }
+
+        let arc_current = Arc::clone(&self.current);
+        let arc_last = Arc::clone(&self.last);
+        let arc_pending = Arc::clone(&self.pending);
+        let sender = self.sender.clone();
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        rayon_core::spawn(move || {
+            let outdated =
+                || *arc_current.lock().expect("lock error") != hash;
+
+            if !outdated() {
+                let syntax = HIGHLIGHTER.highlight_diff(&path, &diff);
+
+                if !outdated() {
+                    *arc_last.lock().expect("lock error") =
+                        Some((hash, Arc::new(syntax)));
+                }
+            }
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+
+            sender
+                .send(AsyncNotification::Syntax)
+                .expect("error sending");
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::diff::{DiffLine, Hunk};
+
+    fn line(content: &str, line_type: DiffLineType) -> DiffLine {
+        DiffLine {
+            content: content.to_string(),
+            line_type,
+            ..DiffLine::default()
+        }
+    }
+
+    fn diff(lines: Vec<DiffLine>) -> FileDiff {
+        FileDiff {
+            lines: lines.len(),
+            hunks: vec![Hunk {
+                header_hash: 0,
+                lines,
+            }],
+            ..FileDiff::default()
+        }
+    }
+
+    #[test]
+    fn test_highlight_diff() {
+        let highlighter = Highlighter::new();
+        let diff = diff(vec![
+            line("@@ -1 +1 @@\n", DiffLineType::Header),
+            line("fn foo() {}\n", DiffLineType::Delete),
+            line("fn bar() {}\n", DiffLineType::Add),
+        ]);
+
+        let syntax = highlighter.highlight_diff("src/lib.rs", &diff);
+
+        assert_eq!(syntax.len(), 3);
+        assert!(syntax[0].is_empty());
+        for (spans, line) in
+            syntax[1..].iter().zip(&diff.hunks[0].lines[1..])
+        {
+            assert_eq!(
+                spans.last().map(|s| s.range.end),
+                Some(line.content.len())
+            );
+            // keyword and function name differ in color
+            assert!(spans
+                .windows(2)
+                .any(|w| w[0].color != w[1].color));
+        }
+
+        let unknown =
+            highlighter.highlight_diff("foo.unknown", &diff);
+        assert!(unknown.iter().all(Vec::is_empty));
+    }
+
+    #[test]
+    fn test_combined_diff() {
+        let highlighter = Highlighter::new();
+        let mut diff = FileDiff::default();
+        for path in &["README", "src/lib.rs"] {
+            diff.append_file(
+                path,
+                self::diff(vec![line(
+                    "fn foo() {}\n",
+                    DiffLineType::Add,
+                )]),
+            );
+        }
+
+        let syntax = highlighter.highlight_diff("", &diff);
+
+        assert!(syntax[1].is_empty());
+        assert!(!syntax[3].is_empty());
+    }
+}
@@ -12,12 +12,13 @@ use std::{
     },
     time::{Duration, Instant},
 };
-use sync::Tags;
+use sync::{RefsState, Tags};
 
 ///
-#[derive(Default, Clone)]
+#[derive(Clone)]
 struct TagsResult {
     hash: u64,
+    refs_state: RefsState,
     tags: Tags,
 }
 
@@ -98,6 +99,19 @@ impl AsyncTags {
     fn getter(
         arc_last: Arc<Mutex<Option<(Instant, TagsResult)>>>,
     ) -> Result<bool> {
+        let refs_state = sync::get_refs_state(CWD)?;
+
+        // skip the expensive enumeration if no ref changed on disk
+        {
+            let mut last = arc_last.lock()?;
+            if let Some((time, last)) = last.as_mut() {
+                if last.refs_state == refs_state {
+                    *time = Instant::now();
+                    return Ok(false);
+                }
+            }
+        }
+
         let tags = sync::get_tags(CWD)?;
 
         let hash = hash(&tags);
@@ -106,13 +120,23 @@ impl AsyncTags {
             .map(|last| last == hash)
             .unwrap_or_default()
         {
+            if let Some((_, last)) = arc_last.lock()?.as_mut() {
+                last.refs_state = refs_state;
+            }
             return Ok(false);
         }
 
         {
             let mut last = arc_last.lock()?;
             let now = Instant::now();
-            *last = Some((now, TagsResult { hash, tags }));
+            *last = Some((
+                now,
+                TagsResult {
+                    hash,
+                    refs_state,
+                    tags,
+                },
+            ));
         }
 
         Ok(true)
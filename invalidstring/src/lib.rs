@@ -2,6 +2,7 @@
 pub fn invalid_utf8(prefix: &str) -> String {
     let bytes = b"\xc3\x73";
 
+    #[allow(invalid_from_utf8_unchecked)]
     unsafe {
         format!("{}{}", prefix, std::str::from_utf8_unchecked(bytes))
     }
@@ -2,27 +2,56 @@ use crate::{
     accessors,
     cmdbar::CommandBar,
     components::{
-        event_pump, BranchListComponent, CommandBlocking,
-        CommandInfo, CommitComponent, Component,
-        CreateBranchComponent, DrawableComponent,
-        ExternalEditorComponent, HelpComponent,
-        InspectCommitComponent, MsgComponent, PullComponent,
-        PushComponent, PushTagsComponent, RenameBranchComponent,
-        ResetComponent, StashMsgComponent, TagCommitComponent,
+        event_pump, ArchiveComponent, BisectComponent,
+        BlameComponent, BranchDescriptionComponent,
+        BranchListComponent, BranchSearchComponent,
+        BranchUpstreamComponent, CheckoutConflictComponent,
+        CleanComponent, CommandBlocking, CommandInfo,
+        CommitComponent, CompareCommitsComponent,
+        CompareUpstreamComponent, Component, ConflictComponent,
+        CreateBranchComponent, CreateWorktreeComponent,
+        DiffOptionsComponent, DrawableComponent, EditIgnoreComponent,
+        ExternalEditorComponent, FetchAllComponent,
+        FileHistoryComponent, HelpComponent, IgnoreComponent,
+        InspectCommitComponent, MergeBranchComponent, MsgComponent,
+        ObjectViewerComponent, PatchFileComponent,
+        PatchViewerComponent, PendingOperation, PickHunksComponent,
+        PullComponent, PullStrategyComponent, PushComponent,
+        PushTagsComponent, PushTargetComponent, RebaseComponent,
+        ReflogComponent, RemoteListComponent, RenameBranchComponent,
+        RenameStashComponent, ResetCommitComponent, ResetComponent,
+        RevisionFilesComponent, RewordComponent,
+        SelectCoAuthorComponent, SelectIdentityComponent,
+        StartupFetchComponent, StashBranchComponent,
+        StashMsgComponent, StatusBarComponent,
+        SubmodulesListComponent, TagCommitComponent,
+        TagListComponent, WorktreeListComponent,
     },
     input::{Input, InputEvent, InputState},
     keys::{KeyConfig, SharedKeyConfig},
-    queue::{Action, InternalEvent, NeedsUpdate, Queue},
+    options::{Options, SharedOptions},
+    queue::{
+        Action, Advice, InternalEvent, NeedsUpdate, Queue, ResetItem,
+    },
     strings::{self, order},
     tabs::{Revlog, StashList, Stashing, Status},
     ui::style::{SharedTheme, Theme},
 };
 use anyhow::{bail, Result};
-use asyncgit::{sync, AsyncNotification, CWD};
+use asyncgit::{
+    sync::{
+        self, CherryPickResult, CommitId, IndexProblem, MergeResult,
+        MergeStrategy, PushTagsTarget, RebaseState, RevertResult,
+    },
+    AsyncNotification, CWD,
+};
 use crossbeam_channel::Sender;
 use crossterm::event::{Event, KeyEvent};
 use std::{
     cell::{Cell, RefCell},
+    collections::HashSet,
+    env,
+    mem::{self, Discriminant},
     path::Path,
     rc::Rc,
 };
@@ -43,14 +72,53 @@ pub struct App {
     commit: CommitComponent,
     stashmsg_popup: StashMsgComponent,
     inspect_commit_popup: InspectCommitComponent,
+    revision_files_popup: RevisionFilesComponent,
+    patch_file_popup: PatchFileComponent,
+    compare_upstream_popup: CompareUpstreamComponent,
     external_editor_popup: ExternalEditorComponent,
     push_popup: PushComponent,
     push_tags_popup: PushTagsComponent,
+    push_target_popup: PushTargetComponent,
     pull_popup: PullComponent,
+    fetch_all_popup: FetchAllComponent,
+    archive_popup: ArchiveComponent,
+    pull_strategy_popup: PullStrategyComponent,
+    diff_options_popup: DiffOptionsComponent,
+    ignore_popup: IgnoreComponent,
+    edit_ignore_popup: EditIgnoreComponent,
     tag_commit_popup: TagCommitComponent,
     create_branch_popup: CreateBranchComponent,
     rename_branch_popup: RenameBranchComponent,
+    branch_description_popup: BranchDescriptionComponent,
+    rename_stash_popup: RenameStashComponent,
+    reword_popup: RewordComponent,
+    reset_commit_popup: ResetCommitComponent,
+    stash_branch_popup: StashBranchComponent,
     select_branch_popup: BranchListComponent,
+    object_viewer_popup: ObjectViewerComponent,
+    patch_viewer_popup: PatchViewerComponent,
+    blame_popup: BlameComponent,
+    file_history_popup: FileHistoryComponent,
+    compare_commits_popup: CompareCommitsComponent,
+    submodules_popup: SubmodulesListComponent,
+    worktrees_popup: WorktreeListComponent,
+    remotes_popup: RemoteListComponent,
+    reflog_popup: ReflogComponent,
+    taglist_popup: TagListComponent,
+    bisect_popup: BisectComponent,
+    conflict_popup: ConflictComponent,
+    create_worktree_popup: CreateWorktreeComponent,
+    pick_hunks_popup: PickHunksComponent,
+    rebase_popup: RebaseComponent,
+    branch_search_popup: BranchSearchComponent,
+    checkout_conflict_popup: CheckoutConflictComponent,
+    merge_branch_popup: MergeBranchComponent,
+    branch_upstream_popup: BranchUpstreamComponent,
+    clean_popup: CleanComponent,
+    select_identity_popup: SelectIdentityComponent,
+    select_co_author_popup: SelectCoAuthorComponent,
+    startup_fetch: StartupFetchComponent,
+    status_bar: StatusBarComponent,
     cmdbar: RefCell<CommandBar>,
     tab: usize,
     revlog: Revlog,
@@ -60,28 +128,69 @@ pub struct App {
     queue: Queue,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
+    options: SharedOptions,
     input: Input,
 
     // "Flags"
     requires_redraw: Cell<bool>,
     file_to_open: Option<String>,
+    submodule_to_open: Option<String>,
+    mergetool_to_run: Option<String>,
+    /// path, hunk hash
+    hunk_to_edit: Option<(String, u64)>,
+    do_reopen: bool,
+    advice_shown: HashSet<Discriminant<Advice>>,
 }
 
 // public interface
 impl App {
     ///
-    #[allow(clippy::too_many_lines)]
     pub fn new(
         sender: &Sender<AsyncNotification>,
         input: Input,
         theme: Theme,
         key_config: KeyConfig,
+        options: Options,
+    ) -> Self {
+        Self::with_shared(
+            sender,
+            input,
+            Rc::new(theme),
+            Rc::new(key_config),
+            Rc::new(options),
+        )
+    }
+
+    /// a new app for the current working dir after switching the
+    /// worktree, keeping the input and the configs
+    pub fn reopen(self, sender: &Sender<AsyncNotification>) -> Self {
+        Self::with_shared(
+            sender,
+            self.input,
+            self.theme,
+            self.key_config,
+            self.options,
+        )
+    }
+
+    ///
+    #[allow(clippy::too_many_lines)]
+    fn with_shared(
+        sender: &Sender<AsyncNotification>,
+        input: Input,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
         let queue = Queue::default();
-        let theme = Rc::new(theme);
-        let key_config = Rc::new(key_config);
 
-        Self {
+        if sync::is_partial_clone(CWD).unwrap_or_default() {
+            queue.borrow_mut().push_back(InternalEvent::ShowAdvice(
+                Advice::PartialClone,
+            ));
+        }
+
+        let mut app = Self {
             input,
             reset: ResetComponent::new(
                 queue.clone(),
@@ -92,6 +201,7 @@ impl App {
                 queue.clone(),
                 theme.clone(),
                 key_config.clone(),
+                options.clone(),
             ),
             stashmsg_popup: StashMsgComponent::new(
                 queue.clone(),
@@ -103,6 +213,14 @@ impl App {
                 sender,
                 theme.clone(),
                 key_config.clone(),
+                options.clone(),
+            ),
+            compare_upstream_popup: CompareUpstreamComponent::new(
+                &queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+                options.clone(),
             ),
             external_editor_popup: ExternalEditorComponent::new(
                 theme.clone(),
@@ -120,6 +238,44 @@ impl App {
                 theme.clone(),
                 key_config.clone(),
             ),
+            fetch_all_popup: FetchAllComponent::new(
+                &queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            archive_popup: ArchiveComponent::new(
+                &queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            pull_strategy_popup: PullStrategyComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            diff_options_popup: DiffOptionsComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+                options.clone(),
+            ),
+            ignore_popup: IgnoreComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            edit_ignore_popup: EditIgnoreComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            push_target_popup: PushTargetComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
             pull_popup: PullComponent::new(
                 &queue,
                 sender,
@@ -136,16 +292,178 @@ impl App {
                 theme.clone(),
                 key_config.clone(),
             ),
+            branch_search_popup: BranchSearchComponent::new(
+                &queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            object_viewer_popup: ObjectViewerComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            patch_viewer_popup: PatchViewerComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            blame_popup: BlameComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            file_history_popup: FileHistoryComponent::new(
+                &queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+                options.clone(),
+            ),
+            compare_commits_popup: CompareCommitsComponent::new(
+                &queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+                options.clone(),
+            ),
+            bisect_popup: BisectComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            conflict_popup: ConflictComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            reflog_popup: ReflogComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            taglist_popup: TagListComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            worktrees_popup: WorktreeListComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            remotes_popup: RemoteListComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            create_worktree_popup: CreateWorktreeComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            submodules_popup: SubmodulesListComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            pick_hunks_popup: PickHunksComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            rebase_popup: RebaseComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
             rename_branch_popup: RenameBranchComponent::new(
                 queue.clone(),
                 theme.clone(),
                 key_config.clone(),
             ),
+            branch_description_popup: BranchDescriptionComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            rename_stash_popup: RenameStashComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            reword_popup: RewordComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            revision_files_popup: RevisionFilesComponent::new(
+                &queue,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            patch_file_popup: PatchFileComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            reset_commit_popup: ResetCommitComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            stash_branch_popup: StashBranchComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
             select_branch_popup: BranchListComponent::new(
+                queue.clone(),
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            checkout_conflict_popup: CheckoutConflictComponent::new(
                 queue.clone(),
                 theme.clone(),
                 key_config.clone(),
             ),
+            merge_branch_popup: MergeBranchComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            branch_upstream_popup: BranchUpstreamComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            clean_popup: CleanComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            select_identity_popup: SelectIdentityComponent::new(
+                queue.clone(),
+                options.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            select_co_author_popup: SelectCoAuthorComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            startup_fetch: StartupFetchComponent::new(
+                &queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            status_bar: StatusBarComponent::new(
+                theme.clone(),
+                key_config.clone(),
+            ),
             do_quit: false,
             cmdbar: RefCell::new(CommandBar::new(
                 theme.clone(),
@@ -162,12 +480,14 @@ impl App {
                 sender,
                 theme.clone(),
                 key_config.clone(),
+                options.clone(),
             ),
             status_tab: Status::new(
                 &queue,
                 sender,
                 theme.clone(),
                 key_config.clone(),
+                options.clone(),
             ),
             stashing_tab: Stashing::new(
                 sender,
@@ -177,15 +497,33 @@ impl App {
             ),
             stashlist_tab: StashList::new(
                 &queue,
+                sender,
                 theme.clone(),
                 key_config.clone(),
+                options.clone(),
             ),
             queue,
             theme,
             key_config,
+            options,
             requires_redraw: Cell::new(false),
             file_to_open: None,
+            submodule_to_open: None,
+            mergetool_to_run: None,
+            hunk_to_edit: None,
+            do_reopen: false,
+            advice_shown: HashSet::new(),
+        };
+
+        if app.options.fetch_on_startup {
+            let ask_credentials =
+                app.options.fetch_on_startup_ask_credentials;
+            if let Err(e) = app.startup_fetch.start(ask_credentials) {
+                log::error!("startup fetch: {}", e);
+            }
         }
+
+        app
     }
 
     ///
@@ -200,15 +538,18 @@ impl App {
                 [
                     Constraint::Length(2),
                     Constraint::Min(2),
+                    Constraint::Length(1),
                     Constraint::Length(self.cmdbar.borrow().height()),
                 ]
                 .as_ref(),
             )
             .split(fsize);
 
-        self.cmdbar.borrow().draw(f, chunks_main[2]);
+        self.status_bar.draw(f, chunks_main[2])?;
+        self.cmdbar.borrow().draw(f, chunks_main[3]);
 
         self.draw_tabs(f, chunks_main[0]);
+        self.startup_fetch.draw(f, chunks_main[0])?;
 
         //TODO: macro because of generic draw call
         match self.tab {
@@ -265,6 +606,19 @@ impl App {
         } else if let InputEvent::State(polling_state) = ev {
             self.external_editor_popup.hide();
             if let InputState::Paused = polling_state {
+                if let Some(workdir) = self.submodule_to_open.take() {
+                    self.open_submodule(&workdir)?;
+                    return Ok(());
+                }
+                if let Some(path) = self.mergetool_to_run.take() {
+                    self.run_mergetool(&path)?;
+                    return Ok(());
+                }
+                if let Some((path, hash)) = self.hunk_to_edit.take() {
+                    self.edit_hunk(&path, hash)?;
+                    return Ok(());
+                }
+
                 let result = match self.file_to_open.take() {
                     Some(path) => {
                         ExternalEditorComponent::open_file_in_editor(
@@ -279,6 +633,7 @@ impl App {
                         format!("failed to launch editor:\n{}", e);
                     log::error!("{}", msg.as_str());
                     self.msg.show_error(msg.as_str())?;
+                    self.offer_index_recovery();
                 }
 
                 self.requires_redraw.set(true);
@@ -299,6 +654,9 @@ impl App {
         self.revlog.update()?;
         self.stashing_tab.update()?;
         self.stashlist_tab.update()?;
+        self.pick_hunks_popup.update()?;
+        self.status_bar.update()?;
+        self.update_pending();
 
         self.update_commands();
 
@@ -314,11 +672,22 @@ impl App {
 
         self.status_tab.update_git(ev)?;
         self.stashing_tab.update_git(ev)?;
+        self.stashlist_tab.update_git(ev)?;
         self.revlog.update_git(ev)?;
         self.inspect_commit_popup.update_git(ev)?;
+        self.file_history_popup.update_git(ev)?;
+        self.compare_commits_popup.update_git(ev)?;
+        self.compare_upstream_popup.update_git(ev)?;
         self.push_popup.update_git(ev)?;
+        self.select_branch_popup.update_git(ev)?;
         self.push_tags_popup.update_git(ev)?;
         self.pull_popup.update_git(ev)?;
+        self.fetch_all_popup.update_git(ev)?;
+        self.archive_popup.update_git(ev)?;
+        self.startup_fetch.update_git(ev)?;
+        self.branch_search_popup.update_git(ev)?;
+        self.status_bar.update_git(ev)?;
+        self.update_pending();
 
         //TODO: better system for this
         // can we simply process the queue here and everyone just uses the queue to schedule a cmd update?
@@ -332,16 +701,30 @@ impl App {
         self.do_quit
     }
 
+    /// the working dir changed, see `reopen`
+    pub const fn needs_reopen(&self) -> bool {
+        self.do_reopen
+    }
+
     ///
     pub fn any_work_pending(&self) -> bool {
         self.status_tab.anything_pending()
             || self.revlog.any_work_pending()
             || self.stashing_tab.anything_pending()
+            || self.stashlist_tab.any_work_pending()
             || self.inspect_commit_popup.any_work_pending()
+            || self.file_history_popup.any_work_pending()
+            || self.compare_commits_popup.any_work_pending()
+            || self.compare_upstream_popup.any_work_pending()
             || self.input.is_state_changing()
             || self.push_popup.any_work_pending()
             || self.push_tags_popup.any_work_pending()
             || self.pull_popup.any_work_pending()
+            || self.fetch_all_popup.any_work_pending()
+            || self.archive_popup.any_work_pending()
+            || self.startup_fetch.any_work_pending()
+            || self.branch_search_popup.any_work_pending()
+            || self.select_branch_popup.any_work_pending()
     }
 
     ///
@@ -357,22 +740,87 @@ impl App {
 
 // private impls
 impl App {
+    /// the remote operations (and archives) the status bar names
+    fn update_pending(&mut self) {
+        let mut pending = Vec::new();
+        if self.push_popup.any_work_pending() {
+            pending.push(PendingOperation::Push);
+        }
+        if self.push_tags_popup.any_work_pending() {
+            pending.push(PendingOperation::PushTags);
+        }
+        if self.pull_popup.any_work_pending() {
+            pending.push(PendingOperation::Pull);
+        }
+        if self.fetch_all_popup.any_work_pending()
+            || self.startup_fetch.any_work_pending()
+        {
+            pending.push(PendingOperation::Fetch);
+        }
+        if self.archive_popup.any_work_pending() {
+            pending.push(PendingOperation::Archive);
+        }
+        if self.select_branch_popup.rebase_pending() {
+            pending.push(PendingOperation::Rebase);
+        }
+
+        self.status_bar.set_pending(pending);
+    }
+
     accessors!(
         self,
         [
+            startup_fetch,
             msg,
             reset,
+            select_co_author_popup,
             commit,
             stashmsg_popup,
+            diff_options_popup,
+            ignore_popup,
+            edit_ignore_popup,
+            blame_popup,
+            file_history_popup,
+            compare_commits_popup,
+            reflog_popup,
+            bisect_popup,
+            conflict_popup,
+            submodules_popup,
+            create_worktree_popup,
+            worktrees_popup,
+            remotes_popup,
             inspect_commit_popup,
+            revision_files_popup,
+            compare_upstream_popup,
             external_editor_popup,
             push_popup,
             push_tags_popup,
+            push_target_popup,
+            pull_strategy_popup,
             pull_popup,
+            fetch_all_popup,
+            archive_popup,
             tag_commit_popup,
+            taglist_popup,
             create_branch_popup,
             rename_branch_popup,
+            branch_description_popup,
+            rename_stash_popup,
+            reword_popup,
+            patch_file_popup,
+            reset_commit_popup,
+            stash_branch_popup,
+            checkout_conflict_popup,
+            merge_branch_popup,
+            branch_upstream_popup,
+            clean_popup,
+            select_identity_popup,
             select_branch_popup,
+            object_viewer_popup,
+            patch_viewer_popup,
+            pick_hunks_popup,
+            rebase_popup,
+            branch_search_popup,
             help,
             revlog,
             status_tab,
@@ -381,6 +829,241 @@ impl App {
         ]
     );
 
+    /// stops at the first conflict, it is left to be resolved in the
+    /// status tab
+    fn cherry_pick(&mut self, ids: &[CommitId]) -> Result<()> {
+        for (i, id) in ids.iter().enumerate() {
+            match sync::cherry_pick(CWD, *id) {
+                Ok(CherryPickResult::Committed(_)) => (),
+                Ok(CherryPickResult::Conflict) => {
+                    self.msg.show_info(
+                        &strings::cherry_pick_conflict_msg(
+                            &self.key_config,
+                            ids.len() - i - 1,
+                        ),
+                    )?;
+                    self.set_tab(0)?;
+                    break;
+                }
+                Err(e) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "cherry-pick error:\n{}",
+                            e
+                        )),
+                    );
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// conflicts close the branch list for the status tab
+    fn merge_branch(
+        &mut self,
+        branch_ref: &str,
+        name: &str,
+        strategy: MergeStrategy,
+    ) -> Result<()> {
+        match sync::merge_branch(CWD, branch_ref, strategy) {
+            Ok(result) => {
+                self.msg.show_info(&strings::merge_branch_result(
+                    &self.key_config,
+                    name,
+                    result,
+                ))?;
+
+                if result == MergeResult::Conflict {
+                    self.select_branch_popup.hide();
+                    self.set_tab(0)?;
+                } else {
+                    self.select_branch_popup.update_branches()?;
+                }
+            }
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "merge error:\n{}",
+                        e
+                    )),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// like cherry-picking, conflicts go to the status tab
+    fn revert_commit(&mut self, id: CommitId) -> Result<()> {
+        match sync::revert_commit(CWD, id) {
+            Ok(RevertResult::Committed(_)) => (),
+            Ok(RevertResult::Conflict) => {
+                self.msg.show_info(&strings::revert_conflict_msg(
+                    &self.key_config,
+                ))?;
+                self.set_tab(0)?;
+            }
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "revert error:\n{}",
+                        e
+                    )),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// a conflict of the commits after it is resolved like in any
+    /// other rebase
+    fn drop_commit(&mut self, id: CommitId) -> Result<()> {
+        match sync::drop_commit(CWD, id) {
+            Ok(RebaseState::Finished) => (),
+            Ok(RebaseState::Conflict(_)) => {
+                self.rebase_popup.open(id)?;
+            }
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "drop commit error:\n{}",
+                        e
+                    )),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// all state belongs to the old working dir, so the main loop
+    /// rebuilds the app once `CWD` points to the worktree
+    fn switch_worktree(&mut self, path: &str) -> Result<()> {
+        if !sync::is_repo(path) {
+            self.msg
+                .show_error(&format!("not a repository: {}", path))?;
+            return Ok(());
+        }
+
+        if let Err(e) = env::set_current_dir(path) {
+            let msg = format!("failed to switch worktree:\n{}", e);
+            log::error!("{}", msg.as_str());
+            self.msg.show_error(msg.as_str())?;
+        } else {
+            self.do_reopen = true;
+        }
+
+        Ok(())
+    }
+
+    /// the updates of the submodule show up once back
+    fn open_submodule(&mut self, workdir: &str) -> Result<()> {
+        if let Err(e) =
+            SubmodulesListComponent::open_in_gitui(workdir)
+        {
+            let msg = format!("failed to open submodule:\n{}", e);
+            log::error!("{}", msg.as_str());
+            self.msg.show_error(msg.as_str())?;
+        }
+
+        self.requires_redraw.set(true);
+        self.input.set_polling(true);
+        self.update()
+    }
+
+    /// the popup shows up again while conflicts are left
+    fn run_mergetool(&mut self, path: &str) -> Result<()> {
+        if let Err(e) = ConflictComponent::run_mergetool(path) {
+            let msg = format!("mergetool error:\n{}", e);
+            log::error!("{}", msg.as_str());
+            self.msg.show_error(msg.as_str())?;
+        } else if sync::get_conflict(CWD, path).is_ok() {
+            self.conflict_popup.open(path)?;
+        }
+
+        self.requires_redraw.set(true);
+        self.input.set_polling(true);
+        self.process_queue(NeedsUpdate::ALL)
+    }
+
+    fn edit_hunk(&mut self, path: &str, hash: u64) -> Result<()> {
+        if let Err(e) =
+            self.external_editor_popup.edit_hunk(path, hash)
+        {
+            let msg = format!("edit hunk error:\n{}", e);
+            log::error!("{}", msg.as_str());
+            self.msg.show_error(msg.as_str())?;
+        }
+
+        self.requires_redraw.set(true);
+        self.input.set_polling(true);
+        self.process_queue(NeedsUpdate::ALL)
+    }
+
+    /// keeps the changes of `paths` (unless `discard_backup` is off)
+    /// to undo discarding them, `discard` is not run if that fails.
+    /// the backup is dropped again if `discard` fails, so undoing
+    /// cannot overwrite newer changes with it. returns whether
+    /// `discard` succeeded
+    fn discard_with_backup<F>(
+        &mut self,
+        paths: &[&str],
+        discard: F,
+    ) -> bool
+    where
+        F: FnOnce(&mut Self) -> bool,
+    {
+        let backup = if self.options.discard_backup {
+            match sync::backup_discard(CWD, paths) {
+                Ok(backup) => backup,
+                Err(e) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "discard backup error:\n{}",
+                            e
+                        )),
+                    );
+                    return false;
+                }
+            }
+        } else {
+            None
+        };
+
+        if discard(self) {
+            return true;
+        }
+
+        if let Some(backup) = backup {
+            if let Err(e) = sync::drop_discard_backup(CWD, backup) {
+                log::error!("drop discard backup error: {}", e);
+            }
+        }
+
+        false
+    }
+
+    /// errors after a crashed git process are mostly caused by a
+    /// leftover `index.lock`, offers to fix that (or a corrupt index)
+    fn offer_index_recovery(&self) {
+        if self.reset.is_visible() {
+            return;
+        }
+
+        let action = match sync::get_index_problem(CWD) {
+            Ok(Some(IndexProblem::Locked)) => Action::RemoveIndexLock,
+            Ok(Some(IndexProblem::Corrupt)) => Action::RebuildIndex,
+            _ => return,
+        };
+
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::ConfirmAction(action));
+    }
+
     fn check_quit_key(&mut self, ev: Event) -> bool {
         if let Event::Key(e) = ev {
             if e == self.key_config.exit {
@@ -458,6 +1141,16 @@ impl App {
         if flags.contains(NeedsUpdate::DIFF) {
             self.status_tab.update_diff()?;
             self.inspect_commit_popup.update_diff()?;
+            self.compare_upstream_popup.update_diff()?;
+            if self.file_history_popup.is_visible() {
+                self.file_history_popup.update_diff()?;
+            }
+            if self.compare_commits_popup.is_visible() {
+                self.compare_commits_popup.update_diff()?;
+            }
+            if self.stashlist_tab.is_visible() {
+                self.stashlist_tab.update_diff()?;
+            }
         }
         if flags.contains(NeedsUpdate::COMMANDS) {
             self.update_commands();
@@ -482,6 +1175,7 @@ impl App {
         Ok(flags)
     }
 
+    #[allow(clippy::too_many_lines)]
     fn process_internal_event(
         &mut self,
         ev: InternalEvent,
@@ -490,21 +1184,62 @@ impl App {
         match ev {
             InternalEvent::ConfirmedAction(action) => match action {
                 Action::Reset(r) => {
-                    if self.status_tab.reset(&r) {
+                    if self.discard_with_backup(&[&r.path], |app| {
+                        app.status_tab.reset(&r)
+                    }) {
                         flags.insert(NeedsUpdate::ALL);
                     }
                 }
+                Action::ResetItems(paths) => {
+                    let refs: Vec<&str> =
+                        paths.iter().map(String::as_str).collect();
+                    // the backup is kept once anything was discarded
+                    self.discard_with_backup(&refs, |app| {
+                        let mut reset = 0;
+                        for path in &paths {
+                            if !app.status_tab.reset(&ResetItem {
+                                path: path.clone(),
+                                is_folder: false,
+                            }) {
+                                break;
+                            }
+                            reset += 1;
+                        }
+                        reset > 0
+                    });
+                    flags.insert(NeedsUpdate::ALL);
+                }
                 Action::StashDrop(s) => {
                     if StashList::drop(s) {
                         flags.insert(NeedsUpdate::ALL);
                     }
                 }
                 Action::ResetHunk(path, hash) => {
-                    sync::reset_hunk(CWD, path, hash)?;
+                    let mut res = Ok(());
+                    self.discard_with_backup(&[&path], |_| {
+                        res =
+                            sync::reset_hunk(CWD, path.clone(), hash);
+                        res.is_ok()
+                    });
+                    res?;
                     flags.insert(NeedsUpdate::ALL);
                 }
-                Action::ResetLines(path, lines) => {
-                    sync::discard_lines(CWD, &path, &lines)?;
+                Action::ResetLines(path, lines, diff_hash) => {
+                    // an outdated diff gets reloaded by the update
+                    self.discard_with_backup(&[&path], |app| {
+                        let res = sync::discard_lines(
+                            CWD, &path, &lines, diff_hash,
+                        );
+                        if let Err(e) = &res {
+                            app.queue.borrow_mut().push_back(
+                                InternalEvent::ShowErrorMsg(format!(
+                                    "discard lines error:\n{}",
+                                    e
+                                )),
+                            );
+                        }
+                        res.is_ok()
+                    });
                     flags.insert(NeedsUpdate::ALL);
                 }
                 Action::DeleteBranch(branch_ref) => {
@@ -521,14 +1256,199 @@ impl App {
                         self.select_branch_popup.update_branches()?;
                     }
                 }
-                Action::ForcePush(branch, force) => self
+                Action::DeleteBranches(branch_refs) => {
+                    let errors: Vec<String> = branch_refs
+                        .iter()
+                        .filter_map(|branch_ref| {
+                            sync::delete_branch(CWD, branch_ref)
+                                .err()
+                                .map(|e| {
+                                    format!("{}: {}", branch_ref, e)
+                                })
+                        })
+                        .collect();
+                    if !errors.is_empty() {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "delete branches error:\n{}",
+                                errors.join("\n")
+                            )),
+                        );
+                    }
+                    flags.insert(NeedsUpdate::ALL);
+                    self.select_branch_popup.update_branches()?;
+                }
+                Action::DeleteRemoteBranch(branch_ref) => {
+                    match sync::get_remote_branch_parts(
+                        CWD,
+                        &branch_ref,
+                    ) {
+                        Ok((remote, branch)) => self
+                            .push_popup
+                            .delete_remote_branch(remote, branch)?,
+                        Err(e) => self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "delete remote branch error:\n{}",
+                                e
+                            )),
+                        ),
+                    }
+                }
+                Action::ForcePush(branch, _) => self
                     .queue
                     .borrow_mut()
-                    .push_back(InternalEvent::Push(branch, force)),
+                    .push_back(InternalEvent::Push(branch, true)),
                 Action::PullMerge { rebase, .. } => {
                     self.pull_popup.try_conflict_free_merge(rebase);
                     flags.insert(NeedsUpdate::ALL);
                 }
+                Action::AmendNoEdit { .. } => {
+                    self.commit.amend_no_edit()?;
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::RevertCommit(id) => {
+                    self.revert_commit(id)?;
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::DropCommit(id, _) => {
+                    self.drop_commit(id)?;
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::AbortState(_) => {
+                    if let Err(e) = sync::state_abort(CWD) {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "abort error:\n{}",
+                                e
+                            )),
+                        );
+                    }
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::RemoveWorktree(name) => {
+                    if let Err(e) = sync::remove_worktree(CWD, &name)
+                    {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "remove worktree error:\n{}",
+                                e
+                            )),
+                        );
+                    }
+                    self.worktrees_popup.refresh()?;
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::RemoveRemote(name) => {
+                    if let Err(e) = sync::remove_remote(CWD, &name) {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "remove remote error:\n{}",
+                                e
+                            )),
+                        );
+                    }
+                    self.remotes_popup.refresh()?;
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::ResetToCommit(id, kind) => {
+                    if let Err(e) =
+                        sync::reset_to_commit(CWD, id, kind)
+                    {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "reset error:\n{}",
+                                e
+                            )),
+                        );
+                    }
+                    self.reflog_popup.refresh()?;
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::RestoreFile(id, path) => {
+                    if let Err(e) =
+                        sync::checkout_file(CWD, id, &path)
+                    {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "restore file error:\n{}",
+                                e
+                            )),
+                        );
+                    }
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::DeleteTag(tag) => {
+                    if let Err(e) = sync::delete_tag(CWD, &tag) {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "delete tag error:\n{}",
+                                e
+                            )),
+                        );
+                    }
+                    self.taglist_popup.refresh()?;
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::DeleteRemoteTag(tag) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::PushTags(
+                            PushTagsTarget::DeleteTag(tag),
+                        ),
+                    );
+                }
+                Action::RemoveIndexLock => {
+                    match sync::remove_stale_index_lock(CWD) {
+                        Ok(true) => flags.insert(NeedsUpdate::ALL),
+                        Ok(false) => self.msg.show_info(
+                            strings::index_lock_kept(
+                                &self.key_config,
+                            )
+                            .as_str(),
+                        )?,
+                        Err(e) => self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "remove index.lock error:\n{}",
+                                e
+                            )),
+                        ),
+                    }
+                }
+                Action::RebuildIndex => {
+                    if let Err(e) = sync::rebuild_index_from_head(CWD)
+                    {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "rebuild index error:\n{}",
+                                e
+                            )),
+                        );
+                    }
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::UndoDiscard(_) => {
+                    if let Err(e) = sync::undo_discard(CWD) {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "undo discard error:\n{}",
+                                e
+                            )),
+                        );
+                    }
+                    flags.insert(NeedsUpdate::ALL);
+                }
+                Action::Clean(options, paths) => {
+                    if let Err(e) =
+                        sync::run_clean(CWD, options, &paths)
+                    {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "clean error:\n{}",
+                                e
+                            )),
+                        );
+                    }
+                    flags.insert(NeedsUpdate::ALL);
+                }
             },
             InternalEvent::ConfirmAction(action) => {
                 self.reset.open(action)?;
@@ -539,6 +1459,10 @@ impl App {
                 flags
                     .insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
             }
+            InternalEvent::ShowInfoMsg(msg) => {
+                self.msg.show_info(msg.as_str())?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
             InternalEvent::Update(u) => flags.insert(u),
             InternalEvent::OpenCommit => self.commit.show()?,
             InternalEvent::PopupStashing(opts) => {
@@ -551,13 +1475,255 @@ impl App {
             InternalEvent::CreateBranch => {
                 self.create_branch_popup.open()?;
             }
+            InternalEvent::CreateBranchAt(id) => {
+                self.create_branch_popup.open_at(id)?;
+            }
             InternalEvent::RenameBranch(branch_ref, cur_name) => {
                 self.rename_branch_popup
                     .open(branch_ref, cur_name)?;
             }
+            InternalEvent::EditBranchDescription(branch) => {
+                self.branch_description_popup.open(branch)?;
+            }
+            InternalEvent::RenameStash(id, cur_msg) => {
+                self.rename_stash_popup.open(id, cur_msg)?;
+            }
+            InternalEvent::RewordCommit(id) => {
+                self.reword_popup.open(id)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::StashBranch(id) => {
+                self.stash_branch_popup.open(id)?;
+            }
             InternalEvent::SelectBranch => {
                 self.select_branch_popup.open()?;
             }
+            InternalEvent::ViewObject(id) => {
+                self.object_viewer_popup.open(&id)?;
+            }
+            InternalEvent::ViewPatch(path, is_stage) => {
+                self.patch_viewer_popup.open(path, is_stage)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenSubmodules => {
+                self.submodules_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenSubmodule(workdir) => {
+                self.input.set_polling(false);
+                self.submodule_to_open = Some(workdir);
+            }
+            InternalEvent::OpenConflict(path) => {
+                if let Err(e) = self.conflict_popup.open(&path) {
+                    self.msg.show_error(&format!(
+                        "conflict error:\n{}",
+                        e
+                    ))?;
+                }
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::RunMergetool(path) => {
+                self.input.set_polling(false);
+                self.mergetool_to_run = Some(path);
+            }
+            InternalEvent::EditHunk(path, hash) => {
+                self.input.set_polling(false);
+                self.external_editor_popup.show()?;
+                self.hunk_to_edit = Some((path, hash));
+            }
+            InternalEvent::OpenBisect(good) => {
+                if let Err(e) = self.bisect_popup.open(good) {
+                    self.msg.show_error(&format!(
+                        "bisect error:\n{}",
+                        e
+                    ))?;
+                }
+                flags.insert(NeedsUpdate::ALL);
+            }
+            InternalEvent::OpenReflog => {
+                self.reflog_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenTags => {
+                self.taglist_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenWorktrees => {
+                self.worktrees_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenRemotes => {
+                self.remotes_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::CreateWorktree => {
+                self.create_worktree_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::SwitchWorktree(path) => {
+                self.switch_worktree(&path)?;
+            }
+            InternalEvent::OpenBlame(path, commit) => {
+                self.blame_popup.open(&path, commit)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenFileHistory(path) => {
+                self.file_history_popup.open(&path)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::CompareCommits(from, to) => {
+                self.compare_commits_popup.open(from, to)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::CheckoutConflict(
+                branch_ref,
+                conflicts,
+            ) => {
+                self.checkout_conflict_popup
+                    .open(branch_ref, conflicts)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenMergeBranch(branch_ref, name) => {
+                self.merge_branch_popup.open(branch_ref, name)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::MergeBranch(
+                branch_ref,
+                name,
+                strategy,
+            ) => {
+                self.merge_branch(&branch_ref, &name, strategy)?;
+                flags.insert(NeedsUpdate::ALL);
+            }
+            InternalEvent::OpenBranchUpstream(branch) => {
+                self.branch_upstream_popup.open(branch)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::SetUpstream(branch, upstream) => {
+                if let Err(e) = sync::set_upstream(
+                    CWD,
+                    &branch,
+                    upstream.as_deref(),
+                ) {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "set upstream error:\n{}",
+                            e
+                        )),
+                    );
+                }
+                self.select_branch_popup.update_branches()?;
+                flags.insert(NeedsUpdate::ALL);
+            }
+            InternalEvent::PickHunks => {
+                self.pick_hunks_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::CherryPick(ids) => {
+                self.cherry_pick(&ids)?;
+                flags.insert(NeedsUpdate::ALL);
+            }
+            InternalEvent::SquashCommits(ids) => {
+                self.rebase_popup.open_squash(&ids)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::CommitFixup(target, squash) => {
+                if let Err(e) =
+                    sync::commit_fixup(CWD, target, squash)
+                {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "fixup commit error:\n{}",
+                            e
+                        )),
+                    );
+                }
+                flags.insert(NeedsUpdate::ALL);
+            }
+            InternalEvent::OpenAutosquash => {
+                self.rebase_popup.open_autosquash()?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenRebase(base) => {
+                self.rebase_popup.open(base)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenResetCommit(id) => {
+                self.reset_commit_popup.open(id)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenRevisionFiles(id) => {
+                self.revision_files_popup.open(id)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::ExportPatches(commits) => {
+                self.patch_file_popup.open_export(commits)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenArchive(id, name) => {
+                self.archive_popup.open(id, &name)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenApplyPatch => {
+                self.patch_file_popup.open_apply()?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenClean => {
+                self.clean_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::SelectIdentity => {
+                if self.options.identities.is_empty() {
+                    self.msg.show_info(
+                        strings::identity_none_configured(
+                            &self.key_config,
+                        )
+                        .as_str(),
+                    )?;
+                } else {
+                    self.select_identity_popup.open()?;
+                }
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::SelectCoAuthor => {
+                self.select_co_author_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::AddCoAuthor(author) => {
+                self.commit.add_co_author(&author);
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::CompareUpstream(branch) => {
+                match sync::get_upstream_diff(CWD, &branch) {
+                    Ok(diff) if diff.is_merged() => {
+                        self.msg.show_info(
+                            strings::compare_upstream_merged(
+                                &self.key_config,
+                                &branch,
+                                &diff.upstream,
+                            )
+                            .as_str(),
+                        )?;
+                    }
+                    Ok(diff) => {
+                        self.compare_upstream_popup.open(diff)?;
+                    }
+                    Err(e) => self.msg.show_error(&format!(
+                        "compare with upstream failed:\n{}",
+                        e
+                    ))?,
+                }
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::SearchBranches => {
+                self.branch_search_popup.open()?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::ShowAdvice(advice) => {
+                if self.show_advice(&advice)? {
+                    flags.insert(NeedsUpdate::COMMANDS);
+                }
+            }
             InternalEvent::TabSwitch => self.set_tab(0)?,
             InternalEvent::InspectCommit(id, tags) => {
                 self.inspect_commit_popup.open(id, tags)?;
@@ -573,14 +1739,59 @@ impl App {
                 self.push_popup.push(branch, force)?;
                 flags.insert(NeedsUpdate::ALL)
             }
-            InternalEvent::Pull(branch) => {
-                self.pull_popup.fetch(branch)?;
+            InternalEvent::Pull(branch, strategy) => {
+                self.pull_popup.fetch(branch, strategy)?;
                 flags.insert(NeedsUpdate::ALL)
             }
-            InternalEvent::PushTags => {
-                self.push_tags_popup.push_tags()?;
+            InternalEvent::PushTags(target) => {
+                self.push_tags_popup.push_tags(target)?;
                 flags.insert(NeedsUpdate::ALL)
             }
+            InternalEvent::FetchAll => {
+                self.fetch_all_popup.fetch()?;
+                flags.insert(NeedsUpdate::ALL);
+            }
+            InternalEvent::OpenPullStrategy(branch) => {
+                self.pull_strategy_popup.open(branch)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenDiffOptions => {
+                self.diff_options_popup.show()?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenIgnore(path, is_folder) => {
+                self.ignore_popup.open(&path, is_folder)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenEditIgnore => {
+                if let Err(e) = self.edit_ignore_popup.open() {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "read gitignore error:\n{}",
+                            e
+                        )),
+                    );
+                }
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenPushTarget(branch) => {
+                self.push_target_popup.open(branch)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::PushTo(
+                branch,
+                remote,
+                target,
+                set_upstream,
+            ) => {
+                self.push_popup.push_to(
+                    branch,
+                    remote,
+                    target,
+                    set_upstream,
+                )?;
+                flags.insert(NeedsUpdate::ALL);
+            }
         };
 
         Ok(flags)
@@ -629,6 +1840,43 @@ impl App {
         res
     }
 
+    /// returns `true` if the advice was shown
+    fn show_advice(&mut self, advice: &Advice) -> Result<bool> {
+        // never hide an error behind advice, retry on next occurrence
+        if self.options.hide_advice
+            || self.msg.is_visible()
+            || !self.advice_shown.insert(mem::discriminant(advice))
+        {
+            return Ok(false);
+        }
+
+        let msg = match advice {
+            Advice::DetachedHead => {
+                strings::advice_detached_head(&self.key_config)
+            }
+            Advice::PushSetUpstream(branch, remote) => {
+                strings::advice_push_set_upstream(
+                    &self.key_config,
+                    branch,
+                    remote,
+                )
+            }
+            Advice::PartialClone => {
+                strings::advice_partial_clone(&self.key_config)
+            }
+            Advice::GlobalIdentity(global) => {
+                strings::advice_global_identity(
+                    &self.key_config,
+                    global,
+                )
+            }
+        };
+
+        self.msg.show_advice(&msg)?;
+
+        Ok(true)
+    }
+
     //TODO: make this automatic, i keep forgetting to add popups here
     fn any_popup_visible(&self) -> bool {
         self.commit.is_visible()
@@ -637,14 +1885,52 @@ impl App {
             || self.msg.is_visible()
             || self.stashmsg_popup.is_visible()
             || self.inspect_commit_popup.is_visible()
+            || self.revision_files_popup.is_visible()
+            || self.compare_upstream_popup.is_visible()
             || self.external_editor_popup.is_visible()
             || self.tag_commit_popup.is_visible()
             || self.create_branch_popup.is_visible()
             || self.push_popup.is_visible()
             || self.push_tags_popup.is_visible()
+            || self.push_target_popup.is_visible()
+            || self.pull_strategy_popup.is_visible()
+            || self.diff_options_popup.is_visible()
+            || self.ignore_popup.is_visible()
+            || self.edit_ignore_popup.is_visible()
             || self.pull_popup.is_visible()
+            || self.fetch_all_popup.is_visible()
+            || self.archive_popup.is_visible()
             || self.select_branch_popup.is_visible()
             || self.rename_branch_popup.is_visible()
+            || self.branch_description_popup.is_visible()
+            || self.rename_stash_popup.is_visible()
+            || self.reword_popup.is_visible()
+            || self.reset_commit_popup.is_visible()
+            || self.patch_file_popup.is_visible()
+            || self.stash_branch_popup.is_visible()
+            || self.object_viewer_popup.is_visible()
+            || self.patch_viewer_popup.is_visible()
+            || self.blame_popup.is_visible()
+            || self.file_history_popup.is_visible()
+            || self.compare_commits_popup.is_visible()
+            || self.submodules_popup.is_visible()
+            || self.worktrees_popup.is_visible()
+            || self.remotes_popup.is_visible()
+            || self.reflog_popup.is_visible()
+            || self.taglist_popup.is_visible()
+            || self.bisect_popup.is_visible()
+            || self.conflict_popup.is_visible()
+            || self.create_worktree_popup.is_visible()
+            || self.pick_hunks_popup.is_visible()
+            || self.rebase_popup.is_visible()
+            || self.branch_search_popup.is_visible()
+            || self.checkout_conflict_popup.is_visible()
+            || self.merge_branch_popup.is_visible()
+            || self.branch_upstream_popup.is_visible()
+            || self.clean_popup.is_visible()
+            || self.select_identity_popup.is_visible()
+            || self.select_co_author_popup.is_visible()
+            || self.startup_fetch.is_visible()
     }
 
     fn draw_popups<B: Backend>(
@@ -663,17 +1949,54 @@ impl App {
             .split(f.size())[0];
 
         self.commit.draw(f, size)?;
+        self.select_co_author_popup.draw(f, size)?;
         self.stashmsg_popup.draw(f, size)?;
         self.help.draw(f, size)?;
+        self.branch_search_popup.draw(f, size)?;
         self.inspect_commit_popup.draw(f, size)?;
+        self.revision_files_popup.draw(f, size)?;
+        self.reflog_popup.draw(f, size)?;
+        self.taglist_popup.draw(f, size)?;
+        self.bisect_popup.draw(f, size)?;
+        self.conflict_popup.draw(f, size)?;
+        self.blame_popup.draw(f, size)?;
+        self.file_history_popup.draw(f, size)?;
+        self.compare_commits_popup.draw(f, size)?;
+        self.compare_upstream_popup.draw(f, size)?;
+        self.diff_options_popup.draw(f, size)?;
+        self.ignore_popup.draw(f, size)?;
+        self.edit_ignore_popup.draw(f, size)?;
         self.external_editor_popup.draw(f, size)?;
         self.tag_commit_popup.draw(f, size)?;
         self.select_branch_popup.draw(f, size)?;
+        self.checkout_conflict_popup.draw(f, size)?;
+        self.merge_branch_popup.draw(f, size)?;
+        self.branch_upstream_popup.draw(f, size)?;
+        self.push_target_popup.draw(f, size)?;
+        self.pull_strategy_popup.draw(f, size)?;
+        self.clean_popup.draw(f, size)?;
+        self.pick_hunks_popup.draw(f, size)?;
+        self.rebase_popup.draw(f, size)?;
+        self.select_identity_popup.draw(f, size)?;
         self.create_branch_popup.draw(f, size)?;
         self.rename_branch_popup.draw(f, size)?;
+        self.branch_description_popup.draw(f, size)?;
+        self.rename_stash_popup.draw(f, size)?;
+        self.reword_popup.draw(f, size)?;
+        self.patch_file_popup.draw(f, size)?;
+        self.reset_commit_popup.draw(f, size)?;
+        self.stash_branch_popup.draw(f, size)?;
+        self.object_viewer_popup.draw(f, size)?;
+        self.patch_viewer_popup.draw(f, size)?;
+        self.submodules_popup.draw(f, size)?;
+        self.worktrees_popup.draw(f, size)?;
+        self.remotes_popup.draw(f, size)?;
+        self.create_worktree_popup.draw(f, size)?;
         self.push_popup.draw(f, size)?;
         self.push_tags_popup.draw(f, size)?;
         self.pull_popup.draw(f, size)?;
+        self.fetch_all_popup.draw(f, size)?;
+        self.archive_popup.draw(f, size)?;
         self.reset.draw(f, size)?;
         self.msg.draw(f, size)?;
 
@@ -0,0 +1,280 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+    InputType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{ArchiveFormat, CommitId},
+    ArchiveRequest, AsyncArchive, AsyncNotification,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::Span,
+    widgets::{Block, BorderType, Borders, Clear, Gauge},
+    Frame,
+};
+
+/// asks for the file to write the tree of a commit to and writes
+/// it in the background (like `git archive`)
+pub struct ArchiveComponent {
+    input: TextInputComponent,
+    commit: Option<CommitId>,
+    format: ArchiveFormat,
+    git_archive: AsyncArchive,
+    pending: bool,
+    progress: u8,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ArchiveComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        self.input.draw(f, rect)?;
+
+        if self.pending {
+            let area = ui::centered_rect_absolute(30, 3, f.size());
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Gauge::default()
+                    .label(format!("{}%", self.progress))
+                    .block(
+                        Block::default()
+                            .title(Span::styled(
+                                strings::ARCHIVE_POPUP_MSG,
+                                self.theme.title(true),
+                            ))
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick)
+                            .border_style(self.theme.block(true)),
+                    )
+                    .gauge_style(self.theme.push_gauge())
+                    .percent(u16::from(self.progress)),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ArchiveComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.pending {
+            out.clear();
+            out.push(CommandInfo::new(
+                strings::commands::close_msg(&self.key_config),
+                false,
+                true,
+            ));
+            return CommandBlocking::Blocking;
+        }
+
+        if self.is_visible() || force_all {
+            self.input.commands(out, force_all);
+
+            out.push(CommandInfo::new(
+                strings::commands::archive_confirm(&self.key_config),
+                !self.input.get_text().trim().is_empty(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::archive_format(
+                    &self.key_config,
+                    self.format,
+                ),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.pending {
+            return Ok(true);
+        }
+
+        if self.is_visible() {
+            if self.input.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter {
+                    self.confirm()?;
+                } else if e == self.key_config.archive_format {
+                    self.cycle_format();
+                }
+
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.pending || self.input.is_visible()
+    }
+
+    fn hide(&mut self) {
+        self.input.hide();
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.input.show()?;
+
+        Ok(())
+    }
+}
+
+impl ArchiveComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            queue: queue.clone(),
+            input: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                &strings::archive_popup_title(&key_config),
+                &strings::archive_popup_msg(&key_config),
+                false,
+            )
+            .with_input_type(InputType::Singleline),
+            commit: None,
+            format: ArchiveFormat::TarGz,
+            git_archive: AsyncArchive::new(sender),
+            pending: false,
+            progress: 0,
+            theme,
+            key_config,
+        }
+    }
+
+    /// `name` (a tag or the short id) is the suggested file name
+    pub fn open(
+        &mut self,
+        commit: CommitId,
+        name: &str,
+    ) -> Result<()> {
+        if self.pending {
+            return Ok(());
+        }
+
+        self.commit = Some(commit);
+        self.input.set_text(format!(
+            "{}{}",
+            name,
+            self.format.extension()
+        ));
+
+        self.show()
+    }
+
+    ///
+    pub const fn any_work_pending(&self) -> bool {
+        self.pending
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if self.pending && ev == AsyncNotification::Archive {
+            self.update()?;
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self) -> Result<()> {
+        self.pending = self.git_archive.is_pending()?;
+        self.progress = self.git_archive.progress()?;
+
+        if !self.pending {
+            if let Some((count, err)) =
+                self.git_archive.last_result()?
+            {
+                let event = if err.is_empty() {
+                    InternalEvent::ShowInfoMsg(strings::archive_msg(
+                        &self.key_config,
+                        count,
+                    ))
+                } else {
+                    InternalEvent::ShowErrorMsg(format!(
+                        "archive failed:\n{}",
+                        err
+                    ))
+                };
+                self.queue.borrow_mut().push_back(event);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// keeps the typed name, only the extension follows the format
+    fn cycle_format(&mut self) {
+        let next = match self.format {
+            ArchiveFormat::TarGz => ArchiveFormat::Zip,
+            ArchiveFormat::Zip => ArchiveFormat::Tar,
+            ArchiveFormat::Tar => ArchiveFormat::TarGz,
+        };
+
+        let text = self.input.get_text();
+        if let Some(name) = text.strip_suffix(self.format.extension())
+        {
+            self.input.set_text(format!(
+                "{}{}",
+                name,
+                next.extension()
+            ));
+        }
+
+        self.format = next;
+    }
+
+    fn confirm(&mut self) -> Result<()> {
+        let path = self.input.get_text().trim().to_string();
+
+        if let (Some(commit), false) = (self.commit, path.is_empty())
+        {
+            self.git_archive.request(ArchiveRequest {
+                commit,
+                format: self.format,
+                output_path: path,
+            })?;
+            self.pending = true;
+            self.progress = 0;
+            self.hide();
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,367 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings, try_or_popup,
+    ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, BisectState, BisectStep, CommitId, CommitInfo},
+    CWD,
+};
+use crossterm::event::Event;
+use std::cell::Cell;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const SUBJECT_LIMIT: usize = 100;
+
+/// guides through bisecting: the commits that may have introduced
+/// the bug with the checked out one marked, classified step by step
+pub struct BisectComponent {
+    state: Option<BisectState>,
+    commits: Vec<CommitInfo>,
+    head: Option<CommitId>,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for BisectComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(80, 60);
+            const MIN_SIZE: Size = Size::new(60, 12);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            // first line is the step
+            let height = (area.height as usize).saturating_sub(4);
+            self.scroll_top.set(ui::calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                self.selection,
+            ));
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height)).block(
+                    Block::default()
+                        .title(Span::styled(
+                            strings::bisect_title(&self.key_config),
+                            self.theme.title(true),
+                        ))
+                        .border_type(BorderType::Thick)
+                        .borders(Borders::ALL),
+                ),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for BisectComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            let testing = self.testing().is_some();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::bisect_good(&self.key_config),
+                testing,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::bisect_bad(&self.key_config),
+                testing,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::bisect_skip(&self.key_config),
+                testing,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::bisect_inspect_commit(
+                    &self.key_config,
+                ),
+                !self.commits.is_empty(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::bisect_reset(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = (self.selection + 1)
+                        .min(self.commits.len().saturating_sub(1));
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.bisect_good {
+                    self.classify(sync::bisect_good);
+                } else if e == self.key_config.bisect_bad {
+                    self.classify(sync::bisect_bad);
+                } else if e == self.key_config.bisect_skip {
+                    self.classify(sync::bisect_skip);
+                } else if e == self.key_config.bisect_reset {
+                    try_or_popup!(
+                        self,
+                        "bisect reset error:",
+                        self.reset()
+                    );
+                } else if e == self.key_config.enter {
+                    if let Some(info) =
+                        self.commits.get(self.selection)
+                    {
+                        let id = info.id;
+                        self.hide();
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::InspectCommit(id, None),
+                        );
+                    }
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl BisectComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            state: None,
+            commits: Vec::new(),
+            head: None,
+            selection: 0,
+            scroll_top: Cell::new(0),
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// shows the running bisect, otherwise starts one between the
+    /// `good` commit and `HEAD`
+    pub fn open(&mut self, good: CommitId) -> Result<()> {
+        if sync::bisect_state(CWD)?.is_none() {
+            let head = sync::get_head(CWD)?;
+            sync::bisect_start(CWD, head, good)?;
+        }
+
+        self.load()?;
+        self.selection = 0;
+        self.scroll_top.set(0);
+
+        self.show()
+    }
+
+    fn load(&mut self) -> Result<()> {
+        self.state = sync::bisect_state(CWD)?;
+        self.head = sync::get_head(CWD).ok();
+        self.commits = match &self.state {
+            Some(state) => sync::get_commits_info(
+                CWD,
+                &state.remaining,
+                SUBJECT_LIMIT,
+            )?,
+            None => Vec::new(),
+        };
+        self.selection =
+            self.selection.min(self.commits.len().saturating_sub(1));
+
+        Ok(())
+    }
+
+    /// the checked out commit waiting to be classified
+    fn testing(&self) -> Option<CommitId> {
+        self.state.as_ref().and_then(|state| match state.step {
+            BisectStep::Test(id) => Some(id),
+            _ => None,
+        })
+    }
+
+    fn classify<E: std::fmt::Display>(
+        &mut self,
+        mark: impl FnOnce(&str, CommitId) -> Result<BisectStep, E>,
+    ) {
+        if let Some(id) = self.testing() {
+            if let Err(e) = mark(CWD, id) {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "bisect error:\n{}",
+                        e
+                    )),
+                );
+            }
+
+            if let Err(e) = self.load() {
+                log::error!("bisect: {}", e);
+            }
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+        }
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        sync::bisect_reset(CWD)?;
+
+        self.state = None;
+        self.commits.clear();
+        self.hide();
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+
+        Ok(())
+    }
+
+    fn get_text(&self, height: usize) -> Text<'_> {
+        let state = if let Some(state) = &self.state {
+            state
+        } else {
+            return Text::default();
+        };
+
+        let mut txt = vec![
+            Spans::from(Span::styled(
+                strings::bisect_step(
+                    &self.key_config,
+                    state.step,
+                    state.remaining.len(),
+                ),
+                self.theme.text(true, false),
+            )),
+            Spans::default(),
+        ];
+
+        txt.extend(
+            self.commits
+                .iter()
+                .enumerate()
+                .skip(self.scroll_top.get())
+                .take(height)
+                .map(|(idx, info)| {
+                    self.commit_line(
+                        state,
+                        info,
+                        idx == self.selection,
+                    )
+                }),
+        );
+
+        Text::from(txt)
+    }
+
+    fn commit_line(
+        &self,
+        state: &BisectState,
+        info: &CommitInfo,
+        selected: bool,
+    ) -> Spans<'_> {
+        let mark = if info.id == state.bad {
+            "bad "
+        } else if state.skipped.contains(&info.id) {
+            "skip"
+        } else {
+            "    "
+        };
+
+        Spans::from(vec![
+            Span::styled(
+                if Some(info.id) == self.head {
+                    "> "
+                } else {
+                    "  "
+                },
+                self.theme.text(true, selected),
+            ),
+            Span::styled(
+                format!("{} ", info.id.get_short_string()),
+                self.theme.commit_hash(selected),
+            ),
+            Span::styled(
+                format!("{} ", mark),
+                self.theme.text_danger(),
+            ),
+            Span::styled(
+                info.message.clone(),
+                self.theme.text(true, selected),
+            ),
+        ])
+    }
+}
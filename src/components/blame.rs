@@ -0,0 +1,341 @@
+use super::{
+    utils::time_to_string, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId, FileBlame},
+    CWD,
+};
+use crossterm::event::Event;
+use std::cell::Cell;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const AUTHOR_WIDTH: usize = 14;
+
+/// what files of a list or diff are blamed as of
+#[derive(Clone, Copy)]
+pub enum BlameRevision {
+    ///
+    Head,
+    ///
+    Commit(CommitId),
+}
+
+impl BlameRevision {
+    ///
+    pub const fn commit(self) -> Option<CommitId> {
+        match self {
+            Self::Head => None,
+            Self::Commit(id) => Some(id),
+        }
+    }
+}
+
+/// the commit of each line of a file, the date colored by its age.
+/// the commit of the selected line can be inspected
+pub struct BlameComponent {
+    blame: Option<FileBlame>,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    current_height: Cell<usize>,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for BlameComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(90, 90);
+            const MIN_SIZE: Size = Size::new(60, 20);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height = (area.height as usize).saturating_sub(2);
+            self.current_height.set(height);
+            self.scroll_top.set(ui::calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                self.selection,
+            ));
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height)).block(
+                    Block::default()
+                        .title(Span::styled(
+                            self.get_title(),
+                            self.theme.title(true),
+                        ))
+                        .border_type(BorderType::Thick)
+                        .borders(Borders::ALL),
+                ),
+                area,
+            );
+
+            ui::draw_scrollbar(
+                f,
+                area,
+                &self.theme,
+                self.lines_count(),
+                self.scroll_top.get(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for BlameComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::blame_inspect_commit(
+                    &self.key_config,
+                ),
+                self.selected_commit().is_some(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                let last = self.lines_count().saturating_sub(1);
+                let page = self.current_height.get().max(1);
+
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = (self.selection + 1).min(last);
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.page_down {
+                    self.selection =
+                        self.selection.saturating_add(page).min(last);
+                } else if e == self.key_config.page_up {
+                    self.selection =
+                        self.selection.saturating_sub(page);
+                } else if e == self.key_config.home
+                    || e == self.key_config.shift_up
+                {
+                    self.selection = 0;
+                } else if e == self.key_config.end
+                    || e == self.key_config.shift_down
+                {
+                    self.selection = last;
+                } else if e == self.key_config.enter {
+                    if let Some(id) = self.selected_commit() {
+                        self.hide();
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::InspectCommit(id, None),
+                        );
+                    }
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl BlameComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            blame: None,
+            selection: 0,
+            scroll_top: Cell::new(0),
+            current_height: Cell::new(0),
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// blames `path` as of `commit` (`HEAD` if `None`)
+    pub fn open(
+        &mut self,
+        path: &str,
+        commit: Option<CommitId>,
+    ) -> Result<()> {
+        match sync::blame(CWD, path, commit) {
+            Ok(blame) => {
+                self.blame = Some(blame);
+                self.selection = 0;
+                self.scroll_top.set(0);
+                self.show()?;
+            }
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "blame error:\n{}",
+                        e
+                    )),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn lines_count(&self) -> usize {
+        self.blame.as_ref().map_or(0, |blame| blame.lines.len())
+    }
+
+    fn selected_commit(&self) -> Option<CommitId> {
+        self.blame
+            .as_ref()
+            .and_then(|blame| blame.lines.get(self.selection))
+            .map(|line| line.commit_id)
+    }
+
+    fn get_title(&self) -> String {
+        self.blame.as_ref().map_or_else(String::new, |blame| {
+            strings::blame_title(
+                &self.key_config,
+                &blame.path,
+                &blame.commit_id.get_short_string(),
+            )
+        })
+    }
+
+    fn get_text(&self, height: usize) -> Text<'_> {
+        let blame = if let Some(blame) = &self.blame {
+            blame
+        } else {
+            return Text::default();
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        let number_width = blame.lines.len().to_string().len();
+
+        let txt: Vec<Spans> = blame
+            .lines
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|(idx, line)| {
+                let selected = idx == self.selection;
+
+                Spans::from(vec![
+                    Span::styled(
+                        format!(
+                            "{} ",
+                            line.commit_id.get_short_string()
+                        ),
+                        self.theme.commit_hash(selected),
+                    ),
+                    Span::styled(
+                        format!("{} ", author_column(&line.author)),
+                        self.theme.commit_author(selected),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{} ",
+                            time_to_string(line.time, true)
+                        ),
+                        self.theme
+                            .blame_age(now - line.time, selected),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{:>w$} │ ",
+                            idx + 1,
+                            w = number_width
+                        ),
+                        self.theme.text(false, selected),
+                    ),
+                    Span::styled(
+                        line.content.replace('\t', "    "),
+                        self.theme.text(true, selected),
+                    ),
+                ])
+            })
+            .collect();
+
+        Text::from(txt)
+    }
+}
+
+/// cut or padded to `AUTHOR_WIDTH` columns
+fn author_column(author: &str) -> String {
+    let mut width = 0;
+    let column: String = author
+        .chars()
+        .take_while(|c| {
+            width += c.width().unwrap_or_default();
+            width <= AUTHOR_WIDTH
+        })
+        .collect();
+
+    let pad = AUTHOR_WIDTH.saturating_sub(column.width());
+    format!("{}{:w$}", column, "", w = pad)
+}
@@ -0,0 +1,152 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{sync, CWD};
+use crossterm::event::Event;
+use tui::{backend::Backend, layout::Rect, Frame};
+
+/// edits `branch.<name>.description` of a local branch
+pub struct BranchDescriptionComponent {
+    input: TextInputComponent,
+    branch: Option<String>,
+    queue: Queue,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for BranchDescriptionComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        self.input.draw(f, rect)?;
+
+        Ok(())
+    }
+}
+
+impl Component for BranchDescriptionComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            self.input.commands(out, force_all);
+
+            out.push(CommandInfo::new(
+                strings::commands::branch_description_confirm_msg(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.is_visible() {
+            if self.input.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter {
+                    self.save();
+                }
+
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.input.is_visible()
+    }
+
+    fn hide(&mut self) {
+        self.input.hide();
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.input.show()?;
+
+        Ok(())
+    }
+}
+
+impl BranchDescriptionComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            queue,
+            input: TextInputComponent::new(
+                theme,
+                key_config.clone(),
+                &strings::branch_description_popup_title(&key_config),
+                &strings::branch_description_popup_msg(&key_config),
+                true,
+            ),
+            branch: None,
+            key_config,
+        }
+    }
+
+    /// `branch` is the name of a local branch
+    pub fn open(&mut self, branch: String) -> Result<()> {
+        let description =
+            sync::get_description(CWD, &branch)?.unwrap_or_default();
+
+        self.branch = Some(branch);
+        self.input.set_text(description);
+        self.show()?;
+
+        Ok(())
+    }
+
+    /// an empty description removes it
+    fn save(&mut self) {
+        if let Some(branch) = &self.branch {
+            match sync::set_description(
+                CWD,
+                branch,
+                self.input.get_text(),
+            ) {
+                Ok(()) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::Update(NeedsUpdate::ALL),
+                    );
+                    self.hide();
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::SelectBranch);
+                }
+                Err(e) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "branch description error:\n{}",
+                            e,
+                        )),
+                    );
+                }
+            }
+        }
+
+        self.input.clear();
+    }
+}
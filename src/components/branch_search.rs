@@ -0,0 +1,495 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+    InputType, ScrollType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::{self, calc_scroll_top, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{
+        branch::{
+            checkout::checkout_conflicts, checkout_remote_branch,
+        },
+        checkout_branch, BranchInfo, BranchSearchMatch,
+        BranchSearchResult,
+    },
+    AsyncBranchSearch, AsyncNotification, BranchSearchRequest, CWD,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use std::cell::Cell;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const SEARCH_LIMIT: usize = 500;
+
+/// search for commits across all branches
+pub struct BranchSearchComponent {
+    input: TextInputComponent,
+    git_search: AsyncBranchSearch,
+    result: Option<BranchSearchResult>,
+    visible: bool,
+    selection: usize,
+    branch_selection: usize,
+    scroll_top: Cell<usize>,
+    current_height: Cell<usize>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for BranchSearchComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(80, 60);
+            const MIN_SIZE: Size = Size::new(60, 20);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height_in_lines =
+                (area.height as usize).saturating_sub(2);
+
+            self.scroll_top.set(calc_scroll_top(
+                self.scroll_top.get(),
+                height_in_lines,
+                self.selection,
+            ));
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(
+                    self.get_text(
+                        height_in_lines,
+                        area.width as usize,
+                    ),
+                )
+                .block(
+                    Block::default()
+                        .title(Span::styled(
+                            self.get_title(),
+                            self.theme.title(true),
+                        ))
+                        .border_type(BorderType::Thick)
+                        .borders(Borders::ALL),
+                )
+                .alignment(Alignment::Left),
+                area,
+            );
+
+            ui::draw_scrollbar(
+                f,
+                area,
+                &self.theme,
+                self.matches().len(),
+                self.scroll_top.get(),
+            );
+
+            self.current_height.set(height_in_lines);
+        }
+
+        self.input.draw(f, rect)?;
+
+        Ok(())
+    }
+}
+
+impl Component for BranchSearchComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.input.is_visible() {
+            self.input.commands(out, force_all);
+
+            out.push(CommandInfo::new(
+                strings::commands::branch_search_start(
+                    &self.key_config,
+                ),
+                !self.input.get_text().is_empty(),
+                true,
+            ));
+        } else if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::branch_search_cycle_branch(
+                    &self.key_config,
+                ),
+                self.selected_match()
+                    .map_or(false, |m| m.branches.len() > 1),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::branch_search_inspect(
+                    &self.key_config,
+                ),
+                self.selected_match().is_some(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::branch_search_checkout(
+                    &self.key_config,
+                ),
+                self.selected_branch().is_some(),
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.input.is_visible() {
+            if self.input.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter
+                    && !self.input.get_text().is_empty()
+                {
+                    self.start_search()?;
+                }
+
+                return Ok(true);
+            }
+        } else if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.move_selection(ScrollType::Up);
+                } else if e == self.key_config.move_up {
+                    self.move_selection(ScrollType::Down);
+                } else if e == self.key_config.page_down {
+                    self.move_selection(ScrollType::PageDown);
+                } else if e == self.key_config.page_up {
+                    self.move_selection(ScrollType::PageUp);
+                } else if e == self.key_config.move_right {
+                    self.cycle_branch(true);
+                } else if e == self.key_config.move_left {
+                    self.cycle_branch(false);
+                } else if e == self.key_config.enter {
+                    if let Some(m) = self.selected_match() {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::InspectCommit(
+                                m.commit.id,
+                                None,
+                            ),
+                        );
+                    }
+                } else if e == self.key_config.select_branch {
+                    self.checkout_selected_branch();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible || self.input.is_visible()
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+        self.input.hide();
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.input.show()?;
+
+        Ok(())
+    }
+}
+
+impl BranchSearchComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            input: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                &strings::branch_search_popup_title(&key_config),
+                &strings::branch_search_popup_msg(&key_config),
+                false,
+            )
+            .with_input_type(InputType::Singleline),
+            git_search: AsyncBranchSearch::new(sender),
+            result: None,
+            visible: false,
+            selection: 0,
+            branch_selection: 0,
+            scroll_top: Cell::new(0),
+            current_height: Cell::new(0),
+            queue: queue.clone(),
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self) -> Result<()> {
+        self.visible = false;
+        self.show()
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_search.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if ev == AsyncNotification::BranchSearch {
+            if let Some(res) = self.git_search.take_last_result()? {
+                match res {
+                    Ok(res) => self.result = Some(res),
+                    Err(e) => {
+                        self.hide();
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowErrorMsg(format!(
+                                "branch search error:\n{}",
+                                e
+                            )),
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn start_search(&mut self) -> Result<()> {
+        self.git_search.request(BranchSearchRequest {
+            query: self.input.get_text().clone(),
+            limit: SEARCH_LIMIT,
+        })?;
+
+        self.result = None;
+        self.selection = 0;
+        self.branch_selection = 0;
+        self.scroll_top.set(0);
+
+        self.input.hide();
+        self.visible = true;
+
+        Ok(())
+    }
+
+    fn matches(&self) -> &[BranchSearchMatch] {
+        self.result
+            .as_ref()
+            .map_or(&[], |res| res.matches.as_slice())
+    }
+
+    fn selected_match(&self) -> Option<&BranchSearchMatch> {
+        self.matches().get(self.selection)
+    }
+
+    fn selected_branch(&self) -> Option<&BranchInfo> {
+        let res = self.result.as_ref()?;
+        let idx = self
+            .selected_match()?
+            .branches
+            .get(self.branch_selection)?;
+        res.branches.get(*idx)
+    }
+
+    fn move_selection(&mut self, scroll: ScrollType) {
+        let new_selection = match scroll {
+            ScrollType::Up => self.selection.saturating_add(1),
+            ScrollType::Down => self.selection.saturating_sub(1),
+            ScrollType::PageDown => self
+                .selection
+                .saturating_add(self.current_height.get()),
+            ScrollType::PageUp => self
+                .selection
+                .saturating_sub(self.current_height.get()),
+            _ => self.selection,
+        };
+
+        self.selection =
+            new_selection.min(self.matches().len().saturating_sub(1));
+        self.branch_selection = 0;
+    }
+
+    fn cycle_branch(&mut self, forward: bool) {
+        let count =
+            self.selected_match().map_or(0, |m| m.branches.len());
+
+        if count > 0 {
+            self.branch_selection = if forward {
+                (self.branch_selection + 1) % count
+            } else {
+                (self.branch_selection + count - 1) % count
+            };
+        }
+    }
+
+    fn checkout_selected_branch(&mut self) {
+        if let Some(branch) = self.selected_branch() {
+            let res = if branch.local_details().is_some() {
+                match checkout_conflicts(CWD, &branch.reference) {
+                    Ok(conflicts) if !conflicts.is_empty() => {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::CheckoutConflict(
+                                branch.reference.clone(),
+                                conflicts,
+                            ),
+                        );
+                        return;
+                    }
+                    _ => checkout_branch(CWD, &branch.reference),
+                }
+            } else {
+                checkout_remote_branch(CWD, branch)
+            };
+
+            match res {
+                Ok(()) => {
+                    self.hide();
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::Update(NeedsUpdate::ALL),
+                    );
+                }
+                Err(e) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "checkout error:\n{}",
+                            e
+                        )),
+                    );
+                }
+            }
+        }
+    }
+
+    fn get_title(&self) -> String {
+        if self.git_search.is_pending() {
+            strings::branch_search_title_pending(
+                &self.key_config,
+                self.git_search.progress(),
+            )
+        } else {
+            self.result.as_ref().map_or_else(String::new, |res| {
+                strings::branch_search_title(
+                    &self.key_config,
+                    &res.query,
+                    res.matches.len(),
+                    res.limit_reached,
+                )
+            })
+        }
+    }
+
+    fn get_text(&self, height: usize, width: usize) -> Text<'_> {
+        let mut txt = Vec::new();
+
+        let res = if let Some(res) = &self.result {
+            res
+        } else {
+            return Text::from(txt);
+        };
+
+        for (i, m) in res
+            .matches
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+        {
+            let selected = i == self.selection;
+
+            let mut spans = vec![
+                Span::styled(
+                    format!("{} ", m.commit.id.get_short_string()),
+                    self.theme.commit_hash(selected),
+                ),
+                Span::styled(
+                    format!("{} ", m.commit.author),
+                    self.theme.commit_author(selected),
+                ),
+            ];
+
+            for (branch_idx, idx) in m.branches.iter().enumerate() {
+                let name = res
+                    .branches
+                    .get(*idx)
+                    .map_or("", |b| b.name.as_str());
+                let highlight =
+                    selected && branch_idx == self.branch_selection;
+
+                spans.push(Span::styled(
+                    format!("[{}]", name),
+                    self.theme.branch(highlight, false),
+                ));
+                spans.push(Span::raw(" "));
+            }
+
+            let used: usize =
+                spans.iter().map(|s| s.content.len()).sum();
+            let msg: String = m
+                .commit
+                .message
+                .chars()
+                .take(width.saturating_sub(used + 2))
+                .collect();
+            spans.push(Span::styled(
+                msg,
+                self.theme.text(true, selected),
+            ));
+
+            txt.push(Spans::from(spans));
+        }
+
+        Text::from(txt)
+    }
+}
@@ -0,0 +1,227 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{sync, CWD};
+use crossterm::event::Event;
+use std::cell::Cell;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// picks the remote tracking branch a local branch gets as upstream
+pub struct BranchUpstreamComponent {
+    branch: String,
+    /// names like `origin/master`
+    remote_branches: Vec<String>,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for BranchUpstreamComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(50, 40);
+            const MIN_SIZE: Size = Size::new(40, 10);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height = (area.height as usize).saturating_sub(2);
+            self.scroll_top.set(ui::calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                self.selection,
+            ));
+
+            let txt: Vec<Spans> = self
+                .remote_branches
+                .iter()
+                .enumerate()
+                .skip(self.scroll_top.get())
+                .take(height)
+                .map(|(i, name)| {
+                    Spans::from(Span::styled(
+                        name.as_str(),
+                        self.theme.branch(i == self.selection, false),
+                    ))
+                })
+                .collect();
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(Text::from(txt)).block(
+                    Block::default()
+                        .title(Span::styled(
+                            strings::branch_upstream_title(
+                                &self.key_config,
+                                &self.branch,
+                            ),
+                            self.theme.title(true),
+                        ))
+                        .border_type(BorderType::Thick)
+                        .borders(Borders::ALL),
+                ),
+                area,
+            );
+
+            ui::draw_scrollbar(
+                f,
+                area,
+                &self.theme,
+                self.remote_branches.len(),
+                self.scroll_top.get(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for BranchUpstreamComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::branch_upstream_confirm(
+                    &self.key_config,
+                ),
+                !self.remote_branches.is_empty(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = (self.selection + 1).min(
+                        self.remote_branches.len().saturating_sub(1),
+                    );
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.enter {
+                    self.confirm();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl BranchUpstreamComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            branch: String::new(),
+            remote_branches: Vec::new(),
+            selection: 0,
+            scroll_top: Cell::new(0),
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// preselects the remote branch of the same name
+    pub fn open(&mut self, branch: String) -> Result<()> {
+        self.remote_branches = sync::get_branches_info(CWD, false)?
+            .into_iter()
+            .map(|b| b.name)
+            .filter(|name| !name.ends_with("/HEAD"))
+            .collect();
+
+        let suffix = format!("/{}", branch);
+        self.selection = self
+            .remote_branches
+            .iter()
+            .position(|name| name.ends_with(&suffix))
+            .unwrap_or_default();
+        self.scroll_top.set(0);
+        self.branch = branch;
+
+        self.show()
+    }
+
+    fn confirm(&mut self) {
+        if let Some(upstream) =
+            self.remote_branches.get(self.selection)
+        {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::SetUpstream(
+                    self.branch.clone(),
+                    Some(upstream.clone()),
+                ),
+            );
+        }
+
+        self.hide();
+    }
+}
@@ -1,4 +1,5 @@
 use super::{
+    utils::{fuzzy::fuzzy_score, time_to_relative_string},
     visibility_blocking, CommandBlocking, CommandInfo, Component,
     DrawableComponent,
 };
@@ -11,27 +12,62 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::{
+    cached,
     sync::{
-        branch::checkout_remote_branch, checkout_branch,
-        get_branches_info, BranchInfo,
+        branch::{
+            checkout::checkout_conflicts, checkout_remote_branch,
+        },
+        branches_merged_into, checkout_branch, get_description,
+        get_favorite_branches, toggle_favorite_branch, BranchDetails,
+        BranchInfo, CommitId, RebaseState,
     },
-    CWD,
+    AsyncBranches, AsyncNotification, AsyncRebase, CWD,
+};
+use chrono::Utc;
+use crossbeam_channel::Sender;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use std::{
+    cell::Cell, cmp::Reverse, collections::HashMap, convert::TryInto,
 };
-use crossterm::event::Event;
-use std::{cell::Cell, convert::TryInto};
 use tui::{
     backend::Backend,
     layout::{Alignment, Rect},
     text::{Span, Spans, Text},
-    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 use ui::style::SharedTheme;
 use unicode_truncate::UnicodeTruncateStr;
 
+/// "↑12 ↓3 "
+const COMPARE_LENGTH: usize = 10;
+/// "11mo ago "
+const AGE_LENGTH: usize = 9;
+
 ///
 pub struct BranchListComponent {
+    /// the shown ones: sorted and, while finding, only the matches
     branches: Vec<BranchInfo>,
+    /// sorted by name
+    all_branches: Vec<BranchInfo>,
+    favorites: Vec<String>,
+    sort_by_date: bool,
+    /// query typed since the find key was pressed
+    find: Option<String>,
+    /// selected when the find started, selected again on cancel
+    find_origin: Option<String>,
+    /// references of the local branches marked to be deleted
+    /// together
+    marks: Vec<String>,
+    /// of the selected branch, shown below the list
+    description: Option<String>,
+    git_refs: cached::Refs,
+    /// the upstream comparison takes a while, the cached refs are
+    /// shown until it is done
+    git_branches: AsyncBranches,
+    git_rebase: AsyncRebase,
+    /// top commit of the branch the running rebase is onto
+    rebase_onto: Option<CommitId>,
     local: bool,
     visible: bool,
     selection: u16,
@@ -61,6 +97,9 @@ impl DrawableComponent for BranchListComponent {
                 ui::rect_inside(MIN_SIZE, f.size().into(), area);
             let area = area.intersection(rect);
 
+            f.render_widget(Clear, area);
+            let area = self.draw_description(f, area);
+
             let height_in_lines =
                 (area.height as usize).saturating_sub(2);
 
@@ -70,7 +109,6 @@ impl DrawableComponent for BranchListComponent {
                 self.selection as usize,
             ));
 
-            f.render_widget(Clear, area);
             f.render_widget(
                 Paragraph::new(self.get_text(
                     &self.theme,
@@ -79,7 +117,7 @@ impl DrawableComponent for BranchListComponent {
                 ))
                 .block(
                     Block::default()
-                        .title(strings::title_branches(self.local))
+                        .title(self.get_title())
                         .border_type(BorderType::Thick)
                         .borders(Borders::ALL),
                 )
@@ -117,6 +155,11 @@ impl Component for BranchListComponent {
                 true,
             ));
 
+            if self.find.is_some() {
+                self.add_find_commands(out);
+                return visibility_blocking(self);
+            }
+
             out.push(CommandInfo::new(
                 strings::commands::close_popup(&self.key_config),
                 true,
@@ -143,8 +186,9 @@ impl Component for BranchListComponent {
                 strings::commands::delete_branch_popup(
                     &self.key_config,
                 ),
-                !self.selection_is_cur_branch(),
-                self.local,
+                !self.selection_is_cur_branch()
+                    && !self.selection_is_remote_head(),
+                true,
             ));
 
             out.push(CommandInfo::new(
@@ -155,6 +199,34 @@ impl Component for BranchListComponent {
                 self.local,
             ));
 
+            self.add_config_commands(out);
+
+            out.push(CommandInfo::new(
+                strings::commands::merge_branch_popup(
+                    &self.key_config,
+                ),
+                !self.branches.is_empty()
+                    && !self.selection_is_cur_branch(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::rebase_branch_popup(
+                    &self.key_config,
+                ),
+                self.can_rebase(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::favorite_branch_popup(
+                    &self.key_config,
+                    self.selection_is_favorite(),
+                ),
+                !self.branches.is_empty(),
+                true,
+            ));
+
             out.push(CommandInfo::new(
                 strings::commands::toggle_branch_popup(
                     &self.key_config,
@@ -163,6 +235,9 @@ impl Component for BranchListComponent {
                 true,
                 true,
             ));
+
+            self.add_mark_commands(out);
+            self.add_sort_find_commands(out);
         }
         visibility_blocking(self)
     }
@@ -170,6 +245,13 @@ impl Component for BranchListComponent {
     fn event(&mut self, ev: Event) -> Result<bool> {
         if self.visible {
             if let Event::Key(e) = ev {
+                if self.sort_find_event(e)?
+                    || self.mark_event(e)
+                    || self.config_event(e)
+                {
+                    return Ok(true);
+                }
+
                 if e == self.key_config.exit_popup {
                     self.hide()
                 } else if e == self.key_config.move_down {
@@ -204,21 +286,40 @@ impl Component for BranchListComponent {
                     self.update_branches()?;
                 } else if e == self.key_config.delete_branch
                     && !self.selection_is_cur_branch()
+                    && !self.selection_is_remote_head()
                 {
-                    self.queue.borrow_mut().push_back(
-                        InternalEvent::ConfirmAction(
-                            Action::DeleteBranch(
-                                self.branches
-                                    [self.selection as usize]
-                                    .reference
-                                    .clone(),
-                            ),
-                        ),
-                    );
+                    let local = self.local;
+                    self.queue_for_selected(|branch| {
+                        let reference = branch.reference.clone();
+                        InternalEvent::ConfirmAction(if local {
+                            Action::DeleteBranch(reference)
+                        } else {
+                            Action::DeleteRemoteBranch(reference)
+                        })
+                    });
                 } else if e == self.key_config.toggle_remote_branches
                 {
                     self.local = !self.local;
                     self.update_branches()?;
+                } else if e == self.key_config.merge_branch
+                    && !self.selection_is_cur_branch()
+                {
+                    self.queue_for_selected(|branch| {
+                        InternalEvent::OpenMergeBranch(
+                            branch.reference.clone(),
+                            branch.name.clone(),
+                        )
+                    });
+                } else if e == self.key_config.rebase_branch
+                    && self.can_rebase()
+                {
+                    self.rebase_onto_selected()?;
+                } else if e == self.key_config.favorite_branch {
+                    try_or_popup!(
+                        self,
+                        "favorite branch error:",
+                        self.toggle_favorite()
+                    );
                 }
             }
 
@@ -246,11 +347,23 @@ impl Component for BranchListComponent {
 impl BranchListComponent {
     pub fn new(
         queue: Queue,
+        sender: &Sender<AsyncNotification>,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
     ) -> Self {
         Self {
             branches: Vec::new(),
+            all_branches: Vec::new(),
+            favorites: Vec::new(),
+            sort_by_date: false,
+            find: None,
+            find_origin: None,
+            marks: Vec::new(),
+            description: None,
+            git_refs: cached::Refs::new(CWD),
+            git_branches: AsyncBranches::new(sender),
+            git_rebase: AsyncRebase::new(sender),
+            rebase_onto: None,
             local: true,
             visible: false,
             selection: 0,
@@ -270,10 +383,499 @@ impl BranchListComponent {
         Ok(())
     }
 
-    /// fetch list of branches
+    /// fetch list of branches, favorites first. the upstream
+    /// comparisons of the last list are kept until the new ones
+    /// are read
     pub fn update_branches(&mut self) -> Result<()> {
-        self.branches = get_branches_info(CWD, self.local)?;
-        self.set_selection(self.selection)?;
+        self.favorites = get_favorite_branches(CWD)?;
+
+        let compares: HashMap<String, _> = self
+            .all_branches
+            .iter()
+            .filter_map(|b| {
+                b.local_details()?
+                    .upstream_compare
+                    .map(|c| (b.reference.clone(), c))
+            })
+            .collect();
+
+        self.all_branches =
+            self.git_refs.lookup()?.branches(self.local).to_vec();
+        for branch in &mut self.all_branches {
+            if let BranchDetails::Local(details) = &mut branch.details
+            {
+                details.upstream_compare =
+                    compares.get(&branch.reference).copied();
+            }
+        }
+
+        let all_branches = &self.all_branches;
+        self.marks.retain(|reference| {
+            all_branches.iter().any(|b| &b.reference == reference)
+        });
+
+        self.git_branches.request(self.local)?;
+
+        self.apply_branches()
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_branches.is_pending() || self.rebase_pending()
+    }
+
+    /// the branch list follows the deletion of a remote branch
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if self.visible
+            && !self.local
+            && ev == AsyncNotification::Push
+        {
+            self.update_branches()?;
+        } else if ev == AsyncNotification::Branches {
+            if let Some((local, branches)) =
+                self.git_branches.take_last_result()?
+            {
+                if local == self.local {
+                    self.all_branches = branches;
+                    self.apply_branches()?;
+                } else if self.visible {
+                    // switched while reading the other ones
+                    self.git_branches.request(self.local)?;
+                }
+            }
+        } else if ev == AsyncNotification::Rebase {
+            if let Some(res) = self.git_rebase.take_last_result()? {
+                self.rebase_done(res)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    pub fn rebase_pending(&self) -> bool {
+        self.git_rebase.is_pending()
+    }
+
+    fn can_rebase(&self) -> bool {
+        !self.branches.is_empty()
+            && !self.selection_is_cur_branch()
+            && !self.rebase_pending()
+    }
+
+    fn rebase_onto_selected(&mut self) -> Result<()> {
+        if let Some(branch) =
+            self.branches.get(self.selection as usize)
+        {
+            self.rebase_onto = Some(branch.top_commit);
+            self.git_rebase.request(&branch.reference)?;
+        }
+
+        Ok(())
+    }
+
+    /// a conflict is resolved in the rebase popup (or continued
+    /// and aborted like any stopped rebase)
+    fn rebase_done(
+        &mut self,
+        res: std::result::Result<RebaseState, String>,
+    ) -> Result<()> {
+        let onto = self.rebase_onto.take();
+
+        match res {
+            Ok(RebaseState::Finished) => {
+                if self.visible {
+                    self.update_branches()?;
+                }
+            }
+            Ok(RebaseState::Conflict(_)) => {
+                self.hide();
+                if let Some(onto) = onto {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenRebase(onto));
+                }
+            }
+            Err(e) => self.queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(format!(
+                    "rebase error:\n{}",
+                    e
+                )),
+            ),
+        }
+
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+
+        Ok(())
+    }
+
+    /// sorts `all_branches` into `branches` (favorites first),
+    /// while finding only the matches and with the best one
+    /// selected, otherwise the selected branch stays selected
+    fn apply_branches(&mut self) -> Result<()> {
+        let selected = self
+            .branches
+            .get(self.selection as usize)
+            .map(|b| b.reference.clone());
+
+        let query = self.find.as_deref().unwrap_or_default();
+        let mut scored: Vec<(i64, BranchInfo)> = self
+            .all_branches
+            .iter()
+            .filter_map(|b| {
+                fuzzy_score(query, &b.name).map(|s| (s, b.clone()))
+            })
+            .collect();
+
+        if self.sort_by_date {
+            scored.sort_by_key(|(_, b)| Reverse(b.top_commit_time));
+        }
+        let favorites = &self.favorites;
+        // stable, keeps the order within both groups
+        scored
+            .sort_by_key(|(_, b)| !favorites.contains(&b.reference));
+
+        let best = scored
+            .iter()
+            .enumerate()
+            .max_by_key(|(idx, (score, _))| (*score, Reverse(*idx)))
+            .map(|(_, (_, b))| b.reference.clone());
+        self.branches = scored.into_iter().map(|(_, b)| b).collect();
+
+        let select = if query.is_empty() { selected } else { best };
+        match select.and_then(|reference| {
+            self.branches
+                .iter()
+                .position(|b| b.reference == reference)
+        }) {
+            Some(pos) => self.set_selection(pos.try_into()?),
+            None => self.set_selection(self.selection),
+        }
+    }
+
+    fn add_config_commands(&self, out: &mut Vec<CommandInfo>) {
+        out.push(CommandInfo::new(
+            strings::commands::edit_branch_description(
+                &self.key_config,
+            ),
+            !self.branches.is_empty(),
+            self.local,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::branch_set_upstream_popup(
+                &self.key_config,
+            ),
+            !self.branches.is_empty(),
+            self.local,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::branch_unset_upstream(
+                &self.key_config,
+            ),
+            self.selection_has_upstream(),
+            self.local,
+        ));
+    }
+
+    fn add_mark_commands(&self, out: &mut Vec<CommandInfo>) {
+        out.push(CommandInfo::new(
+            strings::commands::toggle_mark(&self.key_config),
+            !self.selection_is_cur_branch(),
+            self.local,
+        ));
+
+        if !self.marks.is_empty() {
+            out.push(CommandInfo::new(
+                strings::commands::delete_marked_branches(
+                    &self.key_config,
+                    self.marks.len(),
+                ),
+                true,
+                true,
+            ));
+        }
+
+        out.push(CommandInfo::new(
+            strings::commands::delete_merged_branches(
+                &self.key_config,
+            ),
+            true,
+            self.local,
+        ));
+    }
+
+    fn add_sort_find_commands(&self, out: &mut Vec<CommandInfo>) {
+        out.push(CommandInfo::new(
+            strings::commands::branch_sort(
+                &self.key_config,
+                self.sort_by_date,
+            ),
+            true,
+            true,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::branch_find(&self.key_config),
+            !self.branches.is_empty(),
+            true,
+        ));
+    }
+
+    fn add_find_commands(&self, out: &mut Vec<CommandInfo>) {
+        out.push(CommandInfo::new(
+            strings::commands::branch_find_jump(&self.key_config),
+            true,
+            true,
+        ));
+        out.push(CommandInfo::new(
+            strings::commands::tree_find_cancel(&self.key_config),
+            true,
+            true,
+        ));
+    }
+
+    /// description and upstream of the selected local branch,
+    /// `false` if `e` is none of their keys
+    fn config_event(&self, e: KeyEvent) -> bool {
+        if e == self.key_config.edit_branch_description && self.local
+        {
+            self.queue_for_selected(|branch| {
+                InternalEvent::EditBranchDescription(
+                    branch.name.clone(),
+                )
+            });
+        } else if e == self.key_config.branch_set_upstream
+            && self.local
+        {
+            self.queue_for_selected(|branch| {
+                InternalEvent::OpenBranchUpstream(branch.name.clone())
+            });
+        } else if e == self.key_config.branch_unset_upstream
+            && self.selection_has_upstream()
+        {
+            self.queue_for_selected(|branch| {
+                InternalEvent::SetUpstream(branch.name.clone(), None)
+            });
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    /// marking and deleting several branches at once, `Ok(false)`
+    /// if `e` is none of their keys
+    fn mark_event(&mut self, e: KeyEvent) -> bool {
+        if e == self.key_config.toggle_mark
+            && self.local
+            && !self.selection_is_cur_branch()
+        {
+            if let Some(branch) =
+                self.branches.get(self.selection as usize)
+            {
+                let reference = branch.reference.clone();
+                if let Some(idx) =
+                    self.marks.iter().position(|r| *r == reference)
+                {
+                    self.marks.remove(idx);
+                } else {
+                    self.marks.push(reference);
+                }
+            }
+        } else if e == self.key_config.delete_branch
+            && !self.marks.is_empty()
+        {
+            // in the order listed
+            let marked = self
+                .branches
+                .iter()
+                .filter(|b| self.marks.contains(&b.reference))
+                .map(|b| b.reference.clone())
+                .collect();
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ConfirmAction(Action::DeleteBranches(
+                    marked,
+                )),
+            );
+        } else if e == self.key_config.branch_delete_merged
+            && self.local
+        {
+            try_or_popup!(
+                self,
+                "delete merged branches error:",
+                self.delete_merged()
+            );
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    /// asks to delete the local branches merged into the checked
+    /// out one
+    fn delete_merged(&self) -> Result<()> {
+        let head = self.all_branches.iter().find(|b| {
+            b.local_details().map_or(false, |details| details.is_head)
+        });
+
+        if let Some(head) = head {
+            let merged = branches_merged_into(CWD, &head.reference)?;
+            self.queue.borrow_mut().push_back(if merged.is_empty() {
+                InternalEvent::ShowInfoMsg(
+                    strings::msg_no_merged_branches(
+                        &self.key_config,
+                        &head.name,
+                    ),
+                )
+            } else {
+                InternalEvent::ConfirmAction(Action::DeleteBranches(
+                    merged,
+                ))
+            });
+        }
+
+        Ok(())
+    }
+
+    /// sorting and finding, `Ok(false)` if `e` is none of their keys
+    fn sort_find_event(&mut self, e: KeyEvent) -> Result<bool> {
+        if self.find.is_some() {
+            self.find_event(e)?;
+        } else if e == self.key_config.branch_sort {
+            self.sort_by_date = !self.sort_by_date;
+            self.apply_branches()?;
+        } else if e == self.key_config.branch_find
+            && !self.branches.is_empty()
+        {
+            self.find = Some(String::new());
+            self.find_origin = self
+                .branches
+                .get(self.selection as usize)
+                .map(|b| b.reference.clone());
+        } else {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    /// typed chars go to the query, so only the arrow keys move.
+    /// the whole list is shown again with the found branch
+    /// selected (enter) or what was selected before (esc)
+    fn find_event(&mut self, e: KeyEvent) -> Result<()> {
+        if e == self.key_config.exit_popup
+            || e == self.key_config.enter
+        {
+            let found = self
+                .branches
+                .get(self.selection as usize)
+                .map(|b| b.reference.clone());
+            let origin = self.find_origin.take();
+            let select = if e == self.key_config.enter {
+                found.or(origin)
+            } else {
+                origin
+            };
+
+            self.find = None;
+            self.apply_branches()?;
+
+            if let Some(pos) = select.and_then(|reference| {
+                self.branches
+                    .iter()
+                    .position(|b| b.reference == reference)
+            }) {
+                self.set_selection(pos.try_into()?)?;
+            }
+        } else if e.code == KeyCode::Down {
+            self.move_selection(ScrollType::Up)?;
+        } else if e.code == KeyCode::Up {
+            self.move_selection(ScrollType::Down)?;
+        } else if let Some(query) = &mut self.find {
+            match e.code {
+                KeyCode::Backspace => {
+                    query.pop();
+                }
+                KeyCode::Char(c)
+                    if !e
+                        .modifiers
+                        .contains(KeyModifiers::CONTROL) =>
+                {
+                    query.push(c);
+                }
+                _ => return Ok(()),
+            }
+
+            self.apply_branches()?;
+        }
+
+        Ok(())
+    }
+
+    fn queue_for_selected(
+        &self,
+        event: impl FnOnce(&BranchInfo) -> InternalEvent,
+    ) {
+        if let Some(branch) =
+            self.branches.get(self.selection as usize)
+        {
+            self.queue.borrow_mut().push_back(event(branch));
+        }
+    }
+
+    fn selection_has_upstream(&self) -> bool {
+        self.branches
+            .get(self.selection as usize)
+            .and_then(BranchInfo::local_details)
+            .map_or(false, |details| details.has_upstream)
+    }
+
+    /// `<remote>/HEAD` only points at the default branch of a remote
+    fn selection_is_remote_head(&self) -> bool {
+        !self.local
+            && self
+                .branches
+                .get(self.selection as usize)
+                .map_or(false, |b| b.name.ends_with("/HEAD"))
+    }
+
+    fn is_favorite(&self, branch: &BranchInfo) -> bool {
+        self.favorites.contains(&branch.reference)
+    }
+
+    fn selection_is_favorite(&self) -> bool {
+        self.branches
+            .get(self.selection as usize)
+            .map_or(false, |b| self.is_favorite(b))
+    }
+
+    /// the selection follows the branch to its new position
+    fn toggle_favorite(&mut self) -> Result<()> {
+        if let Some(branch) =
+            self.branches.get(self.selection as usize)
+        {
+            let reference = branch.reference.clone();
+
+            toggle_favorite_branch(CWD, &reference)?;
+            self.update_branches()?;
+
+            if let Some(pos) = self
+                .branches
+                .iter()
+                .position(|b| b.reference == reference)
+            {
+                self.set_selection(pos.try_into()?)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -323,10 +925,138 @@ impl BranchListComponent {
         };
 
         self.selection = selection;
+        self.update_description();
 
         Ok(())
     }
 
+    /// of the selected local branch
+    fn update_description(&mut self) {
+        self.description = if self.local {
+            self.branches.get(self.selection as usize).and_then(|b| {
+                get_description(CWD, &b.name).ok().flatten()
+            })
+        } else {
+            None
+        };
+    }
+
+    /// below the list (up to 3 lines), returns the area left for it
+    fn draw_description<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        area: Rect,
+    ) -> Rect {
+        const MAX_LINES: usize = 3;
+
+        let description = match &self.description {
+            Some(description) => description,
+            None => return area,
+        };
+
+        let lines = description.lines().count().clamp(1, MAX_LINES);
+        // 2 for the borders, at least 5 lines stay for the list
+        let height: u16 = (lines + 2).try_into().unwrap_or_default();
+        let height = height.min(area.height.saturating_sub(5));
+
+        let list = Rect {
+            height: area.height - height,
+            ..area
+        };
+        let below = Rect {
+            y: list.y + list.height,
+            height,
+            ..area
+        };
+
+        f.render_widget(
+            Paragraph::new(Text::styled(
+                description.as_str(),
+                self.theme.text(true, false),
+            ))
+            .block(
+                Block::default()
+                    .title(Span::styled(
+                        strings::branch_description_title(
+                            &self.key_config,
+                        ),
+                        self.theme.title(false),
+                    ))
+                    .border_type(BorderType::Thick)
+                    .borders(Borders::ALL),
+            )
+            .wrap(Wrap { trim: false }),
+            below,
+        );
+
+        list
+    }
+
+    /// local and remote branches as tabs, the shown one
+    /// highlighted, and the query while finding
+    fn get_title(&self) -> Spans<'static> {
+        let mut spans = vec![
+            Span::styled(
+                strings::title_branches(true),
+                self.theme.title(self.local),
+            ),
+            Span::raw(" | "),
+            Span::styled(
+                strings::title_branches(false),
+                self.theme.title(!self.local),
+            ),
+        ];
+        if let Some(query) = &self.find {
+            spans.push(Span::styled(
+                strings::tree_find_title(&self.key_config, query),
+                self.theme.title(true),
+            ));
+        }
+        Spans::from(spans)
+    }
+
+    /// ahead/behind its upstream, age and hash of the last commit
+    fn get_info_spans(
+        &self,
+        branch: &BranchInfo,
+        selected: bool,
+        now: i64,
+    ) -> Vec<Span<'static>> {
+        let compare = branch
+            .local_details()
+            .and_then(|details| details.upstream_compare)
+            .map(|c| {
+                format!("\u{2191}{} \u{2193}{}", c.ahead, c.behind)
+            })
+            .unwrap_or_default();
+
+        vec![
+            Span::styled(
+                format!("{:w$} ", compare, w = COMPARE_LENGTH - 1),
+                self.theme.text(true, selected),
+            ),
+            Span::styled(
+                format!(
+                    "{:w$} ",
+                    time_to_relative_string(
+                        branch.top_commit_time,
+                        now
+                    ),
+                    w = AGE_LENGTH - 1
+                ),
+                self.theme.commit_time(selected),
+            ),
+            Span::styled(
+                format!("{} ", branch.top_commit.get_short_string()),
+                if self.marks.contains(&branch.reference) {
+                    self.theme.commit_marked(selected)
+                } else {
+                    self.theme.commit_hash(selected)
+                },
+            ),
+        ]
+    }
+
     /// Get branches to display
     fn get_text(
         &self,
@@ -335,9 +1065,10 @@ impl BranchListComponent {
         height: usize,
     ) -> Text {
         const COMMIT_HASH_LENGTH: usize = 8;
-        const IS_HEAD_STAR_LENGTH: usize = 3; // "*  "
+        const IS_HEAD_STAR_LENGTH: usize = 4; // "*   "
         const THREE_DOTS_LENGTH: usize = 3; // "..."
 
+        let now = Utc::now().timestamp();
         let branch_name_length: usize =
             width_available as usize * 40 / 100;
         // commit message takes up the remaining width
@@ -345,6 +1076,7 @@ impl BranchListComponent {
             .saturating_sub(COMMIT_HASH_LENGTH)
             .saturating_sub(branch_name_length)
             .saturating_sub(IS_HEAD_STAR_LENGTH)
+            .saturating_sub(COMPARE_LENGTH + AGE_LENGTH)
             .saturating_sub(THREE_DOTS_LENGTH);
         let mut txt = Vec::new();
 
@@ -397,16 +1129,18 @@ impl BranchListComponent {
                 " "
             };
 
+            let is_favorite_str = if self.is_favorite(displaybranch) {
+                "\u{2605}"
+            } else {
+                " "
+            };
+
             let span_prefix = Span::styled(
-                format!("{}{} ", is_head_str, has_upstream_str),
-                theme.commit_author(selected),
-            );
-            let span_hash = Span::styled(
                 format!(
-                    "{} ",
-                    displaybranch.top_commit.get_short_string()
+                    "{}{}{} ",
+                    is_head_str, has_upstream_str, is_favorite_str
                 ),
-                theme.commit_hash(selected),
+                theme.commit_author(selected),
             );
             let span_msg = Span::styled(
                 commit_message.to_string(),
@@ -421,12 +1155,14 @@ impl BranchListComponent {
                 theme.branch(selected, is_head),
             );
 
-            txt.push(Spans::from(vec![
-                span_prefix,
-                span_name,
-                span_hash,
-                span_msg,
-            ]));
+            let mut spans = vec![span_prefix, span_name];
+            spans.extend(self.get_info_spans(
+                displaybranch,
+                selected,
+                now,
+            ));
+            spans.push(span_msg);
+            txt.push(Spans::from(spans));
         }
 
         Text::from(txt)
@@ -435,10 +1171,21 @@ impl BranchListComponent {
     ///
     fn switch_to_selected_branch(&mut self) -> Result<()> {
         if self.local {
-            checkout_branch(
-                asyncgit::CWD,
-                &self.branches[self.selection as usize].reference,
-            )?;
+            let branch_ref =
+                &self.branches[self.selection as usize].reference;
+
+            let conflicts = checkout_conflicts(CWD, branch_ref)?;
+            if !conflicts.is_empty() {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::CheckoutConflict(
+                        branch_ref.clone(),
+                        conflicts,
+                    ),
+                );
+                return Ok(());
+            }
+
+            checkout_branch(CWD, branch_ref)?;
             self.hide()
         } else {
             checkout_remote_branch(
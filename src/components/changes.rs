@@ -1,7 +1,7 @@
 use super::{
     filetree::FileTreeComponent,
     utils::filetree::{FileTreeItem, FileTreeItemKind},
-    CommandBlocking, DrawableComponent,
+    BlameRevision, CommandBlocking, DrawableComponent,
 };
 use crate::{
     components::{CommandInfo, Component},
@@ -11,14 +11,24 @@ use crate::{
     ui::style::SharedTheme,
 };
 use anyhow::Result;
-use asyncgit::{sync, StatusItem, StatusItemType, CWD};
+use asyncgit::{
+    sync::{self, SubmoduleInfo},
+    StatusItem, StatusItemType, CWD,
+};
 use crossterm::event::Event;
-use std::path::Path;
-use tui::{backend::Backend, layout::Rect, Frame};
+use std::{convert::TryFrom, path::Path};
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    Frame,
+};
 
-///
+/// the working dir lists its conflicted files in a section of their
+/// own above the others, the stage leaves them out
 pub struct ChangesComponent {
     files: FileTreeComponent,
+    conflicts: FileTreeComponent,
+    conflicts_selected: bool,
     is_working_dir: bool,
     queue: Queue,
     key_config: SharedKeyConfig,
@@ -34,14 +44,28 @@ impl ChangesComponent {
         theme: SharedTheme,
         key_config: SharedKeyConfig,
     ) -> Self {
+        let mut files = FileTreeComponent::new(
+            title,
+            focus,
+            Some(queue.clone()),
+            theme.clone(),
+            key_config.clone(),
+        );
+        files.enable_blame(BlameRevision::Head);
+        files.enable_marks();
+
+        let conflicts = FileTreeComponent::new(
+            &strings::title_conflicts(&key_config),
+            false,
+            Some(queue.clone()),
+            theme,
+            key_config.clone(),
+        );
+
         Self {
-            files: FileTreeComponent::new(
-                title,
-                focus,
-                Some(queue.clone()),
-                theme,
-                key_config.clone(),
-            ),
+            files,
+            conflicts,
+            conflicts_selected: false,
             is_working_dir,
             queue,
             key_config,
@@ -50,43 +74,120 @@ impl ChangesComponent {
 
     ///
     pub fn set_items(&mut self, list: &[StatusItem]) -> Result<()> {
-        self.files.update(list)?;
+        let (conflicts, files): (Vec<_>, Vec<_>) =
+            list.iter().cloned().partition(|item| {
+                item.status == StatusItemType::Conflicted
+            });
+
+        self.files.update(&files)?;
+        if self.is_working_dir {
+            self.conflicts.update(&conflicts)?;
+            if self.conflicts_selected && self.conflicts.is_empty() {
+                self.select_conflicts(false);
+            } else if !self.conflicts_selected
+                && self.files.is_empty()
+                && !self.conflicts.is_empty()
+            {
+                self.select_conflicts(true);
+            }
+        }
+
         Ok(())
     }
 
+    ///
+    pub fn set_submodules(&mut self, submodules: Vec<SubmoduleInfo>) {
+        self.files.set_submodules(submodules);
+    }
+
     ///
     pub fn selection(&self) -> Option<FileTreeItem> {
-        self.files.selection()
+        self.active().selection()
     }
 
     ///
     pub fn focus_select(&mut self, focus: bool) {
-        self.files.focus(focus);
-        self.files.show_selection(focus);
+        let active = self.active_mut();
+        active.focus(focus);
+        active.show_selection(focus);
     }
 
     /// returns true if list is empty
     pub fn is_empty(&self) -> bool {
-        self.files.is_empty()
+        self.files.is_empty() && self.conflicts.is_empty()
     }
 
     ///
     pub fn is_file_seleted(&self) -> bool {
-        self.files.is_file_seleted()
+        self.active().is_file_seleted()
+    }
+
+    ///
+    pub fn is_expanded_folder_selected(&self) -> bool {
+        self.active().is_expanded_folder_selected()
+    }
+
+    const fn active(&self) -> &FileTreeComponent {
+        if self.conflicts_selected {
+            &self.conflicts
+        } else {
+            &self.files
+        }
     }
 
+    fn active_mut(&mut self) -> &mut FileTreeComponent {
+        if self.conflicts_selected {
+            &mut self.conflicts
+        } else {
+            &mut self.files
+        }
+    }
+
+    /// moves the focus between the conflicts and the other files
+    fn select_conflicts(&mut self, conflicts: bool) {
+        let focused = self.focused();
+        self.focus(false);
+        self.conflicts_selected = conflicts;
+        self.focus(focused);
+
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::DIFF));
+    }
+
+    fn open_conflict(&self) -> bool {
+        if let Some(FileTreeItemKind::File(item)) =
+            self.selection().map(|item| item.kind)
+        {
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::OpenConflict(item.path));
+            return true;
+        }
+
+        false
+    }
+
+    /// the marked files if any, otherwise the selected item
     fn index_add_remove(&mut self) -> Result<bool> {
+        let marked = self.files.marked_files();
+        if !marked.is_empty() {
+            for item in &marked {
+                if self.is_working_dir {
+                    Self::stage_file(item)?;
+                } else {
+                    sync::reset_stage(CWD, &item.path)?;
+                }
+            }
+
+            self.files.clear_marks();
+            return Ok(true);
+        }
+
         if let Some(tree_item) = self.selection() {
             if self.is_working_dir {
                 if let FileTreeItemKind::File(i) = tree_item.kind {
-                    let path = Path::new(i.path.as_str());
-                    match i.status {
-                        StatusItemType::Deleted => {
-                            sync::stage_addremoved(CWD, path)?
-                        }
-                        _ => sync::stage_add_file(CWD, path)?,
-                    };
-
+                    Self::stage_file(&i)?;
                     return Ok(true);
                 }
 
@@ -107,6 +208,18 @@ impl ChangesComponent {
         Ok(false)
     }
 
+    fn stage_file(item: &StatusItem) -> Result<()> {
+        let path = Path::new(item.path.as_str());
+        match item.status {
+            StatusItemType::Deleted => {
+                sync::stage_addremoved(CWD, path)?
+            }
+            _ => sync::stage_add_file(CWD, path)?,
+        };
+
+        Ok(())
+    }
+
     fn index_add_all(&mut self) -> Result<()> {
         sync::stage_add_all(CWD, "*")?;
 
@@ -128,6 +241,20 @@ impl ChangesComponent {
     }
 
     fn dispatch_reset_workdir(&mut self) -> bool {
+        let marked = self.files.marked_files();
+        if !marked.is_empty() {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ConfirmAction(Action::ResetItems(
+                    marked
+                        .into_iter()
+                        .map(|item| item.path)
+                        .collect(),
+                )),
+            );
+
+            return true;
+        }
+
         if let Some(tree_item) = self.selection() {
             let is_folder =
                 matches!(tree_item.kind, FileTreeItemKind::Path(_));
@@ -147,22 +274,16 @@ impl ChangesComponent {
 
     fn add_to_ignore(&mut self) -> bool {
         if let Some(tree_item) = self.selection() {
-            if let Err(e) =
-                sync::add_to_ignore(CWD, &tree_item.info.full_path)
-            {
-                self.queue.borrow_mut().push_back(
-                    InternalEvent::ShowErrorMsg(format!(
-                        "ignore error:\n{}\nfile:\n{:?}",
-                        e, tree_item.info.full_path
-                    )),
-                );
-            } else {
-                self.queue.borrow_mut().push_back(
-                    InternalEvent::Update(NeedsUpdate::ALL),
-                );
-
-                return true;
-            }
+            let is_folder =
+                matches!(tree_item.kind, FileTreeItemKind::Path(_));
+            self.queue.borrow_mut().push_back(
+                InternalEvent::OpenIgnore(
+                    tree_item.info.full_path,
+                    is_folder,
+                ),
+            );
+
+            return true;
         }
 
         false
@@ -175,7 +296,24 @@ impl DrawableComponent for ChangesComponent {
         f: &mut Frame<B>,
         r: Rect,
     ) -> Result<()> {
-        self.files.draw(f, r)?;
+        if self.conflicts.is_empty() {
+            self.files.draw(f, r)?;
+        } else {
+            let height = u16::try_from(self.conflicts.file_count())
+                .unwrap_or(u16::MAX)
+                .saturating_add(2)
+                .min(r.height / 2);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [Constraint::Length(height), Constraint::Min(0)]
+                        .as_ref(),
+                )
+                .split(r);
+
+            self.conflicts.draw(f, chunks[0])?;
+            self.files.draw(f, chunks[1])?;
+        }
 
         Ok(())
     }
@@ -187,11 +325,17 @@ impl Component for ChangesComponent {
         out: &mut Vec<CommandInfo>,
         force_all: bool,
     ) -> CommandBlocking {
-        self.files.commands(out, force_all);
+        self.active().commands(out, force_all);
 
         let some_selection = self.selection().is_some();
 
-        if self.is_working_dir {
+        if self.conflicts_selected {
+            out.push(CommandInfo::new(
+                strings::commands::open_conflict(&self.key_config),
+                self.is_file_seleted(),
+                self.focused(),
+            ));
+        } else if self.is_working_dir {
             out.push(CommandInfo::new(
                 strings::commands::stage_all(&self.key_config),
                 some_selection,
@@ -212,6 +356,11 @@ impl Component for ChangesComponent {
                 some_selection,
                 self.focused(),
             ));
+            out.push(CommandInfo::new(
+                strings::commands::status_clean(&self.key_config),
+                true,
+                self.focused(),
+            ));
         } else {
             out.push(CommandInfo::new(
                 strings::commands::unstage_item(&self.key_config),
@@ -237,13 +386,29 @@ impl Component for ChangesComponent {
     }
 
     fn event(&mut self, ev: Event) -> Result<bool> {
-        if self.files.event(ev)? {
+        if self.active_mut().event(ev)? {
             return Ok(true);
         }
 
         if self.focused() {
             if let Event::Key(e) = ev {
-                return if e == self.key_config.open_commit
+                // the lists did not move, so we are at their edge
+                return if e == self.key_config.move_up
+                    && !self.conflicts_selected
+                    && !self.conflicts.is_empty()
+                {
+                    self.select_conflicts(true);
+                    Ok(true)
+                } else if e == self.key_config.move_down
+                    && self.conflicts_selected
+                    && !self.files.is_empty()
+                {
+                    self.select_conflicts(false);
+                    Ok(true)
+                } else if self.conflicts_selected {
+                    Ok(e == self.key_config.enter
+                        && self.open_conflict())
+                } else if e == self.key_config.open_commit
                     && !self.is_working_dir
                     && !self.is_empty()
                 {
@@ -284,6 +449,13 @@ impl Component for ChangesComponent {
                     && !self.is_empty()
                 {
                     Ok(self.add_to_ignore())
+                } else if e == self.key_config.open_clean
+                    && self.is_working_dir
+                {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenClean);
+                    Ok(true)
                 } else {
                     Ok(false)
                 };
@@ -294,9 +466,9 @@ impl Component for ChangesComponent {
     }
 
     fn focused(&self) -> bool {
-        self.files.focused()
+        self.active().focused()
     }
     fn focus(&mut self, focus: bool) {
-        self.files.focus(focus)
+        self.active_mut().focus(focus);
     }
 }
@@ -0,0 +1,200 @@
+use crate::{
+    components::{
+        popup_paragraph, visibility_blocking, CommandBlocking,
+        CommandInfo, Component, DrawableComponent,
+    },
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings, ui,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::branch::checkout::{
+        checkout_branch_force, stash_and_checkout_branch,
+    },
+    CWD,
+};
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::Clear,
+    Frame,
+};
+use ui::style::SharedTheme;
+
+/// offers ways out when local changes are in the way of a checkout
+pub struct CheckoutConflictComponent {
+    branch_ref: String,
+    conflicts: Vec<String>,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for CheckoutConflictComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let mut txt = vec![Spans::from(Span::styled(
+                strings::checkout_conflict_msg(
+                    &self.key_config,
+                    &self.branch_ref,
+                ),
+                self.theme.text_danger(),
+            ))];
+
+            txt.extend(self.conflicts.iter().map(|path| {
+                Spans::from(Span::styled(
+                    format!(" {}", path),
+                    self.theme.text(true, false),
+                ))
+            }));
+
+            let title =
+                strings::checkout_conflict_title(&self.key_config);
+
+            let area = ui::centered_rect(50, 30, f.size());
+            f.render_widget(Clear, area);
+            f.render_widget(
+                popup_paragraph(
+                    &title,
+                    Text::from(txt),
+                    &self.theme,
+                    true,
+                ),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for CheckoutConflictComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        _force_all: bool,
+    ) -> CommandBlocking {
+        out.push(CommandInfo::new(
+            strings::commands::checkout_stash_reapply(
+                &self.key_config,
+            ),
+            true,
+            self.visible,
+        ));
+        out.push(CommandInfo::new(
+            strings::commands::checkout_stash(&self.key_config),
+            true,
+            self.visible,
+        ));
+        out.push(CommandInfo::new(
+            strings::commands::checkout_force(&self.key_config),
+            true,
+            self.visible,
+        ));
+        out.push(CommandInfo::new(
+            strings::commands::close_popup(&self.key_config),
+            true,
+            self.visible,
+        ));
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.enter {
+                    self.stash_and_checkout(true);
+                } else if e == self.key_config.stashing_save {
+                    self.stash_and_checkout(false);
+                } else if e == self.key_config.checkout_force {
+                    let res =
+                        checkout_branch_force(CWD, &self.branch_ref);
+                    self.finish(res.map_err(Into::into));
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl CheckoutConflictComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            branch_ref: String::new(),
+            conflicts: Vec::new(),
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(
+        &mut self,
+        branch_ref: String,
+        conflicts: Vec<String>,
+    ) -> Result<()> {
+        self.branch_ref = branch_ref;
+        self.conflicts = conflicts;
+        self.show()
+    }
+
+    fn stash_and_checkout(&mut self, reapply: bool) {
+        let res =
+            stash_and_checkout_branch(CWD, &self.branch_ref, reapply)
+                .map(|_| ());
+        self.finish(res.map_err(Into::into));
+    }
+
+    fn finish(&mut self, res: Result<()>) {
+        self.hide();
+
+        if let Err(e) = res {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(format!(
+                    "checkout error:\n{}",
+                    e
+                )),
+            );
+        }
+
+        // a failed re-apply still switched the branch
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+    }
+}
@@ -0,0 +1,280 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent, ScrollType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{Action, InternalEvent, Queue},
+    strings,
+    ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CleanOptions},
+    CWD,
+};
+use crossterm::event::Event;
+use std::cell::Cell;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// preview of what `git clean` would remove,
+/// removing itself needs another confirmation
+pub struct CleanComponent {
+    options: CleanOptions,
+    preview: Vec<String>,
+    visible: bool,
+    scroll_top: Cell<usize>,
+    current_height: Cell<usize>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for CleanComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(60, 60);
+            const MIN_SIZE: Size = Size::new(50, 10);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height_in_lines =
+                (area.height as usize).saturating_sub(2);
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height_in_lines))
+                    .block(
+                        Block::default()
+                            .title(Span::styled(
+                                strings::clean_title(
+                                    &self.key_config,
+                                    self.preview.len(),
+                                    self.options.directories,
+                                    self.options.ignored,
+                                ),
+                                self.theme.title(true),
+                            ))
+                            .border_type(BorderType::Thick)
+                            .borders(Borders::ALL),
+                    )
+                    .alignment(Alignment::Left),
+                area,
+            );
+
+            ui::draw_scrollbar(
+                f,
+                area,
+                &self.theme,
+                self.preview.len(),
+                self.scroll_top.get(),
+            );
+
+            self.current_height.set(height_in_lines);
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for CleanComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::clean_toggle_directories(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::clean_toggle_ignored(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::clean_confirm(&self.key_config),
+                !self.preview.is_empty(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.scroll(ScrollType::Down);
+                } else if e == self.key_config.move_up {
+                    self.scroll(ScrollType::Up);
+                } else if e == self.key_config.page_down {
+                    self.scroll(ScrollType::PageDown);
+                } else if e == self.key_config.page_up {
+                    self.scroll(ScrollType::PageUp);
+                } else if e
+                    == self.key_config.clean_toggle_directories
+                {
+                    self.options.directories =
+                        !self.options.directories;
+                    self.update_preview()?;
+                } else if e == self.key_config.clean_toggle_ignored {
+                    self.options.ignored = !self.options.ignored;
+                    self.update_preview()?;
+                } else if e == self.key_config.enter
+                    && !self.preview.is_empty()
+                {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ConfirmAction(Action::Clean(
+                            self.options,
+                            self.preview.clone(),
+                        )),
+                    );
+                    self.hide();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl CleanComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            options: CleanOptions::default(),
+            preview: Vec::new(),
+            visible: false,
+            scroll_top: Cell::new(0),
+            current_height: Cell::new(0),
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self) -> Result<()> {
+        self.update_preview()?;
+        self.show()
+    }
+
+    fn update_preview(&mut self) -> Result<()> {
+        self.preview = sync::clean_workdir(
+            CWD,
+            self.options.directories,
+            self.options.ignored,
+            true,
+        )?;
+        self.scroll_top.set(0);
+
+        Ok(())
+    }
+
+    fn scroll(&self, scroll: ScrollType) {
+        let height = self.current_height.get();
+        let max = self.preview.len().saturating_sub(height);
+
+        let new_top = match scroll {
+            ScrollType::Down => {
+                self.scroll_top.get().saturating_add(1)
+            }
+            ScrollType::Up => self.scroll_top.get().saturating_sub(1),
+            ScrollType::PageDown => {
+                self.scroll_top.get().saturating_add(height)
+            }
+            ScrollType::PageUp => {
+                self.scroll_top.get().saturating_sub(height)
+            }
+            _ => self.scroll_top.get(),
+        };
+
+        self.scroll_top.set(new_top.min(max));
+    }
+
+    fn get_text(&self, height: usize) -> Text<'_> {
+        if self.preview.is_empty() {
+            return Text::styled(
+                strings::clean_nothing(&self.key_config),
+                self.theme.text(false, false),
+            );
+        }
+
+        Text::from(
+            self.preview
+                .iter()
+                .skip(self.scroll_top.get())
+                .take(height)
+                .map(|path| {
+                    Spans::from(Span::styled(
+                        path.as_str(),
+                        self.theme.text(true, false),
+                    ))
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+}
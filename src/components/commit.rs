@@ -1,11 +1,13 @@
 use super::{
-    textinput::TextInputComponent, visibility_blocking,
-    CommandBlocking, CommandInfo, Component, DrawableComponent,
-    ExternalEditorComponent,
+    textinput::{InputType, TextInputComponent},
+    utils::{string_to_time, time_to_string_in},
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent, ExternalEditorComponent,
 };
 use crate::{
     get_app_config_path,
     keys::SharedKeyConfig,
+    options::{SharedOptions, TimeZoneDisplay},
     queue::{InternalEvent, NeedsUpdate, Queue},
     strings,
     ui::style::SharedTheme,
@@ -13,11 +15,17 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
     cached,
-    sync::{self, CommitId, HookResult},
-    CWD,
+    sync::{
+        self, CommitId, CommitMessage, CommitSignature, HookResult,
+        PrepareCommitMsgSource, TRAILER_CO_AUTHORED_BY,
+        TRAILER_SIGNED_OFF_BY,
+    },
+    DiffLineType, CWD,
 };
 use crossterm::event::Event;
 use std::{
+    cell::Cell,
+    convert::TryFrom,
     fs::File,
     io::{Read, Write},
     path::PathBuf,
@@ -25,16 +33,42 @@ use std::{
 use tui::{
     backend::Backend,
     layout::{Alignment, Rect},
-    widgets::Paragraph,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 
+#[derive(Clone, Copy, PartialEq)]
+enum Focus {
+    Message,
+    Author,
+    Date,
+}
+
 pub struct CommitComponent {
     input: TextInputComponent,
+    /// author fields of an advanced amend, below the message
+    author_input: TextInputComponent,
+    date_input: TextInputComponent,
+    amend_author: bool,
+    focus: Focus,
     amend: Option<CommitId>,
     queue: Queue,
     key_config: SharedKeyConfig,
+    theme: SharedTheme,
+    options: SharedOptions,
     git_branch_name: cached::BranchName,
+    signing: bool,
+    /// adds a `Signed-off-by` trailer on commit
+    signoff: bool,
+    /// message prefilled by the template or the `prepare-commit-msg`
+    /// hook, its `#` lines are left out of the commit like git does
+    prepared: Option<String>,
+    /// staged patch shown below the message in verbose mode
+    diff: String,
+    diff_scroll: usize,
+    /// lines of the diff pane (last time it got drawn)
+    diff_height: Cell<usize>,
 }
 
 impl DrawableComponent for CommitComponent {
@@ -45,7 +79,16 @@ impl DrawableComponent for CommitComponent {
     ) -> Result<()> {
         if self.is_visible() {
             self.input.draw(f, rect)?;
+            if self.amend_author {
+                self.draw_author_fields(f);
+            }
+            self.draw_rulers(f);
+            self.draw_line_length(f);
             self.draw_branch_name(f);
+
+            if self.options.commit_verbose.get() {
+                self.draw_diff(f);
+            }
         }
 
         Ok(())
@@ -67,12 +110,34 @@ impl Component for CommitComponent {
                 true,
             ));
 
+            out.push(CommandInfo::new(
+                strings::commands::commit_and_push(&self.key_config),
+                self.can_commit() && self.amend.is_none(),
+                true,
+            ));
+
             out.push(CommandInfo::new(
                 strings::commands::commit_amend(&self.key_config),
                 self.can_amend(),
                 true,
             ));
 
+            out.push(CommandInfo::new(
+                strings::commands::commit_amend_advanced(
+                    &self.key_config,
+                ),
+                self.can_amend(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::commit_next_field(
+                    &self.key_config,
+                ),
+                true,
+                self.amend_author,
+            ));
+
             out.push(CommandInfo::new(
                 strings::commands::commit_open_editor(
                     &self.key_config,
@@ -80,6 +145,36 @@ impl Component for CommitComponent {
                 true,
                 true,
             ));
+
+            out.push(CommandInfo::new(
+                strings::commands::commit_toggle_signoff(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::commit_co_author(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::commit_toggle_verbose(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::commit_scroll_diff(
+                    &self.key_config,
+                ),
+                !self.diff.is_empty(),
+                self.options.commit_verbose.get(),
+            ));
         }
 
         visibility_blocking(self)
@@ -87,22 +182,49 @@ impl Component for CommitComponent {
 
     fn event(&mut self, ev: Event) -> Result<bool> {
         if self.is_visible() {
-            if self.input.event(ev)? {
+            if self.fields_event(ev)?
+                || (self.focus == Focus::Message
+                    && self.input.event(ev)?)
+            {
                 return Ok(true);
             }
 
             if let Event::Key(e) = ev {
                 if e == self.key_config.enter && self.can_commit() {
                     self.commit()?;
+                } else if e == self.key_config.commit_and_push
+                    && self.can_commit()
+                    && self.amend.is_none()
+                {
+                    self.commit_and_push()?;
                 } else if e == self.key_config.commit_amend
                     && self.can_amend()
                 {
-                    self.amend()?;
+                    self.amend(false)?;
+                } else if e == self.key_config.commit_amend_advanced
+                    && self.can_amend()
+                {
+                    self.amend(true)?;
                 } else if e == self.key_config.open_commit_editor {
                     self.queue.borrow_mut().push_back(
                         InternalEvent::OpenExternalEditor(None),
                     );
                     self.hide();
+                } else if e == self.key_config.commit_toggle_signoff {
+                    self.signoff = !self.signoff;
+                } else if e == self.key_config.commit_co_author {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::SelectCoAuthor);
+                } else if e == self.key_config.commit_toggle_verbose {
+                    self.options
+                        .commit_verbose
+                        .set(!self.options.commit_verbose.get());
+                    self.update_diff();
+                } else if e == self.key_config.page_down {
+                    self.scroll_diff(true);
+                } else if e == self.key_config.page_up {
+                    self.scroll_diff(false);
                 } else {
                 }
                 // stop key event propagation
@@ -126,9 +248,27 @@ impl Component for CommitComponent {
             self.input.clear();
         }
         self.amend = None;
+        self.amend_author = false;
+        self.focus = Focus::Message;
+        self.signoff = sync::signoff_enabled(CWD).unwrap_or_default();
+
+        // a draft kept from the last time stays as it is
+        if self.input.get_text().is_empty() {
+            let template = self.read_template();
+            let source = template
+                .as_ref()
+                .map(|_| PrepareCommitMsgSource::Template);
+            match self
+                .prepare_msg(&template.unwrap_or_default(), source)?
+            {
+                Some(msg) => self.input.set_text(msg),
+                None => return Ok(()),
+            }
+        }
 
         self.input
             .set_title(strings::commit_title(&self.key_config));
+        self.update_diff();
         self.input.show()?;
 
         Ok(())
@@ -141,32 +281,287 @@ impl CommitComponent {
         queue: Queue,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
         Self {
             queue,
             amend: None,
             input: TextInputComponent::new(
-                theme,
+                theme.clone(),
                 key_config.clone(),
                 "",
                 &strings::commit_msg(&key_config),
-                true,
-            ),
+                false,
+            )
+            .with_bold_first_line(),
+            author_input: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                &strings::commit_author_title(&key_config),
+                &strings::commit_author_msg(&key_config),
+                false,
+            )
+            .with_input_type(InputType::Singleline),
+            date_input: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                &strings::commit_author_date_title(&key_config),
+                &strings::commit_author_date_msg(&key_config),
+                false,
+            )
+            .with_input_type(InputType::Singleline),
+            amend_author: false,
+            focus: Focus::Message,
             key_config,
+            theme,
+            options,
             git_branch_name: cached::BranchName::new(CWD),
+            signing: false,
+            signoff: false,
+            prepared: None,
+            diff: String::new(),
+            diff_scroll: 0,
+            diff_height: Cell::new(0),
         }
     }
 
     ///
     pub fn update(&mut self) -> Result<()> {
         self.git_branch_name.lookup().map(Some).unwrap_or(None);
+        self.signing =
+            sync::commit_signing_enabled(CWD).unwrap_or_default();
         Ok(())
     }
 
+    /// marks the subject limit on the top border and the body
+    /// wrap column on the bottom border, red once exceeded
+    fn draw_rulers<B: Backend>(&self, f: &mut Frame<B>) {
+        let area = self.input.get_area();
+        let msg = self.input.get_text();
+        let mut lines = msg.lines();
+        let subject_len =
+            lines.next().map_or(0, |l| l.chars().count());
+        let body_len =
+            lines.map(|l| l.chars().count()).max().unwrap_or(0);
+
+        let rulers = [
+            (
+                self.options.commit_subject_length,
+                subject_len,
+                area.y,
+                "┳",
+            ),
+            (
+                self.options.commit_body_wrap,
+                body_len,
+                area.bottom().saturating_sub(1),
+                "┻",
+            ),
+        ];
+
+        for (column, len, y, symbol) in &rulers {
+            let column = u16::try_from(*column).unwrap_or(u16::MAX);
+            if column == 0 || column.saturating_add(2) >= area.width {
+                continue;
+            }
+
+            let style = if *len > usize::from(column) {
+                self.theme.text_danger()
+            } else {
+                self.theme.block(true)
+            };
+
+            f.render_widget(
+                Paragraph::new(Span::styled(*symbol, style)),
+                Rect::new(area.x + 1 + column, *y, 1, 1),
+            );
+        }
+    }
+
+    /// length of the line with the cursor against the limit of the
+    /// subject or the body, and a hint if they are not separated by
+    /// a blank line
+    fn draw_line_length<B: Backend>(&self, f: &mut Frame<B>) {
+        let msg = self.input.get_text();
+        if msg.is_empty() {
+            return;
+        }
+
+        let mut rect = self.input.get_area();
+        rect.y = rect.bottom().saturating_sub(1);
+        rect.height = 1;
+        rect.x += 1;
+        rect.width = rect.width.saturating_sub(2);
+
+        let line = self.input.get_cursor_line();
+        let len = msg
+            .split('\n')
+            .nth(line)
+            .map_or(0, |l| l.chars().count());
+        let limit = if line == 0 {
+            self.options.commit_subject_length
+        } else {
+            self.options.commit_body_wrap
+        };
+
+        let (text, style) = if limit == 0 {
+            (format!("[{}]", len), self.theme.text(true, false))
+        } else if len > limit {
+            (format!("[{}/{}]", len, limit), self.theme.text_danger())
+        } else {
+            (
+                format!("[{}/{}]", len, limit),
+                self.theme.text(true, false),
+            )
+        };
+        f.render_widget(
+            Paragraph::new(Span::styled(text, style))
+                .alignment(Alignment::Right),
+            rect,
+        );
+
+        let separated = msg
+            .split('\n')
+            .nth(1)
+            .map_or(true, |l| l.trim().is_empty());
+        if !separated {
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    strings::commit_body_separation(&self.key_config),
+                    self.theme.text_danger(),
+                )),
+                rect,
+            );
+        }
+    }
+
+    /// the staged changes (`commit --verbose`) right below the
+    /// message popup, as far down as the screen allows
+    /// author and date next to each other below the message
+    fn draw_author_fields<B: Backend>(&self, f: &mut Frame<B>) {
+        let input = self.input.get_area();
+        if input.bottom().saturating_add(3) > f.size().height {
+            return;
+        }
+
+        let author_width = input.width / 3 * 2;
+        self.author_input.draw_at(
+            f,
+            Rect::new(input.x, input.bottom(), author_width, 3),
+            self.focus == Focus::Author,
+        );
+        self.date_input.draw_at(
+            f,
+            Rect::new(
+                input.x + author_width,
+                input.bottom(),
+                input.width - author_width,
+                3,
+            ),
+            self.focus == Focus::Date,
+        );
+    }
+
+    fn draw_diff<B: Backend>(&self, f: &mut Frame<B>) {
+        let input = self.input.get_area();
+        let top = if self.amend_author {
+            input.bottom().saturating_add(3)
+        } else {
+            input.bottom()
+        };
+        // keeps the command bar visible
+        let height =
+            f.size().height.saturating_sub(top).saturating_sub(2);
+        if height < 3 {
+            return;
+        }
+
+        let area = Rect::new(input.x, top, input.width, height);
+        let lines = usize::from(height - 2);
+        self.diff_height.set(lines);
+
+        let text = if self.diff.is_empty() {
+            vec![Spans::from(Span::styled(
+                strings::commit_diff_empty(&self.key_config),
+                self.theme.text(false, false),
+            ))]
+        } else {
+            self.diff
+                .lines()
+                .skip(self.diff_scroll)
+                .take(lines)
+                .map(|line| {
+                    let typ = match line.chars().next() {
+                        Some('+') => DiffLineType::Add,
+                        Some('-') => DiffLineType::Delete,
+                        Some('@') => DiffLineType::Header,
+                        _ => DiffLineType::None,
+                    };
+                    Spans::from(Span::styled(
+                        line.to_string(),
+                        self.theme.diff_line(typ, false),
+                    ))
+                })
+                .collect()
+        };
+
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(text).block(
+                Block::default()
+                    .title(Span::styled(
+                        strings::commit_diff_title(&self.key_config),
+                        self.theme.title(false),
+                    ))
+                    .borders(Borders::ALL)
+                    .border_style(self.theme.block(false)),
+            ),
+            area,
+        );
+    }
+
+    /// reads the staged patch in verbose mode
+    fn update_diff(&mut self) {
+        self.diff_scroll = 0;
+        self.diff.clear();
+
+        if self.options.commit_verbose.get() {
+            match sync::get_stage_patch(CWD) {
+                Ok(patch) => self.diff = patch,
+                Err(e) => log::error!("staged diff error: {}", e),
+            }
+        }
+    }
+
+    /// by a page of the diff pane
+    fn scroll_diff(&mut self, down: bool) {
+        let page = self.diff_height.get().max(1);
+        self.diff_scroll = if down {
+            let last = self.diff.lines().count().saturating_sub(1);
+            self.diff_scroll.saturating_add(page).min(last)
+        } else {
+            self.diff_scroll.saturating_sub(page)
+        };
+    }
+
     fn draw_branch_name<B: Backend>(&self, f: &mut Frame<B>) {
         if let Some(name) = self.git_branch_name.last() {
-            let w = Paragraph::new(format!("{{{}}}", name))
-                .alignment(Alignment::Right);
+            let flags = [
+                (
+                    self.signing,
+                    strings::commit_signed(&self.key_config),
+                ),
+                (
+                    self.signoff,
+                    strings::commit_signoff(&self.key_config),
+                ),
+            ];
+            let text =
+                flags.iter().filter(|(on, _)| *on).fold(
+                    format!("{{{}}}", name),
+                    |text, (_, flag)| format!("{} [{}]", text, flag),
+                );
+            let w = Paragraph::new(text).alignment(Alignment::Right);
 
             let rect = {
                 let mut rect = self.input.get_area();
@@ -225,13 +620,206 @@ impl CommitComponent {
         Ok(())
     }
 
-    fn commit(&mut self) -> Result<()> {
+    /// `None` (and an error shown) if it cannot be read
+    fn read_template(&self) -> Option<String> {
+        match sync::commit_msg_template(CWD) {
+            Ok(template) => template,
+            Err(e) => {
+                log::error!("commit template error: {}", e);
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "commit template error:\n{}",
+                        e
+                    )),
+                );
+                None
+            }
+        }
+    }
+
+    /// runs `prepare-commit-msg` on `msg` (prefilled from
+    /// `source`), `None` if the hook failed: like git nothing is
+    /// committed then
+    fn prepare_msg(
+        &mut self,
+        msg: &str,
+        source: Option<PrepareCommitMsgSource>,
+    ) -> Result<Option<String>> {
+        let mut prepared = msg.to_string();
+        if let HookResult::NotOk(e) = sync::hooks_prepare_commit_msg(
+            CWD,
+            source,
+            &mut prepared,
+        )? {
+            log::error!("prepare-commit-msg hook error: {}", e);
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(format!(
+                    "prepare-commit-msg hook error:\n{}",
+                    e
+                )),
+            );
+            return Ok(None);
+        }
+
+        self.prepared = if source
+            == Some(PrepareCommitMsgSource::Template)
+            || prepared != msg
+        {
+            Some(prepared.clone())
+        } else {
+            None
+        };
+
+        Ok(Some(prepared))
+    }
+
+    /// leaves out the `#` lines of a prepared message
+    fn finish_msg(&mut self, msg: String) -> String {
+        if self.prepared.take().is_some() {
+            msg.lines()
+                .filter(|line| !line.starts_with('#'))
+                .collect::<Vec<_>>()
+                .join("\n")
+                .trim()
+                .to_string()
+        } else {
+            msg
+        }
+    }
+
+    /// clears the input
+    fn take_msg(&mut self) -> String {
         let msg = self.input.get_text().clone();
         self.input.clear();
-        self.commit_msg(msg)
+
+        self.finish_msg(msg)
+    }
+
+    /// moves the focus between message, author and date of an
+    /// advanced amend, keys go to the focused field
+    fn fields_event(&mut self, ev: Event) -> Result<bool> {
+        if !self.amend_author {
+            return Ok(false);
+        }
+
+        if let Event::Key(e) = ev {
+            if e == self.key_config.tab_toggle {
+                self.focus = match self.focus {
+                    Focus::Message => Focus::Author,
+                    Focus::Author => Focus::Date,
+                    Focus::Date => Focus::Message,
+                };
+                return Ok(true);
+            } else if e == self.key_config.exit_popup {
+                self.hide();
+                return Ok(true);
+            }
+        }
+
+        match self.focus {
+            Focus::Message => Ok(false),
+            Focus::Author => self.author_input.event(ev),
+            Focus::Date => self.date_input.event(ev),
+        }
+    }
+
+    /// author of an advanced amend, the error tells which field
+    /// cannot be read
+    fn read_author(
+        &self,
+    ) -> std::result::Result<Option<CommitSignature>, String> {
+        if !self.amend_author {
+            return Ok(None);
+        }
+
+        let (name, email) = parse_author(
+            self.author_input.get_text(),
+        )
+        .ok_or_else(|| {
+            format!(
+                "invalid author, expected: {}",
+                strings::commit_author_msg(&self.key_config)
+            )
+        })?;
+        let (time, time_offset) = string_to_time(
+            self.date_input.get_text(),
+        )
+        .ok_or_else(|| {
+            format!(
+                "invalid author date, expected: {}",
+                strings::commit_author_date_msg(&self.key_config)
+            )
+        })?;
+
+        Ok(Some(CommitSignature {
+            name,
+            email,
+            time,
+            time_offset,
+        }))
+    }
+
+    fn commit(&mut self) -> Result<()> {
+        // checked first to keep the message on a typo
+        let author = match self.read_author() {
+            Ok(author) => author,
+            Err(e) => {
+                self.queue
+                    .borrow_mut()
+                    .push_back(InternalEvent::ShowErrorMsg(e));
+                return Ok(());
+            }
+        };
+
+        let msg = self.take_msg();
+        self.commit_msg(msg, author.as_ref())?;
+
+        Ok(())
+    }
+
+    /// push is only started if the commit went through,
+    /// a failing push leaves the new commit in place
+    fn commit_and_push(&mut self) -> Result<()> {
+        let msg = self.take_msg();
+
+        if self.commit_msg(msg, None)? {
+            if let Some(branch) = self.git_branch_name.last() {
+                self.queue
+                    .borrow_mut()
+                    .push_back(InternalEvent::Push(branch, false));
+            }
+        }
+
+        Ok(())
     }
 
-    fn commit_msg(&mut self, msg: String) -> Result<()> {
+    /// `Co-authored-by` trailer for `author` (`Name <email>`)
+    pub fn add_co_author(&mut self, author: &str) {
+        let msg = sync::add_trailer(
+            self.input.get_text(),
+            TRAILER_CO_AUTHORED_BY,
+            author,
+        );
+        self.input.set_text(msg);
+    }
+
+    /// returns `true` if the commit was created, an `author` replaces
+    /// the one of an amended commit
+    fn commit_msg(
+        &mut self,
+        msg: String,
+        author: Option<&CommitSignature>,
+    ) -> Result<bool> {
+        let msg = if self.signoff {
+            sync::add_trailer(
+                &msg,
+                TRAILER_SIGNED_OFF_BY,
+                &sync::get_signoff(CWD)?,
+            )
+        } else {
+            msg
+        };
+
         if let HookResult::NotOk(e) = sync::hooks_pre_commit(CWD)? {
             log::error!("pre-commit hook error: {}", e);
             self.queue.borrow_mut().push_back(
@@ -240,7 +828,7 @@ impl CommitComponent {
                     e
                 )),
             );
-            return Ok(());
+            return Ok(false);
         }
         let mut msg = msg;
         if let HookResult::NotOk(e) =
@@ -253,12 +841,12 @@ impl CommitComponent {
                     e
                 )),
             );
-            return Ok(());
+            return Ok(false);
         }
 
         let res = self.amend.map_or_else(
             || sync::commit(CWD, &msg),
-            |amend| sync::amend(CWD, amend, &msg),
+            |amend| sync::amend(CWD, amend, &msg, author),
         );
         if let Err(e) = res {
             log::error!("commit error: {}", &e);
@@ -268,7 +856,7 @@ impl CommitComponent {
                     &e
                 )),
             );
-            return Ok(());
+            return Ok(false);
         }
 
         if let HookResult::NotOk(e) = sync::hooks_post_commit(CWD)? {
@@ -287,32 +875,123 @@ impl CommitComponent {
             .borrow_mut()
             .push_back(InternalEvent::Update(NeedsUpdate::ALL));
 
-        Ok(())
+        Ok(true)
     }
 
     fn can_commit(&self) -> bool {
         !self.input.get_text().is_empty()
     }
 
+    /// unless a message was typed
     fn can_amend(&self) -> bool {
+        let msg = self.input.get_text();
         self.amend.is_none()
             && sync::get_head(CWD).is_ok()
-            && self.input.get_text().is_empty()
+            && (msg.is_empty() || Some(msg) == self.prepared.as_ref())
     }
 
-    fn amend(&mut self) -> Result<()> {
+    /// amends HEAD with the stage right away, reusing its message
+    /// (`git commit --amend --no-edit`), hooks run like on commit
+    pub fn amend_no_edit(&mut self) -> Result<()> {
         let id = sync::get_head(CWD)?;
+        let details = sync::get_commit_details(CWD, id)?;
+        let msg = details
+            .message
+            .map(CommitMessage::combine)
+            .unwrap_or_default();
+
+        let msg = match self
+            .prepare_msg(&msg, Some(PrepareCommitMsgSource::Amend))?
+        {
+            Some(msg) => self.finish_msg(msg),
+            None => return Ok(()),
+        };
+
         self.amend = Some(id);
+        let res = self.commit_msg(msg, None);
+        self.amend = None;
 
-        let details = sync::get_commit_details(CWD, id)?;
+        res.map(|_| ())
+    }
 
-        self.input
-            .set_title(strings::commit_title_amend(&self.key_config));
+    /// `with_author` shows the fields to change author and date
+    fn amend(&mut self, with_author: bool) -> Result<()> {
+        let id = sync::get_head(CWD)?;
+        let details = sync::get_commit_details(CWD, id)?;
 
-        if let Some(msg) = details.message {
-            self.input.set_text(msg.combine());
+        let msg = details
+            .message
+            .map(CommitMessage::combine)
+            .unwrap_or_default();
+        if let Some(msg) = self
+            .prepare_msg(&msg, Some(PrepareCommitMsgSource::Amend))?
+        {
+            self.amend = Some(id);
+            if with_author {
+                let author = details.author;
+                self.author_input.set_text(format!(
+                    "{} <{}>",
+                    author.name, author.email
+                ));
+                self.date_input.set_text(time_to_string_in(
+                    author.time,
+                    author.time_offset,
+                    false,
+                    TimeZoneDisplay::Commit,
+                ));
+                self.author_input.show()?;
+                self.date_input.show()?;
+                self.amend_author = true;
+                self.input.set_title(
+                    strings::commit_title_amend_advanced(
+                        &self.key_config,
+                    ),
+                );
+            } else {
+                self.input.set_title(strings::commit_title_amend(
+                    &self.key_config,
+                ));
+            }
+            self.input.set_text(msg);
         }
 
         Ok(())
     }
 }
+
+/// `Name <email>`
+fn parse_author(s: &str) -> Option<(String, String)> {
+    let s = s.trim();
+    let start = s.rfind('<')?;
+    if !s.ends_with('>') {
+        return None;
+    }
+
+    let name = s[..start].trim();
+    let email = s[start + 1..s.len() - 1].trim();
+    if name.is_empty() || email.is_empty() {
+        None
+    } else {
+        Some((name.to_string(), email.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_author;
+
+    #[test]
+    fn test_parse_author() {
+        assert_eq!(
+            parse_author(" Jane Doe <jane@doe.org> "),
+            Some((
+                String::from("Jane Doe"),
+                String::from("jane@doe.org")
+            ))
+        );
+        assert_eq!(parse_author("Jane Doe"), None);
+        assert_eq!(parse_author("<jane@doe.org>"), None);
+        assert_eq!(parse_author("Jane <>"), None);
+        assert_eq!(parse_author("Jane <jane@doe.org"), None);
+    }
+}
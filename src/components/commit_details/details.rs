@@ -1,16 +1,17 @@
 use crate::{
     components::{
-        dialog_paragraph, utils::time_to_string, CommandBlocking,
+        dialog_paragraph, utils::time_to_string_in, CommandBlocking,
         CommandInfo, Component, DrawableComponent, ScrollType,
     },
     keys::SharedKeyConfig,
+    options::SharedOptions,
     strings::{self, order},
     ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
-use asyncgit::{
-    sync::{self, CommitDetails, CommitId, CommitMessage},
-    CWD,
+use asyncgit::sync::{
+    self, CommitDetails, CommitMessage, CommitSignature,
+    SignatureInfo,
 };
 use crossterm::event::Event;
 use itertools::Itertools;
@@ -39,6 +40,7 @@ pub struct DetailsComponent {
     current_size: Cell<(u16, u16)>,
     scroll_top: Cell<usize>,
     key_config: SharedKeyConfig,
+    options: SharedOptions,
 }
 
 type WrappedCommitMessage<'a> =
@@ -49,6 +51,7 @@ impl DetailsComponent {
     pub const fn new(
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
         focused: bool,
     ) -> Self {
         Self {
@@ -59,20 +62,25 @@ impl DetailsComponent {
             current_size: Cell::new((0, 0)),
             scroll_top: Cell::new(0),
             key_config,
+            options,
         }
     }
 
     pub fn set_commit(
         &mut self,
-        id: Option<CommitId>,
+        details: Option<CommitDetails>,
         tags: Option<CommitTags>,
     ) -> Result<()> {
         self.tags.clear();
 
-        self.data =
-            id.and_then(|id| sync::get_commit_details(CWD, id).ok());
+        let changed = self.data.as_ref().map(|data| &data.hash)
+            != details.as_ref().map(|data| &data.hash);
 
-        self.scroll_top.set(0);
+        self.data = details;
+
+        if changed {
+            self.scroll_top.set(0);
+        }
 
         if let Some(tags) = tags {
             self.tags.extend(tags)
@@ -180,7 +188,44 @@ impl DetailsComponent {
         }
     }
 
+    fn get_signature_line(
+        &self,
+        signature: &SignatureInfo,
+    ) -> Spans<'_> {
+        Spans::from(vec![
+            Span::styled(
+                Cow::from(strings::commit::details_signature(
+                    &self.key_config,
+                )),
+                self.theme.text(false, false),
+            ),
+            Span::styled(
+                Cow::from(strings::commit::details_signature_badge(
+                    &self.key_config,
+                    signature,
+                )),
+                self.theme.commit_signature(signature.status),
+            ),
+            Span::styled(
+                Cow::from(strings::commit::details_signer(
+                    &self.key_config,
+                    signature,
+                )),
+                self.theme.text(true, false),
+            ),
+        ])
+    }
+
     #[allow(unstable_name_collisions)]
+    fn format_time(&self, signature: &CommitSignature) -> String {
+        time_to_string_in(
+            signature.time,
+            signature.time_offset,
+            false,
+            self.options.time_zone.get(),
+        )
+    }
+
     fn get_text_info(&self) -> Vec<Spans> {
         if let Some(ref data) = self.data {
             let mut res = vec![
@@ -197,10 +242,7 @@ impl DetailsComponent {
                 Spans::from(vec![
                     self.style_detail(&Detail::Date),
                     Span::styled(
-                        Cow::from(time_to_string(
-                            data.author.time,
-                            false,
-                        )),
+                        Cow::from(self.format_time(&data.author)),
                         self.theme.text(true, false),
                     ),
                 ]),
@@ -221,10 +263,7 @@ impl DetailsComponent {
                     Spans::from(vec![
                         self.style_detail(&Detail::Date),
                         Span::styled(
-                            Cow::from(time_to_string(
-                                committer.time,
-                                false,
-                            )),
+                            Cow::from(self.format_time(committer)),
                             self.theme.text(true, false),
                         ),
                     ]),
@@ -244,25 +283,41 @@ impl DetailsComponent {
                 ),
             ]));
 
+            if let Some(ref signature) = data.signature {
+                res.push(self.get_signature_line(signature));
+            }
+
+            if let Some(ref encoding) = data.unsupported_encoding {
+                res.push(Spans::from(Span::styled(
+                    Cow::from(
+                        strings::commit::details_unsupported_encoding(
+                            &self.key_config,
+                            encoding,
+                        ),
+                    ),
+                    self.theme.text_danger(),
+                )));
+            }
+
             if !self.tags.is_empty() {
                 res.push(Spans::from(
                     self.style_detail(&Detail::Sha),
                 ));
 
                 res.push(Spans::from(
-                    self.tags
-                        .iter()
-                        .map(|tag| {
+                    Itertools::intersperse(
+                        self.tags.iter().map(|tag| {
                             Span::styled(
                                 Cow::from(tag),
                                 self.theme.text(true, false),
                             )
-                        })
-                        .intersperse(Span::styled(
+                        }),
+                        Span::styled(
                             Cow::from(","),
                             self.theme.text(true, false),
-                        ))
-                        .collect::<Vec<Span>>(),
+                        ),
+                    )
+                    .collect::<Vec<Span>>(),
                 ));
             }
 
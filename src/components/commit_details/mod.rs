@@ -1,17 +1,22 @@
 mod details;
 
 use super::{
-    command_pump, event_pump, CommandBlocking, CommandInfo,
-    Component, DrawableComponent, FileTreeComponent,
+    command_pump, event_pump, BlameRevision, CommandBlocking,
+    CommandInfo, Component, DrawableComponent, FileTreeComponent,
 };
 use crate::{
-    accessors, keys::SharedKeyConfig, queue::Queue, strings,
+    accessors,
+    keys::SharedKeyConfig,
+    options::SharedOptions,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
     ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
-    sync::{CommitId, CommitTags},
-    AsyncCommitFiles, AsyncNotification,
+    sync::{CommitId, CommitTags, MergeDiff},
+    AsyncCommitDetails, AsyncCommitFiles, AsyncNotification,
+    StatusItem,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
@@ -25,7 +30,15 @@ use tui::{
 pub struct CommitDetailsComponent {
     details: DetailsComponent,
     file_tree: FileTreeComponent,
+    /// untracked files of a stash, only shown if there are any
+    untracked_tree: FileTreeComponent,
+    /// the untracked files are the current file list
+    untracked_selected: bool,
+    /// what the files of a merge commit are listed against
+    merge: MergeDiff,
+    queue: Queue,
     git_commit_files: AsyncCommitFiles,
+    git_commit_details: AsyncCommitDetails,
     visible: bool,
     key_config: SharedKeyConfig,
 }
@@ -39,61 +52,126 @@ impl CommitDetailsComponent {
         sender: &Sender<AsyncNotification>,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
         Self {
             details: DetailsComponent::new(
                 theme.clone(),
                 key_config.clone(),
+                options,
                 false,
             ),
             git_commit_files: AsyncCommitFiles::new(sender),
+            git_commit_details: AsyncCommitDetails::new(sender),
             file_tree: FileTreeComponent::new(
+                "",
+                false,
+                Some(queue.clone()),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            untracked_tree: FileTreeComponent::new(
                 "",
                 false,
                 Some(queue.clone()),
                 theme,
                 key_config.clone(),
             ),
+            untracked_selected: false,
+            merge: MergeDiff::default(),
+            queue: queue.clone(),
             visible: false,
             key_config,
         }
     }
 
-    fn get_files_title(&self) -> String {
-        let files_count = self.file_tree.file_count();
+    /// applies with the next `set_commit`
+    pub fn set_merge_diff(&mut self, merge: MergeDiff) {
+        self.merge = merge;
+    }
+
+    fn has_untracked(&self) -> bool {
+        !self.untracked_tree.is_empty()
+    }
 
-        format!(
+    fn update_titles(&mut self) {
+        let files_title = if self.has_untracked() {
+            strings::commit::details_tracked_files_title(
+                &self.key_config,
+            )
+        } else {
+            strings::commit::details_files_title(&self.key_config)
+        };
+
+        self.file_tree.set_title(format!(
+            "{} {}",
+            files_title,
+            self.file_tree.file_count()
+        ));
+        self.untracked_tree.set_title(format!(
             "{} {}",
-            strings::commit::details_files_title(&self.key_config),
-            files_count
-        )
+            strings::commit::details_untracked_files_title(
+                &self.key_config
+            ),
+            self.untracked_tree.file_count()
+        ));
     }
 
-    ///
+    /// details of the commits in `prefetch` are fetched ahead
+    /// (cached until they are not part of it anymore)
     pub fn set_commit(
         &mut self,
         id: Option<CommitId>,
         tags: Option<CommitTags>,
+        prefetch: &[CommitId],
     ) -> Result<()> {
-        self.details.set_commit(id, tags)?;
+        let details = if let Some(id) = id {
+            let mut wanted = vec![id];
+            wanted.extend(prefetch.iter().filter(|p| **p != id));
+
+            self.git_commit_details.fetch(&wanted)?;
+            self.git_commit_details.retain(&wanted)?;
+            self.git_commit_details.verify_signature(id)?;
+            self.git_commit_details.get(id)?
+        } else {
+            None
+        };
+
+        self.details.set_commit(details, tags)?;
+
+        if let Some(id) = id {
+            self.file_tree.enable_blame(BlameRevision::Commit(id));
+        }
 
         if let Some(id) = id {
-            if let Some((fetched_id, res)) =
+            if let Some((fetched, res)) =
                 self.git_commit_files.current()?
             {
-                if fetched_id == id {
-                    self.file_tree.update(res.as_slice())?;
-                    self.file_tree.set_title(self.get_files_title());
+                if fetched == (id, self.merge) {
+                    self.file_tree.update(res.files.as_slice())?;
+                    self.untracked_tree
+                        .update(res.untracked.as_slice())?;
+                    if !self.has_untracked()
+                        && self.untracked_selected
+                    {
+                        self.untracked_selected = false;
+                        if self.untracked_tree.focused() {
+                            self.untracked_tree.focus(false);
+                            self.file_tree.focus(true);
+                        }
+                    }
+                    self.update_titles();
 
                     return Ok(());
                 }
             }
 
             self.file_tree.clear()?;
-            self.git_commit_files.fetch(id)?;
+            self.untracked_tree.clear()?;
+            self.git_commit_files.fetch(id, self.merge)?;
         }
 
-        self.file_tree.set_title(self.get_files_title());
+        self.update_titles();
 
         Ok(())
     }
@@ -101,11 +179,16 @@ impl CommitDetailsComponent {
     ///
     pub fn any_work_pending(&self) -> bool {
         self.git_commit_files.is_pending()
+            || self.git_commit_details.is_pending()
     }
 
-    ///
-    pub const fn files(&self) -> &FileTreeComponent {
-        &self.file_tree
+    /// selected file of the focused file list
+    pub fn selection_file(&self) -> Option<StatusItem> {
+        if self.untracked_selected {
+            self.untracked_tree.selection_file()
+        } else {
+            self.file_tree.selection_file()
+        }
     }
 }
 
@@ -115,7 +198,9 @@ impl DrawableComponent for CommitDetailsComponent {
         f: &mut Frame<B>,
         rect: Rect,
     ) -> Result<()> {
-        let percentages = if self.file_tree.focused() {
+        let percentages = if self.file_tree.focused()
+            || self.untracked_tree.focused()
+        {
             (40, 60)
         } else if self.details.focused() {
             (60, 40)
@@ -135,7 +220,24 @@ impl DrawableComponent for CommitDetailsComponent {
             .split(rect);
 
         self.details.draw(f, chunks[0])?;
-        self.file_tree.draw(f, chunks[1])?;
+
+        if self.has_untracked() {
+            let files_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [
+                        Constraint::Percentage(50),
+                        Constraint::Percentage(50),
+                    ]
+                    .as_ref(),
+                )
+                .split(chunks[1]);
+
+            self.file_tree.draw(f, files_chunks[0])?;
+            self.untracked_tree.draw(f, files_chunks[1])?;
+        } else {
+            self.file_tree.draw(f, chunks[1])?;
+        }
 
         Ok(())
     }
@@ -153,6 +255,10 @@ impl Component for CommitDetailsComponent {
                 force_all,
                 self.components().as_slice(),
             );
+
+            if self.untracked_tree.focused() {
+                self.untracked_tree.commands(out, false);
+            }
         }
 
         CommandBlocking::PassingOn
@@ -163,6 +269,10 @@ impl Component for CommitDetailsComponent {
             return Ok(true);
         }
 
+        if self.untracked_tree.event(ev)? {
+            return Ok(true);
+        }
+
         if self.focused() {
             if let Event::Key(e) = ev {
                 return if e == self.key_config.focus_below
@@ -171,6 +281,27 @@ impl Component for CommitDetailsComponent {
                     self.details.focus(false);
                     self.file_tree.focus(true);
                     Ok(true)
+                } else if e == self.key_config.focus_below
+                    && self.file_tree.focused()
+                    && self.has_untracked()
+                {
+                    self.file_tree.focus(false);
+                    self.untracked_tree.focus(true);
+                    self.untracked_selected = true;
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::Update(NeedsUpdate::DIFF),
+                    );
+                    Ok(true)
+                } else if e == self.key_config.focus_above
+                    && self.untracked_tree.focused()
+                {
+                    self.untracked_tree.focus(false);
+                    self.file_tree.focus(true);
+                    self.untracked_selected = false;
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::Update(NeedsUpdate::DIFF),
+                    );
+                    Ok(true)
                 } else if e == self.key_config.focus_above
                     && self.file_tree.focused()
                 {
@@ -198,11 +329,20 @@ impl Component for CommitDetailsComponent {
     }
 
     fn focused(&self) -> bool {
-        self.details.focused() || self.file_tree.focused()
+        self.details.focused()
+            || self.file_tree.focused()
+            || self.untracked_tree.focused()
     }
     fn focus(&mut self, focus: bool) {
         self.details.focus(false);
-        self.file_tree.focus(focus);
-        self.file_tree.show_selection(true);
+
+        let (current, other) = if self.untracked_selected {
+            (&mut self.untracked_tree, &mut self.file_tree)
+        } else {
+            (&mut self.file_tree, &mut self.untracked_tree)
+        };
+        other.focus(false);
+        current.focus(focus);
+        current.show_selection(true);
     }
 }
@@ -1,19 +1,31 @@
-use super::utils::logitems::{ItemBatch, LogEntry};
+use super::utils::{
+    logitems::{ItemBatch, LogEntry},
+    time_to_relative_string, time_to_string_in,
+};
 use crate::{
     components::{
         CommandBlocking, CommandInfo, Component, DrawableComponent,
         ScrollType,
     },
     keys::SharedKeyConfig,
+    options::{LogColumns, Options, SharedOptions},
     strings,
     ui::calc_scroll_top,
     ui::style::{SharedTheme, Theme},
 };
 use anyhow::Result;
-use asyncgit::sync::Tags;
+use asyncgit::sync::{
+    CommitId, CommitRef, GraphCell, GraphRow, Tags,
+};
+use chrono::Utc;
 use crossterm::event::Event;
 use std::{
-    borrow::Cow, cell::Cell, cmp, convert::TryFrom, time::Instant,
+    borrow::Cow,
+    cell::Cell,
+    cmp,
+    collections::{BTreeMap, HashSet},
+    convert::TryFrom,
+    time::Instant,
 };
 use tui::{
     backend::Backend,
@@ -25,6 +37,16 @@ use tui::{
 use unicode_width::UnicodeWidthStr;
 
 const ELEMENTS_PER_LINE: usize = 10;
+/// lanes of the graph further right are cut off
+const GRAPH_MAX_LANES: usize = 12;
+/// width of `%Y-%m-%d`
+const DATE_WIDTH: usize = 10;
+/// width of the longest relative date ("11mo ago")
+const RELATIVE_DATE_WIDTH: usize = 8;
+const AUTHOR_MIN_WIDTH: usize = 3;
+const AUTHOR_MAX_WIDTH: usize = 20;
+/// columns are left out before the message gets narrower
+const MSG_MIN_WIDTH: usize = 20;
 
 ///
 pub struct CommitList {
@@ -32,6 +54,20 @@ pub struct CommitList {
     selection: usize,
     branch: Option<String>,
     count_total: usize,
+    no_merges: bool,
+    /// walked from all branches instead of HEAD
+    all_branches: bool,
+    first_parent: bool,
+    /// state of the log search, shown in the title
+    search_title: Option<String>,
+    /// commits matching the log search
+    highlights: Option<HashSet<CommitId>>,
+    /// to be compared with another commit
+    marked: Option<CommitId>,
+    /// commits marked to be acted on together, by index in the log
+    marks: BTreeMap<usize, CommitId>,
+    /// index of the commit toggled last, a range gets marked from here
+    mark_anchor: Option<usize>,
     items: ItemBatch,
     scroll_state: (Instant, f32),
     tags: Option<Tags>,
@@ -39,6 +75,7 @@ pub struct CommitList {
     scroll_top: Cell<usize>,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
+    options: SharedOptions,
 }
 
 impl CommitList {
@@ -47,18 +84,28 @@ impl CommitList {
         title: &str,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
         Self {
             items: ItemBatch::default(),
             selection: 0,
             branch: None,
             count_total: 0,
+            no_merges: false,
+            all_branches: false,
+            first_parent: false,
+            search_title: None,
+            highlights: None,
+            marked: None,
+            marks: BTreeMap::new(),
+            mark_anchor: None,
             scroll_state: (Instant::now(), 0_f32),
             tags: None,
             current_size: Cell::new((0, 0)),
             scroll_top: Cell::new(0),
             theme,
             key_config,
+            options,
             title: String::from(title),
         }
     }
@@ -83,6 +130,141 @@ impl CommitList {
         self.current_size.get()
     }
 
+    ///
+    pub const fn no_merges(&self) -> bool {
+        self.no_merges
+    }
+
+    /// merge commits are hidden from the list
+    pub fn set_no_merges(&mut self, no_merges: bool) {
+        self.no_merges = no_merges;
+        self.reset();
+    }
+
+    ///
+    pub const fn all_branches(&self) -> bool {
+        self.all_branches
+    }
+
+    /// the log is walked from all branches instead of HEAD
+    pub fn set_all_branches(&mut self, all_branches: bool) {
+        self.all_branches = all_branches;
+        self.reset();
+    }
+
+    ///
+    pub const fn first_parent(&self) -> bool {
+        self.first_parent
+    }
+
+    /// only the first parent of merge commits is followed
+    pub fn set_first_parent(&mut self, first_parent: bool) {
+        self.first_parent = first_parent;
+        self.reset();
+    }
+
+    /// back to the top for a log walked anew (with other filters)
+    pub fn reset(&mut self) {
+        self.selection = 0;
+        self.scroll_top.set(0);
+        self.items.clear();
+        self.clear_marks();
+    }
+
+    ///
+    pub fn set_search_title(&mut self, title: Option<String>) {
+        self.search_title = title;
+    }
+
+    /// messages of these commits get highlighted
+    pub fn set_highlights(
+        &mut self,
+        highlights: Option<HashSet<CommitId>>,
+    ) {
+        self.highlights = highlights;
+    }
+
+    ///
+    pub const fn marked(&self) -> Option<CommitId> {
+        self.marked
+    }
+
+    ///
+    pub fn set_marked(&mut self, marked: Option<CommitId>) {
+        self.marked = marked;
+    }
+
+    /// marks the selected commit, unmarks it if marked already
+    pub fn toggle_mark(&mut self) -> bool {
+        if let Some(id) = self.selected_entry().map(|e| e.id) {
+            let marked = self
+                .marks
+                .iter()
+                .find(|(_, marked)| **marked == id)
+                .map(|(idx, _)| *idx);
+
+            if let Some(idx) = marked {
+                self.marks.remove(&idx);
+            } else {
+                self.marks.insert(self.selection, id);
+            }
+
+            self.mark_anchor = Some(self.selection);
+            return true;
+        }
+
+        false
+    }
+
+    /// marks the loaded commits from the one toggled last to the
+    /// selected one
+    pub fn mark_range(&mut self) -> bool {
+        let anchor = match self.mark_anchor {
+            Some(anchor) => anchor,
+            None => return self.toggle_mark(),
+        };
+
+        let (from, to) = if anchor < self.selection {
+            (anchor, self.selection)
+        } else {
+            (self.selection, anchor)
+        };
+        let offset = self.items.index_offset();
+
+        for (idx, e) in self.items.iter().enumerate() {
+            let idx = idx + offset;
+            if idx >= from
+                && idx <= to
+                && !self.marks.values().any(|id| *id == e.id)
+            {
+                self.marks.insert(idx, e.id);
+            }
+        }
+
+        true
+    }
+
+    /// marked commits, oldest first
+    pub fn marks(&self) -> Vec<CommitId> {
+        self.marks.values().rev().copied().collect()
+    }
+
+    ///
+    pub fn clear_marks(&mut self) {
+        self.marks.clear();
+        self.mark_anchor = None;
+    }
+
+    /// selects the commit at `index` of the log if loaded already
+    pub fn select(&mut self, index: usize) -> bool {
+        if index < self.count_total {
+            self.selection = index;
+            true
+        } else {
+            false
+        }
+    }
+
     ///
     pub fn set_count_total(&mut self, total: usize) {
         self.count_total = total;
@@ -118,6 +300,28 @@ impl CommitList {
         )
     }
 
+    /// selected commit and up to `count` loaded ones before and
+    /// after it, nearest first
+    pub fn ids_around_selection(
+        &self,
+        count: usize,
+    ) -> Vec<CommitId> {
+        let selection = self.relative_selection();
+        let items = self.items.iter().collect::<Vec<_>>();
+
+        let mut res = Vec::with_capacity(count * 2 + 1);
+        res.extend(items.get(selection).map(|e| e.id));
+
+        for distance in 1..=count {
+            res.extend(items.get(selection + distance).map(|e| e.id));
+            if let Some(idx) = selection.checked_sub(distance) {
+                res.extend(items.get(idx).map(|e| e.id));
+            }
+        }
+
+        res
+    }
+
     pub fn copy_entry_hash(&self) -> Result<()> {
         if let Some(e) = self.items.iter().nth(
             self.selection.saturating_sub(self.items.index_offset()),
@@ -188,12 +392,48 @@ impl CommitList {
         self.scroll_state.1 = speed.min(SCROLL_SPEED_MAX);
     }
 
+    /// date and author columns that fit into `width`, the author
+    /// is left out first
+    fn fitting_columns(
+        columns: LogColumns,
+        width: usize,
+    ) -> (bool, bool) {
+        let hash = if columns.hash_length > 0 {
+            columns.hash_length + 1
+        } else {
+            0
+        };
+        let date = Self::date_width(columns) + 1;
+        let fits = |w: usize| hash + w + MSG_MIN_WIDTH <= width;
+
+        let show_date = columns.date && fits(date);
+        let show_author = columns.author
+            && show_date == columns.date
+            && fits(
+                AUTHOR_MIN_WIDTH
+                    + 1
+                    + if show_date { date } else { 0 },
+            );
+
+        (show_date, show_author)
+    }
+
+    const fn date_width(columns: LogColumns) -> usize {
+        if columns.relative_date {
+            RELATIVE_DATE_WIDTH
+        } else {
+            DATE_WIDTH
+        }
+    }
+
+    /// `now` is what relative dates are relative to
     fn get_entry_to_add<'a>(
         e: &'a LogEntry,
         selected: bool,
-        tags: Option<String>,
         theme: &Theme,
         width: usize,
+        options: &Options,
+        now: i64,
     ) -> Spans<'a> {
         let mut txt: Vec<Span> = Vec::new();
         txt.reserve(ELEMENTS_PER_LINE);
@@ -202,59 +442,168 @@ impl CommitList {
         let splitter =
             Span::styled(splitter_txt, theme.text(true, selected));
 
-        // commit hash
-        txt.push(Span::styled(
-            Cow::from(e.hash_short.as_str()),
-            theme.commit_hash(selected),
-        ));
+        let columns = options.log_columns;
+        let (show_date, show_author) =
+            Self::fitting_columns(columns, width);
+        let mut used = 0;
 
-        txt.push(splitter.clone());
+        // commit hash
+        if columns.hash_length > 0 {
+            let hash = if columns.hash_length <= e.hash_short.len() {
+                Cow::from(&e.hash_short[..columns.hash_length])
+            } else {
+                Cow::from(
+                    e.id.to_string()
+                        .chars()
+                        .take(columns.hash_length)
+                        .collect::<String>(),
+                )
+            };
+            used += hash.len() + 1;
+            txt.push(Span::styled(hash, theme.commit_hash(selected)));
+            txt.push(splitter.clone());
+        }
 
         // commit timestamp
-        txt.push(Span::styled(
-            Cow::from(e.time.as_str()),
-            theme.commit_time(selected),
-        ));
-
-        txt.push(splitter.clone());
-
-        let author_width =
-            (width.saturating_sub(19) / 3).max(3).min(20);
-        let author = string_width_align(&e.author, author_width);
+        if show_date {
+            let date = if columns.relative_date {
+                format!(
+                    "{:w$}",
+                    time_to_relative_string(e.time.0, now),
+                    w = RELATIVE_DATE_WIDTH
+                )
+            } else {
+                time_to_string_in(
+                    e.time.0,
+                    e.time.1,
+                    true,
+                    options.time_zone.get(),
+                )
+            };
+            used += Self::date_width(columns) + 1;
+            txt.push(Span::styled(
+                Cow::from(date),
+                theme.commit_time(selected),
+            ));
+            txt.push(splitter.clone());
+        }
 
         // commit author
-        txt.push(Span::styled::<String>(
-            author,
-            theme.commit_author(selected),
-        ));
-
-        txt.push(splitter.clone());
-
-        // commit tags
-        txt.push(Span::styled(
-            Cow::from(if let Some(tags) = tags {
-                format!(" {}", tags)
-            } else {
-                String::from("")
-            }),
-            theme.tags(selected),
-        ));
+        if show_author {
+            let author_width = (width.saturating_sub(used) / 3)
+                .clamp(AUTHOR_MIN_WIDTH, AUTHOR_MAX_WIDTH);
+            txt.push(Span::styled::<String>(
+                string_width_align(&e.author, author_width),
+                theme.commit_author(selected),
+            ));
+            txt.push(splitter.clone());
+        }
 
-        txt.push(splitter);
+        // branches, tags and HEAD
+        for commit_ref in &e.refs {
+            txt.push(Span::styled(
+                Cow::from(format!(
+                    " {} ",
+                    Self::ref_label(commit_ref)
+                )),
+                theme.commit_ref(commit_ref),
+            ));
+            txt.push(splitter.clone());
+        }
 
         // commit msg
         txt.push(Span::styled(
-            Cow::from(e.msg.as_str()),
+            truncate_msg(&e.msg, options.commit_subject_length),
             theme.text(true, selected),
         ));
         Spans::from(txt)
     }
 
+    fn ref_label(commit_ref: &CommitRef) -> Cow<'_, str> {
+        match commit_ref {
+            CommitRef::Head(Some(branch)) => {
+                Cow::from(format!("HEAD -> {}", branch))
+            }
+            CommitRef::Head(None) => Cow::from("HEAD"),
+            CommitRef::LocalBranch(name)
+            | CommitRef::RemoteBranch(name)
+            | CommitRef::Tag(name) => Cow::from(name.as_str()),
+        }
+    }
+
+    const fn graph_symbol(cell: GraphCell) -> char {
+        if cell.commit {
+            return '\u{25cf}';
+        }
+
+        match (cell.up, cell.down, cell.left, cell.right) {
+            (true, true, true, true) => '\u{253c}',
+            (true, true, true, false) => '\u{2524}',
+            (true, true, false, true) => '\u{251c}',
+            (true, false, true, true) => '\u{2534}',
+            (false, true, true, true) => '\u{252c}',
+            (true, false, true, false) => '\u{256f}',
+            (true, false, false, true) => '\u{2570}',
+            (false, true, true, false) => '\u{256e}',
+            (false, true, false, true) => '\u{256d}',
+            (false, false, true, _) | (false, false, _, true) => {
+                '\u{2500}'
+            }
+            (true, _, _, _) | (_, true, _, _) => '\u{2502}',
+            _ => ' ',
+        }
+    }
+
+    /// two columns per lane: the lane and what joins it to the next
+    fn get_graph_spans(
+        row: &GraphRow,
+        lanes: usize,
+        theme: &Theme,
+        selected: bool,
+    ) -> Vec<Span<'static>> {
+        let commit_lane = row.commit_lane().unwrap_or_default();
+
+        (0..lanes)
+            .map(|lane| {
+                let cell =
+                    row.cells.get(lane).copied().unwrap_or_default();
+                // the joining line takes the color of the lane it
+                // leads to
+                let joined =
+                    if lane < commit_lane { lane } else { lane + 1 };
+
+                Span::styled(
+                    format!(
+                        "{}{}",
+                        Self::graph_symbol(cell),
+                        if cell.right { '\u{2500}' } else { ' ' }
+                    ),
+                    theme.graph_lane(
+                        if cell.right { joined } else { lane },
+                        selected,
+                    ),
+                )
+            })
+            .collect()
+    }
+
     fn get_text(&self, height: usize, width: usize) -> Vec<Spans> {
         let selection = self.relative_selection();
 
         let mut txt: Vec<Spans> = Vec::with_capacity(height);
 
+        let graph_lanes = self
+            .items
+            .iter()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .filter_map(|e| e.graph.as_ref().map(|g| g.cells.len()))
+            .max()
+            .unwrap_or_default()
+            .min(GRAPH_MAX_LANES);
+
+        let now = Utc::now().timestamp();
+
         for (idx, e) in self
             .items
             .iter()
@@ -262,18 +611,45 @@ impl CommitList {
             .take(height)
             .enumerate()
         {
-            let tags = self
-                .tags
-                .as_ref()
-                .and_then(|t| t.get(&e.id))
-                .map(|tags| tags.join(" "));
-            txt.push(Self::get_entry_to_add(
+            let selected = idx + self.scroll_top.get() == selection;
+            let mut entry = Self::get_entry_to_add(
                 e,
-                idx + self.scroll_top.get() == selection,
-                tags,
+                selected,
                 &self.theme,
                 width,
-            ));
+                &self.options,
+                now,
+            );
+
+            if self.marked == Some(e.id)
+                || self.marks.values().any(|id| *id == e.id)
+            {
+                if let Some(hash) = entry.0.first_mut() {
+                    hash.style = self.theme.commit_marked(selected);
+                }
+            }
+            if self
+                .highlights
+                .as_ref()
+                .map_or(false, |ids| ids.contains(&e.id))
+            {
+                if let Some(msg) = entry.0.last_mut() {
+                    msg.style = self.theme.search_match(selected);
+                }
+            }
+
+            txt.push(if let Some(graph) = &e.graph {
+                let mut spans = Self::get_graph_spans(
+                    graph,
+                    graph_lanes,
+                    &self.theme,
+                    selected,
+                );
+                spans.extend(entry.0);
+                Spans::from(spans)
+            } else {
+                entry
+            });
         }
 
         txt
@@ -310,11 +686,31 @@ impl DrawableComponent for CommitList {
             self.branch.as_ref().map(|b| format!("- {{{}}}", b));
 
         let title = format!(
-            "{} {}/{} {}",
+            "{} {}/{} {}{}{}{}{}{}",
             self.title,
             self.count_total.saturating_sub(self.selection),
             self.count_total,
             branch_post_fix.as_deref().unwrap_or(""),
+            if self.all_branches {
+                strings::log_title_all_branches(&self.key_config)
+            } else {
+                String::new()
+            },
+            if self.first_parent {
+                strings::log_title_first_parent(&self.key_config)
+            } else {
+                String::new()
+            },
+            if self.no_merges {
+                strings::log_title_no_merges(&self.key_config)
+            } else {
+                String::new()
+            },
+            self.search_title.as_deref().unwrap_or(""),
+            strings::log_title_time_zone(
+                &self.key_config,
+                self.options.time_zone.get()
+            ),
         );
 
         f.render_widget(
@@ -383,10 +779,10 @@ impl Component for CommitList {
     }
 }
 
+static POSTFIX: &str = "..";
+
 #[inline]
 fn string_width_align(s: &str, width: usize) -> String {
-    static POSTFIX: &str = "..";
-
     let len = UnicodeWidthStr::width(s);
     let width_wo_postfix = width.saturating_sub(POSTFIX.len());
 
@@ -401,6 +797,18 @@ fn string_width_align(s: &str, width: usize) -> String {
     }
 }
 
+/// cuts `s` to at most `max` chars (0 means no limit)
+#[inline]
+fn truncate_msg(s: &str, max: usize) -> Cow<'_, str> {
+    if max == 0 || s.chars().count() <= max {
+        Cow::from(s)
+    } else {
+        let end =
+            find_truncate_point(s, max.saturating_sub(POSTFIX.len()));
+        Cow::from(format!("{}{}", &s[..end], POSTFIX))
+    }
+}
+
 #[inline]
 fn find_truncate_point(s: &str, chars: usize) -> usize {
     s.chars().take(chars).map(char::len_utf8).sum()
@@ -431,4 +839,45 @@ mod tests {
             "Jon Grythe Stødle  "
         );
     }
+
+    #[test]
+    fn test_fitting_columns() {
+        let columns = LogColumns::default();
+        assert_eq!(
+            CommitList::fitting_columns(columns, 80),
+            (true, true)
+        );
+        // hash, date and message
+        assert_eq!(
+            CommitList::fitting_columns(columns, 8 + 11 + 20),
+            (true, false)
+        );
+        assert_eq!(
+            CommitList::fitting_columns(columns, 32),
+            (false, false)
+        );
+        assert_eq!(
+            CommitList::fitting_columns(columns, 20),
+            (false, false)
+        );
+
+        let columns = LogColumns {
+            hash_length: 0,
+            relative_date: true,
+            author: false,
+            ..columns
+        };
+        assert_eq!(
+            CommitList::fitting_columns(columns, 29),
+            (true, false)
+        );
+    }
+
+    #[test]
+    fn test_truncate_msg() {
+        assert_eq!(truncate_msg("fix typo", 0), "fix typo");
+        assert_eq!(truncate_msg("fix typo", 8), "fix typo");
+        assert_eq!(truncate_msg("fix typo", 7), "fix t..");
+        assert_eq!(truncate_msg("überall", 5), "übe..");
+    }
 }
@@ -0,0 +1,192 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DiffComponent, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig, options::SharedOptions, queue::Queue,
+    strings, ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::CommitId, AsyncDiff, AsyncNotification, DiffParams,
+    DiffType,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use tui::{backend::Backend, layout::Rect, widgets::Clear, Frame};
+
+/// combined diff of everything changed between two commits
+pub struct CompareCommitsComponent {
+    /// from, to
+    commits: Option<(CommitId, CommitId)>,
+    diff: DiffComponent,
+    git_diff: AsyncDiff,
+    visible: bool,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for CompareCommitsComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            f.render_widget(Clear, rect);
+            self.diff.draw(f, rect)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for CompareCommitsComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            self.diff.commands(out, force_all);
+
+            out.push(
+                CommandInfo::new(
+                    strings::commands::close_popup(&self.key_config),
+                    true,
+                    true,
+                )
+                .order(1),
+            );
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if self.diff.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup
+                    || e == self.key_config.focus_left
+                {
+                    self.hide();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        self.diff.focus(true);
+
+        Ok(())
+    }
+}
+
+impl CompareCommitsComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+        options: SharedOptions,
+    ) -> Self {
+        Self {
+            commits: None,
+            diff: DiffComponent::new(
+                queue.clone(),
+                sender,
+                theme,
+                key_config.clone(),
+                options,
+                true,
+            ),
+            git_diff: AsyncDiff::new(sender),
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// shows what changed from `from` to `to`
+    pub fn open(
+        &mut self,
+        from: CommitId,
+        to: CommitId,
+    ) -> Result<()> {
+        self.commits = Some((from, to));
+        self.show()?;
+        self.update_diff()
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_diff.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        self.diff.update_git(ev)?;
+
+        if self.visible && ev == AsyncNotification::Diff {
+            self.update_diff()?;
+        }
+
+        Ok(())
+    }
+
+    ///
+    pub fn update_diff(&mut self) -> Result<()> {
+        if let Some(err) = self.git_diff.take_last_error()? {
+            self.diff.show_error(err);
+        }
+
+        if let Some((from, to)) = self.commits {
+            let diff_params = DiffParams {
+                path: String::new(),
+                diff_type: DiffType::Range(from, to),
+                options: self.diff.options(),
+            };
+
+            if let Some((params, last)) = self.git_diff.last()? {
+                if params == diff_params {
+                    self.diff.update_folder(
+                        strings::compare_commits_title(
+                            &self.key_config,
+                            &from.get_short_string(),
+                            &to.get_short_string(),
+                        ),
+                        false,
+                        last,
+                    );
+                    return Ok(());
+                }
+            }
+
+            self.git_diff.request(diff_params)?;
+            self.diff.clear(true)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,263 @@
+use super::{
+    command_pump, event_pump, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DiffComponent, DrawableComponent,
+    FileTreeComponent,
+};
+use crate::{
+    accessors, keys::SharedKeyConfig, options::SharedOptions,
+    queue::Queue, strings, ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::UpstreamDiff, AsyncDiff, AsyncNotification, DiffParams,
+    DiffType,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::Clear,
+    Frame,
+};
+
+/// review of everything a push would publish in one combined diff
+pub struct CompareUpstreamComponent {
+    upstream_diff: Option<UpstreamDiff>,
+    files: FileTreeComponent,
+    diff: DiffComponent,
+    git_diff: AsyncDiff,
+    visible: bool,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for CompareUpstreamComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.is_visible() {
+            let percentages = if self.diff.focused() {
+                (30, 70)
+            } else {
+                (50, 50)
+            };
+
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(
+                    [
+                        Constraint::Percentage(percentages.0),
+                        Constraint::Percentage(percentages.1),
+                    ]
+                    .as_ref(),
+                )
+                .split(rect);
+
+            f.render_widget(Clear, rect);
+
+            self.files.draw(f, chunks[0])?;
+            self.diff.draw(f, chunks[1])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for CompareUpstreamComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            command_pump(
+                out,
+                force_all,
+                self.components().as_slice(),
+            );
+
+            out.push(
+                CommandInfo::new(
+                    strings::commands::close_popup(&self.key_config),
+                    true,
+                    true,
+                )
+                .order(1),
+            );
+
+            out.push(CommandInfo::new(
+                strings::commands::diff_focus_right(&self.key_config),
+                self.can_focus_diff(),
+                !self.diff.focused() || force_all,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::diff_focus_left(&self.key_config),
+                true,
+                self.diff.focused() || force_all,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.is_visible() {
+            if event_pump(ev, self.components_mut().as_mut_slice())? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.focus_right
+                    && self.can_focus_diff()
+                {
+                    self.files.focus(false);
+                    self.diff.focus(true);
+                } else if e == self.key_config.focus_left
+                    && self.diff.focused()
+                {
+                    self.files.focus(true);
+                    self.diff.focus(false);
+                } else if e == self.key_config.focus_left {
+                    self.hide();
+                }
+
+                // stop key event propagation
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        self.files.focus(true);
+        self.diff.focus(false);
+        self.update_diff()?;
+        Ok(())
+    }
+}
+
+impl CompareUpstreamComponent {
+    accessors!(self, [diff, files]);
+
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+        options: SharedOptions,
+    ) -> Self {
+        Self {
+            upstream_diff: None,
+            files: FileTreeComponent::new(
+                "",
+                true,
+                Some(queue.clone()),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            diff: DiffComponent::new(
+                queue.clone(),
+                sender,
+                theme,
+                key_config.clone(),
+                options,
+                true,
+            ),
+            git_diff: AsyncDiff::new(sender),
+            visible: false,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(
+        &mut self,
+        upstream_diff: UpstreamDiff,
+    ) -> Result<()> {
+        self.files.update(&upstream_diff.files)?;
+        self.files.set_title(strings::compare_upstream_title(
+            &self.key_config,
+            &upstream_diff,
+        ));
+        self.upstream_diff = Some(upstream_diff);
+        self.show()?;
+
+        Ok(())
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_diff.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        self.diff.update_git(ev)?;
+
+        if self.is_visible() && ev == AsyncNotification::Diff {
+            self.update_diff()?;
+        }
+
+        Ok(())
+    }
+
+    /// called when any tree component changed selection
+    pub fn update_diff(&mut self) -> Result<()> {
+        if let Some(err) = self.git_diff.take_last_error()? {
+            self.diff.show_error(err);
+        }
+
+        if self.is_visible() {
+            if let Some(upstream_diff) = &self.upstream_diff {
+                if let Some(f) = self.files.selection_file() {
+                    let diff_params = DiffParams {
+                        path: f.path.clone(),
+                        diff_type: DiffType::Commits(
+                            upstream_diff.merge_base,
+                            upstream_diff.head,
+                        ),
+                        options: self.diff.options(),
+                    };
+
+                    if let Some((params, last)) =
+                        self.git_diff.last()?
+                    {
+                        if params == diff_params {
+                            self.diff.update(f.path, false, last)?;
+                            return Ok(());
+                        }
+                    }
+
+                    self.git_diff.request(diff_params)?;
+                    self.diff.clear(true)?;
+                    return Ok(());
+                }
+            }
+
+            self.diff.clear(false)?;
+        }
+
+        Ok(())
+    }
+
+    fn can_focus_diff(&self) -> bool {
+        self.files.selection_file().is_some()
+    }
+}
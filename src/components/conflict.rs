@@ -0,0 +1,400 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings, try_or_popup,
+    ui::{self, style::SharedTheme, Size},
+};
+use anyhow::{anyhow, bail, Result};
+use asyncgit::{
+    sync::{self, utils::repo_work_dir, ConflictInfo, ConflictSide},
+    StatusItemType, CWD,
+};
+use crossterm::{
+    event::Event,
+    terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
+    ExecutableCommand,
+};
+use scopeguard::defer;
+use std::{cell::Cell, io, process::Command};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::Style,
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// the hunks of a conflicted file with our, the base and their
+/// side, each can be taken or the whole file handed to the mergetool
+pub struct ConflictComponent {
+    conflict: Option<ConflictInfo>,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ConflictComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if let (true, Some(conflict)) = (self.visible, &self.conflict)
+        {
+            const PERCENT_SIZE: Size = Size::new(80, 70);
+            const MIN_SIZE: Size = Size::new(60, 16);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let (lines, selected_line) = self.get_lines(conflict);
+            let height = (area.height as usize).saturating_sub(2);
+            self.scroll_top.set(ui::calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                selected_line,
+            ));
+
+            let lines = lines
+                .into_iter()
+                .skip(self.scroll_top.get())
+                .take(height)
+                .collect::<Vec<_>>();
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(Text::from(lines)).block(
+                    Block::default()
+                        .title(Span::styled(
+                            strings::conflict_title(
+                                &self.key_config,
+                                &conflict.path,
+                                self.selection,
+                                conflict.hunks.len(),
+                            ),
+                            self.theme.title(true),
+                        ))
+                        .border_type(BorderType::Thick)
+                        .borders(Borders::ALL),
+                ),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ConflictComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::conflict_take_ours(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::conflict_take_theirs(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::conflict_mergetool(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::conflict_resolved(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = (self.selection + 1)
+                        .min(self.hunk_count().saturating_sub(1));
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.conflict_take_ours {
+                    try_or_popup!(
+                        self,
+                        "take ours error:",
+                        self.take(ConflictSide::Ours)
+                    );
+                } else if e == self.key_config.conflict_take_theirs {
+                    try_or_popup!(
+                        self,
+                        "take theirs error:",
+                        self.take(ConflictSide::Theirs)
+                    );
+                } else if e == self.key_config.conflict_mergetool {
+                    self.open_mergetool();
+                } else if e == self.key_config.conflict_resolved {
+                    try_or_popup!(
+                        self,
+                        "mark resolved error:",
+                        self.mark_resolved()
+                    );
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl ConflictComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            conflict: None,
+            selection: 0,
+            scroll_top: Cell::new(0),
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self, path: &str) -> Result<()> {
+        self.conflict = Some(sync::get_conflict(CWD, path)?);
+        self.selection = 0;
+        self.scroll_top.set(0);
+
+        self.show()
+    }
+
+    /// runs `git mergetool` on the file in the terminal gitui left
+    pub fn run_mergetool(path: &str) -> Result<()> {
+        io::stdout().execute(LeaveAlternateScreen)?;
+        disable_raw_mode()?;
+        defer! {
+            enable_raw_mode().expect("reset terminal");
+            io::stdout().execute(EnterAlternateScreen).expect("reset terminal");
+        }
+
+        let status = Command::new("git")
+            .args(&["mergetool", "--no-prompt", "--", path])
+            .current_dir(repo_work_dir(CWD)?)
+            .status()
+            .map_err(|e| anyhow!("\"git mergetool\": {}", e))?;
+
+        if !status.success() {
+            bail!("git mergetool failed on {}", path);
+        }
+
+        Ok(())
+    }
+
+    fn hunk_count(&self) -> usize {
+        self.conflict.as_ref().map_or(0, |c| c.hunks.len())
+    }
+
+    fn path(&self) -> Option<String> {
+        self.conflict.as_ref().map(|c| c.path.clone())
+    }
+
+    /// the selected hunk, or the whole file if it has no markers
+    fn take(&mut self, side: ConflictSide) -> Result<()> {
+        if let Some(path) = self.path() {
+            let resolved = if self.hunk_count() == 0 {
+                sync::conflict_take(CWD, &path, side)?;
+                true
+            } else {
+                sync::conflict_take_hunk(
+                    CWD,
+                    &path,
+                    self.selection,
+                    side,
+                )?
+            };
+
+            if resolved {
+                self.hide();
+            } else {
+                self.conflict = Some(sync::get_conflict(CWD, &path)?);
+                self.selection = self
+                    .selection
+                    .min(self.hunk_count().saturating_sub(1));
+            }
+
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+        }
+
+        Ok(())
+    }
+
+    fn open_mergetool(&mut self) {
+        if let Some(path) = self.path() {
+            self.hide();
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::RunMergetool(path));
+        }
+    }
+
+    fn mark_resolved(&mut self) -> Result<()> {
+        if let Some(path) = self.path() {
+            sync::conflict_mark_resolved(CWD, &path)?;
+
+            self.hide();
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+        }
+
+        Ok(())
+    }
+
+    /// all lines and the first one of the selected hunk
+    fn get_lines(
+        &self,
+        conflict: &ConflictInfo,
+    ) -> (Vec<Spans<'_>>, usize) {
+        if conflict.hunks.is_empty() {
+            let text = strings::conflict_whole_file(
+                &self.key_config,
+                conflict.in_ours,
+                conflict.in_theirs,
+            );
+            let lines = text
+                .lines()
+                .map(|l| {
+                    Spans::from(Span::styled(
+                        l.to_string(),
+                        self.theme.text(true, false),
+                    ))
+                })
+                .collect();
+            return (lines, 0);
+        }
+
+        let mut lines = Vec::new();
+        let mut selected_line = 0;
+
+        for (idx, hunk) in conflict.hunks.iter().enumerate() {
+            let selected = idx == self.selection;
+            if selected {
+                selected_line = lines.len();
+            }
+
+            let marker = |text: String| {
+                Spans::from(Span::styled(
+                    text,
+                    self.theme.diff_hunk_marker(selected),
+                ))
+            };
+            let content = |text: &String, style: Style| {
+                Spans::from(Span::styled(text.clone(), style))
+            };
+
+            lines.push(marker(strings::conflict_hunk_header(
+                &self.key_config,
+                hunk.line,
+            )));
+            lines.push(marker(strings::conflict_ours(
+                &self.key_config,
+            )));
+            lines.extend(hunk.ours.iter().map(|l| {
+                content(
+                    l,
+                    self.theme.item(StatusItemType::New, false),
+                )
+            }));
+            if let Some(base) = &hunk.base {
+                lines.push(marker(strings::conflict_base(
+                    &self.key_config,
+                )));
+                lines.extend(base.iter().map(|l| {
+                    content(l, self.theme.text(false, false))
+                }));
+            }
+            lines.push(marker(String::from("=======")));
+            lines.extend(hunk.theirs.iter().map(|l| {
+                content(
+                    l,
+                    self.theme.item(StatusItemType::Renamed, false),
+                )
+            }));
+            lines.push(marker(strings::conflict_theirs(
+                &self.key_config,
+            )));
+            lines.push(Spans::from(""));
+        }
+
+        (lines, selected_line)
+    }
+}
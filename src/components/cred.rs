@@ -60,6 +60,18 @@ impl CredComponent {
     pub const fn get_cred(&self) -> &BasicAuthCredential {
         &self.cred
     }
+
+    /// asks only for the passphrase of the ssh key of `user`
+    pub fn ask_passphrase(&mut self, user: String) -> Result<()> {
+        self.input_password.set_title(
+            strings::passphrase_popup_title(&self.key_config, &user),
+        );
+        self.input_password.set_default_msg(
+            strings::passphrase_popup_msg(&self.key_config),
+        );
+        self.cred = BasicAuthCredential::new(Some(user), None);
+        self.show()
+    }
 }
 
 impl DrawableComponent for CredComponent {
@@ -153,6 +165,12 @@ impl Component for CredComponent {
 
     fn hide(&mut self) {
         self.cred = BasicAuthCredential::new(None, None);
+        self.input_password.set_title(strings::password_popup_title(
+            &self.key_config,
+        ));
+        self.input_password.set_default_msg(
+            strings::password_popup_msg(&self.key_config),
+        );
         self.visible = false;
     }
 
@@ -1,9 +1,12 @@
 use super::{
-    CommandBlocking, Direction, DrawableComponent, ScrollType,
+    utils::split_diff::{row_of_line, split_rows, SplitRow},
+    BlameRevision, CommandBlocking, Direction, DrawableComponent,
+    ScrollType,
 };
 use crate::{
     components::{CommandInfo, Component},
     keys::SharedKeyConfig,
+    options::SharedOptions,
     queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
     strings, try_or_popup,
     ui::{self, calc_scroll_top, style::SharedTheme},
@@ -11,15 +14,18 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
     hash,
-    sync::{self, diff::DiffLinePosition},
-    DiffLine, DiffLineType, FileDiff, CWD,
+    sync::{self, diff::DiffLinePosition, DiffOptions},
+    AsyncNotification, AsyncSyntax, DiffLine, DiffLineType,
+    DiffSyntax, FileDiff, SyntaxSpan, CWD,
 };
 use bytesize::ByteSize;
-use crossterm::event::Event;
-use std::{borrow::Cow, cell::Cell, cmp, path::Path};
+use crossbeam_channel::Sender;
+use crossterm::event::{Event, KeyEvent};
+use std::{borrow::Cow, cell::Cell, cmp, path::Path, sync::Arc};
 use tui::{
     backend::Backend,
     layout::Rect,
+    style::Style,
     symbols,
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
@@ -30,6 +36,8 @@ use tui::{
 struct Current {
     path: String,
     is_stage: bool,
+    /// combined diff of the files beneath `path`
+    is_folder: bool,
     hash: u64,
 }
 
@@ -97,9 +105,16 @@ impl Selection {
 }
 
 ///
+#[allow(clippy::struct_excessive_bools)]
 pub struct DiffComponent {
     diff: Option<FileDiff>,
+    /// `diff` laid out side by side
+    rows: Vec<SplitRow>,
+    git_syntax: AsyncSyntax,
+    /// colors of the code in `diff` once highlighted
+    syntax: Option<Arc<DiffSyntax>>,
     pending: bool,
+    progress: Option<(usize, usize)>,
     selection: Selection,
     selected_hunk: Option<usize>,
     current_size: Cell<(u16, u16)>,
@@ -109,15 +124,27 @@ pub struct DiffComponent {
     queue: Queue,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
+    options: SharedOptions,
     is_immutable: bool,
+    /// shown even if above `max_diff_lines`
+    load_large: bool,
+    /// moving extends the selection (ended by collapsing it)
+    visual: bool,
+    /// no blame or file history unless enabled
+    blame: Option<BlameRevision>,
+    /// hunk just (un)staged, the cursor goes to the one that takes
+    /// its place once the async diff without it arrives
+    moved_hunk: Option<usize>,
 }
 
 impl DiffComponent {
     ///
     pub fn new(
         queue: Queue,
+        sender: &Sender<AsyncNotification>,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
         is_immutable: bool,
     ) -> Self {
         Self {
@@ -125,22 +152,80 @@ impl DiffComponent {
             queue,
             current: Current::default(),
             pending: false,
+            progress: None,
             selected_hunk: None,
             diff: None,
+            rows: Vec::new(),
+            git_syntax: AsyncSyntax::new(sender),
+            syntax: None,
             current_size: Cell::new((0, 0)),
             selection: Selection::Single(0),
             scroll_top: Cell::new(0),
             theme,
             key_config,
+            options,
             is_immutable,
+            load_large: false,
+            visual: false,
+            blame: None,
+            moved_hunk: None,
         }
     }
     ///
+    pub fn enable_blame(&mut self, revision: BlameRevision) {
+        self.blame = Some(revision);
+    }
+    /// untracked files have no history
+    fn can_blame(&self) -> bool {
+        self.blame.is_some()
+            && !self.current.is_folder
+            && !self.current.path.is_empty()
+            && self
+                .diff
+                .as_ref()
+                .map_or(false, |diff| !diff.untracked)
+    }
+    ///
     fn can_scroll(&self) -> bool {
+        self.suppressed_lines().is_none()
+            && self
+                .diff
+                .as_ref()
+                .map(|diff| diff.lines > 1)
+                .unwrap_or_default()
+    }
+    /// lines of a diff not rendered until loaded explicitly
+    fn suppressed_lines(&self) -> Option<usize> {
+        let max = self.options.max_diff_lines;
+
         self.diff
             .as_ref()
-            .map(|diff| diff.lines > 1)
-            .unwrap_or_default()
+            .filter(|diff| {
+                !self.load_large && max > 0 && diff.lines > max
+            })
+            .map(|diff| diff.lines)
+    }
+    /// picks up the colors of the code once highlighted
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if ev == AsyncNotification::Syntax && self.syntax.is_none() {
+            self.syntax = self.git_syntax.last(self.current.hash)?;
+        }
+
+        Ok(())
+    }
+    /// syntax colors of the line at `index` of all lines
+    fn line_syntax(&self, index: usize) -> &[SyntaxSpan] {
+        self.syntax
+            .as_ref()
+            .and_then(|syntax| syntax.get(index))
+            .map_or(&[], Vec::as_slice)
+    }
+    /// old and new lines next to each other
+    fn split(&self) -> bool {
+        self.options.diff_split.get()
     }
     ///
     pub fn current(&self) -> (String, bool) {
@@ -150,13 +235,30 @@ impl DiffComponent {
     pub fn clear(&mut self, pending: bool) -> Result<()> {
         self.current = Current::default();
         self.diff = None;
+        self.rows.clear();
+        self.syntax = None;
         self.scroll_top.set(0);
         self.selection = Selection::Single(0);
         self.selected_hunk = None;
         self.pending = pending;
+        self.progress = None;
+        self.load_large = false;
+        self.visual = false;
 
         Ok(())
     }
+    /// a diff that failed to load, like one of objects missing from
+    /// a partial clone
+    pub fn show_error(&mut self, msg: String) {
+        self.pending = false;
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::ShowErrorMsg(msg));
+    }
+    /// files done and total while a pending folder diff loads
+    pub fn set_progress(&mut self, progress: Option<(usize, usize)>) {
+        self.progress = progress;
+    }
     ///
     pub fn update(
         &mut self,
@@ -164,7 +266,29 @@ impl DiffComponent {
         is_stage: bool,
         diff: FileDiff,
     ) -> Result<()> {
+        self.set_diff(path, is_stage, false, diff);
+
+        Ok(())
+    }
+    /// combined diff of a folder, it cannot be (un)staged or reset
+    pub fn update_folder(
+        &mut self,
+        path: String,
+        is_stage: bool,
+        diff: FileDiff,
+    ) {
+        self.set_diff(path, is_stage, true, diff);
+    }
+
+    fn set_diff(
+        &mut self,
+        path: String,
+        is_stage: bool,
+        is_folder: bool,
+        diff: FileDiff,
+    ) {
         self.pending = false;
+        self.progress = None;
 
         let hash = hash(&diff);
 
@@ -174,15 +298,32 @@ impl DiffComponent {
             self.current = Current {
                 path,
                 is_stage,
+                is_folder,
                 hash,
             };
 
+            self.rows = split_rows(&diff);
+            self.syntax = None;
+            if self.options.diff_syntax {
+                if let Err(e) = self.git_syntax.request(
+                    hash,
+                    self.current.path.clone(),
+                    diff.clone(),
+                ) {
+                    log::error!("syntax request error: {}", e);
+                }
+            }
             self.diff = Some(diff);
 
+            let moved_hunk = self.moved_hunk.take();
+
             if reset_selection {
+                self.load_large = false;
                 self.scroll_top.set(0);
                 self.selection = Selection::Single(0);
                 self.update_selection(0);
+            } else if let Some(hunk) = moved_hunk {
+                self.select_hunk_start(hunk);
             } else {
                 let old_selection = match self.selection {
                     Selection::Single(line) => line,
@@ -191,44 +332,67 @@ impl DiffComponent {
                 self.update_selection(old_selection);
             }
         }
-
-        Ok(())
     }
 
+    /// moves by rows in the side by side view
     fn move_selection(&mut self, move_type: ScrollType) {
         if let Some(diff) = &self.diff {
-            let max = diff.lines.saturating_sub(1) as usize;
+            let split = self.split();
+            let position = |line| {
+                if split {
+                    row_of_line(&self.rows, line)
+                } else {
+                    line
+                }
+            };
+            let max =
+                if split { self.rows.len() } else { diff.lines }
+                    .saturating_sub(1);
 
             let new_start = match move_type {
                 ScrollType::Down => {
-                    self.selection.get_bottom().saturating_add(1)
-                }
-                ScrollType::Up => {
-                    self.selection.get_top().saturating_sub(1)
+                    position(self.selection.get_bottom())
+                        .saturating_add(1)
                 }
+                ScrollType::Up => position(self.selection.get_top())
+                    .saturating_sub(1),
                 ScrollType::Home => 0,
                 ScrollType::End => max,
                 ScrollType::PageDown => {
-                    self.selection.get_bottom().saturating_add(
-                        self.current_size.get().1.saturating_sub(1)
-                            as usize,
-                    )
+                    position(self.selection.get_bottom())
+                        .saturating_add(
+                            self.current_size
+                                .get()
+                                .1
+                                .saturating_sub(1)
+                                as usize,
+                        )
                 }
                 ScrollType::PageUp => {
-                    self.selection.get_top().saturating_sub(
+                    position(self.selection.get_top()).saturating_sub(
                         self.current_size.get().1.saturating_sub(1)
                             as usize,
                     )
                 }
             };
 
+            let new_start = if split {
+                self.rows
+                    .get(cmp::min(max, new_start))
+                    .map_or(0, SplitRow::line)
+            } else {
+                new_start
+            };
+
             self.update_selection(new_start);
         }
     }
 
     fn update_selection(&mut self, new_start: usize) {
+        self.visual = false;
+
         if let Some(diff) = &self.diff {
-            let max = diff.lines.saturating_sub(1) as usize;
+            let max = diff.lines.saturating_sub(1);
             let new_start = cmp::min(max, new_start);
             self.selection = Selection::Single(new_start);
             self.selected_hunk =
@@ -237,13 +401,40 @@ impl DiffComponent {
     }
 
     fn lines_count(&self) -> usize {
+        if self.split() {
+            return self.rows.len().saturating_sub(1);
+        }
+
         self.diff
             .as_ref()
             .map_or(0, |diff| diff.lines.saturating_sub(1))
     }
 
+    /// selection start or end (in rows when side by side)
+    fn position(&self, line: usize) -> usize {
+        if self.split() {
+            row_of_line(&self.rows, line)
+        } else {
+            line
+        }
+    }
+
     fn modify_selection(&mut self, direction: Direction) {
-        if let Some(diff) = &self.diff {
+        if self.split() {
+            let row = self.position(self.selection.get_end());
+            let row = match direction {
+                Direction::Up => row.checked_sub(1),
+                Direction::Down => Some(row + 1),
+            };
+
+            if let Some(row) = row.and_then(|row| self.rows.get(row))
+            {
+                self.selection = Selection::Multiple(
+                    self.selection.get_start(),
+                    row.line(),
+                );
+            }
+        } else if let Some(diff) = &self.diff {
             let max = diff.lines.saturating_sub(1);
 
             self.selection.modify(direction, max);
@@ -307,7 +498,15 @@ impl DiffComponent {
 
     fn get_text(&self, width: u16, height: u16) -> Vec<Spans> {
         let mut res: Vec<Spans> = Vec::new();
-        if let Some(diff) = &self.diff {
+        if let Some(lines) = self.suppressed_lines() {
+            res.push(Spans::from(Span::styled(
+                Cow::from(strings::diff_suppressed_text(
+                    &self.key_config,
+                    lines,
+                )),
+                self.theme.text(false, false),
+            )));
+        } else if let Some(diff) = &self.diff {
             if diff.hunks.is_empty() {
                 let is_positive = diff.size_delta >= 0;
                 let delta_byte_size =
@@ -347,6 +546,8 @@ impl DiffComponent {
                     ),
                     Span::raw(Cow::from(")")),
                 ])]);
+            } else if self.split() {
+                res = self.get_split_text(diff, width, height);
             } else {
                 let min = self.scroll_top.get();
                 let max = min + height as usize;
@@ -379,6 +580,7 @@ impl DiffComponent {
                                 res.push(Self::get_line_to_add(
                                     width,
                                     line,
+                                    self.line_syntax(line_cursor),
                                     self.focused()
                                         && self
                                             .selection
@@ -404,31 +606,36 @@ impl DiffComponent {
     fn get_line_to_add<'a>(
         width: u16,
         line: &'a DiffLine,
+        syntax: &[SyntaxSpan],
         selected: bool,
         selected_hunk: bool,
         end_of_hunk: bool,
         theme: &SharedTheme,
     ) -> Spans<'a> {
-        let style = theme.diff_hunk_marker(selected_hunk);
-
-        let left_side_of_line = if end_of_hunk {
-            Span::styled(Cow::from(symbols::line::BOTTOM_LEFT), style)
-        } else {
-            match line.line_type {
-                DiffLineType::Header => Span::styled(
-                    Cow::from(symbols::line::TOP_LEFT),
-                    style,
-                ),
-                _ => Span::styled(
-                    Cow::from(symbols::line::VERTICAL),
-                    style,
-                ),
-            }
-        };
+        let left_side_of_line = Self::hunk_marker(
+            line.line_type,
+            selected_hunk,
+            end_of_hunk,
+            theme,
+        );
 
         let trimmed =
             line.content.trim_matches(|c| c == '\n' || c == '\r');
 
+        if !line.changes.is_empty() || !syntax.is_empty() {
+            let fill = if selected {
+                (width as usize)
+                    .saturating_sub(trimmed.chars().count())
+            } else {
+                0
+            };
+            let mut spans = vec![left_side_of_line];
+            spans.extend(Self::line_with_changes(
+                trimmed, line, syntax, fill, selected, theme,
+            ));
+            return Spans::from(spans);
+        }
+
         let filled = if selected {
             // selected line
             format!("{:w$}\n", trimmed, w = width as usize)
@@ -448,6 +655,237 @@ impl DiffComponent {
         ])
     }
 
+    fn hunk_marker<'a>(
+        line_type: DiffLineType,
+        selected_hunk: bool,
+        end_of_hunk: bool,
+        theme: &SharedTheme,
+    ) -> Span<'a> {
+        let style = theme.diff_hunk_marker(selected_hunk);
+
+        if end_of_hunk {
+            Span::styled(Cow::from(symbols::line::BOTTOM_LEFT), style)
+        } else {
+            match line_type {
+                DiffLineType::Header => Span::styled(
+                    Cow::from(symbols::line::TOP_LEFT),
+                    style,
+                ),
+                _ => Span::styled(
+                    Cow::from(symbols::line::VERTICAL),
+                    style,
+                ),
+            }
+        }
+    }
+
+    /// the line split up around its changed words to highlight them
+    fn line_with_changes<'a>(
+        trimmed: &str,
+        line: &DiffLine,
+        syntax: &[SyntaxSpan],
+        fill: usize,
+        selected: bool,
+        theme: &SharedTheme,
+    ) -> Vec<Span<'a>> {
+        let mut spans: Vec<Span> = Self::line_segments(
+            trimmed, line, syntax, selected, theme,
+        )
+        .into_iter()
+        .map(|(text, style)| Span::styled(Cow::from(text), style))
+        .collect();
+
+        spans.push(Span::styled(
+            Cow::from(format!("{:w$}\n", "", w = fill)),
+            theme.diff_line(line.line_type, selected),
+        ));
+
+        spans
+    }
+
+    /// pieces of the line with their style (changed words and the
+    /// syntax colors), tabs expanded
+    fn line_segments(
+        trimmed: &str,
+        line: &DiffLine,
+        syntax: &[SyntaxSpan],
+        selected: bool,
+        theme: &SharedTheme,
+    ) -> Vec<(String, Style)> {
+        let mut bounds = vec![0, trimmed.len()];
+        for range in line
+            .changes
+            .iter()
+            .chain(syntax.iter().map(|span| &span.range))
+        {
+            bounds.push(range.start);
+            bounds.push(range.end);
+        }
+        bounds.retain(|bound| *bound <= trimmed.len());
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        let mut segments: Vec<(String, Style)> = Vec::new();
+        for piece in bounds.windows(2) {
+            let text = match trimmed.get(piece[0]..piece[1]) {
+                Some(text) => text.replace('\t', "  "),
+                None => continue,
+            };
+            let changed =
+                line.changes.iter().any(|r| r.contains(&piece[0]));
+            let color = syntax
+                .iter()
+                .find(|span| span.range.contains(&piece[0]))
+                .map(|span| span.color);
+
+            let style = match color {
+                Some(color) => theme.diff_line_syntax(
+                    line.line_type,
+                    changed,
+                    selected,
+                    color,
+                ),
+                None if changed => {
+                    theme.diff_line_changed(line.line_type, selected)
+                }
+                None => theme.diff_line(line.line_type, selected),
+            };
+
+            match segments.last_mut() {
+                Some(last) if last.1 == style => {
+                    last.0.push_str(&text);
+                }
+                _ => segments.push((text, style)),
+            }
+        }
+
+        segments
+    }
+
+    /// rows of the side by side view in sight
+    fn get_split_text<'a>(
+        &self,
+        diff: &'a FileDiff,
+        width: u16,
+        height: u16,
+    ) -> Vec<Spans<'a>> {
+        let lines: Vec<&DiffLine> =
+            diff.hunks.iter().flat_map(|hunk| &hunk.lines).collect();
+
+        // borders and hunk marker, the separator between the sides
+        let inner = (width as usize).saturating_sub(3);
+        let left_width = inner.saturating_sub(1) / 2;
+        let right_width = inner.saturating_sub(1 + left_width);
+
+        let start = self.position(self.selection.get_start());
+        let end = self.position(self.selection.get_end());
+        let selected_rows =
+            cmp::min(start, end)..=cmp::max(start, end);
+
+        self.rows
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height as usize)
+            .map(|(i, row)| {
+                let selected =
+                    self.focused() && selected_rows.contains(&i);
+                let hunk_selected = self.focused()
+                    && self.selected_hunk == Some(row.hunk);
+                let end_of_hunk = self
+                    .rows
+                    .get(i + 1)
+                    .map_or(true, |next| next.hunk != row.hunk);
+                let side = |index: Option<usize>| {
+                    index.and_then(|l| {
+                        lines
+                            .get(l)
+                            .map(|line| (*line, self.line_syntax(l)))
+                    })
+                };
+                let old = side(row.old);
+                let new = side(row.new);
+
+                match old {
+                    Some((line, _))
+                        if row.is_shared()
+                            && line.line_type
+                                != DiffLineType::None =>
+                    {
+                        Self::get_line_to_add(
+                            width,
+                            line,
+                            &[],
+                            selected,
+                            hunk_selected,
+                            end_of_hunk,
+                            &self.theme,
+                        )
+                    }
+                    _ => {
+                        let mut spans = vec![Self::hunk_marker(
+                            DiffLineType::None,
+                            hunk_selected,
+                            end_of_hunk,
+                            &self.theme,
+                        )];
+                        spans.extend(Self::split_side(
+                            old,
+                            left_width,
+                            selected,
+                            &self.theme,
+                        ));
+                        spans.push(Span::styled(
+                            Cow::from(symbols::line::VERTICAL),
+                            self.theme.diff_hunk_marker(false),
+                        ));
+                        spans.extend(Self::split_side(
+                            new,
+                            right_width,
+                            selected,
+                            &self.theme,
+                        ));
+                        Spans::from(spans)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// one side of a row, cut off or filled up to `width`
+    fn split_side<'a>(
+        line: Option<(&DiffLine, &[SyntaxSpan])>,
+        width: usize,
+        selected: bool,
+        theme: &SharedTheme,
+    ) -> Vec<Span<'a>> {
+        let mut spans = Vec::new();
+        let mut left = width;
+
+        if let Some((line, syntax)) = line {
+            let trimmed =
+                line.content.trim_matches(|c| c == '\n' || c == '\r');
+
+            for (text, style) in Self::line_segments(
+                trimmed, line, syntax, selected, theme,
+            ) {
+                let text: String = text.chars().take(left).collect();
+                left -= text.chars().count();
+                spans.push(Span::styled(Cow::from(text), style));
+            }
+        }
+
+        spans.push(Span::styled(
+            Cow::from(" ".repeat(left)),
+            theme.diff_line(
+                line.map_or(DiffLineType::None, |(l, _)| l.line_type),
+                selected,
+            ),
+        ));
+
+        spans
+    }
+
     const fn hunk_visible(
         hunk_min: usize,
         hunk_max: usize,
@@ -478,6 +916,7 @@ impl DiffComponent {
                     self.current.path.clone(),
                     hash,
                 )?;
+                self.moved_hunk = Some(hunk);
                 self.queue_update();
             }
         }
@@ -496,6 +935,7 @@ impl DiffComponent {
                     sync::stage_hunk(CWD, path, hash)?;
                 }
 
+                self.moved_hunk = Some(hunk);
                 self.queue_update();
             }
         }
@@ -503,6 +943,34 @@ impl DiffComponent {
         Ok(())
     }
 
+    /// selects the first line of `hunk` (or the last hunk)
+    fn select_hunk_start(&mut self, hunk: usize) {
+        let next_line: usize = self.diff.as_ref().map_or(0, |diff| {
+            diff.hunks
+                .iter()
+                .take(cmp::min(
+                    hunk,
+                    diff.hunks.len().saturating_sub(1),
+                ))
+                .map(|h| h.lines.len())
+                .sum()
+        });
+
+        self.update_selection(next_line);
+    }
+
+    /// a hunk was (un)staged and the diff without it is still to come
+    pub const fn is_hunk_moving(&self) -> bool {
+        self.moved_hunk.is_some()
+    }
+
+    /// `true` if a diff is shown that has no hunks (left)
+    pub fn has_no_hunks(&self) -> bool {
+        self.diff
+            .as_ref()
+            .map_or(false, |diff| diff.hunks.is_empty())
+    }
+
     fn queue_update(&self) {
         self.queue
             .as_ref()
@@ -530,6 +998,7 @@ impl DiffComponent {
             InternalEvent::ConfirmAction(Action::ResetLines(
                 self.current.path.clone(),
                 self.selected_lines(),
+                self.current.hash,
             )),
         );
     }
@@ -581,6 +1050,25 @@ impl DiffComponent {
             .unwrap_or_default()
     }
 
+    fn edit_hunk(&self) {
+        if let (Some(diff), Some(hunk)) =
+            (&self.diff, self.selected_hunk)
+        {
+            if !diff.untracked {
+                self.queue.as_ref().borrow_mut().push_back(
+                    InternalEvent::EditHunk(
+                        self.current.path.clone(),
+                        diff.hunks[hunk].header_hash,
+                    ),
+                );
+            }
+        }
+    }
+
+    fn is_untracked(&self) -> bool {
+        self.diff.as_ref().map_or(false, |diff| diff.untracked)
+    }
+
     fn reset_untracked(&self) {
         self.queue.as_ref().borrow_mut().push_back(
             InternalEvent::ConfirmAction(Action::Reset(ResetItem {
@@ -600,9 +1088,85 @@ impl DiffComponent {
         Ok(())
     }
 
+    /// `false` for keys not selecting lines, up and down extend
+    /// the selection while in visual mode
+    fn visual_event(&mut self, e: KeyEvent) -> bool {
+        if e == self.key_config.diff_visual_select {
+            if self.visual {
+                self.update_selection(self.selection.get_end());
+            } else {
+                self.visual = true;
+            }
+        } else if !self.visual {
+            return false;
+        } else if e == self.key_config.exit_popup {
+            self.update_selection(self.selection.get_end());
+        } else if e == self.key_config.move_down {
+            self.modify_selection(Direction::Down);
+        } else if e == self.key_config.move_up {
+            self.modify_selection(Direction::Up);
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    /// `false` for keys not moving the selection
+    fn move_event(&mut self, e: KeyEvent) -> bool {
+        let move_type = if e == self.key_config.move_down {
+            ScrollType::Down
+        } else if e == self.key_config.move_up {
+            ScrollType::Up
+        } else if e == self.key_config.end {
+            ScrollType::End
+        } else if e == self.key_config.home {
+            ScrollType::Home
+        } else if e == self.key_config.page_up {
+            ScrollType::PageUp
+        } else if e == self.key_config.page_down {
+            ScrollType::PageDown
+        } else {
+            return false;
+        };
+
+        self.move_selection(move_type);
+        true
+    }
+
+    /// `false` for keys not changing how the diff is shown
+    fn view_event(&self, e: KeyEvent) -> bool {
+        if e == self.key_config.diff_toggle_split {
+            let split = &self.options.diff_split;
+            split.set(!split.get());
+        } else if e == self.key_config.diff_options {
+            self.queue
+                .as_ref()
+                .borrow_mut()
+                .push_back(InternalEvent::OpenDiffOptions);
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    /// whitespace and context to request the diff with
+    pub fn options(&self) -> DiffOptions {
+        self.options.diff.get()
+    }
+
     const fn is_stage(&self) -> bool {
         self.current.is_stage
     }
+
+    /// hunks of a diff with other than the default options do not
+    /// match the ones to stage
+    fn is_immutable(&self) -> bool {
+        self.is_immutable
+            || self.current.is_folder
+            || self.options() != DiffOptions::default()
+    }
 }
 
 impl DrawableComponent for DiffComponent {
@@ -619,18 +1183,34 @@ impl DrawableComponent for DiffComponent {
         self.scroll_top.set(calc_scroll_top(
             self.scroll_top.get(),
             self.current_size.get().1 as usize,
-            self.selection.get_end(),
+            self.position(self.selection.get_end()),
         ));
 
         let title = format!(
-            "{}{}",
+            "{}{}{}",
             strings::title_diff(&self.key_config),
-            self.current.path
+            self.current.path,
+            if self.visual {
+                strings::title_diff_visual(&self.key_config)
+            } else {
+                String::new()
+            }
         );
 
         let txt = if self.pending {
+            let loading = self.progress.map_or_else(
+                || strings::loading_text(&self.key_config),
+                |(done, total)| {
+                    strings::loading_folder_text(
+                        &self.key_config,
+                        done,
+                        total,
+                    )
+                },
+            );
+
             vec![Spans::from(vec![Span::styled(
-                Cow::from(strings::loading_text(&self.key_config)),
+                Cow::from(loading),
                 self.theme.text(false, false),
             )])]
         } else {
@@ -675,6 +1255,12 @@ impl Component for DiffComponent {
             self.focused,
         ));
 
+        out.push(CommandInfo::new(
+            strings::commands::diff_load_large(&self.key_config),
+            true,
+            self.focused && self.suppressed_lines().is_some(),
+        ));
+
         out.push(
             CommandInfo::new(
                 strings::commands::diff_home_end(&self.key_config),
@@ -684,7 +1270,31 @@ impl Component for DiffComponent {
             .hidden(),
         );
 
-        if !self.is_immutable {
+        out.push(CommandInfo::new(
+            strings::commands::diff_toggle_split(
+                &self.key_config,
+                self.split(),
+            ),
+            true,
+            self.focused,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::diff_options(&self.key_config),
+            true,
+            self.focused,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::diff_visual_select(
+                &self.key_config,
+                self.visual,
+            ),
+            self.diff.is_some(),
+            self.focused,
+        ));
+
+        if !self.is_immutable() {
             out.push(CommandInfo::new(
                 strings::commands::diff_hunk_remove(&self.key_config),
                 self.selected_hunk.is_some(),
@@ -700,18 +1310,23 @@ impl Component for DiffComponent {
                 self.selected_hunk.is_some(),
                 self.focused && !self.is_stage(),
             ));
+            out.push(CommandInfo::new(
+                strings::commands::diff_hunk_edit(&self.key_config),
+                self.selected_hunk.is_some() && !self.is_untracked(),
+                self.focused && !self.is_stage(),
+            ));
             out.push(CommandInfo::new(
                 strings::commands::diff_lines_revert(
                     &self.key_config,
                 ),
                 //TODO: only if any modifications are selected
-                true,
+                !self.split(),
                 self.focused && !self.is_stage(),
             ));
             out.push(CommandInfo::new(
                 strings::commands::diff_lines_stage(&self.key_config),
                 //TODO: only if any modifications are selected
-                true,
+                !self.split(),
                 self.focused && !self.is_stage(),
             ));
             out.push(CommandInfo::new(
@@ -719,7 +1334,7 @@ impl Component for DiffComponent {
                     &self.key_config,
                 ),
                 //TODO: only if any modifications are selected
-                true,
+                !self.split(),
                 self.focused && self.is_stage(),
             ));
         }
@@ -730,6 +1345,19 @@ impl Component for DiffComponent {
             self.focused,
         ));
 
+        if self.blame.is_some() {
+            out.push(CommandInfo::new(
+                strings::commands::blame_file(&self.key_config),
+                self.can_blame(),
+                self.focused,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::file_history(&self.key_config),
+                self.can_blame(),
+                self.focused,
+            ));
+        }
+
         CommandBlocking::PassingOn
     }
 
@@ -737,8 +1365,41 @@ impl Component for DiffComponent {
     fn event(&mut self, ev: Event) -> Result<bool> {
         if self.focused {
             if let Event::Key(e) = ev {
-                return if e == self.key_config.move_down {
-                    self.move_selection(ScrollType::Down);
+                // nothing to act on until the diff is shown
+                if self.suppressed_lines().is_some() {
+                    return if e == self.key_config.diff_load_large {
+                        self.load_large = true;
+                        Ok(true)
+                    } else {
+                        Ok(false)
+                    };
+                }
+
+                return if self.visual_event(e)
+                    || self.move_event(e)
+                    || self.view_event(e)
+                {
+                    Ok(true)
+                } else if e == self.key_config.blame
+                    && self.can_blame()
+                {
+                    if let Some(revision) = self.blame {
+                        self.queue.as_ref().borrow_mut().push_back(
+                            InternalEvent::OpenBlame(
+                                self.current.path.clone(),
+                                revision.commit(),
+                            ),
+                        );
+                    }
+                    Ok(true)
+                } else if e == self.key_config.file_history
+                    && self.can_blame()
+                {
+                    self.queue.as_ref().borrow_mut().push_back(
+                        InternalEvent::OpenFileHistory(
+                            self.current.path.clone(),
+                        ),
+                    );
                     Ok(true)
                 } else if e == self.key_config.shift_down {
                     self.modify_selection(Direction::Down);
@@ -746,23 +1407,8 @@ impl Component for DiffComponent {
                 } else if e == self.key_config.shift_up {
                     self.modify_selection(Direction::Up);
                     Ok(true)
-                } else if e == self.key_config.end {
-                    self.move_selection(ScrollType::End);
-                    Ok(true)
-                } else if e == self.key_config.home {
-                    self.move_selection(ScrollType::Home);
-                    Ok(true)
-                } else if e == self.key_config.move_up {
-                    self.move_selection(ScrollType::Up);
-                    Ok(true)
-                } else if e == self.key_config.page_up {
-                    self.move_selection(ScrollType::PageUp);
-                    Ok(true)
-                } else if e == self.key_config.page_down {
-                    self.move_selection(ScrollType::PageDown);
-                    Ok(true)
                 } else if e == self.key_config.enter
-                    && !self.is_immutable
+                    && !self.is_immutable()
                 {
                     try_or_popup!(
                         self,
@@ -772,7 +1418,7 @@ impl Component for DiffComponent {
 
                     Ok(true)
                 } else if e == self.key_config.status_reset_item
-                    && !self.is_immutable
+                    && !self.is_immutable()
                     && !self.is_stage()
                 {
                     if let Some(diff) = &self.diff {
@@ -783,14 +1429,22 @@ impl Component for DiffComponent {
                         }
                     }
                     Ok(true)
+                } else if e == self.key_config.diff_edit_hunk
+                    && !self.is_immutable()
+                    && !self.is_stage()
+                {
+                    self.edit_hunk();
+                    Ok(true)
                 } else if e == self.key_config.diff_stage_lines
-                    && !self.is_immutable
+                    && !self.is_immutable()
+                    && !self.split()
                 {
                     self.stage_lines();
                     Ok(true)
                 } else if e == self.key_config.diff_reset_lines
-                    && !self.is_immutable
+                    && !self.is_immutable()
                     && !self.is_stage()
+                    && !self.split()
                 {
                     if let Some(diff) = &self.diff {
                         //TODO: reset untracked lines
@@ -818,3 +1472,254 @@ impl Component for DiffComponent {
         self.focused = focus
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::Options;
+    use asyncgit::sync::diff::Hunk;
+    use std::rc::Rc;
+
+    fn line(
+        content: &str,
+        line_type: DiffLineType,
+        new_lineno: Option<u32>,
+    ) -> DiffLine {
+        DiffLine {
+            content: content.to_string(),
+            line_type,
+            position: DiffLinePosition {
+                old_lineno: None,
+                new_lineno,
+            },
+            changes: Vec::new(),
+        }
+    }
+
+    fn missing_newline_diff() -> FileDiff {
+        let lines = vec![
+            line("@@ -0,0 +1 @@\n", DiffLineType::Header, None),
+            line("foo", DiffLineType::Add, Some(1)),
+            line(
+                "\n\\ No newline at end of file\n",
+                DiffLineType::NoNewline,
+                Some(1),
+            ),
+        ];
+
+        FileDiff {
+            lines: lines.len(),
+            hunks: vec![Hunk {
+                header_hash: 0,
+                lines,
+            }],
+            ..FileDiff::default()
+        }
+    }
+
+    #[test]
+    fn test_no_newline_marker_rendering() {
+        let theme = SharedTheme::default();
+        let diff = missing_newline_diff();
+        let marker = &diff.hunks[0].lines[2];
+
+        let spans = DiffComponent::get_line_to_add(
+            20,
+            marker,
+            &[],
+            false,
+            false,
+            true,
+            &theme,
+        );
+
+        assert_eq!(
+            spans.0[1].content,
+            "\\ No newline at end of file\n"
+        );
+        assert_eq!(
+            spans.0[1].style,
+            theme.diff_line(DiffLineType::NoNewline, false)
+        );
+        assert_ne!(
+            spans.0[1].style,
+            theme.diff_line(DiffLineType::Add, false)
+        );
+    }
+
+    #[test]
+    fn test_changed_words_highlighted() {
+        let theme = SharedTheme::default();
+        let mut line =
+            line("let\tfoo = 2;\n", DiffLineType::Add, Some(1));
+        line.changes.push(10..11);
+
+        let spans = DiffComponent::get_line_to_add(
+            20,
+            &line,
+            &[],
+            false,
+            false,
+            false,
+            &theme,
+        );
+
+        let texts: Vec<_> = spans
+            .0
+            .iter()
+            .skip(1)
+            .map(|s| s.content.clone())
+            .collect();
+        assert_eq!(texts, vec!["let  foo = ", "2", ";", "\n"]);
+        assert_eq!(
+            spans.0[2].style,
+            theme.diff_line_changed(DiffLineType::Add, false)
+        );
+        assert_eq!(
+            spans.0[3].style,
+            theme.diff_line(DiffLineType::Add, false)
+        );
+    }
+
+    #[test]
+    fn test_syntax_colors() {
+        let theme = SharedTheme::default();
+        let mut line = line("fn foo\n", DiffLineType::Add, Some(1));
+        line.changes.push(3..6);
+        let syntax = vec![
+            SyntaxSpan {
+                range: 0..2,
+                color: (1, 2, 3),
+            },
+            SyntaxSpan {
+                range: 2..7,
+                color: (4, 5, 6),
+            },
+        ];
+
+        let spans = DiffComponent::get_line_to_add(
+            20, &line, &syntax, false, false, false, &theme,
+        );
+
+        let texts: Vec<_> = spans
+            .0
+            .iter()
+            .skip(1)
+            .map(|s| s.content.clone())
+            .collect();
+        assert_eq!(texts, vec!["fn", " ", "foo", "\n"]);
+        assert_eq!(
+            spans.0[3].style,
+            theme.diff_line_syntax(
+                DiffLineType::Add,
+                true,
+                false,
+                (4, 5, 6)
+            )
+        );
+    }
+
+    /// without highlighting, nothing to wait for
+    fn component(options: Options) -> DiffComponent {
+        DiffComponent::new(
+            Queue::default(),
+            &crossbeam_channel::unbounded().0,
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+            Rc::new(Options {
+                diff_syntax: false,
+                ..options
+            }),
+            false,
+        )
+    }
+
+    #[test]
+    fn test_no_newline_marker_not_selectable() {
+        let mut comp = component(Options::default());
+        comp.update(
+            String::from("foo"),
+            false,
+            missing_newline_diff(),
+        )
+        .unwrap();
+        comp.selection = Selection::Multiple(0, 2);
+
+        assert_eq!(
+            comp.selected_lines(),
+            vec![DiffLinePosition {
+                old_lineno: None,
+                new_lineno: Some(1),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_large_diff_suppressed_until_loaded() {
+        let options = Options {
+            max_diff_lines: 2,
+            ..Options::default()
+        };
+        let mut comp = component(options);
+        comp.update(
+            String::from("foo"),
+            false,
+            missing_newline_diff(),
+        )
+        .unwrap();
+        comp.focus(true);
+
+        assert_eq!(comp.suppressed_lines(), Some(3));
+        assert_eq!(comp.get_text(20, 10).len(), 1);
+
+        let load = comp.key_config.diff_load_large;
+        assert!(comp.event(Event::Key(load)).unwrap());
+
+        assert_eq!(comp.suppressed_lines(), None);
+        assert_eq!(comp.get_text(20, 10).len(), 3);
+
+        comp.clear(false).unwrap();
+        comp.update(
+            String::from("bar"),
+            false,
+            missing_newline_diff(),
+        )
+        .unwrap();
+
+        assert_eq!(comp.suppressed_lines(), Some(3));
+    }
+
+    #[test]
+    fn test_visual_select() {
+        let mut comp = component(Options::default());
+        comp.update(
+            String::from("foo"),
+            false,
+            missing_newline_diff(),
+        )
+        .unwrap();
+        comp.focus(true);
+        let key = |comp: &mut DiffComponent, key| {
+            assert!(comp.event(Event::Key(key)).unwrap());
+        };
+
+        let visual = comp.key_config.diff_visual_select;
+        let down = comp.key_config.move_down;
+        let exit = comp.key_config.exit_popup;
+        key(&mut comp, visual);
+        key(&mut comp, down);
+        key(&mut comp, down);
+
+        assert!(comp.visual);
+        assert_eq!(comp.selected_lines().len(), 1);
+        assert!(
+            comp.selection.contains(0) && comp.selection.contains(2)
+        );
+
+        key(&mut comp, exit);
+
+        assert!(!comp.visual);
+        assert!(!comp.selection.contains(0));
+        assert!(comp.selection.contains(2));
+    }
+}
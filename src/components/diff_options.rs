@@ -0,0 +1,209 @@
+use super::{
+    popup_paragraph, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    options::SharedOptions,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::DiffOptions;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::Clear,
+    Frame,
+};
+
+/// lines of the popup, one per option
+const ENTRIES: usize = 4;
+
+/// changes whitespace handling and context of all diffs while open,
+/// every change is applied right away
+pub struct DiffOptionsComponent {
+    selection: usize,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+    options: SharedOptions,
+}
+
+impl DrawableComponent for DiffOptionsComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let txt: Vec<Spans> = strings::diff_options_entries(
+                &self.key_config,
+                self.options.diff.get(),
+            )
+            .into_iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                Spans::from(Span::styled(
+                    format!(" {}", entry),
+                    self.theme.text(true, i == self.selection),
+                ))
+            })
+            .collect();
+
+            let title = strings::diff_options_title(&self.key_config);
+
+            let area = ui::centered_rect(40, 20, f.size());
+            f.render_widget(Clear, area);
+            f.render_widget(
+                popup_paragraph(
+                    &title,
+                    Text::from(txt),
+                    &self.theme,
+                    true,
+                ),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for DiffOptionsComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::diff_options_change(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup
+                    || e == self.key_config.diff_options
+                {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection =
+                        (self.selection + 1).min(ENTRIES - 1);
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.enter
+                    || e == self.key_config.move_right
+                {
+                    self.change(true);
+                } else if e == self.key_config.move_left {
+                    self.change(false);
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl DiffOptionsComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+        options: SharedOptions,
+    ) -> Self {
+        Self {
+            selection: 0,
+            visible: false,
+            queue,
+            theme,
+            key_config,
+            options,
+        }
+    }
+
+    /// toggles the selected flag or steps the selected number of
+    /// lines up (`increase`) or down
+    fn change(&self, increase: bool) {
+        let old = self.options.diff.get();
+        let new = change_option(old, self.selection, increase);
+
+        if new != old {
+            self.options.diff.set(new);
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::Update(NeedsUpdate::DIFF));
+        }
+    }
+}
+
+fn change_option(
+    mut options: DiffOptions,
+    entry: usize,
+    increase: bool,
+) -> DiffOptions {
+    let step = |lines: u32| {
+        if increase {
+            lines.saturating_add(1)
+        } else {
+            lines.saturating_sub(1)
+        }
+    };
+
+    match entry {
+        0 => {
+            options.ignore_whitespace_change =
+                !options.ignore_whitespace_change;
+        }
+        1 => options.ignore_whitespace = !options.ignore_whitespace,
+        2 => options.context = step(options.context),
+        _ => options.interhunk_lines = step(options.interhunk_lines),
+    }
+
+    options
+}
@@ -3,13 +3,15 @@ use crate::{
         visibility_blocking, CommandBlocking, CommandInfo, Component,
         DrawableComponent,
     },
+    get_app_config_path,
     keys::SharedKeyConfig,
     strings,
     ui::{self, style::SharedTheme},
 };
 use anyhow::{anyhow, bail, Result};
 use asyncgit::{
-    sync::utils::get_config_string, sync::utils::repo_work_dir, CWD,
+    sync::{self, utils::get_config_string, utils::repo_work_dir},
+    CWD,
 };
 use crossterm::{
     event::Event,
@@ -18,7 +20,7 @@ use crossterm::{
 };
 use scopeguard::defer;
 use std::ffi::OsStr;
-use std::{env, io, path::Path, process::Command};
+use std::{env, fs, io, path::Path, process::Command};
 use tui::{
     backend::Backend,
     layout::Rect,
@@ -115,6 +117,30 @@ impl ExternalEditorComponent {
 
         Ok(())
     }
+
+    /// opens the unstaged hunk of `path` in the editor and stages
+    /// what is left of it once the editor is closed
+    pub fn edit_hunk(
+        &self,
+        path: &str,
+        hunk_hash: u64,
+    ) -> Result<()> {
+        const HUNK_FILE_NAME: &str = "HUNK_EDITOR.diff";
+
+        let mut hunk = sync::get_hunk_patch(CWD, path, hunk_hash)?;
+        hunk.push_str(&strings::hunk_editor_msg(&self.key_config));
+
+        let file_path = get_app_config_path()?.join(HUNK_FILE_NAME);
+        fs::write(&file_path, hunk)?;
+
+        let edited = Self::open_file_in_editor(&file_path)
+            .and_then(|()| Ok(fs::read_to_string(&file_path)?));
+        fs::remove_file(&file_path)?;
+
+        sync::apply_to_index(CWD, &edited?)?;
+
+        Ok(())
+    }
 }
 
 impl DrawableComponent for ExternalEditorComponent {
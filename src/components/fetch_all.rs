@@ -0,0 +1,240 @@
+use super::PushComponent;
+use crate::{
+    components::{
+        cred::CredComponent, visibility_blocking, CommandBlocking,
+        CommandInfo, Component, DrawableComponent,
+    },
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::cred::{
+        extract_username_password, need_ssh_passphrase,
+        need_username_password, BasicAuthCredential,
+    },
+    AsyncFetchAll, AsyncNotification, FetchAllRequest,
+    RemoteProgress,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::Span,
+    widgets::{Block, BorderType, Borders, Clear, Gauge},
+    Frame,
+};
+
+/// `git fetch --all --prune`
+pub struct FetchAllComponent {
+    visible: bool,
+    git_fetch: AsyncFetchAll,
+    progress: Option<RemoteProgress>,
+    pending: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+    input_cred: CredComponent,
+}
+
+impl FetchAllComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            queue: queue.clone(),
+            pending: false,
+            visible: false,
+            git_fetch: AsyncFetchAll::new(sender),
+            progress: None,
+            input_cred: CredComponent::new(
+                theme.clone(),
+                key_config.clone(),
+            ),
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn fetch(&mut self) -> Result<()> {
+        self.show()?;
+        if need_username_password()? {
+            let cred =
+                extract_username_password().unwrap_or_else(|_| {
+                    BasicAuthCredential::new(None, None)
+                });
+            if cred.is_complete() {
+                self.fetch_all(Some(cred))
+            } else {
+                self.input_cred.set_cred(cred);
+                self.input_cred.show()
+            }
+        } else if let Some(user) = need_ssh_passphrase()? {
+            self.input_cred.ask_passphrase(user)
+        } else {
+            self.fetch_all(None)
+        }
+    }
+
+    fn fetch_all(
+        &mut self,
+        cred: Option<BasicAuthCredential>,
+    ) -> Result<()> {
+        self.pending = true;
+        self.progress = None;
+        self.git_fetch.request(FetchAllRequest {
+            prune: true,
+            basic_credential: cred,
+        })?;
+
+        Ok(())
+    }
+
+    ///
+    pub const fn any_work_pending(&self) -> bool {
+        self.pending
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if self.is_visible() && ev == AsyncNotification::FetchAll {
+            self.update()?;
+        }
+
+        Ok(())
+    }
+
+    ///
+    fn update(&mut self) -> Result<()> {
+        self.pending = self.git_fetch.is_pending()?;
+        self.progress = self.git_fetch.progress()?;
+
+        if !self.pending {
+            if let Some((_bytes, err)) =
+                self.git_fetch.last_result()?
+            {
+                if !err.is_empty() {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "fetch failed:\n{}",
+                            err
+                        )),
+                    );
+                }
+            }
+
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+            self.hide();
+        }
+
+        Ok(())
+    }
+}
+
+impl DrawableComponent for FetchAllComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let (state, progress) =
+                PushComponent::get_progress(&self.progress);
+
+            let area = ui::centered_rect_absolute(30, 3, f.size());
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Gauge::default()
+                    .label(state.as_str())
+                    .block(
+                        Block::default()
+                            .title(Span::styled(
+                                strings::FETCH_ALL_POPUP_MSG,
+                                self.theme.title(true),
+                            ))
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick)
+                            .border_style(self.theme.block(true)),
+                    )
+                    .gauge_style(self.theme.push_gauge())
+                    .percent(u16::from(progress)),
+                area,
+            );
+            self.input_cred.draw(f, rect)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for FetchAllComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() {
+            out.clear();
+        }
+
+        if self.input_cred.is_visible() {
+            self.input_cred.commands(out, force_all)
+        } else {
+            out.push(CommandInfo::new(
+                strings::commands::close_msg(&self.key_config),
+                !self.pending,
+                self.visible,
+            ));
+            visibility_blocking(self)
+        }
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(_) = ev {
+                if self.input_cred.is_visible() {
+                    self.input_cred.event(ev)?;
+
+                    if self.input_cred.get_cred().is_complete()
+                        || !self.input_cred.is_visible()
+                    {
+                        self.fetch_all(Some(
+                            self.input_cred.get_cred().clone(),
+                        ))?;
+                        self.input_cred.hide();
+                    }
+                }
+            }
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,298 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, CommitList,
+    Component, DiffComponent, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    options::SharedOptions,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, FileHistoryEntry, MergeDiff},
+    AsyncDiff, AsyncNotification, DiffParams, DiffType, CWD,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::Clear,
+    Frame,
+};
+
+/// the commits changing a file, the diff of the file in the selected
+/// one next to them
+pub struct FileHistoryComponent {
+    history: Vec<FileHistoryEntry>,
+    list: CommitList,
+    diff: DiffComponent,
+    git_diff: AsyncDiff,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+    options: SharedOptions,
+}
+
+impl DrawableComponent for FileHistoryComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let percentages = if self.diff.focused() {
+                (30, 70)
+            } else {
+                (50, 50)
+            };
+
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(
+                    [
+                        Constraint::Percentage(percentages.0),
+                        Constraint::Percentage(percentages.1),
+                    ]
+                    .as_ref(),
+                )
+                .split(rect);
+
+            f.render_widget(Clear, rect);
+
+            self.list.draw(f, chunks[0])?;
+            self.diff.draw(f, chunks[1])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for FileHistoryComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            if self.diff.focused() {
+                self.diff.commands(out, force_all);
+            } else {
+                self.list.commands(out, force_all);
+            }
+
+            out.push(
+                CommandInfo::new(
+                    strings::commands::close_popup(&self.key_config),
+                    true,
+                    true,
+                )
+                .order(1),
+            );
+            out.push(CommandInfo::new(
+                strings::commands::diff_focus_right(&self.key_config),
+                self.selected().is_some(),
+                !self.diff.focused() || force_all,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::diff_focus_left(&self.key_config),
+                true,
+                self.diff.focused() || force_all,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::file_history_inspect(
+                    &self.key_config,
+                ),
+                self.selected().is_some(),
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if self.diff.focused() {
+                if self.diff.event(ev)? {
+                    return Ok(true);
+                }
+            } else if self.list.event(ev)? {
+                self.update_diff()?;
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.focus_right
+                    && self.selected().is_some()
+                {
+                    self.diff.focus(true);
+                } else if e == self.key_config.focus_left
+                    && self.diff.focused()
+                {
+                    self.diff.focus(false);
+                } else if e == self.key_config.enter {
+                    if let Some(entry) = self.selected() {
+                        let id = entry.commit;
+                        self.hide();
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::InspectCommit(id, None),
+                        );
+                    }
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        self.diff.focus(false);
+
+        Ok(())
+    }
+}
+
+impl FileHistoryComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+        options: SharedOptions,
+    ) -> Self {
+        Self {
+            history: Vec::new(),
+            list: CommitList::new(
+                "",
+                theme.clone(),
+                key_config.clone(),
+                options.clone(),
+            ),
+            diff: DiffComponent::new(
+                queue.clone(),
+                sender,
+                theme.clone(),
+                key_config.clone(),
+                options.clone(),
+                true,
+            ),
+            git_diff: AsyncDiff::new(sender),
+            visible: false,
+            queue: queue.clone(),
+            theme,
+            key_config,
+            options,
+        }
+    }
+
+    ///
+    pub fn open(&mut self, path: &str) -> Result<()> {
+        let history = match sync::file_history(CWD, path) {
+            Ok(history) => history,
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "file history error:\n{}",
+                        e
+                    )),
+                );
+                return Ok(());
+            }
+        };
+
+        let ids: Vec<_> = history.iter().map(|e| e.commit).collect();
+        let commits = sync::get_commits_info(CWD, &ids, 100)?;
+
+        self.list = CommitList::new(
+            &strings::file_history_title(&self.key_config, path),
+            self.theme.clone(),
+            self.key_config.clone(),
+            self.options.clone(),
+        );
+        self.list.set_count_total(commits.len());
+        self.list.items().set_items(0, commits, Vec::new());
+        self.history = history;
+
+        self.show()?;
+        self.update_diff()
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_diff.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        self.diff.update_git(ev)?;
+
+        if self.visible && ev == AsyncNotification::Diff {
+            self.update_diff()?;
+        }
+
+        Ok(())
+    }
+
+    fn selected(&self) -> Option<&FileHistoryEntry> {
+        self.history.get(self.list.selection())
+    }
+
+    /// the file in the selected commit, under the name it had there
+    pub fn update_diff(&mut self) -> Result<()> {
+        if let Some(err) = self.git_diff.take_last_error()? {
+            self.diff.show_error(err);
+        }
+
+        if let Some(entry) = self.selected() {
+            let diff_params = DiffParams {
+                path: entry.path.clone(),
+                diff_type: DiffType::Commit(
+                    entry.commit,
+                    MergeDiff::default(),
+                ),
+                options: self.diff.options(),
+            };
+
+            if let Some((params, last)) = self.git_diff.last()? {
+                if params == diff_params {
+                    self.diff.update(
+                        diff_params.path,
+                        false,
+                        last,
+                    )?;
+                    return Ok(());
+                }
+            }
+
+            self.git_diff.request(diff_params)?;
+            self.diff.clear(true)?;
+        } else {
+            self.diff.clear(false)?;
+        }
+
+        Ok(())
+    }
+}
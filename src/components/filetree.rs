@@ -1,9 +1,10 @@
 use super::{
     utils::{
-        filetree::{FileTreeItem, FileTreeItemKind},
+        filetree::{FileTreeItem, FileTreeItemKind, PathCollapsed},
+        fuzzy::fuzzy_score,
         statustree::{MoveSelection, StatusTree},
     },
-    CommandBlocking, DrawableComponent,
+    BlameRevision, CommandBlocking, DrawableComponent,
 };
 use crate::{
     components::{CommandInfo, Component},
@@ -14,19 +15,47 @@ use crate::{
     ui::style::SharedTheme,
 };
 use anyhow::Result;
-use asyncgit::{hash, StatusItem, StatusItemType};
-use crossterm::event::Event;
-use std::{borrow::Cow, cell::Cell, convert::From, path::Path};
+use asyncgit::{
+    hash,
+    sync::{CommitId, SubmoduleInfo, SubmoduleStatus},
+    StatusItem, StatusItemType,
+};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use std::{
+    borrow::Cow,
+    cell::Cell,
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    convert::From,
+    path::Path,
+};
 use tui::{backend::Backend, layout::Rect, text::Span, Frame};
 
 ///
+#[allow(clippy::struct_excessive_bools)]
 pub struct FileTreeComponent {
     title: String,
     tree: StatusTree,
+    /// all files, the tree shows only the ones matching `find`
+    items: Vec<StatusItem>,
+    /// query typed since the find key was pressed
+    find: Option<String>,
+    /// selected when the find started, selected again on cancel
+    find_origin: Option<String>,
+    /// no marking unless enabled
+    markable: bool,
+    /// paths of the files marked to be acted on together
+    marks: HashSet<String>,
+    /// item toggled last, a range gets marked from here
+    mark_anchor: Option<String>,
     pending: bool,
     current_hash: u64,
     focused: bool,
     show_selection: bool,
+    /// no blame or file history unless enabled
+    blame: Option<BlameRevision>,
+    /// state of the submodules by path, shown next to them
+    submodules: HashMap<String, SubmoduleStatus>,
     queue: Option<Queue>,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
@@ -45,9 +74,17 @@ impl FileTreeComponent {
         Self {
             title: title.to_string(),
             tree: StatusTree::default(),
+            items: Vec::new(),
+            find: None,
+            find_origin: None,
+            markable: false,
+            marks: HashSet::new(),
+            mark_anchor: None,
             current_hash: 0,
             focused: focus,
             show_selection: focus,
+            blame: None,
+            submodules: HashMap::new(),
             queue,
             theme,
             key_config,
@@ -62,7 +99,14 @@ impl FileTreeComponent {
         let new_hash = hash(list);
         if self.current_hash != new_hash {
             self.tree.update(list)?;
+            self.items = list.to_vec();
             self.current_hash = new_hash;
+
+            let paths: HashSet<&str> =
+                list.iter().map(|item| item.path.as_str()).collect();
+            self.marks.retain(|path| paths.contains(path.as_str()));
+
+            self.apply_find();
         }
 
         Ok(())
@@ -84,6 +128,66 @@ impl FileTreeComponent {
         })
     }
 
+    ///
+    pub fn enable_marks(&mut self) {
+        self.markable = true;
+    }
+
+    /// marked files in the order of the list
+    pub fn marked_files(&self) -> Vec<StatusItem> {
+        self.items
+            .iter()
+            .filter(|item| self.marks.contains(&item.path))
+            .cloned()
+            .collect()
+    }
+
+    ///
+    pub fn clear_marks(&mut self) {
+        self.marks.clear();
+        self.mark_anchor = None;
+    }
+
+    ///
+    pub fn set_submodules(&mut self, submodules: Vec<SubmoduleInfo>) {
+        self.submodules = submodules
+            .into_iter()
+            .map(|sm| (sm.path, sm.status))
+            .collect();
+    }
+
+    /// `None` unless `path` is a submodule that is not up to date
+    fn submodule_state(&self, path: &str) -> Option<String> {
+        self.submodules
+            .get(path)
+            .filter(|status| {
+                !status.initialized
+                    || status.out_of_date
+                    || status.dirty
+            })
+            .map(|status| {
+                strings::submodule_state(&self.key_config, *status)
+            })
+    }
+
+    ///
+    pub fn enable_blame(&mut self, revision: BlameRevision) {
+        self.blame = Some(revision);
+    }
+
+    /// deleted files are gone in the revision, new ones are not
+    /// committed yet when blaming `HEAD`
+    fn blame_selection(&self) -> Option<(String, Option<CommitId>)> {
+        let revision = self.blame?;
+        self.selection_file()
+            .filter(|file| match file.status {
+                StatusItemType::Deleted => false,
+                StatusItemType::New => revision.commit().is_some(),
+                _ => true,
+            })
+            .map(|file| (file.path, revision.commit()))
+    }
+
     ///
     pub fn show_selection(&mut self, show: bool) {
         self.show_selection = show;
@@ -108,6 +212,10 @@ impl FileTreeComponent {
     pub fn clear(&mut self) -> Result<()> {
         self.current_hash = 0;
         self.pending = true;
+        self.items.clear();
+        self.clear_marks();
+        self.find = None;
+        self.find_origin = None;
         self.tree.update(&[])
     }
 
@@ -121,20 +229,237 @@ impl FileTreeComponent {
         })
     }
 
+    /// an expanded folder, its files are listed below it
+    pub fn is_expanded_folder_selected(&self) -> bool {
+        self.tree.selected_item().map_or(false, |item| {
+            matches!(
+                item.kind,
+                FileTreeItemKind::Path(PathCollapsed(false))
+            )
+        })
+    }
+
     fn move_selection(&mut self, dir: MoveSelection) -> bool {
         let changed = self.tree.move_selection(dir);
 
         if changed {
-            if let Some(ref queue) = self.queue {
-                queue.borrow_mut().push_back(InternalEvent::Update(
-                    NeedsUpdate::DIFF,
-                ));
-            }
+            self.selection_changed();
+        }
+
+        changed
+    }
+
+    fn collapse_all(&mut self, collapse: bool) -> bool {
+        let changed = self.tree.collapse_all(collapse);
+
+        if changed {
+            self.selection_changed();
         }
 
         changed
     }
 
+    fn selection_changed(&self) {
+        if let Some(ref queue) = self.queue {
+            queue
+                .borrow_mut()
+                .push_back(InternalEvent::Update(NeedsUpdate::DIFF));
+        }
+    }
+
+    /// the file itself or all files in the folder
+    fn files_at(&self, item: &FileTreeItem) -> Vec<String> {
+        match &item.kind {
+            FileTreeItemKind::File(file) => vec![file.path.clone()],
+            FileTreeItemKind::Path(_) => {
+                let folder = format!("{}/", item.info.full_path);
+                self.items
+                    .iter()
+                    .filter(|file| file.path.starts_with(&folder))
+                    .map(|file| file.path.clone())
+                    .collect()
+            }
+        }
+    }
+
+    /// marks the selected file (or the files of the selected
+    /// folder), unmarks them if all were marked
+    fn toggle_mark(&mut self) -> bool {
+        if let Some(item) = self.tree.selected_item() {
+            let paths = self.files_at(&item);
+
+            if paths.iter().all(|path| self.marks.contains(path)) {
+                for path in &paths {
+                    self.marks.remove(path);
+                }
+            } else {
+                self.marks.extend(paths);
+            }
+
+            self.mark_anchor = Some(item.info.full_path);
+            return true;
+        }
+
+        false
+    }
+
+    /// marks everything from the item toggled last to the selected one
+    fn mark_range(&mut self) -> bool {
+        let items = self.tree.tree.items();
+        let anchor = self.mark_anchor.as_ref().and_then(|anchor| {
+            items
+                .iter()
+                .position(|item| item.info.full_path == *anchor)
+        });
+
+        match (anchor, self.tree.selection) {
+            (Some(anchor), Some(selection)) => {
+                let range = if anchor < selection {
+                    anchor..=selection
+                } else {
+                    selection..=anchor
+                };
+                let paths: Vec<String> = items[range]
+                    .iter()
+                    .flat_map(|item| self.files_at(item))
+                    .collect();
+
+                self.marks.extend(paths);
+                true
+            }
+            _ => self.toggle_mark(),
+        }
+    }
+
+    fn start_find(&mut self) {
+        self.find = Some(String::new());
+        self.find_origin =
+            self.tree.selected_item().map(|item| item.info.full_path);
+    }
+
+    /// shows only the files matching the query and selects the best
+    /// match, shorter paths win a tie
+    fn apply_find(&mut self) {
+        let query = match &self.find {
+            Some(query) => query,
+            None => return,
+        };
+
+        if query.is_empty() {
+            self.tree.filter(None);
+            return;
+        }
+
+        let matches: Vec<(i64, &str)> = self
+            .items
+            .iter()
+            .filter_map(|item| {
+                fuzzy_score(query, &item.path)
+                    .map(|score| (score, item.path.as_str()))
+            })
+            .collect();
+
+        let paths: HashSet<&str> =
+            matches.iter().map(|(_, path)| *path).collect();
+        self.tree.filter(Some(&paths));
+
+        if let Some((_, best)) = matches
+            .iter()
+            .max_by_key(|(score, path)| (*score, Reverse(path.len())))
+        {
+            self.tree.select_path(best);
+        }
+
+        self.selection_changed();
+    }
+
+    /// shows the whole tree again, selecting what was found
+    /// (`jump`) or what was selected before
+    fn finish_find(&mut self, jump: bool) {
+        let found = self
+            .tree
+            .selection
+            .map(|idx| &self.tree.tree[idx])
+            .filter(|item| jump && item.info.visible)
+            .map(|item| item.info.full_path.clone());
+        let origin = self.find_origin.take();
+
+        self.find = None;
+        self.tree.filter(None);
+
+        if let Some(path) = found.or(origin) {
+            self.tree.select_path(&path);
+        }
+
+        self.selection_changed();
+    }
+
+    /// typed chars go to the query, so only the arrow keys move
+    fn find_event(&mut self, e: KeyEvent) {
+        if e == self.key_config.exit_popup {
+            self.finish_find(false);
+        } else if e == self.key_config.enter {
+            self.finish_find(true);
+        } else if e.code == KeyCode::Down {
+            self.move_selection(MoveSelection::Down);
+        } else if e.code == KeyCode::Up {
+            self.move_selection(MoveSelection::Up);
+        } else if let Some(query) = &mut self.find {
+            match e.code {
+                KeyCode::Backspace => {
+                    query.pop();
+                }
+                KeyCode::Char(c)
+                    if !e
+                        .modifiers
+                        .contains(KeyModifiers::CONTROL) =>
+                {
+                    query.push(c);
+                }
+                _ => return,
+            }
+
+            self.apply_find();
+        }
+    }
+
+    /// new files have no history before they are committed
+    fn history_selection(&self) -> Option<String> {
+        let revision = self.blame?;
+        self.selection_file()
+            .filter(|file| {
+                file.status != StatusItemType::New
+                    || revision.commit().is_some()
+            })
+            .map(|file| file.path)
+    }
+
+    fn open_file_history(&self) -> bool {
+        if let (Some(queue), Some(path)) =
+            (&self.queue, self.history_selection())
+        {
+            queue
+                .borrow_mut()
+                .push_back(InternalEvent::OpenFileHistory(path));
+            return true;
+        }
+
+        false
+    }
+
+    fn open_blame(&self) -> bool {
+        if let (Some(queue), Some((path, revision))) =
+            (&self.queue, self.blame_selection())
+        {
+            queue
+                .borrow_mut()
+                .push_back(InternalEvent::OpenBlame(path, revision));
+            return true;
+        }
+
+        false
+    }
+
     const fn item_status_char(item_type: StatusItemType) -> char {
         match item_type {
             StatusItemType::Modified => 'M',
@@ -146,15 +471,16 @@ impl FileTreeComponent {
         }
     }
 
-    fn item_to_text<'b>(
+    fn item_to_text(
+        &self,
         string: &str,
         indent: usize,
         visible: bool,
         file_item_kind: &FileTreeItemKind,
         width: u16,
         selected: bool,
-        theme: &'b SharedTheme,
-    ) -> Option<Span<'b>> {
+    ) -> Option<Span<'_>> {
+        let theme = &self.theme;
         let indent_str = if indent == 0 {
             String::from("")
         } else {
@@ -169,21 +495,47 @@ impl FileTreeComponent {
             FileTreeItemKind::File(status_item) => {
                 let status_char =
                     Self::item_status_char(status_item.status);
+                let mark_char =
+                    if self.marks.contains(&status_item.path) {
+                        '*'
+                    } else {
+                        ' '
+                    };
                 let file = Path::new(&status_item.path)
                     .file_name()
                     .and_then(std::ffi::OsStr::to_str)
                     .expect("invalid path.");
+                let file = self
+                    .submodule_state(&status_item.path)
+                    .map_or_else(
+                        || Cow::from(file),
+                        |state| {
+                            Cow::from(format!("{} ({})", file, state))
+                        },
+                    );
+                let file = match &status_item.old_path {
+                    Some(old_path) => Cow::from(format!(
+                        "{} \u{2192} {}",
+                        renamed_from(old_path, &status_item.path),
+                        file
+                    )),
+                    None => file,
+                };
 
                 let txt = if selected {
                     format!(
-                        "{} {}{:w$}",
+                        "{}{}{}{:w$}",
                         status_char,
+                        mark_char,
                         indent_str,
                         file,
                         w = width as usize
                     )
                 } else {
-                    format!("{} {}{}", status_char, indent_str, file)
+                    format!(
+                        "{}{}{}{}",
+                        status_char, mark_char, indent_str, file
+                    )
                 };
 
                 Some(Span::styled(
@@ -326,6 +678,20 @@ impl DrawableComponent for FileTreeComponent {
                 &self.theme,
             );
         } else {
+            let title = self.find.as_ref().map_or_else(
+                || Cow::from(self.title.as_str()),
+                |query| {
+                    Cow::from(format!(
+                        "{}{}",
+                        self.title,
+                        strings::tree_find_title(
+                            &self.key_config,
+                            query
+                        )
+                    ))
+                },
+            );
+
             let (
                 vec_draw_text_info,
                 selection_offset,
@@ -349,21 +715,20 @@ impl DrawableComponent for FileTreeComponent {
                 .iter()
                 .enumerate()
                 .filter_map(|(index, draw_text_info)| {
-                    Self::item_to_text(
+                    self.item_to_text(
                         &draw_text_info.name,
                         draw_text_info.indent as usize,
                         draw_text_info.visible,
                         draw_text_info.item_kind,
                         r.width,
                         self.show_selection && select == index,
-                        &self.theme,
                     )
                 })
                 .skip(self.scroll_top.get());
             ui::draw_list(
                 f,
                 r,
-                self.title.as_str(),
+                &title,
                 items,
                 self.focused,
                 &self.theme,
@@ -389,13 +754,81 @@ impl Component for FileTreeComponent {
             .order(order::NAV),
         );
 
+        if self.find.is_some() {
+            out.push(CommandInfo::new(
+                strings::commands::tree_find_jump(&self.key_config),
+                true,
+                self.focused || force_all,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::tree_find_cancel(&self.key_config),
+                true,
+                self.focused || force_all,
+            ));
+        } else {
+            out.push(CommandInfo::new(
+                strings::commands::tree_find(&self.key_config),
+                !self.is_empty(),
+                self.focused || force_all,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::tree_collapse_all(
+                    &self.key_config,
+                ),
+                !self.is_empty(),
+                self.focused || force_all,
+            ));
+            if self.markable {
+                out.push(CommandInfo::new(
+                    strings::commands::toggle_mark(&self.key_config),
+                    !self.is_empty(),
+                    self.focused || force_all,
+                ));
+            }
+        }
+
+        if self.blame.is_some() {
+            out.push(CommandInfo::new(
+                strings::commands::blame_file(&self.key_config),
+                self.blame_selection().is_some(),
+                self.focused || force_all,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::file_history(&self.key_config),
+                self.history_selection().is_some(),
+                self.focused || force_all,
+            ));
+        }
+
         CommandBlocking::PassingOn
     }
 
     fn event(&mut self, ev: Event) -> Result<bool> {
         if self.focused {
             if let Event::Key(e) = ev {
-                return if e == self.key_config.move_down {
+                if self.find.is_some() {
+                    self.find_event(e);
+                    return Ok(true);
+                }
+
+                return if e == self.key_config.tree_find
+                    && !self.is_empty()
+                {
+                    self.start_find();
+                    Ok(true)
+                } else if e == self.key_config.toggle_mark
+                    && self.markable
+                {
+                    Ok(self.toggle_mark())
+                } else if e == self.key_config.mark_range
+                    && self.markable
+                {
+                    Ok(self.mark_range())
+                } else if e == self.key_config.tree_collapse_all {
+                    Ok(self.collapse_all(true))
+                } else if e == self.key_config.tree_expand_all {
+                    Ok(self.collapse_all(false))
+                } else if e == self.key_config.move_down {
                     Ok(self.move_selection(MoveSelection::Down))
                 } else if e == self.key_config.move_up {
                     Ok(self.move_selection(MoveSelection::Up))
@@ -411,6 +844,10 @@ impl Component for FileTreeComponent {
                     Ok(self.move_selection(MoveSelection::Left))
                 } else if e == self.key_config.move_right {
                     Ok(self.move_selection(MoveSelection::Right))
+                } else if e == self.key_config.blame {
+                    Ok(self.open_blame())
+                } else if e == self.key_config.file_history {
+                    Ok(self.open_file_history())
                 } else {
                     Ok(false)
                 };
@@ -429,6 +866,18 @@ impl Component for FileTreeComponent {
     }
 }
 
+/// name of the old file, its full path if it was in another folder
+fn renamed_from<'a>(old_path: &'a str, path: &str) -> &'a str {
+    let old = Path::new(old_path);
+    if old.parent() == Path::new(path).parent() {
+        old.file_name()
+            .and_then(std::ffi::OsStr::to_str)
+            .unwrap_or(old_path)
+    } else {
+        old_path
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -439,6 +888,7 @@ mod tests {
             .iter()
             .map(|a| StatusItem {
                 path: String::from(*a),
+                old_path: None,
                 status: StatusItemType::Modified,
             })
             .collect::<Vec<_>>()
@@ -525,4 +975,59 @@ mod tests {
 
         assert_eq!(ftc.scroll_top.get(), 0); // should still be at top
     }
+
+    #[test]
+    fn test_marks() {
+        let items = string_vec_to_status(&[
+            "a/b1", //
+            "a/b2", //
+            "c",    //
+            "d",    //
+        ]);
+
+        //0 a/
+        //1   b1
+        //2   b2
+        //3 c
+        //4 d
+
+        let mut ftc = FileTreeComponent::new(
+            "title",
+            true,
+            None,
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        ftc.enable_marks();
+        ftc.update(&items).unwrap();
+
+        let marked = |ftc: &FileTreeComponent| {
+            ftc.marked_files()
+                .into_iter()
+                .map(|item| item.path)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(ftc.toggle_mark()); // whole folder a/
+        assert_eq!(marked(&ftc), vec!["a/b1", "a/b2"]);
+
+        ftc.move_selection(MoveSelection::Down);
+        assert!(ftc.toggle_mark()); // unmark b1
+        assert_eq!(marked(&ftc), vec!["a/b2"]);
+
+        ftc.move_selection(MoveSelection::Down);
+        ftc.move_selection(MoveSelection::Down);
+        assert!(ftc.mark_range()); // b1 to c
+        assert_eq!(marked(&ftc), vec!["a/b1", "a/b2", "c"]);
+
+        ftc.update(&items[1..]).unwrap();
+        assert_eq!(marked(&ftc), vec!["a/b2", "c"]);
+    }
+
+    #[test]
+    fn test_renamed_from() {
+        assert_eq!(renamed_from("src/a.rs", "src/b.rs"), "a.rs");
+        assert_eq!(renamed_from("a.rs", "b.rs"), "a.rs");
+        assert_eq!(renamed_from("lib/a.rs", "src/a.rs"), "lib/a.rs");
+    }
 }
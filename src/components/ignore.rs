@@ -0,0 +1,213 @@
+use super::{
+    popup_paragraph, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{sync, CWD};
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::Clear,
+    Frame,
+};
+
+/// offers patterns to ignore a file or folder by, added to the
+/// `.gitignore` of the repo or the global excludes file
+pub struct IgnoreComponent {
+    patterns: Vec<String>,
+    selection: usize,
+    global: bool,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for IgnoreComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let txt: Vec<Spans> = self
+                .patterns
+                .iter()
+                .enumerate()
+                .map(|(i, pattern)| {
+                    Spans::from(Span::styled(
+                        format!(" {}", pattern),
+                        self.theme.text(true, i == self.selection),
+                    ))
+                })
+                .collect();
+
+            let title =
+                strings::ignore_title(&self.key_config, self.global);
+
+            let area = ui::centered_rect(40, 20, f.size());
+            f.render_widget(Clear, area);
+            f.render_widget(
+                popup_paragraph(
+                    &title,
+                    Text::from(txt),
+                    &self.theme,
+                    true,
+                ),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for IgnoreComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::ignore_add(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::ignore_toggle_global(
+                    &self.key_config,
+                    self.global,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::ignore_edit(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = (self.selection + 1)
+                        .min(self.patterns.len().saturating_sub(1));
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.ignore_toggle_global {
+                    self.global = !self.global;
+                } else if e == self.key_config.ignore_edit {
+                    self.hide();
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenEditIgnore);
+                } else if e == self.key_config.enter {
+                    self.add();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl IgnoreComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            patterns: Vec::new(),
+            selection: 0,
+            global: false,
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(
+        &mut self,
+        path: &str,
+        is_folder: bool,
+    ) -> Result<()> {
+        self.patterns = sync::ignore_patterns(path, is_folder);
+        self.selection = 0;
+        self.global = false;
+
+        self.show()
+    }
+
+    fn add(&mut self) {
+        if let Some(pattern) = self.patterns.get(self.selection) {
+            let res = if self.global {
+                sync::add_to_global_ignore(CWD, pattern)
+            } else {
+                sync::add_to_ignore(CWD, pattern)
+            };
+
+            if let Err(e) = res {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "ignore error:\n{}\npattern:\n{}",
+                        e, pattern
+                    )),
+                );
+            } else {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::Update(NeedsUpdate::ALL),
+                );
+            }
+        }
+
+        self.hide();
+    }
+}
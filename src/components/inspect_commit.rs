@@ -1,16 +1,21 @@
 use super::{
-    command_pump, event_pump, visibility_blocking, CommandBlocking,
-    CommandInfo, CommitDetailsComponent, Component, DiffComponent,
-    DrawableComponent,
+    command_pump, event_pump, visibility_blocking, BlameRevision,
+    CommandBlocking, CommandInfo, CommitDetailsComponent, Component,
+    DiffComponent, DrawableComponent,
 };
 use crate::{
-    accessors, keys::SharedKeyConfig, queue::Queue, strings,
+    accessors,
+    keys::SharedKeyConfig,
+    options::SharedOptions,
+    queue::{Action, InternalEvent, Queue},
+    strings,
     ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
-    sync::{CommitId, CommitTags},
+    sync::{self, CommitId, CommitTags, MergeDiff},
     AsyncDiff, AsyncNotification, DiffParams, DiffType,
+    StatusItemType, CWD,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
@@ -24,11 +29,16 @@ use tui::{
 pub struct InspectCommitComponent {
     commit_id: Option<CommitId>,
     tags: Option<CommitTags>,
+    /// only a merge has a choice of what to diff against
+    parents: usize,
+    merge: MergeDiff,
     diff: DiffComponent,
     details: CommitDetailsComponent,
     git_diff: AsyncDiff,
     visible: bool,
+    queue: Queue,
     key_config: SharedKeyConfig,
+    options: SharedOptions,
 }
 
 impl DrawableComponent for InspectCommitComponent {
@@ -98,6 +108,37 @@ impl Component for InspectCommitComponent {
                 true,
                 self.diff.focused() || force_all,
             ));
+
+            out.push(CommandInfo::new(
+                strings::commands::toggle_time_zone(
+                    &self.key_config,
+                    self.options.time_zone.get(),
+                ),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::restore_file(&self.key_config),
+                self.restore_selection().is_some(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::cherry_pick(&self.key_config),
+                self.commit_id.is_some(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::inspect_merge_diff(
+                    &self.key_config,
+                    self.merge,
+                    self.parents,
+                ),
+                true,
+                self.parents > 1 || force_all,
+            ));
         }
 
         visibility_blocking(self)
@@ -124,6 +165,26 @@ impl Component for InspectCommitComponent {
                     self.diff.focus(false);
                 } else if e == self.key_config.focus_left {
                     self.hide();
+                } else if e == self.key_config.toggle_time_zone {
+                    let zone = &self.options.time_zone;
+                    zone.set(zone.get().next());
+                } else if e == self.key_config.restore_file {
+                    if let Some(action) = self.restore_selection() {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ConfirmAction(action),
+                        );
+                    }
+                } else if e == self.key_config.cherry_pick {
+                    if let Some(id) = self.commit_id {
+                        self.hide();
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::CherryPick(vec![id]),
+                        );
+                    }
+                } else if e == self.key_config.inspect_merge_diff
+                    && self.parents > 1
+                {
+                    self.cycle_merge_diff()?;
                 }
 
                 // stop key event propagation
@@ -159,6 +220,7 @@ impl InspectCommitComponent {
         sender: &Sender<AsyncNotification>,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
         Self {
             details: CommitDetailsComponent::new(
@@ -166,18 +228,25 @@ impl InspectCommitComponent {
                 sender,
                 theme.clone(),
                 key_config.clone(),
+                options.clone(),
             ),
             diff: DiffComponent::new(
                 queue.clone(),
+                sender,
                 theme,
                 key_config.clone(),
+                options.clone(),
                 true,
             ),
             commit_id: None,
             tags: None,
+            parents: 0,
+            merge: MergeDiff::default(),
             git_diff: AsyncDiff::new(sender),
             visible: false,
+            queue: queue.clone(),
             key_config,
+            options,
         }
     }
 
@@ -189,6 +258,10 @@ impl InspectCommitComponent {
     ) -> Result<()> {
         self.commit_id = Some(id);
         self.tags = tags;
+        self.parents = sync::get_commit_parents(CWD, id)?.len();
+        self.merge = MergeDiff::default();
+        self.details.set_merge_diff(self.merge);
+        self.diff.enable_blame(BlameRevision::Commit(id));
         self.show()?;
 
         Ok(())
@@ -204,8 +277,12 @@ impl InspectCommitComponent {
         &mut self,
         ev: AsyncNotification,
     ) -> Result<()> {
+        self.diff.update_git(ev)?;
+
         if self.is_visible() {
-            if let AsyncNotification::CommitFiles = ev {
+            if let AsyncNotification::CommitFiles
+            | AsyncNotification::CommitDetails = ev
+            {
                 self.update()?
             } else if let AsyncNotification::Diff = ev {
                 self.update_diff()?
@@ -217,13 +294,17 @@ impl InspectCommitComponent {
 
     /// called when any tree component changed selection
     pub fn update_diff(&mut self) -> Result<()> {
+        if let Some(err) = self.git_diff.take_last_error()? {
+            self.diff.show_error(err);
+        }
+
         if self.is_visible() {
             if let Some(id) = self.commit_id {
-                if let Some(f) = self.details.files().selection_file()
-                {
+                if let Some(f) = self.details.selection_file() {
                     let diff_params = DiffParams {
                         path: f.path.clone(),
-                        diff_type: DiffType::Commit(id),
+                        diff_type: DiffType::Commit(id, self.merge),
+                        options: self.diff.options(),
                     };
 
                     if let Some((params, last)) =
@@ -248,13 +329,42 @@ impl InspectCommitComponent {
     }
 
     fn update(&mut self) -> Result<()> {
-        self.details.set_commit(self.commit_id, self.tags.clone())?;
+        self.details.set_commit(
+            self.commit_id,
+            self.tags.clone(),
+            &[],
+        )?;
         self.update_diff()?;
 
         Ok(())
     }
 
+    /// each parent in turn, then all of them combined
+    fn cycle_merge_diff(&mut self) -> Result<()> {
+        self.merge = match self.merge {
+            MergeDiff::Parent(parent)
+                if parent + 1 < self.parents =>
+            {
+                MergeDiff::Parent(parent + 1)
+            }
+            MergeDiff::Parent(_) => MergeDiff::Combined,
+            MergeDiff::Combined => MergeDiff::Parent(0),
+        };
+        self.details.set_merge_diff(self.merge);
+
+        self.update()
+    }
+
+    /// a file deleted by the commit has no version to restore
+    fn restore_selection(&self) -> Option<Action> {
+        let id = self.commit_id?;
+        self.details
+            .selection_file()
+            .filter(|file| file.status != StatusItemType::Deleted)
+            .map(|file| Action::RestoreFile(id, file.path))
+    }
+
     fn can_focus_diff(&self) -> bool {
-        self.details.files().selection_file().is_some()
+        self.details.selection_file().is_some()
     }
 }
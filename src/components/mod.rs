@@ -1,49 +1,127 @@
+mod archive;
+mod bisect;
+mod blame;
+mod branch_description;
+mod branch_search;
+mod branch_upstream;
 mod branchlist;
 mod changes;
+mod checkout_conflict;
+mod clean;
 mod command;
 mod commit;
 mod commit_details;
 mod commitlist;
+mod compare_commits;
+mod compare_upstream;
+mod conflict;
 mod create_branch;
+mod create_worktree;
 mod cred;
 mod diff;
+mod diff_options;
+mod edit_ignore;
 mod externaleditor;
+mod fetch_all;
+mod file_history;
 mod filetree;
 mod help;
+mod ignore;
 mod inspect_commit;
+mod merge_branch;
 mod msg;
+mod object_viewer;
+mod patch_file;
+mod patch_viewer;
+mod pick_hunks;
 mod pull;
+mod pull_strategy;
 mod push;
 mod push_tags;
+mod push_target;
+mod rebase;
+mod reflog;
+mod remotelist;
 mod rename_branch;
+mod rename_stash;
 mod reset;
+mod reset_commit;
+mod revision_files;
+mod reword;
+mod select_co_author;
+mod select_identity;
+mod startup_fetch;
+mod stash_branch;
 mod stashmsg;
+mod status_bar;
+mod submodules;
 mod tag_commit;
+mod taglist;
 mod textinput;
 mod utils;
+mod worktrees;
 
+pub use archive::ArchiveComponent;
+pub use bisect::BisectComponent;
+pub use blame::{BlameComponent, BlameRevision};
+pub use branch_description::BranchDescriptionComponent;
+pub use branch_search::BranchSearchComponent;
+pub use branch_upstream::BranchUpstreamComponent;
 pub use branchlist::BranchListComponent;
 pub use changes::ChangesComponent;
+pub use checkout_conflict::CheckoutConflictComponent;
+pub use clean::CleanComponent;
 pub use command::{CommandInfo, CommandText};
 pub use commit::CommitComponent;
 pub use commit_details::CommitDetailsComponent;
 pub use commitlist::CommitList;
+pub use compare_commits::CompareCommitsComponent;
+pub use compare_upstream::CompareUpstreamComponent;
+pub use conflict::ConflictComponent;
 pub use create_branch::CreateBranchComponent;
+pub use create_worktree::CreateWorktreeComponent;
 pub use diff::DiffComponent;
+pub use diff_options::DiffOptionsComponent;
+pub use edit_ignore::EditIgnoreComponent;
 pub use externaleditor::ExternalEditorComponent;
+pub use fetch_all::FetchAllComponent;
+pub use file_history::FileHistoryComponent;
 pub use filetree::FileTreeComponent;
 pub use help::HelpComponent;
+pub use ignore::IgnoreComponent;
 pub use inspect_commit::InspectCommitComponent;
+pub use merge_branch::MergeBranchComponent;
 pub use msg::MsgComponent;
+pub use object_viewer::ObjectViewerComponent;
+pub use patch_file::PatchFileComponent;
+pub use patch_viewer::PatchViewerComponent;
+pub use pick_hunks::PickHunksComponent;
 pub use pull::PullComponent;
+pub use pull_strategy::PullStrategyComponent;
 pub use push::PushComponent;
 pub use push_tags::PushTagsComponent;
+pub use push_target::PushTargetComponent;
+pub use rebase::RebaseComponent;
+pub use reflog::ReflogComponent;
+pub use remotelist::RemoteListComponent;
 pub use rename_branch::RenameBranchComponent;
+pub use rename_stash::RenameStashComponent;
 pub use reset::ResetComponent;
+pub use reset_commit::ResetCommitComponent;
+pub use revision_files::RevisionFilesComponent;
+pub use reword::RewordComponent;
+pub use select_co_author::SelectCoAuthorComponent;
+pub use select_identity::SelectIdentityComponent;
+pub use startup_fetch::StartupFetchComponent;
+pub use stash_branch::StashBranchComponent;
 pub use stashmsg::StashMsgComponent;
+pub use status_bar::{PendingOperation, StatusBarComponent};
+pub use submodules::SubmodulesListComponent;
 pub use tag_commit::TagCommitComponent;
+pub use taglist::TagListComponent;
 pub use textinput::{InputType, TextInputComponent};
 pub use utils::filetree::FileTreeItemKind;
+pub use worktrees::WorktreeListComponent;
 
 use crate::ui::style::Theme;
 use anyhow::Result;
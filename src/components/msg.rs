@@ -16,6 +16,7 @@ use ui::style::SharedTheme;
 pub struct MsgComponent {
     title: String,
     msg: String,
+    is_error: bool,
     visible: bool,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
@@ -60,7 +61,11 @@ impl DrawableComponent for MsgComponent {
                     Block::default()
                         .title(Span::styled(
                             self.title.as_str(),
-                            self.theme.text_danger(),
+                            if self.is_error {
+                                self.theme.text_danger()
+                            } else {
+                                self.theme.title(true)
+                            },
                         ))
                         .borders(Borders::ALL)
                         .border_type(BorderType::Thick),
@@ -125,6 +130,7 @@ impl MsgComponent {
         Self {
             title: String::new(),
             msg: String::new(),
+            is_error: true,
             visible: false,
             theme,
             key_config,
@@ -135,6 +141,27 @@ impl MsgComponent {
     pub fn show_error(&mut self, msg: &str) -> Result<()> {
         self.title = strings::msg_title_error(&self.key_config);
         self.msg = msg.to_string();
+        self.is_error = true;
+        self.show()?;
+
+        Ok(())
+    }
+
+    ///
+    pub fn show_info(&mut self, msg: &str) -> Result<()> {
+        self.title = strings::msg_title_info(&self.key_config);
+        self.msg = msg.to_string();
+        self.is_error = false;
+        self.show()?;
+
+        Ok(())
+    }
+
+    ///
+    pub fn show_advice(&mut self, msg: &str) -> Result<()> {
+        self.title = strings::msg_title_advice(&self.key_config);
+        self.msg = msg.to_string();
+        self.is_error = false;
         self.show()?;
 
         Ok(())
@@ -0,0 +1,336 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent, ScrollType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, calc_scroll_top, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, objects::BLOB_DUMP_MAX_SIZE, ObjectDump},
+    CWD,
+};
+use bytesize::ByteSize;
+use crossterm::event::Event;
+use std::{
+    cell::Cell,
+    convert::{TryFrom, TryInto},
+};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use ui::style::SharedTheme;
+
+/// `git cat-file -p`-like viewer for raw objects
+pub struct ObjectViewerComponent {
+    dump: Option<ObjectDump>,
+    history: Vec<String>,
+    visible: bool,
+    selection: u16,
+    scroll_top: Cell<usize>,
+    current_height: Cell<u16>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ObjectViewerComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(80, 80);
+            const MIN_SIZE: Size = Size::new(60, 20);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height_in_lines =
+                (area.height as usize).saturating_sub(2);
+
+            self.scroll_top.set(calc_scroll_top(
+                self.scroll_top.get(),
+                height_in_lines,
+                self.selection as usize,
+            ));
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height_in_lines))
+                    .block(
+                        Block::default()
+                            .title(Span::styled(
+                                self.get_title(),
+                                self.theme.title(true),
+                            ))
+                            .border_type(BorderType::Thick)
+                            .borders(Borders::ALL),
+                    )
+                    .alignment(Alignment::Left),
+                area,
+            );
+
+            ui::draw_scrollbar(
+                f,
+                area,
+                &self.theme,
+                self.lines_count(),
+                self.scroll_top.get(),
+            );
+
+            self.current_height.set(height_in_lines.try_into()?);
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ObjectViewerComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::object_viewer_open_link(
+                    &self.key_config,
+                ),
+                self.selected_link().is_some(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::object_viewer_back(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+        }
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.back();
+                } else if e == self.key_config.move_down {
+                    return Ok(self.move_selection(ScrollType::Up));
+                } else if e == self.key_config.move_up {
+                    return Ok(self.move_selection(ScrollType::Down));
+                } else if e == self.key_config.page_down {
+                    return Ok(
+                        self.move_selection(ScrollType::PageDown)
+                    );
+                } else if e == self.key_config.page_up {
+                    return Ok(
+                        self.move_selection(ScrollType::PageUp)
+                    );
+                } else if e == self.key_config.enter {
+                    if let Some(link) = self.selected_link() {
+                        if let Some(dump) = &self.dump {
+                            self.history.push(dump.id.clone());
+                        }
+                        self.load(&link);
+                    }
+                }
+            }
+
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl ObjectViewerComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            dump: None,
+            history: Vec::new(),
+            visible: false,
+            selection: 0,
+            scroll_top: Cell::new(0),
+            current_height: Cell::new(0),
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self, id: &str) -> Result<()> {
+        self.history.clear();
+
+        if self.load(id) {
+            self.show()?;
+        }
+
+        Ok(())
+    }
+
+    /// returns `false` if the object could not be loaded
+    fn load(&mut self, id: &str) -> bool {
+        match sync::show_object(CWD, id) {
+            Ok(dump) => {
+                self.dump = Some(dump);
+                self.selection = 0;
+                self.scroll_top.set(0);
+                true
+            }
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "show object error:\n{}",
+                        e
+                    )),
+                );
+                false
+            }
+        }
+    }
+
+    /// goes back to the previously viewed object or closes
+    fn back(&mut self) {
+        if let Some(id) = self.history.pop() {
+            self.load(&id);
+        } else {
+            self.hide();
+        }
+    }
+
+    fn selected_link(&self) -> Option<String> {
+        self.dump.as_ref().and_then(|dump| {
+            dump.lines
+                .get(self.selection as usize)
+                .and_then(|line| line.link.clone())
+        })
+    }
+
+    fn lines_count(&self) -> usize {
+        self.dump.as_ref().map_or(0, |dump| dump.lines.len())
+    }
+
+    fn move_selection(&mut self, scroll: ScrollType) -> bool {
+        let new_selection = match scroll {
+            ScrollType::Up => self.selection.saturating_add(1),
+            ScrollType::Down => self.selection.saturating_sub(1),
+            ScrollType::PageDown => self
+                .selection
+                .saturating_add(self.current_height.get()),
+            ScrollType::PageUp => self
+                .selection
+                .saturating_sub(self.current_height.get()),
+            _ => self.selection,
+        };
+
+        let max = u16::try_from(self.lines_count())
+            .unwrap_or(u16::MAX)
+            .saturating_sub(1);
+        self.selection = new_selection.min(max);
+
+        true
+    }
+
+    fn get_title(&self) -> String {
+        self.dump.as_ref().map_or_else(String::new, |dump| {
+            format!(
+                "{} {} ({})",
+                dump.kind,
+                dump.id,
+                ByteSize::b(dump.size as u64)
+            )
+        })
+    }
+
+    fn get_text(&self, height: usize) -> Text<'_> {
+        let mut txt = Vec::new();
+
+        if let Some(dump) = &self.dump {
+            if dump.binary {
+                txt.push(Spans::from(Span::styled(
+                    strings::object_viewer_binary(&self.key_config),
+                    self.theme.text(false, false),
+                )));
+            }
+
+            for (i, line) in dump
+                .lines
+                .iter()
+                .enumerate()
+                .skip(self.scroll_top.get())
+                .take(height)
+            {
+                let selected = i == self.selection as usize;
+                let style = if line.link.is_some() {
+                    self.theme.commit_hash(selected)
+                } else {
+                    self.theme.text(true, selected)
+                };
+
+                txt.push(Spans::from(Span::styled(
+                    line.text.replace('\t', "    "),
+                    style,
+                )));
+            }
+
+            let at_end =
+                self.scroll_top.get() + height >= dump.lines.len();
+            if dump.truncated && at_end {
+                txt.push(Spans::from(Span::styled(
+                    strings::object_viewer_truncated(
+                        &self.key_config,
+                        BLOB_DUMP_MAX_SIZE,
+                    ),
+                    self.theme.text(false, false),
+                )));
+            }
+        }
+
+        Text::from(txt)
+    }
+}
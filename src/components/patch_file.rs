@@ -0,0 +1,223 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+    InputType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId},
+    CWD,
+};
+use crossterm::event::Event;
+use tui::{backend::Backend, layout::Rect, Frame};
+
+/// asks for the folder to export commits as patch files to, or for
+/// the patch file to apply
+pub struct PatchFileComponent {
+    input: TextInputComponent,
+    /// exporting these, applying a patch if `None`
+    commits: Option<Vec<CommitId>>,
+    to_index: bool,
+    queue: Queue,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for PatchFileComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        self.input.draw(f, rect)?;
+
+        Ok(())
+    }
+}
+
+impl Component for PatchFileComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            self.input.commands(out, force_all);
+
+            if self.commits.is_some() {
+                out.push(CommandInfo::new(
+                    strings::commands::patch_export_confirm(
+                        &self.key_config,
+                    ),
+                    true,
+                    true,
+                ));
+            } else {
+                out.push(CommandInfo::new(
+                    strings::commands::patch_apply_confirm(
+                        &self.key_config,
+                    ),
+                    !self.input.get_text().trim().is_empty(),
+                    true,
+                ));
+                out.push(CommandInfo::new(
+                    strings::commands::patch_apply_toggle_index(
+                        &self.key_config,
+                        self.to_index,
+                    ),
+                    true,
+                    true,
+                ));
+            }
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.is_visible() {
+            if self.input.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter {
+                    self.confirm();
+                } else if e
+                    == self.key_config.patch_apply_toggle_index
+                    && self.commits.is_none()
+                {
+                    self.to_index = !self.to_index;
+                    self.input.set_title(
+                        strings::patch_apply_popup_title(
+                            &self.key_config,
+                            self.to_index,
+                        ),
+                    );
+                }
+
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.input.is_visible()
+    }
+
+    fn hide(&mut self) {
+        self.input.hide();
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.input.show()?;
+
+        Ok(())
+    }
+}
+
+impl PatchFileComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            queue,
+            input: TextInputComponent::new(
+                theme,
+                key_config.clone(),
+                &strings::patch_apply_popup_title(&key_config, false),
+                &strings::patch_apply_popup_msg(&key_config),
+                false,
+            )
+            .with_input_type(InputType::Singleline),
+            commits: None,
+            to_index: false,
+            key_config,
+        }
+    }
+
+    /// an empty folder means the root of the workdir
+    pub fn open_export(
+        &mut self,
+        commits: Vec<CommitId>,
+    ) -> Result<()> {
+        self.input.set_title(strings::patch_export_popup_title(
+            &self.key_config,
+            commits.len(),
+        ));
+        self.input.set_default_msg(strings::patch_export_popup_msg(
+            &self.key_config,
+        ));
+        self.input.clear();
+        self.commits = Some(commits);
+
+        self.show()
+    }
+
+    ///
+    pub fn open_apply(&mut self) -> Result<()> {
+        self.to_index = false;
+        self.input.set_title(strings::patch_apply_popup_title(
+            &self.key_config,
+            self.to_index,
+        ));
+        self.input.set_default_msg(strings::patch_apply_popup_msg(
+            &self.key_config,
+        ));
+        self.input.clear();
+        self.commits = None;
+
+        self.show()
+    }
+
+    fn confirm(&mut self) {
+        let path = self.input.get_text().trim().to_string();
+
+        let res = match &self.commits {
+            Some(commits) => sync::export_patch(CWD, commits, &path)
+                .map(|files| {
+                    Some(strings::patch_export_msg(
+                        &self.key_config,
+                        &files,
+                    ))
+                }),
+            None if path.is_empty() => return,
+            None => sync::apply_patch(CWD, &path, self.to_index)
+                .map(|()| None),
+        };
+
+        match res {
+            Ok(msg) => {
+                self.hide();
+                self.input.clear();
+
+                if let Some(msg) = msg {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::ShowInfoMsg(msg));
+                }
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::Update(NeedsUpdate::ALL),
+                );
+            }
+            Err(e) => {
+                log::error!("patch file: {}", e);
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "patch error:\n{}",
+                        e,
+                    )),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent, ScrollType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings, try_or_popup,
+    ui::{self, Size},
+};
+use anyhow::Result;
+use asyncgit::{sync, DiffLineType, CWD};
+use crossterm::event::Event;
+use std::{cell::Cell, convert::TryInto};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use ui::style::SharedTheme;
+
+/// shows the patch that staging (or unstaging) a file
+/// applies to the index, like `git diff [--cached] -- <path>`
+pub struct PatchViewerComponent {
+    path: String,
+    is_stage: bool,
+    /// as applied
+    patch: Vec<u8>,
+    /// as shown
+    text: String,
+    visible: bool,
+    scroll_top: usize,
+    current_height: Cell<u16>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for PatchViewerComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(80, 80);
+            const MIN_SIZE: Size = Size::new(60, 20);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height_in_lines =
+                (area.height as usize).saturating_sub(2);
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height_in_lines))
+                    .block(
+                        Block::default()
+                            .title(Span::styled(
+                                strings::patch_viewer_title(
+                                    &self.key_config,
+                                    &self.path,
+                                    self.is_stage,
+                                ),
+                                self.theme.title(true),
+                            ))
+                            .border_type(BorderType::Thick)
+                            .borders(Borders::ALL),
+                    )
+                    .alignment(Alignment::Left),
+                area,
+            );
+
+            ui::draw_scrollbar(
+                f,
+                area,
+                &self.theme,
+                self.lines_count(),
+                self.scroll_top,
+            );
+
+            self.current_height.set(height_in_lines.try_into()?);
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for PatchViewerComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::copy_patch(&self.key_config),
+                !self.patch.is_empty(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.scroll(ScrollType::Down);
+                } else if e == self.key_config.move_up {
+                    self.scroll(ScrollType::Up);
+                } else if e == self.key_config.page_down {
+                    self.scroll(ScrollType::PageDown);
+                } else if e == self.key_config.page_up {
+                    self.scroll(ScrollType::PageUp);
+                } else if e == self.key_config.copy
+                    && !self.patch.is_empty()
+                {
+                    try_or_popup!(
+                        self,
+                        "copy patch error:",
+                        self.copy_patch()
+                    );
+                }
+            }
+
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl PatchViewerComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            path: String::new(),
+            is_stage: false,
+            patch: Vec::new(),
+            text: String::new(),
+            visible: false,
+            scroll_top: 0,
+            current_height: Cell::new(0),
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(
+        &mut self,
+        path: String,
+        is_stage: bool,
+    ) -> Result<()> {
+        match sync::get_file_patch(CWD, &path, is_stage) {
+            Ok(content) => {
+                self.path = path;
+                self.is_stage = is_stage;
+                self.text = String::from_utf8_lossy(&content).into();
+                self.patch = content;
+                self.scroll_top = 0;
+                self.show()?;
+            }
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "show patch error:\n{}",
+                        e
+                    )),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// refused if it is not utf8, the copy would not apply
+    fn copy_patch(&self) -> Result<()> {
+        crate::clipboard::copy_string(std::str::from_utf8(
+            &self.patch,
+        )?)
+    }
+
+    fn lines_count(&self) -> usize {
+        self.text.lines().count()
+    }
+
+    fn scroll(&mut self, scroll: ScrollType) {
+        let height = self.current_height.get() as usize;
+        let top = self.scroll_top;
+
+        let new_top = match scroll {
+            ScrollType::Down => top.saturating_add(1),
+            ScrollType::Up => top.saturating_sub(1),
+            ScrollType::PageDown => top.saturating_add(height),
+            ScrollType::PageUp => top.saturating_sub(height),
+            _ => top,
+        };
+
+        self.scroll_top =
+            new_top.min(self.lines_count().saturating_sub(height));
+    }
+
+    fn get_text(&self, height: usize) -> Text<'_> {
+        if self.patch.is_empty() {
+            return Text::from(Span::styled(
+                strings::patch_viewer_empty(&self.key_config),
+                self.theme.text(false, false),
+            ));
+        }
+
+        let txt: Vec<Spans> = self
+            .text
+            .lines()
+            .skip(self.scroll_top)
+            .take(height)
+            .map(|line| {
+                let typ = match line.chars().next() {
+                    Some('+') => DiffLineType::Add,
+                    Some('-') => DiffLineType::Delete,
+                    Some('@') => DiffLineType::Header,
+                    _ => DiffLineType::None,
+                };
+
+                Spans::from(Span::styled(
+                    line.replace('\t', "    "),
+                    self.theme.diff_line(typ, false),
+                ))
+            })
+            .collect();
+
+        Text::from(txt)
+    }
+}
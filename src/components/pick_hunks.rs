@@ -0,0 +1,492 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings, try_or_popup,
+    ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{
+        self, diff::get_diff, diff::Hunk, split_hunk, FileLineStats,
+        UnstagedHunk, UnstagedHunks,
+    },
+    DiffLineType, CWD,
+};
+use crossterm::event::Event;
+use std::{cell::Cell, path::Path};
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// guided `git add -p`: walks through the hunks of all unstaged
+/// files, staging the picked ones, next to a preview of the stage
+pub struct PickHunksComponent {
+    hunks: Option<UnstagedHunks>,
+    current: Option<UnstagedHunk>,
+    /// parts of the split `current` hunk
+    parts: Vec<Hunk>,
+    /// picks of the parts shown so far
+    chosen: Vec<bool>,
+    scroll: usize,
+    stats: Vec<FileLineStats>,
+    visible: bool,
+    current_height: Cell<usize>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for PickHunksComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(80, 80);
+            const MIN_SIZE: Size = Size::new(60, 20);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(
+                    [
+                        Constraint::Percentage(65),
+                        Constraint::Percentage(35),
+                    ]
+                    .as_ref(),
+                )
+                .split(area);
+
+            let height =
+                (chunks[0].height as usize).saturating_sub(2);
+            self.current_height.set(height);
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_hunk_text(height)).block(
+                    Block::default()
+                        .title(Span::styled(
+                            self.get_title(),
+                            self.theme.title(true),
+                        ))
+                        .border_type(BorderType::Thick)
+                        .borders(Borders::ALL),
+                ),
+                chunks[0],
+            );
+            f.render_widget(
+                Paragraph::new(self.get_stage_text()).block(
+                    Block::default()
+                        .title(Span::styled(
+                            self.get_stage_title(),
+                            self.theme.title(false),
+                        ))
+                        .borders(Borders::ALL)
+                        .border_style(self.theme.block(false)),
+                ),
+                chunks[1],
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for PickHunksComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::pick_hunks_stage(&self.key_config),
+                self.current.is_some(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::pick_hunks_skip(&self.key_config),
+                self.current.is_some(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::pick_hunks_split(&self.key_config),
+                self.can_split(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::diff_hunk_edit(&self.key_config),
+                self.can_edit(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::commit_open(&self.key_config),
+                !self.stats.is_empty(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.enter {
+                    try_or_popup!(
+                        self,
+                        "stage hunk error:",
+                        self.pick(true)
+                    );
+                } else if e == self.key_config.move_down {
+                    try_or_popup!(
+                        self,
+                        "load diff error:",
+                        self.pick(false)
+                    );
+                } else if e == self.key_config.pick_hunks_split
+                    && self.can_split()
+                {
+                    self.split();
+                } else if e == self.key_config.diff_edit_hunk
+                    && self.can_edit()
+                {
+                    self.edit();
+                } else if e == self.key_config.page_down {
+                    self.scroll = self
+                        .scroll
+                        .saturating_add(self.current_height.get())
+                        .min(self.hunk_lines().saturating_sub(1));
+                } else if e == self.key_config.page_up {
+                    self.scroll = self
+                        .scroll
+                        .saturating_sub(self.current_height.get());
+                } else if e == self.key_config.open_commit
+                    && !self.stats.is_empty()
+                {
+                    self.hide();
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenCommit);
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl PickHunksComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            hunks: None,
+            current: None,
+            parts: Vec::new(),
+            chosen: Vec::new(),
+            scroll: 0,
+            stats: Vec::new(),
+            visible: false,
+            current_height: Cell::new(0),
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// starts with the first hunk of the first unstaged file
+    pub fn open(&mut self) -> Result<()> {
+        self.hunks = Some(UnstagedHunks::new(CWD)?);
+        self.stats = sync::get_stage_stats(CWD)?;
+        self.next_hunk()?;
+
+        self.show()
+    }
+
+    /// moves on once the shown hunk is gone from the workdir diff
+    /// (after it was edited in the external editor)
+    pub fn update(&mut self) -> Result<()> {
+        if !self.visible || !self.parts.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(current) = &self.current {
+            let diff =
+                get_diff(CWD, current.path.clone(), false, None)?;
+            let gone = !diff
+                .hunks
+                .iter()
+                .any(|h| h.header_hash == current.hunk.header_hash);
+
+            if gone {
+                self.stats = sync::get_stage_stats(CWD)?;
+                self.next_hunk()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// without any hunk left `current` is `None`
+    fn next_hunk(&mut self) -> Result<()> {
+        self.parts.clear();
+        self.chosen.clear();
+        self.scroll = 0;
+        self.current = self
+            .hunks
+            .as_mut()
+            .and_then(Iterator::next)
+            .transpose()?;
+
+        Ok(())
+    }
+
+    fn can_split(&self) -> bool {
+        self.parts.is_empty()
+            && self.current.as_ref().map_or(false, |current| {
+                !current.untracked
+                    && !split_hunk(&current.hunk).is_empty()
+            })
+    }
+
+    fn can_edit(&self) -> bool {
+        self.parts.is_empty()
+            && self
+                .current
+                .as_ref()
+                .map_or(false, |current| !current.untracked)
+    }
+
+    fn split(&mut self) {
+        if let Some(current) = &self.current {
+            self.parts = split_hunk(&current.hunk);
+            self.chosen.clear();
+            self.scroll = 0;
+        }
+    }
+
+    fn edit(&self) {
+        if let Some(current) = &self.current {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::EditHunk(
+                    current.path.clone(),
+                    current.hunk.header_hash,
+                ),
+            );
+        }
+    }
+
+    /// stages (`stage`) or skips the shown hunk, parts of a split
+    /// hunk are staged together after the last one was picked
+    fn pick(&mut self, stage: bool) -> Result<()> {
+        let current = match &self.current {
+            Some(current) => current,
+            None => return Ok(()),
+        };
+
+        if self.parts.is_empty() {
+            if stage {
+                if current.untracked {
+                    sync::stage_add_file(
+                        CWD,
+                        Path::new(&current.path),
+                    )?;
+                } else {
+                    sync::stage_hunk(
+                        CWD,
+                        current.path.clone(),
+                        current.hunk.header_hash,
+                    )?;
+                }
+                self.staged()?;
+            }
+
+            return self.next_hunk();
+        }
+
+        self.chosen.push(stage);
+        self.scroll = 0;
+
+        if self.chosen.len() == self.parts.len() {
+            let lines: Vec<_> = self
+                .parts
+                .iter()
+                .zip(&self.chosen)
+                .filter(|(_, chosen)| **chosen)
+                .flat_map(|(part, _)| &part.lines)
+                .filter(|line| {
+                    line.line_type == DiffLineType::Add
+                        || line.line_type == DiffLineType::Delete
+                })
+                .map(|line| line.position)
+                .collect();
+
+            if !lines.is_empty() {
+                sync::stage_lines(CWD, &current.path, false, &lines)?;
+                self.staged()?;
+            }
+
+            self.next_hunk()?;
+        }
+
+        Ok(())
+    }
+
+    fn staged(&mut self) -> Result<()> {
+        self.stats = sync::get_stage_stats(CWD)?;
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+
+        Ok(())
+    }
+
+    /// the shown part of a split hunk or the whole one
+    fn shown_hunk(&self) -> Option<&Hunk> {
+        self.parts
+            .get(self.chosen.len())
+            .or_else(|| self.current.as_ref().map(|c| &c.hunk))
+    }
+
+    fn hunk_lines(&self) -> usize {
+        self.shown_hunk().map_or(0, |hunk| hunk.lines.len())
+    }
+
+    fn get_title(&self) -> String {
+        self.current.as_ref().map_or_else(
+            || strings::pick_hunks_title_done(&self.key_config),
+            |current| {
+                let part = if self.parts.is_empty() {
+                    None
+                } else {
+                    Some((self.chosen.len() + 1, self.parts.len()))
+                };
+
+                strings::pick_hunks_title(
+                    &self.key_config,
+                    &current.path,
+                    (current.file.0 + 1, current.file.1),
+                    (current.index.0 + 1, current.index.1),
+                    part,
+                )
+            },
+        )
+    }
+
+    fn get_hunk_text(&self, height: usize) -> Text<'_> {
+        self.shown_hunk().map_or_else(
+            || {
+                Text::from(Span::styled(
+                    strings::pick_hunks_done(&self.key_config),
+                    self.theme.text(false, false),
+                ))
+            },
+            |hunk| {
+                let txt: Vec<Spans> = hunk
+                    .lines
+                    .iter()
+                    .skip(self.scroll)
+                    .take(height)
+                    .map(|line| {
+                        Spans::from(Span::styled(
+                            line.content
+                                .trim_end_matches(&['\n', '\r'][..])
+                                .replace('\t', "    "),
+                            self.theme
+                                .diff_line(line.line_type, false),
+                        ))
+                    })
+                    .collect();
+
+                Text::from(txt)
+            },
+        )
+    }
+
+    fn get_stage_title(&self) -> String {
+        let (additions, deletions) =
+            self.stats.iter().fold((0, 0), |(add, del), file| {
+                (add + file.additions, del + file.deletions)
+            });
+
+        strings::pick_hunks_stage_title(
+            &self.key_config,
+            self.stats.len(),
+            additions,
+            deletions,
+        )
+    }
+
+    fn get_stage_text(&self) -> Text<'_> {
+        let txt: Vec<Spans> = self
+            .stats
+            .iter()
+            .map(|file| {
+                Spans::from(vec![
+                    Span::styled(
+                        format!("+{} ", file.additions),
+                        self.theme
+                            .diff_line(DiffLineType::Add, false),
+                    ),
+                    Span::styled(
+                        format!("-{} ", file.deletions),
+                        self.theme
+                            .diff_line(DiffLineType::Delete, false),
+                    ),
+                    Span::styled(
+                        file.path.as_str(),
+                        self.theme.text(true, false),
+                    ),
+                ])
+            })
+            .collect();
+
+        Text::from(txt)
+    }
+}
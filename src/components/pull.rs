@@ -14,10 +14,10 @@ use asyncgit::{
     sync::{
         self,
         cred::{
-            extract_username_password, need_username_password,
-            BasicAuthCredential,
+            extract_username_password, need_ssh_passphrase,
+            need_username_password, BasicAuthCredential,
         },
-        get_default_remote,
+        get_default_remote, PullStrategy,
     },
     AsyncFetch, AsyncNotification, FetchRequest, RemoteProgress, CWD,
 };
@@ -38,6 +38,7 @@ pub struct PullComponent {
     progress: Option<RemoteProgress>,
     pending: bool,
     branch: String,
+    strategy: PullStrategy,
     queue: Queue,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
@@ -57,6 +58,7 @@ impl PullComponent {
             pending: false,
             visible: false,
             branch: String::new(),
+            strategy: PullStrategy::Merge,
             git_fetch: AsyncFetch::new(sender),
             progress: None,
             input_cred: CredComponent::new(
@@ -68,8 +70,16 @@ impl PullComponent {
         }
     }
 
-    ///
-    pub fn fetch(&mut self, branch: String) -> Result<()> {
+    /// `strategy` overrides the configured one for this pull
+    pub fn fetch(
+        &mut self,
+        branch: String,
+        strategy: Option<PullStrategy>,
+    ) -> Result<()> {
+        self.strategy = match strategy {
+            Some(strategy) => strategy,
+            None => sync::config_pull_strategy(CWD)?,
+        };
         self.branch = branch;
         self.show()?;
         if need_username_password()? {
@@ -83,6 +93,8 @@ impl PullComponent {
                 self.input_cred.set_cred(cred);
                 self.input_cred.show()
             }
+        } else if let Some(user) = need_ssh_passphrase()? {
+            self.input_cred.ask_passphrase(user)
         } else {
             self.fetch_from_remote(None)
         }
@@ -160,7 +172,26 @@ impl PullComponent {
             );
             if let Err(err) = ff_res {
                 log::trace!("ff failed: {}", err);
-                self.confirm_merge(branch_compare.behind);
+                if self.strategy != PullStrategy::FastForwardOnly {
+                    self.confirm_merge(branch_compare.behind);
+                } else if branch_compare.ahead > 0 {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(
+                            strings::pull_not_fast_forward(
+                                &self.key_config,
+                                branch_compare.ahead,
+                                branch_compare.behind,
+                            ),
+                        ),
+                    );
+                } else {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "pull failed:\n{}",
+                            err
+                        )),
+                    );
+                }
             }
         }
 
@@ -189,8 +220,7 @@ impl PullComponent {
         self.queue.borrow_mut().push_back(
             InternalEvent::ConfirmAction(Action::PullMerge {
                 incoming,
-                rebase: sync::config_is_pull_rebase(CWD)
-                    .unwrap_or_default(),
+                rebase: self.strategy == PullStrategy::Rebase,
             }),
         );
         self.hide();
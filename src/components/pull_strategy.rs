@@ -0,0 +1,200 @@
+use super::{
+    popup_paragraph, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{sync, sync::PullStrategy, CWD};
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::Clear,
+    Frame,
+};
+
+const STRATEGIES: [PullStrategy; 3] = [
+    PullStrategy::Merge,
+    PullStrategy::Rebase,
+    PullStrategy::FastForwardOnly,
+];
+
+/// picks how to pull this once, preselecting the configured way
+pub struct PullStrategyComponent {
+    branch: Option<String>,
+    selection: usize,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for PullStrategyComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if let (true, Some(branch)) = (self.visible, &self.branch) {
+            let mut txt = vec![
+                Spans::from(Span::styled(
+                    strings::pull_strategy_into(
+                        &self.key_config,
+                        branch,
+                    ),
+                    self.theme.text(true, false),
+                )),
+                Spans::default(),
+            ];
+
+            txt.extend(STRATEGIES.iter().enumerate().map(
+                |(i, strategy)| {
+                    Spans::from(Span::styled(
+                        format!(
+                            " {}",
+                            strings::pull_strategy_name(
+                                &self.key_config,
+                                *strategy,
+                            )
+                        ),
+                        self.theme.text(true, i == self.selection),
+                    ))
+                },
+            ));
+
+            let title =
+                strings::pull_strategy_title(&self.key_config);
+
+            let area = ui::centered_rect(50, 20, f.size());
+            f.render_widget(Clear, area);
+            f.render_widget(
+                popup_paragraph(
+                    &title,
+                    Text::from(txt),
+                    &self.theme,
+                    true,
+                ),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for PullStrategyComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::pull_strategy_confirm(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = (self.selection + 1)
+                        .min(STRATEGIES.len() - 1);
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.enter {
+                    self.confirm();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl PullStrategyComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            branch: None,
+            selection: 0,
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self, branch: String) -> Result<()> {
+        let configured = sync::config_pull_strategy(CWD)?;
+
+        self.branch = Some(branch);
+        self.selection = STRATEGIES
+            .iter()
+            .position(|s| *s == configured)
+            .unwrap_or_default();
+
+        self.show()
+    }
+
+    fn confirm(&mut self) {
+        if let Some(branch) = self.branch.take() {
+            self.queue.borrow_mut().push_back(InternalEvent::Pull(
+                branch,
+                Some(STRATEGIES[self.selection]),
+            ));
+        }
+
+        self.hide();
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
         CommandInfo, Component, DrawableComponent,
     },
     keys::SharedKeyConfig,
-    queue::{InternalEvent, Queue},
+    queue::{Advice, InternalEvent, Queue},
     strings,
     ui::{self, style::SharedTheme},
 };
@@ -12,8 +12,8 @@ use anyhow::Result;
 use asyncgit::{
     sync::{
         cred::{
-            extract_username_password, need_username_password,
-            BasicAuthCredential,
+            extract_username_password, need_ssh_passphrase,
+            need_username_password, BasicAuthCredential,
         },
         get_branch_remote, get_default_remote,
     },
@@ -38,6 +38,12 @@ pub struct PushComponent {
     progress: Option<RemoteProgress>,
     pending: bool,
     branch: String,
+    /// remote the branch will track after a push without upstream
+    new_upstream: Option<String>,
+    /// remote, branch on it and whether to track that after the push
+    target: Option<(String, String, bool)>,
+    /// remote and branch on it to delete instead of pushing
+    delete: Option<(String, String)>,
     queue: Queue,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
@@ -58,6 +64,9 @@ impl PushComponent {
             pending: false,
             visible: false,
             branch: String::new(),
+            new_upstream: None,
+            target: None,
+            delete: None,
             git_push: AsyncPush::new(sender),
             progress: None,
             input_cred: CredComponent::new(
@@ -75,6 +84,36 @@ impl PushComponent {
         branch: String,
         force: bool,
     ) -> Result<()> {
+        self.target = None;
+        self.delete = None;
+        self.start(branch, force)
+    }
+
+    /// pushes `branch` to `target` on `remote` instead of its upstream
+    pub fn push_to(
+        &mut self,
+        branch: String,
+        remote: String,
+        target: String,
+        set_upstream: bool,
+    ) -> Result<()> {
+        self.target = Some((remote, target, set_upstream));
+        self.delete = None;
+        self.start(branch, false)
+    }
+
+    /// like `git push <remote> --delete <branch>`
+    pub fn delete_remote_branch(
+        &mut self,
+        remote: String,
+        branch: String,
+    ) -> Result<()> {
+        self.target = None;
+        self.delete = Some((remote, branch.clone()));
+        self.start(branch, false)
+    }
+
+    fn start(&mut self, branch: String, force: bool) -> Result<()> {
         self.branch = branch;
         self.force = force;
         self.show()?;
@@ -90,6 +129,8 @@ impl PushComponent {
                 self.input_cred.set_cred(cred);
                 self.input_cred.show()
             }
+        } else if let Some(user) = need_ssh_passphrase()? {
+            self.input_cred.ask_passphrase(user)
         } else {
             self.push_to_remote(None, force)
         }
@@ -100,6 +141,51 @@ impl PushComponent {
         cred: Option<BasicAuthCredential>,
         force: bool,
     ) -> Result<()> {
+        self.new_upstream = None;
+
+        if let Some((remote, branch)) = &self.delete {
+            log::info!(
+                "push: delete branch '{}' on remote '{}'",
+                branch,
+                remote
+            );
+
+            self.pending = true;
+            self.progress = None;
+            self.git_push.request(PushRequest {
+                remote: remote.clone(),
+                branch: branch.clone(),
+                force: false,
+                target_branch: Some(branch.clone()),
+                set_upstream: false,
+                delete: true,
+                basic_credential: cred,
+            })?;
+            return Ok(());
+        }
+
+        if let Some((remote, target, set_upstream)) = &self.target {
+            log::info!(
+                "push: branch '{}' to '{}' on remote '{}'",
+                self.branch,
+                target,
+                remote
+            );
+
+            self.pending = true;
+            self.progress = None;
+            self.git_push.request(PushRequest {
+                remote: remote.clone(),
+                branch: self.branch.clone(),
+                force,
+                target_branch: Some(target.clone()),
+                set_upstream: *set_upstream,
+                delete: false,
+                basic_credential: cred,
+            })?;
+            return Ok(());
+        }
+
         let remote = if let Some(remote) =
             get_branch_remote(CWD, &self.branch)?
         {
@@ -113,6 +199,7 @@ impl PushComponent {
                 self.branch,
                 remote
             );
+            self.new_upstream = Some(remote.clone());
             remote
         };
 
@@ -122,6 +209,9 @@ impl PushComponent {
             remote,
             branch: self.branch.clone(),
             force,
+            target_branch: None,
+            set_upstream: false,
+            delete: false,
             basic_credential: cred,
         })?;
         Ok(())
@@ -154,6 +244,15 @@ impl PushComponent {
                         err
                     )),
                 );
+            } else if let Some(remote) = self.new_upstream.take() {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowAdvice(
+                        Advice::PushSetUpstream(
+                            self.branch.clone(),
+                            remote,
+                        ),
+                    ),
+                );
             }
             self.hide();
         }
@@ -184,22 +283,24 @@ impl PushComponent {
     fn progress_state_name(state: &RemoteProgressState) -> String {
         match state {
             RemoteProgressState::PackingAddingObject => {
-                strings::PUSH_POPUP_STATES_ADDING
+                strings::PUSH_POPUP_STATES_ADDING.into()
             }
             RemoteProgressState::PackingDeltafiction => {
-                strings::PUSH_POPUP_STATES_DELTAS
+                strings::PUSH_POPUP_STATES_DELTAS.into()
             }
             RemoteProgressState::Pushing => {
-                strings::PUSH_POPUP_STATES_PUSHING
+                strings::PUSH_POPUP_STATES_PUSHING.into()
             }
             RemoteProgressState::Transfer => {
-                strings::PUSH_POPUP_STATES_TRANSFER
+                strings::PUSH_POPUP_STATES_TRANSFER.into()
+            }
+            RemoteProgressState::Remote(phase) => {
+                strings::push_popup_states_remote(phase)
             }
             RemoteProgressState::Done => {
-                strings::PUSH_POPUP_STATES_DONE
+                strings::PUSH_POPUP_STATES_DONE.into()
             }
         }
-        .into()
     }
 }
 
@@ -222,7 +323,9 @@ impl DrawableComponent for PushComponent {
                     .block(
                         Block::default()
                             .title(Span::styled(
-                                if self.force {
+                                if self.delete.is_some() {
+                                    strings::DELETE_REMOTE_BRANCH_POPUP_MSG
+                                } else if self.force {
                                     strings::FORCE_PUSH_POPUP_MSG
                                 } else {
                                     strings::PUSH_POPUP_MSG
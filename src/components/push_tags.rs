@@ -12,10 +12,11 @@ use anyhow::Result;
 use asyncgit::{
     sync::{
         cred::{
-            extract_username_password, need_username_password,
-            BasicAuthCredential,
+            extract_username_password, need_ssh_passphrase,
+            need_username_password, BasicAuthCredential,
         },
         get_default_remote, AsyncProgress, PushTagsProgress,
+        PushTagsTarget,
     },
     AsyncNotification, AsyncPushTags, PushTagsRequest, CWD,
 };
@@ -33,6 +34,7 @@ use tui::{
 pub struct PushTagsComponent {
     visible: bool,
     git_push: AsyncPushTags,
+    target: PushTagsTarget,
     progress: Option<PushTagsProgress>,
     pending: bool,
     queue: Queue,
@@ -54,6 +56,7 @@ impl PushTagsComponent {
             pending: false,
             visible: false,
             git_push: AsyncPushTags::new(sender),
+            target: PushTagsTarget::Missing,
             progress: None,
             input_cred: CredComponent::new(
                 theme.clone(),
@@ -65,7 +68,11 @@ impl PushTagsComponent {
     }
 
     ///
-    pub fn push_tags(&mut self) -> Result<()> {
+    pub fn push_tags(
+        &mut self,
+        target: PushTagsTarget,
+    ) -> Result<()> {
+        self.target = target;
         self.show()?;
         if need_username_password()? {
             let cred =
@@ -78,6 +85,8 @@ impl PushTagsComponent {
                 self.input_cred.set_cred(cred);
                 self.input_cred.show()
             }
+        } else if let Some(user) = need_ssh_passphrase()? {
+            self.input_cred.ask_passphrase(user)
         } else {
             self.push_to_remote(None)
         }
@@ -91,6 +100,7 @@ impl PushTagsComponent {
         self.progress = None;
         self.git_push.request(PushTagsRequest {
             remote: get_default_remote(CWD)?,
+            target: self.target.clone(),
             basic_credential: cred,
         })?;
         Ok(())
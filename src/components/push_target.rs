@@ -0,0 +1,278 @@
+use super::{
+    popup_paragraph, textinput::TextInputComponent,
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent, InputType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{sync, CWD};
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::Clear,
+    Frame,
+};
+
+/// picks the remote and then the branch on it to push the current
+/// branch to
+pub struct PushTargetComponent {
+    branch: String,
+    remotes: Vec<String>,
+    selection: usize,
+    /// set once the remote is picked and the branch is asked for
+    remote: Option<String>,
+    set_upstream: bool,
+    visible: bool,
+    input: TextInputComponent,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for PushTargetComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+
+        if self.remote.is_some() {
+            return self.input.draw(f, rect);
+        }
+
+        let mut txt = vec![
+            Spans::from(Span::styled(
+                strings::push_target_remote(
+                    &self.key_config,
+                    &self.branch,
+                ),
+                self.theme.text(true, false),
+            )),
+            Spans::default(),
+        ];
+
+        txt.extend(self.remotes.iter().enumerate().map(
+            |(i, remote)| {
+                Spans::from(Span::styled(
+                    format!(" {}", remote),
+                    self.theme.text(true, i == self.selection),
+                ))
+            },
+        ));
+
+        let title = strings::push_target_title(&self.key_config);
+
+        let area = ui::centered_rect(50, 20, f.size());
+        f.render_widget(Clear, area);
+        f.render_widget(
+            popup_paragraph(
+                &title,
+                Text::from(txt),
+                &self.theme,
+                true,
+            ),
+            area,
+        );
+
+        Ok(())
+    }
+}
+
+impl Component for PushTargetComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            if self.remote.is_some() {
+                self.input.commands(out, force_all);
+
+                out.push(CommandInfo::new(
+                    strings::commands::push_target_confirm(
+                        &self.key_config,
+                    ),
+                    !self.input.get_text().trim().is_empty(),
+                    true,
+                ));
+                out.push(CommandInfo::new(
+                    strings::commands::push_toggle_upstream(
+                        &self.key_config,
+                        self.set_upstream,
+                    ),
+                    true,
+                    true,
+                ));
+            } else {
+                out.push(CommandInfo::new(
+                    strings::commands::scroll(&self.key_config),
+                    true,
+                    true,
+                ));
+                out.push(CommandInfo::new(
+                    strings::commands::push_target_select_remote(
+                        &self.key_config,
+                    ),
+                    !self.remotes.is_empty(),
+                    true,
+                ));
+                out.push(CommandInfo::new(
+                    strings::commands::close_popup(&self.key_config),
+                    true,
+                    true,
+                ));
+            }
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if !self.visible {
+            return Ok(false);
+        }
+
+        if self.remote.is_some() {
+            if self.input.event(ev)? {
+                if !self.input.is_visible() {
+                    self.hide();
+                }
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter {
+                    self.confirm();
+                } else if e == self.key_config.push_toggle_upstream {
+                    self.set_upstream = !self.set_upstream;
+                    self.update_title();
+                }
+            }
+        } else if let Event::Key(e) = ev {
+            if e == self.key_config.exit_popup {
+                self.hide();
+            } else if e == self.key_config.move_down {
+                self.selection = (self.selection + 1)
+                    .min(self.remotes.len().saturating_sub(1));
+            } else if e == self.key_config.move_up {
+                self.selection = self.selection.saturating_sub(1);
+            } else if e == self.key_config.enter {
+                self.ask_branch()?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+        self.input.hide();
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl PushTargetComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            branch: String::new(),
+            remotes: Vec::new(),
+            selection: 0,
+            remote: None,
+            set_upstream: false,
+            visible: false,
+            input: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                "",
+                &strings::push_target_branch_msg(&key_config),
+                true,
+            )
+            .with_input_type(InputType::Singleline),
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// setting the upstream defaults to on for a branch without one
+    pub fn open(&mut self, branch: String) -> Result<()> {
+        self.remotes = sync::get_remotes(CWD)?;
+        self.selection = sync::get_default_remote(CWD)
+            .ok()
+            .and_then(|default| {
+                self.remotes.iter().position(|r| *r == default)
+            })
+            .unwrap_or_default();
+        self.set_upstream =
+            sync::get_branch_remote(CWD, &branch)?.is_none();
+        self.branch = branch;
+        self.remote = None;
+
+        self.show()
+    }
+
+    fn ask_branch(&mut self) -> Result<()> {
+        if let Some(remote) = self.remotes.get(self.selection) {
+            self.remote = Some(remote.clone());
+            self.input.set_text(self.branch.clone());
+            self.update_title();
+            self.input.show()?;
+        }
+
+        Ok(())
+    }
+
+    fn update_title(&mut self) {
+        if let Some(remote) = &self.remote {
+            self.input.set_title(strings::push_target_branch_title(
+                &self.key_config,
+                remote,
+                self.set_upstream,
+            ));
+        }
+    }
+
+    fn confirm(&mut self) {
+        let target = self.input.get_text().trim().to_string();
+        if target.is_empty() {
+            return;
+        }
+
+        if let Some(remote) = self.remote.take() {
+            self.queue.borrow_mut().push_back(InternalEvent::PushTo(
+                self.branch.clone(),
+                remote,
+                target,
+                self.set_upstream,
+            ));
+        }
+
+        self.hide();
+    }
+}
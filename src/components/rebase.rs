@@ -0,0 +1,501 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings, try_or_popup,
+    ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{
+        self, CommitId, RebaseAction, RebaseProgress, RebaseState,
+        RebaseTodo,
+    },
+    CWD,
+};
+use crossterm::event::{Event, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// edits the todo list of an interactive rebase and drives it,
+/// once stopped by a conflict it offers to continue, skip or abort
+pub struct RebaseComponent {
+    base: Option<CommitId>,
+    todo: Vec<RebaseTodo>,
+    progress: Option<RebaseProgress>,
+    selection: usize,
+    reword_input: TextInputComponent,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for RebaseComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(70, 60);
+            const MIN_SIZE: Size = Size::new(60, 12);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height = (area.height as usize).saturating_sub(2);
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height)).block(
+                    Block::default()
+                        .title(Span::styled(
+                            self.get_title(),
+                            self.theme.title(true),
+                        ))
+                        .border_type(BorderType::Thick)
+                        .borders(Borders::ALL),
+                ),
+                area,
+            );
+
+            self.reword_input.draw(f, rect)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for RebaseComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.reword_input.is_visible() {
+            return self.reword_input.commands(out, force_all);
+        }
+
+        if self.visible || force_all {
+            out.clear();
+
+            let stopped = self.progress.is_some();
+
+            out.push(CommandInfo::new(
+                strings::commands::rebase_set_action(
+                    &self.key_config,
+                ),
+                true,
+                !stopped,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_move(&self.key_config),
+                true,
+                !stopped,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_start(&self.key_config),
+                true,
+                !stopped,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_continue(&self.key_config),
+                true,
+                stopped,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_skip(&self.key_config),
+                true,
+                stopped,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::rebase_abort(&self.key_config),
+                true,
+                stopped,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if !self.visible {
+            return Ok(false);
+        }
+
+        if self.reword_input.is_visible() {
+            if self.reword_input.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter {
+                    self.confirm_reword();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        if let Event::Key(e) = ev {
+            if e == self.key_config.exit_popup {
+                self.hide();
+            } else if self.progress.is_some() {
+                self.event_stopped(e);
+            } else {
+                self.event_todo(e)?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl RebaseComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            base: None,
+            todo: Vec::new(),
+            progress: None,
+            selection: 0,
+            reword_input: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                &strings::rebase_reword_title(&key_config),
+                &strings::rebase_reword_msg(&key_config),
+                true,
+            ),
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// todo list of the commits after `base`, or the state of the
+    /// rebase in progress
+    pub fn open(&mut self, base: CommitId) -> Result<()> {
+        self.selection = 0;
+        self.progress = sync::get_rebase_progress(CWD)?;
+
+        if self.progress.is_none() {
+            let todo = match sync::get_rebase_todo(CWD, base) {
+                Ok(todo) if todo.is_empty() => {
+                    self.show_error(strings::rebase_nothing_msg(
+                        &self.key_config,
+                    ));
+                    return Ok(());
+                }
+                Ok(todo) => todo,
+                Err(e) => {
+                    self.show_error(format!("rebase error:\n{}", e));
+                    return Ok(());
+                }
+            };
+
+            self.base = Some(base);
+            self.todo = todo;
+        }
+
+        self.show()
+    }
+
+    /// todo list squashing `ids` into the oldest of them, or the
+    /// state of the rebase in progress
+    pub fn open_squash(&mut self, ids: &[CommitId]) -> Result<()> {
+        self.open_prepared("squash", || {
+            sync::get_squash_todo(CWD, ids).map_err(Into::into)
+        })
+    }
+
+    /// todo list folding in the `fixup!` and `squash!` commits, or
+    /// the state of the rebase in progress
+    pub fn open_autosquash(&mut self) -> Result<()> {
+        self.open_prepared("autosquash", || {
+            sync::get_autosquash_todo(CWD).map_err(Into::into)
+        })
+    }
+
+    /// `prepare` returns the base and todo list of a new rebase
+    fn open_prepared(
+        &mut self,
+        what: &str,
+        prepare: impl FnOnce() -> Result<(CommitId, Vec<RebaseTodo>)>,
+    ) -> Result<()> {
+        self.selection = 0;
+        self.progress = sync::get_rebase_progress(CWD)?;
+
+        if self.progress.is_none() {
+            match prepare() {
+                Ok((base, todo)) => {
+                    self.base = Some(base);
+                    self.todo = todo;
+                }
+                Err(e) => {
+                    self.show_error(format!(
+                        "{} error:\n{}",
+                        what, e
+                    ));
+                    return Ok(());
+                }
+            }
+        }
+
+        self.show()
+    }
+
+    fn show_error(&self, msg: String) {
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::ShowErrorMsg(msg));
+    }
+
+    fn event_todo(&mut self, e: KeyEvent) -> Result<()> {
+        let last = self.todo.len().saturating_sub(1);
+
+        if e == self.key_config.move_down {
+            self.selection = (self.selection + 1).min(last);
+        } else if e == self.key_config.move_up {
+            self.selection = self.selection.saturating_sub(1);
+        } else if e == self.key_config.shift_down
+            && self.selection < last
+        {
+            self.todo.swap(self.selection, self.selection + 1);
+            self.selection += 1;
+        } else if e == self.key_config.shift_up && self.selection > 0
+        {
+            self.todo.swap(self.selection, self.selection - 1);
+            self.selection -= 1;
+        } else if e == self.key_config.rebase_pick {
+            self.set_action(RebaseAction::Pick);
+        } else if e == self.key_config.rebase_squash {
+            self.set_action(RebaseAction::Squash);
+        } else if e == self.key_config.rebase_fixup {
+            self.set_action(RebaseAction::Fixup);
+        } else if e == self.key_config.rebase_drop {
+            self.set_action(RebaseAction::Drop);
+        } else if e == self.key_config.rebase_reword {
+            self.open_reword()?;
+        } else if e == self.key_config.enter {
+            if let Some(base) = self.base {
+                let res =
+                    sync::rebase_interactive(CWD, base, &self.todo);
+                self.handle_result(res.map_err(Into::into));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn event_stopped(&mut self, e: KeyEvent) {
+        if e == self.key_config.enter {
+            let res = sync::rebase_continue(CWD);
+            self.handle_result(res.map_err(Into::into));
+        } else if e == self.key_config.rebase_skip {
+            let res = sync::rebase_skip(CWD);
+            self.handle_result(res.map_err(Into::into));
+        } else if e == self.key_config.rebase_abort {
+            try_or_popup!(
+                self,
+                "rebase abort error:",
+                sync::rebase_abort(CWD)
+            );
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+            self.hide();
+        }
+    }
+
+    /// stays open to show a conflict, closes once finished
+    fn handle_result(&mut self, res: Result<RebaseState>) {
+        match res {
+            Ok(RebaseState::Finished) => self.hide(),
+            Ok(RebaseState::Conflict(_)) => {
+                self.selection = 0;
+                match sync::get_rebase_progress(CWD) {
+                    Ok(progress) => self.progress = progress,
+                    Err(e) => {
+                        self.show_error(format!(
+                            "rebase error:\n{}",
+                            e
+                        ));
+                    }
+                }
+            }
+            Err(e) => {
+                self.show_error(format!("rebase error:\n{}", e));
+            }
+        }
+
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+    }
+
+    fn set_action(&mut self, action: RebaseAction) {
+        if let Some(item) = self.todo.get_mut(self.selection) {
+            item.action = action;
+        }
+    }
+
+    fn open_reword(&mut self) -> Result<()> {
+        if let Some(item) = self.todo.get(self.selection) {
+            let msg = if let RebaseAction::Reword(msg) = &item.action
+            {
+                msg.clone()
+            } else {
+                sync::get_commit_details(CWD, item.id)?
+                    .message
+                    .map_or_else(String::new, |msg| match msg.body {
+                        // the body starts with the blank line
+                        Some(body) => {
+                            format!("{}\n{}", msg.subject, body)
+                        }
+                        None => msg.subject,
+                    })
+            };
+
+            self.reword_input.set_text(msg);
+            self.reword_input.show()?;
+        }
+
+        Ok(())
+    }
+
+    fn confirm_reword(&mut self) {
+        let msg = self.reword_input.get_text().trim().to_string();
+
+        if !msg.is_empty() {
+            self.set_action(RebaseAction::Reword(msg));
+        }
+
+        self.reword_input.hide();
+    }
+
+    fn get_title(&self) -> String {
+        match (&self.progress, self.base) {
+            (Some(progress), _) => strings::rebase_stopped_title(
+                &self.key_config,
+                progress.done,
+                progress.done
+                    + progress.remaining.len()
+                    + usize::from(progress.current.is_some()),
+            ),
+            (None, Some(base)) => strings::rebase_title(
+                &self.key_config,
+                &base.get_short_string(),
+                self.todo.len(),
+            ),
+            (None, None) => String::new(),
+        }
+    }
+
+    fn todo_line(
+        &self,
+        item: &RebaseTodo,
+        selected: bool,
+    ) -> Spans<'_> {
+        let action = Span::styled(
+            format!("{:<7}", item.action.name()),
+            if item.action == RebaseAction::Drop {
+                self.theme.text(false, selected)
+            } else {
+                self.theme.text(true, selected)
+            },
+        );
+
+        Spans::from(vec![
+            action,
+            Span::styled(
+                format!("{} ", item.id.get_short_string()),
+                self.theme.commit_hash(selected),
+            ),
+            Span::styled(
+                match &item.action {
+                    RebaseAction::Reword(msg) => {
+                        msg.lines().next().unwrap_or_default()
+                    }
+                    _ => item.summary.as_str(),
+                }
+                .to_string(),
+                self.theme.text(true, selected),
+            ),
+        ])
+    }
+
+    fn get_text(&self, height: usize) -> Text<'_> {
+        let mut txt: Vec<Spans> = Vec::new();
+
+        if let Some(progress) = &self.progress {
+            txt.push(Spans::from(Span::styled(
+                strings::rebase_conflict_msg(&self.key_config),
+                self.theme.text_danger(),
+            )));
+            txt.push(Spans::from(""));
+
+            if let Some(current) = &progress.current {
+                txt.push(self.todo_line(current, true));
+            }
+            txt.extend(
+                progress
+                    .remaining
+                    .iter()
+                    .map(|item| self.todo_line(item, false)),
+            );
+        } else {
+            let skip = self
+                .selection
+                .saturating_sub(height.saturating_sub(1));
+
+            txt.extend(self.todo.iter().enumerate().skip(skip).map(
+                |(idx, item)| {
+                    self.todo_line(item, idx == self.selection)
+                },
+            ));
+        }
+
+        txt.truncate(height);
+
+        Text::from(txt)
+    }
+}
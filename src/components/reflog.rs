@@ -0,0 +1,343 @@
+use super::{
+    utils::time_to_string, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{Action, InternalEvent, NeedsUpdate, Queue},
+    strings, try_or_popup,
+    ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId, ReflogEntry, ResetKind},
+    CWD,
+};
+use crossterm::event::Event;
+use std::cell::Cell;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const HEAD_REF: &str = "HEAD";
+
+/// the reflog of `HEAD` or the current branch, the selected entry
+/// can be checked out or hard reset to
+pub struct ReflogComponent {
+    entries: Vec<ReflogEntry>,
+    ref_name: String,
+    /// full ref of the current branch, `None` if detached
+    branch_ref: Option<String>,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    current_height: Cell<usize>,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ReflogComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(80, 70);
+            const MIN_SIZE: Size = Size::new(60, 20);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height = (area.height as usize).saturating_sub(2);
+            self.current_height.set(height);
+            self.scroll_top.set(ui::calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                self.selection,
+            ));
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height)).block(
+                    Block::default()
+                        .title(Span::styled(
+                            strings::reflog_title(
+                                &self.key_config,
+                                &self.ref_name,
+                            ),
+                            self.theme.title(true),
+                        ))
+                        .border_type(BorderType::Thick)
+                        .borders(Borders::ALL),
+                ),
+                area,
+            );
+
+            ui::draw_scrollbar(
+                f,
+                area,
+                &self.theme,
+                self.entries.len(),
+                self.scroll_top.get(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ReflogComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            let selected = self.selected_id().is_some();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::reflog_checkout(&self.key_config),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::reflog_reset_hard(
+                    &self.key_config,
+                ),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::reflog_inspect_commit(
+                    &self.key_config,
+                ),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::reflog_toggle_ref(
+                    &self.key_config,
+                ),
+                self.branch_ref.is_some(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                let last = self.entries.len().saturating_sub(1);
+                let page = self.current_height.get().max(1);
+
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = (self.selection + 1).min(last);
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.page_down {
+                    self.selection =
+                        self.selection.saturating_add(page).min(last);
+                } else if e == self.key_config.page_up {
+                    self.selection =
+                        self.selection.saturating_sub(page);
+                } else if e == self.key_config.tab_toggle {
+                    try_or_popup!(
+                        self,
+                        "reflog error:",
+                        self.toggle_ref()
+                    );
+                } else if e == self.key_config.enter {
+                    try_or_popup!(
+                        self,
+                        "checkout error:",
+                        self.checkout_selected()
+                    );
+                } else if e == self.key_config.reflog_reset_hard {
+                    if let Some(id) = self.selected_id() {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ConfirmAction(
+                                Action::ResetToCommit(
+                                    id,
+                                    ResetKind::Hard,
+                                ),
+                            ),
+                        );
+                    }
+                } else if e == self.key_config.focus_right {
+                    if let Some(id) = self.selected_id() {
+                        self.hide();
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::InspectCommit(id, None),
+                        );
+                    }
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl ReflogComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            entries: Vec::new(),
+            ref_name: HEAD_REF.to_string(),
+            branch_ref: None,
+            selection: 0,
+            scroll_top: Cell::new(0),
+            current_height: Cell::new(0),
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// shows the reflog of `HEAD`
+    pub fn open(&mut self) -> Result<()> {
+        let head = sync::get_head_tuple(CWD)?;
+        self.branch_ref =
+            Some(head.name).filter(|name| name != HEAD_REF);
+
+        self.ref_name = HEAD_REF.to_string();
+        self.load()?;
+
+        self.show()
+    }
+
+    /// reloads the entries after the ref moved
+    pub fn refresh(&mut self) -> Result<()> {
+        if self.visible {
+            self.load()?;
+        }
+
+        Ok(())
+    }
+
+    fn load(&mut self) -> Result<()> {
+        self.entries = sync::get_reflog(CWD, &self.ref_name)?;
+        self.selection = 0;
+        self.scroll_top.set(0);
+
+        Ok(())
+    }
+
+    fn toggle_ref(&mut self) -> Result<()> {
+        if let Some(branch_ref) = &self.branch_ref {
+            self.ref_name = if self.ref_name == HEAD_REF {
+                branch_ref.clone()
+            } else {
+                HEAD_REF.to_string()
+            };
+            self.load()?;
+        }
+
+        Ok(())
+    }
+
+    fn selected_id(&self) -> Option<CommitId> {
+        self.entries.get(self.selection).map(|entry| entry.id)
+    }
+
+    fn checkout_selected(&mut self) -> Result<()> {
+        if let Some(id) = self.selected_id() {
+            sync::checkout_commit(CWD, id)?;
+
+            self.branch_ref = None;
+            self.ref_name = HEAD_REF.to_string();
+            self.load()?;
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+        }
+
+        Ok(())
+    }
+
+    fn get_text(&self, height: usize) -> Text<'_> {
+        let txt: Vec<Spans> = self
+            .entries
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|(idx, entry)| {
+                let selected = idx == self.selection;
+
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{} ", entry.id.get_short_string()),
+                        self.theme.commit_hash(selected),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{} ",
+                            time_to_string(entry.time, true)
+                        ),
+                        self.theme.commit_time(selected),
+                    ),
+                    Span::styled(
+                        format!("{{{}}} ", idx),
+                        self.theme.text(false, selected),
+                    ),
+                    Span::styled(
+                        entry.message.as_str(),
+                        self.theme.text(true, selected),
+                    ),
+                ])
+            })
+            .collect();
+
+        Text::from(txt)
+    }
+}
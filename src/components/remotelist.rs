@@ -0,0 +1,394 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+    InputType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{Action, InternalEvent, NeedsUpdate, Queue},
+    strings, try_or_popup,
+    ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, RemoteInfo},
+    CWD,
+};
+use crossterm::event::Event;
+use std::cell::Cell;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// what the input is asked for
+enum Edit {
+    AddName,
+    /// name of the remote to add
+    AddUrl(String),
+    Rename(String),
+    SetUrl(String),
+}
+
+/// the remotes of the repo with their urls, remotes are added,
+/// renamed, pointed elsewhere and removed here
+pub struct RemoteListComponent {
+    remotes: Vec<RemoteInfo>,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    visible: bool,
+    edit: Option<Edit>,
+    input: TextInputComponent,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for RemoteListComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(70, 40);
+            const MIN_SIZE: Size = Size::new(60, 10);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height = (area.height as usize).saturating_sub(2);
+            self.scroll_top.set(ui::calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                self.selection,
+            ));
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height)).block(
+                    Block::default()
+                        .title(Span::styled(
+                            strings::remotes_title(&self.key_config),
+                            self.theme.title(self.edit.is_none()),
+                        ))
+                        .border_type(BorderType::Thick)
+                        .borders(Borders::ALL),
+                ),
+                area,
+            );
+
+            self.input.draw(f, rect)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for RemoteListComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            if self.edit.is_some() {
+                self.input.commands(out, force_all);
+
+                out.push(CommandInfo::new(
+                    strings::commands::remote_edit_confirm(
+                        &self.key_config,
+                    ),
+                    !self.input.get_text().trim().is_empty(),
+                    true,
+                ));
+
+                return visibility_blocking(self);
+            }
+
+            let selected = self.selected().is_some();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::remote_add(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::remote_rename(&self.key_config),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::remote_set_url(&self.key_config),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::remote_remove(&self.key_config),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if !self.visible {
+            return Ok(false);
+        }
+
+        if self.edit.is_some() {
+            if self.input.event(ev)? {
+                if !self.input.is_visible() {
+                    self.edit = None;
+                }
+            } else if let Event::Key(e) = ev {
+                if e == self.key_config.enter {
+                    try_or_popup!(
+                        self,
+                        "remote error:",
+                        self.confirm_edit()
+                    );
+                }
+            }
+
+            return Ok(true);
+        }
+
+        if let Event::Key(e) = ev {
+            if e == self.key_config.exit_popup {
+                self.hide();
+            } else if e == self.key_config.move_down {
+                self.selection = (self.selection + 1)
+                    .min(self.remotes.len().saturating_sub(1));
+            } else if e == self.key_config.move_up {
+                self.selection = self.selection.saturating_sub(1);
+            } else if e == self.key_config.remote_add {
+                self.start_edit(Edit::AddName, String::new())?;
+            } else if e == self.key_config.remote_rename {
+                if let Some(name) = self.selected_name() {
+                    self.start_edit(
+                        Edit::Rename(name.clone()),
+                        name,
+                    )?;
+                }
+            } else if e == self.key_config.remote_set_url {
+                if let Some(remote) = self.selected() {
+                    let url = remote.url.clone().unwrap_or_default();
+                    let name = remote.name.clone();
+                    self.start_edit(Edit::SetUrl(name), url)?;
+                }
+            } else if e == self.key_config.remote_remove {
+                if let Some(name) = self.selected_name() {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ConfirmAction(
+                            Action::RemoveRemote(name),
+                        ),
+                    );
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+        self.edit = None;
+        self.input.hide();
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl RemoteListComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            remotes: Vec::new(),
+            selection: 0,
+            scroll_top: Cell::new(0),
+            visible: false,
+            edit: None,
+            input: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                "",
+                "",
+                false,
+            )
+            .with_input_type(InputType::Singleline),
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self) -> Result<()> {
+        self.remotes = sync::get_remotes_info(CWD)?;
+        self.selection = 0;
+        self.edit = None;
+
+        self.show()
+    }
+
+    /// reloads the list after remotes were changed
+    pub fn refresh(&mut self) -> Result<()> {
+        if self.visible {
+            self.remotes = sync::get_remotes_info(CWD)?;
+            self.selection = self
+                .selection
+                .min(self.remotes.len().saturating_sub(1));
+        }
+
+        Ok(())
+    }
+
+    fn selected(&self) -> Option<&RemoteInfo> {
+        self.remotes.get(self.selection)
+    }
+
+    fn selected_name(&self) -> Option<String> {
+        self.selected().map(|remote| remote.name.clone())
+    }
+
+    fn start_edit(&mut self, edit: Edit, text: String) -> Result<()> {
+        let (title, msg) = match &edit {
+            Edit::AddName => (
+                strings::remote_add_title(&self.key_config),
+                strings::remote_name_msg(&self.key_config),
+            ),
+            Edit::AddUrl(name) | Edit::SetUrl(name) => (
+                strings::remote_url_title(&self.key_config, name),
+                strings::remote_url_msg(&self.key_config),
+            ),
+            Edit::Rename(name) => (
+                strings::remote_rename_title(&self.key_config, name),
+                strings::remote_name_msg(&self.key_config),
+            ),
+        };
+
+        self.edit = Some(edit);
+        self.input.set_title(title);
+        self.input.set_default_msg(msg);
+        self.input.set_text(text);
+        self.input.show()
+    }
+
+    fn confirm_edit(&mut self) -> Result<()> {
+        let text = self.input.get_text().trim().to_string();
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let edit = self.edit.take();
+        self.input.hide();
+
+        match edit {
+            Some(Edit::AddName) => {
+                return self
+                    .start_edit(Edit::AddUrl(text), String::new())
+            }
+            Some(Edit::AddUrl(name)) => {
+                sync::add_remote(CWD, &name, &text)?;
+            }
+            Some(Edit::Rename(name)) => {
+                sync::rename_remote(CWD, &name, &text)?;
+            }
+            Some(Edit::SetUrl(name)) => {
+                sync::set_url(CWD, &name, &text)?;
+            }
+            None => (),
+        }
+
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+
+        self.refresh()
+    }
+
+    fn get_text(&self, height: usize) -> Text<'_> {
+        let name_width = self
+            .remotes
+            .iter()
+            .map(|remote| remote.name.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let txt: Vec<Spans> = self
+            .remotes
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|(idx, remote)| {
+                let selected = idx == self.selection;
+
+                let mut spans = vec![
+                    Span::styled(
+                        format!(
+                            "{:w$} ",
+                            remote.name,
+                            w = name_width
+                        ),
+                        self.theme.branch(selected, false),
+                    ),
+                    Span::styled(
+                        remote.url.clone().unwrap_or_default(),
+                        self.theme.text(true, selected),
+                    ),
+                ];
+
+                if let Some(push_url) = &remote.push_url {
+                    spans.push(Span::styled(
+                        strings::remote_push_url(
+                            &self.key_config,
+                            push_url,
+                        ),
+                        self.theme.text(false, selected),
+                    ));
+                }
+
+                Spans::from(spans)
+            })
+            .collect();
+
+        Text::from(txt)
+    }
+}
@@ -134,6 +134,7 @@ impl ResetComponent {
         self.hide();
     }
 
+    #[allow(clippy::too_many_lines)]
     fn get_text(&self) -> (String, String) {
         if let Some(ref a) = self.target {
             return match a {
@@ -141,6 +142,13 @@ impl ResetComponent {
                     strings::confirm_title_reset(&self.key_config),
                     strings::confirm_msg_reset(&self.key_config),
                 ),
+                Action::ResetItems(paths) => (
+                    strings::confirm_title_reset(&self.key_config),
+                    strings::confirm_msg_reset_items(
+                        &self.key_config,
+                        paths.len(),
+                    ),
+                ),
                 Action::StashDrop(_) => (
                     strings::confirm_title_stashdrop(
                         &self.key_config,
@@ -151,7 +159,7 @@ impl ResetComponent {
                     strings::confirm_title_reset(&self.key_config),
                     strings::confirm_msg_resethunk(&self.key_config),
                 ),
-                Action::ResetLines(_, lines) => (
+                Action::ResetLines(_, lines, _) => (
                     strings::confirm_title_reset(&self.key_config),
                     strings::confirm_msg_reset_lines(&self.key_config,lines.len()),
                 ),
@@ -164,19 +172,109 @@ impl ResetComponent {
                         branch_ref,
                     ),
                 ),
-                Action::ForcePush(branch, _force) => (
+                Action::DeleteBranches(branch_refs) => (
+                    strings::confirm_title_delete_branches(
+                        &self.key_config,
+                    ),
+                    strings::confirm_msg_delete_branches(
+                        &self.key_config,
+                        branch_refs,
+                    ),
+                ),
+                Action::DeleteRemoteBranch(branch_ref) => (
+                    strings::confirm_title_delete_remote_branch(
+                        &self.key_config,
+                    ),
+                    strings::confirm_msg_delete_remote_branch(
+                        &self.key_config,
+                        branch_ref.trim_start_matches("refs/remotes/"),
+                    ),
+                ),
+                Action::ForcePush(branch, overwritten) => (
                     strings::confirm_title_force_push(
                         &self.key_config,
                     ),
                     strings::confirm_msg_force_push(
                         &self.key_config,
                         branch.rsplit('/').next().expect("There was no / in the head reference which is impossible in git"),
+                        *overwritten,
                     ),
                 ),
                 Action::PullMerge{incoming,rebase} => (
                     strings::confirm_title_merge(&self.key_config,*rebase),
                     strings::confirm_msg_merge(&self.key_config,*incoming,*rebase),
                 ),
+                Action::Clean(_, paths) => (
+                    strings::confirm_title_clean(&self.key_config),
+                    strings::confirm_msg_clean(&self.key_config, paths.len()),
+                ),
+                Action::AmendNoEdit { pushed } => (
+                    strings::confirm_title_amend_no_edit(&self.key_config),
+                    strings::confirm_msg_amend_no_edit(&self.key_config, *pushed),
+                ),
+                Action::RevertCommit(id) => (
+                    strings::confirm_title_revert_commit(&self.key_config),
+                    strings::confirm_msg_revert_commit(
+                        &self.key_config,
+                        &id.get_short_string(),
+                    ),
+                ),
+                Action::DropCommit(id, pushed) => (
+                    strings::confirm_title_drop_commit(&self.key_config),
+                    strings::confirm_msg_drop_commit(
+                        &self.key_config,
+                        &id.get_short_string(),
+                        *pushed,
+                    ),
+                ),
+                Action::AbortState(state) => (
+                    strings::confirm_title_abort_state(&self.key_config, *state),
+                    strings::confirm_msg_abort_state(&self.key_config, *state),
+                ),
+                Action::RemoveWorktree(name) => (
+                    strings::confirm_title_remove_worktree(&self.key_config),
+                    strings::confirm_msg_remove_worktree(&self.key_config, name),
+                ),
+                Action::RemoveRemote(name) => (
+                    strings::confirm_title_remove_remote(&self.key_config),
+                    strings::confirm_msg_remove_remote(&self.key_config, name),
+                ),
+                Action::ResetToCommit(id, kind) => (
+                    strings::confirm_title_reset_commit(&self.key_config, *kind),
+                    strings::confirm_msg_reset_commit(
+                        &self.key_config,
+                        &id.get_short_string(),
+                        *kind,
+                    ),
+                ),
+                Action::RestoreFile(id, path) => (
+                    strings::confirm_title_restore_file(&self.key_config),
+                    strings::confirm_msg_restore_file(
+                        &self.key_config,
+                        &id.get_short_string(),
+                        path,
+                    ),
+                ),
+                Action::RemoveIndexLock => (
+                    strings::confirm_title_index_lock(&self.key_config),
+                    strings::confirm_msg_index_lock(&self.key_config),
+                ),
+                Action::RebuildIndex => (
+                    strings::confirm_title_rebuild_index(&self.key_config),
+                    strings::confirm_msg_rebuild_index(&self.key_config),
+                ),
+                Action::DeleteTag(tag) => (
+                    strings::confirm_title_delete_tag(&self.key_config, false),
+                    strings::confirm_msg_delete_tag(&self.key_config, tag, false),
+                ),
+                Action::DeleteRemoteTag(tag) => (
+                    strings::confirm_title_delete_tag(&self.key_config, true),
+                    strings::confirm_msg_delete_tag(&self.key_config, tag, true),
+                ),
+                Action::UndoDiscard(paths) => (
+                    strings::confirm_title_undo_discard(&self.key_config),
+                    strings::confirm_msg_undo_discard(&self.key_config, paths),
+                ),
             };
         }
 
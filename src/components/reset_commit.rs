@@ -0,0 +1,187 @@
+use super::{
+    popup_paragraph, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{Action, InternalEvent, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{CommitId, ResetKind};
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::Clear,
+    Frame,
+};
+
+/// offered in this order, mixed is preselected like in `git reset`
+const KINDS: [ResetKind; 3] =
+    [ResetKind::Soft, ResetKind::Mixed, ResetKind::Hard];
+
+/// picks how the current branch is reset to a commit, the reset
+/// itself is confirmed once more
+pub struct ResetCommitComponent {
+    commit: Option<CommitId>,
+    selection: usize,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ResetCommitComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if let (true, Some(commit)) = (self.visible, self.commit) {
+            let txt: Vec<Spans> = KINDS
+                .iter()
+                .enumerate()
+                .map(|(i, kind)| {
+                    Spans::from(Span::styled(
+                        format!(
+                            " {}",
+                            strings::reset_kind_entry(
+                                &self.key_config,
+                                *kind
+                            )
+                        ),
+                        self.theme.text(true, i == self.selection),
+                    ))
+                })
+                .collect();
+
+            let title = strings::reset_commit_title(
+                &self.key_config,
+                &commit.get_short_string(),
+            );
+
+            let area = ui::centered_rect(50, 20, f.size());
+            f.render_widget(Clear, area);
+            f.render_widget(
+                popup_paragraph(
+                    &title,
+                    Text::from(txt),
+                    &self.theme,
+                    true,
+                ),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ResetCommitComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::reset_commit_confirm(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection =
+                        (self.selection + 1).min(KINDS.len() - 1);
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.enter {
+                    self.confirm();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl ResetCommitComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            commit: None,
+            selection: 1,
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self, commit: CommitId) -> Result<()> {
+        self.commit = Some(commit);
+        self.selection = 1;
+
+        self.show()
+    }
+
+    fn confirm(&mut self) {
+        if let Some(commit) = self.commit {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ConfirmAction(Action::ResetToCommit(
+                    commit,
+                    KINDS[self.selection],
+                )),
+            );
+        }
+
+        self.hide();
+    }
+}
@@ -0,0 +1,412 @@
+use super::{
+    visibility_blocking, BlameRevision, CommandBlocking, CommandInfo,
+    Component, DrawableComponent, FileTreeComponent, ScrollType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{Action, InternalEvent, Queue},
+    strings, try_or_popup,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{
+        self, objects::BLOB_DUMP_MAX_SIZE, CommitId, ObjectDump,
+        TreeFile,
+    },
+    StatusItem, StatusItemType, CWD,
+};
+use crossterm::event::Event;
+use std::cell::Cell;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// browses all files of a commit and shows their content at that
+/// revision, nothing gets checked out
+pub struct RevisionFilesComponent {
+    commit: Option<CommitId>,
+    files: Vec<TreeFile>,
+    tree: FileTreeComponent,
+    /// path and content of the file shown next to the tree
+    content: Option<(String, ObjectDump)>,
+    content_focused: bool,
+    scroll_top: usize,
+    current_height: Cell<usize>,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for RevisionFilesComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let percentages = if self.content_focused {
+                (30, 70)
+            } else {
+                (40, 60)
+            };
+
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(
+                    [
+                        Constraint::Percentage(percentages.0),
+                        Constraint::Percentage(percentages.1),
+                    ]
+                    .as_ref(),
+                )
+                .split(rect);
+
+            f.render_widget(Clear, rect);
+
+            self.tree.draw(f, chunks[0])?;
+            self.draw_content(f, chunks[1]);
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for RevisionFilesComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            self.tree.commands(out, force_all);
+
+            out.push(
+                CommandInfo::new(
+                    strings::commands::close_popup(&self.key_config),
+                    true,
+                    true,
+                )
+                .order(1),
+            );
+            out.push(CommandInfo::new(
+                strings::commands::revision_files_view(
+                    &self.key_config,
+                ),
+                self.content.is_some(),
+                !self.content_focused || force_all,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                self.content_focused || force_all,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::revision_files_back(
+                    &self.key_config,
+                ),
+                true,
+                self.content_focused || force_all,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::restore_file(&self.key_config),
+                self.content.is_some(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::copy_path(&self.key_config),
+                self.tree.selection().is_some(),
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if self.tree.event(ev)? {
+                self.update_content();
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.copy {
+                    try_or_popup!(
+                        self,
+                        "copy path error:",
+                        self.copy_path()
+                    );
+                } else if e == self.key_config.restore_file {
+                    self.restore_file();
+                } else if self.content_focused {
+                    self.content_event(e);
+                } else if e == self.key_config.exit_popup
+                    || e == self.key_config.focus_left
+                {
+                    self.hide();
+                } else if (e == self.key_config.focus_right
+                    || e == self.key_config.enter)
+                    && self.content.is_some()
+                {
+                    self.focus_content(true);
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl RevisionFilesComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            commit: None,
+            files: Vec::new(),
+            tree: FileTreeComponent::new(
+                "",
+                true,
+                Some(queue.clone()),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            content: None,
+            content_focused: false,
+            scroll_top: 0,
+            current_height: Cell::new(0),
+            visible: false,
+            queue: queue.clone(),
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self, commit: CommitId) -> Result<()> {
+        self.files = sync::tree_files(CWD, commit)?;
+
+        // a typechange is drawn without status char and colour,
+        // so every file shows up plain
+        let items: Vec<StatusItem> = self
+            .files
+            .iter()
+            .map(|file| StatusItem {
+                path: file.path.clone(),
+                old_path: None,
+                status: StatusItemType::Typechange,
+            })
+            .collect();
+
+        self.commit = Some(commit);
+        self.tree.clear()?;
+        self.tree.update(&items)?;
+        self.tree.set_title(strings::revision_files_title(
+            &self.key_config,
+            &commit.get_short_string(),
+        ));
+        self.tree.enable_blame(BlameRevision::Commit(commit));
+        self.content = None;
+        self.focus_content(false);
+        self.update_content();
+
+        self.show()
+    }
+
+    /// loads the selected file unless it is shown already, a
+    /// selected folder shows nothing
+    fn update_content(&mut self) {
+        let path = self.tree.selection_file().map(|item| item.path);
+
+        if self.content.as_ref().map(|(shown, _)| shown)
+            == path.as_ref()
+        {
+            return;
+        }
+
+        self.content = None;
+        self.scroll_top = 0;
+
+        let file = path.and_then(|path| {
+            self.files.iter().find(|file| file.path == path)
+        });
+
+        if let Some(file) = file {
+            match sync::show_object(CWD, &file.id) {
+                Ok(dump) => {
+                    self.content = Some((file.path.clone(), dump));
+                }
+                Err(e) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "show file error:\n{}",
+                            e
+                        )),
+                    );
+                }
+            }
+        }
+    }
+
+    fn focus_content(&mut self, focus: bool) {
+        self.content_focused = focus;
+        self.tree.focus(!focus);
+    }
+
+    fn content_event(&mut self, e: crossterm::event::KeyEvent) {
+        if e == self.key_config.exit_popup
+            || e == self.key_config.focus_left
+        {
+            self.focus_content(false);
+        } else if e == self.key_config.move_down {
+            self.scroll(ScrollType::Down);
+        } else if e == self.key_config.move_up {
+            self.scroll(ScrollType::Up);
+        } else if e == self.key_config.page_down {
+            self.scroll(ScrollType::PageDown);
+        } else if e == self.key_config.page_up {
+            self.scroll(ScrollType::PageUp);
+        } else if e == self.key_config.home {
+            self.scroll(ScrollType::Home);
+        } else if e == self.key_config.end {
+            self.scroll(ScrollType::End);
+        }
+    }
+
+    fn scroll(&mut self, scroll: ScrollType) {
+        let height = self.current_height.get();
+        let max = self.lines_count().saturating_sub(height);
+
+        self.scroll_top = match scroll {
+            ScrollType::Down => self.scroll_top.saturating_add(1),
+            ScrollType::Up => self.scroll_top.saturating_sub(1),
+            ScrollType::PageDown => {
+                self.scroll_top.saturating_add(height)
+            }
+            ScrollType::PageUp => {
+                self.scroll_top.saturating_sub(height)
+            }
+            ScrollType::Home => 0,
+            ScrollType::End => max,
+        }
+        .min(max);
+    }
+
+    /// the shown file, as it was loaded for the selection
+    fn restore_file(&self) {
+        if let (Some(commit), Some((path, _))) =
+            (self.commit, &self.content)
+        {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ConfirmAction(Action::RestoreFile(
+                    commit,
+                    path.clone(),
+                )),
+            );
+        }
+    }
+
+    /// the full path of the selected file or folder
+    fn copy_path(&self) -> Result<()> {
+        if let Some(item) = self.tree.selection() {
+            crate::clipboard::copy_string(&item.info.full_path)?;
+        }
+
+        Ok(())
+    }
+
+    fn lines_count(&self) -> usize {
+        self.content
+            .as_ref()
+            .map_or(0, |(_, dump)| dump.lines.len())
+    }
+
+    fn draw_content<B: Backend>(&self, f: &mut Frame<B>, r: Rect) {
+        let height = (r.height as usize).saturating_sub(2);
+        self.current_height.set(height);
+
+        let mut txt = Vec::new();
+
+        if let Some((_, dump)) = &self.content {
+            if dump.binary {
+                txt.push(Spans::from(Span::styled(
+                    strings::object_viewer_binary(&self.key_config),
+                    self.theme.text(false, false),
+                )));
+            }
+
+            for line in
+                dump.lines.iter().skip(self.scroll_top).take(height)
+            {
+                txt.push(Spans::from(Span::styled(
+                    line.text.replace('\t', "    "),
+                    self.theme.text(true, false),
+                )));
+            }
+
+            if dump.truncated
+                && self.scroll_top + height >= dump.lines.len()
+            {
+                txt.push(Spans::from(Span::styled(
+                    strings::object_viewer_truncated(
+                        &self.key_config,
+                        BLOB_DUMP_MAX_SIZE,
+                    ),
+                    self.theme.text(false, false),
+                )));
+            }
+        }
+
+        f.render_widget(
+            Paragraph::new(Text::from(txt)).block(
+                Block::default()
+                    .title(Span::styled(
+                        self.content
+                            .as_ref()
+                            .map_or("", |(path, _)| path.as_str()),
+                        self.theme.title(self.content_focused),
+                    ))
+                    .borders(Borders::ALL)
+                    .border_style(
+                        self.theme.block(self.content_focused),
+                    ),
+            ),
+            r,
+        );
+
+        ui::draw_scrollbar(
+            f,
+            r,
+            &self.theme,
+            self.lines_count(),
+            self.scroll_top,
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId, RebaseState},
+    CWD,
+};
+use crossterm::event::Event;
+use tui::{backend::Backend, layout::Rect, Frame};
+
+/// edits the message of a commit of the current branch, the commits
+/// after it are rebased onto the reworded one
+pub struct RewordComponent {
+    input: TextInputComponent,
+    commit: Option<CommitId>,
+    queue: Queue,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for RewordComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        self.input.draw(f, rect)?;
+
+        Ok(())
+    }
+}
+
+impl Component for RewordComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            self.input.commands(out, force_all);
+
+            out.push(CommandInfo::new(
+                strings::commands::reword_confirm(&self.key_config),
+                !self.input.get_text().trim().is_empty(),
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.is_visible() {
+            if self.input.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter
+                    && !self.input.get_text().trim().is_empty()
+                {
+                    self.reword();
+                }
+
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.input.is_visible()
+    }
+
+    fn hide(&mut self) {
+        self.input.hide();
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.input.show()?;
+
+        Ok(())
+    }
+}
+
+impl RewordComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            queue,
+            input: TextInputComponent::new(
+                theme,
+                key_config.clone(),
+                &strings::reword_popup_title(&key_config),
+                &strings::reword_popup_msg(&key_config),
+                true,
+            ),
+            commit: None,
+            key_config,
+        }
+    }
+
+    /// starts with the current message of `commit`
+    pub fn open(&mut self, commit: CommitId) -> Result<()> {
+        let msg = sync::get_commit_details(CWD, commit)?
+            .message
+            .map_or_else(String::new, |msg| match msg.body {
+                // the body starts with the blank line
+                Some(body) => format!("{}\n{}", msg.subject, body),
+                None => msg.subject,
+            });
+
+        self.commit = Some(commit);
+        self.input.set_text(msg);
+        self.show()
+    }
+
+    fn reword(&mut self) {
+        if let Some(commit) = self.commit {
+            let res = sync::reword(
+                CWD,
+                commit,
+                self.input.get_text().trim(),
+            );
+
+            match res {
+                Ok(RebaseState::Finished) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::Update(NeedsUpdate::ALL),
+                    );
+                }
+                // a reword alone cannot conflict, but the rebase
+                // popup is where a stopped one is driven
+                Ok(RebaseState::Conflict(_)) => {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenRebase(commit));
+                }
+                Err(e) => {
+                    log::error!("reword: {}", e);
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "reword error:\n{}",
+                            e,
+                        )),
+                    );
+                }
+            }
+        }
+
+        self.hide();
+        self.input.clear();
+    }
+}
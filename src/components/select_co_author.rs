@@ -0,0 +1,197 @@
+use super::{
+    popup_paragraph, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{sync, CWD};
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::Clear,
+    Frame,
+};
+
+/// commits whose authors are offered
+const RECENT_COMMITS: usize = 200;
+
+/// picks one of the recent authors for a `Co-authored-by` trailer
+/// of the commit message
+pub struct SelectCoAuthorComponent {
+    authors: Vec<String>,
+    selection: usize,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for SelectCoAuthorComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let area = ui::centered_rect(50, 30, f.size());
+            let height =
+                usize::from(area.height.saturating_sub(2)).max(1);
+            // keeps the selection in view
+            let skip = self
+                .selection
+                .saturating_sub(height.saturating_sub(1));
+
+            let txt = self
+                .authors
+                .iter()
+                .enumerate()
+                .skip(skip)
+                .take(height)
+                .map(|(i, author)| {
+                    Spans::from(Span::styled(
+                        format!(" {}", author),
+                        self.theme.text(true, i == self.selection),
+                    ))
+                })
+                .collect::<Vec<_>>();
+
+            let title = strings::co_author_title(&self.key_config);
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                popup_paragraph(
+                    &title,
+                    Text::from(txt),
+                    &self.theme,
+                    true,
+                ),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for SelectCoAuthorComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::co_author_confirm(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = self
+                        .selection
+                        .saturating_add(1)
+                        .min(self.authors.len().saturating_sub(1));
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.enter {
+                    if let Some(author) =
+                        self.authors.get(self.selection)
+                    {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::AddCoAuthor(
+                                author.clone(),
+                            ),
+                        );
+                    }
+                    self.hide();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl SelectCoAuthorComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            authors: Vec::new(),
+            selection: 0,
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// tells if there is nobody to pick
+    pub fn open(&mut self) -> Result<()> {
+        self.authors = sync::get_recent_authors(CWD, RECENT_COMMITS)?;
+        self.selection = 0;
+
+        if self.authors.is_empty() {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowInfoMsg(strings::co_author_none(
+                    &self.key_config,
+                    RECENT_COMMITS,
+                )),
+            );
+            return Ok(());
+        }
+
+        self.show()
+    }
+}
@@ -0,0 +1,233 @@
+use super::{
+    popup_paragraph, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    options::SharedOptions,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, Identity},
+    CWD,
+};
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::Clear,
+    Frame,
+};
+
+/// switches between the identities of `options.ron`
+/// by writing one to the config of the repo
+pub struct SelectIdentityComponent {
+    current: Identity,
+    selection: usize,
+    visible: bool,
+    queue: Queue,
+    options: SharedOptions,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for SelectIdentityComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let mut txt = vec![Spans::from(Span::styled(
+                strings::identity_current(
+                    &self.key_config,
+                    &self.current,
+                ),
+                self.theme.text(true, false),
+            ))];
+
+            if !self.current.local {
+                txt.push(Spans::from(Span::styled(
+                    strings::identity_not_local(&self.key_config),
+                    self.theme.text_danger(),
+                )));
+            }
+
+            txt.push(Spans::default());
+
+            txt.extend(
+                self.options.identities.iter().enumerate().map(
+                    |(i, identity)| {
+                        Spans::from(Span::styled(
+                            format!(
+                                " {} <{}>",
+                                identity.name, identity.email
+                            ),
+                            self.theme
+                                .text(true, i == self.selection),
+                        ))
+                    },
+                ),
+            );
+
+            let title = strings::identity_title(&self.key_config);
+
+            let area = ui::centered_rect(50, 30, f.size());
+            f.render_widget(Clear, area);
+            f.render_widget(
+                popup_paragraph(
+                    &title,
+                    Text::from(txt),
+                    &self.theme,
+                    true,
+                ),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for SelectIdentityComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::identity_confirm(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection =
+                        self.selection.saturating_add(1).min(
+                            self.options
+                                .identities
+                                .len()
+                                .saturating_sub(1),
+                        );
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.enter {
+                    self.switch_identity();
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl SelectIdentityComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        options: SharedOptions,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            current: Identity::default(),
+            selection: 0,
+            visible: false,
+            queue,
+            options,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self) -> Result<()> {
+        self.current = sync::get_identity(CWD)?;
+        self.selection = self
+            .options
+            .identities
+            .iter()
+            .position(|identity| {
+                self.current.name.as_ref() == Some(&identity.name)
+                    && self.current.email.as_ref()
+                        == Some(&identity.email)
+            })
+            .unwrap_or_default();
+
+        self.show()
+    }
+
+    fn switch_identity(&mut self) {
+        if let Some(identity) =
+            self.options.identities.get(self.selection)
+        {
+            let res = sync::set_local_identity(
+                CWD,
+                &identity.name,
+                &identity.email,
+            );
+
+            match res {
+                Ok(()) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::Update(NeedsUpdate::ALL),
+                    );
+                    self.hide();
+                }
+                Err(e) => {
+                    log::error!("switch identity: {}", e,);
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "switch identity error:\n{}",
+                            e,
+                        )),
+                    );
+                }
+            }
+        }
+    }
+}
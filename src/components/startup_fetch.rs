@@ -0,0 +1,266 @@
+use crate::{
+    components::{
+        cred::CredComponent, visibility_blocking, CommandBlocking,
+        CommandInfo, Component, DrawableComponent,
+    },
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+    cached,
+    sync::{
+        cred::{
+            extract_username_password, need_ssh_passphrase,
+            need_username_password, BasicAuthCredential,
+        },
+        get_default_remote,
+    },
+    AsyncFetch, AsyncNotification, FetchRequest, CWD,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    text::Span,
+    widgets::Paragraph,
+    Frame,
+};
+
+enum State {
+    Idle,
+    Running(String),
+    /// remote, received bytes
+    Fetched(String, usize),
+    Failed(String),
+}
+
+/// fetch of the default remote right after launch (`fetch_on_startup`
+/// in `options.ron`), only shows up as a hint in the tab bar
+pub struct StartupFetchComponent {
+    state: State,
+    branch: String,
+    git_fetch: AsyncFetch,
+    input_cred: CredComponent,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl StartupFetchComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            state: State::Idle,
+            branch: String::new(),
+            git_fetch: AsyncFetch::new(sender),
+            input_cred: CredComponent::new(
+                theme.clone(),
+                key_config.clone(),
+            ),
+            queue: queue.clone(),
+            theme,
+            key_config,
+        }
+    }
+
+    /// without a remote or a branch there is nothing to fetch.
+    /// missing credentials are only asked for if `ask_credentials`,
+    /// otherwise the fetch is skipped
+    pub fn start(&mut self, ask_credentials: bool) -> Result<()> {
+        self.branch = match cached::BranchName::new(CWD).lookup() {
+            Ok(branch) if get_default_remote(CWD).is_ok() => branch,
+            _ => return Ok(()),
+        };
+
+        if need_username_password()? {
+            let cred =
+                extract_username_password().unwrap_or_else(|_| {
+                    BasicAuthCredential::new(None, None)
+                });
+            if cred.is_complete() {
+                self.fetch(Some(cred))
+            } else if ask_credentials {
+                self.input_cred.set_cred(cred);
+                self.input_cred.show()
+            } else {
+                log::info!(
+                    "startup fetch skipped: credentials needed"
+                );
+                Ok(())
+            }
+        } else if let Some(user) = need_ssh_passphrase()? {
+            if ask_credentials {
+                self.input_cred.ask_passphrase(user)
+            } else {
+                log::info!(
+                    "startup fetch skipped: passphrase needed"
+                );
+                Ok(())
+            }
+        } else {
+            self.fetch(None)
+        }
+    }
+
+    fn fetch(
+        &mut self,
+        cred: Option<BasicAuthCredential>,
+    ) -> Result<()> {
+        let remote = get_default_remote(CWD)?;
+
+        self.git_fetch.request(FetchRequest {
+            remote: remote.clone(),
+            branch: self.branch.clone(),
+            basic_credential: cred,
+        })?;
+        self.state = State::Running(remote);
+
+        Ok(())
+    }
+
+    ///
+    pub const fn any_work_pending(&self) -> bool {
+        matches!(self.state, State::Running(_))
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if ev == AsyncNotification::Fetch {
+            if let State::Running(remote) = &self.state {
+                if !self.git_fetch.is_pending()? {
+                    let remote = remote.clone();
+
+                    self.state = match self.git_fetch.last_result()? {
+                        Some((bytes, err)) if err.is_empty() => {
+                            State::Fetched(remote, bytes)
+                        }
+                        _ => State::Failed(remote),
+                    };
+
+                    // ahead/behind changed
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::Update(NeedsUpdate::ALL),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn hint(&self) -> Option<Span<'_>> {
+        match &self.state {
+            State::Idle => None,
+            State::Running(remote) => Some(Span::styled(
+                strings::startup_fetch_running(
+                    &self.key_config,
+                    remote,
+                ),
+                self.theme.text(false, false),
+            )),
+            State::Fetched(remote, bytes) => Some(Span::styled(
+                strings::startup_fetch_done(
+                    &self.key_config,
+                    remote,
+                    *bytes,
+                ),
+                self.theme.text(false, false),
+            )),
+            State::Failed(remote) => Some(Span::styled(
+                strings::startup_fetch_failed(
+                    &self.key_config,
+                    remote,
+                ),
+                self.theme.text_danger(),
+            )),
+        }
+    }
+}
+
+impl DrawableComponent for StartupFetchComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if let Some(hint) = self.hint() {
+            let mut r = rect;
+            r.x += 1;
+            r.width = r.width.saturating_sub(2);
+            r.height = r.height.min(1);
+
+            f.render_widget(
+                Paragraph::new(hint).alignment(Alignment::Right),
+                r,
+            );
+        }
+
+        self.input_cred.draw(f, rect)?;
+
+        Ok(())
+    }
+}
+
+impl Component for StartupFetchComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.input_cred.is_visible() {
+            self.input_cred.commands(out, force_all)
+        } else {
+            visibility_blocking(self)
+        }
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.input_cred.is_visible() {
+            self.input_cred.event(ev)?;
+
+            if self.input_cred.get_cred().is_complete() {
+                self.fetch(Some(self.input_cred.get_cred().clone()))?;
+                self.input_cred.hide();
+            }
+
+            return Ok(true);
+        }
+
+        // the result is dismissed by the next key,
+        // which is not consumed
+        if let Event::Key(_) = ev {
+            if matches!(
+                self.state,
+                State::Fetched(..) | State::Failed(_)
+            ) {
+                self.state = State::Idle;
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.input_cred.is_visible()
+    }
+
+    fn hide(&mut self) {
+        self.input_cred.hide();
+    }
+
+    fn show(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use super::{
+    textinput::TextInputComponent, visibility_blocking,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CommitId},
+    CWD,
+};
+use crossterm::event::Event;
+use tui::{backend::Backend, layout::Rect, Frame};
+
+pub struct StashBranchComponent {
+    input: TextInputComponent,
+    stash_id: Option<CommitId>,
+    queue: Queue,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for StashBranchComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        self.input.draw(f, rect)?;
+
+        Ok(())
+    }
+}
+
+impl Component for StashBranchComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            self.input.commands(out, force_all);
+
+            out.push(CommandInfo::new(
+                strings::commands::stash_branch_confirm_msg(
+                    &self.key_config,
+                ),
+                !self.input.get_text().trim().is_empty(),
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.is_visible() {
+            if self.input.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter
+                    && !self.input.get_text().trim().is_empty()
+                {
+                    self.stash_branch();
+                }
+
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.input.is_visible()
+    }
+
+    fn hide(&mut self) {
+        self.input.hide();
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.input.show()?;
+
+        Ok(())
+    }
+}
+
+impl StashBranchComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            queue,
+            input: TextInputComponent::new(
+                theme,
+                key_config.clone(),
+                &strings::stash_branch_popup_title(&key_config),
+                &strings::stash_branch_popup_msg(&key_config),
+                true,
+            ),
+            stash_id: None,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self, stash_id: CommitId) -> Result<()> {
+        self.stash_id = Some(stash_id);
+        self.input.clear();
+        self.show()?;
+
+        Ok(())
+    }
+
+    /// the status tab shows the popped changes on the new branch
+    pub fn stash_branch(&mut self) {
+        if let Some(id) = self.stash_id {
+            let res = sync::stash_branch(
+                CWD,
+                id,
+                self.input.get_text().trim(),
+            );
+
+            match res {
+                Ok(()) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::Update(NeedsUpdate::ALL),
+                    );
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::TabSwitch);
+                    self.hide();
+                }
+                Err(e) => {
+                    log::error!("stash branch: {}", e,);
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "stash branch error:\n{}",
+                            e,
+                        )),
+                    );
+                }
+            }
+        }
+    }
+}
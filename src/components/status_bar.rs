@@ -0,0 +1,171 @@
+use super::DrawableComponent;
+use crate::{keys::SharedKeyConfig, strings, ui::style::SharedTheme};
+use anyhow::Result;
+use asyncgit::{
+    cached,
+    sync::{self, BranchCompare, RefsState, RepoState},
+    AsyncNotification, CWD,
+};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    text::{Span, Spans},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// background operations named in the status bar
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PendingOperation {
+    Push,
+    PushTags,
+    Pull,
+    Fetch,
+    Archive,
+    Rebase,
+}
+
+/// one line above the command bar: the current branch, how far it
+/// is ahead of and behind its upstream, a merge, rebase etc. in
+/// progress and the remote operations running in the background
+pub struct StatusBarComponent {
+    git_branch_name: cached::BranchName,
+    /// the comparison is only redone once refs changed
+    refs_state: Option<(RefsState, String)>,
+    branch_state: Option<BranchCompare>,
+    repo_state: RepoState,
+    pending: Vec<PendingOperation>,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl StatusBarComponent {
+    ///
+    pub fn new(
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            git_branch_name: cached::BranchName::new(CWD),
+            refs_state: None,
+            branch_state: None,
+            repo_state: RepoState::Clean,
+            pending: Vec::new(),
+            theme,
+            key_config,
+        }
+    }
+
+    /// branch, upstream and repo state are read again
+    pub fn update(&mut self) -> Result<()> {
+        self.repo_state = sync::repo_state(CWD)?;
+
+        let branch = self.git_branch_name.lookup().ok();
+        let refs_state = sync::get_refs_state(CWD)?;
+
+        if let Some(branch) = branch {
+            let current = Some((refs_state, branch.clone()));
+            if self.refs_state != current {
+                self.branch_state =
+                    sync::branch_compare_upstream(CWD, &branch).ok();
+                self.refs_state = current;
+            }
+        } else {
+            self.branch_state = None;
+            self.refs_state = None;
+        }
+
+        Ok(())
+    }
+
+    /// events that may have moved HEAD, a ref or the repo state
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        match ev {
+            AsyncNotification::Status
+            | AsyncNotification::Push
+            | AsyncNotification::PushTags
+            | AsyncNotification::Fetch
+            | AsyncNotification::FetchAll => self.update(),
+            _ => Ok(()),
+        }
+    }
+
+    ///
+    pub fn set_pending(&mut self, pending: Vec<PendingOperation>) {
+        self.pending = pending;
+    }
+
+    fn left_spans(&self) -> Vec<Span<'_>> {
+        let mut spans = Vec::new();
+
+        match self.git_branch_name.last() {
+            Some(branch) => spans.push(Span::styled(
+                branch,
+                self.theme.branch(false, true),
+            )),
+            None => spans.push(Span::styled(
+                strings::status_bar_detached(&self.key_config),
+                self.theme.text_danger(),
+            )),
+        }
+
+        if let Some(state) = &self.branch_state {
+            spans.push(Span::styled(
+                format!(
+                    " \u{2191}{} \u{2193}{}",
+                    state.ahead, state.behind
+                ),
+                self.theme.text(true, false),
+            ));
+        }
+
+        if self.repo_state != RepoState::Clean {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                strings::status_bar_repo_state(
+                    &self.key_config,
+                    self.repo_state,
+                ),
+                self.theme.text_danger(),
+            ));
+        }
+
+        spans
+    }
+}
+
+impl DrawableComponent for StatusBarComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        let mut r = rect;
+        r.x += 1;
+        r.width = r.width.saturating_sub(2);
+
+        f.render_widget(
+            Paragraph::new(Spans::from(self.left_spans())),
+            r,
+        );
+
+        if !self.pending.is_empty() {
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    strings::status_bar_pending(
+                        &self.key_config,
+                        &self.pending,
+                    ),
+                    self.theme.text(false, false),
+                ))
+                .alignment(Alignment::Right),
+                r,
+            );
+        }
+
+        Ok(())
+    }
+}
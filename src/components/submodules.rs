@@ -0,0 +1,309 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings, try_or_popup,
+    ui::{self, style::SharedTheme, Size},
+};
+use anyhow::{anyhow, Result};
+use asyncgit::{
+    sync::{self, SubmoduleInfo},
+    CWD,
+};
+use crossterm::{
+    event::Event,
+    terminal::{
+        enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+    },
+    ExecutableCommand,
+};
+use scopeguard::defer;
+use std::{cell::Cell, env, io, process::Command};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// the submodules with their state, the selected one can be updated
+/// or opened in a nested gitui
+pub struct SubmodulesListComponent {
+    submodules: Vec<SubmoduleInfo>,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for SubmodulesListComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(70, 40);
+            const MIN_SIZE: Size = Size::new(60, 10);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height = (area.height as usize).saturating_sub(2);
+            self.scroll_top.set(ui::calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                self.selection,
+            ));
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height)).block(
+                    Block::default()
+                        .title(Span::styled(
+                            strings::submodules_title(
+                                &self.key_config,
+                            ),
+                            self.theme.title(true),
+                        ))
+                        .border_type(BorderType::Thick)
+                        .borders(Borders::ALL),
+                ),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for SubmodulesListComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::submodule_update(&self.key_config),
+                self.selected().is_some(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::submodule_open(&self.key_config),
+                self.selected()
+                    .map_or(false, |sm| sm.status.initialized),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = (self.selection + 1)
+                        .min(self.submodules.len().saturating_sub(1));
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.submodule_update {
+                    try_or_popup!(
+                        self,
+                        "update submodule error:",
+                        self.update_selected()
+                    );
+                } else if e == self.key_config.enter {
+                    try_or_popup!(
+                        self,
+                        "open submodule error:",
+                        self.open_selected()
+                    );
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl SubmodulesListComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            submodules: Vec::new(),
+            selection: 0,
+            scroll_top: Cell::new(0),
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self) -> Result<()> {
+        self.submodules = sync::get_submodules(CWD)?;
+        self.selection = 0;
+
+        if self.submodules.is_empty() {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(
+                    strings::submodules_none(&self.key_config),
+                ),
+            );
+            return Ok(());
+        }
+
+        self.show()
+    }
+
+    /// runs gitui in the workdir of the submodule until it quits,
+    /// with the input of this one paused
+    pub fn open_in_gitui(workdir: &str) -> Result<()> {
+        io::stdout().execute(LeaveAlternateScreen)?;
+        defer! {
+            // the nested instance resets the terminal on quitting
+            enable_raw_mode().expect("reset terminal");
+            io::stdout().execute(EnterAlternateScreen).expect("reset terminal");
+        }
+
+        let exe = env::current_exe()?;
+        Command::new(&exe)
+            .arg("--directory")
+            .arg(workdir)
+            .status()
+            .map_err(|e| anyhow!("\"{:?}\": {}", exe, e))?;
+
+        Ok(())
+    }
+
+    fn selected(&self) -> Option<&SubmoduleInfo> {
+        self.submodules.get(self.selection)
+    }
+
+    fn update_selected(&mut self) -> Result<()> {
+        if let Some(sm) = self.selected() {
+            sync::update_submodule(CWD, &sm.path)?;
+
+            self.submodules = sync::get_submodules(CWD)?;
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+        }
+
+        Ok(())
+    }
+
+    fn open_selected(&mut self) -> Result<()> {
+        if let Some(sm) = self.selected() {
+            let workdir = sync::submodule_workdir(CWD, &sm.path)?;
+
+            self.hide();
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::OpenSubmodule(workdir));
+        }
+
+        Ok(())
+    }
+
+    fn get_text(&self, height: usize) -> Text<'_> {
+        let path_width = self
+            .submodules
+            .iter()
+            .map(|sm| sm.path.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let txt: Vec<Spans> = self
+            .submodules
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|(idx, sm)| {
+                let selected = idx == self.selection;
+
+                Spans::from(vec![
+                    Span::styled(
+                        format!(
+                            "{} ",
+                            sm.workdir_id.or(sm.head_id).map_or_else(
+                                || " ".repeat(7),
+                                |id| id.get_short_string()
+                            )
+                        ),
+                        self.theme.commit_hash(selected),
+                    ),
+                    Span::styled(
+                        format!("{:w$} ", sm.path, w = path_width),
+                        self.theme.text(true, selected),
+                    ),
+                    Span::styled(
+                        strings::submodule_state(
+                            &self.key_config,
+                            sm.status,
+                        ),
+                        if sm.status.initialized
+                            && !sm.status.out_of_date
+                        {
+                            self.theme.text(false, selected)
+                        } else {
+                            self.theme.text_danger()
+                        },
+                    ),
+                ])
+            })
+            .collect();
+
+        Text::from(txt)
+    }
+}
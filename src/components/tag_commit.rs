@@ -1,6 +1,7 @@
 use super::{
     textinput::TextInputComponent, visibility_blocking,
     CommandBlocking, CommandInfo, Component, DrawableComponent,
+    InputType,
 };
 use crate::{
     keys::SharedKeyConfig,
@@ -16,9 +17,14 @@ use asyncgit::{
 use crossterm::event::Event;
 use tui::{backend::Backend, layout::Rect, Frame};
 
+/// asks for the tag name and then for a message, tags with a
+/// message are annotated
 pub struct TagCommitComponent {
     input: TextInputComponent,
     commit_id: Option<CommitId>,
+    /// set once the name is entered and the message is asked for
+    tag_name: Option<String>,
+    sign: bool,
     queue: Queue,
     key_config: SharedKeyConfig,
 }
@@ -44,13 +50,31 @@ impl Component for TagCommitComponent {
         if self.is_visible() || force_all {
             self.input.commands(out, force_all);
 
-            out.push(CommandInfo::new(
-                strings::commands::tag_commit_confirm_msg(
-                    &self.key_config,
-                ),
-                true,
-                true,
-            ));
+            if self.tag_name.is_some() {
+                out.push(CommandInfo::new(
+                    strings::commands::tag_commit_confirm_msg(
+                        &self.key_config,
+                    ),
+                    true,
+                    true,
+                ));
+                out.push(CommandInfo::new(
+                    strings::commands::tag_commit_toggle_sign(
+                        &self.key_config,
+                        self.sign,
+                    ),
+                    !self.input.get_text().trim().is_empty(),
+                    true,
+                ));
+            } else {
+                out.push(CommandInfo::new(
+                    strings::commands::tag_commit_next(
+                        &self.key_config,
+                    ),
+                    !self.input.get_text().trim().is_empty(),
+                    true,
+                ));
+            }
         }
 
         visibility_blocking(self)
@@ -64,7 +88,16 @@ impl Component for TagCommitComponent {
 
             if let Event::Key(e) = ev {
                 if e == self.key_config.enter {
-                    self.tag()
+                    if self.tag_name.is_some() {
+                        self.tag();
+                    } else {
+                        self.ask_message();
+                    }
+                } else if e == self.key_config.tag_toggle_sign
+                    && self.tag_name.is_some()
+                {
+                    self.sign = !self.sign;
+                    self.update_title();
                 }
 
                 return Ok(true);
@@ -105,6 +138,8 @@ impl TagCommitComponent {
                 true,
             ),
             commit_id: None,
+            tag_name: None,
+            sign: false,
             key_config,
         }
     }
@@ -112,15 +147,62 @@ impl TagCommitComponent {
     ///
     pub fn open(&mut self, id: CommitId) -> Result<()> {
         self.commit_id = Some(id);
+        self.tag_name = None;
+        self.sign = sync::tag_signing_enabled(CWD)?;
+
+        self.input.clear();
+        self.input.set_input_type(InputType::Singleline);
+        self.input.set_title(strings::tag_commit_popup_title(
+            &self.key_config,
+        ));
+        self.input.set_default_msg(strings::tag_commit_popup_msg(
+            &self.key_config,
+        ));
         self.show()?;
 
         Ok(())
     }
 
-    ///
+    fn ask_message(&mut self) {
+        let name = self.input.get_text().trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+
+        self.tag_name = Some(name);
+        self.input.clear();
+        self.input.set_input_type(InputType::Multiline);
+        self.input.set_default_msg(strings::tag_commit_message_msg(
+            &self.key_config,
+        ));
+        self.update_title();
+    }
+
+    fn update_title(&mut self) {
+        if let Some(name) = &self.tag_name {
+            self.input.set_title(strings::tag_commit_message_title(
+                &self.key_config,
+                name,
+                self.sign,
+            ));
+        }
+    }
+
+    /// lightweight without a message
     pub fn tag(&mut self) {
-        if let Some(commit_id) = self.commit_id {
-            match sync::tag(CWD, &commit_id, self.input.get_text()) {
+        if let (Some(commit_id), Some(name)) =
+            (self.commit_id, self.tag_name.as_ref())
+        {
+            let message = self.input.get_text().trim();
+            let res = if message.is_empty() {
+                sync::tag(CWD, &commit_id, name)
+            } else {
+                sync::tag_annotated(
+                    CWD, &commit_id, name, message, self.sign,
+                )
+            };
+
+            match res {
                 Ok(_) => {
                     self.input.clear();
                     self.hide();
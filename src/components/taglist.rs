@@ -0,0 +1,353 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{Action, InternalEvent, NeedsUpdate, Queue},
+    strings, try_or_popup,
+    ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, PushTagsTarget, TagWithMetadata},
+    CWD,
+};
+use crossterm::event::Event;
+use std::cell::Cell;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// all tags of the repo, the selected one can be checked out,
+/// pushed or deleted locally and on the remote
+pub struct TagListComponent {
+    tags: Vec<TagWithMetadata>,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    current_height: Cell<usize>,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for TagListComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(80, 70);
+            const MIN_SIZE: Size = Size::new(60, 20);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height = (area.height as usize).saturating_sub(2);
+            self.current_height.set(height);
+            self.scroll_top.set(ui::calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                self.selection,
+            ));
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height)).block(
+                    Block::default()
+                        .title(Span::styled(
+                            strings::taglist_title(
+                                &self.key_config,
+                                self.tags.len(),
+                            ),
+                            self.theme.title(true),
+                        ))
+                        .border_type(BorderType::Thick)
+                        .borders(Borders::ALL),
+                ),
+                area,
+            );
+
+            ui::draw_scrollbar(
+                f,
+                area,
+                &self.theme,
+                self.tags.len(),
+                self.scroll_top.get(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for TagListComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            let selected = self.selected_tag().is_some();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::taglist_checkout(&self.key_config),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::taglist_inspect_commit(
+                    &self.key_config,
+                ),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::taglist_push(&self.key_config),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::taglist_delete(&self.key_config),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::taglist_delete_remote(
+                    &self.key_config,
+                ),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::taglist_archive(&self.key_config),
+                selected,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                let last = self.tags.len().saturating_sub(1);
+                let page = self.current_height.get().max(1);
+
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = (self.selection + 1).min(last);
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.page_down {
+                    self.selection =
+                        self.selection.saturating_add(page).min(last);
+                } else if e == self.key_config.page_up {
+                    self.selection =
+                        self.selection.saturating_sub(page);
+                } else if e == self.key_config.enter {
+                    try_or_popup!(
+                        self,
+                        "checkout error:",
+                        self.checkout_selected()
+                    );
+                } else if e == self.key_config.focus_right {
+                    if let Some(tag) = self.selected_tag() {
+                        let event = InternalEvent::InspectCommit(
+                            tag.target,
+                            Some(vec![tag.name.clone()]),
+                        );
+                        self.hide();
+                        self.queue.borrow_mut().push_back(event);
+                    }
+                } else if e == self.key_config.tag_push {
+                    self.queue_for_selected(|name| {
+                        InternalEvent::PushTags(PushTagsTarget::Tag(
+                            name,
+                        ))
+                    });
+                } else if e == self.key_config.tag_delete {
+                    self.queue_for_selected(|name| {
+                        InternalEvent::ConfirmAction(
+                            Action::DeleteTag(name),
+                        )
+                    });
+                } else if e == self.key_config.archive {
+                    if let Some(tag) = self.selected_tag() {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::OpenArchive(
+                                tag.target,
+                                tag.name.clone(),
+                            ),
+                        );
+                    }
+                } else if e == self.key_config.tag_delete_remote {
+                    self.queue_for_selected(|name| {
+                        InternalEvent::ConfirmAction(
+                            Action::DeleteRemoteTag(name),
+                        )
+                    });
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl TagListComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            tags: Vec::new(),
+            selection: 0,
+            scroll_top: Cell::new(0),
+            current_height: Cell::new(0),
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self) -> Result<()> {
+        self.tags = sync::get_tags_with_metadata(CWD)?;
+        self.selection = 0;
+        self.scroll_top.set(0);
+
+        self.show()
+    }
+
+    /// reloads the tags after one got deleted
+    pub fn refresh(&mut self) -> Result<()> {
+        if self.visible {
+            self.tags = sync::get_tags_with_metadata(CWD)?;
+            self.selection =
+                self.selection.min(self.tags.len().saturating_sub(1));
+        }
+
+        Ok(())
+    }
+
+    fn selected_tag(&self) -> Option<&TagWithMetadata> {
+        self.tags.get(self.selection)
+    }
+
+    fn queue_for_selected(
+        &self,
+        event: impl FnOnce(String) -> InternalEvent,
+    ) {
+        if let Some(tag) = self.selected_tag() {
+            self.queue
+                .borrow_mut()
+                .push_back(event(tag.name.clone()));
+        }
+    }
+
+    fn checkout_selected(&mut self) -> Result<()> {
+        if let Some(tag) = self.selected_tag() {
+            sync::checkout_commit(CWD, tag.target)?;
+
+            self.hide();
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+        }
+
+        Ok(())
+    }
+
+    fn get_text(&self, height: usize) -> Text<'_> {
+        let name_width = self
+            .tags
+            .iter()
+            .map(|tag| tag.name.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let txt: Vec<Spans> = self
+            .tags
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|(idx, tag)| {
+                let selected = idx == self.selection;
+
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{:w$} ", tag.name, w = name_width),
+                        self.theme.tags(selected),
+                    ),
+                    Span::styled(
+                        format!("{} ", tag.target.get_short_string()),
+                        self.theme.commit_hash(selected),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{:11} ",
+                            strings::taglist_kind(
+                                &self.key_config,
+                                tag.annotated,
+                            )
+                        ),
+                        self.theme.text(false, selected),
+                    ),
+                    Span::styled(
+                        tag.message.as_str(),
+                        self.theme.text(true, selected),
+                    ),
+                ])
+            })
+            .collect();
+
+        Text::from(txt)
+    }
+}
@@ -17,7 +17,7 @@ use tui::{
     layout::{Alignment, Rect},
     style::Modifier,
     text::{Spans, Text},
-    widgets::{Clear, Paragraph},
+    widgets::{Clear, Paragraph, Wrap},
     Frame,
 };
 
@@ -39,6 +39,7 @@ pub struct TextInputComponent {
     key_config: SharedKeyConfig,
     cursor_position: usize,
     input_type: InputType,
+    bold_first_line: bool,
     current_area: Cell<Rect>,
 }
 
@@ -61,6 +62,7 @@ impl TextInputComponent {
             default_msg: default_msg.to_string(),
             cursor_position: 0,
             input_type: InputType::Multiline,
+            bold_first_line: false,
             current_area: Cell::new(Rect::default()),
         }
     }
@@ -73,6 +75,13 @@ impl TextInputComponent {
         self
     }
 
+    /// sets the first line apart (e.g. the subject of a commit
+    /// message)
+    pub const fn with_bold_first_line(mut self) -> Self {
+        self.bold_first_line = true;
+        self
+    }
+
     /// Clear the `msg`.
     pub fn clear(&mut self) {
         self.msg.clear();
@@ -89,6 +98,57 @@ impl TextInputComponent {
         self.current_area.get()
     }
 
+    /// index of the line the cursor is in
+    pub fn get_cursor_line(&self) -> usize {
+        self.msg[..self.cursor_position].matches('\n').count()
+    }
+
+    fn line_start(&self, pos: usize) -> usize {
+        self.msg[..pos].rfind('\n').map_or(0, |idx| idx + 1)
+    }
+
+    fn line_end(&self, pos: usize) -> usize {
+        self.msg[pos..]
+            .find('\n')
+            .map_or(self.msg.len(), |idx| pos + idx)
+    }
+
+    /// position of the char in `column` of the line starting at
+    /// `start`, its end if it is shorter
+    fn column_position(&self, start: usize, column: usize) -> usize {
+        let end = self.line_end(start);
+        self.msg[start..end]
+            .char_indices()
+            .nth(column)
+            .map_or(end, |(idx, _)| start + idx)
+    }
+
+    fn cursor_column(&self) -> usize {
+        let start = self.line_start(self.cursor_position);
+        self.msg[start..self.cursor_position].chars().count()
+    }
+
+    /// Move the cursor to the same column of the previous line.
+    fn line_up(&mut self) {
+        let start = self.line_start(self.cursor_position);
+        if start > 0 {
+            let column = self.cursor_column();
+            let prev_start = self.line_start(start - 1);
+            self.cursor_position =
+                self.column_position(prev_start, column);
+        }
+    }
+
+    /// Move the cursor to the same column of the next line.
+    fn line_down(&mut self) {
+        let end = self.line_end(self.cursor_position);
+        if end < self.msg.len() {
+            let column = self.cursor_column();
+            self.cursor_position =
+                self.column_position(end + 1, column);
+        }
+    }
+
     /// Move the cursor right one char.
     fn incr_cursor(&mut self) {
         if let Some(pos) = self.next_char_position() {
@@ -139,6 +199,16 @@ impl TextInputComponent {
         self.title = t;
     }
 
+    /// Switch between a single and multi-line input.
+    pub fn set_input_type(&mut self, input_type: InputType) {
+        self.input_type = input_type;
+    }
+
+    /// Set the placeholder shown while empty.
+    pub fn set_default_msg(&mut self, msg: String) {
+        self.default_msg = msg;
+    }
+
     fn get_draw_text(&self) -> Text {
         let style = self.theme.text(true, false);
 
@@ -210,6 +280,15 @@ impl TextInputComponent {
             }
         }
 
+        if self.bold_first_line {
+            if let Some(line) = txt.lines.first_mut() {
+                for span in &mut line.0 {
+                    span.style =
+                        span.style.add_modifier(Modifier::BOLD);
+                }
+            }
+        }
+
         txt
     }
 
@@ -270,15 +349,6 @@ impl DrawableComponent for TextInputComponent {
         _rect: Rect,
     ) -> Result<()> {
         if self.visible {
-            let txt = if self.msg.is_empty() {
-                Text::styled(
-                    self.default_msg.as_str(),
-                    self.theme.text(false, false),
-                )
-            } else {
-                self.get_draw_text()
-            };
-
             let area = match self.input_type {
                 InputType::Multiline => {
                     let area = ui::centered_rect(60, 20, f.size());
@@ -291,25 +361,49 @@ impl DrawableComponent for TextInputComponent {
                 _ => ui::centered_rect_absolute(32, 3, f.size()),
             };
 
-            f.render_widget(Clear, area);
-            f.render_widget(
-                popup_paragraph(
-                    self.title.as_str(),
-                    txt,
-                    &self.theme,
-                    true,
-                ),
-                area,
-            );
+            self.draw_at(f, area, true);
+        }
 
-            if self.show_char_count {
-                self.draw_char_count(f, area);
-            }
+        Ok(())
+    }
+}
+
+impl TextInputComponent {
+    /// draws into `area` (e.g. as one field of a bigger popup)
+    /// instead of the popup's own, shown or not
+    pub fn draw_at<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        area: Rect,
+        focused: bool,
+    ) {
+        let txt = if self.msg.is_empty() {
+            Text::styled(
+                self.default_msg.as_str(),
+                self.theme.text(false, false),
+            )
+        } else {
+            self.get_draw_text()
+        };
 
-            self.current_area.set(area);
+        f.render_widget(Clear, area);
+        f.render_widget(
+            popup_paragraph(
+                self.title.as_str(),
+                txt,
+                &self.theme,
+                focused,
+            )
+            // keeps the indentation of wrapped lines
+            .wrap(Wrap { trim: false }),
+            area,
+        );
+
+        if self.show_char_count {
+            self.draw_char_count(f, area);
         }
 
-        Ok(())
+        self.current_area.set(area);
     }
 }
 
@@ -327,6 +421,11 @@ impl Component for TextInputComponent {
             )
             .order(1),
         );
+        out.push(CommandInfo::new(
+            strings::commands::insert_newline(&self.key_config),
+            true,
+            self.visible && self.input_type == InputType::Multiline,
+        ));
         visibility_blocking(self)
     }
 
@@ -338,6 +437,14 @@ impl Component for TextInputComponent {
                     return Ok(true);
                 }
 
+                if e == self.key_config.insert_newline
+                    && self.input_type == InputType::Multiline
+                {
+                    self.msg.insert(self.cursor_position, '\n');
+                    self.incr_cursor();
+                    return Ok(true);
+                }
+
                 let is_ctrl =
                     e.modifiers.contains(KeyModifiers::CONTROL);
 
@@ -365,12 +472,28 @@ impl Component for TextInputComponent {
                         self.incr_cursor();
                         return Ok(true);
                     }
+                    KeyCode::Up
+                        if self.input_type
+                            == InputType::Multiline =>
+                    {
+                        self.line_up();
+                        return Ok(true);
+                    }
+                    KeyCode::Down
+                        if self.input_type
+                            == InputType::Multiline =>
+                    {
+                        self.line_down();
+                        return Ok(true);
+                    }
                     KeyCode::Home => {
-                        self.cursor_position = 0;
+                        self.cursor_position =
+                            self.line_start(self.cursor_position);
                         return Ok(true);
                     }
                     KeyCode::End => {
-                        self.cursor_position = self.msg.len();
+                        self.cursor_position =
+                            self.line_end(self.cursor_position);
                         return Ok(true);
                     }
                     _ => (),
@@ -421,6 +544,51 @@ mod tests {
         assert_eq!(comp.cursor_position, 0);
     }
 
+    #[test]
+    fn test_line_navigation() {
+        let mut comp = TextInputComponent::new(
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+            "",
+            "",
+            false,
+        );
+
+        comp.set_text(String::from("abc\n\näöü"));
+        comp.cursor_position = 2;
+
+        // the column ends up at the end of a shorter line
+        comp.line_down();
+        assert_eq!(comp.cursor_position, 4);
+        assert_eq!(comp.get_cursor_line(), 1);
+
+        comp.line_down();
+        assert_eq!(comp.cursor_position, 5);
+        comp.incr_cursor();
+        comp.incr_cursor();
+        assert_eq!(comp.cursor_position, 9);
+        assert_eq!(comp.get_cursor_line(), 2);
+
+        comp.line_down();
+        assert_eq!(comp.cursor_position, 9);
+
+        // columns count chars, not bytes
+        comp.set_text(String::from("abc\näöü"));
+        comp.cursor_position = 8;
+        comp.line_up();
+        assert_eq!(comp.cursor_position, 2);
+        comp.line_down();
+        assert_eq!(comp.cursor_position, 8);
+
+        comp.line_up();
+        comp.line_up();
+        assert_eq!(comp.cursor_position, 2);
+
+        assert_eq!(comp.line_start(8), 4);
+        assert_eq!(comp.line_end(2), 3);
+        assert_eq!(comp.line_end(4), comp.msg.len());
+    }
+
     #[test]
     fn text_cursor_initial_position() {
         let mut comp = TextInputComponent::new(
@@ -265,6 +265,7 @@ mod tests {
             .iter()
             .map(|a| StatusItem {
                 path: String::from(*a),
+                old_path: None,
                 status: StatusItemType::Modified,
             })
             .collect::<Vec<_>>()
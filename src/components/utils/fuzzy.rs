@@ -0,0 +1,98 @@
+//! fuzzy matching of paths against what the user typed
+
+/// bonus for a char that directly follows the previous match
+const CONSECUTIVE: i64 = 4;
+/// bonus for a char starting a path segment or a word
+const WORD_START: i64 = 3;
+/// bonus for a char in the file name
+const FILE_NAME: i64 = 2;
+
+/// `None` unless all chars of `query` appear in `path` in order,
+/// ignoring case. the higher the better: matches in a row, at the
+/// start of words and in the file name score more
+pub fn fuzzy_score(query: &str, path: &str) -> Option<i64> {
+    let query: Vec<char> = query.chars().map(lowercase).collect();
+    let path: Vec<char> = path.chars().collect();
+
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let file_start =
+        path.iter().rposition(|c| *c == '/').map_or(0, |i| i + 1);
+
+    let char_score = |i: usize| {
+        let mut score = 1;
+        if i == 0
+            || matches!(path[i - 1], '/' | '_' | '-' | '.' | ' ')
+        {
+            score += WORD_START;
+        }
+        if i >= file_start {
+            score += FILE_NAME;
+        }
+        score
+    };
+
+    // best score of the query so far with its last char at each
+    // position of the path
+    let mut scores: Vec<Option<i64>> = path
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            if lowercase(*c) == query[0] {
+                Some(char_score(i))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    for q in &query[1..] {
+        let mut next = vec![None; path.len()];
+        let mut best_before = None;
+
+        for i in 1..path.len() {
+            if lowercase(path[i]) == *q {
+                let consecutive =
+                    scores[i - 1].map(|s| s + CONSECUTIVE);
+                next[i] = best_before
+                    .max(consecutive)
+                    .map(|s| s + char_score(i));
+            }
+
+            best_before = best_before.max(scores[i - 1]);
+        }
+
+        scores = next;
+    }
+
+    scores.into_iter().flatten().max()
+}
+
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match() {
+        assert!(fuzzy_score("", "a/b.rs").is_some());
+        assert!(fuzzy_score("ab", "a/b.rs").is_some());
+        assert!(fuzzy_score("AB", "a/b.rs").is_some());
+        assert!(fuzzy_score("ba", "a/b.rs").is_none());
+        assert!(fuzzy_score("abc", "a/b.rs").is_none());
+    }
+
+    #[test]
+    fn test_ranking() {
+        let score = |path| fuzzy_score("tree", path).unwrap();
+
+        assert!(score("src/statustree.rs") > score("src/t/r/e/e.rs"));
+        assert!(score("src/tree/mod.rs") < score("src/mod/tree.rs"));
+        assert!(score("src/tree.rs") > score("src/stree.rs"));
+    }
+}
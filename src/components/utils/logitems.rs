@@ -1,15 +1,18 @@
-use super::time_to_string;
-use asyncgit::sync::{CommitId, CommitInfo};
+use asyncgit::sync::{CommitId, CommitInfo, CommitRef, GraphRow};
 use std::slice::Iter;
 
 static SLICE_OFFSET_RELOAD_THRESHOLD: usize = 100;
 
 pub struct LogEntry {
-    pub time: String,
+    /// secs since Unix epoch and offset in minutes, formatted
+    /// when drawn since the shown time zone can be toggled
+    pub time: (i64, i32),
     pub author: String,
     pub msg: String,
     pub hash_short: String,
     pub id: CommitId,
+    pub graph: Option<GraphRow>,
+    pub refs: Vec<CommitRef>,
 }
 
 impl From<CommitInfo> for LogEntry {
@@ -17,9 +20,11 @@ impl From<CommitInfo> for LogEntry {
         Self {
             author: c.author,
             msg: c.message,
-            time: time_to_string(c.time, true),
+            time: (c.time, c.time_offset),
             hash_short: c.id.get_short_string(),
             id: c.id,
+            graph: None,
+            refs: c.refs,
         }
     }
 }
@@ -51,14 +56,21 @@ impl ItemBatch {
         self.items.clear();
     }
 
-    /// insert new batch of items
+    /// insert new batch of items, `graph` has the rows of the
+    /// commits (if any)
     pub fn set_items(
         &mut self,
         start_index: usize,
         commits: Vec<CommitInfo>,
+        graph: Vec<GraphRow>,
     ) {
+        let mut graph = graph.into_iter();
+
         self.items.clear();
-        self.items.extend(commits.into_iter().map(LogEntry::from));
+        self.items.extend(commits.into_iter().map(|c| LogEntry {
+            graph: graph.next(),
+            ..LogEntry::from(c)
+        }));
         self.index_offset = start_index;
     }
 
@@ -1,7 +1,10 @@
-use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use crate::options::TimeZoneDisplay;
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, Utc};
 
 pub mod filetree;
+pub mod fuzzy;
 pub mod logitems;
+pub mod split_diff;
 pub mod statustree;
 
 /// macro to simplify running code that might return Err.
@@ -34,3 +37,152 @@ pub fn time_to_string(secs: i64, short: bool) -> String {
     })
     .to_string()
 }
+
+/// like `time_to_string` but in the time zone of `zone`,
+/// `offset_minutes` is the one recorded with the time (east of UTC).
+/// other zones than the local one show their offset
+pub fn time_to_string_in(
+    secs: i64,
+    offset_minutes: i32,
+    short: bool,
+    zone: TimeZoneDisplay,
+) -> String {
+    let offset = match zone {
+        TimeZoneDisplay::Local => return time_to_string(secs, short),
+        TimeZoneDisplay::Utc => FixedOffset::east(0),
+        TimeZoneDisplay::Commit => {
+            FixedOffset::east_opt(offset_minutes.saturating_mul(60))
+                .unwrap_or_else(|| FixedOffset::east(0))
+        }
+    };
+
+    let time = DateTime::<Utc>::from_utc(
+        NaiveDateTime::from_timestamp(secs, 0),
+        Utc,
+    )
+    .with_timezone(&offset);
+    time.format(if short {
+        "%Y-%m-%d"
+    } else {
+        "%Y-%m-%d %H:%M:%S %z"
+    })
+    .to_string()
+}
+
+/// reads a time written like `time_to_string_in` does with the
+/// commit zone (`%Y-%m-%d %H:%M:%S %z`), `None` if it is not.
+/// returns the secs since epoch and the offset in minutes
+pub fn string_to_time(s: &str) -> Option<(i64, i32)> {
+    DateTime::parse_from_str(s.trim(), "%Y-%m-%d %H:%M:%S %z")
+        .ok()
+        .map(|time| {
+            (time.timestamp(), time.offset().local_minus_utc() / 60)
+        })
+}
+
+/// age of `secs` at `now` in its largest whole unit (like "3d ago"),
+/// times after `now` (clock skew) are "now"
+pub fn time_to_relative_string(secs: i64, now: i64) -> String {
+    const DAY: i64 = 24 * 60 * 60;
+    const UNITS: [(i64, &str); 6] = [
+        (365 * DAY, "y"),
+        (30 * DAY, "mo"),
+        (7 * DAY, "w"),
+        (DAY, "d"),
+        (60 * 60, "h"),
+        (60, "m"),
+    ];
+
+    let age = now.saturating_sub(secs);
+    UNITS.iter().find(|(unit, _)| age >= *unit).map_or_else(
+        || "now".to_string(),
+        |(unit, name)| format!("{}{} ago", age / unit, name),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        string_to_time, time_to_relative_string, time_to_string_in,
+        TimeZoneDisplay,
+    };
+
+    #[test]
+    fn test_time_zones() {
+        // 2021-03-01 23:30:00 UTC, made at UTC-5
+        let secs = 1_614_641_400;
+
+        assert_eq!(
+            time_to_string_in(
+                secs,
+                -300,
+                false,
+                TimeZoneDisplay::Utc
+            ),
+            "2021-03-01 23:30:00 +0000"
+        );
+        assert_eq!(
+            time_to_string_in(
+                secs,
+                -300,
+                false,
+                TimeZoneDisplay::Commit
+            ),
+            "2021-03-01 18:30:00 -0500"
+        );
+        assert_eq!(
+            time_to_string_in(
+                secs,
+                120,
+                true,
+                TimeZoneDisplay::Commit
+            ),
+            "2021-03-02"
+        );
+    }
+
+    #[test]
+    fn test_string_to_time() {
+        let secs = 1_614_641_400;
+        let s = time_to_string_in(
+            secs,
+            -300,
+            false,
+            TimeZoneDisplay::Commit,
+        );
+
+        assert_eq!(string_to_time(&s), Some((secs, -300)));
+        assert_eq!(
+            string_to_time(" 2021-03-02 01:30:00 +0200 "),
+            Some((secs, 120))
+        );
+        assert_eq!(string_to_time("2021-03-02 01:30:00"), None);
+        assert_eq!(string_to_time("yesterday"), None);
+    }
+
+    #[test]
+    fn test_relative_time() {
+        let now = 1_614_641_400;
+
+        assert_eq!(time_to_relative_string(now, now), "now");
+        assert_eq!(time_to_relative_string(now + 60, now), "now");
+        assert_eq!(time_to_relative_string(now - 59, now), "now");
+        assert_eq!(time_to_relative_string(now - 150, now), "2m ago");
+        assert_eq!(
+            time_to_relative_string(now - 5 * 60 * 60, now),
+            "5h ago"
+        );
+        assert_eq!(
+            time_to_relative_string(now - 13 * 24 * 60 * 60, now),
+            "1w ago"
+        );
+        assert_eq!(
+            time_to_relative_string(now - 364 * 24 * 60 * 60, now),
+            "12mo ago"
+        );
+        assert_eq!(
+            time_to_relative_string(now - 800 * 24 * 60 * 60, now),
+            "2y ago"
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use asyncgit::{DiffLineType, FileDiff};
+
+/// lines of a diff (indices into all its lines) next to each other
+/// in the side by side view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitRow {
+    /// left side, same as `new` for lines shown on both sides
+    pub old: Option<usize>,
+    /// right side
+    pub new: Option<usize>,
+    ///
+    pub hunk: usize,
+}
+
+impl SplitRow {
+    /// the line selected when moving onto the row
+    pub fn line(&self) -> usize {
+        self.old.or(self.new).unwrap_or_default()
+    }
+
+    ///
+    pub fn contains(&self, line: usize) -> bool {
+        self.old == Some(line) || self.new == Some(line)
+    }
+
+    /// headers and unchanged lines
+    pub fn is_shared(&self) -> bool {
+        self.old.is_some() && self.old == self.new
+    }
+}
+
+/// deleted lines face the lines added right after them, every other
+/// line gets a row of its own
+pub fn split_rows(diff: &FileDiff) -> Vec<SplitRow> {
+    let mut rows = Vec::with_capacity(diff.lines);
+    let mut index = 0;
+
+    for (hunk, lines) in diff.hunks.iter().enumerate() {
+        let mut deleted = Vec::new();
+        let mut added = Vec::new();
+
+        for line in &lines.lines {
+            match line.line_type {
+                DiffLineType::Delete if added.is_empty() => {
+                    deleted.push(index);
+                }
+                DiffLineType::Add => added.push(index),
+                line_type => {
+                    push_changes(
+                        &mut rows,
+                        &mut deleted,
+                        &mut added,
+                        hunk,
+                    );
+                    if line_type == DiffLineType::Delete {
+                        deleted.push(index);
+                    } else {
+                        rows.push(SplitRow {
+                            old: Some(index),
+                            new: Some(index),
+                            hunk,
+                        });
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        push_changes(&mut rows, &mut deleted, &mut added, hunk);
+    }
+
+    rows
+}
+
+fn push_changes(
+    rows: &mut Vec<SplitRow>,
+    deleted: &mut Vec<usize>,
+    added: &mut Vec<usize>,
+    hunk: usize,
+) {
+    for i in 0..deleted.len().max(added.len()) {
+        rows.push(SplitRow {
+            old: deleted.get(i).copied(),
+            new: added.get(i).copied(),
+            hunk,
+        });
+    }
+
+    deleted.clear();
+    added.clear();
+}
+
+/// row showing `line`
+pub fn row_of_line(rows: &[SplitRow], line: usize) -> usize {
+    rows.iter()
+        .position(|row| row.contains(line))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asyncgit::{sync::diff::Hunk, DiffLine};
+
+    fn hunk(types: &[DiffLineType]) -> Hunk {
+        Hunk {
+            header_hash: 0,
+            lines: types
+                .iter()
+                .map(|line_type| DiffLine {
+                    line_type: *line_type,
+                    ..DiffLine::default()
+                })
+                .collect(),
+        }
+    }
+
+    fn row(old: Option<usize>, new: Option<usize>) -> SplitRow {
+        SplitRow { old, new, hunk: 0 }
+    }
+
+    #[test]
+    fn test_split_rows() {
+        use DiffLineType::{Add, Delete, Header, None};
+
+        let diff = FileDiff {
+            hunks: vec![hunk(&[
+                Header, None, Delete, Delete, Add, None, Add, Delete,
+            ])],
+            lines: 8,
+            ..FileDiff::default()
+        };
+
+        let rows = split_rows(&diff);
+
+        assert_eq!(
+            rows,
+            vec![
+                row(Some(0), Some(0)),
+                row(Some(1), Some(1)),
+                row(Some(2), Some(4)),
+                row(Some(3), Option::None),
+                row(Some(5), Some(5)),
+                row(Option::None, Some(6)),
+                row(Some(7), Option::None),
+            ]
+        );
+        assert_eq!(row_of_line(&rows, 4), 2);
+        assert_eq!(row_of_line(&rows, 6), 5);
+        assert!(rows[1].is_shared());
+        assert!(!rows[2].is_shared());
+    }
+}
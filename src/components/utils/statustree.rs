@@ -3,7 +3,11 @@ use super::filetree::{
 };
 use anyhow::Result;
 use asyncgit::StatusItem;
-use std::{cmp, collections::BTreeSet};
+use std::{
+    cmp,
+    collections::{BTreeSet, HashSet},
+    path::Path,
+};
 
 ///
 #[derive(Default)]
@@ -14,6 +18,10 @@ pub struct StatusTree {
     // some folders may be folded up, this allows jumping
     // over folders which are folded into their parent
     pub available_selections: Vec<usize>,
+
+    /// folders collapsed so far, even if no longer in the tree,
+    /// so they come back collapsed
+    collapsed: BTreeSet<String>,
 }
 
 ///
@@ -41,7 +49,8 @@ impl SelectionChange {
 impl StatusTree {
     /// update tree with a new list, try to retain selection and collapse states
     pub fn update(&mut self, list: &[StatusItem]) -> Result<()> {
-        let last_collapsed = self.all_collapsed();
+        self.remember_collapsed();
+        let last_collapsed = self.collapsed.iter().collect();
 
         let last_selection =
             self.selected_item().map(|e| e.info.full_path);
@@ -152,6 +161,99 @@ impl StatusTree {
         })
     }
 
+    /// shows only the files in `paths` and the folders they are in,
+    /// no matter if collapsed, or everything again if `None`
+    pub fn filter(&mut self, paths: Option<&HashSet<&str>>) {
+        if let Some(paths) = paths {
+            let folders: HashSet<&Path> = paths
+                .iter()
+                .flat_map(|path| Path::new(path).ancestors().skip(1))
+                .collect();
+
+            for i in 0..self.tree.len() {
+                let item = &mut self.tree[i];
+                item.info.visible = match item.kind {
+                    FileTreeItemKind::File(_) => {
+                        paths.contains(item.info.full_path.as_str())
+                    }
+                    FileTreeItemKind::Path(_) => folders
+                        .contains(Path::new(&item.info.full_path)),
+                };
+            }
+        } else {
+            self.update_visibility(None, 0, true);
+        }
+
+        if let Some(idx) = self.selection {
+            self.selection = Some(self.find_visible_idx(idx));
+        }
+    }
+
+    /// selects the file at `path`, expanding the folders it is in,
+    /// returns false if there is no such file
+    pub fn select_path(&mut self, path: &str) -> bool {
+        let idx = match self
+            .tree
+            .items()
+            .iter()
+            .position(|item| item.info.full_path == path)
+        {
+            Some(idx) => idx,
+            None => return false,
+        };
+
+        if !self.is_visible_index(idx) {
+            let folders: HashSet<&Path> =
+                Path::new(path).ancestors().skip(1).collect();
+
+            for i in 0..idx {
+                let item = &mut self.tree[i];
+                if let FileTreeItemKind::Path(PathCollapsed(
+                    ref mut collapsed,
+                )) = item.kind
+                {
+                    if folders
+                        .contains(Path::new(&item.info.full_path))
+                    {
+                        *collapsed = false;
+                    }
+                }
+            }
+
+            self.update_visibility(None, 0, true);
+        }
+
+        self.selection = Some(idx);
+
+        true
+    }
+
+    /// collapses (`collapse`) or expands every folder, returns true
+    /// if the tree changed
+    pub fn collapse_all(&mut self, collapse: bool) -> bool {
+        let mut changed = false;
+
+        for i in 0..self.tree.len() {
+            if let FileTreeItemKind::Path(PathCollapsed(
+                ref mut collapsed,
+            )) = self.tree[i].kind
+            {
+                changed |= *collapsed != collapse;
+                *collapsed = collapse;
+            }
+        }
+
+        if changed {
+            self.update_visibility(None, 0, true);
+
+            if let Some(idx) = self.selection {
+                self.selection = Some(self.find_visible_idx(idx));
+            }
+        }
+
+        changed
+    }
+
     ///
     pub fn selected_item(&self) -> Option<FileTreeItem> {
         self.selection.map(|i| self.tree[i].clone())
@@ -178,6 +280,19 @@ impl StatusTree {
         res
     }
 
+    fn remember_collapsed(&mut self) {
+        let collapsed: Vec<String> =
+            self.all_collapsed().into_iter().cloned().collect();
+
+        for item in self.tree.items() {
+            if let FileTreeItemKind::Path(_) = item.kind {
+                self.collapsed.remove(&item.info.full_path);
+            }
+        }
+
+        self.collapsed.extend(collapsed);
+    }
+
     fn find_last_selection(
         &self,
         last_selection: &str,
@@ -437,6 +552,7 @@ mod tests {
             .iter()
             .map(|a| StatusItem {
                 path: String::from(*a),
+                old_path: None,
                 status: StatusItemType::Modified,
             })
             .collect::<Vec<_>>()
@@ -562,6 +678,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_remember_collapsed_while_gone() {
+        let mut res = StatusTree::default();
+        res.update(&string_vec_to_status(&["a/b", "c"])).unwrap();
+        res.collapse("a", 0);
+
+        res.update(&string_vec_to_status(&["c"])).unwrap();
+        res.update(&string_vec_to_status(&["a/b", "c"])).unwrap();
+
+        assert_eq!(get_visibles(&res), vec![true, false, true]);
+
+        res.expand("a", 0);
+        res.update(&string_vec_to_status(&["c"])).unwrap();
+        res.update(&string_vec_to_status(&["a/b", "c"])).unwrap();
+
+        assert_eq!(get_visibles(&res), vec![true, true, true]);
+    }
+
+    #[test]
+    fn test_collapse_all() {
+        let items = string_vec_to_status(&[
+            "a/b/c", //
+            "a/d",   //
+            "e",     //
+        ]);
+
+        let mut res = StatusTree::default();
+        res.update(&items).unwrap();
+        res.selection = Some(2);
+
+        assert!(res.collapse_all(true));
+        assert!(!res.collapse_all(true));
+        assert_eq!(
+            get_visibles(&res),
+            vec![true, false, false, false, true]
+        );
+        assert_eq!(res.selection, Some(0));
+
+        assert!(res.collapse_all(false));
+        assert_eq!(
+            get_visibles(&res),
+            vec![true, true, true, true, true]
+        );
+    }
+
     #[test]
     fn test_expand() {
         let items = string_vec_to_status(&[
@@ -899,4 +1060,50 @@ mod tests {
         assert!(res.move_selection(MoveSelection::Left)); // jump to 0
         assert_eq!(res.selection, Some(0));
     }
+
+    #[test]
+    fn test_filter_and_select_path() {
+        let items = string_vec_to_status(&[
+            "a/b/c", //
+            "a/d",   //
+            "e",     //
+        ]);
+
+        //0 a/
+        //1   b/
+        //2     c
+        //3   d
+        //4 e
+
+        let mut res = StatusTree::default();
+        res.update(&items).unwrap();
+        res.selection = Some(1);
+        assert!(res.move_selection(MoveSelection::Left)); // fold b/
+
+        let paths = vec!["a/b/c"].into_iter().collect();
+        res.filter(Some(&paths));
+        assert_eq!(
+            get_visibles(&res),
+            vec![true, true, true, false, false]
+        );
+
+        assert!(res.select_path("a/b/c"));
+        assert_eq!(res.selection, Some(2));
+
+        res.filter(None);
+        assert_eq!(
+            get_visibles(&res),
+            vec![true, true, false, true, true]
+        );
+        assert_eq!(res.selection, Some(1));
+
+        assert!(res.select_path("a/b/c"));
+        assert_eq!(res.selection, Some(2));
+        assert_eq!(
+            get_visibles(&res),
+            vec![true, true, true, true, true]
+        );
+
+        assert!(!res.select_path("a/x"));
+    }
 }
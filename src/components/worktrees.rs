@@ -0,0 +1,296 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{Action, InternalEvent, Queue},
+    strings, try_or_popup,
+    ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, WorktreeInfo},
+    CWD,
+};
+use crossterm::event::Event;
+use std::cell::Cell;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// the main and the linked worktrees, gitui can switch over to the
+/// selected one
+pub struct WorktreeListComponent {
+    worktrees: Vec<WorktreeInfo>,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for WorktreeListComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(70, 40);
+            const MIN_SIZE: Size = Size::new(60, 10);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height = (area.height as usize).saturating_sub(2);
+            self.scroll_top.set(ui::calc_scroll_top(
+                self.scroll_top.get(),
+                height,
+                self.selection,
+            ));
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height)).block(
+                    Block::default()
+                        .title(Span::styled(
+                            strings::worktrees_title(
+                                &self.key_config,
+                            ),
+                            self.theme.title(true),
+                        ))
+                        .border_type(BorderType::Thick)
+                        .borders(Borders::ALL),
+                ),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for WorktreeListComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::worktree_switch(&self.key_config),
+                self.switchable().is_some(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::worktree_create(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::worktree_remove(&self.key_config),
+                self.removable().is_some(),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::worktree_prune(&self.key_config),
+                self.worktrees.iter().any(|wt| wt.is_prunable),
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide();
+                } else if e == self.key_config.move_down {
+                    self.selection = (self.selection + 1)
+                        .min(self.worktrees.len().saturating_sub(1));
+                } else if e == self.key_config.move_up {
+                    self.selection = self.selection.saturating_sub(1);
+                } else if e == self.key_config.enter {
+                    if let Some(path) = self.switchable() {
+                        let path = path.to_string();
+                        self.hide();
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::SwitchWorktree(path),
+                        );
+                    }
+                } else if e == self.key_config.worktree_create {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::CreateWorktree);
+                } else if e == self.key_config.worktree_remove {
+                    if let Some(name) = self.removable() {
+                        let name = name.to_string();
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ConfirmAction(
+                                Action::RemoveWorktree(name),
+                            ),
+                        );
+                    }
+                } else if e == self.key_config.worktree_prune {
+                    try_or_popup!(
+                        self,
+                        "prune worktrees error:",
+                        self.prune()
+                    );
+                }
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl WorktreeListComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            worktrees: Vec::new(),
+            selection: 0,
+            scroll_top: Cell::new(0),
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self) -> Result<()> {
+        self.worktrees = sync::get_worktrees(CWD)?;
+        self.selection = 0;
+
+        self.show()
+    }
+
+    /// reloads the list after worktrees were added or removed
+    pub fn refresh(&mut self) -> Result<()> {
+        if self.visible {
+            self.worktrees = sync::get_worktrees(CWD)?;
+            self.selection = self
+                .selection
+                .min(self.worktrees.len().saturating_sub(1));
+        }
+
+        Ok(())
+    }
+
+    fn selected(&self) -> Option<&WorktreeInfo> {
+        self.worktrees.get(self.selection)
+    }
+
+    /// path of the selected worktree unless it is the current one
+    fn switchable(&self) -> Option<&str> {
+        self.selected()
+            .filter(|wt| !wt.is_current && !wt.is_prunable)
+            .map(|wt| wt.path.as_str())
+    }
+
+    /// name of the selected worktree if it is a linked one
+    fn removable(&self) -> Option<&str> {
+        self.selected()
+            .filter(|wt| !wt.is_current)
+            .and_then(|wt| wt.name.as_deref())
+    }
+
+    fn prune(&mut self) -> Result<()> {
+        sync::prune_worktrees(CWD)?;
+        self.refresh()
+    }
+
+    fn get_text(&self, height: usize) -> Text<'_> {
+        let branch_width = self
+            .worktrees
+            .iter()
+            .filter_map(|wt| wt.branch.as_ref())
+            .map(|branch| branch.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let txt: Vec<Spans> = self
+            .worktrees
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|(idx, wt)| {
+                let selected = idx == self.selection;
+
+                Spans::from(vec![
+                    Span::styled(
+                        if wt.is_current { "* " } else { "  " },
+                        self.theme.text(true, selected),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{:w$} ",
+                            wt.branch.as_deref().unwrap_or("-"),
+                            w = branch_width
+                        ),
+                        self.theme.branch(selected, wt.is_current),
+                    ),
+                    Span::styled(
+                        format!("{} ", wt.path),
+                        self.theme.text(true, selected),
+                    ),
+                    Span::styled(
+                        strings::worktree_state(&self.key_config, wt),
+                        self.theme.text_danger(),
+                    ),
+                ])
+            })
+            .collect();
+
+        Text::from(txt)
+    }
+}
@@ -58,22 +58,133 @@ pub struct KeyConfig {
     pub stashing_toggle_index: KeyEvent,
     pub stash_open: KeyEvent,
     pub stash_drop: KeyEvent,
+    pub stash_rename: KeyEvent,
+    pub stash_pop: KeyEvent,
+    pub stash_branch: KeyEvent,
     pub cmd_bar_toggle: KeyEvent,
     pub log_tag_commit: KeyEvent,
     pub commit_amend: KeyEvent,
+    pub commit_amend_no_edit: KeyEvent,
+    pub commit_amend_advanced: KeyEvent,
     pub copy: KeyEvent,
     pub create_branch: KeyEvent,
     pub rename_branch: KeyEvent,
+    pub edit_branch_description: KeyEvent,
     pub select_branch: KeyEvent,
     pub delete_branch: KeyEvent,
     pub toggle_remote_branches: KeyEvent,
+    pub favorite_branch: KeyEvent,
+    pub merge_branch: KeyEvent,
+    pub rebase_branch: KeyEvent,
+    pub branch_set_upstream: KeyEvent,
+    pub branch_unset_upstream: KeyEvent,
+    pub branch_sort: KeyEvent,
+    pub branch_find: KeyEvent,
+    pub branch_delete_merged: KeyEvent,
     pub push: KeyEvent,
     pub force_push: KeyEvent,
     pub pull: KeyEvent,
+    pub pull_strategy: KeyEvent,
+    pub fetch_all: KeyEvent,
+    pub view_object: KeyEvent,
+    pub log_toggle_no_merges: KeyEvent,
+    pub log_toggle_all_branches: KeyEvent,
+    pub log_toggle_first_parent: KeyEvent,
+    pub log_search: KeyEvent,
+    pub log_search_next: KeyEvent,
+    pub log_search_prev: KeyEvent,
+    pub log_search_filter: KeyEvent,
+    pub log_mark_compare: KeyEvent,
+    pub commit_and_push: KeyEvent,
+    pub commit_toggle_verbose: KeyEvent,
+    pub commit_toggle_signoff: KeyEvent,
+    pub commit_co_author: KeyEvent,
+    pub search_branches: KeyEvent,
+    pub checkout_force: KeyEvent,
+    pub open_clean: KeyEvent,
+    pub undo_discard: KeyEvent,
+    pub ignore_toggle_global: KeyEvent,
+    pub ignore_edit: KeyEvent,
+    pub clean_toggle_directories: KeyEvent,
+    pub clean_toggle_ignored: KeyEvent,
+    pub compare_upstream: KeyEvent,
+    pub select_identity: KeyEvent,
+    pub view_patch: KeyEvent,
+    pub pick_hunks: KeyEvent,
+    pub pick_hunks_split: KeyEvent,
+    pub toggle_time_zone: KeyEvent,
+    pub diff_load_large: KeyEvent,
+    pub diff_toggle_split: KeyEvent,
+    pub diff_options: KeyEvent,
+    pub diff_visual_select: KeyEvent,
+    pub diff_edit_hunk: KeyEvent,
+    pub rebase_interactive: KeyEvent,
+    pub rebase_pick: KeyEvent,
+    pub rebase_reword: KeyEvent,
+    pub rebase_squash: KeyEvent,
+    pub rebase_fixup: KeyEvent,
+    pub rebase_drop: KeyEvent,
+    pub rebase_skip: KeyEvent,
+    pub rebase_abort: KeyEvent,
+    pub commit_fixup: KeyEvent,
+    pub commit_squash: KeyEvent,
+    pub autosquash: KeyEvent,
+    pub cherry_pick: KeyEvent,
+    pub cherry_pick_abort: KeyEvent,
+    pub revert_commit: KeyEvent,
+    pub log_reset_commit: KeyEvent,
+    pub log_checkout_commit: KeyEvent,
+    pub log_browse_files: KeyEvent,
+    pub restore_file: KeyEvent,
+    pub inspect_merge_diff: KeyEvent,
+    pub log_export_patches: KeyEvent,
+    pub apply_patch: KeyEvent,
+    pub patch_apply_toggle_index: KeyEvent,
+    pub archive: KeyEvent,
+    pub archive_format: KeyEvent,
+    pub log_goto: KeyEvent,
+    pub blame: KeyEvent,
+    pub file_history: KeyEvent,
+    pub tree_find: KeyEvent,
+    pub tree_collapse_all: KeyEvent,
+    pub tree_expand_all: KeyEvent,
+    pub toggle_mark: KeyEvent,
+    pub mark_range: KeyEvent,
+    pub open_submodules: KeyEvent,
+    pub submodule_update: KeyEvent,
+    pub open_worktrees: KeyEvent,
+    pub worktree_create: KeyEvent,
+    pub worktree_remove: KeyEvent,
+    pub worktree_prune: KeyEvent,
+    pub open_remotes: KeyEvent,
+    pub remote_add: KeyEvent,
+    pub remote_rename: KeyEvent,
+    pub remote_set_url: KeyEvent,
+    pub remote_remove: KeyEvent,
+    pub open_reflog: KeyEvent,
+    pub reflog_reset_hard: KeyEvent,
+    pub open_bisect: KeyEvent,
+    pub bisect_good: KeyEvent,
+    pub bisect_bad: KeyEvent,
+    pub bisect_skip: KeyEvent,
+    pub bisect_reset: KeyEvent,
+    pub conflict_take_ours: KeyEvent,
+    pub conflict_take_theirs: KeyEvent,
+    pub conflict_mergetool: KeyEvent,
+    pub conflict_resolved: KeyEvent,
+    pub open_tags: KeyEvent,
+    pub tag_push: KeyEvent,
+    pub tag_delete: KeyEvent,
+    pub tag_delete_remote: KeyEvent,
+    pub insert_newline: KeyEvent,
+    pub tag_toggle_sign: KeyEvent,
+    pub push_to: KeyEvent,
+    pub push_toggle_upstream: KeyEvent,
 }
 
 #[rustfmt::skip]
 impl Default for KeyConfig {
+    #[allow(clippy::too_many_lines)]
     fn default() -> Self {
         Self {
 			tab_status: KeyEvent { code: KeyCode::Char('1'), modifiers: KeyModifiers::empty()},
@@ -114,18 +225,128 @@ impl Default for KeyConfig {
 			stashing_toggle_index: KeyEvent { code: KeyCode::Char('i'), modifiers: KeyModifiers::empty()},
 			stash_open: KeyEvent { code: KeyCode::Right, modifiers: KeyModifiers::empty()},
 			stash_drop: KeyEvent { code: KeyCode::Char('D'), modifiers: KeyModifiers::SHIFT},
+			stash_rename: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty()},
+			stash_pop: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::empty()},
+			stash_branch: KeyEvent { code: KeyCode::Char('b'), modifiers: KeyModifiers::empty()},
 			cmd_bar_toggle: KeyEvent { code: KeyCode::Char('.'), modifiers: KeyModifiers::empty()},
 			log_tag_commit: KeyEvent { code: KeyCode::Char('t'), modifiers: KeyModifiers::empty()},
 			commit_amend: KeyEvent { code: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL},
+			commit_amend_no_edit: KeyEvent { code: KeyCode::Char('A'), modifiers: KeyModifiers::SHIFT},
+			commit_amend_advanced: KeyEvent { code: KeyCode::Char('u'), modifiers: KeyModifiers::CONTROL},
             copy: KeyEvent { code: KeyCode::Char('y'), modifiers: KeyModifiers::empty()},
             create_branch: KeyEvent { code: KeyCode::Char('c'), modifiers: KeyModifiers::NONE},
             rename_branch: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::NONE},
+            edit_branch_description: KeyEvent { code: KeyCode::Char('e'), modifiers: KeyModifiers::NONE},
             select_branch: KeyEvent { code: KeyCode::Char('b'), modifiers: KeyModifiers::NONE},
             delete_branch: KeyEvent{code: KeyCode::Char('D'), modifiers: KeyModifiers::SHIFT},
             toggle_remote_branches: KeyEvent{code: KeyCode::Char('t'), modifiers: KeyModifiers::NONE},
+            favorite_branch: KeyEvent{code: KeyCode::Char('f'), modifiers: KeyModifiers::NONE},
+            merge_branch: KeyEvent{code: KeyCode::Char('m'), modifiers: KeyModifiers::NONE},
+            rebase_branch: KeyEvent{code: KeyCode::Char('R'), modifiers: KeyModifiers::SHIFT},
+            branch_set_upstream: KeyEvent{code: KeyCode::Char('u'), modifiers: KeyModifiers::NONE},
+            branch_unset_upstream: KeyEvent{code: KeyCode::Char('U'), modifiers: KeyModifiers::SHIFT},
+            branch_sort: KeyEvent{code: KeyCode::Char('s'), modifiers: KeyModifiers::NONE},
+            branch_find: KeyEvent{code: KeyCode::Char('/'), modifiers: KeyModifiers::NONE},
+            branch_delete_merged: KeyEvent{code: KeyCode::Char('X'), modifiers: KeyModifiers::SHIFT},
             push: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::empty()},
             force_push: KeyEvent { code: KeyCode::Char('P'), modifiers: KeyModifiers::SHIFT},
             pull: KeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::empty()},
+            pull_strategy: KeyEvent { code: KeyCode::Char('F'), modifiers: KeyModifiers::SHIFT},
+            fetch_all: KeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::CONTROL},
+            view_object: KeyEvent { code: KeyCode::Char('o'), modifiers: KeyModifiers::empty()},
+            log_toggle_no_merges: KeyEvent { code: KeyCode::Char('m'), modifiers: KeyModifiers::empty()},
+            log_toggle_all_branches: KeyEvent { code: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL},
+            log_toggle_first_parent: KeyEvent { code: KeyCode::Char('^'), modifiers: KeyModifiers::empty()},
+            log_search: KeyEvent { code: KeyCode::Char('/'), modifiers: KeyModifiers::empty()},
+            log_search_next: KeyEvent { code: KeyCode::Char('n'), modifiers: KeyModifiers::empty()},
+            log_search_prev: KeyEvent { code: KeyCode::Char('N'), modifiers: KeyModifiers::SHIFT},
+            log_search_filter: KeyEvent { code: KeyCode::Char('&'), modifiers: KeyModifiers::empty()},
+            log_mark_compare: KeyEvent { code: KeyCode::Char('='), modifiers: KeyModifiers::empty()},
+            commit_and_push: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::CONTROL},
+            commit_toggle_verbose: KeyEvent { code: KeyCode::Char('d'), modifiers: KeyModifiers::CONTROL},
+            commit_toggle_signoff: KeyEvent { code: KeyCode::Char('s'), modifiers: KeyModifiers::CONTROL},
+            commit_co_author: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::CONTROL},
+            search_branches: KeyEvent { code: KeyCode::Char('S'), modifiers: KeyModifiers::SHIFT},
+            checkout_force: KeyEvent { code: KeyCode::Char('F'), modifiers: KeyModifiers::SHIFT},
+            open_clean: KeyEvent { code: KeyCode::Char('C'), modifiers: KeyModifiers::SHIFT},
+            undo_discard: KeyEvent { code: KeyCode::Char('Z'), modifiers: KeyModifiers::SHIFT},
+            ignore_toggle_global: KeyEvent { code: KeyCode::Char('g'), modifiers: KeyModifiers::empty()},
+            ignore_edit: KeyEvent { code: KeyCode::Char('e'), modifiers: KeyModifiers::empty()},
+            clean_toggle_directories: KeyEvent { code: KeyCode::Char('d'), modifiers: KeyModifiers::empty()},
+            clean_toggle_ignored: KeyEvent { code: KeyCode::Char('i'), modifiers: KeyModifiers::empty()},
+            compare_upstream: KeyEvent { code: KeyCode::Char('U'), modifiers: KeyModifiers::SHIFT},
+            select_identity: KeyEvent { code: KeyCode::Char('I'), modifiers: KeyModifiers::SHIFT},
+            view_patch: KeyEvent { code: KeyCode::Char('v'), modifiers: KeyModifiers::empty()},
+            pick_hunks: KeyEvent { code: KeyCode::Char('H'), modifiers: KeyModifiers::SHIFT},
+            pick_hunks_split: KeyEvent { code: KeyCode::Char('s'), modifiers: KeyModifiers::empty()},
+            toggle_time_zone: KeyEvent { code: KeyCode::Char('z'), modifiers: KeyModifiers::empty()},
+            diff_load_large: KeyEvent { code: KeyCode::Char('L'), modifiers: KeyModifiers::SHIFT},
+            diff_toggle_split: KeyEvent { code: KeyCode::Char('|'), modifiers: KeyModifiers::empty()},
+            diff_options: KeyEvent { code: KeyCode::Char('o'), modifiers: KeyModifiers::CONTROL},
+            diff_visual_select: KeyEvent { code: KeyCode::Char('V'), modifiers: KeyModifiers::SHIFT},
+            diff_edit_hunk: KeyEvent { code: KeyCode::Char('E'), modifiers: KeyModifiers::SHIFT},
+            rebase_interactive: KeyEvent { code: KeyCode::Char('R'), modifiers: KeyModifiers::SHIFT},
+            rebase_pick: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::empty()},
+            rebase_reword: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty()},
+            rebase_squash: KeyEvent { code: KeyCode::Char('s'), modifiers: KeyModifiers::empty()},
+            rebase_fixup: KeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::empty()},
+            rebase_drop: KeyEvent { code: KeyCode::Char('d'), modifiers: KeyModifiers::empty()},
+            rebase_skip: KeyEvent { code: KeyCode::Char('s'), modifiers: KeyModifiers::empty()},
+            rebase_abort: KeyEvent { code: KeyCode::Char('a'), modifiers: KeyModifiers::empty()},
+            commit_fixup: KeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::empty()},
+            commit_squash: KeyEvent { code: KeyCode::Char('F'), modifiers: KeyModifiers::SHIFT},
+            autosquash: KeyEvent { code: KeyCode::Char('A'), modifiers: KeyModifiers::SHIFT},
+            cherry_pick: KeyEvent { code: KeyCode::Char('x'), modifiers: KeyModifiers::empty()},
+            cherry_pick_abort: KeyEvent { code: KeyCode::Char('X'), modifiers: KeyModifiers::SHIFT},
+            revert_commit: KeyEvent { code: KeyCode::Char('V'), modifiers: KeyModifiers::SHIFT},
+            log_reset_commit: KeyEvent { code: KeyCode::Char('H'), modifiers: KeyModifiers::SHIFT},
+            log_checkout_commit: KeyEvent { code: KeyCode::Char('C'), modifiers: KeyModifiers::SHIFT},
+            log_browse_files: KeyEvent { code: KeyCode::Char('e'), modifiers: KeyModifiers::empty()},
+            restore_file: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty()},
+            inspect_merge_diff: KeyEvent { code: KeyCode::Char('m'), modifiers: KeyModifiers::empty()},
+            log_export_patches: KeyEvent { code: KeyCode::Char('E'), modifiers: KeyModifiers::SHIFT},
+            apply_patch: KeyEvent { code: KeyCode::Char('k'), modifiers: KeyModifiers::CONTROL},
+            patch_apply_toggle_index: KeyEvent { code: KeyCode::Char('s'), modifiers: KeyModifiers::CONTROL},
+            archive: KeyEvent { code: KeyCode::Char('a'), modifiers: KeyModifiers::empty()},
+            archive_format: KeyEvent { code: KeyCode::Char('t'), modifiers: KeyModifiers::CONTROL},
+            log_goto: KeyEvent { code: KeyCode::Char('g'), modifiers: KeyModifiers::CONTROL},
+            blame: KeyEvent { code: KeyCode::Char('B'), modifiers: KeyModifiers::SHIFT},
+            file_history: KeyEvent { code: KeyCode::Char('Y'), modifiers: KeyModifiers::SHIFT},
+            tree_find: KeyEvent { code: KeyCode::Char('/'), modifiers: KeyModifiers::empty()},
+            tree_collapse_all: KeyEvent { code: KeyCode::Char('-'), modifiers: KeyModifiers::empty()},
+            tree_expand_all: KeyEvent { code: KeyCode::Char('+'), modifiers: KeyModifiers::empty()},
+            toggle_mark: KeyEvent { code: KeyCode::Char(' '), modifiers: KeyModifiers::empty()},
+            mark_range: KeyEvent { code: KeyCode::Char(' '), modifiers: KeyModifiers::CONTROL},
+            open_submodules: KeyEvent { code: KeyCode::Char('M'), modifiers: KeyModifiers::SHIFT},
+            submodule_update: KeyEvent { code: KeyCode::Char('u'), modifiers: KeyModifiers::empty()},
+            open_worktrees: KeyEvent { code: KeyCode::Char('W'), modifiers: KeyModifiers::SHIFT},
+            worktree_create: KeyEvent { code: KeyCode::Char('c'), modifiers: KeyModifiers::empty()},
+            worktree_remove: KeyEvent { code: KeyCode::Char('D'), modifiers: KeyModifiers::SHIFT},
+            worktree_prune: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::empty()},
+            open_remotes: KeyEvent { code: KeyCode::Char('O'), modifiers: KeyModifiers::SHIFT},
+            remote_add: KeyEvent { code: KeyCode::Char('a'), modifiers: KeyModifiers::empty()},
+            remote_rename: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty()},
+            remote_set_url: KeyEvent { code: KeyCode::Char('u'), modifiers: KeyModifiers::empty()},
+            remote_remove: KeyEvent { code: KeyCode::Char('D'), modifiers: KeyModifiers::SHIFT},
+            open_reflog: KeyEvent { code: KeyCode::Char('G'), modifiers: KeyModifiers::SHIFT},
+            reflog_reset_hard: KeyEvent { code: KeyCode::Char('R'), modifiers: KeyModifiers::SHIFT},
+            open_bisect: KeyEvent { code: KeyCode::Char('T'), modifiers: KeyModifiers::SHIFT},
+            bisect_good: KeyEvent { code: KeyCode::Char('g'), modifiers: KeyModifiers::empty()},
+            bisect_bad: KeyEvent { code: KeyCode::Char('b'), modifiers: KeyModifiers::empty()},
+            bisect_skip: KeyEvent { code: KeyCode::Char('s'), modifiers: KeyModifiers::empty()},
+            bisect_reset: KeyEvent { code: KeyCode::Char('R'), modifiers: KeyModifiers::SHIFT},
+            conflict_take_ours: KeyEvent { code: KeyCode::Char('o'), modifiers: KeyModifiers::empty()},
+            conflict_take_theirs: KeyEvent { code: KeyCode::Char('t'), modifiers: KeyModifiers::empty()},
+            conflict_mergetool: KeyEvent { code: KeyCode::Char('m'), modifiers: KeyModifiers::empty()},
+            conflict_resolved: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty()},
+            open_tags: KeyEvent { code: KeyCode::Char('t'), modifiers: KeyModifiers::CONTROL},
+            tag_push: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::empty()},
+            tag_delete: KeyEvent { code: KeyCode::Char('D'), modifiers: KeyModifiers::SHIFT},
+            tag_delete_remote: KeyEvent { code: KeyCode::Char('R'), modifiers: KeyModifiers::SHIFT},
+            insert_newline: KeyEvent { code: KeyCode::Enter, modifiers: KeyModifiers::ALT},
+            tag_toggle_sign: KeyEvent { code: KeyCode::Char('s'), modifiers: KeyModifiers::CONTROL},
+            push_to: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::CONTROL},
+            push_toggle_upstream: KeyEvent { code: KeyCode::Char('u'), modifiers: KeyModifiers::CONTROL},
         }
     }
 }
@@ -221,6 +442,12 @@ impl KeyConfig {
                     self.get_key_symbol(ev.code)
                 )
             }
+            KeyCode::Char(' ') => {
+                format!(
+                    "{}\u{2423}", //␣
+                    Self::get_modifier_hint(ev.modifiers),
+                )
+            }
             KeyCode::Char(c) => {
                 format!(
                     "{}{}",
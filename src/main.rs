@@ -18,6 +18,7 @@ mod components;
 mod input;
 mod keys;
 mod notify_mutex;
+mod options;
 mod profiler;
 mod queue;
 mod spinner;
@@ -44,6 +45,7 @@ use crossterm::{
 };
 use input::{Input, InputEvent, InputState};
 use keys::KeyConfig;
+use options::Options;
 use profiler::Profiler;
 use scopeguard::defer;
 use scopetime::scope_time;
@@ -93,6 +95,9 @@ fn main() -> Result<()> {
     let key_config = KeyConfig::init(KeyConfig::get_config_file()?)
         .map_err(|e| eprintln!("KeyConfig loading error: {}", e))
         .unwrap_or_default();
+    let options = Options::init(Options::get_config_file()?)
+        .map_err(|e| eprintln!("Options loading error: {}", e))
+        .unwrap_or_default();
     let theme = Theme::init(cliargs.theme)
         .map_err(|e| eprintln!("Theme loading error: {}", e))
         .unwrap_or_default();
@@ -114,7 +119,8 @@ fn main() -> Result<()> {
     let ticker = tick(TICK_INTERVAL);
     let spinner_ticker = tick(SPINNER_INTERVAL);
 
-    let mut app = App::new(&tx_git, input, theme, key_config);
+    let mut app =
+        App::new(&tx_git, input, theme, key_config, options);
 
     let mut spinner = Spinner::default();
     let mut first_update = true;
@@ -168,6 +174,11 @@ fn main() -> Result<()> {
             if app.is_quit() {
                 break;
             }
+
+            if app.needs_reopen() {
+                app = app.reopen(&tx_git);
+                first_update = true;
+            }
         }
     }
 
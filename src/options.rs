@@ -0,0 +1,263 @@
+use crate::get_app_config_path;
+use anyhow::Result;
+use asyncgit::sync::DiffOptions;
+use ron::{
+    self,
+    ser::{to_string_pretty, PrettyConfig},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::Cell,
+    fs::{self, File},
+    io::{Read, Write},
+    path::PathBuf,
+    rc::Rc,
+};
+
+pub type SharedOptions = Rc<Options>;
+
+/// entry of `identities` in `options.ron`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IdentityOption {
+    pub name: String,
+    pub email: String,
+}
+
+/// time zone commit times are shown in
+#[derive(
+    Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq,
+)]
+pub enum TimeZoneDisplay {
+    /// the one of this machine
+    Local,
+    ///
+    Utc,
+    /// the one the commit was made in (author or committer)
+    Commit,
+}
+
+impl TimeZoneDisplay {
+    /// order of the toggle
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Local => Self::Utc,
+            Self::Utc => Self::Commit,
+            Self::Commit => Self::Local,
+        }
+    }
+}
+
+/// columns of the commit list (`log_columns` in `options.ron`),
+/// the time zone of the dates is `time_zone`
+#[derive(
+    Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq,
+)]
+#[serde(default)]
+pub struct LogColumns {
+    /// characters of the commit hash (0 hides it)
+    pub hash_length: usize,
+    ///
+    pub date: bool,
+    /// age of the commit (like "3d ago") instead of its date
+    pub relative_date: bool,
+    ///
+    pub author: bool,
+}
+
+impl Default for LogColumns {
+    fn default() -> Self {
+        Self {
+            hash_length: 7,
+            date: true,
+            relative_date: false,
+            author: true,
+        }
+    }
+}
+
+/// general app settings (`options.ron`)
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Options {
+    /// never show advice popups (e.g. on detached HEAD)
+    pub hide_advice: bool,
+    /// subject ruler in commit popup and message truncation
+    /// in the log (0 disables both)
+    pub commit_subject_length: usize,
+    /// body ruler in commit popup (0 disables)
+    pub commit_body_wrap: usize,
+    /// staged diff below the commit message (like
+    /// `git commit --verbose`), toggled at runtime (not saved)
+    #[serde(skip)]
+    pub commit_verbose: Cell<bool>,
+    /// commits before and after the selection in the log whose
+    /// details are fetched ahead (0 disables)
+    pub commit_details_prefetch: usize,
+    /// identities to quickly switch between, written to the
+    /// config of the current repo
+    pub identities: Vec<IdentityOption>,
+    /// fetch the default remote in the background after launch
+    pub fetch_on_startup: bool,
+    /// ask for credentials the startup fetch needs but cannot
+    /// get from a credential helper (skipped otherwise)
+    pub fetch_on_startup_ask_credentials: bool,
+    /// ask before amending the last commit without editing its
+    /// message (always asked if it was pushed already)
+    pub confirm_amend_no_edit: bool,
+    /// time zone of commit times in the log and commit details at
+    /// startup, toggling it at runtime does not change the file
+    pub time_zone: Cell<TimeZoneDisplay>,
+    /// diffs with more lines are only shown on request
+    /// (0 always shows them)
+    pub max_diff_lines: usize,
+    /// old and new lines of diffs next to each other at startup,
+    /// toggling it at runtime does not change the file
+    pub diff_split: Cell<bool>,
+    /// color code in diffs by its language
+    pub diff_syntax: bool,
+    /// whitespace and context of diffs, changed at runtime
+    /// (not saved)
+    #[serde(skip)]
+    pub diff: Cell<DiffOptions>,
+    /// keep discarded changes (in `refs/gitui/discarded`) to undo
+    /// the last discard
+    pub discard_backup: bool,
+    /// the log of all branches `[^a]` includes remote branches
+    pub log_all_remotes: bool,
+    /// on narrow terminals the author and then the date are left
+    /// out to keep room for the message
+    pub log_columns: LogColumns,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            hide_advice: false,
+            commit_subject_length: 0,
+            commit_body_wrap: 72,
+            commit_verbose: Cell::new(false),
+            commit_details_prefetch: 5,
+            identities: Vec::new(),
+            fetch_on_startup: false,
+            fetch_on_startup_ask_credentials: false,
+            confirm_amend_no_edit: true,
+            time_zone: Cell::new(TimeZoneDisplay::Local),
+            max_diff_lines: 10_000,
+            diff_split: Cell::new(false),
+            diff_syntax: true,
+            diff: Cell::new(DiffOptions::default()),
+            discard_backup: true,
+            log_all_remotes: true,
+            log_columns: LogColumns::default(),
+        }
+    }
+}
+
+impl Options {
+    fn save(&self, file: PathBuf) -> Result<()> {
+        let mut file = File::create(file)?;
+        let data = to_string_pretty(self, PrettyConfig::default())?;
+        file.write_all(data.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn get_config_file() -> Result<PathBuf> {
+        let app_home = get_app_config_path()?;
+        Ok(app_home.join("options.ron"))
+    }
+
+    fn read_file(config_file: PathBuf) -> Result<Self> {
+        let mut f = File::open(config_file)?;
+        let mut buffer = Vec::new();
+        f.read_to_end(&mut buffer)?;
+        Ok(ron::de::from_bytes(&buffer)?)
+    }
+
+    pub fn init(file: PathBuf) -> Result<Self> {
+        if file.exists() {
+            match Self::read_file(file.clone()) {
+                Err(e) => {
+                    let config_path = file.clone();
+                    let config_path_old =
+                        format!("{}.old", file.to_string_lossy());
+                    fs::rename(
+                        config_path.clone(),
+                        config_path_old.clone(),
+                    )?;
+
+                    Self::default().save(file)?;
+
+                    Err(anyhow::anyhow!("{}\n Old file was renamed to {:?}.\n Defaults loaded and saved as {:?}",
+                        e,config_path_old,config_path.to_string_lossy()))
+                }
+                Ok(res) => Ok(res),
+            }
+        } else {
+            Self::default().save(file)?;
+            Ok(Self::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        IdentityOption, LogColumns, Options, TimeZoneDisplay,
+    };
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let options: Options = ron::de::from_str("()").unwrap();
+        assert!(!options.hide_advice);
+        assert_eq!(options.commit_subject_length, 0);
+        assert_eq!(options.commit_body_wrap, 72);
+        assert!(!options.commit_verbose.get());
+        assert_eq!(options.commit_details_prefetch, 5);
+        assert!(options.identities.is_empty());
+        assert!(!options.fetch_on_startup);
+        assert!(!options.fetch_on_startup_ask_credentials);
+        assert!(options.confirm_amend_no_edit);
+        assert_eq!(options.time_zone.get(), TimeZoneDisplay::Local);
+        assert_eq!(options.max_diff_lines, 10_000);
+        assert!(options.discard_backup);
+        assert!(options.log_all_remotes);
+        assert_eq!(options.log_columns, LogColumns::default());
+
+        let options: Options =
+            ron::de::from_str("(hide_advice: true)").unwrap();
+        assert!(options.hide_advice);
+
+        let options: Options =
+            ron::de::from_str("(time_zone: Commit)").unwrap();
+        assert_eq!(options.time_zone.get(), TimeZoneDisplay::Commit);
+
+        let options: Options = ron::de::from_str(
+            "(log_columns: (hash_length: 0, relative_date: true))",
+        )
+        .unwrap();
+        assert_eq!(
+            options.log_columns,
+            LogColumns {
+                hash_length: 0,
+                relative_date: true,
+                ..LogColumns::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_identities() {
+        let options: Options = ron::de::from_str(
+            "(identities: [(name: \"me\", email: \"me@home.org\")])",
+        )
+        .unwrap();
+        assert_eq!(
+            options.identities,
+            vec![IdentityOption {
+                name: String::from("me"),
+                email: String::from("me@home.org"),
+            }]
+        );
+    }
+}
@@ -1,5 +1,9 @@
 use crate::tabs::StashingOptions;
-use asyncgit::sync::{diff::DiffLinePosition, CommitId, CommitTags};
+use asyncgit::sync::{
+    diff::DiffLinePosition, CleanOptions, CommitId, CommitTags,
+    Identity, MergeStrategy, PullStrategy, PushTagsTarget, RepoState,
+    ResetKind,
+};
 use bitflags::bitflags;
 use std::{cell::RefCell, collections::VecDeque, rc::Rc};
 
@@ -26,12 +30,68 @@ pub struct ResetItem {
 ///
 pub enum Action {
     Reset(ResetItem),
+    /// paths of the marked files
+    ResetItems(Vec<String>),
     ResetHunk(String, u64),
-    ResetLines(String, Vec<DiffLinePosition>),
+    /// path, lines, hash of the diff the lines were selected in
+    ResetLines(String, Vec<DiffLinePosition>, u64),
     StashDrop(CommitId),
     DeleteBranch(String),
-    ForcePush(String, bool),
-    PullMerge { incoming: usize, rebase: bool },
+    /// references of the local branches, in the order listed
+    DeleteBranches(Vec<String>),
+    /// reference of the remote tracking branch to delete on the remote
+    DeleteRemoteBranch(String),
+    /// branch, commits of the remote branch (as of the last fetch)
+    /// the force push drops
+    ForcePush(String, usize),
+    PullMerge {
+        incoming: usize,
+        rebase: bool,
+    },
+    /// options and previewed paths
+    Clean(CleanOptions, Vec<String>),
+    /// `git commit --amend --no-edit`, `pushed` if the
+    /// commit is on the upstream already
+    AmendNoEdit {
+        pushed: bool,
+    },
+    ///
+    RevertCommit(CommitId),
+    /// `true` if the upstream has the commit already
+    DropCommit(CommitId, bool),
+    /// drop the merge, rebase, cherry-pick or revert stopped by
+    /// conflicts
+    AbortState(RepoState),
+    /// name of a linked worktree
+    RemoveWorktree(String),
+    /// name of the remote
+    RemoveRemote(String),
+    /// moves the current branch to the commit
+    ResetToCommit(CommitId, ResetKind),
+    /// brings the file back to its version in the commit
+    RestoreFile(CommitId, String),
+    /// `index.lock` left behind by a crashed git process
+    RemoveIndexLock,
+    /// unreadable index, replaced by the tree of HEAD
+    RebuildIndex,
+    /// name of a local tag
+    DeleteTag(String),
+    /// name of a tag to delete on the default remote
+    DeleteRemoteTag(String),
+    /// paths of the last discard that was backed up
+    UndoDiscard(Vec<String>),
+}
+
+/// hints for situations newcomers might not be familiar with
+pub enum Advice {
+    ///
+    DetachedHead,
+    /// branch, remote
+    PushSetUpstream(String, String),
+    ///
+    PartialClone,
+    /// commits fall back to this identity of the global config
+    GlobalIdentity(Identity),
 }
 
 ///
@@ -43,6 +103,8 @@ pub enum InternalEvent {
     ///
     ShowErrorMsg(String),
     ///
+    ShowInfoMsg(String),
+    ///
     Update(NeedsUpdate),
     /// open commit msg input
     OpenCommit,
@@ -56,18 +118,126 @@ pub enum InternalEvent {
     TagCommit(CommitId),
     ///
     CreateBranch,
+    /// branch at the commit, not checked out
+    CreateBranchAt(CommitId),
     ///
     RenameBranch(String, String),
+    /// name of the local branch
+    EditBranchDescription(String),
+    ///
+    RenameStash(CommitId, String),
+    ///
+    StashBranch(CommitId),
     ///
     SelectBranch,
     ///
     OpenExternalEditor(Option<String>),
+    /// path, hash of the unstaged hunk to edit and stage
+    EditHunk(String, u64),
     ///
     Push(String, bool),
+    /// branch, the configured strategy if `None`
+    Pull(String, Option<PullStrategy>),
+    /// branch, picks the strategy first
+    OpenPullStrategy(String),
+    /// whitespace and context of diffs
+    OpenDiffOptions,
+    /// path, is a folder
+    OpenIgnore(String, bool),
+    /// root `.gitignore`
+    OpenEditIgnore,
+    /// all remotes, pruned
+    FetchAll,
+    ///
+    PushTags(PushTagsTarget),
+    /// branch, picks the remote and the branch on it first
+    OpenPushTarget(String),
+    /// branch, remote, branch on the remote, set it as upstream
+    PushTo(String, String, String, bool),
+    /// open raw object viewer for given revision
+    ViewObject(String),
+    /// path, whether it is the staged patch
+    ViewPatch(String, bool),
+    /// path, as of the commit (`HEAD` if `None`)
+    OpenBlame(String, Option<CommitId>),
+    /// path
+    OpenFileHistory(String),
+    /// from, to
+    CompareCommits(CommitId, CommitId),
+    ///
+    OpenSubmodules,
+    /// workdir of the submodule to run gitui in
+    OpenSubmodule(String),
+    ///
+    OpenWorktrees,
+    ///
+    OpenRemotes,
+    ///
+    CreateWorktree,
+    /// path of the worktree gitui reopens in
+    SwitchWorktree(String),
+    ///
+    OpenReflog,
+    ///
+    OpenTags,
+    /// selected commit, taken as the good one when starting
+    OpenBisect(CommitId),
+    /// path of a conflicted file
+    OpenConflict(String),
+    /// path of a conflicted file to run `git mergetool` on
+    RunMergetool(String),
+    /// shown once per session unless disabled in options
+    ShowAdvice(Advice),
+    ///
+    SearchBranches,
+    ///
+    OpenClean,
+    /// picks soft, mixed or hard before confirming the reset
+    OpenResetCommit(CommitId),
+    /// all files of the commit, not just the changed ones
+    OpenRevisionFiles(CommitId),
+    /// asks where to write the patch files to
+    ExportPatches(Vec<CommitId>),
+    /// asks for the patch file
+    OpenApplyPatch,
+    /// asks where to write the archive of a commit to, suggesting
+    /// the name
+    OpenArchive(CommitId, String),
+    /// review what pushing this branch would publish
+    CompareUpstream(String),
     ///
-    Pull(String),
+    SelectIdentity,
+    /// picks a recent author for a `Co-authored-by` trailer
+    SelectCoAuthor,
+    /// appends the `Co-authored-by` trailer to the commit message
+    AddCoAuthor(String),
     ///
-    PushTags,
+    PickHunks,
+    /// interactive rebase onto the given commit (or the state of
+    /// the one in progress)
+    OpenRebase(CommitId),
+    /// onto HEAD (oldest first), conflicts are resolved in the status
+    /// tab
+    CherryPick(Vec<CommitId>),
+    /// edits the message of the commit
+    RewordCommit(CommitId),
+    /// opens the rebase popup to squash them into the oldest one
+    SquashCommits(Vec<CommitId>),
+    /// staged changes as `fixup!` (or `squash!` if true) commit of
+    /// the target
+    CommitFixup(CommitId, bool),
+    /// rebase popup folding in the `fixup!`/`squash!` commits
+    OpenAutosquash,
+    /// branch ref, paths with conflicting local changes
+    CheckoutConflict(String, Vec<String>),
+    /// branch ref and name, picks the strategy first
+    OpenMergeBranch(String, String),
+    /// branch ref and name, conflicts are resolved in the status tab
+    MergeBranch(String, String, MergeStrategy),
+    /// local branch name, picks the remote branch to track
+    OpenBranchUpstream(String),
+    /// local branch name and the remote tracking branch (`None` unsets)
+    SetUpstream(String, Option<String>),
 }
 
 ///
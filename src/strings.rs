@@ -1,4 +1,13 @@
-use crate::keys::SharedKeyConfig;
+use crate::{
+    components::PendingOperation, keys::SharedKeyConfig,
+    options::TimeZoneDisplay,
+};
+use asyncgit::sync::{
+    is_stopped_state, BisectStep, DiffOptions, Identity, MergeResult,
+    MergeStrategy, PullStrategy, RepoState, ResetKind,
+    SubmoduleStatus, UpstreamDiff, WorktreeInfo,
+};
+use bytesize::ByteSize;
 
 pub mod order {
     pub static NAV: i8 = 1;
@@ -6,24 +15,33 @@ pub mod order {
 
 pub static PUSH_POPUP_MSG: &str = "Push";
 pub static FORCE_PUSH_POPUP_MSG: &str = "Force Push";
+pub static DELETE_REMOTE_BRANCH_POPUP_MSG: &str =
+    "Delete Remote Branch";
 pub static PULL_POPUP_MSG: &str = "Pull";
+pub static FETCH_ALL_POPUP_MSG: &str = "Fetch All";
+pub static ARCHIVE_POPUP_MSG: &str = "Archive";
 pub static PUSH_POPUP_PROGRESS_NONE: &str = "preparing...";
-pub static PUSH_POPUP_STATES_ADDING: &str = "adding objects (1/3)";
-pub static PUSH_POPUP_STATES_DELTAS: &str = "deltas (2/3)";
-pub static PUSH_POPUP_STATES_PUSHING: &str = "pushing (3/3)";
+pub static PUSH_POPUP_STATES_ADDING: &str =
+    "enumerating objects (1/3)";
+pub static PUSH_POPUP_STATES_DELTAS: &str = "compressing (2/3)";
+pub static PUSH_POPUP_STATES_PUSHING: &str = "writing (3/3)";
 pub static PUSH_POPUP_STATES_TRANSFER: &str = "transfer";
 pub static PUSH_POPUP_STATES_DONE: &str = "done";
+pub fn push_popup_states_remote(phase: &str) -> String {
+    format!("remote: {}", phase.to_lowercase())
+}
 
 pub static PUSH_TAGS_POPUP_MSG: &str = "Push Tags";
 pub static PUSH_TAGS_STATES_FETCHING: &str = "fetching";
 pub static PUSH_TAGS_STATES_PUSHING: &str = "pushing";
 pub static PUSH_TAGS_STATES_DONE: &str = "done";
 
+/// tab of the branch list
 pub fn title_branches(local: bool) -> String {
     if local {
-        "Branches (local)"
+        "Local Branches"
     } else {
-        "Branches (remote)"
+        "Remote Branches"
     }
     .to_string()
 }
@@ -34,9 +52,15 @@ pub fn title_status(_key_config: &SharedKeyConfig) -> String {
 pub fn title_diff(_key_config: &SharedKeyConfig) -> String {
     "Diff: ".to_string()
 }
+pub fn title_diff_visual(_key_config: &SharedKeyConfig) -> String {
+    " (visual)".to_string()
+}
 pub fn title_index(_key_config: &SharedKeyConfig) -> String {
     "Staged Changes".to_string()
 }
+pub fn title_conflicts(_key_config: &SharedKeyConfig) -> String {
+    "Conflicts".to_string()
+}
 pub fn tab_status(key_config: &SharedKeyConfig) -> String {
     format!("Status [{}]", key_config.get_hint(key_config.tab_status))
 }
@@ -67,21 +91,269 @@ pub fn msg_opening_editor(_key_config: &SharedKeyConfig) -> String {
 pub fn msg_title_error(_key_config: &SharedKeyConfig) -> String {
     "Error".to_string()
 }
+pub fn msg_title_info(_key_config: &SharedKeyConfig) -> String {
+    "Info".to_string()
+}
+pub fn msg_title_advice(_key_config: &SharedKeyConfig) -> String {
+    "Advice".to_string()
+}
+pub fn advice_detached_head(key_config: &SharedKeyConfig) -> String {
+    format!(
+        "You are in 'detached HEAD' state: HEAD points to a commit \
+        instead of a branch.\n\n\
+        You can look around and make experimental commits, but they \
+        do not belong to any branch and are easily lost when switching \
+        away. To keep them, create a new branch from here: [{}] lists \
+        the branches, [{}] creates one at HEAD and checks it out.\n\n\
+        (set `hide_advice: true` in options.ron to disable advice)",
+        key_config.get_hint(key_config.select_branch),
+        key_config.get_hint(key_config.create_branch),
+    )
+}
+pub fn detached_head_label(
+    _key_config: &SharedKeyConfig,
+    id: &str,
+) -> String {
+    format!("HEAD detached at {}", id)
+}
+pub fn advice_push_set_upstream(
+    _key_config: &SharedKeyConfig,
+    branch: &str,
+    remote: &str,
+) -> String {
+    format!(
+        "Branch '{}' had no upstream and was pushed to remote \
+        '{}'.\n\n\
+        It now tracks '{}/{}', future pushes and pulls will use it.\n\n\
+        (set `hide_advice: true` in options.ron to disable advice)",
+        branch, remote, remote, branch
+    )
+}
+pub fn advice_partial_clone(_key_config: &SharedKeyConfig) -> String {
+    "This repository is a partial clone (`git clone --filter`).\n\n\
+    gitui cannot download missing objects on demand: diffs or \
+    files whose blobs were filtered out will fail to load. Use \
+    `git fetch` or `git checkout` on the command line to fetch \
+    them.\n\n\
+    (set `hide_advice: true` in options.ron to disable advice)"
+        .to_string()
+}
+pub fn advice_global_identity(
+    key_config: &SharedKeyConfig,
+    global: &Identity,
+) -> String {
+    format!(
+        "This repository has no identity of its own, commits are \
+        made as {} from the global config.\n\n\
+        Press [{}] in the status tab to pick one of your identities.\n\n\
+        (set `hide_advice: true` in options.ron to disable advice)",
+        identity(key_config, global),
+        key_config.get_hint(key_config.select_identity),
+    )
+}
+pub fn checkout_conflict_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Checkout Conflicts".to_string()
+}
+pub fn checkout_conflict_msg(
+    _key_config: &SharedKeyConfig,
+    branch_ref: &str,
+) -> String {
+    format!(
+        "Checking out '{}' would overwrite local changes in:",
+        branch_ref.trim_start_matches("refs/heads/")
+    )
+}
+pub fn clean_title(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+    directories: bool,
+    ignored: bool,
+) -> String {
+    format!(
+        "Clean: {} to remove{}{}",
+        count,
+        if directories { " [+dirs]" } else { "" },
+        if ignored { " [+ignored]" } else { "" },
+    )
+}
+pub fn compare_upstream_title(
+    _key_config: &SharedKeyConfig,
+    diff: &UpstreamDiff,
+) -> String {
+    format!(
+        "{} commits ahead of {}: {} files, +{} -{}",
+        diff.commits,
+        diff.upstream,
+        diff.files.len(),
+        diff.insertions,
+        diff.deletions,
+    )
+}
+pub fn compare_upstream_merged(
+    _key_config: &SharedKeyConfig,
+    branch: &str,
+    upstream: &str,
+) -> String {
+    format!(
+        "nothing to push: '{}' is fully merged into '{}'",
+        branch, upstream
+    )
+}
+pub fn startup_fetch_running(
+    _key_config: &SharedKeyConfig,
+    remote: &str,
+) -> String {
+    format!("fetching {}..", remote)
+}
+pub fn startup_fetch_done(
+    _key_config: &SharedKeyConfig,
+    remote: &str,
+    bytes: usize,
+) -> String {
+    format!("fetched {} ({})", remote, ByteSize::b(bytes as u64))
+}
+pub fn startup_fetch_failed(
+    _key_config: &SharedKeyConfig,
+    remote: &str,
+) -> String {
+    format!("fetching {} failed", remote)
+}
+pub fn status_bar_detached(_key_config: &SharedKeyConfig) -> String {
+    "detached HEAD".to_string()
+}
+pub fn status_bar_repo_state(
+    _key_config: &SharedKeyConfig,
+    state: RepoState,
+) -> String {
+    format!("{:?} in progress", state)
+}
+pub fn status_bar_pending(
+    _key_config: &SharedKeyConfig,
+    pending: &[PendingOperation],
+) -> String {
+    let names: Vec<&str> = pending
+        .iter()
+        .map(|op| match op {
+            PendingOperation::Push => "pushing",
+            PendingOperation::PushTags => "pushing tags",
+            PendingOperation::Pull => "pulling",
+            PendingOperation::Fetch => "fetching",
+            PendingOperation::Archive => "archiving",
+            PendingOperation::Rebase => "rebasing",
+        })
+        .collect();
+    format!("{}..", names.join(", "))
+}
+pub fn identity(
+    _key_config: &SharedKeyConfig,
+    identity: &Identity,
+) -> String {
+    format!(
+        "{} <{}>",
+        identity.name.as_deref().unwrap_or("?"),
+        identity.email.as_deref().unwrap_or("?"),
+    )
+}
+pub fn identity_title(_key_config: &SharedKeyConfig) -> String {
+    "Identity".to_string()
+}
+pub fn identity_current(
+    key_config: &SharedKeyConfig,
+    current: &Identity,
+) -> String {
+    format!("current: {}", identity(key_config, current))
+}
+pub fn identity_not_local(_key_config: &SharedKeyConfig) -> String {
+    "not set in this repo, commits use the global identity"
+        .to_string()
+}
+pub fn identity_none_configured(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "no identities to switch between.\n\n\
+    add them to options.ron like:\n\
+    identities: [(name: \"Me\", email: \"me@home.org\")]"
+        .to_string()
+}
+pub fn clean_nothing(_key_config: &SharedKeyConfig) -> String {
+    "nothing to clean".to_string()
+}
 pub fn commit_title(_key_config: &SharedKeyConfig) -> String {
     "Commit".to_string()
 }
 pub fn commit_title_amend(_key_config: &SharedKeyConfig) -> String {
     "Commit (Amend)".to_string()
 }
+pub fn commit_title_amend_advanced(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Commit (Amend Author)".to_string()
+}
+pub fn commit_author_title(_key_config: &SharedKeyConfig) -> String {
+    "Author".to_string()
+}
+pub fn commit_author_msg(_key_config: &SharedKeyConfig) -> String {
+    "Name <email>".to_string()
+}
+pub fn commit_author_date_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Author Date".to_string()
+}
+pub fn commit_author_date_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "YYYY-MM-DD HH:MM:SS +ZZZZ".to_string()
+}
+/// shown next to the branch name while `commit.gpgsign` is set
+pub fn commit_signed(_key_config: &SharedKeyConfig) -> String {
+    "signed".to_string()
+}
 pub fn commit_msg(_key_config: &SharedKeyConfig) -> String {
     "type commit message..".to_string()
 }
+/// shown next to the branch name while a `Signed-off-by` trailer
+/// gets added
+pub fn commit_signoff(_key_config: &SharedKeyConfig) -> String {
+    "sign-off".to_string()
+}
+pub fn co_author_title(_key_config: &SharedKeyConfig) -> String {
+    "Co-authored-by".to_string()
+}
+pub fn co_author_none(
+    _key_config: &SharedKeyConfig,
+    commits: usize,
+) -> String {
+    format!("no other authors in the last {} commits", commits)
+}
+pub fn commit_diff_title(_key_config: &SharedKeyConfig) -> String {
+    "Staged Changes".to_string()
+}
+pub fn commit_diff_empty(_key_config: &SharedKeyConfig) -> String {
+    "nothing staged".to_string()
+}
+/// shown while the second line of a commit message is not empty
+pub fn commit_body_separation(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "blank line after subject missing".to_string()
+}
 pub fn commit_editor_msg(_key_config: &SharedKeyConfig) -> String {
     r##"
 # Edit your commit message
 # Lines starting with '#' will be ignored"##
         .to_string()
 }
+pub fn hunk_editor_msg(_key_config: &SharedKeyConfig) -> String {
+    r"
+# Edit the hunk to stage: remove '-' lines by turning them into
+# context (' ') lines, remove '+' lines by deleting them
+# Lines starting with '#' will be ignored
+# An empty hunk stages nothing"
+        .to_string()
+}
 pub fn stash_popup_title(_key_config: &SharedKeyConfig) -> String {
     "Stash".to_string()
 }
@@ -120,6 +392,12 @@ pub fn confirm_msg_merge(
 pub fn confirm_msg_reset(_key_config: &SharedKeyConfig) -> String {
     "confirm file reset?".to_string()
 }
+pub fn confirm_msg_reset_items(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+) -> String {
+    format!("confirm reset of {} marked files?", count)
+}
 pub fn confirm_msg_reset_lines(
     _key_config: &SharedKeyConfig,
     lines: usize,
@@ -150,811 +428,3873 @@ pub fn confirm_msg_delete_branch(
 ) -> String {
     format!("Confirm deleting branch: '{}' ?", branch_ref)
 }
-pub fn confirm_title_force_push(
+pub fn msg_no_merged_branches(
     _key_config: &SharedKeyConfig,
+    branch: &str,
 ) -> String {
-    "Force Push".to_string()
+    format!("No other local branches are merged into '{}'", branch)
 }
-pub fn confirm_msg_force_push(
+pub fn confirm_title_delete_branches(
     _key_config: &SharedKeyConfig,
-    branch_ref: &str,
+) -> String {
+    "Delete Branches".to_string()
+}
+pub fn confirm_msg_delete_branches(
+    _key_config: &SharedKeyConfig,
+    branch_refs: &[String],
 ) -> String {
     format!(
-        "Confirm force push to branch '{}' ?  This may rewrite history.",
-        branch_ref
+        "Confirm deleting {} {}:\n{}",
+        branch_refs.len(),
+        if branch_refs.len() == 1 {
+            "branch"
+        } else {
+            "branches"
+        },
+        branch_refs
+            .iter()
+            .map(|r| r.trim_start_matches("refs/heads/"))
+            .collect::<Vec<_>>()
+            .join("\n")
     )
 }
-pub fn log_title(_key_config: &SharedKeyConfig) -> String {
-    "Commit".to_string()
-}
-pub fn tag_commit_popup_title(
+pub fn confirm_title_delete_remote_branch(
     _key_config: &SharedKeyConfig,
 ) -> String {
-    "Tag".to_string()
+    "Delete Remote Branch".to_string()
 }
-pub fn tag_commit_popup_msg(_key_config: &SharedKeyConfig) -> String {
-    "type tag".to_string()
+pub fn confirm_msg_delete_remote_branch(
+    _key_config: &SharedKeyConfig,
+    branch: &str,
+) -> String {
+    format!("Confirm deleting branch '{}' on its remote?", branch)
 }
-pub fn stashlist_title(_key_config: &SharedKeyConfig) -> String {
-    "Stashes".to_string()
+pub fn confirm_title_undo_discard(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Undo Discard".to_string()
 }
-pub fn help_title(_key_config: &SharedKeyConfig) -> String {
-    "Help: all commands".to_string()
+pub fn confirm_msg_undo_discard(
+    _key_config: &SharedKeyConfig,
+    paths: &[String],
+) -> String {
+    let names = if paths.len() > 3 {
+        format!("{}, ...", paths[..3].join(", "))
+    } else {
+        paths.join(", ")
+    };
+    format!(
+        "Restore the discarded changes of {} ({})? Their current content is overwritten.",
+        if paths.len() == 1 {
+            "1 file".to_string()
+        } else {
+            format!("{} files", paths.len())
+        },
+        names
+    )
 }
-pub fn stashing_files_title(_key_config: &SharedKeyConfig) -> String {
-    "Files to Stash".to_string()
+pub fn confirm_title_clean(_key_config: &SharedKeyConfig) -> String {
+    "Clean".to_string()
 }
-pub fn stashing_options_title(
+pub fn confirm_msg_clean(
     _key_config: &SharedKeyConfig,
+    count: usize,
 ) -> String {
-    "Options".to_string()
+    format!(
+        "Confirm removing {} untracked {}? This cannot be undone.",
+        count,
+        if count == 1 { "entry" } else { "entries" }
+    )
 }
-pub fn loading_text(_key_config: &SharedKeyConfig) -> String {
-    "Loading ...".to_string()
+pub fn confirm_title_amend_no_edit(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Amend".to_string()
 }
-pub fn create_branch_popup_title(
+pub fn confirm_msg_amend_no_edit(
     _key_config: &SharedKeyConfig,
+    pushed: bool,
 ) -> String {
-    "Branch".to_string()
+    if pushed {
+        "The last commit was pushed already, amending it requires a force push. Amend it with the staged changes anyway (keeping its message)?".to_string()
+    } else {
+        "Amend the last commit with the staged changes (keeping its message)?".to_string()
+    }
 }
-pub fn create_branch_popup_msg(
+pub fn confirm_title_index_lock(
     _key_config: &SharedKeyConfig,
 ) -> String {
-    "type branch name".to_string()
+    "Stale Lock".to_string()
 }
-pub fn username_popup_title(_key_config: &SharedKeyConfig) -> String {
-    "Username".to_string()
+pub fn confirm_msg_index_lock(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "A leftover '.git/index.lock' (probably from a crashed git process) blocks changes to the index. Remove it? It is kept if a git process is running.".to_string()
 }
-pub fn username_popup_msg(_key_config: &SharedKeyConfig) -> String {
-    "type username".to_string()
+pub fn confirm_title_rebuild_index(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Corrupt Index".to_string()
 }
-pub fn password_popup_title(_key_config: &SharedKeyConfig) -> String {
-    "Password".to_string()
+pub fn confirm_msg_rebuild_index(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "The index ('.git/index') can not be read. Rebuild it from HEAD? The working directory is not touched but staged changes are lost.".to_string()
 }
-pub fn password_popup_msg(_key_config: &SharedKeyConfig) -> String {
-    "type password".to_string()
+pub fn confirm_title_revert_commit(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Revert Commit".to_string()
 }
-
-pub fn rename_branch_popup_title(
+pub fn confirm_msg_revert_commit(
     _key_config: &SharedKeyConfig,
+    id: &str,
 ) -> String {
-    "Rename Branch".to_string()
+    format!("Commit a revert of {} on top of the current branch?", id)
 }
-pub fn rename_branch_popup_msg(
+pub fn confirm_title_drop_commit(
     _key_config: &SharedKeyConfig,
 ) -> String {
-    "new branch name".to_string()
+    "Drop Commit".to_string()
 }
-
-pub mod commit {
-    use crate::keys::SharedKeyConfig;
-    pub fn details_author(_key_config: &SharedKeyConfig) -> String {
-        "Author: ".to_string()
-    }
-    pub fn details_committer(
-        _key_config: &SharedKeyConfig,
-    ) -> String {
-        "Committer: ".to_string()
-    }
-    pub fn details_sha(_key_config: &SharedKeyConfig) -> String {
-        "Sha: ".to_string()
+pub fn confirm_msg_drop_commit(
+    _key_config: &SharedKeyConfig,
+    id: &str,
+    pushed: bool,
+) -> String {
+    if pushed {
+        format!("Commit {} was pushed already, dropping it requires a force push. Remove it from the history of the current branch anyway?", id)
+    } else {
+        format!(
+            "Remove commit {} from the history of the current branch?",
+            id
+        )
     }
-    pub fn details_date(_key_config: &SharedKeyConfig) -> String {
-        "Date: ".to_string()
+}
+pub fn confirm_title_restore_file(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Restore File".to_string()
+}
+pub fn confirm_msg_restore_file(
+    _key_config: &SharedKeyConfig,
+    id: &str,
+    path: &str,
+) -> String {
+    format!(
+        "Restore '{}' to its version in commit {}? Its current changes are lost.",
+        path, id
+    )
+}
+/// lowercase name of an operation that can stop on conflicts
+const fn stopped_state_name(state: RepoState) -> &'static str {
+    match state {
+        RepoState::Merge => "merge",
+        RepoState::Rebase => "rebase",
+        RepoState::CherryPick => "cherry-pick",
+        RepoState::Revert => "revert",
+        _ => "operation",
     }
-    pub fn details_tags(_key_config: &SharedKeyConfig) -> String {
-        "Tags: ".to_string()
+}
+pub fn confirm_title_abort_state(
+    _key_config: &SharedKeyConfig,
+    state: RepoState,
+) -> String {
+    format!("Abort {:?}", state)
+}
+pub fn confirm_msg_abort_state(
+    _key_config: &SharedKeyConfig,
+    state: RepoState,
+) -> String {
+    format!(
+        "Drop the stopped {}? Its changes and any resolved conflicts are lost.",
+        stopped_state_name(state)
+    )
+}
+/// shown in the status tab while the repo is not clean
+pub fn repo_state_hint(
+    key_config: &SharedKeyConfig,
+    state: RepoState,
+) -> String {
+    if is_stopped_state(state) {
+        format!(
+            "{:?}: resolve and stage, then continue [{}] or abort [{}]",
+            state,
+            key_config.get_hint(key_config.cherry_pick),
+            key_config.get_hint(key_config.cherry_pick_abort),
+        )
+    } else {
+        format!("{:?}", state)
     }
-    pub fn details_info_title(
-        _key_config: &SharedKeyConfig,
-    ) -> String {
-        "Info".to_string()
+}
+pub fn confirm_title_remove_worktree(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Remove Worktree".to_string()
+}
+pub fn confirm_msg_remove_worktree(
+    _key_config: &SharedKeyConfig,
+    name: &str,
+) -> String {
+    format!(
+        "Remove worktree '{}' and delete its directory? Its branch is kept.\nNot possible with uncommitted changes or untracked files in it.",
+        name
+    )
+}
+pub fn confirm_title_remove_remote(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Remove Remote".to_string()
+}
+pub fn confirm_msg_remove_remote(
+    _key_config: &SharedKeyConfig,
+    name: &str,
+) -> String {
+    format!(
+        "Remove remote '{}' along with its remote branches?",
+        name
+    )
+}
+pub fn confirm_title_reset_commit(
+    _key_config: &SharedKeyConfig,
+    kind: ResetKind,
+) -> String {
+    match kind {
+        ResetKind::Soft => "Reset Soft",
+        ResetKind::Mixed => "Reset Mixed",
+        ResetKind::Hard => "Reset Hard",
     }
-    pub fn details_message_title(
-        _key_config: &SharedKeyConfig,
-    ) -> String {
-        "Message".to_string()
+    .to_string()
+}
+pub fn confirm_msg_reset_commit(
+    _key_config: &SharedKeyConfig,
+    id: &str,
+    kind: ResetKind,
+) -> String {
+    match kind {
+        ResetKind::Soft => format!("Reset the current branch to {}? The changes of the commits after it stay staged, the reset itself can be undone from the reflog.", id),
+        ResetKind::Mixed => format!("Reset the current branch to {}? The changes of the commits after it and the staged ones are kept unstaged, the reset itself can be undone from the reflog.", id),
+        ResetKind::Hard => format!("Reset the current branch to {}? Uncommitted changes are lost, the reset itself can be undone from the reflog.", id),
     }
-    pub fn details_files_title(
-        _key_config: &SharedKeyConfig,
-    ) -> String {
-        "Files:".to_string()
+}
+pub fn reset_commit_title(
+    _key_config: &SharedKeyConfig,
+    id: &str,
+) -> String {
+    format!("Reset to {}", id)
+}
+pub fn reset_kind_entry(
+    _key_config: &SharedKeyConfig,
+    kind: ResetKind,
+) -> String {
+    match kind {
+        ResetKind::Soft => "soft: keep index and workdir",
+        ResetKind::Mixed => "mixed: keep workdir, reset index",
+        ResetKind::Hard => "hard: discard all changes",
     }
+    .to_string()
+}
+pub fn revert_conflict_msg(key_config: &SharedKeyConfig) -> String {
+    format!(
+        "The revert stopped on conflicts. Resolve and stage them, then continue with [{}] (or abort with [{}]).",
+        key_config.get_hint(key_config.cherry_pick),
+        key_config.get_hint(key_config.cherry_pick_abort),
+    )
+}
+pub fn cherry_pick_conflict_msg(
+    key_config: &SharedKeyConfig,
+    not_picked: usize,
+) -> String {
+    let rest = if not_picked > 0 {
+        format!(" {} more commit(s) were not picked.", not_picked)
+    } else {
+        String::new()
+    };
+    format!(
+        "The cherry-pick stopped on conflicts. Resolve and stage them, then continue with [{}] (or abort with [{}]).{}",
+        key_config.get_hint(key_config.cherry_pick),
+        key_config.get_hint(key_config.cherry_pick_abort),
+        rest,
+    )
+}
+pub fn index_lock_kept(_key_config: &SharedKeyConfig) -> String {
+    "'.git/index.lock' was kept: a git process is running (or running processes could not be listed) or the lock is in use again.".to_string()
+}
+pub fn confirm_title_force_push(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Force Push".to_string()
+}
+pub fn confirm_msg_force_push(
+    _key_config: &SharedKeyConfig,
+    branch_ref: &str,
+    overwritten: usize,
+) -> String {
+    format!(
+        "Confirm force push to branch '{}' ?  This overwrites {} commit(s) on the remote (as of the last fetch). It is refused if the remote branch moved since, but this best-effort lease is checked right before pushing and a push landing in between is still overwritten.",
+        branch_ref, overwritten
+    )
+}
+pub fn log_title(_key_config: &SharedKeyConfig) -> String {
+    "Commit".to_string()
+}
+pub fn log_title_all_branches(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    " [all branches]".to_string()
+}
+pub fn log_title_no_merges(_key_config: &SharedKeyConfig) -> String {
+    " [no merges]".to_string()
+}
+pub fn log_title_first_parent(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    " [first parent]".to_string()
+}
+pub fn log_title_search(
+    _key_config: &SharedKeyConfig,
+    query: &str,
+    position: Option<(usize, usize)>,
+) -> String {
+    match position {
+        Some((current, total)) => {
+            format!(" [search '{}': {}/{}]", query, current, total)
+        }
+        None => format!(" [searching '{}'...]", query),
+    }
+}
+pub fn log_title_filter(
+    _key_config: &SharedKeyConfig,
+    query: &str,
+) -> String {
+    format!(" [filter '{}']", query)
+}
+pub fn compare_commits_title(
+    _key_config: &SharedKeyConfig,
+    from: &str,
+    to: &str,
+) -> String {
+    format!("{}..{}", from, to)
+}
+pub fn log_search_title(_key_config: &SharedKeyConfig) -> String {
+    "Search Log".to_string()
+}
+pub fn log_search_msg(_key_config: &SharedKeyConfig) -> String {
+    "text, or prefixed msg: author: sha: path:".to_string()
+}
+pub fn log_goto_title(_key_config: &SharedKeyConfig) -> String {
+    "Go to Commit".to_string()
+}
+pub fn log_goto_msg(_key_config: &SharedKeyConfig) -> String {
+    "hash, branch, tag or revision like HEAD~3".to_string()
+}
+pub fn log_goto_not_found(
+    _key_config: &SharedKeyConfig,
+    id: &str,
+) -> String {
+    format!("commit {} is not part of the log", id)
+}
+pub fn log_title_time_zone(
+    _key_config: &SharedKeyConfig,
+    zone: TimeZoneDisplay,
+) -> String {
+    match zone {
+        TimeZoneDisplay::Local => String::new(),
+        TimeZoneDisplay::Utc => " [UTC]".to_string(),
+        TimeZoneDisplay::Commit => " [commit time zones]".to_string(),
+    }
+}
+pub fn rebase_title(
+    _key_config: &SharedKeyConfig,
+    base: &str,
+    count: usize,
+) -> String {
+    format!("Rebase {} commits onto {}", count, base)
+}
+pub fn rebase_stopped_title(
+    _key_config: &SharedKeyConfig,
+    done: usize,
+    total: usize,
+) -> String {
+    format!("Rebase stopped ({}/{} done)", done, total)
+}
+pub fn rebase_conflict_msg(key_config: &SharedKeyConfig) -> String {
+    format!(
+        "resolve the conflicts and stage them in the status tab, then continue with [{}] (reopen with [{}] in the log)",
+        key_config.get_hint(key_config.enter),
+        key_config.get_hint(key_config.rebase_interactive),
+    )
+}
+pub fn rebase_reword_title(_key_config: &SharedKeyConfig) -> String {
+    "Reword".to_string()
+}
+pub fn rebase_reword_msg(_key_config: &SharedKeyConfig) -> String {
+    "new commit message".to_string()
+}
+pub fn reword_popup_title(_key_config: &SharedKeyConfig) -> String {
+    "Reword Commit".to_string()
+}
+pub fn reword_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    "new commit message".to_string()
+}
+pub fn rebase_nothing_msg(_key_config: &SharedKeyConfig) -> String {
+    "nothing to rebase: select a commit below HEAD as the base"
+        .to_string()
+}
+pub fn tag_commit_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Tag".to_string()
+}
+pub fn tag_commit_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    "type tag".to_string()
+}
+pub fn tag_commit_message_title(
+    _key_config: &SharedKeyConfig,
+    tag: &str,
+    sign: bool,
+) -> String {
+    if sign {
+        format!("Tag Message: {} (signed)", tag)
+    } else {
+        format!("Tag Message: {}", tag)
+    }
+}
+pub fn tag_commit_message_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "type message (empty for a lightweight tag)".to_string()
+}
+pub fn stashlist_title(_key_config: &SharedKeyConfig) -> String {
+    "Stashes".to_string()
+}
+pub fn help_title(_key_config: &SharedKeyConfig) -> String {
+    "Help: all commands".to_string()
+}
+pub fn stashing_files_title(_key_config: &SharedKeyConfig) -> String {
+    "Files to Stash".to_string()
+}
+pub fn stashing_options_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Options".to_string()
+}
+pub fn loading_text(_key_config: &SharedKeyConfig) -> String {
+    "Loading ...".to_string()
+}
+pub fn loading_folder_text(
+    _key_config: &SharedKeyConfig,
+    done: usize,
+    total: usize,
+) -> String {
+    format!("Loading ... ({}/{} files)", done, total)
+}
+pub fn diff_suppressed_text(
+    key_config: &SharedKeyConfig,
+    lines: usize,
+) -> String {
+    format!(
+        "large diff suppressed ({} lines) - press [{}] to load",
+        lines,
+        key_config.get_hint(key_config.diff_load_large),
+    )
+}
+pub fn create_branch_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Branch".to_string()
+}
+pub fn create_branch_at_popup_title(
+    _key_config: &SharedKeyConfig,
+    id: &str,
+) -> String {
+    format!("Branch at {}", id)
+}
+pub fn create_branch_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "type branch name".to_string()
+}
+pub fn username_popup_title(_key_config: &SharedKeyConfig) -> String {
+    "Username".to_string()
+}
+pub fn username_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    "type username".to_string()
+}
+pub fn password_popup_title(_key_config: &SharedKeyConfig) -> String {
+    "Password".to_string()
+}
+pub fn password_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    "type password".to_string()
+}
+pub fn passphrase_popup_title(
+    _key_config: &SharedKeyConfig,
+    user: &str,
+) -> String {
+    format!("SSH Key Passphrase ({})", user)
+}
+pub fn passphrase_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    "type passphrase of the ssh key".to_string()
 }
 
-pub mod commands {
-    use crate::components::CommandText;
-    use crate::keys::SharedKeyConfig;
-
-    static CMD_GROUP_GENERAL: &str = "-- General --";
-    static CMD_GROUP_DIFF: &str = "-- Diff --";
-    static CMD_GROUP_CHANGES: &str = "-- Changes --";
-    static CMD_GROUP_COMMIT: &str = "-- Commit --";
-    static CMD_GROUP_STASHING: &str = "-- Stashing --";
-    static CMD_GROUP_STASHES: &str = "-- Stashes --";
-    static CMD_GROUP_LOG: &str = "-- Log --";
+pub fn rename_branch_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Rename Branch".to_string()
+}
+pub fn rename_branch_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "new branch name".to_string()
+}
+pub fn branch_description_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Branch Description".to_string()
+}
+pub fn branch_description_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "what the branch is about (empty removes it)".to_string()
+}
+pub fn branch_description_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Description".to_string()
+}
+pub fn rename_stash_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Rename Stash".to_string()
+}
+pub fn rename_stash_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "new stash message".to_string()
+}
+pub fn stash_branch_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Stash Branch".to_string()
+}
+pub fn stash_branch_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "branch to pop the stash on".to_string()
+}
+pub fn branch_search_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Search All Branches".to_string()
+}
+pub fn branch_search_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "message, author or hash".to_string()
+}
+pub fn branch_search_title_pending(
+    _key_config: &SharedKeyConfig,
+    scanned: usize,
+) -> String {
+    format!("Searching... ({} commits scanned)", scanned)
+}
+pub fn branch_search_title(
+    _key_config: &SharedKeyConfig,
+    query: &str,
+    matches: usize,
+    limit_reached: bool,
+) -> String {
+    format!(
+        "Search '{}': {}{} matches",
+        query,
+        if limit_reached { "first " } else { "" },
+        matches
+    )
+}
+pub fn object_viewer_binary(_key_config: &SharedKeyConfig) -> String {
+    "binary blob, content not shown".to_string()
+}
+pub fn object_viewer_truncated(
+    _key_config: &SharedKeyConfig,
+    limit: usize,
+) -> String {
+    format!("... content truncated after {} bytes", limit)
+}
+pub fn patch_export_popup_title(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+) -> String {
+    format!("Export {} Commit(s) as Patches", count)
+}
+pub fn patch_export_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "folder to write to (empty for the repo root)".to_string()
+}
+pub fn patch_export_msg(
+    _key_config: &SharedKeyConfig,
+    files: &[String],
+) -> String {
+    format!("Wrote {} patch(es):\n{}", files.len(), files.join("\n"))
+}
+pub fn patch_apply_popup_title(
+    _key_config: &SharedKeyConfig,
+    to_index: bool,
+) -> String {
+    if to_index {
+        "Apply and Stage Patch".to_string()
+    } else {
+        "Apply Patch".to_string()
+    }
+}
+pub fn patch_apply_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "path of the patch file".to_string()
+}
+pub fn archive_popup_title(_key_config: &SharedKeyConfig) -> String {
+    "Archive".to_string()
+}
+pub fn archive_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    "file to write to (relative to the repo root)".to_string()
+}
+pub fn archive_msg(
+    _key_config: &SharedKeyConfig,
+    files: usize,
+) -> String {
+    format!("Archived {} file(s)", files)
+}
+pub fn revision_files_title(
+    _key_config: &SharedKeyConfig,
+    id: &str,
+) -> String {
+    format!("Files at {}", id)
+}
+pub fn patch_viewer_title(
+    _key_config: &SharedKeyConfig,
+    path: &str,
+    staged: bool,
+) -> String {
+    format!(
+        "Patch: {} ({})",
+        path,
+        if staged { "staged" } else { "unstaged" }
+    )
+}
+pub fn blame_title(
+    _key_config: &SharedKeyConfig,
+    path: &str,
+    commit: &str,
+) -> String {
+    format!("Blame: {} ({})", path, commit)
+}
+pub fn file_history_title(
+    _key_config: &SharedKeyConfig,
+    path: &str,
+) -> String {
+    format!("History: {}", path)
+}
+pub fn submodules_title(_key_config: &SharedKeyConfig) -> String {
+    "Submodules".to_string()
+}
+pub fn submodules_none(_key_config: &SharedKeyConfig) -> String {
+    "this repository has no submodules".to_string()
+}
+pub fn submodule_state(
+    _key_config: &SharedKeyConfig,
+    status: SubmoduleStatus,
+) -> String {
+    if !status.initialized {
+        return "not initialized".to_string();
+    }
 
-    pub fn toggle_tabs(key_config: &SharedKeyConfig) -> CommandText {
+    let mut parts = Vec::new();
+    if status.out_of_date {
+        parts.push("new commits");
+    }
+    if status.dirty {
+        parts.push("modified content");
+    }
+
+    if parts.is_empty() {
+        "up to date".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+pub fn worktrees_title(_key_config: &SharedKeyConfig) -> String {
+    "Worktrees".to_string()
+}
+pub fn remotes_title(_key_config: &SharedKeyConfig) -> String {
+    "Remotes".to_string()
+}
+pub fn remote_add_title(_key_config: &SharedKeyConfig) -> String {
+    "Add Remote".to_string()
+}
+pub fn remote_rename_title(
+    _key_config: &SharedKeyConfig,
+    name: &str,
+) -> String {
+    format!("Rename Remote: {}", name)
+}
+pub fn remote_url_title(
+    _key_config: &SharedKeyConfig,
+    name: &str,
+) -> String {
+    format!("Url Of Remote: {}", name)
+}
+pub fn remote_name_msg(_key_config: &SharedKeyConfig) -> String {
+    "type remote name".to_string()
+}
+pub fn remote_url_msg(_key_config: &SharedKeyConfig) -> String {
+    "type remote url".to_string()
+}
+pub fn remote_push_url(
+    _key_config: &SharedKeyConfig,
+    push_url: &str,
+) -> String {
+    format!(" (push: {})", push_url)
+}
+pub fn worktree_state(
+    _key_config: &SharedKeyConfig,
+    worktree: &WorktreeInfo,
+) -> String {
+    if worktree.is_prunable {
+        "(missing)".to_string()
+    } else if worktree.is_locked {
+        "(locked)".to_string()
+    } else {
+        String::new()
+    }
+}
+pub fn create_worktree_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Worktree".to_string()
+}
+pub fn create_worktree_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "type branch name (created from HEAD if new)".to_string()
+}
+pub fn reflog_title(
+    _key_config: &SharedKeyConfig,
+    ref_name: &str,
+) -> String {
+    format!("Reflog: {}", ref_name)
+}
+pub fn taglist_title(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+) -> String {
+    format!("Tags ({})", count)
+}
+pub const fn taglist_kind(
+    _key_config: &SharedKeyConfig,
+    annotated: bool,
+) -> &'static str {
+    if annotated {
+        "annotated"
+    } else {
+        "lightweight"
+    }
+}
+pub fn confirm_title_delete_tag(
+    _key_config: &SharedKeyConfig,
+    remote: bool,
+) -> String {
+    if remote {
+        "Delete Remote Tag".to_string()
+    } else {
+        "Delete Tag".to_string()
+    }
+}
+pub fn confirm_msg_delete_tag(
+    _key_config: &SharedKeyConfig,
+    tag: &str,
+    remote: bool,
+) -> String {
+    if remote {
+        format!("Confirm deleting tag '{}' on the remote?", tag)
+    } else {
+        format!("Confirm deleting tag '{}'?", tag)
+    }
+}
+pub fn conflict_title(
+    _key_config: &SharedKeyConfig,
+    path: &str,
+    hunk: usize,
+    hunks: usize,
+) -> String {
+    if hunks == 0 {
+        format!("Conflict: {}", path)
+    } else {
+        format!("Conflict: {} ({}/{})", path, hunk + 1, hunks)
+    }
+}
+pub fn conflict_ours(_key_config: &SharedKeyConfig) -> String {
+    "<<<<<<< ours".to_string()
+}
+pub fn conflict_base(_key_config: &SharedKeyConfig) -> String {
+    "||||||| base".to_string()
+}
+pub fn conflict_theirs(_key_config: &SharedKeyConfig) -> String {
+    ">>>>>>> theirs".to_string()
+}
+pub fn conflict_hunk_header(
+    _key_config: &SharedKeyConfig,
+    line: usize,
+) -> String {
+    format!("@@ line {}", line + 1)
+}
+/// for conflicts without markers: binary files or one side deleted
+pub fn conflict_whole_file(
+    key_config: &SharedKeyConfig,
+    in_ours: bool,
+    in_theirs: bool,
+) -> String {
+    let side =
+        |present: bool| if present { "changed" } else { "deleted" };
+    format!(
+        "no conflict markers, ours {} the file and theirs {} it.\ntake ours [{}] or theirs [{}] as a whole.",
+        side(in_ours),
+        side(in_theirs),
+        key_config.get_hint(key_config.conflict_take_ours),
+        key_config.get_hint(key_config.conflict_take_theirs),
+    )
+}
+pub fn branch_upstream_title(
+    _key_config: &SharedKeyConfig,
+    branch: &str,
+) -> String {
+    format!("Upstream of '{}'", branch)
+}
+pub fn merge_branch_title(_key_config: &SharedKeyConfig) -> String {
+    "Merge".to_string()
+}
+pub fn merge_branch_into(
+    _key_config: &SharedKeyConfig,
+    branch: &str,
+) -> String {
+    format!("merge '{}' into the current branch:", branch)
+}
+pub fn merge_branch_strategy(
+    _key_config: &SharedKeyConfig,
+    strategy: MergeStrategy,
+) -> String {
+    match strategy {
+        MergeStrategy::FastForwardOnly => {
+            "fast-forward only (--ff-only)".to_string()
+        }
+        MergeStrategy::NoFastForward => {
+            "always create a merge commit (--no-ff)".to_string()
+        }
+    }
+}
+pub fn merge_branch_result(
+    _key_config: &SharedKeyConfig,
+    branch: &str,
+    result: MergeResult,
+) -> String {
+    match result {
+        MergeResult::UpToDate => format!("already contains '{}'", branch),
+        MergeResult::FastForward(id) => {
+            format!("fast-forwarded to {}", id.get_short_string())
+        }
+        MergeResult::Merged(id) => {
+            format!("merged '{}' in {}", branch, id.get_short_string())
+        }
+        MergeResult::Conflict => format!(
+            "merging '{}' left conflicts, resolve them in the status tab",
+            branch
+        ),
+    }
+}
+pub fn pull_strategy_title(_key_config: &SharedKeyConfig) -> String {
+    "Pull".to_string()
+}
+pub fn pull_strategy_into(
+    _key_config: &SharedKeyConfig,
+    branch: &str,
+) -> String {
+    format!("pull the upstream of '{}' unless up to date by:", branch)
+}
+pub fn pull_strategy_name(
+    _key_config: &SharedKeyConfig,
+    strategy: PullStrategy,
+) -> String {
+    match strategy {
+        PullStrategy::Merge => "merge commit".to_string(),
+        PullStrategy::Rebase => "rebase local commits".to_string(),
+        PullStrategy::FastForwardOnly => {
+            "fast-forward only (--ff-only)".to_string()
+        }
+    }
+}
+pub fn diff_options_title(_key_config: &SharedKeyConfig) -> String {
+    "Diff Options".to_string()
+}
+pub fn diff_options_entries(
+    _key_config: &SharedKeyConfig,
+    options: DiffOptions,
+) -> Vec<String> {
+    let check = |on: bool| if on { 'x' } else { ' ' };
+
+    vec![
+        format!(
+            "[{}] ignore whitespace changes (-b)",
+            check(options.ignore_whitespace_change)
+        ),
+        format!(
+            "[{}] ignore all whitespace (-w)",
+            check(options.ignore_whitespace)
+        ),
+        format!("context lines: {}", options.context),
+        format!("inter-hunk context: {}", options.interhunk_lines),
+    ]
+}
+pub fn pull_not_fast_forward(
+    key_config: &SharedKeyConfig,
+    ahead: usize,
+    behind: usize,
+) -> String {
+    format!(
+        "can't fast-forward: {} local and {} incoming commits diverged.\npull with merge or rebase [{}] instead",
+        ahead,
+        behind,
+        key_config.get_hint(key_config.pull_strategy),
+    )
+}
+pub fn push_target_title(_key_config: &SharedKeyConfig) -> String {
+    "Push To".to_string()
+}
+pub fn push_target_remote(
+    _key_config: &SharedKeyConfig,
+    branch: &str,
+) -> String {
+    format!("push '{}' to remote:", branch)
+}
+pub fn push_target_branch_title(
+    _key_config: &SharedKeyConfig,
+    remote: &str,
+    set_upstream: bool,
+) -> String {
+    if set_upstream {
+        format!("Branch On {} (set upstream)", remote)
+    } else {
+        format!("Branch On {}", remote)
+    }
+}
+pub fn push_target_branch_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "type remote branch name".to_string()
+}
+pub fn bisect_title(_key_config: &SharedKeyConfig) -> String {
+    "Bisect".to_string()
+}
+pub fn bisect_step(
+    key_config: &SharedKeyConfig,
+    step: BisectStep,
+    remaining: usize,
+) -> String {
+    match step {
+        BisectStep::Test(id) => format!(
+            "{} commits left, testing {}: good [{}], bad [{}] or skip [{}]?",
+            remaining,
+            id.get_short_string(),
+            key_config.get_hint(key_config.bisect_good),
+            key_config.get_hint(key_config.bisect_bad),
+            key_config.get_hint(key_config.bisect_skip),
+        ),
+        BisectStep::Found(id) => format!(
+            "{} is the first bad commit, reset [{}] to finish",
+            id.get_short_string(),
+            key_config.get_hint(key_config.bisect_reset),
+        ),
+        BisectStep::OnlySkipped => {
+            "only skipped commits are left, one of them is the first bad commit".to_string()
+        }
+    }
+}
+pub fn patch_viewer_empty(_key_config: &SharedKeyConfig) -> String {
+    "no changes".to_string()
+}
+pub fn tree_find_title(
+    _key_config: &SharedKeyConfig,
+    query: &str,
+) -> String {
+    format!(" [find '{}']", query)
+}
+pub fn ignore_title(
+    _key_config: &SharedKeyConfig,
+    global: bool,
+) -> String {
+    if global {
+        "Ignore (global excludes file)".to_string()
+    } else {
+        "Ignore (.gitignore)".to_string()
+    }
+}
+pub fn edit_ignore_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    ".gitignore".to_string()
+}
+pub fn edit_ignore_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "one pattern per line".to_string()
+}
+pub fn pick_hunks_title(
+    _key_config: &SharedKeyConfig,
+    path: &str,
+    file: (usize, usize),
+    hunk: (usize, usize),
+    part: Option<(usize, usize)>,
+) -> String {
+    let part = part.map_or_else(String::new, |(idx, parts)| {
+        format!(", part {}/{}", idx, parts)
+    });
+    format!(
+        "Pick Hunks: {} (file {}/{}, hunk {}/{}{})",
+        path, file.0, file.1, hunk.0, hunk.1, part
+    )
+}
+pub fn pick_hunks_title_done(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    "Pick Hunks".to_string()
+}
+pub fn pick_hunks_done(_key_config: &SharedKeyConfig) -> String {
+    "no more unstaged hunks".to_string()
+}
+pub fn pick_hunks_stage_title(
+    _key_config: &SharedKeyConfig,
+    files: usize,
+    additions: usize,
+    deletions: usize,
+) -> String {
+    format!("Staged: {} files +{} -{}", files, additions, deletions)
+}
+
+pub mod commit {
+    use crate::keys::SharedKeyConfig;
+    use asyncgit::sync::{
+        SignatureFormat, SignatureInfo, SignatureStatus,
+    };
+    pub fn details_author(_key_config: &SharedKeyConfig) -> String {
+        "Author: ".to_string()
+    }
+    pub fn details_committer(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        "Committer: ".to_string()
+    }
+    pub fn details_sha(_key_config: &SharedKeyConfig) -> String {
+        "Sha: ".to_string()
+    }
+    pub fn details_date(_key_config: &SharedKeyConfig) -> String {
+        "Date: ".to_string()
+    }
+    pub fn details_tags(_key_config: &SharedKeyConfig) -> String {
+        "Tags: ".to_string()
+    }
+    pub fn details_signature(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        "Signature: ".to_string()
+    }
+    pub fn details_signature_badge(
+        _key_config: &SharedKeyConfig,
+        signature: &SignatureInfo,
+    ) -> String {
+        let format = match signature.format {
+            SignatureFormat::Gpg => "GPG",
+            SignatureFormat::Ssh => "SSH",
+            SignatureFormat::X509 => "X509",
+        };
+        let status = match signature.status {
+            SignatureStatus::Trusted => "good, trusted key",
+            SignatureStatus::Untrusted => "good, untrusted key",
+            SignatureStatus::ExpiredKey => "good, expired key",
+            SignatureStatus::RevokedKey => "good, revoked key",
+            SignatureStatus::NoPublicKey => "no public key",
+            SignatureStatus::Bad => "BAD",
+            SignatureStatus::Unknown => "cannot verify",
+        };
+        format!("[{}] {}", format, status)
+    }
+    pub fn details_signer(
+        _key_config: &SharedKeyConfig,
+        signature: &SignatureInfo,
+    ) -> String {
+        match (&signature.signer, &signature.key) {
+            (Some(signer), Some(key)) => {
+                format!(" {} ({})", signer, key)
+            }
+            (Some(signer), None) => format!(" {}", signer),
+            (None, Some(key)) => format!(" ({})", key),
+            (None, None) => String::new(),
+        }
+    }
+    pub fn details_unsupported_encoding(
+        _key_config: &SharedKeyConfig,
+        encoding: &str,
+    ) -> String {
+        format!("Encoding: {} (unsupported, shown as utf8)", encoding)
+    }
+    pub fn details_info_title(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        "Info".to_string()
+    }
+    pub fn details_message_title(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        "Message".to_string()
+    }
+    pub fn details_files_title(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        "Files:".to_string()
+    }
+    pub fn details_tracked_files_title(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        "Tracked changes:".to_string()
+    }
+    pub fn details_untracked_files_title(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        "Untracked files:".to_string()
+    }
+}
+
+pub mod commands {
+    use crate::components::CommandText;
+    use crate::keys::SharedKeyConfig;
+    use crate::options::TimeZoneDisplay;
+    use asyncgit::sync::{ArchiveFormat, MergeDiff, RepoState};
+
+    static CMD_GROUP_GENERAL: &str = "-- General --";
+    static CMD_GROUP_DIFF: &str = "-- Diff --";
+    static CMD_GROUP_CHANGES: &str = "-- Changes --";
+    static CMD_GROUP_COMMIT: &str = "-- Commit --";
+    static CMD_GROUP_STASHING: &str = "-- Stashing --";
+    static CMD_GROUP_STASHES: &str = "-- Stashes --";
+    static CMD_GROUP_LOG: &str = "-- Log --";
+
+    pub fn toggle_tabs(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Next [{}]",
+                key_config.get_hint(key_config.tab_toggle)
+            ),
+            "switch to next tab",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn toggle_tabs_direct(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Tab [{}{}{}{}]",
+                key_config.get_hint(key_config.tab_status),
+                key_config.get_hint(key_config.tab_log),
+                key_config.get_hint(key_config.tab_stashing),
+                key_config.get_hint(key_config.tab_stashes),
+            ),
+            "switch top level tabs directly",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn help_open(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Help [{}]",
+                key_config.get_hint(key_config.open_help)
+            ),
+            "open this help screen",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn navigate_commit_message(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Nav [{}{}]",
+                key_config.get_hint(key_config.move_up),
+                key_config.get_hint(key_config.move_down)
+            ),
+            "navigate commit message",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn navigate_tree(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Nav [{}{}{}{}]",
+                key_config.get_hint(key_config.move_up),
+                key_config.get_hint(key_config.move_down),
+                key_config.get_hint(key_config.move_right),
+                key_config.get_hint(key_config.move_left)
+            ),
+            "navigate tree view",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn scroll(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Scroll [{}{}]",
+                key_config.get_hint(key_config.focus_above),
+                key_config.get_hint(key_config.focus_below)
+            ),
+            "scroll up or down in focused view",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn copy(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Copy [{}]",
+                key_config.get_hint(key_config.copy),
+            ),
+            "copy selected lines to clipboard",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_load_large(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Load diff [{}]",
+                key_config.get_hint(key_config.diff_load_large),
+            ),
+            "show a diff above 'max_diff_lines'",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_toggle_split(
+        key_config: &SharedKeyConfig,
+        split: bool,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "{} [{}]",
+                if split { "Unified" } else { "Side by Side" },
+                key_config.get_hint(key_config.diff_toggle_split),
+            ),
+            "show old and new lines next to each other or in one column",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_options(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Options [{}]",
+                key_config.get_hint(key_config.diff_options),
+            ),
+            "ignore whitespace or change the context of diffs",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn reset_commit_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reset [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "reset the current branch the selected way (confirmed first)",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn diff_options_change(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Change [{}{}]",
+                key_config.get_hint(key_config.move_left),
+                key_config.get_hint(key_config.move_right),
+            ),
+            "toggle the selected option or change its number of lines",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn copy_hash(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Copy Hash [{}]",
+                key_config.get_hint(key_config.copy),
+            ),
+            "copy selected commit hash to clipboard",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_toggle_no_merges(
+        key_config: &SharedKeyConfig,
+        no_merges: bool,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "{} Merges [{}]",
+                if no_merges { "Show" } else { "Hide" },
+                key_config.get_hint(key_config.log_toggle_no_merges),
+            ),
+            "toggle showing merge commits (like `git log --no-merges`)",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_toggle_all_branches(
+        key_config: &SharedKeyConfig,
+        all_branches: bool,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "{} [{}]",
+                if all_branches {
+                    "Current Branch"
+                } else {
+                    "All Branches"
+                },
+                key_config.get_hint(key_config.log_toggle_all_branches),
+            ),
+            "toggle showing the log of all branches (like `git log --all`)",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_toggle_first_parent(
+        key_config: &SharedKeyConfig,
+        first_parent: bool,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "{} [{}]",
+                if first_parent {
+                    "All Parents"
+                } else {
+                    "First Parent"
+                },
+                key_config.get_hint(key_config.log_toggle_first_parent),
+            ),
+            "toggle following only the first parent of merges (like `git log --first-parent`)",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn inspect_merge_diff(
+        key_config: &SharedKeyConfig,
+        merge: MergeDiff,
+        parents: usize,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Diff: {} [{}]",
+                match merge {
+                    MergeDiff::Parent(parent) => {
+                        format!("Parent {}/{}", parent + 1, parents)
+                    }
+                    MergeDiff::Combined => "Combined".to_string(),
+                },
+                key_config.get_hint(key_config.inspect_merge_diff),
+            ),
+            "diff the merge commit against its next parent or all parents combined",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn toggle_time_zone(
+        key_config: &SharedKeyConfig,
+        current: TimeZoneDisplay,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Time: {} [{}]",
+                match current {
+                    TimeZoneDisplay::Local => "Local",
+                    TimeZoneDisplay::Utc => "UTC",
+                    TimeZoneDisplay::Commit => "Commit",
+                },
+                key_config.get_hint(key_config.toggle_time_zone),
+            ),
+            "toggle showing commit times in local time, UTC or the time zone of the commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_search(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Search [{}]",
+                key_config.get_hint(key_config.log_search),
+            ),
+            "search the log by message, author, hash prefix or touched path",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_search_step(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Next/Prev Match [{}/{}]",
+                key_config.get_hint(key_config.log_search_next),
+                key_config.get_hint(key_config.log_search_prev),
+            ),
+            "select the next or previous commit matching the search",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_search_filter(
+        key_config: &SharedKeyConfig,
+        filtered: bool,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "{} [{}]",
+                if filtered { "Unfilter" } else { "Filter" },
+                key_config.get_hint(key_config.log_search_filter),
+            ),
+            "toggle showing only the commits matching the search",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_search_clear(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Clear Search [{}]",
+                key_config.get_hint(key_config.exit_popup),
+            ),
+            "end the log search",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_search_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Search [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "search the log",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_goto(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Go To [{}]",
+                key_config.get_hint(key_config.log_goto),
+            ),
+            "jump to a commit by hash or revision",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_goto_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!("Go [{}]", key_config.get_hint(key_config.enter)),
+            "select the commit in the log",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_mark_compare(
+        key_config: &SharedKeyConfig,
+        marked: bool,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "{} [{}]",
+                if marked {
+                    "Compare with Marked"
+                } else {
+                    "Mark to Compare"
+                },
+                key_config.get_hint(key_config.log_mark_compare),
+            ),
+            "mark a commit, then show the combined diff from it to the selected one",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_search_branches(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Search Branches [{}]",
+                key_config.get_hint(key_config.search_branches),
+            ),
+            "search commits on all branches by message, author or hash",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn branch_search_start(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Search [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "start searching all branches",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn branch_search_cycle_branch(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Branch [{}{}]",
+                key_config.get_hint(key_config.move_left),
+                key_config.get_hint(key_config.move_right),
+            ),
+            "select one of the branches containing the commit",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn branch_search_inspect(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Inspect [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "inspect selected commit",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn branch_search_checkout(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Checkout [{}]",
+                key_config.get_hint(key_config.select_branch),
+            ),
+            "checkout selected branch",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn log_view_object(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Object [{}]",
+                key_config.get_hint(key_config.view_object),
+            ),
+            "view raw commit object (like `git cat-file -p`)",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn object_viewer_open_link(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Open [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "open object referenced by selected line",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn object_viewer_back(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Back [{}]",
+                key_config.get_hint(key_config.exit_popup),
+            ),
+            "go back to previous object or close",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn push_tags(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Push Tags [{}]",
+                key_config.get_hint(key_config.push),
+            ),
+            "push tags to remote",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn diff_home_end(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Jump up/down [{},{},{},{}]",
+                key_config.get_hint(key_config.home),
+                key_config.get_hint(key_config.end),
+                key_config.get_hint(key_config.move_up),
+                key_config.get_hint(key_config.move_down)
+            ),
+            "scroll to top or bottom of diff",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_hunk_add(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Add hunk [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "adds selected hunk to stage",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_hunk_edit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Edit hunk [{}]",
+                key_config.get_hint(key_config.diff_edit_hunk),
+            ),
+            "edit selected hunk in the external editor and stage the result",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_hunk_revert(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reset hunk [{}]",
+                key_config.get_hint(key_config.status_reset_item),
+            ),
+            "reverts selected hunk",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_visual_select(
+        key_config: &SharedKeyConfig,
+        visual: bool,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "{} [{}]",
+                if visual { "End Selection" } else { "Select Lines" },
+                key_config.get_hint(key_config.diff_visual_select),
+            ),
+            "select lines by moving up and down to stage, unstage, reset or copy them",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_lines_revert(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reset lines [{}]",
+                key_config.get_hint(key_config.diff_reset_lines),
+            ),
+            "resets selected lines",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_lines_stage(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Stage lines [{}]",
+                key_config.get_hint(key_config.diff_stage_lines),
+            ),
+            "stage selected lines",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_lines_unstage(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Unstage lines [{}]",
+                key_config.get_hint(key_config.diff_stage_lines),
+            ),
+            "unstage selected lines",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn diff_hunk_remove(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Remove hunk [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "removes selected hunk from stage",
+            CMD_GROUP_DIFF,
+        )
+    }
+    pub fn close_popup(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Close [{}]",
+                key_config.get_hint(key_config.exit_popup),
+            ),
+            "close overlay (e.g commit, help)",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn close_msg(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Close [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "close msg popup (e.g msg)",
+            CMD_GROUP_GENERAL,
+        )
+        .hide_help()
+    }
+    pub fn validate_msg(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Validate [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "validate msg",
+            CMD_GROUP_GENERAL,
+        )
+        .hide_help()
+    }
+    pub fn select_status(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "To files [{},{}]",
+                key_config.get_hint(key_config.tab_status),
+                key_config.get_hint(key_config.tab_log),
+            ),
+            "focus/select file tree of staged or unstaged files",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn select_staging(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "To stage [{}]",
+                key_config.get_hint(key_config.toggle_workarea),
+            ),
+            "focus/select staging area",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn select_unstaged(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "To unstaged [{}]",
+                key_config.get_hint(key_config.toggle_workarea),
+            ),
+            "focus/select unstaged area",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn commit_open(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Commit [{}]",
+                key_config.get_hint(key_config.open_commit),
+            ),
+            "open commit popup (available in non-empty stage)",
+            CMD_GROUP_COMMIT,
+        )
+    }
+    pub fn commit_open_editor(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Open editor [{}]",
+                key_config.get_hint(key_config.open_commit_editor),
+            ),
+            "open commit editor (available in non-empty stage)",
+            CMD_GROUP_COMMIT,
+        )
+    }
+    pub fn commit_enter(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Commit [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "commit (available when commit message is non-empty)",
+            CMD_GROUP_COMMIT,
+        )
+    }
+    pub fn commit_amend(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Amend [{}]",
+                key_config.get_hint(key_config.commit_amend),
+            ),
+            "amend last commit",
+            CMD_GROUP_COMMIT,
+        )
+    }
+    pub fn commit_amend_no_edit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Amend (keep msg) [{}]",
+                key_config.get_hint(key_config.commit_amend_no_edit),
+            ),
+            "amend last commit with the stage, keeping its message (available in non-empty stage)",
+            CMD_GROUP_COMMIT,
+        )
+    }
+    pub fn commit_and_push(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Commit & Push [{}]",
+                key_config.get_hint(key_config.commit_and_push),
+            ),
+            "commit changes and push to tracking branch",
+            CMD_GROUP_COMMIT,
+        )
+    }
+    pub fn commit_toggle_verbose(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Staged diff [{}]",
+                key_config.get_hint(key_config.commit_toggle_verbose),
+            ),
+            "show or hide the staged changes below the message",
+            CMD_GROUP_COMMIT,
+        )
+    }
+    pub fn commit_amend_advanced(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Amend Author [{}]",
+                key_config.get_hint(key_config.commit_amend_advanced),
+            ),
+            "amend last commit and change its author and date",
+            CMD_GROUP_COMMIT,
+        )
+    }
+    pub fn commit_next_field(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Next field [{}]",
+                key_config.get_hint(key_config.tab_toggle),
+            ),
+            "switch between message, author and date",
+            CMD_GROUP_COMMIT,
+        )
+    }
+    pub fn commit_toggle_signoff(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Sign-off [{}]",
+                key_config.get_hint(key_config.commit_toggle_signoff),
+            ),
+            "add a Signed-off-by trailer to the message (or not)",
+            CMD_GROUP_COMMIT,
+        )
+    }
+    pub fn commit_co_author(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Co-author [{}]",
+                key_config.get_hint(key_config.commit_co_author),
+            ),
+            "add a Co-authored-by trailer for a recent author",
+            CMD_GROUP_COMMIT,
+        )
+    }
+    pub fn co_author_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Add [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "add the selected author as co-author",
+            CMD_GROUP_COMMIT,
+        )
+    }
+    pub fn commit_scroll_diff(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Scroll diff [{}{}]",
+                key_config.get_hint(key_config.page_up),
+                key_config.get_hint(key_config.page_down),
+            ),
+            "scroll the staged changes below the message",
+            CMD_GROUP_COMMIT,
+        )
+    }
+    pub fn view_patch(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Patch [{}]",
+                key_config.get_hint(key_config.view_patch),
+            ),
+            "show the patch applied to the index for the selected file",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn blame_file(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Blame [{}]",
+                key_config.get_hint(key_config.blame),
+            ),
+            "show the commit that last changed each line of the file",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn tree_find(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Find [{}]",
+                key_config.get_hint(key_config.tree_find),
+            ),
+            "fuzzy find a file by typing parts of its path",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn tree_collapse_all(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Fold all [{}{}]",
+                key_config.get_hint(key_config.tree_collapse_all),
+                key_config.get_hint(key_config.tree_expand_all),
+            ),
+            "collapse or expand all folders",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn toggle_mark(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Mark [{}] range [{}]",
+                key_config.get_hint(key_config.toggle_mark),
+                key_config.get_hint(key_config.mark_range),
+            ),
+            "mark items to act on all of them at once, or all items up to the one marked last",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn tree_find_jump(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Jump [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "select the found file in the whole tree",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn tree_find_cancel(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Cancel [{}]",
+                key_config.get_hint(key_config.exit_popup),
+            ),
+            "stop finding and restore the selection",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn file_history(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "History [{}]",
+                key_config.get_hint(key_config.file_history),
+            ),
+            "show the commits changing the file (following renames)",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn file_history_inspect(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Inspect [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "inspect the selected commit",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn blame_inspect_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Inspect [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "inspect the commit of the selected line",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn open_submodules(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Submodules [{}]",
+                key_config.get_hint(key_config.open_submodules),
+            ),
+            "list the submodules to update or open them",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn submodule_update(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Update [{}]",
+                key_config.get_hint(key_config.submodule_update),
+            ),
+            "clone or check out the commit recorded for the submodule",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn submodule_open(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Open [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "browse the submodule in gitui until quitting it",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn open_worktrees(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Worktrees [{}]",
+                key_config.get_hint(key_config.open_worktrees),
+            ),
+            "list the worktrees to add, remove or switch to them",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn open_remotes(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Remotes [{}]",
+                key_config.get_hint(key_config.open_remotes),
+            ),
+            "list the remotes to add, rename, re-point or remove them",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn remote_add(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Add [{}]",
+                key_config.get_hint(key_config.remote_add),
+            ),
+            "add a remote by name and url",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn remote_rename(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Rename [{}]",
+                key_config.get_hint(key_config.remote_rename),
+            ),
+            "rename the remote and its remote branches",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn remote_set_url(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Set Url [{}]",
+                key_config.get_hint(key_config.remote_set_url),
+            ),
+            "change the url of the remote",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn remote_remove(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Remove [{}]",
+                key_config.get_hint(key_config.remote_remove),
+            ),
+            "remove the remote",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn remote_edit_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Confirm [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "apply the entered name or url",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn worktree_switch(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Switch [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "reopen gitui in the selected worktree",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn worktree_create(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Add [{}]",
+                key_config.get_hint(key_config.worktree_create),
+            ),
+            "check out a branch in a new worktree",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn worktree_remove(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Remove [{}]",
+                key_config.get_hint(key_config.worktree_remove),
+            ),
+            "delete the selected worktree",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn worktree_prune(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Prune [{}]",
+                key_config.get_hint(key_config.worktree_prune),
+            ),
+            "forget the worktrees whose directory is gone",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn open_reflog(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reflog [{}]",
+                key_config.get_hint(key_config.open_reflog),
+            ),
+            "list where HEAD pointed to before, to undo resets and rebases",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn reflog_checkout(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Checkout [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "detach HEAD at the commit of the selected entry",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn reflog_reset_hard(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reset Hard [{}]",
+                key_config.get_hint(key_config.reflog_reset_hard),
+            ),
+            "move the current branch to the selected entry",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn reflog_inspect_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Inspect [{}]",
+                key_config.get_hint(key_config.focus_right),
+            ),
+            "inspect the commit of the selected entry",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn reflog_toggle_ref(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "HEAD/Branch [{}]",
+                key_config.get_hint(key_config.tab_toggle),
+            ),
+            "switch between the reflog of HEAD and the current branch",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn open_tags(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Tags [{}]",
+                key_config.get_hint(key_config.open_tags),
+            ),
+            "list all tags",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn taglist_checkout(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Checkout [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "detach HEAD at the commit of the selected tag",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn taglist_inspect_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Inspect [{}]",
+                key_config.get_hint(key_config.focus_right),
+            ),
+            "inspect the commit of the selected tag",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn taglist_push(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Push [{}]",
+                key_config.get_hint(key_config.tag_push),
+            ),
+            "push the selected tag to the default remote",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn taglist_delete(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Delete [{}]",
+                key_config.get_hint(key_config.tag_delete),
+            ),
+            "delete the selected tag locally",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn taglist_delete_remote(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Delete Remote [{}]",
+                key_config.get_hint(key_config.tag_delete_remote),
+            ),
+            "delete the selected tag on the default remote",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn open_bisect(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Bisect [{}]",
+                key_config.get_hint(key_config.open_bisect),
+            ),
+            "find the first bad commit between the selected (good) one and HEAD",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn bisect_good(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Good [{}]",
+                key_config.get_hint(key_config.bisect_good),
+            ),
+            "the checked out commit does not have the bug",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn bisect_bad(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Bad [{}]",
+                key_config.get_hint(key_config.bisect_bad),
+            ),
+            "the checked out commit has the bug",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn bisect_skip(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Skip [{}]",
+                key_config.get_hint(key_config.bisect_skip),
+            ),
+            "the checked out commit cannot be tested",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn bisect_reset(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reset [{}]",
+                key_config.get_hint(key_config.bisect_reset),
+            ),
+            "stop bisecting and check out the branch it started on",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn open_conflict(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Resolve [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "resolve the conflicts of the selected file",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn conflict_take_ours(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Take ours [{}]",
+                key_config.get_hint(key_config.conflict_take_ours),
+            ),
+            "resolve the selected hunk (or file) with our side",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn conflict_take_theirs(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Take theirs [{}]",
+                key_config.get_hint(key_config.conflict_take_theirs),
+            ),
+            "resolve the selected hunk (or file) with their side",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn conflict_mergetool(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Mergetool [{}]",
+                key_config.get_hint(key_config.conflict_mergetool),
+            ),
+            "resolve the file in the configured merge.tool",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn conflict_resolved(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Mark resolved [{}]",
+                key_config.get_hint(key_config.conflict_resolved),
+            ),
+            "stage the file once no conflict markers are left",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn bisect_inspect_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Inspect [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "inspect the selected commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn create_worktree_confirm_msg(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Add Worktree [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "add worktree",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn copy_patch(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Copy [{}]",
+                key_config.get_hint(key_config.copy),
+            ),
+            "copy the whole patch to clipboard",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn rebase_interactive(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Rebase [{}]",
+                key_config.get_hint(key_config.rebase_interactive),
+            ),
+            "rebase the commits after the selected one interactively (pick, reword, squash, fixup, drop, reorder)",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_reword(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reword [{}]",
+                key_config.get_hint(key_config.rebase_reword),
+            ),
+            "edit the message of the selected commit (rebases the commits after it)",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_commit_fixup(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Fixup [{}{}]",
+                key_config.get_hint(key_config.commit_fixup),
+                key_config.get_hint(key_config.commit_squash),
+            ),
+            "commit the staged changes as fixup! (or squash!) of the selected commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_autosquash(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Autosquash [{}]",
+                key_config.get_hint(key_config.autosquash),
+            ),
+            "rebase folding the fixup! and squash! commits into their targets (reviewed in the rebase popup first)",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_squash_marked(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Squash marked [{}]",
+                key_config.get_hint(key_config.rebase_squash),
+            ),
+            "squash the marked commits into the oldest of them (reviewed in the rebase popup first)",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_set_action(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Pick/Reword/Squash/Fixup/Drop [{}{}{}{}{}]",
+                key_config.get_hint(key_config.rebase_pick),
+                key_config.get_hint(key_config.rebase_reword),
+                key_config.get_hint(key_config.rebase_squash),
+                key_config.get_hint(key_config.rebase_fixup),
+                key_config.get_hint(key_config.rebase_drop),
+            ),
+            "set what happens to the selected commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_move(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Move [{}{}]",
+                key_config.get_hint(key_config.shift_up),
+                key_config.get_hint(key_config.shift_down),
+            ),
+            "move the selected commit up or down the todo list",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_start(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Start [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "run the todo list from top to bottom",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_continue(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Continue [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "commit the staged resolution and carry on with the rebase",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_skip(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Skip [{}]",
+                key_config.get_hint(key_config.rebase_skip),
+            ),
+            "drop the conflicting commit and carry on with the rebase",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn rebase_abort(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Abort [{}]",
+                key_config.get_hint(key_config.rebase_abort),
+            ),
+            "restore the branch to where it was before the rebase",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn cherry_pick(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Cherry-pick [{}]",
+                key_config.get_hint(key_config.cherry_pick),
+            ),
+            "apply the changes of the selected (or marked) commits onto the current branch",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn restore_file(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Restore File [{}]",
+                key_config.get_hint(key_config.restore_file),
+            ),
+            "restore the selected file to this version (index and workdir)",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn revert_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Revert [{}]",
+                key_config.get_hint(key_config.revert_commit),
+            ),
+            "undo the changes of the selected commit with a new commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn log_reset_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reset [{}]",
+                key_config.get_hint(key_config.log_reset_commit),
+            ),
+            "reset the current branch to the selected commit (soft, mixed or hard)",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn drop_commit(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Drop [{}]",
+                key_config.get_hint(key_config.rebase_drop),
+            ),
+            "remove the selected commit from the history (rebases the commits after it)",
+            CMD_GROUP_LOG,
+        )
+    }
+    /// `None` for the help of all commands
+    pub fn state_continue(
+        key_config: &SharedKeyConfig,
+        state: Option<RepoState>,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Continue {} [{}]",
+                state.map_or("", super::stopped_state_name),
+                key_config.get_hint(key_config.cherry_pick),
+            ),
+            "commit the staged resolution of the stopped merge, rebase, cherry-pick or revert",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn state_abort(
+        key_config: &SharedKeyConfig,
+        state: Option<RepoState>,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Abort {} [{}]",
+                state.map_or("", super::stopped_state_name),
+                key_config.get_hint(key_config.cherry_pick_abort),
+            ),
+            "drop the stopped merge, rebase, cherry-pick or revert",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn status_pick_hunks(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Pick Hunks [{}]",
+                key_config.get_hint(key_config.pick_hunks),
+            ),
+            "go through the hunks of all files picking the ones to stage for the next commit (like `git add -p`)",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn pick_hunks_stage(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Stage Hunk [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "stage the shown hunk and go to the next one",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn pick_hunks_skip(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Skip Hunk [{}]",
+                key_config.get_hint(key_config.move_down),
+            ),
+            "leave the shown hunk unstaged and go to the next one",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn pick_hunks_split(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Split Hunk [{}]",
+                key_config.get_hint(key_config.pick_hunks_split),
+            ),
+            "pick the parts of the shown hunk one by one",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn edit_item(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Edit Item [{}]",
+                key_config.get_hint(key_config.edit_file),
+            ),
+            "edit the currently selected file in an external editor",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn stage_item(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Stage Item [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "stage currently selected file or entire path",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn stage_all(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Stage All [{}]",
+                key_config.get_hint(key_config.status_stage_all),
+            ),
+            "stage all changes (in unstaged files)",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn unstage_item(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Unstage Item [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "unstage currently selected file or entire path",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn unstage_all(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Unstage all [{}]",
+                key_config.get_hint(key_config.status_stage_all),
+            ),
+            "unstage all files (in staged files)",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn reset_item(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Reset Item [{}]",
+                key_config.get_hint(key_config.status_reset_item),
+            ),
+            "revert changes in selected file or entire path",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn ignore_item(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Ignore [{}]",
+                key_config.get_hint(key_config.status_ignore_file),
+            ),
+            "ignore the file or path by a pattern of choice",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn ignore_add(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Add [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "add the selected pattern to the ignore file",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn ignore_toggle_global(
+        key_config: &SharedKeyConfig,
+        global: bool,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "{} [{}]",
+                if global { "Repo" } else { "Global" },
+                key_config.get_hint(key_config.ignore_toggle_global),
+            ),
+            "switch between the .gitignore of the repo and the global excludes file (core.excludesfile)",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn ignore_edit(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Edit .gitignore [{}]",
+                key_config.get_hint(key_config.ignore_edit),
+            ),
+            "edit the .gitignore of the repo",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn edit_ignore_save(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Save [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "write the .gitignore",
+            CMD_GROUP_GENERAL,
+        )
+    }
+
+    pub fn diff_focus_left(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Back [{}]",
+                key_config.get_hint(key_config.focus_left),
+            ),
+            "view and select changed files",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn diff_focus_right(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Diff [{}]",
+                key_config.get_hint(key_config.focus_right),
+            ),
+            "inspect file diff",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn quit(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Quit [{}]",
+                key_config.get_hint(key_config.exit),
+            ),
+            "quit gitui application",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn confirm_action(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Confirm [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "confirm action",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn status_apply_patch(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Apply Patch [{}]",
+                key_config.get_hint(key_config.apply_patch),
+            ),
+            "apply a patch file to the workdir",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn status_undo_discard(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Undo Discard [{}]",
+                key_config.get_hint(key_config.undo_discard),
+            ),
+            "restore the changes discarded last",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn status_clean(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Clean [{}]",
+                key_config.get_hint(key_config.open_clean),
+            ),
+            "preview removing untracked files (git clean)",
+            CMD_GROUP_CHANGES,
+        )
+    }
+    pub fn clean_toggle_directories(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Toggle Dirs [{}]",
+                key_config
+                    .get_hint(key_config.clean_toggle_directories),
+            ),
+            "toggle including untracked directories",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn clean_toggle_ignored(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Toggle Ignored [{}]",
+                key_config.get_hint(key_config.clean_toggle_ignored),
+            ),
+            "toggle including ignored files",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn clean_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Remove [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "remove the listed files (asks for confirmation)",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn checkout_stash_reapply(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Stash, Switch & Reapply [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "stash local changes, switch branch and re-apply them",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn checkout_stash(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Stash & Switch [{}]",
+                key_config.get_hint(key_config.stashing_save),
+            ),
+            "stash local changes and switch branch",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn checkout_force(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Force Switch [{}]",
+                key_config.get_hint(key_config.checkout_force),
+            ),
+            "switch branch and discard local changes",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn stashing_save(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Save [{}]",
+                key_config.get_hint(key_config.stashing_save),
+            ),
+            "opens stash name input popup",
+            CMD_GROUP_STASHING,
+        )
+    }
+    pub fn stashing_toggle_indexed(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Toggle Staged [{}]",
+                key_config.get_hint(key_config.stashing_toggle_index),
+            ),
+            "toggle including staged files into stash",
+            CMD_GROUP_STASHING,
+        )
+    }
+    pub fn stashing_toggle_untracked(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Toggle Untracked [{}]",
+                key_config
+                    .get_hint(key_config.stashing_toggle_untracked),
+            ),
+            "toggle including untracked files into stash",
+            CMD_GROUP_STASHING,
+        )
+    }
+    pub fn stashing_confirm_msg(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Stash [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "save files to stash",
+            CMD_GROUP_STASHING,
+        )
+    }
+    pub fn stashlist_apply(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Apply [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "apply selected stash",
+            CMD_GROUP_STASHES,
+        )
+    }
+    pub fn stashlist_pop(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Next [{}]",
-                key_config.get_hint(key_config.tab_toggle)
+                "Pop [{}]",
+                key_config.get_hint(key_config.stash_pop),
             ),
-            "switch to next tab",
-            CMD_GROUP_GENERAL,
+            "apply selected stash and drop it",
+            CMD_GROUP_STASHES,
         )
     }
-    pub fn toggle_tabs_direct(
+    pub fn stashlist_toggle_index(
         key_config: &SharedKeyConfig,
+        with_index: bool,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Tab [{}{}{}{}]",
-                key_config.get_hint(key_config.tab_status),
-                key_config.get_hint(key_config.tab_log),
-                key_config.get_hint(key_config.tab_stashing),
-                key_config.get_hint(key_config.tab_stashes),
+                "{} index [{}]",
+                if with_index { "Without" } else { "With" },
+                key_config.get_hint(key_config.stashing_toggle_index),
             ),
-            "switch top level tabs directly",
-            CMD_GROUP_GENERAL,
+            "whether apply and pop stage the staged changes of the stash again",
+            CMD_GROUP_STASHES,
         )
     }
-    pub fn help_open(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn stashlist_branch(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Help [{}]",
-                key_config.get_hint(key_config.open_help)
+                "Branch [{}]",
+                key_config.get_hint(key_config.stash_branch),
             ),
-            "open this help screen",
-            CMD_GROUP_GENERAL,
+            "pop selected stash on a new branch at the commit it was made on",
+            CMD_GROUP_STASHES,
         )
     }
-    pub fn navigate_commit_message(
+    pub fn stashlist_drop(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Nav [{}{}]",
-                key_config.get_hint(key_config.move_up),
-                key_config.get_hint(key_config.move_down)
+                "Drop [{}]",
+                key_config.get_hint(key_config.stash_drop),
             ),
-            "navigate commit message",
-            CMD_GROUP_GENERAL,
+            "drop selected stash",
+            CMD_GROUP_STASHES,
         )
     }
-    pub fn navigate_tree(
+    pub fn stashlist_rename(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Nav [{}{}{}{}]",
-                key_config.get_hint(key_config.move_up),
-                key_config.get_hint(key_config.move_down),
-                key_config.get_hint(key_config.move_right),
-                key_config.get_hint(key_config.move_left)
+                "Rename [{}]",
+                key_config.get_hint(key_config.stash_rename),
             ),
-            "navigate tree view",
-            CMD_GROUP_GENERAL,
+            "change message of selected stash",
+            CMD_GROUP_STASHES,
         )
     }
-    pub fn scroll(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn stashlist_inspect(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Scroll [{}{}]",
-                key_config.get_hint(key_config.focus_above),
-                key_config.get_hint(key_config.focus_below)
+                "Inspect [{}]",
+                key_config.get_hint(key_config.focus_right),
             ),
-            "scroll up or down in focused view",
-            CMD_GROUP_GENERAL,
+            "open stash commit details (allows to diff files)",
+            CMD_GROUP_STASHES,
         )
     }
-    pub fn copy(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_details_toggle(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Copy [{}]",
-                key_config.get_hint(key_config.copy),
+                "Details [{}]",
+                key_config.get_hint(key_config.enter),
             ),
-            "copy selected lines to clipboard",
-            CMD_GROUP_DIFF,
+            "open details of selected commit",
+            CMD_GROUP_LOG,
         )
     }
-    pub fn copy_hash(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_details_open(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Copy Hash [{}]",
-                key_config.get_hint(key_config.copy),
+                "Inspect [{}]",
+                key_config.get_hint(key_config.focus_right),
             ),
-            "copy selected commit hash to clipboard",
+            "inspect selected commit in detail",
             CMD_GROUP_LOG,
         )
     }
-    pub fn push_tags(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_tag_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Push Tags [{}]",
-                key_config.get_hint(key_config.push),
+                "Tag [{}]",
+                key_config.get_hint(key_config.log_tag_commit),
             ),
-            "push tags to remote",
+            "tag commit",
             CMD_GROUP_LOG,
         )
     }
-    pub fn diff_home_end(
+    pub fn log_browse_files(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Jump up/down [{},{},{},{}]",
-                key_config.get_hint(key_config.home),
-                key_config.get_hint(key_config.end),
-                key_config.get_hint(key_config.move_up),
-                key_config.get_hint(key_config.move_down)
+                "Files [{}]",
+                key_config.get_hint(key_config.log_browse_files),
             ),
-            "scroll to top or bottom of diff",
-            CMD_GROUP_DIFF,
+            "browse the files of the selected commit",
+            CMD_GROUP_LOG,
         )
     }
-    pub fn diff_hunk_add(
+    pub fn revision_files_view(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Add hunk [{}]",
-                key_config.get_hint(key_config.enter),
+                "View [{}]",
+                key_config.get_hint(key_config.focus_right),
             ),
-            "adds selected hunk to stage",
-            CMD_GROUP_DIFF,
+            "view the file content at this revision",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn diff_hunk_revert(
+    pub fn revision_files_back(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Reset hunk [{}]",
-                key_config.get_hint(key_config.status_reset_item),
+                "Back [{}]",
+                key_config.get_hint(key_config.focus_left),
             ),
-            "reverts selected hunk",
-            CMD_GROUP_DIFF,
+            "go back to the files",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn diff_lines_revert(
+    pub fn copy_path(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Copy Path [{}]",
+                key_config.get_hint(key_config.copy),
+            ),
+            "copy selected path to clipboard",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn log_checkout_commit(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Reset lines [{}]",
-                key_config.get_hint(key_config.diff_reset_lines),
+                "Checkout [{}]",
+                key_config.get_hint(key_config.log_checkout_commit),
             ),
-            "resets selected lines",
-            CMD_GROUP_DIFF,
+            "detach HEAD at the selected commit",
+            CMD_GROUP_LOG,
         )
     }
-    pub fn diff_lines_stage(
+    pub fn log_create_branch(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Stage lines [{}]",
-                key_config.get_hint(key_config.diff_stage_lines),
+                "Branch [{}]",
+                key_config.get_hint(key_config.create_branch),
             ),
-            "stage selected lines",
-            CMD_GROUP_DIFF,
+            "create a branch at the selected commit (without checking it out)",
+            CMD_GROUP_LOG,
         )
     }
-    pub fn diff_lines_unstage(
+    pub fn tag_commit_next(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Unstage lines [{}]",
-                key_config.get_hint(key_config.diff_stage_lines),
+                "Message [{}]",
+                key_config.get_hint(key_config.enter),
             ),
-            "unstage selected lines",
-            CMD_GROUP_DIFF,
+            "continue with the tag message",
+            CMD_GROUP_LOG,
         )
     }
-    pub fn diff_hunk_remove(
+    pub fn log_export_patches(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Remove hunk [{}]",
-                key_config.get_hint(key_config.enter),
+                "Export Patches [{}]",
+                key_config.get_hint(key_config.log_export_patches),
             ),
-            "removes selected hunk from stage",
-            CMD_GROUP_DIFF,
+            "write the selected (or marked) commits as patch files",
+            CMD_GROUP_LOG,
         )
     }
-    pub fn close_popup(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn patch_export_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Close [{}]",
-                key_config.get_hint(key_config.exit_popup),
+                "Export [{}]",
+                key_config.get_hint(key_config.enter),
             ),
-            "close overlay (e.g commit, help)",
+            "write the patch files",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn close_msg(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn patch_apply_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Close [{}]",
+                "Apply [{}]",
                 key_config.get_hint(key_config.enter),
             ),
-            "close msg popup (e.g msg)",
+            "apply the patch file",
             CMD_GROUP_GENERAL,
         )
-        .hide_help()
     }
-    pub fn validate_msg(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn patch_apply_toggle_index(
+        key_config: &SharedKeyConfig,
+        to_index: bool,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Validate [{}]",
+                "{} [{}]",
+                if to_index { "Don't Stage" } else { "Stage" },
+                key_config
+                    .get_hint(key_config.patch_apply_toggle_index),
+            ),
+            "stage the applied changes as well",
+            CMD_GROUP_GENERAL,
+        )
+    }
+    pub fn log_archive(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            format!(
+                "Archive [{}]",
+                key_config.get_hint(key_config.archive),
+            ),
+            "write the tree of the selected commit to a tar or zip file",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn taglist_archive(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Archive [{}]",
+                key_config.get_hint(key_config.archive),
+            ),
+            "write the tree of the selected tag to a tar or zip file",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn archive_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Archive [{}]",
                 key_config.get_hint(key_config.enter),
             ),
-            "validate msg",
+            "write the archive",
             CMD_GROUP_GENERAL,
         )
-        .hide_help()
     }
-    pub fn select_status(
+    pub fn archive_format(
         key_config: &SharedKeyConfig,
+        format: ArchiveFormat,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "To files [{},{}]",
-                key_config.get_hint(key_config.tab_status),
-                key_config.get_hint(key_config.tab_log),
+                "Format: {} [{}]",
+                &format.extension()[1..],
+                key_config.get_hint(key_config.archive_format),
             ),
-            "focus/select file tree of staged or unstaged files",
+            "switch between tar.gz, zip and tar",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn select_staging(
+    pub fn tag_commit_toggle_sign(
         key_config: &SharedKeyConfig,
+        sign: bool,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "To stage [{}]",
-                key_config.get_hint(key_config.toggle_workarea),
+                "{} [{}]",
+                if sign { "Don't Sign" } else { "Sign" },
+                key_config.get_hint(key_config.tag_toggle_sign),
             ),
-            "focus/select staging area",
+            "sign the annotated tag (default from tag.gpgSign)",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn insert_newline(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Newline [{}]",
+                key_config.get_hint(key_config.insert_newline),
+            ),
+            "start a new line in the message",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn select_unstaged(
+    pub fn tag_commit_confirm_msg(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "To unstaged [{}]",
-                key_config.get_hint(key_config.toggle_workarea),
+                "Tag [{}]",
+                key_config.get_hint(key_config.enter),
             ),
-            "focus/select unstaged area",
+            "tag commit",
+            CMD_GROUP_LOG,
+        )
+    }
+    pub fn create_branch_confirm_msg(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            format!(
+                "Create Branch [{}]",
+                key_config.get_hint(key_config.enter),
+            ),
+            "create branch",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn commit_open(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn open_branch_create_popup(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Commit [{}]",
-                key_config.get_hint(key_config.open_commit),
+                "Create [{}]",
+                key_config.get_hint(key_config.create_branch),
             ),
-            "open commit popup (available in non-empty stage)",
-            CMD_GROUP_COMMIT,
+            "open create branch popup",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn commit_open_editor(
+    pub fn branch_description_confirm_msg(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Open editor [{}]",
-                key_config.get_hint(key_config.open_commit_editor),
+                "Save [{}]",
+                key_config.get_hint(key_config.enter),
             ),
-            "open commit editor (available in non-empty stage)",
-            CMD_GROUP_COMMIT,
+            "save the branch description",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn commit_enter(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn rename_branch_confirm_msg(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Commit [{}]",
+                "Rename Branch [{}]",
                 key_config.get_hint(key_config.enter),
             ),
-            "commit (available when commit message is non-empty)",
-            CMD_GROUP_COMMIT,
+            "rename branch",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn commit_amend(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn rename_stash_confirm_msg(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Amend [{}]",
-                key_config.get_hint(key_config.commit_amend),
+                "Rename Stash [{}]",
+                key_config.get_hint(key_config.enter),
             ),
-            "amend last commit",
-            CMD_GROUP_COMMIT,
+            "rename stash",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn edit_item(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn reword_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Edit Item [{}]",
-                key_config.get_hint(key_config.edit_file),
+                "Reword [{}]",
+                key_config.get_hint(key_config.enter),
             ),
-            "edit the currently selected file in an external editor",
-            CMD_GROUP_CHANGES,
+            "replace the commit message",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn stage_item(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn stash_branch_confirm_msg(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Stage Item [{}]",
+                "Create Branch [{}]",
                 key_config.get_hint(key_config.enter),
             ),
-            "stage currently selected file or entire path",
-            CMD_GROUP_CHANGES,
+            "create branch and pop stash",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn stage_all(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn rename_branch_popup(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Stage All [{}]",
-                key_config.get_hint(key_config.status_stage_all),
+                "Rename Branch [{}]",
+                key_config.get_hint(key_config.rename_branch),
             ),
-            "stage all changes (in unstaged files)",
-            CMD_GROUP_CHANGES,
+            "rename branch",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn unstage_item(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn delete_branch_popup(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Unstage Item [{}]",
-                key_config.get_hint(key_config.enter),
+                "Delete [{}]",
+                key_config.get_hint(key_config.delete_branch),
             ),
-            "unstage currently selected file or entire path",
-            CMD_GROUP_CHANGES,
+            "delete a branch",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn unstage_all(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn delete_marked_branches(
+        key_config: &SharedKeyConfig,
+        count: usize,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Unstage all [{}]",
-                key_config.get_hint(key_config.status_stage_all),
+                "Delete {} marked [{}]",
+                count,
+                key_config.get_hint(key_config.delete_branch),
             ),
-            "unstage all files (in staged files)",
-            CMD_GROUP_CHANGES,
+            "delete the marked branches",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn reset_item(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn delete_merged_branches(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Reset Item [{}]",
-                key_config.get_hint(key_config.status_reset_item),
+                "Delete merged [{}]",
+                key_config.get_hint(key_config.branch_delete_merged),
             ),
-            "revert changes in selected file or entire path",
-            CMD_GROUP_CHANGES,
+            "delete the local branches merged into the current one",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn ignore_item(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn select_branch_popup(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Ignore [{}]",
-                key_config.get_hint(key_config.status_ignore_file),
+                "Checkout [{}]",
+                key_config.get_hint(key_config.enter),
             ),
-            "Add file or path to .gitignore",
-            CMD_GROUP_CHANGES,
+            "checkout branch",
+            CMD_GROUP_GENERAL,
         )
     }
-
-    pub fn diff_focus_left(
+    pub fn edit_branch_description(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Back [{}]",
-                key_config.get_hint(key_config.focus_left),
+                "Description [{}]",
+                key_config
+                    .get_hint(key_config.edit_branch_description),
             ),
-            "view and select changed files",
+            "edit the description of the branch",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn diff_focus_right(
+    pub fn rebase_branch_popup(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Diff [{}]",
-                key_config.get_hint(key_config.focus_right),
+                "Rebase onto [{}]",
+                key_config.get_hint(key_config.rebase_branch),
             ),
-            "inspect file diff",
+            "rebase the current branch onto the selected one",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn quit(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn toggle_branch_popup(
+        key_config: &SharedKeyConfig,
+        local: bool,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Quit [{}]",
-                key_config.get_hint(key_config.exit),
+                "{} Branches [{}]",
+                if local { "Remote" } else { "Local" },
+                key_config
+                    .get_hint(key_config.toggle_remote_branches),
             ),
-            "quit gitui application",
+            "toggle branch type (remote/local)",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn confirm_action(
+    pub fn branch_sort(
         key_config: &SharedKeyConfig,
+        by_date: bool,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Confirm [{}]",
-                key_config.get_hint(key_config.enter),
+                "Sort: {} [{}]",
+                if by_date { "Date" } else { "Name" },
+                key_config.get_hint(key_config.branch_sort),
             ),
-            "confirm action",
+            "sort the branches by name or by their last commit, newest first (favorites stay on top)",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn stashing_save(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn branch_find(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Save [{}]",
-                key_config.get_hint(key_config.stashing_save),
+                "Find [{}]",
+                key_config.get_hint(key_config.branch_find),
             ),
-            "opens stash name input popup",
-            CMD_GROUP_STASHING,
+            "fuzzy find a branch by typing parts of its name",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn stashing_toggle_indexed(
+    pub fn branch_find_jump(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Toggle Staged [{}]",
-                key_config.get_hint(key_config.stashing_toggle_index),
+                "Jump [{}]",
+                key_config.get_hint(key_config.enter),
             ),
-            "toggle including staged files into stash",
-            CMD_GROUP_STASHING,
+            "select the found branch in the whole list",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn stashing_toggle_untracked(
+    pub fn favorite_branch_popup(
         key_config: &SharedKeyConfig,
+        favorite: bool,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Toggle Untracked [{}]",
-                key_config
-                    .get_hint(key_config.stashing_toggle_untracked),
+                "{} [{}]",
+                if favorite { "Unfavorite" } else { "Favorite" },
+                key_config.get_hint(key_config.favorite_branch),
             ),
-            "toggle including untracked files into stash",
-            CMD_GROUP_STASHING,
+            "pin branch to the top of the list",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn stashing_confirm_msg(
+    pub fn merge_branch_popup(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Stash [{}]",
-                key_config.get_hint(key_config.enter),
+                "Merge [{}]",
+                key_config.get_hint(key_config.merge_branch),
             ),
-            "save files to stash",
-            CMD_GROUP_STASHING,
+            "merge branch into the current one",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn stashlist_apply(
+    pub fn branch_set_upstream_popup(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Apply [{}]",
-                key_config.get_hint(key_config.enter),
+                "Set Upstream [{}]",
+                key_config.get_hint(key_config.branch_set_upstream),
             ),
-            "apply selected stash",
-            CMD_GROUP_STASHES,
+            "pick the remote branch the branch tracks",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn stashlist_drop(
+    pub fn branch_unset_upstream(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Drop [{}]",
-                key_config.get_hint(key_config.stash_drop),
+                "Unset Upstream [{}]",
+                key_config.get_hint(key_config.branch_unset_upstream),
             ),
-            "drop selected stash",
-            CMD_GROUP_STASHES,
+            "stop tracking the upstream branch",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn stashlist_inspect(
+    pub fn branch_upstream_confirm(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Inspect [{}]",
-                key_config.get_hint(key_config.focus_right),
+                "Track [{}]",
+                key_config.get_hint(key_config.enter),
             ),
-            "open stash commit details (allows to diff files)",
-            CMD_GROUP_STASHES,
+            "track the selected remote branch",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn log_details_toggle(
+    pub fn merge_branch_confirm(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Details [{}]",
+                "Merge [{}]",
                 key_config.get_hint(key_config.enter),
             ),
-            "open details of selected commit",
-            CMD_GROUP_LOG,
+            "merge with the selected strategy",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn log_details_open(
+    pub fn open_branch_select_popup(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Inspect [{}]",
-                key_config.get_hint(key_config.focus_right),
+                "Branches [{}]",
+                key_config.get_hint(key_config.select_branch),
             ),
-            "inspect selected commit in detail",
-            CMD_GROUP_LOG,
+            "open select branch popup",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn log_tag_commit(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+
+    pub fn status_push(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Tag [{}]",
-                key_config.get_hint(key_config.log_tag_commit),
+                "Push [{}]",
+                key_config.get_hint(key_config.push),
             ),
-            "tag commit",
-            CMD_GROUP_LOG,
+            "push to origin",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn tag_commit_confirm_msg(
+    pub fn status_push_to(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Tag [{}]",
-                key_config.get_hint(key_config.enter),
+                "Push To [{}]",
+                key_config.get_hint(key_config.push_to),
             ),
-            "tag commit",
-            CMD_GROUP_LOG,
+            "push to a chosen remote and branch",
+            CMD_GROUP_GENERAL,
         )
     }
-    pub fn create_branch_confirm_msg(
+    pub fn push_target_select_remote(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Create Branch [{}]",
+                "Select Remote [{}]",
                 key_config.get_hint(key_config.enter),
             ),
-            "create branch",
+            "push to the selected remote",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn open_branch_create_popup(
+    pub fn push_target_confirm(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Create [{}]",
-                key_config.get_hint(key_config.create_branch),
+                "Push [{}]",
+                key_config.get_hint(key_config.enter),
             ),
-            "open create branch popup",
+            "push to the entered remote branch",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn rename_branch_confirm_msg(
+    pub fn push_toggle_upstream(
         key_config: &SharedKeyConfig,
+        set_upstream: bool,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Rename Branch [{}]",
-                key_config.get_hint(key_config.enter),
+                "{} [{}]",
+                if set_upstream {
+                    "Keep Upstream"
+                } else {
+                    "Set Upstream"
+                },
+                key_config.get_hint(key_config.push_toggle_upstream),
             ),
-            "rename branch",
+            "track the remote branch after the push",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn rename_branch_popup(
+    pub fn status_force_push(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Rename Branch [{}]",
-                key_config.get_hint(key_config.rename_branch),
+                "Force Push [{}]",
+                key_config.get_hint(key_config.force_push),
             ),
-            "rename branch",
+            "force push to origin",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn delete_branch_popup(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn status_pull(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Delete [{}]",
-                key_config.get_hint(key_config.delete_branch),
+                "Pull [{}]",
+                key_config.get_hint(key_config.pull),
             ),
-            "delete a branch",
+            "fetch/merge",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn select_branch_popup(
+    pub fn status_pull_strategy(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Checkout [{}]",
-                key_config.get_hint(key_config.enter),
+                "Pull With [{}]",
+                key_config.get_hint(key_config.pull_strategy),
             ),
-            "checkout branch",
+            "pick merge, rebase or fast-forward only for this pull",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn toggle_branch_popup(
+    pub fn status_fetch_all(
         key_config: &SharedKeyConfig,
-        local: bool,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "{} Branches [{}]",
-                if local { "Remote" } else { "Local" },
-                key_config
-                    .get_hint(key_config.toggle_remote_branches),
+                "Fetch All [{}]",
+                key_config.get_hint(key_config.fetch_all),
             ),
-            "toggle branch type (remote/local)",
+            "fetch all remotes and prune deleted remote branches",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn open_branch_select_popup(
+    pub fn pull_strategy_confirm(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Branches [{}]",
-                key_config.get_hint(key_config.select_branch),
+                "Pull [{}]",
+                key_config.get_hint(key_config.enter),
             ),
-            "open select branch popup",
+            "pull with the selected strategy",
             CMD_GROUP_GENERAL,
         )
     }
-
-    pub fn status_push(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn status_compare_upstream(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Push [{}]",
-                key_config.get_hint(key_config.push),
+                "Review Push [{}]",
+                key_config.get_hint(key_config.compare_upstream),
             ),
-            "push to origin",
+            "combined diff of all commits not in upstream",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn status_force_push(
+    pub fn status_select_identity(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Force Push [{}]",
-                key_config.get_hint(key_config.force_push),
+                "Identity [{}]",
+                key_config.get_hint(key_config.select_identity),
             ),
-            "force push to origin",
+            "switch user name/email of this repo",
             CMD_GROUP_GENERAL,
         )
     }
-    pub fn status_pull(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn identity_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
             format!(
-                "Pull [{}]",
-                key_config.get_hint(key_config.pull),
+                "Use [{}]",
+                key_config.get_hint(key_config.enter),
             ),
-            "fetch/merge",
+            "write selected identity to the repo config",
             CMD_GROUP_GENERAL,
         )
     }
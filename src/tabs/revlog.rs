@@ -2,21 +2,26 @@ use crate::{
     components::{
         visibility_blocking, CommandBlocking, CommandInfo,
         CommitDetailsComponent, CommitList, Component,
-        DrawableComponent,
+        DrawableComponent, InputType, TextInputComponent,
     },
     keys::SharedKeyConfig,
-    queue::{InternalEvent, Queue},
+    options::SharedOptions,
+    queue::{Action, InternalEvent, NeedsUpdate, Queue},
     strings,
     ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
     cached,
-    sync::{self, CommitId},
-    AsyncLog, AsyncNotification, AsyncTags, FetchStatus, CWD,
+    sync::{
+        self, CommitId, LogScope, LogSearch, LogWalkerFilter,
+        PushTagsTarget, RefsState,
+    },
+    AsyncCommitFilter, AsyncLog, AsyncNotification, AsyncTags,
+    CommitFilterRequest, FetchStatus, CWD,
 };
 use crossbeam_channel::Sender;
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyEvent};
 use std::time::Duration;
 use sync::CommitTags;
 use tui::{
@@ -33,10 +38,28 @@ pub struct Revlog {
     list: CommitList,
     git_log: AsyncLog,
     git_tags: AsyncTags,
+    git_filter: AsyncCommitFilter,
+    search_input: TextInputComponent,
+    search: Option<LogSearch>,
+    /// positions of the commits matching `search`, `None` while the
+    /// search is running
+    search_matches: Option<Vec<usize>>,
+    /// the log is walked with `search` as filter
+    filter_mode: bool,
+    /// select the first match once the search is done
+    jump_to_match: bool,
+    /// position to select once the log is loaded that far
+    pending_selection: Option<usize>,
+    goto_input: TextInputComponent,
+    /// commit to select once the log is loaded up to it
+    pending_jump: Option<CommitId>,
     queue: Queue,
     visible: bool,
     branch_name: cached::BranchName,
+    /// the loaded commits show the refs of this state
+    refs_state: Option<RefsState>,
     key_config: SharedKeyConfig,
+    options: SharedOptions,
 }
 
 impl Revlog {
@@ -46,6 +69,7 @@ impl Revlog {
         sender: &Sender<AsyncNotification>,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
         Self {
             queue: queue.clone(),
@@ -54,17 +78,44 @@ impl Revlog {
                 sender,
                 theme.clone(),
                 key_config.clone(),
+                options.clone(),
             ),
             list: CommitList::new(
                 &strings::log_title(&key_config),
-                theme,
+                theme.clone(),
                 key_config.clone(),
+                options.clone(),
             ),
             git_log: AsyncLog::new(sender),
             git_tags: AsyncTags::new(sender),
+            git_filter: AsyncCommitFilter::new(sender),
+            search_input: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                &strings::log_search_title(&key_config),
+                &strings::log_search_msg(&key_config),
+                false,
+            )
+            .with_input_type(InputType::Singleline),
+            goto_input: TextInputComponent::new(
+                theme,
+                key_config.clone(),
+                &strings::log_goto_title(&key_config),
+                &strings::log_goto_msg(&key_config),
+                false,
+            )
+            .with_input_type(InputType::Singleline),
+            pending_jump: None,
+            search: None,
+            search_matches: None,
+            filter_mode: false,
+            jump_to_match: false,
+            pending_selection: None,
             visible: false,
             branch_name: cached::BranchName::new(CWD),
+            refs_state: None,
             key_config,
+            options,
         }
     }
 
@@ -72,21 +123,44 @@ impl Revlog {
     pub fn any_work_pending(&self) -> bool {
         self.git_log.is_pending()
             || self.git_tags.is_pending()
+            || self.git_filter.is_pending()
             || self.commit_details.any_work_pending()
     }
 
     ///
     pub fn update(&mut self) -> Result<()> {
         if self.visible {
+            let refs_changed = self.check_refs()?;
             let log_changed =
                 self.git_log.fetch()? == FetchStatus::Started;
+            if log_changed && !self.filter_mode {
+                self.request_search()?;
+            }
+            self.take_search_result()?;
+            self.take_jump_target()?;
+
+            // read on batch by batch until the jump target shows up
+            let wanted = if self.pending_jump.is_some() {
+                self.git_log.count()?
+            } else {
+                self.pending_selection
+                    .unwrap_or_else(|| self.list.selection())
+            };
+            self.git_log.want(wanted);
 
             self.list.set_count_total(self.git_log.count()?);
+            if let Some(index) = self.pending_selection {
+                if self.list.select(index) {
+                    self.pending_selection = None;
+                }
+            }
+            self.update_search_title();
 
             let selection = self.list.selection();
             let selection_max = self.list.selection_max();
             if self.list.items().needs_data(selection, selection_max)
                 || log_changed
+                || refs_changed
             {
                 self.fetch_commits()?;
             }
@@ -94,14 +168,28 @@ impl Revlog {
             self.git_tags.request(Duration::from_secs(3), false)?;
 
             self.list.set_branch(
-                self.branch_name.lookup().map(Some).unwrap_or(None),
+                self.branch_name.lookup().ok().or_else(|| {
+                    sync::get_detached_head(CWD).ok().flatten().map(
+                        |id| {
+                            strings::detached_head_label(
+                                &self.key_config,
+                                &id.get_short_string(),
+                            )
+                        },
+                    )
+                }),
             );
 
             if self.commit_details.is_visible() {
                 let commit = self.selected_commit();
                 let tags = self.selected_commit_tags(&commit);
 
-                self.commit_details.set_commit(commit, tags)?;
+                let prefetch = self.list.ids_around_selection(
+                    self.options.commit_details_prefetch,
+                );
+
+                self.commit_details
+                    .set_commit(commit, tags, &prefetch)?;
             }
         }
 
@@ -116,6 +204,8 @@ impl Revlog {
         if self.visible {
             match ev {
                 AsyncNotification::CommitFiles
+                | AsyncNotification::CommitDetails
+                | AsyncNotification::CommitFilter
                 | AsyncNotification::Log => self.update()?,
                 AsyncNotification::Tags => {
                     if let Some(tags) = self.git_tags.last()? {
@@ -130,6 +220,24 @@ impl Revlog {
         Ok(())
     }
 
+    /// whether a branch or tag got moved, created or deleted, the
+    /// log of all branches is walked anew then (otherwise only a
+    /// change of HEAD does)
+    fn check_refs(&mut self) -> Result<bool> {
+        let refs_state = sync::get_refs_state(CWD).ok();
+        let changed = refs_state != self.refs_state;
+
+        if changed
+            && self.refs_state.is_some()
+            && self.list.all_branches()
+        {
+            self.git_log.set_scope(self.log_scope())?;
+        }
+        self.refs_state = refs_state;
+
+        Ok(changed)
+    }
+
     fn fetch_commits(&mut self) -> Result<()> {
         let want_min =
             self.list.selection().saturating_sub(SLICE_SIZE / 2);
@@ -141,16 +249,664 @@ impl Revlog {
         );
 
         if let Ok(commits) = commits {
-            self.list.items().set_items(want_min, commits);
+            let graph =
+                self.git_log.get_graph_slice(want_min, SLICE_SIZE)?;
+            self.list.items().set_items(want_min, commits, graph);
+        }
+
+        Ok(())
+    }
+
+    /// keys toggling what the log shows, `Ok(false)` if not used
+    fn toggle_event(&mut self, k: KeyEvent) -> Result<bool> {
+        if k == self.key_config.log_toggle_no_merges {
+            self.list.set_no_merges(!self.list.no_merges());
+            self.refilter()?;
+        } else if k == self.key_config.log_toggle_all_branches {
+            self.list.set_all_branches(!self.list.all_branches());
+            self.git_log.set_scope(self.log_scope())?;
+            self.refilter()?;
+        } else if k == self.key_config.log_toggle_first_parent {
+            self.list.set_first_parent(!self.list.first_parent());
+            self.git_log
+                .set_first_parent(self.list.first_parent())?;
+            self.refilter()?;
+        } else if k == self.key_config.toggle_time_zone {
+            let zone = &self.options.time_zone;
+            zone.set(zone.get().next());
+        } else {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    fn log_scope(&self) -> LogScope {
+        if self.list.all_branches() {
+            LogScope::All {
+                remotes: self.options.log_all_remotes,
+            }
+        } else {
+            LogScope::Head
+        }
+    }
+
+    fn walk_filter(&self) -> Option<LogWalkerFilter> {
+        let mut filters = Vec::new();
+        if self.list.no_merges() {
+            filters.push(sync::no_merges_filter());
+        }
+        if let (true, Some(search)) = (self.filter_mode, &self.search)
+        {
+            filters.push(sync::log_search_filter(search.clone()));
+        }
+        sync::combine_filters(filters)
+    }
+
+    /// walks the log anew, `update` searches it again
+    fn refilter(&mut self) -> Result<()> {
+        self.list.reset();
+        self.pending_selection = None;
+        self.git_log.set_filter(self.walk_filter())?;
+
+        self.update()
+    }
+
+    /// nothing to search for with the log filtered by the search
+    fn request_search(&mut self) -> Result<()> {
+        self.search_matches = None;
+        self.list.set_highlights(None);
+
+        match (&self.search, self.filter_mode) {
+            (Some(search), false) => {
+                self.git_filter.request(CommitFilterRequest {
+                    search: search.clone(),
+                    walk_filter: self.walk_filter(),
+                    scope: self.log_scope(),
+                    first_parent: self.list.first_parent(),
+                })?;
+            }
+            _ => self.git_filter.cancel(),
+        }
+
+        Ok(())
+    }
+
+    fn take_search_result(&mut self) -> Result<()> {
+        if let Some(res) = self.git_filter.take_last_result()? {
+            if self.search.as_ref() != Some(&res.search) {
+                return Ok(());
+            }
+
+            if std::mem::take(&mut self.jump_to_match) {
+                let selection = self.list.selection();
+                self.pending_selection = res
+                    .matches
+                    .iter()
+                    .find(|m| **m >= selection)
+                    .or_else(|| res.matches.first())
+                    .copied();
+            }
+
+            self.list.set_highlights(Some(res.ids));
+            self.search_matches = Some(res.matches);
+        }
+
+        Ok(())
+    }
+
+    fn update_search_title(&mut self) {
+        let title = self.search.as_ref().map(|search| {
+            if self.filter_mode {
+                return strings::log_title_filter(
+                    &self.key_config,
+                    &search.query,
+                );
+            }
+
+            let selection = self.list.selection();
+            let position = self.search_matches.as_ref().map(|m| {
+                (
+                    m.iter().take_while(|m| **m <= selection).count(),
+                    m.len(),
+                )
+            });
+            strings::log_title_search(
+                &self.key_config,
+                &search.query,
+                position,
+            )
+        });
+
+        self.list.set_search_title(title);
+    }
+
+    fn confirm_search(&mut self) -> Result<()> {
+        self.search_input.hide();
+
+        match LogSearch::parse(self.search_input.get_text()) {
+            Some(search) => {
+                self.search = Some(search);
+                self.jump_to_match = !self.filter_mode;
+                if self.filter_mode {
+                    self.refilter()
+                } else {
+                    self.request_search()?;
+                    self.update()
+                }
+            }
+            None => self.clear_search(),
+        }
+    }
+
+    fn clear_search(&mut self) -> Result<()> {
+        self.search = None;
+        self.jump_to_match = false;
+        self.search_input.clear();
+
+        if self.filter_mode {
+            self.filter_mode = false;
+            self.refilter()
+        } else {
+            self.request_search()?;
+            self.update()
+        }
+    }
+
+    fn toggle_filter_mode(&mut self) -> Result<()> {
+        self.filter_mode = !self.filter_mode;
+        self.refilter()
+    }
+
+    /// wraps around at the end of the log
+    fn select_match(&mut self, forward: bool) -> Result<()> {
+        let selection = self.list.selection();
+
+        let target = self.search_matches.as_ref().and_then(|m| {
+            if forward {
+                m.iter()
+                    .find(|m| **m > selection)
+                    .or_else(|| m.first())
+                    .copied()
+            } else {
+                m.iter()
+                    .rev()
+                    .find(|m| **m < selection)
+                    .or_else(|| m.last())
+                    .copied()
+            }
+        });
+
+        if let Some(index) = target {
+            self.pending_selection = Some(index);
+            self.update()?;
+        }
+
+        Ok(())
+    }
+
+    /// selects the commit to jump to once it is loaded, the log is
+    /// read on until then
+    fn take_jump_target(&mut self) -> Result<()> {
+        if let Some(id) = self.pending_jump {
+            let done = self.git_log.is_done();
+
+            if let Some(index) = self.git_log.position(id)? {
+                self.pending_jump = None;
+                self.pending_selection = Some(index);
+            } else if done {
+                self.pending_jump = None;
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(
+                        strings::log_goto_not_found(
+                            &self.key_config,
+                            &id.get_short_string(),
+                        ),
+                    ),
+                );
+            }
         }
 
         Ok(())
     }
 
+    /// an invalid revision keeps the input open
+    fn confirm_goto(&mut self) -> Result<()> {
+        let spec = self.goto_input.get_text().trim().to_string();
+
+        if spec.is_empty() {
+            self.goto_input.hide();
+            return Ok(());
+        }
+
+        match sync::resolve_commit(CWD, &spec) {
+            Ok(id) => {
+                self.goto_input.hide();
+                self.goto_input.clear();
+                self.pending_jump = Some(id);
+                self.update()
+            }
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "go to commit error:\n{}",
+                        e
+                    )),
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// events of the search and goto inputs, `Ok(false)` if neither
+    /// is open
+    fn input_event(&mut self, ev: Event) -> Result<bool> {
+        let goto = self.goto_input.is_visible();
+        let input = if goto {
+            &mut self.goto_input
+        } else if self.search_input.is_visible() {
+            &mut self.search_input
+        } else {
+            return Ok(false);
+        };
+
+        if !input.event(ev)? {
+            if let Event::Key(k) = ev {
+                if k == self.key_config.enter {
+                    if goto {
+                        self.confirm_goto()?;
+                    } else {
+                        self.confirm_search()?;
+                    }
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn search_input_commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        out.clear();
+        if self.goto_input.is_visible() {
+            self.goto_input.commands(out, force_all);
+            out.push(CommandInfo::new(
+                strings::commands::log_goto_confirm(&self.key_config),
+                true,
+                true,
+            ));
+        } else {
+            self.search_input.commands(out, force_all);
+            out.push(CommandInfo::new(
+                strings::commands::log_search_confirm(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    /// operations on the selected commit
+    fn selected_commit_commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) {
+        out.push(CommandInfo::new(
+            strings::commands::log_mark_compare(
+                &self.key_config,
+                self.list.marked().is_some(),
+            ),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_view_object(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::rebase_interactive(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::cherry_pick(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::toggle_mark(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_squash_marked(&self.key_config),
+            self.list.marks().len() > 1,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_reword(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_commit_fixup(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_autosquash(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_reset_commit(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::drop_commit(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::revert_commit(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::open_bisect(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+    }
+
+    /// the commands of `toggle_event`
+    fn toggle_commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) {
+        out.push(CommandInfo::new(
+            strings::commands::log_toggle_no_merges(
+                &self.key_config,
+                self.list.no_merges(),
+            ),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_toggle_all_branches(
+                &self.key_config,
+                self.list.all_branches(),
+            ),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_toggle_first_parent(
+                &self.key_config,
+                self.list.first_parent(),
+            ),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::toggle_time_zone(
+                &self.key_config,
+                self.options.time_zone.get(),
+            ),
+            true,
+            self.visible || force_all,
+        ));
+    }
+
+    fn search_commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) {
+        let searching = self.visible && self.search.is_some();
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_goto(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search_step(&self.key_config),
+            self.search_matches
+                .as_ref()
+                .map_or(false, |m| !m.is_empty()),
+            searching || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search_filter(
+                &self.key_config,
+                self.filter_mode,
+            ),
+            true,
+            searching || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search_clear(&self.key_config),
+            true,
+            searching || force_all,
+        ));
+    }
+
+    /// keys of the log search and goto, `Ok(false)` if not used
+    fn search_event(&mut self, k: KeyEvent) -> Result<bool> {
+        if k == self.key_config.log_search {
+            self.search_input.show()?;
+        } else if k == self.key_config.log_goto {
+            self.goto_input.show()?;
+        } else if self.search.is_none() {
+            return Ok(false);
+        } else if k == self.key_config.log_search_next {
+            self.select_match(true)?;
+        } else if k == self.key_config.log_search_prev {
+            self.select_match(false)?;
+        } else if k == self.key_config.log_search_filter {
+            self.toggle_filter_mode()?;
+        } else if k == self.key_config.exit_popup {
+            self.clear_search()?;
+        } else {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
     fn selected_commit(&self) -> Option<CommitId> {
         self.list.selected_entry().map(|e| e.id)
     }
 
+    /// marking a second commit compares it with the first one
+    fn mark_compare(&mut self) {
+        if let Some(id) = self.selected_commit() {
+            match self.list.marked() {
+                Some(marked) if marked != id => {
+                    self.list.set_marked(None);
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::CompareCommits(marked, id),
+                    );
+                }
+                Some(_) => self.list.set_marked(None),
+                None => self.list.set_marked(Some(id)),
+            }
+        }
+    }
+
+    /// keys marking, cherry-picking and squashing commits,
+    /// `false` if not used
+    fn rewrite_event(&mut self, k: KeyEvent) -> bool {
+        if k == self.key_config.cherry_pick {
+            self.cherry_pick()
+        } else if k == self.key_config.rebase_squash
+            && self.list.marks().len() > 1
+        {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::SquashCommits(self.list.marks()),
+            );
+            self.list.clear_marks();
+            true
+        } else if k == self.key_config.log_reset_commit {
+            self.queue_for_selected(InternalEvent::OpenResetCommit)
+        } else if k == self.key_config.rebase_drop {
+            self.drop_commit()
+        } else if k == self.key_config.rebase_reword {
+            self.queue_for_selected(InternalEvent::RewordCommit)
+        } else if k == self.key_config.commit_fixup
+            || k == self.key_config.commit_squash
+        {
+            let squash = k == self.key_config.commit_squash;
+            self.queue_for_selected(|id| {
+                InternalEvent::CommitFixup(id, squash)
+            })
+        } else if k == self.key_config.autosquash {
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::OpenAutosquash);
+            true
+        } else if k == self.key_config.toggle_mark {
+            self.list.toggle_mark()
+        } else if k == self.key_config.mark_range {
+            self.list.mark_range()
+        } else {
+            false
+        }
+    }
+
+    /// the marked commits, or the selected one if none is marked
+    fn cherry_pick(&mut self) -> bool {
+        let marks = self.list.marks();
+        if marks.is_empty() {
+            return self.queue_for_selected(|id| {
+                InternalEvent::CherryPick(vec![id])
+            });
+        }
+
+        self.list.clear_marks();
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::CherryPick(marks));
+        true
+    }
+
+    /// the marked commits oldest first, the selected one otherwise
+    fn export_patches(&mut self) -> bool {
+        let marks = self.list.marks();
+        if marks.is_empty() {
+            return self.queue_for_selected(|id| {
+                InternalEvent::ExportPatches(vec![id])
+            });
+        }
+
+        self.list.clear_marks();
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::ExportPatches(marks));
+        true
+    }
+
+    /// named after the short id
+    fn archive(&self) -> bool {
+        self.queue_for_selected(|id| {
+            InternalEvent::OpenArchive(id, id.get_short_string())
+        })
+    }
+
+    /// confirmed first, merge commits and those of other branches
+    /// are refused right away
+    fn drop_commit(&self) -> bool {
+        self.queue_for_selected(|id| {
+            match sync::check_drop_commit(CWD, id) {
+                Ok(pushed) => InternalEvent::ConfirmAction(
+                    Action::DropCommit(id, pushed),
+                ),
+                Err(e) => InternalEvent::ShowErrorMsg(format!(
+                    "drop commit error:\n{}",
+                    e
+                )),
+            }
+        })
+    }
+
+    /// detaches HEAD, uncommitted changes are refused
+    fn checkout_commit(&self) -> bool {
+        self.selected_commit().map_or(false, |id| {
+            let event = match sync::checkout_commit(CWD, id) {
+                Ok(()) => InternalEvent::Update(NeedsUpdate::ALL),
+                Err(e) => InternalEvent::ShowErrorMsg(format!(
+                    "checkout error:\n{}",
+                    e
+                )),
+            };
+            self.queue.borrow_mut().push_back(event);
+            true
+        })
+    }
+
+    /// `false` without a selected commit
+    fn queue_for_selected(
+        &self,
+        event: impl FnOnce(CommitId) -> InternalEvent,
+    ) -> bool {
+        self.selected_commit().map_or(false, |id| {
+            self.queue.borrow_mut().push_back(event(id));
+            true
+        })
+    }
+
+    /// keys opening a popup regardless of the selection
+    fn popup_for_key(&self, k: KeyEvent) -> Option<InternalEvent> {
+        if k == self.key_config.push {
+            Some(InternalEvent::PushTags(PushTagsTarget::Missing))
+        } else if k == self.key_config.search_branches {
+            Some(InternalEvent::SearchBranches)
+        } else if k == self.key_config.open_reflog {
+            Some(InternalEvent::OpenReflog)
+        } else if k == self.key_config.open_tags {
+            Some(InternalEvent::OpenTags)
+        } else if k == self.key_config.select_branch {
+            Some(InternalEvent::SelectBranch)
+        } else {
+            None
+        }
+    }
+
     fn copy_commit_hash(&self) -> Result<()> {
         self.list.copy_entry_hash()?;
         Ok(())
@@ -192,6 +948,9 @@ impl DrawableComponent for Revlog {
             self.list.draw(f, area)?;
         }
 
+        self.search_input.draw(f, area)?;
+        self.goto_input.draw(f, area)?;
+
         Ok(())
     }
 }
@@ -199,6 +958,10 @@ impl DrawableComponent for Revlog {
 impl Component for Revlog {
     fn event(&mut self, ev: Event) -> Result<bool> {
         if self.visible {
+            if self.input_event(ev)? {
+                return Ok(true);
+            }
+
             let event_used = self.list.event(ev)?;
 
             if event_used {
@@ -209,24 +972,29 @@ impl Component for Revlog {
                     self.commit_details.toggle_visible()?;
                     self.update()?;
                     return Ok(true);
+                } else if self.search_event(k)? {
+                    return Ok(true);
                 } else if k == self.key_config.copy {
                     self.copy_commit_hash()?;
                     return Ok(true);
-                } else if k == self.key_config.push {
-                    self.queue
-                        .borrow_mut()
-                        .push_back(InternalEvent::PushTags);
-                    return Ok(true);
                 } else if k == self.key_config.log_tag_commit {
-                    return self.selected_commit().map_or(
-                        Ok(false),
-                        |id| {
-                            self.queue.borrow_mut().push_back(
-                                InternalEvent::TagCommit(id),
-                            );
-                            Ok(true)
-                        },
-                    );
+                    return Ok(self.queue_for_selected(
+                        InternalEvent::TagCommit,
+                    ));
+                } else if k == self.key_config.log_checkout_commit {
+                    return Ok(self.checkout_commit());
+                } else if k == self.key_config.create_branch {
+                    return Ok(self.queue_for_selected(
+                        InternalEvent::CreateBranchAt,
+                    ));
+                } else if k == self.key_config.log_export_patches {
+                    return Ok(self.export_patches());
+                } else if k == self.key_config.archive {
+                    return Ok(self.archive());
+                } else if k == self.key_config.log_browse_files {
+                    return Ok(self.queue_for_selected(
+                        InternalEvent::OpenRevisionFiles,
+                    ));
                 } else if k == self.key_config.focus_right
                     && self.commit_details.is_visible()
                 {
@@ -244,10 +1012,33 @@ impl Component for Revlog {
                             Ok(true)
                         },
                     );
-                } else if k == self.key_config.select_branch {
-                    self.queue
-                        .borrow_mut()
-                        .push_back(InternalEvent::SelectBranch);
+                } else if k == self.key_config.log_mark_compare {
+                    self.mark_compare();
+                    return Ok(true);
+                } else if self.toggle_event(k)? {
+                    return Ok(true);
+                } else if k == self.key_config.view_object {
+                    return Ok(self.queue_for_selected(|id| {
+                        InternalEvent::ViewObject(id.to_string())
+                    }));
+                } else if k == self.key_config.rebase_interactive {
+                    return Ok(self.queue_for_selected(
+                        InternalEvent::OpenRebase,
+                    ));
+                } else if self.rewrite_event(k) {
+                    return Ok(true);
+                } else if k == self.key_config.revert_commit {
+                    return Ok(self.queue_for_selected(|id| {
+                        InternalEvent::ConfirmAction(
+                            Action::RevertCommit(id),
+                        )
+                    }));
+                } else if k == self.key_config.open_bisect {
+                    return Ok(self.queue_for_selected(
+                        InternalEvent::OpenBisect,
+                    ));
+                } else if let Some(event) = self.popup_for_key(k) {
+                    self.queue.borrow_mut().push_back(event);
                     return Ok(true);
                 }
             }
@@ -261,6 +1052,12 @@ impl Component for Revlog {
         out: &mut Vec<CommandInfo>,
         force_all: bool,
     ) -> CommandBlocking {
+        if self.search_input.is_visible()
+            || self.goto_input.is_visible()
+        {
+            return self.search_input_commands(out, force_all);
+        }
+
         if self.visible || force_all {
             self.list.commands(out, force_all);
         }
@@ -280,6 +1077,60 @@ impl Component for Revlog {
 
         out.push(CommandInfo::new(
             strings::commands::log_tag_commit(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_checkout_commit(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_export_patches(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_browse_files(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_archive(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_create_branch(&self.key_config),
+            self.selected_commit().is_some(),
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search_branches(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        self.search_commands(out, force_all);
+
+        self.toggle_commands(out, force_all);
+
+        self.selected_commit_commands(out, force_all);
+
+        out.push(CommandInfo::new(
+            strings::commands::open_reflog(&self.key_config),
+            true,
+            self.visible || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::open_tags(&self.key_config),
             true,
             self.visible || force_all,
         ));
@@ -313,6 +1164,8 @@ impl Component for Revlog {
 
     fn hide(&mut self) {
         self.visible = false;
+        self.search_input.hide();
+        self.goto_input.hide();
         self.git_log.set_background();
     }
 
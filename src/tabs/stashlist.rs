@@ -1,9 +1,10 @@
 use crate::{
     components::{
         visibility_blocking, CommandBlocking, CommandInfo,
-        CommitList, Component, DrawableComponent,
+        CommitList, Component, DiffComponent, DrawableComponent,
     },
     keys::SharedKeyConfig,
+    options::SharedOptions,
     queue::{Action, InternalEvent, Queue},
     strings,
     ui::style::SharedTheme,
@@ -11,12 +12,20 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
     sync::{self, CommitId},
-    CWD,
+    AsyncDiff, AsyncNotification, DiffParams, DiffType, CWD,
 };
+use crossbeam_channel::Sender;
 use crossterm::event::Event;
+use tui::layout::{Constraint, Direction, Layout};
 
+/// the stashes with the diff of the selected one next to them,
+/// scrolled in the inspect popup like the commit details of the log
 pub struct StashList {
     list: CommitList,
+    diff: DiffComponent,
+    git_diff: AsyncDiff,
+    /// apply and pop stage the staged changes again
+    with_index: bool,
     visible: bool,
     queue: Queue,
     key_config: SharedKeyConfig,
@@ -26,16 +35,29 @@ impl StashList {
     ///
     pub fn new(
         queue: &Queue,
+        sender: &Sender<AsyncNotification>,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
         Self {
             visible: false,
             list: CommitList::new(
                 &strings::stashlist_title(&key_config),
+                theme.clone(),
+                key_config.clone(),
+                options.clone(),
+            ),
+            diff: DiffComponent::new(
+                queue.clone(),
+                sender,
                 theme,
                 key_config.clone(),
+                options,
+                true,
             ),
+            git_diff: AsyncDiff::new(sender),
+            with_index: false,
             queue: queue.clone(),
             key_config,
         }
@@ -49,7 +71,61 @@ impl StashList {
                 sync::get_commits_info(CWD, stashes.as_slice(), 100)?;
 
             self.list.set_count_total(commits.len());
-            self.list.items().set_items(0, commits);
+            self.list.items().set_items(0, commits, Vec::new());
+
+            self.update_diff()?;
+        }
+
+        Ok(())
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_diff.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        self.diff.update_git(ev)?;
+
+        if self.visible && ev == AsyncNotification::Diff {
+            self.update_diff()?;
+        }
+
+        Ok(())
+    }
+
+    /// shows the diff of the selected stash
+    pub fn update_diff(&mut self) -> Result<()> {
+        if let Some(err) = self.git_diff.take_last_error()? {
+            self.diff.show_error(err);
+        }
+
+        if let Some(id) = self.list.selected_entry().map(|e| e.id) {
+            let diff_params = DiffParams {
+                path: String::new(),
+                diff_type: DiffType::Stash(id),
+                options: self.diff.options(),
+            };
+
+            if let Some((params, last)) = self.git_diff.last()? {
+                if params == diff_params {
+                    self.diff.update_folder(
+                        id.get_short_string(),
+                        false,
+                        last,
+                    );
+                    return Ok(());
+                }
+            }
+
+            self.git_diff.request(diff_params)?;
+            self.diff.clear(true)?;
+        } else {
+            self.diff.clear(false)?;
         }
 
         Ok(())
@@ -57,7 +133,13 @@ impl StashList {
 
     fn apply_stash(&mut self) {
         if let Some(e) = self.list.selected_entry() {
-            match sync::stash_apply(CWD, e.id, false) {
+            let res = if self.with_index {
+                sync::stash_apply_index(CWD, e.id)
+            } else {
+                sync::stash_apply(CWD, e.id, false)
+            };
+
+            match res {
                 Ok(_) => {
                     self.queue
                         .borrow_mut()
@@ -75,6 +157,34 @@ impl StashList {
         }
     }
 
+    fn pop_stash(&self) {
+        if let Some(e) = self.list.selected_entry() {
+            match sync::stash_pop(CWD, e.id, self.with_index) {
+                Ok(()) => {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::TabSwitch);
+                }
+                Err(e) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(format!(
+                            "stash pop error:\n{}",
+                            e,
+                        )),
+                    );
+                }
+            }
+        }
+    }
+
+    fn branch_stash(&self) {
+        if let Some(e) = self.list.selected_entry() {
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::StashBranch(e.id));
+        }
+    }
+
     fn drop_stash(&mut self) {
         if let Some(e) = self.list.selected_entry() {
             self.queue.borrow_mut().push_back(
@@ -83,6 +193,22 @@ impl StashList {
         }
     }
 
+    fn rename_stash(&self) {
+        if let Some(e) = self.list.selected_entry() {
+            // drop the `On <branch>: ` prefix, it is kept anyway
+            let cur_msg = e
+                .msg
+                .splitn(2, ": ")
+                .nth(1)
+                .unwrap_or(&e.msg)
+                .to_string();
+
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::RenameStash(e.id, cur_msg));
+        }
+    }
+
     fn inspect(&mut self) {
         if let Some(e) = self.list.selected_entry() {
             self.queue
@@ -103,7 +229,19 @@ impl DrawableComponent for StashList {
         f: &mut tui::Frame<B>,
         rect: tui::layout::Rect,
     ) -> Result<()> {
-        self.list.draw(f, rect)?;
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                [
+                    Constraint::Percentage(40),
+                    Constraint::Percentage(60),
+                ]
+                .as_ref(),
+            )
+            .split(rect);
+
+        self.list.draw(f, chunks[0])?;
+        self.diff.draw(f, chunks[1])?;
 
         Ok(())
     }
@@ -125,11 +263,34 @@ impl Component for StashList {
                 selection_valid,
                 true,
             ));
+            out.push(CommandInfo::new(
+                strings::commands::stashlist_pop(&self.key_config),
+                selection_valid,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::stashlist_toggle_index(
+                    &self.key_config,
+                    self.with_index,
+                ),
+                true,
+                true,
+            ));
             out.push(CommandInfo::new(
                 strings::commands::stashlist_drop(&self.key_config),
                 selection_valid,
                 true,
             ));
+            out.push(CommandInfo::new(
+                strings::commands::stashlist_branch(&self.key_config),
+                selection_valid,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::stashlist_rename(&self.key_config),
+                selection_valid,
+                true,
+            ));
             out.push(CommandInfo::new(
                 strings::commands::stashlist_inspect(
                     &self.key_config,
@@ -145,14 +306,25 @@ impl Component for StashList {
     fn event(&mut self, ev: crossterm::event::Event) -> Result<bool> {
         if self.visible {
             if self.list.event(ev)? {
+                self.update_diff()?;
                 return Ok(true);
             }
 
             if let Event::Key(k) = ev {
                 if k == self.key_config.enter {
                     self.apply_stash()
+                } else if k == self.key_config.stash_pop {
+                    self.pop_stash();
+                } else if k == self.key_config.stashing_toggle_index {
+                    self.with_index = !self.with_index;
+                    // the command text follows the toggle
+                    return Ok(true);
+                } else if k == self.key_config.stash_branch {
+                    self.branch_stash();
                 } else if k == self.key_config.stash_drop {
                     self.drop_stash()
+                } else if k == self.key_config.stash_rename {
+                    self.rename_stash();
                 } else if k == self.key_config.stash_open {
                     self.inspect()
                 } else {
@@ -1,20 +1,23 @@
 use crate::{
     accessors,
     components::{
-        command_pump, event_pump, visibility_blocking,
+        command_pump, event_pump, visibility_blocking, BlameRevision,
         ChangesComponent, CommandBlocking, CommandInfo, Component,
         DiffComponent, DrawableComponent, FileTreeItemKind,
     },
     keys::SharedKeyConfig,
-    queue::{Action, InternalEvent, Queue, ResetItem},
-    strings,
+    options::SharedOptions,
+    queue::{
+        Action, Advice, InternalEvent, NeedsUpdate, Queue, ResetItem,
+    },
+    strings, try_or_popup,
     ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
     cached,
-    sync::BranchCompare,
     sync::{self, status::StatusType, RepoState},
+    sync::{BranchCompare, CommitId, Identity},
     AsyncDiff, AsyncNotification, AsyncStatus, DiffParams, DiffType,
     StatusParams, CWD,
 };
@@ -24,6 +27,7 @@ use std::convert::TryFrom;
 use tui::{
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Style},
+    text::{Span, Spans},
     widgets::Paragraph,
 };
 
@@ -65,8 +69,15 @@ pub struct Status {
     git_status_stage: AsyncStatus,
     git_branch_state: Option<BranchCompare>,
     git_branch_name: cached::BranchName,
+    /// HEAD if it is detached
+    git_detached_head: Option<CommitId>,
+    git_identity: Option<Identity>,
+    /// paths of the last discard that can be undone
+    git_discard_backup: Option<Vec<String>>,
     queue: Queue,
     git_action_executed: bool,
+    options: SharedOptions,
+    theme: SharedTheme,
     key_config: SharedKeyConfig,
 }
 
@@ -116,7 +127,8 @@ impl DrawableComponent for Status {
         self.index.draw(f, left_chunks[1])?;
         self.diff.draw(f, chunks[1])?;
         self.draw_branch_state(f, &left_chunks);
-        Self::draw_repo_state(f, left_chunks[0]);
+        self.draw_identity(f, left_chunks[1]);
+        self.draw_repo_state(f, left_chunks[0]);
 
         Ok(())
     }
@@ -131,7 +143,18 @@ impl Status {
         sender: &Sender<AsyncNotification>,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        options: SharedOptions,
     ) -> Self {
+        let mut diff = DiffComponent::new(
+            queue.clone(),
+            sender,
+            theme.clone(),
+            key_config.clone(),
+            options.clone(),
+            false,
+        );
+        diff.enable_blame(BlameRevision::Head);
+
         Self {
             queue: queue.clone(),
             visible: true,
@@ -153,18 +176,18 @@ impl Status {
                 theme.clone(),
                 key_config.clone(),
             ),
-            diff: DiffComponent::new(
-                queue.clone(),
-                theme,
-                key_config.clone(),
-                false,
-            ),
+            diff,
             git_diff: AsyncDiff::new(sender),
             git_status_workdir: AsyncStatus::new(sender.clone()),
             git_status_stage: AsyncStatus::new(sender.clone()),
             git_action_executed: false,
             git_branch_state: None,
             git_branch_name: cached::BranchName::new(CWD),
+            git_detached_head: None,
+            git_identity: None,
+            git_discard_backup: None,
+            options,
+            theme,
             key_config,
         }
     }
@@ -174,21 +197,42 @@ impl Status {
         f: &mut tui::Frame<B>,
         chunks: &[tui::layout::Rect],
     ) {
-        if let Some(branch_name) = self.git_branch_name.last() {
-            let ahead_behind =
-                if let Some(state) = &self.git_branch_state {
+        let detached = || {
+            self.git_detached_head.map(|id| {
+                Paragraph::new(Span::styled(
                     format!(
-                        "\u{2191}{} \u{2193}{} ",
-                        state.ahead, state.behind,
-                    )
-                } else {
-                    String::new()
-                };
-            let w = Paragraph::new(format!(
-                "{}{{{}}}",
-                ahead_behind, branch_name
-            ))
-            .alignment(Alignment::Right);
+                        "{{{}}}",
+                        strings::detached_head_label(
+                            &self.key_config,
+                            &id.get_short_string(),
+                        )
+                    ),
+                    self.theme.text_danger(),
+                ))
+            })
+        };
+
+        let w = self.git_branch_name.last().map_or_else(
+            detached,
+            |branch_name| {
+                let ahead_behind =
+                    if let Some(state) = &self.git_branch_state {
+                        format!(
+                            "\u{2191}{} \u{2193}{} ",
+                            state.ahead, state.behind,
+                        )
+                    } else {
+                        String::new()
+                    };
+                Some(Paragraph::new(format!(
+                    "{}{{{}}}",
+                    ahead_behind, branch_name
+                )))
+            },
+        );
+
+        if let Some(w) = w {
+            let w = w.alignment(Alignment::Right);
 
             let mut rect = if self.index_wd.focused() {
                 let mut rect = chunks[0];
@@ -208,14 +252,49 @@ impl Status {
         }
     }
 
+    /// who commits would be made as, at the bottom of the stage
+    fn draw_identity<B: tui::backend::Backend>(
+        &self,
+        f: &mut tui::Frame<B>,
+        r: tui::layout::Rect,
+    ) {
+        if let Some(identity) = &self.git_identity {
+            let mut spans = vec![Span::raw(strings::identity(
+                &self.key_config,
+                identity,
+            ))];
+            if !identity.local {
+                spans.push(Span::styled(
+                    " (global)",
+                    self.theme.text_danger(),
+                ));
+            }
+
+            let w = Paragraph::new(Spans::from(spans))
+                .alignment(Alignment::Right);
+
+            let mut rect = r;
+            rect.x += 1;
+            rect.width = rect.width.saturating_sub(2);
+            rect.y += rect.height.saturating_sub(1);
+            rect.height = rect
+                .height
+                .saturating_sub(rect.height.saturating_sub(1));
+
+            f.render_widget(w, rect);
+        }
+    }
+
     fn draw_repo_state<B: tui::backend::Backend>(
+        &self,
         f: &mut tui::Frame<B>,
         r: tui::layout::Rect,
     ) {
         if let Ok(state) = asyncgit::sync::repo_state(CWD) {
             if state != RepoState::Clean {
-                let txt = format!("{:?}", state);
-                let txt_len = u16::try_from(txt.len())
+                let txt =
+                    strings::repo_state_hint(&self.key_config, state);
+                let txt_len = u16::try_from(txt.chars().count())
                     .expect("state name too long");
                 let w = Paragraph::new(txt)
                     .style(Style::default().fg(Color::Red))
@@ -237,8 +316,26 @@ impl Status {
 
     fn can_focus_diff(&self) -> bool {
         match self.focus {
-            Focus::WorkDir => self.index_wd.is_file_seleted(),
-            Focus::Stage => self.index.is_file_seleted(),
+            Focus::WorkDir => {
+                self.index_wd.is_file_seleted()
+                    || self.index_wd.is_expanded_folder_selected()
+            }
+            Focus::Stage => {
+                self.index.is_file_seleted()
+                    || self.index.is_expanded_folder_selected()
+            }
+            Focus::Diff => false,
+        }
+    }
+
+    /// on an expanded folder `focus_right` would select its first
+    /// file (like `move_down`), it focuses the folder's diff instead
+    fn can_focus_folder_diff(&self) -> bool {
+        match self.focus {
+            Focus::WorkDir => {
+                self.index_wd.is_expanded_folder_selected()
+            }
+            Focus::Stage => self.index.is_expanded_folder_selected(),
             Focus::Diff => false,
         }
     }
@@ -276,6 +373,14 @@ impl Status {
         Ok(false)
     }
 
+    /// back to the list the diff belongs to
+    fn focus_file_list(&mut self) -> Result<bool> {
+        self.switch_focus(match self.diff_target {
+            DiffTarget::Stage => Focus::Stage,
+            DiffTarget::WorkingDir => Focus::WorkDir,
+        })
+    }
+
     fn set_diff_target(&mut self, target: DiffTarget) {
         self.diff_target = target;
         let is_stage = self.diff_target == DiffTarget::Stage;
@@ -298,10 +403,36 @@ impl Status {
         None
     }
 
+    fn selected_folder(&self) -> Option<(String, bool)> {
+        let (idx, is_stage) = match self.diff_target {
+            DiffTarget::Stage => (&self.index, true),
+            DiffTarget::WorkingDir => (&self.index_wd, false),
+        };
+
+        idx.selection().and_then(|item| match item.kind {
+            FileTreeItemKind::Path(_) => {
+                Some((item.info.full_path, is_stage))
+            }
+            FileTreeItemKind::File(_) => None,
+        })
+    }
+
     ///
     pub fn update(&mut self) -> Result<()> {
         self.git_branch_name.lookup().map(Some).unwrap_or(None);
 
+        self.git_detached_head =
+            sync::get_detached_head(CWD).ok().flatten();
+        if self.git_detached_head.is_some() {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowAdvice(Advice::DetachedHead),
+            );
+        }
+
+        self.update_identity();
+        self.git_discard_backup =
+            sync::get_discard_backup(CWD).ok().flatten();
+
         if self.is_visible() {
             self.git_diff.refresh()?;
             self.git_status_workdir.fetch(StatusParams::new(
@@ -331,9 +462,11 @@ impl Status {
     ) -> Result<()> {
         match ev {
             AsyncNotification::Diff => self.update_diff()?,
+            AsyncNotification::Syntax => self.diff.update_git(ev)?,
             AsyncNotification::Status => self.update_status()?,
             AsyncNotification::Push
             | AsyncNotification::Fetch
+            | AsyncNotification::FetchAll
             | AsyncNotification::CommitFiles => self.branch_compare(),
             _ => (),
         }
@@ -347,6 +480,12 @@ impl Status {
 
         let workdir_status = self.git_status_workdir.last()?;
         self.index_wd.set_items(&workdir_status.items)?;
+        self.index_wd.set_submodules(
+            sync::get_submodules(CWD).unwrap_or_else(|e| {
+                log::error!("submodules: {}", e);
+                Vec::new()
+            }),
+        );
 
         self.update_diff()?;
 
@@ -370,6 +509,10 @@ impl Status {
 
     ///
     pub fn update_diff(&mut self) -> Result<()> {
+        if let Some(err) = self.git_diff.take_last_error()? {
+            self.diff.show_error(err);
+        }
+
         if let Some((path, is_stage)) = self.selected_path() {
             let diff_type = if is_stage {
                 DiffType::Stage
@@ -380,14 +523,33 @@ impl Status {
             let diff_params = DiffParams {
                 path: path.clone(),
                 diff_type,
+                options: self.diff.options(),
             };
 
             if self.diff.current() == (path.clone(), is_stage) {
                 // we are already showing a diff of the right file
-                // maybe the diff changed (outside file change)
+                // maybe the diff changed (outside file change),
+                // a pending request will be newer than what we show
+                if self.git_diff.is_pending() {
+                    return Ok(());
+                }
+
                 if let Some((params, last)) = self.git_diff.last()? {
                     if params == diff_params {
+                        let hunk_moving = self.diff.is_hunk_moving();
+
                         self.diff.update(path, is_stage, last)?;
+
+                        // the last hunk got (un)staged, nothing left
+                        // to do here
+                        if hunk_moving
+                            && self.is_focus_on_diff()
+                            && self.diff.has_no_hunks()
+                        {
+                            self.focus_file_list()?;
+                        }
+                    } else if params.options != diff_params.options {
+                        self.git_diff.request(diff_params)?;
                     }
                 }
             } else {
@@ -400,6 +562,9 @@ impl Status {
                     self.diff.clear(true)?;
                 }
             }
+        } else if let Some((path, is_stage)) = self.selected_folder()
+        {
+            self.update_folder_diff(path, is_stage)?;
         } else {
             self.diff.clear(false)?;
         }
@@ -407,6 +572,47 @@ impl Status {
         Ok(())
     }
 
+    /// combined diff of all files beneath the folder, computed in
+    /// the background (reporting progress) as it might be many
+    fn update_folder_diff(
+        &mut self,
+        path: String,
+        is_stage: bool,
+    ) -> Result<()> {
+        let diff_params = DiffParams {
+            path: path.clone(),
+            diff_type: if is_stage {
+                DiffType::StageFolder
+            } else {
+                DiffType::WorkDirFolder
+            },
+            options: self.diff.options(),
+        };
+
+        if self.diff.current() == (path.clone(), is_stage) {
+            if self.git_diff.is_pending() {
+                return Ok(());
+            }
+
+            if let Some((params, last)) = self.git_diff.last()? {
+                if params == diff_params {
+                    self.diff.update_folder(path, is_stage, last);
+                } else if params.options != diff_params.options {
+                    self.git_diff.request(diff_params)?;
+                }
+            }
+        } else if let Some(diff) =
+            self.git_diff.request(diff_params)?
+        {
+            self.diff.update_folder(path, is_stage, diff);
+        } else {
+            self.diff.clear(true)?;
+            self.diff.set_progress(self.git_diff.progress()?);
+        }
+
+        Ok(())
+    }
+
     /// called after confirmation
     pub fn reset(&mut self, item: &ResetItem) -> bool {
         if let Err(e) = sync::reset_workdir(CWD, item.path.as_str()) {
@@ -427,9 +633,13 @@ impl Status {
         if self.can_push() {
             if let Some(branch) = self.git_branch_name.last() {
                 if force {
+                    let overwritten =
+                        sync::branch_compare_upstream(CWD, &branch)
+                            .map(|c| c.behind)
+                            .unwrap_or_default();
                     self.queue.borrow_mut().push_back(
                         InternalEvent::ConfirmAction(
-                            Action::ForcePush(branch, force),
+                            Action::ForcePush(branch, overwritten),
                         ),
                     );
                 } else {
@@ -445,7 +655,49 @@ impl Status {
         if let Some(branch) = self.git_branch_name.last() {
             self.queue
                 .borrow_mut()
-                .push_back(InternalEvent::Pull(branch));
+                .push_back(InternalEvent::Pull(branch, None));
+        }
+    }
+
+    fn update_identity(&mut self) {
+        self.git_identity = sync::get_identity(CWD).ok();
+
+        // only worth advising to those set up to switch
+        if let Some(identity) = &self.git_identity {
+            if !identity.local && !self.options.identities.is_empty()
+            {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowAdvice(
+                        Advice::GlobalIdentity(identity.clone()),
+                    ),
+                );
+            }
+        }
+    }
+
+    /// confirmation is optional unless HEAD is pushed already
+    fn amend_no_edit(&self) {
+        let pushed = self
+            .git_branch_state
+            .as_ref()
+            .map_or(false, |state| state.ahead == 0);
+
+        let action = Action::AmendNoEdit { pushed };
+
+        self.queue.borrow_mut().push_back(
+            if pushed || self.options.confirm_amend_no_edit {
+                InternalEvent::ConfirmAction(action)
+            } else {
+                InternalEvent::ConfirmedAction(action)
+            },
+        );
+    }
+
+    fn compare_upstream(&self) {
+        if let Some(branch) = self.git_branch_name.last() {
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::CompareUpstream(branch));
         }
     }
 
@@ -457,14 +709,44 @@ impl Status {
             });
     }
 
+    fn can_amend_no_edit(&self) -> bool {
+        !self.index.is_empty() && sync::get_head(CWD).is_ok()
+    }
+
     fn can_push(&self) -> bool {
         self.git_branch_state
             .as_ref()
             .map_or(true, |state| state.ahead > 0)
     }
+
+    /// merge, rebase, cherry-pick or revert stopped by a conflict,
+    /// all are continued and aborted with the cherry-pick keys
+    fn stopped_state() -> Option<RepoState> {
+        sync::repo_state(CWD)
+            .ok()
+            .filter(|state| sync::is_stopped_state(*state))
+    }
+
+    fn continue_stopped(&self) {
+        try_or_popup!(
+            self,
+            "continue error:",
+            sync::state_continue(CWD)
+        );
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+    }
+
+    fn abort_stopped(&self, state: RepoState) {
+        self.queue.borrow_mut().push_back(
+            InternalEvent::ConfirmAction(Action::AbortState(state)),
+        );
+    }
 }
 
 impl Component for Status {
+    #[allow(clippy::too_many_lines)]
     fn commands(
         &self,
         out: &mut Vec<CommandInfo>,
@@ -492,6 +774,11 @@ impl Component for Status {
                 self.can_push(),
                 !focus_on_diff,
             ));
+            out.push(CommandInfo::new(
+                strings::commands::status_push_to(&self.key_config),
+                true,
+                !focus_on_diff,
+            ));
             out.push(CommandInfo::new(
                 strings::commands::status_force_push(
                     &self.key_config,
@@ -504,6 +791,86 @@ impl Component for Status {
                 true,
                 !focus_on_diff,
             ));
+            out.push(CommandInfo::new(
+                strings::commands::status_pull_strategy(
+                    &self.key_config,
+                ),
+                true,
+                !focus_on_diff,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::status_fetch_all(&self.key_config),
+                true,
+                !focus_on_diff,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::status_compare_upstream(
+                    &self.key_config,
+                ),
+                true,
+                !focus_on_diff,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::status_select_identity(
+                    &self.key_config,
+                ),
+                true,
+                !focus_on_diff,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::commit_amend_no_edit(
+                    &self.key_config,
+                ),
+                self.can_amend_no_edit(),
+                self.focus == Focus::Stage,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::status_apply_patch(
+                    &self.key_config,
+                ),
+                true,
+                !focus_on_diff,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::status_undo_discard(
+                    &self.key_config,
+                ),
+                self.git_discard_backup.is_some(),
+                !focus_on_diff,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::open_submodules(&self.key_config),
+                true,
+                !focus_on_diff,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::open_worktrees(&self.key_config),
+                true,
+                !focus_on_diff,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::open_remotes(&self.key_config),
+                true,
+                !focus_on_diff,
+            ));
+
+            let stopped = Self::stopped_state();
+            out.push(CommandInfo::new(
+                strings::commands::state_continue(
+                    &self.key_config,
+                    stopped,
+                ),
+                true,
+                stopped.is_some() && !focus_on_diff,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::state_abort(
+                    &self.key_config,
+                    stopped,
+                ),
+                true,
+                stopped.is_some() && !focus_on_diff,
+            ));
         }
 
         {
@@ -516,6 +883,18 @@ impl Component for Status {
                 },
                 self.visible || force_all,
             ));
+            out.push(CommandInfo::new(
+                strings::commands::status_pick_hunks(
+                    &self.key_config,
+                ),
+                !self.index_wd.is_empty(),
+                (self.visible && !focus_on_diff) || force_all,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::view_patch(&self.key_config),
+                self.selected_path().is_some(),
+                self.visible || force_all,
+            ));
             out.push(CommandInfo::new(
                 strings::commands::diff_focus_left(&self.key_config),
                 true,
@@ -568,8 +947,17 @@ impl Component for Status {
         visibility_blocking(self)
     }
 
+    #[allow(clippy::too_many_lines)]
     fn event(&mut self, ev: crossterm::event::Event) -> Result<bool> {
         if self.visible {
+            if let Event::Key(k) = ev {
+                if k == self.key_config.focus_right
+                    && self.can_focus_folder_diff()
+                {
+                    return self.switch_focus(Focus::Diff);
+                }
+            }
+
             if event_pump(ev, self.components_mut().as_mut_slice())? {
                 self.git_action_executed = true;
                 return Ok(true);
@@ -588,6 +976,23 @@ impl Component for Status {
                         );
                     }
                     Ok(true)
+                } else if k == self.key_config.view_patch {
+                    if let Some((path, is_stage)) =
+                        self.selected_path()
+                    {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ViewPatch(path, is_stage),
+                        );
+                    }
+                    Ok(true)
+                } else if k == self.key_config.pick_hunks
+                    && !self.is_focus_on_diff()
+                    && !self.index_wd.is_empty()
+                {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::PickHunks);
+                    Ok(true)
                 } else if k == self.key_config.toggle_workarea
                     && !self.is_focus_on_diff()
                 {
@@ -597,10 +1002,7 @@ impl Component for Status {
                 {
                     self.switch_focus(Focus::Diff)
                 } else if k == self.key_config.focus_left {
-                    self.switch_focus(match self.diff_target {
-                        DiffTarget::Stage => Focus::Stage,
-                        DiffTarget::WorkingDir => Focus::WorkDir,
-                    })
+                    self.focus_file_list()
                 } else if k == self.key_config.move_down
                     && self.focus == Focus::WorkDir
                     && !self.index.is_empty()
@@ -629,11 +1031,109 @@ impl Component for Status {
                 {
                     self.push(false);
                     Ok(true)
+                } else if k == self.key_config.push_to
+                    && !self.is_focus_on_diff()
+                {
+                    if let Some(branch) = self.git_branch_name.last()
+                    {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::OpenPushTarget(branch),
+                        );
+                    }
+                    Ok(true)
                 } else if k == self.key_config.pull
                     && !self.is_focus_on_diff()
                 {
                     self.pull();
                     Ok(true)
+                } else if k == self.key_config.fetch_all
+                    && !self.is_focus_on_diff()
+                {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::FetchAll);
+                    Ok(true)
+                } else if k == self.key_config.pull_strategy
+                    && !self.is_focus_on_diff()
+                {
+                    if let Some(branch) = self.git_branch_name.last()
+                    {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::OpenPullStrategy(branch),
+                        );
+                    }
+                    Ok(true)
+                } else if k == self.key_config.apply_patch
+                    && !self.is_focus_on_diff()
+                {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenApplyPatch);
+                    Ok(true)
+                } else if k == self.key_config.undo_discard
+                    && !self.is_focus_on_diff()
+                {
+                    if let Some(paths) = &self.git_discard_backup {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ConfirmAction(
+                                Action::UndoDiscard(paths.clone()),
+                            ),
+                        );
+                    }
+                    Ok(true)
+                } else if k == self.key_config.compare_upstream
+                    && !self.is_focus_on_diff()
+                {
+                    self.compare_upstream();
+                    Ok(true)
+                } else if k == self.key_config.commit_amend_no_edit
+                    && self.focus == Focus::Stage
+                    && self.can_amend_no_edit()
+                {
+                    self.amend_no_edit();
+                    Ok(true)
+                } else if k == self.key_config.select_identity
+                    && !self.is_focus_on_diff()
+                {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::SelectIdentity);
+                    Ok(true)
+                } else if k == self.key_config.open_submodules
+                    && !self.is_focus_on_diff()
+                {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenSubmodules);
+                    Ok(true)
+                } else if k == self.key_config.open_worktrees
+                    && !self.is_focus_on_diff()
+                {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenWorktrees);
+                    Ok(true)
+                } else if k == self.key_config.open_remotes
+                    && !self.is_focus_on_diff()
+                {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenRemotes);
+                    Ok(true)
+                } else if k == self.key_config.cherry_pick
+                    && !self.is_focus_on_diff()
+                {
+                    Self::stopped_state().map_or(Ok(false), |_| {
+                        self.continue_stopped();
+                        Ok(true)
+                    })
+                } else if k == self.key_config.cherry_pick_abort
+                    && !self.is_focus_on_diff()
+                {
+                    Self::stopped_state().map_or(Ok(false), |state| {
+                        self.abort_stopped(state);
+                        Ok(true)
+                    })
                 } else {
                     Ok(false)
                 };
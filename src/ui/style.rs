@@ -2,7 +2,10 @@
 #![allow(clippy::use_self)]
 
 use anyhow::Result;
-use asyncgit::{DiffLineType, StatusItemType};
+use asyncgit::{
+    sync::{CommitRef, SignatureStatus},
+    DiffLineType, StatusItemType,
+};
 use ron::{
     de::from_bytes,
     ser::{to_string_pretty, PrettyConfig},
@@ -19,6 +22,7 @@ use tui::style::{Color, Modifier, Style};
 pub type SharedTheme = Rc<Theme>;
 
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
 pub struct Theme {
     selected_tab: Color,
     #[serde(with = "Color")]
@@ -53,6 +57,28 @@ pub struct Theme {
     push_gauge_bg: Color,
     #[serde(with = "Color")]
     push_gauge_fg: Color,
+    #[serde(with = "Color")]
+    signature_trusted: Color,
+    #[serde(with = "Color")]
+    signature_untrusted: Color,
+    #[serde(with = "Color")]
+    blame_age_recent: Color,
+    #[serde(with = "Color")]
+    blame_age_year: Color,
+    #[serde(with = "Color")]
+    blame_age_old: Color,
+    #[serde(with = "Color")]
+    search_match: Color,
+    #[serde(with = "Color")]
+    diff_line_add_bg: Color,
+    #[serde(with = "Color")]
+    diff_line_delete_bg: Color,
+    #[serde(with = "Color")]
+    ref_head: Color,
+    #[serde(with = "Color")]
+    ref_local_branch: Color,
+    #[serde(with = "Color")]
+    ref_remote_branch: Color,
 }
 
 impl Theme {
@@ -183,6 +209,12 @@ impl Theme {
             DiffLineType::Header => Style::default()
                 .fg(self.disabled_fg)
                 .add_modifier(Modifier::BOLD),
+            DiffLineType::NoNewline => Style::default()
+                .fg(self.disabled_fg)
+                .add_modifier(Modifier::ITALIC),
+            DiffLineType::File => Style::default()
+                .fg(self.command_fg)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
             DiffLineType::None => Style::default().fg(if selected {
                 self.command_fg
             } else {
@@ -193,6 +225,42 @@ impl Theme {
         self.apply_select(style, selected)
     }
 
+    /// changed words within a modified line
+    pub fn diff_line_changed(
+        &self,
+        typ: DiffLineType,
+        selected: bool,
+    ) -> Style {
+        self.diff_line(typ, selected)
+            .add_modifier(Modifier::REVERSED)
+    }
+
+    /// code of a diff line in the color of its syntax, added and
+    /// deleted lines keep apart by their background
+    pub fn diff_line_syntax(
+        &self,
+        typ: DiffLineType,
+        changed: bool,
+        selected: bool,
+        (r, g, b): (u8, u8, u8),
+    ) -> Style {
+        let style = Style::default().fg(Color::Rgb(r, g, b));
+        let style = match typ {
+            DiffLineType::Add => style.bg(self.diff_line_add_bg),
+            DiffLineType::Delete => {
+                style.bg(self.diff_line_delete_bg)
+            }
+            _ => style,
+        };
+        let style = if changed {
+            style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            style
+        };
+
+        self.apply_select(style, selected)
+    }
+
     pub fn text_danger(&self) -> Style {
         Style::default().fg(self.danger_fg)
     }
@@ -229,12 +297,100 @@ impl Theme {
         )
     }
 
+    /// commit marked to be compared with another one
+    pub fn commit_marked(&self, selected: bool) -> Style {
+        self.commit_hash(selected).add_modifier(Modifier::REVERSED)
+    }
+
+    /// commit message matching the log search
+    pub fn search_match(&self, selected: bool) -> Style {
+        self.apply_select(
+            Style::default()
+                .fg(self.search_match)
+                .add_modifier(Modifier::BOLD),
+            selected,
+        )
+    }
+
+    /// chip of a branch, tag or HEAD next to a commit, keeps its
+    /// background when selected
+    pub fn commit_ref(&self, commit_ref: &CommitRef) -> Style {
+        let bg = match commit_ref {
+            CommitRef::Head(_) => self.ref_head,
+            CommitRef::LocalBranch(_) => self.ref_local_branch,
+            CommitRef::RemoteBranch(_) => self.ref_remote_branch,
+            CommitRef::Tag(_) => self.selected_tab,
+        };
+
+        Style::default()
+            .fg(Color::Black)
+            .bg(bg)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// lanes of the commit graph cycle through these
+    pub fn graph_lane(&self, lane: usize, selected: bool) -> Style {
+        const LANES: [Color; 6] = [
+            Color::LightBlue,
+            Color::LightMagenta,
+            Color::LightGreen,
+            Color::LightYellow,
+            Color::LightCyan,
+            Color::LightRed,
+        ];
+
+        self.apply_select(
+            Style::default().fg(LANES[lane % LANES.len()]),
+            selected,
+        )
+    }
+
     pub fn push_gauge(&self) -> Style {
         Style::default()
             .fg(self.push_gauge_fg)
             .bg(self.push_gauge_bg)
     }
 
+    /// lines changed within the last month stand out, those older
+    /// than a year fade
+    pub fn blame_age(&self, age_secs: i64, selected: bool) -> Style {
+        const MONTH: i64 = 30 * 24 * 60 * 60;
+        const YEAR: i64 = 365 * 24 * 60 * 60;
+
+        let color = if age_secs < MONTH {
+            self.blame_age_recent
+        } else if age_secs < YEAR {
+            self.blame_age_year
+        } else {
+            self.blame_age_old
+        };
+
+        self.apply_select(Style::default().fg(color), selected)
+    }
+
+    /// badge of a verified commit signature
+    pub fn commit_signature(&self, status: SignatureStatus) -> Style {
+        match status {
+            SignatureStatus::Trusted => Style::default()
+                .fg(self.signature_trusted)
+                .add_modifier(Modifier::BOLD),
+            SignatureStatus::Untrusted => {
+                Style::default().fg(self.signature_untrusted)
+            }
+            SignatureStatus::ExpiredKey
+            | SignatureStatus::RevokedKey => Style::default()
+                .fg(self.danger_fg)
+                .add_modifier(Modifier::BOLD),
+            SignatureStatus::Bad => Style::default()
+                .fg(self.danger_fg)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+            SignatureStatus::NoPublicKey
+            | SignatureStatus::Unknown => {
+                Style::default().fg(self.disabled_fg)
+            }
+        }
+    }
+
     // This will only be called when theme.ron doesn't already exists
     fn save(&self, theme_file: PathBuf) -> Result<()> {
         let mut file = File::create(theme_file)?;
@@ -296,6 +452,17 @@ impl Default for Theme {
             danger_fg: Color::Red,
             push_gauge_bg: Color::Blue,
             push_gauge_fg: Color::Reset,
+            signature_trusted: Color::Green,
+            signature_untrusted: Color::Yellow,
+            blame_age_recent: Color::LightYellow,
+            blame_age_year: Color::Yellow,
+            blame_age_old: Color::DarkGray,
+            search_match: Color::LightYellow,
+            diff_line_add_bg: Color::Rgb(0, 48, 0),
+            diff_line_delete_bg: Color::Rgb(64, 0, 0),
+            ref_head: Color::LightCyan,
+            ref_local_branch: Color::LightGreen,
+            ref_remote_branch: Color::LightRed,
         }
     }
 }